@@ -72,7 +72,7 @@ async fn main() -> Result<()> {
     println!("└──────────────────────────────────────────────────────────────┘");
 
     let custom_client = ArchClient::builder()
-        .timeout(Duration::from_secs(60))
+        .timeout(Duration::from_mins(1))
         .user_agent("my-arch-tool/1.0")
         .build()?;
     println!("✓ Custom client created with 60s timeout\n");
@@ -112,6 +112,8 @@ async fn main() -> Result<()> {
         retry_info: true,
         retry_comments: true,
         retry_pkgbuild: false, // Disable retries for PKGBUILD
+        circuit_breaker_threshold: 10,
+        circuit_breaker_cooldown_ms: 30_000,
     };
     let _full_retry_client = ArchClient::builder()
         .retry_policy(full_retry_policy)
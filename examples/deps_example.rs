@@ -40,7 +40,7 @@ fn main() -> arch_toolkit::error::Result<()> {
         parse_pkgbuild_deps, parse_srcinfo, parse_srcinfo_conflicts, parse_srcinfo_deps,
         version_satisfies,
     };
-    use arch_toolkit::types::dependency::{DependencySpec, ResolverConfig};
+    use arch_toolkit::types::dependency::{DependencySpec, ResolverConfig, SourceConfig};
     use arch_toolkit::{PackageRef, PackageSource};
     println!("=== Arch Toolkit Deps Module Examples ===\n");
 
@@ -223,10 +223,7 @@ conflicts = conflicting-pkg
 
     if let Ok(installed) = get_installed_packages() {
         let provided = get_provided_packages(&installed);
-        println!(
-            "  Provided packages set size: {} (lazy checking enabled)",
-            provided.len()
-        );
+        println!("  Provided packages set size: {}", provided.len());
         println!(
             "  is_package_installed_or_provided(\"pacman\", ...): {}",
             is_package_installed_or_provided("pacman", &installed, &provided)
@@ -311,8 +308,13 @@ conflicts = conflicting-pkg
         include_makedepends: false,
         include_checkdepends: false,
         max_depth: 0,
+        max_packages: 1000,
         pkgbuild_cache: None,
+        pkgbuild_hash: None,
+        aur_commit: None,
         check_aur: false,
+        offline: false,
+        source_config: SourceConfig::default(),
     };
     let resolver1 = DependencyResolver::with_config(config1);
     let packages = vec![PackageRef {
@@ -338,8 +340,13 @@ conflicts = conflicting-pkg
         include_makedepends: true,
         include_checkdepends: false,
         max_depth: 0,
+        max_packages: 1000,
         pkgbuild_cache: None,
+        pkgbuild_hash: None,
+        aur_commit: None,
         check_aur: false,
+        offline: false,
+        source_config: SourceConfig::default(),
     };
     let resolver2 = DependencyResolver::with_config(config2);
     match resolver2.resolve(&packages) {
@@ -356,8 +363,13 @@ conflicts = conflicting-pkg
         include_makedepends: false,
         include_checkdepends: true,
         max_depth: 0,
+        max_packages: 1000,
         pkgbuild_cache: None,
+        pkgbuild_hash: None,
+        aur_commit: None,
         check_aur: false,
+        offline: false,
+        source_config: SourceConfig::default(),
     };
     let resolver3 = DependencyResolver::with_config(config3);
     match resolver3.resolve(&packages) {
@@ -374,8 +386,13 @@ conflicts = conflicting-pkg
         include_makedepends: true,
         include_checkdepends: true,
         max_depth: 0,
+        max_packages: 1000,
         pkgbuild_cache: None,
+        pkgbuild_hash: None,
+        aur_commit: None,
         check_aur: false,
+        offline: false,
+        source_config: SourceConfig::default(),
     };
     let resolver4 = DependencyResolver::with_config(config4);
     match resolver4.resolve(&packages) {
@@ -386,19 +403,24 @@ conflicts = conflicting-pkg
         Err(e) => println!("  Error resolving dependencies: {}", e),
     }
 
-    // Config 5: With max_depth (note: currently only direct deps are resolved)
+    // Config 5: With max_depth (resolves dependencies of dependencies)
     let config5 = ResolverConfig {
         include_optdepends: false,
         include_makedepends: false,
         include_checkdepends: false,
-        max_depth: 1, // Would be used for transitive deps if implemented
+        max_depth: 1, // Also resolve dependencies of direct dependencies
+        max_packages: 1000,
         pkgbuild_cache: None,
+        pkgbuild_hash: None,
+        aur_commit: None,
         check_aur: false,
+        offline: false,
+        source_config: SourceConfig::default(),
     };
     let resolver5 = DependencyResolver::with_config(config5);
     match resolver5.resolve(&packages) {
         Ok(result) => {
-            println!("  Config 5: max_depth=1 (currently resolves direct deps only)");
+            println!("  Config 5: max_depth=1 (direct deps and their dependencies)");
             println!("  Found {} dependencies", result.dependencies.len());
         }
         Err(e) => println!("  Error resolving dependencies: {}", e),
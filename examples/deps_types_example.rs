@@ -20,8 +20,8 @@ fn main() {
 #[allow(clippy::too_many_lines, clippy::cognitive_complexity)] // Example file - comprehensive demonstration
 fn main() {
     use arch_toolkit::{
-        Dependency, DependencySource, DependencyStatus, PackageRef, PackageSource,
-        ReverseDependencySummary, SrcinfoData,
+        Dependency, DependencyKind, DependencySource, DependencyStatus, InstallReason, PackageRef,
+        PackageSource, ReverseDependencySummary, SrcinfoData,
         deps::{parse_dep_spec, parse_pacman_si_conflicts, parse_pacman_si_deps},
     };
 
@@ -271,6 +271,10 @@ Replaces        : vi"#;
             depends_on: vec!["linux-api-headers".to_string()],
             is_core: true,
             is_system: true,
+            kind: DependencyKind::Runtime,
+            explicit: false,
+            install_reason: InstallReason::Unknown,
+            satisfied_by: None,
         },
         Dependency {
             name: "python".to_string(),
@@ -283,6 +287,10 @@ Replaces        : vi"#;
             depends_on: vec!["gcc".to_string(), "make".to_string()],
             is_core: false,
             is_system: false,
+            kind: DependencyKind::Runtime,
+            explicit: false,
+            install_reason: InstallReason::Unknown,
+            satisfied_by: None,
         },
         Dependency {
             name: "old-lib".to_string(),
@@ -297,6 +305,10 @@ Replaces        : vi"#;
             depends_on: Vec::new(),
             is_core: false,
             is_system: false,
+            kind: DependencyKind::Runtime,
+            explicit: false,
+            install_reason: InstallReason::Unknown,
+            satisfied_by: None,
         },
     ];
 
@@ -378,6 +390,7 @@ Replaces        : vi"#;
         conflicts: vec!["old-package".to_string()],
         provides: vec!["my-package".to_string()],
         replaces: vec!["legacy-package".to_string()],
+        packages: Vec::new(),
     };
 
     println!("Parsed .SRCINFO data:\n");
@@ -485,6 +498,10 @@ Replaces        : vi"#;
         depends_on: Vec::new(),
         is_core: false,
         is_system: false,
+        kind: DependencyKind::Runtime,
+        explicit: false,
+        install_reason: InstallReason::Unknown,
+        satisfied_by: None,
     };
 
     match serde_json::to_string_pretty(&dep) {
@@ -519,6 +536,10 @@ Replaces        : vi"#;
             depends_on: Vec::new(),
             is_core: false,
             is_system: false,
+            kind: DependencyKind::Runtime,
+            explicit: false,
+            install_reason: InstallReason::Unknown,
+            satisfied_by: None,
         },
         Dependency {
             name: "to-install-pkg".to_string(),
@@ -531,6 +552,10 @@ Replaces        : vi"#;
             depends_on: Vec::new(),
             is_core: false,
             is_system: false,
+            kind: DependencyKind::Runtime,
+            explicit: false,
+            install_reason: InstallReason::Unknown,
+            satisfied_by: None,
         },
         Dependency {
             name: "conflict-pkg".to_string(),
@@ -545,6 +570,10 @@ Replaces        : vi"#;
             depends_on: Vec::new(),
             is_core: false,
             is_system: false,
+            kind: DependencyKind::Runtime,
+            explicit: false,
+            install_reason: InstallReason::Unknown,
+            satisfied_by: None,
         },
     ];
 
@@ -585,6 +614,10 @@ Replaces        : vi"#;
             depends_on: Vec::new(),
             is_core: false,
             is_system: false,
+            kind: DependencyKind::Runtime,
+            explicit: false,
+            install_reason: InstallReason::Unknown,
+            satisfied_by: None,
         },
         Dependency {
             name: "conflict".to_string(),
@@ -599,6 +632,10 @@ Replaces        : vi"#;
             depends_on: Vec::new(),
             is_core: false,
             is_system: false,
+            kind: DependencyKind::Runtime,
+            explicit: false,
+            install_reason: InstallReason::Unknown,
+            satisfied_by: None,
         },
         Dependency {
             name: "to-install".to_string(),
@@ -611,6 +648,10 @@ Replaces        : vi"#;
             depends_on: Vec::new(),
             is_core: false,
             is_system: false,
+            kind: DependencyKind::Runtime,
+            explicit: false,
+            install_reason: InstallReason::Unknown,
+            satisfied_by: None,
         },
     ];
 
@@ -25,21 +25,31 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     match client.health_status().await {
         Ok(status) => {
             println!("Detailed Health Status:");
-            println!("  AUR API: {:?}", status.aur_api);
+            println!("  AUR RPC: {:?}", status.rpc);
+            println!("  AUR Web: {:?}", status.web);
+            println!("  AUR cgit: {:?}", status.cgit);
             if let Some(latency) = status.latency {
                 println!("  Latency: {latency:?}");
             }
             println!("  Is Healthy: {}", status.is_healthy());
             println!("  Checked At: {:?}", status.checked_at);
+            println!("  Current Backoff: {}ms", status.current_backoff_ms);
+            println!("  Consecutive Failures: {}", status.consecutive_failures);
 
             // Pattern matching on service status
-            match status.aur_api {
+            match status.rpc {
                 ServiceStatus::Healthy => {
                     println!("\n✓ AUR API is responding normally");
                 }
                 ServiceStatus::Degraded => {
                     println!("\n⚠ AUR API is slow but functional");
                 }
+                ServiceStatus::RateLimited => {
+                    println!("\n⚠ AUR API is up but rate limiting requests");
+                    if let Some(secs) = status.retry_after {
+                        println!("  Retry after: {secs}s");
+                    }
+                }
                 ServiceStatus::Unreachable => {
                     println!("\n✗ AUR API is unreachable");
                 }
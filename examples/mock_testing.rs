@@ -75,6 +75,9 @@ async fn main() -> Result<()> {
                     out_of_date: None,
                     orphaned: false,
                     maintainer: Some("Jguer".to_string()),
+                    num_votes: None,
+                    last_modified: None,
+                    first_submitted: None,
                 },
                 AurPackage {
                     name: "yay-bin".to_string(),
@@ -84,6 +87,9 @@ async fn main() -> Result<()> {
                     out_of_date: None,
                     orphaned: false,
                     maintainer: Some("Jguer".to_string()),
+                    num_votes: None,
+                    last_modified: None,
+                    first_submitted: None,
                 },
             ]),
         )
@@ -97,6 +103,9 @@ async fn main() -> Result<()> {
                 out_of_date: None,
                 orphaned: false,
                 maintainer: Some("Morganamilo".to_string()),
+                num_votes: None,
+                last_modified: None,
+                first_submitted: None,
             }]),
         );
 
@@ -138,6 +147,9 @@ async fn main() -> Result<()> {
         out_of_date: None,
         orphaned: false,
         maintainer: None,
+        num_votes: None,
+        last_modified: None,
+        first_submitted: None,
     }]));
 
     let count = count_packages(&default_mock, "any-query").await?;
@@ -154,6 +166,7 @@ async fn main() -> Result<()> {
         &["yay"],
         Ok(vec![AurPackageDetails {
             name: "yay".to_string(),
+            package_base: "yay".to_string(),
             version: "12.0.0".to_string(),
             description: "Yet Another Yaourt - A simple AUR helper written in Go".to_string(),
             url: "https://github.com/Jguer/yay".to_string(),
@@ -165,6 +178,7 @@ async fn main() -> Result<()> {
             opt_depends: vec![],
             conflicts: vec![],
             replaces: vec![],
+            keywords: vec![],
             maintainer: Some("Jguer".to_string()),
             first_submitted: Some(1_500_000_000),
             last_modified: Some(1_700_000_000),
@@ -196,6 +210,9 @@ async fn main() -> Result<()> {
                 date_url: None,
                 content: "Great package! Works perfectly.".to_string(),
                 pinned: true,
+                numeric_id: None,
+                edited: None,
+                deleted: false,
             },
             AurComment {
                 id: Some("2".to_string()),
@@ -205,6 +222,9 @@ async fn main() -> Result<()> {
                 date_url: None,
                 content: "Thanks for maintaining this!".to_string(),
                 pinned: false,
+                numeric_id: None,
+                edited: None,
+                deleted: false,
             },
         ]),
     );
@@ -260,12 +280,16 @@ makedepends=()
                 out_of_date: None,
                 orphaned: false,
                 maintainer: Some("Jguer".to_string()),
+                num_votes: None,
+                last_modified: None,
+                first_submitted: None,
             }]),
         )
         .with_info_result(
             &["yay"],
             Ok(vec![AurPackageDetails {
                 name: "yay".to_string(),
+                package_base: "yay".to_string(),
                 version: "12.0.0".to_string(),
                 description: "AUR helper".to_string(),
                 url: String::new(),
@@ -277,6 +301,7 @@ makedepends=()
                 opt_depends: vec![],
                 conflicts: vec![],
                 replaces: vec![],
+                keywords: vec![],
                 maintainer: Some("Jguer".to_string()),
                 first_submitted: None,
                 last_modified: None,
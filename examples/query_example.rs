@@ -3,7 +3,7 @@
 //! This example demonstrates:
 //! - Querying installed packages from pacman database
 //! - Finding packages with available upgrades
-//! - Checking provided packages (lazy checking)
+//! - Listing versioned provides of installed packages
 //! - Determining if packages are installed or provided
 //! - Getting installed and available package versions
 //! - Graceful degradation when pacman is unavailable
@@ -181,23 +181,26 @@ fn main() {
     }
 
     // ========================================================================
-    // Example 6: Get Provided Packages (Lazy Checking)
+    // Example 6: Get Provided Packages
     // ========================================================================
-    println!("\n┌─ Example 6: Get Provided Packages (Lazy Checking) ────────────┐");
-    println!("│ Check provided packages using lazy on-demand checking         │");
+    println!("\n┌─ Example 6: Get Provided Packages ────────────────────────────┐");
+    println!("│ List versioned provides of installed packages                 │");
     println!("└──────────────────────────────────────────────────────────────┘");
 
     match get_installed_packages() {
         Ok(installed) => {
             let provided = get_provided_packages(&installed);
             println!("Provided packages set size: {}", provided.len());
-            println!("\nNote: get_provided_packages() returns an empty set");
-            println!(
-                "      Provides are checked lazily on-demand using is_package_installed_or_provided()"
-            );
-            println!(
-                "      This avoids querying all installed packages upfront, which is very slow"
-            );
+            let mut versioned: Vec<_> = provided
+                .iter()
+                .filter_map(|p| p.version.as_ref().map(|v| format!("{}={v}", p.name)))
+                .collect();
+            versioned.sort();
+            for entry in versioned.iter().take(5) {
+                println!("  {entry}");
+            }
+            println!("\nNote: versioned provides let determine_status() check requirements");
+            println!("      like rust>=1.60 against a provider such as rustup");
         }
         Err(e) => {
             println!("Error: {e}");
@@ -317,13 +320,13 @@ fn main() {
     println!("    - get_upgradable_packages(): O(n) where n = upgradable packages");
     println!("    - get_installed_version(pkg): O(1) single package query)");
     println!("    - get_available_version(pkg): O(1) single package query)");
+    println!("    - get_provided_packages(): O(n) single 'pacman -Qi' call");
     println!("\n  Lazy operations (on-demand checking):");
-    println!("    - get_provided_packages(): O(1) returns empty set immediately");
     println!("    - is_package_installed_or_provided(): O(1) per check");
-    println!("      Uses 'pacman -Qqo' which is efficient");
+    println!("      Falls back to 'pacman -Qqo' which is efficient");
     println!("\n  Best practices:");
     println!("    - Cache get_installed_packages() result if checking multiple packages");
-    println!("    - Use is_package_installed_or_provided() instead of building full provided set");
+    println!("    - Build the provided set once and reuse it for status checks");
     println!("    - Batch version queries when possible");
 
     println!("\n╔═══════════════════════════════════════════════════════════════╗");
@@ -31,6 +31,7 @@ fn main() -> arch_toolkit::error::Result<()> {
         ReverseDependencyAnalyzer, get_installed_required_by, has_installed_required_by,
     };
     use arch_toolkit::{PackageRef, PackageSource};
+    use std::io::Write;
 
     println!("╔═══════════════════════════════════════════════════════════════╗");
     println!("║       arch-toolkit: Reverse Dependency Analysis Example       ║");
@@ -83,8 +84,15 @@ fn main() -> arch_toolkit::error::Result<()> {
 
                 println!("\n  Analyzing reverse dependencies for: {}", pkg_name);
                 println!("  Progress: Querying pacman database...");
-                println!("  (This may take a moment for packages with many transitive dependents)");
-                match analyzer.analyze(&packages) {
+                let result = analyzer.analyze_with_progress(&packages, |progress| {
+                    print!(
+                        "\r  {} dependents found, {} queued",
+                        progress.visited, progress.queued
+                    );
+                    let _ = std::io::stdout().flush();
+                });
+                println!();
+                match result {
                     Ok(report) => {
                         println!("  ✓ Analysis complete!");
                         println!("  Found {} dependents", report.dependents.len());
@@ -93,7 +101,14 @@ fn main() -> arch_toolkit::error::Result<()> {
                         if !report.dependents.is_empty() {
                             println!("\n  Sample dependents (first 5):");
                             for (i, dep) in report.dependents.iter().take(5).enumerate() {
-                                println!("    {}. {} - {:?}", i + 1, dep.name, dep.status);
+                                let marker = if dep.explicit { " [explicit]" } else { "" };
+                                println!(
+                                    "    {}. {}{} - {:?}",
+                                    i + 1,
+                                    dep.name,
+                                    marker,
+                                    dep.status
+                                );
                             }
                         }
                     }
@@ -268,6 +283,8 @@ fn main() -> arch_toolkit::error::Result<()> {
                                 println!("      Depends on: {} package(s)", dep.depends_on.len());
                                 println!("      Is core repository: {}", dep.is_core);
                                 println!("      Is system package: {}", dep.is_system);
+                                println!("      Explicitly installed: {}", dep.explicit);
+                                println!("      Install reason: {}", dep.install_reason);
                             }
                         }
                     }
@@ -481,8 +481,16 @@ const fn categorize_error(error: &ArchToolkitError) -> &'static str {
         | ArchToolkitError::PkgbuildFailed { .. }
         | ArchToolkitError::Network(_) => "Network Error",
         ArchToolkitError::Json(_) | ArchToolkitError::Parse(_) => "Parsing Error",
+        ArchToolkitError::AurRpc { .. } => "AUR RPC Error",
         ArchToolkitError::RateLimited { .. } => "Rate Limit Error",
-        ArchToolkitError::PackageNotFound { .. } => "Not Found Error",
+        ArchToolkitError::ServiceUnavailable => "Service Unavailable Error",
+        ArchToolkitError::Cancelled => "Cancellation Error",
+        ArchToolkitError::PacmanUnavailable { .. } | ArchToolkitError::Runtime(_) => "System Error",
+        ArchToolkitError::PackageNotFound { .. }
+        | ArchToolkitError::PackagesNotFound { .. }
+        | ArchToolkitError::Http { status: 404, .. } => "Not Found Error",
+        ArchToolkitError::Http { .. } => "HTTP Status Error",
+        ArchToolkitError::RemovalBlocked { .. } => "Dependency Error",
         ArchToolkitError::InvalidInput(_) => "Input Error",
         ArchToolkitError::EmptyInput { .. }
         | ArchToolkitError::InvalidPackageName { .. }
@@ -556,6 +564,10 @@ async fn demonstrate_practical_patterns(client: &ArchClient) -> Result<()> {
         ArchToolkitError::RateLimited {
             retry_after: Some(60),
         },
+        ArchToolkitError::Http {
+            status: 503,
+            url: "https://aur.archlinux.org/rpc/v5/info".to_string(),
+        },
     ];
 
     for error in &errors {
@@ -701,6 +713,12 @@ async fn demonstrate_validation_errors(client: &ArchClient) -> Result<()> {
     println!("      .build()?;\n");
     println!("In lenient mode, empty inputs return empty results");
     println!("instead of validation errors.\n");
+    println!("To relax package name rules to URL safety only:");
+    println!("      .validation_config(ValidationConfig {{");
+    println!("          lenient: true,");
+    println!("          ..Default::default()");
+    println!("      }})\n");
+    println!("Names like `../../etc/passwd` or `pkg name` are still rejected.\n");
 
     // Example 6: Valid inputs
     println!("Example 6: Valid Inputs");
@@ -1,18 +1,55 @@
 //! AUR package comments fetching via web scraping.
 
+use crate::aur::utils::{instrumented, with_timeout};
 use crate::aur::validation::validate_package_name;
-use crate::cache::cache_key_comments;
-use crate::client::{
-    ArchClient, extract_retry_after, is_archlinux_url, rate_limit_archlinux,
-    reset_archlinux_backoff, retry_with_policy,
-};
+use crate::client::{ArchClient, check_status, extract_retry_after, reset_archlinux_backoff};
 use crate::error::{ArchToolkitError, Result};
 use crate::types::AurComment;
 use chrono::{DateTime, Local, NaiveDate, NaiveDateTime};
 use reqwest::Client as ReqwestClient;
 use reqwest::header::{ACCEPT, ACCEPT_LANGUAGE, HeaderMap, HeaderValue};
 use scraper::{ElementRef, Html, Selector};
-use tracing::debug;
+use std::collections::HashSet;
+use std::time::Duration;
+use tracing::{debug, info_span};
+
+/// Maximum size of an AUR package page accepted for comment parsing (8 MiB).
+const MAX_COMMENTS_RESPONSE_BYTES: usize = 8 * 1024 * 1024;
+
+/// Placeholder text the AUR shows in place of a deleted comment's content.
+const DELETED_COMMENT_PLACEHOLDER: &str = "This comment has been deleted";
+
+/// Number of comments requested per page by `comments_paginated()` (`PP` query parameter).
+pub const COMMENTS_PER_PAGE: usize = 10;
+
+/// Timezone comment dates are displayed in.
+#[derive(Clone, Copy, Debug, Default)]
+enum DisplayZone {
+    /// System local timezone.
+    #[default]
+    Local,
+    /// Configured IANA timezone (`ArchClientBuilder::comment_timezone()`).
+    #[cfg(feature = "timezone")]
+    Named(chrono_tz::Tz),
+}
+
+impl DisplayZone {
+    /// What: Pick the display timezone configured on a client.
+    ///
+    /// Inputs:
+    /// - `client`: Client whose comment timezone setting is used.
+    ///
+    /// Output:
+    /// - `Named` if a comment timezone is configured, `Local` otherwise.
+    #[allow(clippy::missing_const_for_fn, unused_variables)] // Body depends on features
+    fn of(client: &ArchClient) -> Self {
+        #[cfg(feature = "timezone")]
+        if let Some(tz) = client.comment_timezone() {
+            return Self::Named(tz);
+        }
+        Self::Local
+    }
+}
 
 /// Context for extracting comment data from HTML elements.
 struct CommentExtractionContext<'a> {
@@ -28,6 +65,8 @@ struct CommentExtractionContext<'a> {
     has_pinned_section: bool,
     /// Position of "Latest Comments" heading
     latest_comments_pos: Option<usize>,
+    /// Timezone dates are displayed in
+    zone: DisplayZone,
 }
 
 /// What: Fetch AUR package comments by scraping the AUR package page.
@@ -45,17 +84,64 @@ struct CommentExtractionContext<'a> {
 /// - Parses dates to Unix timestamps for sorting
 /// - Sorts comments by date descending (latest first)
 /// - Handles pinned comments (appear before "Latest Comments" heading)
+/// - Returns at most `max_comments` comments (client setting, default 1000), pinned and newest first
+/// - Rejects pages larger than 8 MiB
 /// - Uses retry policy if enabled for comments operations.
 /// - Checks cache before making network request if caching is enabled.
 ///
 /// # Errors
 /// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+/// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
 /// - Returns `Err(ArchToolkitError::InvalidInput)` if the URL is not from archlinux.org
-/// - Returns `Err(ArchToolkitError::Parse)` if HTML parsing fails
+/// - Returns `Err(ArchToolkitError::ServiceUnavailable)` if the circuit breaker is open
+/// - Returns `Err(ArchToolkitError::Parse)` if HTML parsing fails or the page exceeds the size limit
 /// - Returns `Err(ArchToolkitError::EmptyInput)` if package name is empty and strict mode is enabled
 /// - Returns `Err(ArchToolkitError::InvalidPackageName)` if package name is invalid
 /// - Returns `Err(ArchToolkitError::InputTooLong)` if package name exceeds maximum length
 pub async fn comments(client: &ArchClient, pkgname: &str) -> Result<Vec<AurComment>> {
+    comments_with(client, pkgname, None).await
+}
+
+/// What: Fetch AUR package comments with a per-request timeout override.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+/// - `pkgname`: Package name to fetch comments for.
+/// - `timeout`: Per-request timeout override (`None` uses the client timeout).
+///
+/// Output:
+/// - `Result<Vec<AurComment>>` with parsed comments, or an error.
+///
+/// Details:
+/// - Same as `comments()`; the timeout applies to each HTTP attempt of this call only.
+/// - Runs inside an `aur_comments` tracing span with `operation` and `package` fields.
+///
+/// # Errors
+/// - Returns the same errors as `comments()`
+pub async fn comments_with(
+    client: &ArchClient,
+    pkgname: &str,
+    timeout: Option<Duration>,
+) -> Result<Vec<AurComment>> {
+    let span = info_span!("aur_comments", operation = "comments", package = %pkgname);
+    let operation = run_comments(client, pkgname, timeout);
+    instrumented(client, "comments", pkgname, span, operation).await
+}
+
+/// What: Validate, serve from cache or fetch comments; body of `comments_with()`.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+/// - `pkgname`: Package name to fetch comments for.
+/// - `timeout`: Per-request timeout override.
+///
+/// Output:
+/// - `Result<Vec<AurComment>>` with parsed comments, or an error.
+async fn run_comments(
+    client: &ArchClient,
+    pkgname: &str,
+    timeout: Option<Duration>,
+) -> Result<Vec<AurComment>> {
     // Validate input
     let validation_config = client.validation_config();
     validate_package_name(pkgname, Some(validation_config))?;
@@ -64,52 +150,162 @@ pub async fn comments(client: &ArchClient, pkgname: &str) -> Result<Vec<AurComme
         && cache_config.enable_comments
         && let Some(cache) = client.cache()
     {
-        let cache_key = cache_key_comments(pkgname);
-        if let Some(cached) = cache.get::<Vec<AurComment>>(&cache_key) {
+        let cache_key = cache.keys().comments(pkgname);
+        let cached = cache.get::<Vec<AurComment>>(&cache_key);
+        client.observer().on_cache("comments", cached.is_some());
+        if let Some(cached) = cached {
             debug!(pkgname = %pkgname, "cache hit for comments");
             return Ok(cached);
         }
     }
 
-    let url = format!("https://aur.archlinux.org/packages/{pkgname}");
+    let url = format!("{}/packages/{pkgname}", client.aur_base_url());
+    let html_text = fetch_comments_page(client, &url, pkgname, timeout).await?;
+
+    // Parse HTML
+    let zone = DisplayZone::of(client);
+    let result = parse_comments_html(&html_text, pkgname, client.max_comments(), zone)?;
+
+    // Store in cache if enabled
+    if let Some(cache_config) = client.cache_config()
+        && cache_config.enable_comments
+        && let Some(cache) = client.cache()
+    {
+        let cache_key = cache.keys().comments(pkgname);
+        let _ = cache.set(&cache_key, &result, cache_config.comments_ttl);
+    }
+
+    Ok(result)
+}
+
+/// What: Fetch one page of AUR package comments.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+/// - `pkgname`: Package name to fetch comments for.
+/// - `page`: Zero-based page index; page `0` holds the latest comments.
+///
+/// Output:
+/// - `Result<Vec<AurComment>>` with the page's comments (pinned first, then newest first).
+///
+/// Details:
+/// - Fetches `https://aur.archlinux.org/packages/<pkgname>?O=<offset>&PP=10`.
+/// - Pinned comments are shown on every page and are therefore included in each result.
+/// - Applies rate limiting and the retry policy per page request; pages are not cached.
+///
+/// # Errors
+/// - Returns the same errors as `comments()`
+pub async fn comments_paginated(
+    client: &ArchClient,
+    pkgname: &str,
+    page: usize,
+) -> Result<Vec<AurComment>> {
+    let validation_config = client.validation_config();
+    validate_package_name(pkgname, Some(validation_config))?;
+
+    let url = comments_page_url(client.aur_base_url(), pkgname, page);
+    let html_text = fetch_comments_page(client, &url, pkgname, None).await?;
+    let mut seen = HashSet::new();
+    let zone = DisplayZone::of(client);
+    let comments =
+        parse_comments_page(&html_text, pkgname, client.max_comments(), zone, &mut seen)?;
+    Ok(separate_and_sort_comments(comments))
+}
+
+/// What: Fetch all AUR package comments by walking comment pages.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+/// - `pkgname`: Package name to fetch comments for.
+///
+/// Output:
+/// - `Result<Vec<AurComment>>` with all comments (pinned first, then newest first).
+///
+/// Details:
+/// - Requests pages `0, 1, 2, ...` until a page yields no new comments.
+/// - Comments are deduplicated by ID across pages, so repeated pinned comments appear once.
+/// - Stops once `max_comments` comments were collected (client setting, default 1000).
+/// - Each page request is rate limited; walking many pages takes a while.
+///
+/// # Errors
+/// - Returns the same errors as `comments()`; a failing page aborts the walk
+pub async fn all_comments(client: &ArchClient, pkgname: &str) -> Result<Vec<AurComment>> {
+    let validation_config = client.validation_config();
+    validate_package_name(pkgname, Some(validation_config))?;
+
+    let max_comments = client.max_comments();
+    let zone = DisplayZone::of(client);
+    let mut seen = HashSet::new();
+    let mut comments = Vec::new();
+
+    for page in 0.. {
+        let remaining = max_comments.saturating_sub(comments.len());
+        if remaining == 0 {
+            debug!(pkgname = %pkgname, max_comments, "comment limit reached, stopping pagination");
+            break;
+        }
+
+        let url = comments_page_url(client.aur_base_url(), pkgname, page);
+        let html_text = fetch_comments_page(client, &url, pkgname, None).await?;
+        let page_comments = parse_comments_page(&html_text, pkgname, remaining, zone, &mut seen)?;
+        if page_comments.is_empty() {
+            break;
+        }
+        comments.extend(page_comments);
+    }
+
+    Ok(separate_and_sort_comments(comments))
+}
+
+/// What: Build the URL of a comment page.
+///
+/// Inputs:
+/// - `base`: Base URL of the AUR instance.
+/// - `pkgname`: Package name.
+/// - `page`: Zero-based page index.
+///
+/// Output:
+/// - Package page URL with `O` (offset) and `PP` (per page) query parameters.
+fn comments_page_url(base: &str, pkgname: &str, page: usize) -> String {
+    let offset = page.saturating_mul(COMMENTS_PER_PAGE);
+    format!("{base}/packages/{pkgname}?O={offset}&PP={COMMENTS_PER_PAGE}")
+}
 
+/// What: Fetch the HTML of an AUR package page with rate limiting and retries.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+/// - `url`: Package page URL.
+/// - `pkgname`: Package name for error context.
+/// - `timeout`: Per-request timeout override.
+///
+/// Output:
+/// - `Result<String>` containing HTML text, or an error.
+async fn fetch_comments_page(
+    client: &ArchClient,
+    url: &str,
+    pkgname: &str,
+    timeout: Option<Duration>,
+) -> Result<String> {
     debug!(pkgname = %pkgname, url = %url, "fetching AUR comments");
 
     // Apply rate limiting for archlinux.org
-    let _permit = if is_archlinux_url(&url) {
-        rate_limit_archlinux().await
+    let _permit = if client.is_aur_url(url) {
+        client.archlinux_permit().await?
     } else {
         return Err(ArchToolkitError::InvalidInput(format!(
             "Unexpected URL domain: {url}"
         )));
     };
 
-    let retry_policy = client.retry_policy();
     let http_client = client.http_client();
 
     // Wrap the request in retry logic if enabled
-    let html_text = if retry_policy.enabled && retry_policy.retry_comments {
-        retry_with_policy(retry_policy, "comments", pkgname, || async {
-            perform_comments_request(http_client, &url, pkgname).await
+    client
+        .execute_with_retry("comments", pkgname, || async {
+            perform_comments_request(http_client, url, pkgname, timeout).await
         })
-        .await?
-    } else {
-        perform_comments_request(http_client, &url, pkgname).await?
-    };
-
-    // Parse HTML
-    let result = parse_comments_html(&html_text, pkgname)?;
-
-    // Store in cache if enabled
-    if let Some(cache_config) = client.cache_config()
-        && cache_config.enable_comments
-        && let Some(cache) = client.cache()
-    {
-        let cache_key = cache_key_comments(pkgname);
-        let _ = cache.set(&cache_key, &result, cache_config.comments_ttl);
-    }
-
-    Ok(result)
+        .await
 }
 
 /// What: Perform the actual comments request without retry logic.
@@ -117,6 +313,8 @@ pub async fn comments(client: &ArchClient, pkgname: &str) -> Result<Vec<AurComme
 /// Inputs:
 /// - `client`: HTTP client to use for requests.
 /// - `url`: URL to request.
+/// - `pkgname`: Package name for error context.
+/// - `timeout`: Per-request timeout override.
 ///
 /// Output:
 /// - `Result<String>` containing HTML text, or an error.
@@ -124,10 +322,12 @@ pub async fn comments(client: &ArchClient, pkgname: &str) -> Result<Vec<AurComme
 /// Details:
 /// - Internal helper function that performs the HTTP request
 /// - Used by both retry and non-retry code paths
+/// - Reads the body incrementally and aborts once it exceeds `MAX_COMMENTS_RESPONSE_BYTES`
 async fn perform_comments_request(
     client: &ReqwestClient,
     url: &str,
     pkgname: &str,
+    timeout: Option<Duration>,
 ) -> Result<String> {
     // Create request with browser-like headers
     let mut headers = HeaderMap::new();
@@ -137,7 +337,8 @@ async fn perform_comments_request(
     );
     headers.insert(ACCEPT_LANGUAGE, HeaderValue::from_static("en-US,en;q=0.5"));
 
-    let response = match client.get(url).headers(headers).send().await {
+    let request = with_timeout(client.get(url).headers(headers), timeout);
+    let response = match request.send().await {
         Ok(resp) => {
             reset_archlinux_backoff();
             resp
@@ -151,23 +352,29 @@ async fn perform_comments_request(
     // Check for Retry-After header before consuming response
     let _retry_after = extract_retry_after(&response);
 
-    let response = match response.error_for_status() {
-        Ok(resp) => resp,
-        Err(e) => {
-            debug!(error = %e, pkgname = %pkgname, "AUR comments returned non-success status");
-            return Err(ArchToolkitError::comments_failed(pkgname, e));
-        }
-    };
-
-    let html_text = match response.text().await {
-        Ok(text) => text,
-        Err(e) => {
-            debug!(error = %e, pkgname = %pkgname, "failed to read AUR comments response");
-            return Err(ArchToolkitError::comments_failed(pkgname, e));
+    let mut response = check_status(response).inspect_err(|e| {
+        debug!(error = %e, pkgname = %pkgname, "AUR comments returned non-success status");
+    })?;
+    let mut body = Vec::new();
+    loop {
+        match response.chunk().await {
+            Ok(Some(chunk)) => {
+                if body.len() + chunk.len() > MAX_COMMENTS_RESPONSE_BYTES {
+                    return Err(ArchToolkitError::Parse(format!(
+                        "AUR comments page for '{pkgname}' exceeds {MAX_COMMENTS_RESPONSE_BYTES} bytes"
+                    )));
+                }
+                body.extend_from_slice(&chunk);
+            }
+            Ok(None) => break,
+            Err(e) => {
+                debug!(error = %e, pkgname = %pkgname, "failed to read AUR comments response");
+                return Err(ArchToolkitError::comments_failed(pkgname, e));
+            }
         }
-    };
+    }
 
-    Ok(html_text)
+    Ok(String::from_utf8_lossy(&body).into_owned())
 }
 
 /// What: Parse HTML and extract comments.
@@ -175,14 +382,60 @@ async fn perform_comments_request(
 /// Inputs:
 /// - `html_text`: HTML text to parse.
 /// - `pkgname`: Package name for context.
+/// - `max_comments`: Maximum number of comments to extract.
+/// - `zone`: Timezone dates are displayed in.
 ///
 /// Output:
-/// - `Result<Vec<AurComment>>` with parsed comments.
+/// - `Result<Vec<AurComment>>` with at most `max_comments` parsed comments.
 ///
 /// Details:
 /// - Internal helper function that parses HTML and extracts comments
 /// - Separated from request logic for reuse
-fn parse_comments_html(html_text: &str, pkgname: &str) -> Result<Vec<AurComment>> {
+/// - Stops extracting once `max_comments` comments were collected; the AUR page lists
+///   pinned comments first, then the latest comments newest first, so those are kept
+fn parse_comments_html(
+    html_text: &str,
+    pkgname: &str,
+    max_comments: usize,
+    zone: DisplayZone,
+) -> Result<Vec<AurComment>> {
+    let mut seen_comment_ids = HashSet::new();
+    let comments = parse_comments_page(
+        html_text,
+        pkgname,
+        max_comments,
+        zone,
+        &mut seen_comment_ids,
+    )?;
+
+    // Separate, sort, and combine comments
+    Ok(separate_and_sort_comments(comments))
+}
+
+/// What: Extract comments from a page, skipping comment IDs already seen.
+///
+/// Inputs:
+/// - `html_text`: HTML text to parse.
+/// - `pkgname`: Package name for context.
+/// - `max_comments`: Maximum number of comments to extract.
+/// - `zone`: Timezone dates are displayed in.
+/// - `seen_comment_ids`: Comment IDs seen so far; updated with the IDs of this page.
+///
+/// Output:
+/// - `Result<Vec<AurComment>>` with new comments in page order (unsorted).
+///
+/// Details:
+/// - Shared by single-page and multi-page parsing so a comment never appears twice,
+///   within a page or across pages.
+/// - Comments are keyed by `numeric_id` when available, otherwise by their `id`; comments
+///   without either are never treated as duplicates.
+fn parse_comments_page(
+    html_text: &str,
+    pkgname: &str,
+    max_comments: usize,
+    zone: DisplayZone,
+    seen_comment_ids: &mut HashSet<String>,
+) -> Result<Vec<AurComment>> {
     // Parse HTML
     let document = Html::parse_document(html_text);
 
@@ -214,22 +467,18 @@ fn parse_comments_html(html_text: &str, pkgname: &str) -> Result<Vec<AurComment>
     // Collect all headers
     let all_headers: Vec<_> = document.select(&comment_header_selector).collect();
 
-    // Use a HashSet to track seen comment IDs to avoid duplicates
-    let mut seen_comment_ids = std::collections::HashSet::new();
     let mut comments = Vec::new();
 
     // Process each header and find its corresponding content by ID
     for (index, header) in all_headers.iter().enumerate() {
+        if comments.len() >= max_comments {
+            debug!(pkgname = %pkgname, max_comments, "comment limit reached, skipping remaining comments");
+            break;
+        }
+
         // Extract comment ID from header
         let comment_id = header.value().attr("id");
 
-        // Skip if we've already seen this comment ID (deduplication)
-        if let Some(id) = comment_id
-            && !seen_comment_ids.insert(id)
-        {
-            continue; // Skip duplicate
-        }
-
         // Extract comment data from header
         let context = CommentExtractionContext {
             document: &document,
@@ -238,14 +487,26 @@ fn parse_comments_html(html_text: &str, pkgname: &str) -> Result<Vec<AurComment>
             html_text,
             has_pinned_section,
             latest_comments_pos,
+            zone,
         };
-        if let Some(comment) = extract_comment_from_header(header, comment_id, index, &context) {
-            comments.push(comment);
+        let Some(comment) = extract_comment_from_header(header, comment_id, index, &context) else {
+            continue;
+        };
+
+        // Skip if we've already seen this comment (deduplication)
+        let dedup_key = comment
+            .numeric_id
+            .map(|id| id.to_string())
+            .or_else(|| comment.id.clone());
+        if let Some(key) = dedup_key
+            && !seen_comment_ids.insert(key)
+        {
+            continue; // Skip duplicate
         }
+        comments.push(comment);
     }
 
-    // Separate, sort, and combine comments
-    Ok(separate_and_sort_comments(comments))
+    Ok(comments)
 }
 
 /// What: Extract comment data from a header element.
@@ -260,7 +521,8 @@ fn parse_comments_html(html_text: &str, pkgname: &str) -> Result<Vec<AurComment>
 /// - `Some(AurComment)` if comment is valid; `None` if empty/invalid
 ///
 /// Details:
-/// - Extracts author, date, URL, content, and pinned status
+/// - Extracts author, date, URL, content, pinned status, numeric ID, edit date, and the
+///   deleted flag
 /// - Skips empty comments with unknown authors
 fn extract_comment_from_header(
     header: &ElementRef,
@@ -334,12 +596,23 @@ fn extract_comment_from_header(
         );
     }
 
-    // Convert UTC date to local timezone for display
-    let local_date = convert_utc_to_local_date(&date_text);
+    // Convert UTC date to the display timezone
+    let local_date = convert_utc_date(&date_text, context.zone);
 
     // Determine if this comment is pinned
     let is_pinned = determine_pinned_status(comment_id, index, context);
 
+    let numeric_id = comment_id
+        .and_then(parse_numeric_comment_id)
+        .or_else(|| date_url.as_deref().and_then(parse_numeric_comment_id));
+    let edited =
+        extract_edited_date(&header_text).map(|date| convert_utc_date(&date, context.zone));
+    let deleted = header
+        .value()
+        .classes()
+        .any(|class| class == "comment-deleted")
+        || comment_content.contains(DELETED_COMMENT_PLACEHOLDER);
+
     let stable_id = comment_id.map(str::to_string).or_else(|| date_url.clone());
     Some(AurComment {
         id: stable_id,
@@ -349,9 +622,43 @@ fn extract_comment_from_header(
         date_url,
         content: comment_content,
         pinned: is_pinned,
+        numeric_id,
+        edited,
+        deleted,
     })
 }
 
+/// What: Parse the numeric part of a comment ID or comment URL.
+///
+/// Inputs:
+/// - `id`: Header ID (`comment-931234`) or URL ending in `#comment-931234`.
+///
+/// Output:
+/// - `Some(931234)`, or `None` if no numeric comment ID is present.
+fn parse_numeric_comment_id(id: &str) -> Option<u64> {
+    let (_, number) = id.rsplit_once("comment-")?;
+    number.parse().ok()
+}
+
+/// What: Extract the edit date from a comment header.
+///
+/// Inputs:
+/// - `header_text`: Full text of the comment header.
+///
+/// Output:
+/// - `Some(date)` such as `"2024-02-01 10:00 (UTC)"` if the header has an
+///   `edited on <date> by <user>` note, `None` otherwise.
+fn extract_edited_date(header_text: &str) -> Option<String> {
+    let (_, rest) = header_text.split_once("edited on ")?;
+    let end = rest
+        .find(')')
+        .map(|pos| pos + 1)
+        .or_else(|| rest.find(" by "))
+        .unwrap_or(rest.len());
+    let date = rest[..end].trim();
+    (!date.is_empty()).then(|| date.to_string())
+}
+
 /// What: Determine if a comment is pinned based on its position in the HTML.
 ///
 /// Inputs:
@@ -435,20 +742,21 @@ fn sort_comments_by_date(comments: &mut [AurComment]) {
     });
 }
 
-/// What: Convert UTC date string from AUR to local timezone string.
+/// What: Convert UTC date string from AUR to a display timezone string.
 ///
 /// Inputs:
 /// - `utc_date_str`: UTC date string from AUR page (e.g., "2025-05-15 03:55 (UTC)").
+/// - `zone`: Timezone to display the date in.
 ///
 /// Output:
-/// - Local timezone date string formatted as "YYYY-MM-DD HH:MM (TZ)" where TZ is local timezone abbreviation.
+/// - Date string formatted as "YYYY-MM-DD HH:MM (TZ)" where TZ is the timezone abbreviation.
 /// - Returns original string if parsing fails.
 ///
 /// Details:
 /// - Parses UTC date from AUR format
-/// - Converts to local timezone using system timezone
-/// - Formats with local timezone abbreviation
-fn convert_utc_to_local_date(utc_date_str: &str) -> String {
+/// - `DisplayZone::Local` converts to the system timezone; `DisplayZone::Named` uses the IANA
+///   timezone database, so abbreviations follow daylight saving time (e.g. CET/CEST)
+fn convert_utc_date(utc_date_str: &str, zone: DisplayZone) -> String {
     let utc_date_str = utc_date_str.trim();
 
     // AUR format: "YYYY-MM-DD HH:MM (UTC)" or "YYYY-MM-DD HH:MM (CEST)" etc.
@@ -458,17 +766,21 @@ fn convert_utc_to_local_date(utc_date_str: &str) -> String {
 
         // Try parsing "YYYY-MM-DD HH:MM" format as UTC
         if let Ok(naive_dt) = NaiveDateTime::parse_from_str(date_time_part, "%Y-%m-%d %H:%M") {
-            // Treat as UTC and convert to local timezone
+            // Treat as UTC and convert to the display timezone
             let utc_dt = naive_dt.and_utc();
-            let local_dt = utc_dt.with_timezone(&Local);
-
-            // Format with local timezone
-            let formatted = local_dt.format("%Y-%m-%d %H:%M");
-
-            // Get timezone abbreviation
-            let tz_abbr = get_timezone_abbreviation(&local_dt);
-
-            return format!("{formatted} ({tz_abbr})");
+            return match zone {
+                DisplayZone::Local => {
+                    let local_dt = utc_dt.with_timezone(&Local);
+                    let formatted = local_dt.format("%Y-%m-%d %H:%M");
+                    let tz_abbr = get_timezone_abbreviation(&local_dt);
+                    format!("{formatted} ({tz_abbr})")
+                }
+                #[cfg(feature = "timezone")]
+                DisplayZone::Named(tz) => utc_dt
+                    .with_timezone(&tz)
+                    .format("%Y-%m-%d %H:%M (%Z)")
+                    .to_string(),
+            };
         }
     }
 
@@ -486,8 +798,8 @@ fn convert_utc_to_local_date(utc_date_str: &str) -> String {
 ///
 /// Details:
 /// - First tries chrono's %Z format specifier
-/// - Falls back to TZ environment variable parsing
-/// - Finally falls back to UTC offset format
+/// - Falls back to UTC offset format; enable the `timezone` feature and set
+///   `ArchClientBuilder::comment_timezone()` for proper abbreviations
 fn get_timezone_abbreviation(local_dt: &DateTime<Local>) -> String {
     // Try chrono's %Z format specifier first
     let tz_from_format = local_dt.format("%Z").to_string();
@@ -502,24 +814,6 @@ fn get_timezone_abbreviation(local_dt: &DateTime<Local>) -> String {
         return tz_from_format;
     }
 
-    // Try to get timezone from TZ environment variable
-    if let Ok(tz_env) = std::env::var("TZ") {
-        // Extract timezone abbreviation from TZ variable
-        if let Some(tz_name) = tz_env.rsplit('/').next() {
-            // Check if it looks like a timezone abbreviation (3-6 uppercase letters)
-            if tz_name.len() >= 3
-                && tz_name.len() <= 6
-                && tz_name.chars().all(|c| c.is_uppercase() || c == '-')
-            {
-                // Extract just the abbreviation part (before any offset)
-                let abbr = tz_name.split('-').next().unwrap_or(tz_name);
-                if abbr.len() >= 3 && abbr.chars().all(char::is_alphabetic) {
-                    return abbr.to_string();
-                }
-            }
-        }
-    }
-
     // Fallback: Use UTC offset format
     let offset_secs = local_dt.offset().local_minus_utc();
     let hours = offset_secs / 3600;
@@ -620,110 +914,226 @@ fn parse_date_to_timestamp(date_str: &str) -> Option<i64> {
 ///   - `<pre>` → preserves code blocks with triple backticks
 ///   - `<a>` → preserves links as `[text](url)`
 ///   - `<p>` → newlines between paragraphs
+///   - `<ul>`/`<ol>` → `- item` / `1. item` lines, nested lists indented under their item
+///   - `<blockquote>` → lines prefixed with `> `, nested quotes with `> > `
+/// - Walks the DOM recursively: blocks in `render_blocks()`, inline content of each block
+///   in `format_text_node()`
 fn html_to_formatted_text(element: ElementRef) -> String {
-    let mut result = String::new();
+    render_blocks(element).join("\n\n")
+}
 
-    // Process paragraphs to preserve structure
-    let p_selector = Selector::parse("p").ok();
-    if let Some(ref p_sel) = p_selector {
-        let paragraphs: Vec<_> = element.select(p_sel).collect();
-        if !paragraphs.is_empty() {
-            for (i, p) in paragraphs.iter().enumerate() {
-                if i > 0 {
-                    result.push_str("\n\n");
-                }
-                result.push_str(&format_text_node(p));
+/// What: Render the children of an element as markdown blocks.
+///
+/// Inputs:
+/// - `element`: Container element (comment body, list item, blockquote, `<div>`, ...).
+///
+/// Output:
+/// - Non-empty blocks in document order, without separators.
+///
+/// Details:
+/// - Consecutive text and inline elements form one block; `<br>` becomes a line break.
+/// - Unknown container elements (`<div>`, `<section>`) are flattened into their blocks.
+fn render_blocks(element: ElementRef) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut inline = String::new();
+
+    for child in element.children() {
+        let Some(child_element) = ElementRef::wrap(child) else {
+            if let Some(text) = child.value().as_text() {
+                inline.push_str(text);
             }
-            return result;
-        }
+            continue;
+        };
+
+        let block = match child_element.value().name() {
+            "br" => {
+                inline.push('\n');
+                continue;
+            }
+            "p" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => format_text_node(&child_element),
+            "pre" => format_inline(&child_element),
+            "ul" => render_list(child_element, false),
+            "ol" => render_list(child_element, true),
+            "blockquote" => render_blockquote(child_element),
+            "div" | "section" | "article" => {
+                push_block(&mut blocks, &mut inline);
+                blocks.extend(render_blocks(child_element));
+                continue;
+            }
+            _ => {
+                inline.push_str(&format_inline(&child_element));
+                continue;
+            }
+        };
+        push_block(&mut blocks, &mut inline);
+        inline = block;
+        push_block(&mut blocks, &mut inline);
     }
+    push_block(&mut blocks, &mut inline);
 
-    // If no paragraphs, process as a single text node
-    format_text_node(&element)
+    blocks
 }
 
-/// Format a single HTML element to text with markdown-like syntax.
-fn format_text_node(element: &ElementRef) -> String {
-    // Simple approach: extract all text and format common HTML tags
-    let mut result = element.html();
-
-    // Process <pre> blocks (code blocks)
-    let pre_selector = Selector::parse("pre").ok();
-    if let Some(ref pre_sel) = pre_selector {
-        for pre in element.select(pre_sel) {
-            let text = pre.text().collect::<String>();
-            let pre_html = pre.html();
-            let replacement = format!("```\n{}\n```", text.trim());
-            result = result.replace(&pre_html, &replacement);
-        }
+/// What: Move pending text into the block list.
+///
+/// Inputs:
+/// - `blocks`: Rendered blocks.
+/// - `pending`: Text collected for the current block; left empty afterwards.
+///
+/// Output:
+/// - Appends the trimmed text to `blocks` unless it is blank.
+fn push_block(blocks: &mut Vec<String>, pending: &mut String) {
+    let text = std::mem::take(pending);
+    let text = text.trim_matches(|c: char| c.is_whitespace() && c != '\u{a0}');
+    if !text.is_empty() {
+        blocks.push(text.to_string());
     }
+}
 
-    // Process <a> tags (links)
-    let a_selector = Selector::parse("a").ok();
-    if let Some(ref a_sel) = a_selector {
-        for link in element.select(a_sel) {
-            let text = link.text().collect::<String>().trim().to_string();
-            if let Some(href) = link.value().attr("href") {
-                let link_html = link.html();
-                let replacement = format!("[{text}]({href})");
-                result = result.replace(&link_html, &replacement);
+/// What: Render a `<ul>` or `<ol>` element as markdown list lines.
+///
+/// Inputs:
+/// - `list`: List element.
+/// - `ordered`: `true` for numbered (`<ol>`) lists.
+///
+/// Output:
+/// - One `- ` or `N. ` line per item; continuation lines and nested lists are indented by
+///   the width of the marker.
+///
+/// Details:
+/// - Ordered lists honor the `start` attribute.
+fn render_list(list: ElementRef, ordered: bool) -> String {
+    let mut number = list
+        .value()
+        .attr("start")
+        .and_then(|start| start.parse::<u64>().ok())
+        .unwrap_or(1);
+    let mut lines = Vec::new();
+
+    for item in list
+        .children()
+        .filter_map(ElementRef::wrap)
+        .filter(|child| child.value().name() == "li")
+    {
+        let marker = if ordered {
+            let marker = format!("{number}. ");
+            number += 1;
+            marker
+        } else {
+            "- ".to_string()
+        };
+        let indent = " ".repeat(marker.len());
+        let body = render_blocks(item).join("\n");
+        let mut body_lines = body.lines();
+        lines.push(format!("{marker}{}", body_lines.next().unwrap_or_default()));
+        lines.extend(body_lines.map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!("{indent}{line}")
             }
-        }
+        }));
     }
 
-    // Process <strong> and <b> tags (bold)
-    let strong_selector = Selector::parse("strong, b").ok();
-    if let Some(ref strong_sel) = strong_selector {
-        for bold in element.select(strong_sel) {
-            let text = bold.text().collect::<String>().trim().to_string();
-            if !text.is_empty() {
-                let bold_html = bold.html();
-                let replacement = format!("**{text}**");
-                result = result.replace(&bold_html, &replacement);
+    lines.join("\n")
+}
+
+/// What: Render a `<blockquote>` element as `> ` prefixed lines.
+///
+/// Inputs:
+/// - `quote`: Blockquote element.
+///
+/// Output:
+/// - Quoted markdown; blank lines between blocks become `>`.
+fn render_blockquote(quote: ElementRef) -> String {
+    render_blocks(quote)
+        .join("\n\n")
+        .lines()
+        .map(|line| {
+            if line.is_empty() {
+                ">".to_string()
+            } else {
+                format!("> {line}")
             }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// What: Format the inline content of an element with markdown-like syntax.
+///
+/// Inputs:
+/// - `element`: Element whose children are formatted (e.g. a `<p>`).
+///
+/// Output:
+/// - Text of the children in document order; the element's own tag is not rendered.
+///
+/// Details:
+/// - Walks the children recursively, so nested and repeated inline elements each render
+///   exactly once (`[**foo**](url)` for a bold span inside a link).
+fn format_text_node(element: &ElementRef) -> String {
+    let mut result = String::new();
+    for child in element.children() {
+        if let Some(child_element) = ElementRef::wrap(child) {
+            result.push_str(&format_inline(&child_element));
+        } else if let Some(text) = child.value().as_text() {
+            result.push_str(text);
         }
     }
+    result
+}
 
-    // Process <em> and <i> tags (italic)
-    let em_selector = Selector::parse("em, i").ok();
-    if let Some(ref em_sel) = em_selector {
-        for italic in element.select(em_sel) {
-            let text = italic.text().collect::<String>().trim().to_string();
-            if !text.is_empty() {
-                let italic_html = italic.html();
-                let replacement = format!("*{text}*");
-                result = result.replace(&italic_html, &replacement);
-            }
+/// What: Format an inline element, including its own markup.
+///
+/// Inputs:
+/// - `element`: Element to format (`<strong>`, `<em>`, `<code>`, `<a>`, `<pre>`, `<span>`, ...).
+///
+/// Output:
+/// - Markdown-like text for the element.
+///
+/// Details:
+/// - `<strong>`/`<b>` → `**text**`, `<em>`/`<i>` → `*text*`, `<code>` → `` `text` ``,
+///   `<pre>` → fenced code block, `<a href>` → `[text](url)`, `<br>` → line break.
+/// - Emphasis and code with blank content render as their plain content.
+/// - `<code>` and `<pre>` keep their raw text; other elements format their children.
+fn format_inline(element: &ElementRef) -> String {
+    let wrap = |marker: &str, inner: String| {
+        let text = inner.trim();
+        if text.is_empty() {
+            inner
+        } else {
+            format!("{marker}{text}{marker}")
         }
-    }
+    };
 
-    // Process <code> tags
-    let code_selector = Selector::parse("code").ok();
-    if let Some(ref code_sel) = code_selector {
-        for code in element.select(code_sel) {
-            let text = code.text().collect::<String>().trim().to_string();
-            if !text.is_empty() {
-                let code_html = code.html();
-                let replacement = format!("`{text}`");
-                result = result.replace(&code_html, &replacement);
+    match element.value().name() {
+        "br" => "\n".to_string(),
+        "pre" => format!("```\n{}\n```", element.text().collect::<String>().trim()),
+        "code" => wrap("`", element.text().collect()),
+        "strong" | "b" => wrap("**", format_text_node(element)),
+        "em" | "i" => wrap("*", format_text_node(element)),
+        "a" => {
+            let inner = format_text_node(element);
+            match element.value().attr("href") {
+                Some(href) => format!("[{}]({href})", inner.trim()),
+                None => inner,
             }
         }
+        _ => format_text_node(element),
     }
-
-    // Parse the modified HTML and extract text (this removes remaining HTML tags)
-    let temp_doc = Html::parse_fragment(&result);
-    let text_result = temp_doc.root_element().text().collect::<String>();
-
-    // Replace <br> with newlines
-    text_result
-        .replace("<br>", "\n")
-        .replace("<br/>", "\n")
-        .replace("<br />", "\n")
 }
 
 #[cfg(test)]
 mod tests {
+    use super::{
+        COMMENTS_PER_PAGE, DisplayZone, comments_page_url, extract_edited_date,
+        html_to_formatted_text, parse_comments_html, parse_comments_page, parse_numeric_comment_id,
+        separate_and_sort_comments,
+    };
+    use crate::aur::url::AUR_BASE_URL;
     use crate::error::ArchToolkitError;
+    use std::collections::HashSet;
+    use std::fmt::Write;
 
     #[test]
     fn test_comments_error_includes_package_context() {
@@ -751,4 +1161,396 @@ mod tests {
             "Error message should indicate comments operation: {error_msg}"
         );
     }
+
+    /// What: Build an AUR-like package page with one pinned and `count` regular comments.
+    ///
+    /// Details:
+    /// - Regular comments are listed newest first, as on the AUR.
+    fn comments_fixture(count: u32) -> String {
+        let mut html = String::from(
+            "<html><body><h3>Pinned Comments</h3>\
+             <h4 class=\"comment-header\" id=\"comment-9000\">maintainer commented on \
+             <a class=\"date\" href=\"#comment-9000\">2020-01-01 00:00 (UTC)</a></h4>\
+             <div class=\"article-content\" id=\"comment-9000-content\"><p>Pinned note</p></div>\
+             <h3>Latest Comments</h3>",
+        );
+        for i in (1..=count).rev() {
+            let _ = write!(
+                html,
+                "<h4 class=\"comment-header\" id=\"comment-{i}\">user{i} commented on \
+                 <a class=\"date\" href=\"#comment-{i}\">2024-01-{i:02} 12:00 (UTC)</a></h4>\
+                 <div class=\"article-content\" id=\"comment-{i}-content\"><p>Comment {i}</p></div>"
+            );
+        }
+        html.push_str("</body></html>");
+        html
+    }
+
+    #[test]
+    fn test_parse_comments_html_respects_max_comments() {
+        let html = comments_fixture(20);
+
+        let all =
+            parse_comments_html(&html, "foo", 1000, DisplayZone::Local).expect("parse succeeds");
+        assert_eq!(all.len(), 21);
+
+        let limited =
+            parse_comments_html(&html, "foo", 5, DisplayZone::Local).expect("parse succeeds");
+        assert_eq!(limited.len(), 5);
+        assert!(limited[0].pinned, "pinned comment is kept first");
+        assert_eq!(limited[0].author, "maintainer");
+        let authors: Vec<&str> = limited[1..].iter().map(|c| c.author.as_str()).collect();
+        assert_eq!(authors, vec!["user20", "user19", "user18", "user17"]);
+    }
+
+    #[test]
+    fn test_parse_comments_html_zero_limit() {
+        let html = comments_fixture(3);
+        let none =
+            parse_comments_html(&html, "foo", 0, DisplayZone::Local).expect("parse succeeds");
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn test_comments_page_url_offsets() {
+        assert_eq!(
+            comments_page_url(AUR_BASE_URL, "foo", 0),
+            format!("https://aur.archlinux.org/packages/foo?O=0&PP={COMMENTS_PER_PAGE}")
+        );
+        assert_eq!(
+            comments_page_url(AUR_BASE_URL, "foo", 2),
+            "https://aur.archlinux.org/packages/foo?O=20&PP=10"
+        );
+    }
+
+    #[test]
+    fn test_parse_comments_page_dedups_across_pages() {
+        // Both pages repeat the pinned comment; the second page also repeats comment 3
+        let first = comments_fixture(3);
+        let second = comments_fixture(3).replace(
+            "Latest Comments</h3>",
+            "Latest Comments</h3>\
+            <h4 class=\"comment-header\" id=\"comment-0\">old commented on \
+            <a class=\"date\" href=\"#comment-0\">2023-12-31 12:00 (UTC)</a></h4>\
+            <div class=\"article-content\" id=\"comment-0-content\"><p>Old</p></div>",
+        );
+
+        let mut seen = HashSet::new();
+        let mut all = parse_comments_page(&first, "foo", 1000, DisplayZone::Local, &mut seen)
+            .expect("parse succeeds");
+        assert_eq!(all.len(), 4);
+        let new = parse_comments_page(&second, "foo", 1000, DisplayZone::Local, &mut seen)
+            .expect("parse succeeds");
+        assert_eq!(new.len(), 1);
+        assert_eq!(new[0].author, "old");
+        all.extend(new);
+
+        let past_end = parse_comments_page(&first, "foo", 1000, DisplayZone::Local, &mut seen)
+            .expect("parse succeeds");
+        assert!(
+            past_end.is_empty(),
+            "a page with only seen comments ends the walk"
+        );
+
+        let sorted = separate_and_sort_comments(all);
+        assert_eq!(sorted.iter().filter(|c| c.pinned).count(), 1);
+        assert_eq!(sorted.last().map(|c| c.author.as_str()), Some("old"));
+    }
+
+    #[test]
+    /// What: Extract numeric IDs, edit dates, and deleted placeholders.
+    ///
+    /// Inputs:
+    /// - Page with an edited comment, a deleted comment, and a plain comment whose header has
+    ///   no `id` attribute.
+    ///
+    /// Output:
+    /// - The edited comment carries its edit date, the deleted one is flagged, and the
+    ///   numeric ID falls back to the date link.
+    fn test_parse_comments_html_edited_and_deleted() {
+        let html = "<html><body><h3>Latest Comments</h3>\
+            <h4 class=\"comment-header\" id=\"comment-931234\">alice commented on \
+            <a class=\"date\" href=\"#comment-931234\">2024-02-01 09:00 (UTC)</a> \
+            <span class=\"edited\">(edited on 2024-02-02 10:30 (UTC) by alice)</span></h4>\
+            <div class=\"article-content\" id=\"comment-931234-content\"><p>Fixed build</p></div>\
+            <h4 class=\"comment-header comment-deleted\" id=\"comment-931200\">bob commented on \
+            <a class=\"date\" href=\"#comment-931200\">2024-01-31 08:00 (UTC)</a></h4>\
+            <div class=\"article-content\" id=\"comment-931200-content\">\
+            <p>This comment has been deleted</p></div>\
+            <h4 class=\"comment-header\">carol commented on \
+            <a class=\"date\" href=\"/packages/foo#comment-931100\">2024-01-30 07:00 (UTC)</a></h4>\
+            </body></html>";
+
+        let comments =
+            parse_comments_html(html, "foo", 1000, DisplayZone::Local).expect("parse succeeds");
+        assert_eq!(comments.len(), 3);
+
+        let edited = &comments[0];
+        assert_eq!(edited.author, "alice");
+        assert_eq!(edited.numeric_id, Some(931_234));
+        assert!(edited.edited.is_some(), "edit date is extracted");
+        assert!(!edited.deleted);
+
+        let deleted = &comments[1];
+        assert_eq!(deleted.author, "bob");
+        assert_eq!(deleted.numeric_id, Some(931_200));
+        assert_eq!(deleted.edited, None);
+        assert!(deleted.deleted);
+
+        let plain = &comments[2];
+        assert_eq!(plain.numeric_id, Some(931_100));
+        assert!(!plain.deleted);
+    }
+
+    #[test]
+    /// What: Deduplicate by numeric ID when header IDs differ in form.
+    fn test_parse_comments_page_dedups_by_numeric_id() {
+        let html = "<html><body><h3>Latest Comments</h3>\
+            <h4 class=\"comment-header\" id=\"comment-42\">dave commented on \
+            <a class=\"date\" href=\"#comment-42\">2024-01-01 00:00 (UTC)</a></h4>\
+            <h4 class=\"comment-header\">dave commented on \
+            <a class=\"date\" href=\"https://aur.archlinux.org/packages/foo#comment-42\">\
+            2024-01-01 00:00 (UTC)</a></h4>\
+            </body></html>";
+
+        let mut seen = HashSet::new();
+        let comments = parse_comments_page(html, "foo", 1000, DisplayZone::Local, &mut seen)
+            .expect("parse succeeds");
+        assert_eq!(comments.len(), 1);
+        assert!(seen.contains("42"));
+    }
+
+    /// What: Convert a comment body fragment to formatted text.
+    fn format_fragment(html: &str) -> String {
+        let fragment = scraper::Html::parse_fragment(&format!("<div>{html}</div>"));
+        let selector = scraper::Selector::parse("div").expect("valid selector");
+        let body = fragment.select(&selector).next().expect("div is present");
+        html_to_formatted_text(body)
+    }
+
+    #[test]
+    /// What: Render nested unordered and ordered lists as indented markdown.
+    ///
+    /// Inputs:
+    /// - Paragraph followed by a `<ul>` whose second item holds an `<ol>`.
+    ///
+    /// Output:
+    /// - `- ` bullets with the numbered sub-list indented under its item.
+    fn test_html_to_formatted_text_nested_list() {
+        let text = format_fragment(
+            "<p>Steps:</p>\n<ul>\n<li>Install <code>base-devel</code></li>\n\
+             <li>Then build:\n<ol>\n<li>clone</li>\n<li>run <strong>makepkg</strong></li>\n</ol>\n</li>\n\
+             </ul>",
+        );
+        assert_eq!(
+            text,
+            "Steps:\n\n\
+             - Install `base-devel`\n\
+             - Then build:\n  \
+             1. clone\n  \
+             2. run **makepkg**"
+        );
+    }
+
+    #[test]
+    /// What: Render blockquotes, including a nested quote, as `> ` prefixed lines.
+    ///
+    /// Inputs:
+    /// - Quote with two paragraphs and an inner quote, followed by a reply paragraph.
+    ///
+    /// Output:
+    /// - Every quoted line prefixed, inner lines with `> > `, blank lines kept as `>`.
+    fn test_html_to_formatted_text_blockquote() {
+        let text = format_fragment(
+            "<blockquote>\n<p>Build fails with <em>gcc 14</em></p>\n<p>Any ideas?</p>\n\
+             <blockquote><p>Same here</p></blockquote>\n</blockquote>\n<p>Fixed in 1.2-2</p>",
+        );
+        assert_eq!(
+            text,
+            "> Build fails with *gcc 14*\n\
+             >\n\
+             > Any ideas?\n\
+             >\n\
+             > > Same here\n\
+             \n\
+             Fixed in 1.2-2"
+        );
+    }
+
+    #[test]
+    /// What: Render repeated identical bold fragments each exactly once.
+    ///
+    /// Inputs:
+    /// - Paragraph with two identical `<strong>` fragments and a fenced `<pre>` block.
+    ///
+    /// Output:
+    /// - Both fragments bold, no doubled markers; the code block is fenced.
+    fn test_format_text_node_repeated_bold() {
+        let text = format_fragment(
+            "<p><strong>foo</strong> and <strong>foo</strong> again</p>\
+             <pre>make install</pre>",
+        );
+        assert_eq!(text, "**foo** and **foo** again\n\n```\nmake install\n```");
+    }
+
+    #[test]
+    /// What: Render bold text inside a link and a link inside bold text.
+    ///
+    /// Inputs:
+    /// - `<a>` containing `<strong>`, then `<b>` containing `<a>` with `<code>`.
+    ///
+    /// Output:
+    /// - Nested markup in document order without mangled boundaries.
+    fn test_format_text_node_bold_inside_link() {
+        let text = format_fragment(
+            "<p>See <a href=\"https://example.com/wiki\"><strong>the wiki</strong> page</a>, \
+             <b>or <a href=\"https://example.com/x\"><code>x</code></a></b>.</p>",
+        );
+        assert_eq!(
+            text,
+            "See [**the wiki** page](https://example.com/wiki), \
+             **or [`x`](https://example.com/x)**."
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "timezone")]
+    /// What: Convert AUR UTC dates into configured IANA timezones.
+    ///
+    /// Inputs:
+    /// - `2024-07-01 12:00 (UTC)` and `2024-01-15 12:00 (UTC)`.
+    ///
+    /// Output:
+    /// - Berlin and Los Angeles times with their daylight-saving aware abbreviations.
+    fn test_convert_utc_date_named_timezones() {
+        use super::convert_utc_date;
+
+        let berlin = DisplayZone::Named(chrono_tz::Europe::Berlin);
+        let los_angeles = DisplayZone::Named(chrono_tz::America::Los_Angeles);
+
+        assert_eq!(
+            convert_utc_date("2024-07-01 12:00 (UTC)", berlin),
+            "2024-07-01 14:00 (CEST)"
+        );
+        assert_eq!(
+            convert_utc_date("2024-01-15 12:00 (UTC)", berlin),
+            "2024-01-15 13:00 (CET)"
+        );
+        assert_eq!(
+            convert_utc_date("2024-07-01 12:00 (UTC)", los_angeles),
+            "2024-07-01 05:00 (PDT)"
+        );
+        assert_eq!(
+            convert_utc_date("2024-01-15 03:30 (UTC)", los_angeles),
+            "2024-01-14 19:30 (PST)"
+        );
+        assert_eq!(convert_utc_date("yesterday", berlin), "yesterday");
+    }
+
+    #[test]
+    #[cfg(feature = "timezone")]
+    /// What: Display parsed comment and edit dates in the configured timezone.
+    fn test_parse_comments_html_uses_named_timezone() {
+        let html = comments_fixture(1);
+        let comments = parse_comments_html(
+            &html,
+            "foo",
+            1000,
+            DisplayZone::Named(chrono_tz::Europe::Berlin),
+        )
+        .expect("parse succeeds");
+        assert_eq!(comments[1].date, "2024-01-01 13:00 (CET)");
+    }
+
+    #[test]
+    fn test_comment_header_helpers() {
+        assert_eq!(parse_numeric_comment_id("comment-931234"), Some(931_234));
+        assert_eq!(
+            parse_numeric_comment_id("https://aur.archlinux.org/packages/foo#comment-7"),
+            Some(7)
+        );
+        assert_eq!(parse_numeric_comment_id("comment-abc"), None);
+        assert_eq!(
+            extract_edited_date("alice commented on x (edited on 2024-02-02 10:30 (UTC) by alice)")
+                .as_deref(),
+            Some("2024-02-02 10:30 (UTC)")
+        );
+        assert_eq!(extract_edited_date("alice commented on x"), None);
+    }
+
+    /// What: Fetch comments from a mock server answering every request with `status`.
+    ///
+    /// Inputs:
+    /// - `status`: HTTP status code returned for the package page.
+    ///
+    /// Output:
+    /// - The comments result and the number of requests the server received.
+    async fn comments_with_status(
+        status: u16,
+    ) -> (crate::error::Result<Vec<crate::types::AurComment>>, usize) {
+        use crate::client::{ArchClient, RetryPolicy};
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/packages/foo"))
+            .respond_with(ResponseTemplate::new(status))
+            .mount(&server)
+            .await;
+
+        let client = ArchClient::builder()
+            .aur_base_url(server.uri())
+            .retry_policy(RetryPolicy {
+                max_retries: 2,
+                initial_delay_ms: 1,
+                max_delay_ms: 1,
+                jitter_max_ms: 0,
+                circuit_breaker_threshold: 0,
+                ..RetryPolicy::default()
+            })
+            .build()
+            .expect("client builds");
+
+        let result = client.aur().comments("foo").await;
+        let requests = server.received_requests().await.map_or(0, |r| r.len());
+        (result, requests)
+    }
+
+    #[tokio::test]
+    /// What: A 404 surfaces as `Http` with status and URL and is not retried.
+    ///
+    /// Inputs:
+    /// - Mock server answering the package page with 404.
+    ///
+    /// Output:
+    /// - `Http { status: 404, .. }` for the requested URL after a single request.
+    async fn test_comments_not_found_maps_to_http_error() {
+        let (result, requests) = comments_with_status(404).await;
+        match result {
+            Err(ArchToolkitError::Http { status, url }) => {
+                assert_eq!(status, 404);
+                assert!(url.ends_with("/packages/foo"), "unexpected url {url}");
+            }
+            other => panic!("expected Http error, got {other:?}"),
+        }
+        assert_eq!(requests, 1);
+    }
+
+    #[tokio::test]
+    /// What: A 503 surfaces as `Http` after the retries are exhausted.
+    ///
+    /// Inputs:
+    /// - Mock server answering the package page with 503, retry policy with 2 retries.
+    ///
+    /// Output:
+    /// - `Http { status: 503, .. }` after three requests.
+    async fn test_comments_service_unavailable_is_retried_as_http_error() {
+        let (result, requests) = comments_with_status(503).await;
+        assert!(
+            matches!(result, Err(ArchToolkitError::Http { status: 503, .. })),
+            "unexpected result {result:?}"
+        );
+        assert_eq!(requests, 3);
+    }
 }
@@ -0,0 +1,175 @@
+//! Latest `aur.git` commit lookup.
+
+use crate::aur::utils::percent_encode;
+use crate::aur::validation::validate_package_name;
+use crate::client::{ArchClient, check_status, reset_archlinux_backoff};
+use crate::error::{ArchToolkitError, Result};
+use reqwest::Client;
+use std::time::Duration;
+use tracing::debug;
+
+/// Upper bound on bytes read while looking for the patch header line.
+const HEADER_LIMIT: usize = 4096;
+
+/// What: Fetch the id of the latest commit of an AUR package's git repository.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+/// - `package`: Package base name.
+///
+/// Output:
+/// - `Result<String>` with the 40-character hex commit id of the package's `aur.git` ref.
+///
+/// Details:
+/// - Fetches `{aur_base_url}/cgit/aur.git/patch/?h={package}` and reads the commit id from
+///   the `From <id> ...` header line; the rest of the patch is not downloaded.
+/// - Applies archlinux.org rate limiting and the retry policy for pkgbuild operations.
+/// - Never cached: the result is meant to detect stale cached PKGBUILDs.
+///
+/// # Errors
+/// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+/// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
+/// - Returns `Err(ArchToolkitError::Parse)` if the response has no commit header (e.g. unknown
+///   package)
+/// - Returns `Err(ArchToolkitError::InvalidInput)` if the URL is not from the AUR
+/// - Returns `Err(ArchToolkitError::ServiceUnavailable)` if the circuit breaker is open
+/// - Returns `Err(ArchToolkitError::InvalidPackageName)` if package name is invalid
+pub async fn pkgbuild_commit(client: &ArchClient, package: &str) -> Result<String> {
+    let validation_config = client.validation_config();
+    validate_package_name(package, Some(validation_config))?;
+
+    let url = format!(
+        "{}/cgit/aur.git/patch/?h={}",
+        client.aur_base_url(),
+        percent_encode(package)
+    );
+
+    debug!(package = %package, url = %url, "fetching latest aur.git commit");
+
+    // Apply rate limiting for archlinux.org
+    let _permit = if client.is_aur_url(&url) {
+        client.archlinux_permit().await?
+    } else {
+        return Err(ArchToolkitError::InvalidInput(format!(
+            "Unexpected URL domain: {url}"
+        )));
+    };
+
+    let http_client = client.http_client();
+
+    client
+        .execute_with_retry("pkgbuild", package, || async {
+            perform_commit_request(http_client, &url, package).await
+        })
+        .await
+}
+
+/// What: Perform the actual commit request without retry logic.
+///
+/// Inputs:
+/// - `client`: HTTP client to use for requests.
+/// - `url`: URL to request.
+/// - `package`: Package name for logging and errors.
+///
+/// Output:
+/// - `Result<String>` with the commit id, or an error.
+///
+/// Details:
+/// - Reads the body chunk by chunk until the first line is complete, then drops the response.
+async fn perform_commit_request(client: &Client, url: &str, package: &str) -> Result<String> {
+    let response = client
+        .get(url)
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| {
+            debug!(error = %e, package = %package, "commit request failed");
+            ArchToolkitError::Network(e)
+        })?;
+    reset_archlinux_backoff();
+
+    let mut response = check_status(response).inspect_err(|e| {
+        debug!(error = %e, package = %package, "commit request returned non-success status");
+    })?;
+
+    let mut head = Vec::new();
+    while !head.contains(&b'\n')
+        && head.len() < HEADER_LIMIT
+        && let Some(chunk) = response.chunk().await.map_err(ArchToolkitError::Network)?
+    {
+        head.extend_from_slice(&chunk);
+    }
+
+    parse_patch_commit(&String::from_utf8_lossy(&head))
+        .ok_or_else(|| ArchToolkitError::Parse(format!("No aur.git commit found for {package}")))
+}
+
+/// What: Extract the commit id from the header of a `git format-patch` style patch.
+///
+/// Inputs:
+/// - `patch`: Patch text (at least its first line).
+///
+/// Output:
+/// - `Some(id)` if the first line is `From <40 hex chars> ...`, `None` otherwise.
+fn parse_patch_commit(patch: &str) -> Option<String> {
+    let id = patch
+        .lines()
+        .next()?
+        .strip_prefix("From ")?
+        .split(' ')
+        .next()?;
+    (id.len() == 40 && id.bytes().all(|b| b.is_ascii_hexdigit())).then(|| id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// What: Parse commit ids from patch headers and reject other content.
+    fn test_parse_patch_commit() {
+        let id = "0123456789abcdef0123456789abcdef01234567";
+        assert_eq!(
+            parse_patch_commit(&format!("From {id} Mon Sep 17 00:00:00 2001\nFrom: a\n")),
+            Some(id.to_string())
+        );
+        assert_eq!(parse_patch_commit("<!DOCTYPE html>\n"), None);
+        assert_eq!(parse_patch_commit("From abc Mon Sep 17\n"), None);
+        assert_eq!(parse_patch_commit(""), None);
+    }
+
+    #[tokio::test]
+    /// What: Fetch the latest commit id from the cgit patch endpoint.
+    ///
+    /// Inputs:
+    /// - Mock server answering `/cgit/aur.git/patch/` with a patch for `yay`.
+    ///
+    /// Output:
+    /// - The commit id from the patch header.
+    async fn test_pkgbuild_commit_reads_patch_header() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let id = "fedcba9876543210fedcba9876543210fedcba98";
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/cgit/aur.git/patch/"))
+            .and(query_param("h", "yay"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                "From {id} Mon Sep 17 00:00:00 2001\nFrom: someone\nSubject: [PATCH] 12.3.4\n"
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ArchClient::builder()
+            .aur_base_url(server.uri())
+            .build()
+            .expect("client builds");
+
+        let commit = pkgbuild_commit(&client, "yay")
+            .await
+            .expect("commit is fetched");
+        assert_eq!(commit, id);
+    }
+}
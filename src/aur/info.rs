@@ -1,17 +1,16 @@
 //! AUR package info/details functionality.
 
-use crate::aur::utils::{arrs, s, u64_of};
+use crate::aur::url::{AUR_RPC_VERSION, build_rpc_url};
+use crate::aur::utils::{arrs, check_rpc_error, instrumented, s, u64_of, with_timeout};
 use crate::aur::validation::validate_package_names;
-use crate::cache::cache_key_info;
-use crate::client::{
-    ArchClient, extract_retry_after, is_archlinux_url, rate_limit_archlinux,
-    reset_archlinux_backoff, retry_with_policy,
-};
+use crate::client::{ArchClient, check_status, extract_retry_after, reset_archlinux_backoff};
 use crate::error::{ArchToolkitError, Result};
 use crate::types::AurPackageDetails;
 use reqwest::Client;
 use serde_json::Value;
-use tracing::{debug, warn};
+use std::collections::HashSet;
+use std::time::Duration;
+use tracing::{debug, info_span, warn};
 
 /// What: Fetch detailed information for one or more AUR packages.
 ///
@@ -24,7 +23,8 @@ use tracing::{debug, warn};
 ///
 /// Details:
 /// - Uses AUR RPC v5 info endpoint.
-/// - Fetches info for all packages in a single request (more efficient).
+/// - Fetches info for up to `info_chunk_size` packages per request (default: 150); larger
+///   name lists are split into sequential requests and merged, deduplicated by name.
 /// - Returns empty vector if no packages found (not an error).
 /// - Applies rate limiting for archlinux.org requests.
 /// - Uses retry policy if enabled for info operations.
@@ -32,11 +32,64 @@ use tracing::{debug, warn};
 ///
 /// # Errors
 /// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+/// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
+/// - Returns `Err(ArchToolkitError::AurRpc)` if the AUR RPC reports an error
 /// - Returns `Err(ArchToolkitError::InvalidInput)` if the URL is not from archlinux.org
+/// - Returns `Err(ArchToolkitError::ServiceUnavailable)` if the circuit breaker is open
 /// - Returns `Err(ArchToolkitError::EmptyInput)` if names slice is empty and strict mode is enabled
 /// - Returns `Err(ArchToolkitError::InvalidPackageName)` if any package name is invalid
 /// - Returns `Err(ArchToolkitError::InputTooLong)` if any package name exceeds maximum length
 pub async fn info(client: &ArchClient, names: &[&str]) -> Result<Vec<AurPackageDetails>> {
+    info_with(client, names, None).await
+}
+
+/// What: Fetch detailed information for AUR packages with a per-request timeout override.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+/// - `names`: Slice of package names to fetch info for.
+/// - `timeout`: Per-request timeout override (`None` uses the client timeout).
+///
+/// Output:
+/// - `Result<Vec<AurPackageDetails>>` containing package details, or an error.
+///
+/// Details:
+/// - Same as `info()`; the timeout applies to each chunk request of this call only.
+/// - Runs inside an `aur_info` tracing span with `operation` and `package` fields.
+///
+/// # Errors
+/// - Returns the same errors as `info()`
+pub async fn info_with(
+    client: &ArchClient,
+    names: &[&str],
+    timeout: Option<Duration>,
+) -> Result<Vec<AurPackageDetails>> {
+    let packages = names.join(",");
+    let span = info_span!("aur_info", operation = "info", package = %packages);
+    instrumented(
+        client,
+        "info",
+        &packages,
+        span,
+        run_info(client, names, timeout),
+    )
+    .await
+}
+
+/// What: Validate, serve from cache or fetch package details; body of `info_with()`.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+/// - `names`: Slice of package names to fetch info for.
+/// - `timeout`: Per-request timeout override.
+///
+/// Output:
+/// - `Result<Vec<AurPackageDetails>>` containing package details, or an error.
+async fn run_info(
+    client: &ArchClient,
+    names: &[&str],
+    timeout: Option<Duration>,
+) -> Result<Vec<AurPackageDetails>> {
     // Validate input
     let validation_config = client.validation_config();
     validate_package_names(names, Some(validation_config))?;
@@ -51,58 +104,218 @@ pub async fn info(client: &ArchClient, names: &[&str]) -> Result<Vec<AurPackageD
         && cache_config.enable_info
         && let Some(cache) = client.cache()
     {
-        let cache_key = cache_key_info(names);
-        if let Some(cached) = cache.get::<Vec<AurPackageDetails>>(&cache_key) {
+        let cache_key = cache.keys().info(names);
+        let cached = cache.get::<Vec<AurPackageDetails>>(&cache_key);
+        client.observer().on_cache("info", cached.is_some());
+        if let Some(cached) = cached {
             debug!(names = ?names, "cache hit for info");
             return Ok(cached);
         }
     }
 
-    // Build URL with multiple arg parameters using array notation
-    // AUR RPC v5 requires arg[]=name1&arg[]=name2 format for multiple packages
-    let mut url = String::from("https://aur.archlinux.org/rpc/v5/info?");
-    for (i, name) in names.iter().enumerate() {
-        if i > 0 {
-            url.push('&');
+    let result = fetch_in_chunks(names, client.info_chunk_size(), |chunk| async move {
+        fetch_info_chunk(client, &chunk, timeout).await
+    })
+    .await?;
+
+    // Store in cache if enabled
+    if let Some(cache_config) = client.cache_config()
+        && cache_config.enable_info
+        && let Some(cache) = client.cache()
+    {
+        let _ = cache.set_info(names, &result, cache_config.info_ttl);
+    }
+
+    Ok(result)
+}
+
+/// What: Fetch info for a name list in sequential chunks and merge the results.
+///
+/// Inputs:
+/// - `names`: Package names to fetch.
+/// - `chunk_size`: Maximum names per request (0 sends all names at once).
+/// - `fetch`: Async function fetching info for one chunk.
+///
+/// Output:
+/// - Merged details in response order, deduplicated by package name.
+///
+/// Details:
+/// - Chunks are fetched one after another so the shared rate limiter stays in control.
+/// - Stops at the first failing chunk and returns its error.
+async fn fetch_in_chunks<'a, F, Fut>(
+    names: &[&'a str],
+    chunk_size: usize,
+    mut fetch: F,
+) -> Result<Vec<AurPackageDetails>>
+where
+    F: FnMut(Vec<&'a str>) -> Fut,
+    Fut: Future<Output = Result<Vec<AurPackageDetails>>>,
+{
+    let chunk_size = if chunk_size == 0 {
+        names.len().max(1)
+    } else {
+        chunk_size
+    };
+    let mut seen = HashSet::new();
+    let mut merged = Vec::new();
+    for chunk in names.chunks(chunk_size) {
+        for pkg in fetch(chunk.to_vec()).await? {
+            if seen.insert(pkg.name.clone()) {
+                merged.push(pkg);
+            }
         }
-        url.push_str("arg[]=");
-        url.push_str(name);
     }
+    Ok(merged)
+}
+
+/// What: Fetch info for a single chunk of package names.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+/// - `names`: Package names of this chunk.
+/// - `timeout`: Per-request timeout override (`None` uses the client timeout).
+///
+/// Output:
+/// - `Result<Vec<AurPackageDetails>>` for the chunk.
+///
+/// Details:
+/// - Applies rate limiting and the retry policy; caching is handled by `info()`.
+async fn fetch_info_chunk(
+    client: &ArchClient,
+    names: &[&str],
+    timeout: Option<Duration>,
+) -> Result<Vec<AurPackageDetails>> {
+    // Build URL with multiple arg parameters using array notation
+    // AUR RPC v5 requires arg[]=name1&arg[]=name2 format for multiple packages
+    let params: Vec<(&str, &str)> = names.iter().map(|name| ("arg[]", *name)).collect();
+    let url = build_rpc_url(client.aur_base_url(), AUR_RPC_VERSION, "info", &params);
 
     debug!(names = ?names, url = %url, "fetching AUR package info");
 
     // Apply rate limiting for archlinux.org
-    let _permit = if is_archlinux_url(&url) {
-        rate_limit_archlinux().await
+    let _permit = if client.is_aur_url(&url) {
+        client.archlinux_permit().await?
     } else {
         return Err(ArchToolkitError::InvalidInput(format!(
             "Unexpected URL domain: {url}"
         )));
     };
 
-    let retry_policy = client.retry_policy();
     let http_client = client.http_client();
 
     // Wrap the request in retry logic if enabled
-    let result = if retry_policy.enabled && retry_policy.retry_info {
-        retry_with_policy(retry_policy, "info", &names.join(", "), || async {
-            perform_info_request(http_client, &url, names).await
+    client
+        .execute_with_retry("info", &names.join(", "), || async {
+            perform_info_request(http_client, &url, names, timeout).await
         })
         .await
+}
+
+/// What: Fetch package details, failing if any requested package is missing.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+/// - `names`: Slice of package names to fetch info for.
+///
+/// Output:
+/// - `Result<Vec<AurPackageDetails>>` with details for every requested package.
+///
+/// Details:
+/// - Strict variant of `info()`, which silently omits unknown packages.
+///
+/// # Errors
+/// - Returns `Err(ArchToolkitError::PackagesNotFound)` listing every requested name without a result
+/// - Returns the same errors as `info()`
+pub async fn info_checked(client: &ArchClient, names: &[&str]) -> Result<Vec<AurPackageDetails>> {
+    let details = info(client, names).await?;
+    let missing = missing_packages(names, &details);
+    if missing.is_empty() {
+        Ok(details)
     } else {
-        perform_info_request(http_client, &url, names).await
-    }?;
+        debug!(missing = ?missing, "AUR info missing requested packages");
+        Err(ArchToolkitError::PackagesNotFound { missing })
+    }
+}
 
-    // Store in cache if enabled
-    if let Some(cache_config) = client.cache_config()
-        && cache_config.enable_info
-        && let Some(cache) = client.cache()
-    {
-        let cache_key = cache_key_info(names);
-        let _ = cache.set(&cache_key, &result, cache_config.info_ttl);
+/// What: Determine which requested package names have no info result.
+///
+/// Inputs:
+/// - `names`: Requested package names.
+/// - `details`: Info results.
+///
+/// Output:
+/// - Missing names in request order, without duplicates.
+pub fn missing_packages(names: &[&str], details: &[AurPackageDetails]) -> Vec<String> {
+    let mut missing: Vec<String> = Vec::new();
+    for name in names {
+        if !details.iter().any(|pkg| pkg.name == *name) && !missing.iter().any(|m| m == name) {
+            missing.push((*name).to_string());
+        }
     }
+    missing
+}
 
-    Ok(result)
+/// What: Look up the package base (`pkgbase`) of an AUR package.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+/// - `name`: Package name.
+///
+/// Output:
+/// - `Ok(Some(base))` with the current package base; `Ok(None)` if the package is not in the AUR.
+///
+/// Details:
+/// - Uses `info()`, so caching, rate limiting, and retries apply.
+/// - Falls back to the package name when the RPC omits `PackageBase`.
+///
+/// # Errors
+/// - Returns the same errors as `info()`
+pub async fn pkgbase_of(client: &ArchClient, name: &str) -> Result<Option<String>> {
+    let details = info(client, &[name]).await?;
+    Ok(find_pkgbase(&details, name))
+}
+
+/// What: Detect whether an AUR package's package base changed.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+/// - `installed_name`: Name of the installed package.
+/// - `known_base`: Package base recorded locally (e.g., the name of the local git clone).
+///
+/// Output:
+/// - `Ok(Some(new_base))` if the current package base differs from `known_base`.
+/// - `Ok(None)` if it is unchanged or the package is no longer in the AUR.
+///
+/// Details:
+/// - Built on `pkgbase_of()`; a changed base means the git remote must be re-pointed or re-cloned.
+///
+/// # Errors
+/// - Returns the same errors as `info()`
+pub async fn pkgbase_changed(
+    client: &ArchClient,
+    installed_name: &str,
+    known_base: &str,
+) -> Result<Option<String>> {
+    let current = pkgbase_of(client, installed_name).await?;
+    Ok(current.filter(|base| base != known_base))
+}
+
+/// What: Find the package base for a package in info results.
+///
+/// Inputs:
+/// - `details`: Info results to search.
+/// - `name`: Package name.
+///
+/// Output:
+/// - Package base of the matching result, or the name itself if the base is empty.
+fn find_pkgbase(details: &[AurPackageDetails], name: &str) -> Option<String> {
+    details.iter().find(|pkg| pkg.name == name).map(|pkg| {
+        if pkg.package_base.is_empty() {
+            pkg.name.clone()
+        } else {
+            pkg.package_base.clone()
+        }
+    })
 }
 
 /// What: Perform the actual info request without retry logic.
@@ -121,8 +334,9 @@ async fn perform_info_request(
     client: &Client,
     url: &str,
     package_names: &[&str],
+    timeout: Option<Duration>,
 ) -> Result<Vec<AurPackageDetails>> {
-    let response = match client.get(url).send().await {
+    let response = match with_timeout(client.get(url), timeout).send().await {
         Ok(resp) => {
             reset_archlinux_backoff();
             resp
@@ -136,13 +350,9 @@ async fn perform_info_request(
     // Check for Retry-After header before consuming response
     let _retry_after = extract_retry_after(&response);
 
-    let response = match response.error_for_status() {
-        Ok(resp) => resp,
-        Err(e) => {
-            warn!(error = %e, packages = ?package_names, "AUR info returned non-success status");
-            return Err(ArchToolkitError::info_failed(package_names, e));
-        }
-    };
+    let response = check_status(response).inspect_err(|e| {
+        warn!(error = %e, packages = ?package_names, "AUR info returned non-success status");
+    })?;
 
     let json: Value = match response.json().await {
         Ok(json) => json,
@@ -153,7 +363,28 @@ async fn perform_info_request(
             return Err(ArchToolkitError::info_failed(package_names, e));
         }
     };
+    check_rpc_error(&json).inspect_err(|e| {
+        warn!(error = %e, packages = ?package_names, "AUR info returned an RPC error");
+    })?;
+
+    let packages = parse_info_results(&json);
 
+    debug!(found = packages.len(), "AUR info fetch completed");
+
+    Ok(packages)
+}
+
+/// What: Parse the `results` array of an AUR RPC info response.
+///
+/// Inputs:
+/// - `json`: Decoded RPC response body.
+///
+/// Output:
+/// - Package details for every result with a non-empty name.
+///
+/// Details:
+/// - Missing fields default to empty values; `OutOfDate` and timestamps of `0` are treated as unset.
+fn parse_info_results(json: &Value) -> Vec<AurPackageDetails> {
     let mut packages = Vec::new();
 
     if let Some(results) = json.get("results").and_then(Value::as_array) {
@@ -162,6 +393,7 @@ async fn perform_info_request(
             if name.is_empty() {
                 continue;
             }
+            let package_base = s(pkg, "PackageBase");
 
             let version = s(pkg, "Version");
             let description = s(pkg, "Description");
@@ -176,6 +408,7 @@ async fn perform_info_request(
             let opt_depends = arrs(pkg, &["OptDepends"]);
             let conflicts = arrs(pkg, &["Conflicts"]);
             let replaces = arrs(pkg, &["Replaces"]);
+            let keywords = arrs(pkg, &["Keywords"]);
 
             // Extract maintainer
             let maintainer_str = s(pkg, "Maintainer");
@@ -210,6 +443,7 @@ async fn perform_info_request(
 
             packages.push(AurPackageDetails {
                 name,
+                package_base,
                 version,
                 description,
                 url,
@@ -221,6 +455,7 @@ async fn perform_info_request(
                 opt_depends,
                 conflicts,
                 replaces,
+                keywords,
                 maintainer,
                 first_submitted,
                 last_modified,
@@ -232,14 +467,13 @@ async fn perform_info_request(
         }
     }
 
-    debug!(found = packages.len(), "AUR info fetch completed");
-
-    Ok(packages)
+    packages
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cache::cache_key_info;
     use crate::error::ArchToolkitError;
     use serde_json::json;
 
@@ -280,6 +514,7 @@ mod tests {
             "results": [
                 {
                     "Name": "yay",
+                    "PackageBase": "yay",
                     "Version": "12.3.4-1",
                     "Description": "AUR helper",
                     "URL": "https://github.com/Jguer/yay",
@@ -301,86 +536,251 @@ mod tests {
             ]
         });
 
-        let results = json
-            .get("results")
-            .and_then(Value::as_array)
-            .expect("test JSON should have results array");
-        let mut packages = Vec::new();
+        let packages = parse_info_results(&json);
 
-        for pkg in results {
-            let name = s(pkg, "Name");
-            if name.is_empty() {
-                continue;
-            }
+        assert_eq!(packages.len(), 1);
+        assert_eq!(packages[0].name, "yay");
+        assert_eq!(packages[0].version, "12.3.4-1");
+        assert_eq!(packages[0].depends, vec!["git", "go"]);
+        assert_eq!(packages[0].opt_depends, vec!["sudo: privilege escalation"]);
+        assert_eq!(packages[0].num_votes, Some(100));
+        assert_eq!(packages[0].package_base, "yay");
+    }
 
-            let version = s(pkg, "Version");
-            let description = s(pkg, "Description");
-            let url = s(pkg, "URL");
+    #[test]
+    fn test_find_pkgbase_falls_back_to_name() {
+        let json = json!({
+            "results": [
+                { "Name": "foo-git", "PackageBase": "foo" },
+                { "Name": "bar" }
+            ]
+        });
+        let packages = parse_info_results(&json);
+        assert_eq!(find_pkgbase(&packages, "foo-git").as_deref(), Some("foo"));
+        assert_eq!(find_pkgbase(&packages, "bar").as_deref(), Some("bar"));
+        assert_eq!(find_pkgbase(&packages, "missing"), None);
+    }
 
-            let licenses = arrs(pkg, &["License", "Licenses"]);
-            let groups = arrs(pkg, &["Groups", "Group"]);
-            let provides = arrs(pkg, &["Provides"]);
-            let depends = arrs(pkg, &["Depends"]);
-            let make_depends = arrs(pkg, &["MakeDepends"]);
-            let opt_depends = arrs(pkg, &["OptDepends"]);
-            let conflicts = arrs(pkg, &["Conflicts"]);
-            let replaces = arrs(pkg, &["Replaces"]);
+    #[tokio::test]
+    async fn test_pkgbase_changed_reports_new_base() {
+        use crate::cache::CacheConfigBuilder;
+        use std::time::Duration;
 
-            let maintainer_str = s(pkg, "Maintainer");
-            let maintainer = if maintainer_str.is_empty() {
-                None
-            } else {
-                Some(maintainer_str)
-            };
+        let client = ArchClient::builder()
+            .cache_config(CacheConfigBuilder::new().enable_info(true).build())
+            .build()
+            .expect("client builds");
+        let response = json!({
+            "results": [
+                { "Name": "foo-viewer", "PackageBase": "foo-suite", "Version": "2.0-1" }
+            ]
+        });
+        client
+            .cache()
+            .expect("cache enabled")
+            .set(
+                &cache_key_info(&["foo-viewer"]),
+                &parse_info_results(&response),
+                Duration::from_mins(1),
+            )
+            .expect("cache populated");
+
+        let changed = pkgbase_changed(&client, "foo-viewer", "foo-viewer")
+            .await
+            .expect("info served from cache");
+        assert_eq!(changed.as_deref(), Some("foo-suite"));
+
+        let unchanged = pkgbase_changed(&client, "foo-viewer", "foo-suite")
+            .await
+            .expect("info served from cache");
+        assert_eq!(unchanged, None);
+    }
 
-            let first_submitted = pkg
-                .get("FirstSubmitted")
-                .and_then(Value::as_i64)
-                .filter(|&ts| ts > 0);
-            let last_modified = pkg
-                .get("LastModified")
-                .and_then(Value::as_i64)
-                .filter(|&ts| ts > 0);
+    #[test]
+    fn test_missing_packages_reports_unknown_names() {
+        let json = json!({ "results": [{ "Name": "yay" }] });
+        let packages = parse_info_results(&json);
+        assert!(missing_packages(&["yay"], &packages).is_empty());
+        assert_eq!(
+            missing_packages(&["nope", "yay", "gone", "nope"], &packages),
+            vec!["nope", "gone"]
+        );
 
-            let popularity = pkg.get("Popularity").and_then(Value::as_f64);
-            let num_votes = u64_of(pkg, &["NumVotes", "Votes"]);
+        let error = ArchToolkitError::PackagesNotFound {
+            missing: vec!["nope".to_string(), "gone".to_string()],
+        };
+        assert_eq!(error.to_string(), "Packages not found: nope, gone");
+    }
 
-            let out_of_date = pkg
-                .get("OutOfDate")
-                .and_then(Value::as_i64)
-                .and_then(|ts| u64::try_from(ts).ok())
-                .filter(|&ts| ts > 0);
+    #[tokio::test]
+    /// What: Split a large name list into chunks and merge the chunk results.
+    ///
+    /// Inputs:
+    /// - 400 synthetic names, chunk size 150, answered by `MockAurApi` per chunk.
+    /// - The last chunk also returns a package already returned by the first chunk.
+    ///
+    /// Output:
+    /// - Three requests of 150, 150 and 100 names; 400 unique packages merged in order.
+    async fn test_fetch_in_chunks_merges_and_deduplicates() {
+        use crate::aur::{AurApi, MockAurApi};
+
+        fn details(names: &[&str]) -> Vec<AurPackageDetails> {
+            names
+                .iter()
+                .map(|name| AurPackageDetails {
+                    name: (*name).to_string(),
+                    ..AurPackageDetails::default()
+                })
+                .collect()
+        }
 
-            let orphaned = maintainer.is_none();
+        let owned: Vec<String> = (0..400).map(|i| format!("pkg-{i:03}")).collect();
+        let names: Vec<&str> = owned.iter().map(String::as_str).collect();
+        let mut last_chunk = details(&names[300..]);
+        last_chunk.extend(details(&names[..1]));
+        let mock = MockAurApi::new()
+            .with_info_result(&names[..150], Ok(details(&names[..150])))
+            .with_info_result(&names[150..300], Ok(details(&names[150..300])))
+            .with_info_result(&names[300..], Ok(last_chunk));
+
+        let mut chunk_sizes = Vec::new();
+        let merged = fetch_in_chunks(&names, 150, |chunk| {
+            chunk_sizes.push(chunk.len());
+            let mock = &mock;
+            async move { mock.info(&chunk).await }
+        })
+        .await
+        .expect("every chunk answered by the mock");
 
-            packages.push(AurPackageDetails {
-                name,
-                version,
-                description,
-                url,
-                licenses,
-                groups,
-                provides,
-                depends,
-                make_depends,
-                opt_depends,
-                conflicts,
-                replaces,
-                maintainer,
-                first_submitted,
-                last_modified,
-                popularity,
-                num_votes,
-                out_of_date,
-                orphaned,
-            });
-        }
+        assert_eq!(chunk_sizes, vec![150, 150, 100]);
+        assert_eq!(merged.len(), 400);
+        assert_eq!(merged[0].name, "pkg-000");
+        assert_eq!(merged[399].name, "pkg-399");
+    }
+
+    #[tokio::test]
+    /// What: Invalidating one package drops a cached multi-package info result.
+    ///
+    /// Inputs:
+    /// - Mock RPC answering `info(["a", "b"])`; info caching enabled.
+    ///
+    /// Output:
+    /// - Entry is cached after the call and gone after `invalidate_cache().package("a")`.
+    async fn test_info_cache_invalidated_by_single_package() {
+        use crate::cache::CacheConfigBuilder;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/rpc/v5/info"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "results": [
+                    { "Name": "a", "Version": "1.0-1" },
+                    { "Name": "b", "Version": "2.0-1" }
+                ]
+            })))
+            .mount(&server)
+            .await;
+        let client = ArchClient::builder()
+            .aur_base_url(server.uri())
+            .cache_config(CacheConfigBuilder::new().enable_info(true).build())
+            .build()
+            .expect("client builds");
+
+        info(&client, &["a", "b"]).await.expect("info succeeds");
+        let cache = client.cache().expect("cache enabled");
+        let key = cache.keys().info(&["a", "b"]);
+        assert!(cache.get::<Vec<AurPackageDetails>>(&key).is_some());
+
+        let _ = client.invalidate_cache().package("a");
+        assert!(cache.get::<Vec<AurPackageDetails>>(&key).is_none());
+    }
+
+    #[test]
+    /// What: Populate present dependency/metadata arrays and default absent ones.
+    ///
+    /// Inputs:
+    /// - Info result with `Keywords`, `License`, `Groups`, `Depends` and `OptDepends` only.
+    /// - Serialized details without any array fields.
+    ///
+    /// Output:
+    /// - Present arrays are filled, absent arrays are empty, `has_keyword()` matches keywords.
+    fn test_parse_info_results_arrays_and_keywords() {
+        let json = json!({
+            "results": [{
+                "Name": "yay",
+                "Version": "12.3.4-1",
+                "Keywords": ["aur", "helper", "Pacman"],
+                "License": ["GPL-3.0-or-later"],
+                "Groups": ["aur-helpers"],
+                "Depends": ["pacman>6.1", "git"],
+                "OptDepends": ["sudo: privilege escalation"]
+            }]
+        });
+
+        let packages = parse_info_results(&json);
+        let yay = &packages[0];
+
+        assert_eq!(yay.keywords, vec!["aur", "helper", "Pacman"]);
+        assert_eq!(yay.licenses, vec!["GPL-3.0-or-later"]);
+        assert_eq!(yay.groups, vec!["aur-helpers"]);
+        assert_eq!(yay.depends, vec!["pacman>6.1", "git"]);
+        assert_eq!(yay.opt_depends, vec!["sudo: privilege escalation"]);
+        assert!(yay.make_depends.is_empty());
+        assert!(yay.provides.is_empty());
+        assert!(yay.conflicts.is_empty());
+        assert!(yay.replaces.is_empty());
+        assert!(yay.has_keyword("helper"));
+        assert!(yay.has_keyword("pacman"));
+        assert!(!yay.has_keyword("paru"));
+
+        let details: AurPackageDetails = serde_json::from_value(json!({
+            "name": "yay",
+            "version": "12.3.4-1",
+            "description": "",
+            "url": ""
+        }))
+        .expect("array fields default");
+        assert!(details.keywords.is_empty());
+        assert!(details.licenses.is_empty());
+        assert!(details.opt_depends.is_empty());
+        assert!(!details.has_keyword("aur"));
+
+        let round_trip: AurPackageDetails =
+            serde_json::from_value(serde_json::to_value(yay).expect("serializes"))
+                .expect("deserializes");
+        assert_eq!(round_trip.keywords, yay.keywords);
+    }
+
+    #[test]
+    /// What: Unknown fields and a changed `resultcount` do not affect parsing.
+    ///
+    /// Inputs:
+    /// - Info response with a string `resultcount`, unknown top-level and per-package fields.
+    ///
+    /// Output:
+    /// - The package is parsed as usual.
+    fn test_parse_info_results_ignores_unknown_fields() {
+        let json = json!({
+            "resultcount": "one",
+            "results": [{
+                "Name": "yay",
+                "Version": "12.3.4-1",
+                "Depends": ["git"],
+                "CoMaintainers": ["someone"],
+                "Signatures": { "sha256": "abc" }
+            }],
+            "type": "multiinfo",
+            "version": 6,
+            "deprecation": "v5 is deprecated"
+        });
+        assert!(check_rpc_error(&json).is_ok());
+
+        let packages = parse_info_results(&json);
 
         assert_eq!(packages.len(), 1);
         assert_eq!(packages[0].name, "yay");
-        assert_eq!(packages[0].version, "12.3.4-1");
-        assert_eq!(packages[0].depends, vec!["git", "go"]);
-        assert_eq!(packages[0].opt_depends, vec!["sudo: privilege escalation"]);
-        assert_eq!(packages[0].num_votes, Some(100));
+        assert_eq!(packages[0].depends, vec!["git"]);
     }
 }
@@ -4,9 +4,12 @@ use super::traits::AurApi;
 use crate::error::{ArchToolkitError, Result};
 use crate::types::{AurComment, AurPackage, AurPackageDetails};
 use async_trait::async_trait;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex};
 
+/// Scripted response queue for one mock method.
+type ResponseQueue<T> = Arc<Mutex<VecDeque<Result<T>>>>;
+
 /// What: Mock implementation of `AurApi` for testing.
 ///
 /// Inputs: None (created via `MockAurApi::new()` or builder methods)
@@ -19,6 +22,8 @@ use std::sync::{Arc, Mutex};
 /// - Supports both success and error responses
 /// - Thread-safe via `Arc<Mutex<>>` for internal state
 /// - Builder pattern for easy configuration
+/// - Scripted response sequences can be queued via `MockAurApi::builder()`
+/// - Counts calls per method, see `call_count()`
 /// - Useful for unit testing without hitting real AUR endpoints
 #[derive(Debug)]
 pub struct MockAurApi {
@@ -38,6 +43,16 @@ pub struct MockAurApi {
     default_comments_result: Option<Result<Vec<AurComment>>>,
     /// Default pkgbuild result if no specific package match is found.
     default_pkgbuild_result: Option<Result<String>>,
+    /// Scripted search responses, consumed in order.
+    search_queue: ResponseQueue<Vec<AurPackage>>,
+    /// Scripted info responses, consumed in order.
+    info_queue: ResponseQueue<Vec<AurPackageDetails>>,
+    /// Scripted comments responses, consumed in order.
+    comments_queue: ResponseQueue<Vec<AurComment>>,
+    /// Scripted pkgbuild responses, consumed in order.
+    pkgbuild_queue: ResponseQueue<String>,
+    /// Number of calls per method name.
+    call_counts: Arc<Mutex<HashMap<&'static str, usize>>>,
 }
 
 impl Default for MockAurApi {
@@ -77,16 +92,43 @@ impl MockAurApi {
                 ArchToolkitError::PkgbuildFailed { package, .. } => {
                     ArchToolkitError::Parse(format!("Mock pkgbuild error for package: {package}"))
                 }
+                ArchToolkitError::Runtime(err) => {
+                    ArchToolkitError::Runtime(std::io::Error::new(err.kind(), err.to_string()))
+                }
                 ArchToolkitError::Json(_) => ArchToolkitError::Parse("Mock JSON error".to_string()),
                 ArchToolkitError::Parse(s) => ArchToolkitError::Parse(s.clone()),
+                ArchToolkitError::Http { status, url } => ArchToolkitError::Http {
+                    status: *status,
+                    url: url.clone(),
+                },
+                ArchToolkitError::AurRpc { message } => ArchToolkitError::AurRpc {
+                    message: message.clone(),
+                },
                 ArchToolkitError::RateLimited { retry_after } => ArchToolkitError::RateLimited {
                     retry_after: *retry_after,
                 },
+                ArchToolkitError::ServiceUnavailable => ArchToolkitError::ServiceUnavailable,
+                ArchToolkitError::Cancelled => ArchToolkitError::Cancelled,
+                ArchToolkitError::PacmanUnavailable { command } => {
+                    ArchToolkitError::PacmanUnavailable {
+                        command: command.clone(),
+                    }
+                }
                 ArchToolkitError::PackageNotFound { package } => {
                     ArchToolkitError::PackageNotFound {
                         package: package.clone(),
                     }
                 }
+                ArchToolkitError::PackagesNotFound { missing } => {
+                    ArchToolkitError::PackagesNotFound {
+                        missing: missing.clone(),
+                    }
+                }
+                ArchToolkitError::RemovalBlocked { dependents } => {
+                    ArchToolkitError::RemovalBlocked {
+                        dependents: dependents.clone(),
+                    }
+                }
                 ArchToolkitError::InvalidInput(s) => ArchToolkitError::InvalidInput(s.clone()),
                 ArchToolkitError::EmptyInput { field, message } => ArchToolkitError::EmptyInput {
                     field: field.clone(),
@@ -137,6 +179,103 @@ impl MockAurApi {
             default_info_result: None,
             default_comments_result: None,
             default_pkgbuild_result: None,
+            search_queue: Arc::default(),
+            info_queue: Arc::default(),
+            comments_queue: Arc::default(),
+            pkgbuild_queue: Arc::default(),
+            call_counts: Arc::default(),
+        }
+    }
+
+    /// What: Create a builder for a mock with scripted response sequences.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - `MockAurApiBuilder` with empty queues
+    ///
+    /// Details:
+    /// - Useful to test retry and error paths, e.g. "fail twice, then succeed"
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arch_toolkit::{AurApi, MockAurApi};
+    /// use arch_toolkit::error::ArchToolkitError;
+    ///
+    /// # async fn example() {
+    /// let mock = MockAurApi::builder()
+    ///     .search_responses(vec![
+    ///         Err(ArchToolkitError::ServiceUnavailable),
+    ///         Ok(vec![]),
+    ///     ])
+    ///     .build();
+    /// assert!(mock.search("yay").await.is_err());
+    /// assert!(mock.search("yay").await.is_ok());
+    /// assert_eq!(mock.call_count("search"), 2);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn builder() -> MockAurApiBuilder {
+        MockAurApiBuilder::default()
+    }
+
+    /// What: Number of times a mock method has been called.
+    ///
+    /// Inputs:
+    /// - `method`: Method name (`"search"`, `"info"`, `"comments"`, or `"pkgbuild"`)
+    ///
+    /// Output:
+    /// - Call count, `0` for methods never called or unknown names
+    ///
+    /// Details:
+    /// - Counts every call, whether it was answered by a queue, a keyed result, or a default
+    ///
+    /// # Panics
+    /// - Panics if the internal mutex is poisoned (should never happen in practice)
+    #[must_use]
+    pub fn call_count(&self, method: &str) -> usize {
+        self.call_counts
+            .lock()
+            .expect("MockAurApi mutex should not be poisoned")
+            .get(method)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// What: Increment the call counter of a mock method.
+    ///
+    /// Inputs:
+    /// - `method`: Method name
+    fn record_call(&self, method: &'static str) {
+        *self
+            .call_counts
+            .lock()
+            .expect("MockAurApi mutex should not be poisoned")
+            .entry(method)
+            .or_insert(0) += 1;
+    }
+
+    /// What: Take the next scripted response from a queue.
+    ///
+    /// Inputs:
+    /// - `queue`: Scripted response queue
+    ///
+    /// Output:
+    /// - `Some(result)` if responses are queued, `None` otherwise
+    ///
+    /// Details:
+    /// - Pops responses in order, returning them as-is (network errors keep their kind)
+    /// - The last response is kept and repeated once the queue is exhausted,
+    ///   cloned via `clone_result()`
+    fn next_scripted<T: Clone>(queue: &Mutex<VecDeque<Result<T>>>) -> Option<Result<T>> {
+        let mut queue = queue
+            .lock()
+            .expect("MockAurApi mutex should not be poisoned");
+        if queue.len() > 1 {
+            queue.pop_front()
+        } else {
+            queue.front().map(Self::clone_result)
         }
     }
 
@@ -308,6 +447,103 @@ impl MockAurApi {
     }
 }
 
+/// What: Builder for a `MockAurApi` with scripted response sequences.
+///
+/// Inputs: None (created via `MockAurApi::builder()`)
+///
+/// Output:
+/// - `MockAurApiBuilder` that produces a `MockAurApi` via `build()`
+///
+/// Details:
+/// - Each method has its own queue; calls pop responses in order
+/// - An exhausted queue keeps returning its last response
+/// - Queued responses take precedence over keyed and default results
+#[derive(Debug, Default)]
+pub struct MockAurApiBuilder {
+    /// Scripted search responses.
+    search: VecDeque<Result<Vec<AurPackage>>>,
+    /// Scripted info responses.
+    info: VecDeque<Result<Vec<AurPackageDetails>>>,
+    /// Scripted comments responses.
+    comments: VecDeque<Result<Vec<AurComment>>>,
+    /// Scripted pkgbuild responses.
+    pkgbuild: VecDeque<Result<String>>,
+}
+
+impl MockAurApiBuilder {
+    /// What: Queue responses for `search()`.
+    ///
+    /// Inputs:
+    /// - `responses`: Responses returned by successive calls, in order
+    ///
+    /// Output:
+    /// - `Self` for method chaining
+    #[must_use]
+    pub fn search_responses(mut self, responses: Vec<Result<Vec<AurPackage>>>) -> Self {
+        self.search.extend(responses);
+        self
+    }
+
+    /// What: Queue responses for `info()`.
+    ///
+    /// Inputs:
+    /// - `responses`: Responses returned by successive calls, in order
+    ///
+    /// Output:
+    /// - `Self` for method chaining
+    #[must_use]
+    pub fn info_responses(mut self, responses: Vec<Result<Vec<AurPackageDetails>>>) -> Self {
+        self.info.extend(responses);
+        self
+    }
+
+    /// What: Queue responses for `comments()`.
+    ///
+    /// Inputs:
+    /// - `responses`: Responses returned by successive calls, in order
+    ///
+    /// Output:
+    /// - `Self` for method chaining
+    #[must_use]
+    pub fn comments_responses(mut self, responses: Vec<Result<Vec<AurComment>>>) -> Self {
+        self.comments.extend(responses);
+        self
+    }
+
+    /// What: Queue responses for `pkgbuild()`.
+    ///
+    /// Inputs:
+    /// - `responses`: Responses returned by successive calls, in order
+    ///
+    /// Output:
+    /// - `Self` for method chaining
+    #[must_use]
+    pub fn pkgbuild_responses(mut self, responses: Vec<Result<String>>) -> Self {
+        self.pkgbuild.extend(responses);
+        self
+    }
+
+    /// What: Build the configured mock.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - `MockAurApi` answering from the queued responses
+    ///
+    /// Details:
+    /// - The mock can still be configured further with the `with_*` methods
+    #[must_use]
+    pub fn build(self) -> MockAurApi {
+        MockAurApi {
+            search_queue: Arc::new(Mutex::new(self.search)),
+            info_queue: Arc::new(Mutex::new(self.info)),
+            comments_queue: Arc::new(Mutex::new(self.comments)),
+            pkgbuild_queue: Arc::new(Mutex::new(self.pkgbuild)),
+            ..MockAurApi::new()
+        }
+    }
+}
+
 #[async_trait]
 impl AurApi for MockAurApi {
     /// What: Search for packages in the AUR by name (mock implementation).
@@ -319,10 +555,16 @@ impl AurApi for MockAurApi {
     /// - `Result<Vec<AurPackage>>` containing predefined search results, or an error
     ///
     /// Details:
+    /// - Returns the next scripted response if any are queued
     /// - Returns predefined result for the query if available
     /// - Falls back to default search result if set
     /// - Returns error if no match found and no default is set
     async fn search(&self, query: &str) -> Result<Vec<AurPackage>> {
+        self.record_call("search");
+        if let Some(result) = Self::next_scripted(&self.search_queue) {
+            return result;
+        }
+
         let result = {
             let results = self
                 .search_results
@@ -353,10 +595,16 @@ impl AurApi for MockAurApi {
     /// - `Result<Vec<AurPackageDetails>>` containing predefined package details, or an error
     ///
     /// Details:
+    /// - Returns the next scripted response if any are queued
     /// - Returns predefined result for the sorted package names if available
     /// - Falls back to default info result if set
     /// - Returns error if no match found and no default is set
     async fn info(&self, names: &[&str]) -> Result<Vec<AurPackageDetails>> {
+        self.record_call("info");
+        if let Some(result) = Self::next_scripted(&self.info_queue) {
+            return result;
+        }
+
         let mut sorted_names = names.to_vec();
         sorted_names.sort_unstable();
         let key = sorted_names.join(",");
@@ -391,10 +639,16 @@ impl AurApi for MockAurApi {
     /// - `Result<Vec<AurComment>>` containing predefined comments, or an error
     ///
     /// Details:
+    /// - Returns the next scripted response if any are queued
     /// - Returns predefined result for the package if available
     /// - Falls back to default comments result if set
     /// - Returns error if no match found and no default is set
     async fn comments(&self, pkgname: &str) -> Result<Vec<AurComment>> {
+        self.record_call("comments");
+        if let Some(result) = Self::next_scripted(&self.comments_queue) {
+            return result;
+        }
+
         let result = {
             let results = self
                 .comments_results
@@ -425,10 +679,16 @@ impl AurApi for MockAurApi {
     /// - `Result<String>` containing predefined PKGBUILD content, or an error
     ///
     /// Details:
+    /// - Returns the next scripted response if any are queued
     /// - Returns predefined result for the package if available
     /// - Falls back to default pkgbuild result if set
     /// - Returns error if no match found and no default is set
     async fn pkgbuild(&self, package: &str) -> Result<String> {
+        self.record_call("pkgbuild");
+        if let Some(result) = Self::next_scripted(&self.pkgbuild_queue) {
+            return result;
+        }
+
         let result = {
             let results = self
                 .pkgbuild_results
@@ -471,6 +731,9 @@ mod tests {
                 out_of_date: None,
                 orphaned: false,
                 maintainer: Some("user".to_string()),
+                num_votes: None,
+                last_modified: None,
+                first_submitted: None,
             }]),
         );
 
@@ -515,6 +778,9 @@ mod tests {
             out_of_date: None,
             orphaned: false,
             maintainer: None,
+            num_votes: None,
+            last_modified: None,
+            first_submitted: None,
         }]));
 
         let result = mock.search("any-query").await;
@@ -530,6 +796,7 @@ mod tests {
             &["yay"],
             Ok(vec![AurPackageDetails {
                 name: "yay".to_string(),
+                package_base: "yay".to_string(),
                 version: "12.0.0".to_string(),
                 description: "AUR helper".to_string(),
                 url: "https://github.com/Jguer/yay".to_string(),
@@ -541,6 +808,7 @@ mod tests {
                 opt_depends: vec![],
                 conflicts: vec![],
                 replaces: vec![],
+                keywords: vec![],
                 maintainer: Some("user".to_string()),
                 first_submitted: None,
                 last_modified: None,
@@ -564,6 +832,7 @@ mod tests {
             &["yay", "paru"],
             Ok(vec![AurPackageDetails {
                 name: "yay".to_string(),
+                package_base: "yay".to_string(),
                 version: "12.0.0".to_string(),
                 description: "AUR helper".to_string(),
                 url: String::new(),
@@ -575,6 +844,7 @@ mod tests {
                 opt_depends: vec![],
                 conflicts: vec![],
                 replaces: vec![],
+                keywords: vec![],
                 maintainer: None,
                 first_submitted: None,
                 last_modified: None,
@@ -605,6 +875,9 @@ mod tests {
                 date_url: None,
                 content: "Great package!".to_string(),
                 pinned: false,
+                numeric_id: None,
+                edited: None,
+                deleted: false,
             }]),
         );
 
@@ -615,6 +888,46 @@ mod tests {
         assert_eq!(comments[0].author, "user");
     }
 
+    #[tokio::test]
+    async fn test_mock_comments_many_mixed_results() {
+        let mock = MockAurApi::new()
+            .with_comments_result(
+                "yay",
+                Ok(vec![AurComment {
+                    id: Some("1".to_string()),
+                    author: "user".to_string(),
+                    date: "2024-01-01".to_string(),
+                    date_timestamp: Some(1_704_067_200),
+                    date_url: None,
+                    content: "Great package!".to_string(),
+                    pinned: false,
+                    numeric_id: Some(1),
+                    edited: None,
+                    deleted: false,
+                }]),
+            )
+            .with_comments_result(
+                "gone",
+                Err(ArchToolkitError::PackageNotFound {
+                    package: "gone".to_string(),
+                }),
+            )
+            .with_comments_result("paru", Ok(vec![]));
+
+        let results = mock.comments_many(&["yay", "gone", "paru"]).await;
+
+        let names: Vec<&str> = results.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["yay", "gone", "paru"]);
+        assert_eq!(results[0].1.as_ref().expect("yay succeeds").len(), 1);
+        assert!(matches!(
+            results[1].1,
+            Err(ArchToolkitError::PackageNotFound { ref package }) if package == "gone"
+        ));
+        assert!(results[2].1.as_ref().expect("paru succeeds").is_empty());
+        assert_eq!(mock.call_count("comments"), 3);
+        assert!(mock.comments_many(&[]).await.is_empty());
+    }
+
     #[tokio::test]
     async fn test_mock_pkgbuild_success() {
         let mock = MockAurApi::new()
@@ -625,4 +938,88 @@ mod tests {
         let pkgbuild = result.unwrap();
         assert!(pkgbuild.contains("yay"));
     }
+
+    /// What: Produce a real, retryable network error without external network access.
+    ///
+    /// Output:
+    /// - Connection-refused `ArchToolkitError::Network` from a closed local port
+    async fn connect_error() -> ArchToolkitError {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+        let error = reqwest::Client::new()
+            .get(format!("http://{addr}/"))
+            .send()
+            .await
+            .unwrap_err();
+        ArchToolkitError::Network(error)
+    }
+
+    #[tokio::test]
+    /// What: Pop scripted responses in order and repeat the last one.
+    ///
+    /// Inputs:
+    /// - Pkgbuild queue of an error followed by a success.
+    ///
+    /// Output:
+    /// - Error, then the success for every later call; calls are counted per method.
+    async fn test_mock_builder_scripted_sequence() {
+        let mock = MockAurApi::builder()
+            .pkgbuild_responses(vec![
+                Err(ArchToolkitError::ServiceUnavailable),
+                Ok("pkgname=yay".to_string()),
+            ])
+            .build();
+
+        assert!(matches!(
+            mock.pkgbuild("yay").await,
+            Err(ArchToolkitError::ServiceUnavailable)
+        ));
+        assert_eq!(mock.pkgbuild("yay").await.unwrap(), "pkgname=yay");
+        assert_eq!(mock.pkgbuild("other").await.unwrap(), "pkgname=yay");
+        assert_eq!(mock.call_count("pkgbuild"), 3);
+        assert_eq!(mock.call_count("search"), 0);
+
+        // Methods without a queue fall back to keyed/default results
+        assert!(mock.comments("yay").await.is_err());
+        assert_eq!(mock.call_count("comments"), 1);
+    }
+
+    #[tokio::test]
+    /// What: Drive `retry_with_policy` deterministically with a scripted mock.
+    ///
+    /// Inputs:
+    /// - Search queue failing twice with connection errors, then succeeding.
+    ///
+    /// Output:
+    /// - Retries succeed on the third call; a disabled policy stops after one.
+    async fn test_mock_builder_drives_retry_policy() {
+        use crate::client::{RetryPolicy, retry_with_policy};
+
+        let script = vec![
+            Err(connect_error().await),
+            Err(connect_error().await),
+            Ok(Vec::new()),
+        ];
+        let mock = MockAurApi::builder().search_responses(script).build();
+        let policy = RetryPolicy {
+            initial_delay_ms: 1,
+            jitter_max_ms: 0,
+            ..RetryPolicy::default()
+        };
+        let result = retry_with_policy(&policy, "search", "yay", || mock.search("yay")).await;
+        assert!(result.is_ok());
+        assert_eq!(mock.call_count("search"), 3);
+
+        let mock = MockAurApi::builder()
+            .search_responses(vec![Err(connect_error().await), Ok(Vec::new())])
+            .build();
+        let policy = RetryPolicy {
+            enabled: false,
+            ..policy
+        };
+        let result = retry_with_policy(&policy, "search", "yay", || mock.search("yay")).await;
+        assert!(matches!(result, Err(ArchToolkitError::Network(_))));
+        assert_eq!(mock.call_count("search"), 1);
+    }
 }
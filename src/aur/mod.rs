@@ -3,15 +3,27 @@
 #[cfg(feature = "aur")]
 mod comments;
 #[cfg(feature = "aur")]
+mod commit;
+#[cfg(all(feature = "aur", feature = "deps"))]
+mod diff;
+#[cfg(feature = "aur")]
 mod info;
 #[cfg(feature = "aur")]
 mod mock;
 #[cfg(feature = "aur")]
+mod names;
+#[cfg(feature = "aur")]
 mod pkgbuild;
 #[cfg(feature = "aur")]
 mod search;
 #[cfg(feature = "aur")]
+mod srcinfo;
+#[cfg(feature = "aur")]
 mod traits;
+#[cfg(all(feature = "aur", feature = "deps"))]
+mod updates;
+#[cfg(feature = "aur")]
+mod url;
 #[cfg(feature = "aur")]
 pub mod utils;
 #[cfg(feature = "aur")]
@@ -20,14 +32,30 @@ pub mod validation;
 #[cfg(feature = "aur")]
 use crate::client::ArchClient;
 #[cfg(feature = "aur")]
-use crate::error::Result;
+use crate::error::{ArchToolkitError, Result};
+#[cfg(feature = "aur")]
+use crate::types::{AurComment, AurPackage, AurPackageDetails, SearchPage};
+#[cfg(all(feature = "aur", feature = "deps"))]
+use crate::types::{AurUpdateReport, ParsedPkgbuild};
 #[cfg(feature = "aur")]
-use crate::types::{AurComment, AurPackage, AurPackageDetails};
+use std::future::Future;
+#[cfg(feature = "aur")]
+use std::time::Duration;
+#[cfg(feature = "aur")]
+pub use tokio_util::sync::CancellationToken;
 
+#[cfg(all(feature = "aur", feature = "deps"))]
+pub use diff::diff_pkgbuild;
 #[cfg(feature = "aur")]
-pub use mock::MockAurApi;
+pub use mock::{MockAurApi, MockAurApiBuilder};
+#[cfg(feature = "aur")]
+pub use search::SearchBy;
 #[cfg(feature = "aur")]
 pub use traits::AurApi;
+#[cfg(feature = "aur")]
+pub use url::AUR_BASE_URL;
+#[cfg(feature = "aur")]
+pub use utils::{SortField, filter_new_since, sort_packages};
 
 /// What: Wrapper for AUR operations using an `ArchClient`.
 ///
@@ -39,6 +67,7 @@ pub use traits::AurApi;
 /// - Holds a reference to `ArchClient` to access HTTP client and configuration
 /// - Provides methods: `search()`, `info()`, `comments()`, `pkgbuild()`
 /// - All operations use the client's configured timeout and user agent
+/// - `*_with()` variants take a per-call timeout override and a `CancellationToken`
 /// - Rate limiting is handled automatically
 #[cfg(feature = "aur")]
 #[derive(Debug)]
@@ -64,7 +93,7 @@ impl<'a> Aur<'a> {
         Self { client }
     }
 
-    /// What: Search for packages in the AUR by name.
+    /// What: Search for packages in the AUR by name and description.
     ///
     /// Inputs:
     /// - `query`: Search query string.
@@ -73,7 +102,8 @@ impl<'a> Aur<'a> {
     /// - `Result<Vec<AurPackage>>` containing search results, or an error.
     ///
     /// Details:
-    /// - Uses AUR RPC v5 search endpoint.
+    /// - Uses AUR RPC v5 search endpoint (`by=name-desc`).
+    /// - Same as `search_by(query, SearchBy::NameDesc)`.
     /// - Limits results to 200 packages (AUR default).
     /// - Percent-encodes the query string for URL safety.
     /// - Applies rate limiting for archlinux.org requests.
@@ -81,11 +111,201 @@ impl<'a> Aur<'a> {
     ///
     /// # Errors
     /// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+    /// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
+    /// - Returns `Err(ArchToolkitError::AurRpc)` if the AUR RPC reports an error
     /// - Returns `Err(ArchToolkitError::InvalidInput)` if the URL is not from archlinux.org
+    /// - Returns `Err(ArchToolkitError::ServiceUnavailable)` if the circuit breaker is open
     pub async fn search(&self, query: &str) -> Result<Vec<AurPackage>> {
         search::search(self.client, query).await
     }
 
+    /// What: Search for packages in the AUR by a specific field.
+    ///
+    /// Inputs:
+    /// - `query`: Search query string.
+    /// - `by`: Field to match the query against (maintainer, depends, ...).
+    ///
+    /// Output:
+    /// - `Result<Vec<AurPackage>>` containing search results, or an error.
+    ///
+    /// Details:
+    /// - Uses AUR RPC v5 search endpoint with the given `by` parameter.
+    /// - Limits results to 200 packages (AUR default).
+    /// - Results are cached per query and field.
+    ///
+    /// # Errors
+    /// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+    /// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
+    /// - Returns `Err(ArchToolkitError::AurRpc)` if the AUR RPC reports an error
+    /// - Returns `Err(ArchToolkitError::InvalidInput)` if the URL is not from archlinux.org
+    /// - Returns `Err(ArchToolkitError::ServiceUnavailable)` if the circuit breaker is open
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arch_toolkit::ArchClient;
+    /// use arch_toolkit::aur::SearchBy;
+    ///
+    /// # async fn example() -> arch_toolkit::error::Result<()> {
+    /// let client = ArchClient::new()?;
+    /// let maintained = client.aur().search_by("someuser", SearchBy::Maintainer).await?;
+    /// println!("someuser maintains {} packages", maintained.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn search_by(&self, query: &str, by: SearchBy) -> Result<Vec<AurPackage>> {
+        search::search_by(self.client, query, by).await
+    }
+
+    /// What: Search for packages in the AUR with a timeout override and cancellation.
+    ///
+    /// Inputs:
+    /// - `query`: Search query string.
+    /// - `by`: Field to match the query against.
+    /// - `timeout`: Per-request timeout override (`None` keeps the default timeout).
+    /// - `cancel`: Optional token that aborts the call when cancelled.
+    ///
+    /// Output:
+    /// - `Result<Vec<AurPackage>>` containing search results, or an error.
+    ///
+    /// Details:
+    /// - Same as `search_by()`, with the timeout applied to this call's HTTP requests only.
+    /// - On cancellation the in-flight request is dropped and its rate limiter permit released;
+    ///   a cancelled circuit breaker probe lets the next request probe instead.
+    ///
+    /// # Errors
+    /// - Returns the same errors as `search_by()`
+    /// - Returns `Err(ArchToolkitError::Cancelled)` if `cancel` fires before the call completes
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use arch_toolkit::ArchClient;
+    /// use arch_toolkit::aur::SearchBy;
+    /// use arch_toolkit::aur::CancellationToken;
+    ///
+    /// # async fn example() -> arch_toolkit::error::Result<()> {
+    /// let client = ArchClient::new()?;
+    /// let cancel = CancellationToken::new();
+    /// let results = client
+    ///     .aur()
+    ///     .search_with("yay", SearchBy::Name, Some(Duration::from_secs(5)), Some(&cancel))
+    ///     .await?;
+    /// println!("{} results", results.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn search_with(
+        &self,
+        query: &str,
+        by: SearchBy,
+        timeout: Option<Duration>,
+        cancel: Option<&CancellationToken>,
+    ) -> Result<Vec<AurPackage>> {
+        cancellable(
+            cancel,
+            search::search_by_with(self.client, query, by, timeout),
+        )
+        .await
+    }
+
+    /// What: Fetch one page of AUR search results.
+    ///
+    /// Inputs:
+    /// - `query`: Search query string.
+    /// - `offset`: Index of the first result to return.
+    /// - `limit`: Maximum number of results to return (`0` means all remaining results).
+    ///
+    /// Output:
+    /// - `Result<SearchPage>` with the requested slice and the total result count.
+    ///
+    /// Details:
+    /// - Unlike `search()`, results are not capped at 200.
+    /// - The AUR RPC does not paginate: the full result set is fetched once and cached
+    ///   (when search caching is enabled), and subsequent pages are served from the cache.
+    /// - An `offset` at or beyond the total yields an empty page, not an error.
+    ///
+    /// # Errors
+    /// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+    /// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
+    /// - Returns `Err(ArchToolkitError::AurRpc)` if the AUR RPC reports an error
+    /// - Returns `Err(ArchToolkitError::InvalidInput)` if the URL is not from archlinux.org
+    /// - Returns `Err(ArchToolkitError::ServiceUnavailable)` if the circuit breaker is open
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arch_toolkit::ArchClient;
+    ///
+    /// # async fn example() -> arch_toolkit::error::Result<()> {
+    /// let client = ArchClient::new()?;
+    /// let page = client.aur().search_paginated("python", 0, 50).await?;
+    /// println!("Showing {} of {} results", page.items.len(), page.total);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn search_paginated(
+        &self,
+        query: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Result<SearchPage> {
+        search::search_paginated(self.client, query, offset, limit).await
+    }
+
+    /// What: Search for packages in the AUR, yielding results as a stream.
+    ///
+    /// Inputs:
+    /// - `query`: Search query string.
+    ///
+    /// Output:
+    /// - Stream yielding each `AurPackage` as `Ok`, or a single `Err` if the search fails.
+    ///
+    /// Details:
+    /// - The AUR returns all results at once; the stream slices the (cached) full result.
+    /// - Nothing is fetched until the stream is first polled.
+    /// - Rate limiting and retries apply once, to the single RPC request made when the
+    ///   stream starts, not per yielded item.
+    /// - Requires the `stream` feature.
+    /// - Lets consumers use stream combinators such as `take` or `filter` and stop early.
+    /// - Yields the same packages, in the same order, as `search()`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arch_toolkit::ArchClient;
+    /// use futures_util::StreamExt;
+    ///
+    /// # async fn example() -> arch_toolkit::error::Result<()> {
+    /// let client = ArchClient::new()?;
+    /// let aur = client.aur();
+    /// let mut stream = Box::pin(aur.search_stream("yay").take(5));
+    /// while let Some(pkg) = stream.next().await {
+    ///     println!("{}", pkg?.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "stream")]
+    pub fn search_stream<'s>(
+        &'s self,
+        query: &'s str,
+    ) -> impl futures_util::Stream<Item = Result<AurPackage>> + 's {
+        use futures_util::StreamExt;
+
+        let client = self.client;
+        futures_util::stream::once(async move { search::search(client, query).await }).flat_map(
+            |result| {
+                let items: Vec<Result<AurPackage>> = match result {
+                    Ok(packages) => packages.into_iter().map(Ok).collect(),
+                    Err(e) => vec![Err(e)],
+                };
+                futures_util::stream::iter(items)
+            },
+        )
+    }
+
     /// What: Fetch detailed information for one or more AUR packages.
     ///
     /// Inputs:
@@ -102,11 +322,179 @@ impl<'a> Aur<'a> {
     ///
     /// # Errors
     /// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+    /// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
+    /// - Returns `Err(ArchToolkitError::AurRpc)` if the AUR RPC reports an error
     /// - Returns `Err(ArchToolkitError::InvalidInput)` if the URL is not from archlinux.org
+    /// - Returns `Err(ArchToolkitError::ServiceUnavailable)` if the circuit breaker is open
     pub async fn info(&self, names: &[&str]) -> Result<Vec<AurPackageDetails>> {
         info::info(self.client, names).await
     }
 
+    /// What: Fetch AUR package details with a timeout override and cancellation.
+    ///
+    /// Inputs:
+    /// - `names`: Slice of package names to fetch info for.
+    /// - `timeout`: Per-request timeout override (`None` keeps the default timeout).
+    /// - `cancel`: Optional token that aborts the call when cancelled.
+    ///
+    /// Output:
+    /// - `Result<Vec<AurPackageDetails>>` containing package details, or an error.
+    ///
+    /// Details:
+    /// - Same as `info()`, with the timeout applied to this call's HTTP requests only.
+    /// - On cancellation the in-flight request is dropped and its rate limiter permit released;
+    ///   a cancelled circuit breaker probe lets the next request probe instead.
+    ///
+    /// # Errors
+    /// - Returns the same errors as `info()`
+    /// - Returns `Err(ArchToolkitError::Cancelled)` if `cancel` fires before the call completes
+    pub async fn info_with(
+        &self,
+        names: &[&str],
+        timeout: Option<Duration>,
+        cancel: Option<&CancellationToken>,
+    ) -> Result<Vec<AurPackageDetails>> {
+        cancellable(cancel, info::info_with(self.client, names, timeout)).await
+    }
+
+    /// What: Fetch detailed information for AUR packages, failing on unknown names.
+    ///
+    /// Inputs:
+    /// - `names`: Slice of package names to fetch info for.
+    ///
+    /// Output:
+    /// - `Result<Vec<AurPackageDetails>>` with details for every requested package.
+    ///
+    /// Details:
+    /// - Strict variant of `info()`, which returns an empty or partial vector for unknown names.
+    ///
+    /// # Errors
+    /// - Returns `Err(ArchToolkitError::PackagesNotFound)` listing the names without a result
+    /// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+    /// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
+    /// - Returns `Err(ArchToolkitError::AurRpc)` if the AUR RPC reports an error
+    /// - Returns `Err(ArchToolkitError::InvalidInput)` if the URL is not from archlinux.org
+    /// - Returns `Err(ArchToolkitError::ServiceUnavailable)` if the circuit breaker is open
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arch_toolkit::ArchClient;
+    /// use arch_toolkit::error::ArchToolkitError;
+    ///
+    /// # async fn example() -> arch_toolkit::error::Result<()> {
+    /// let client = ArchClient::new()?;
+    /// match client.aur().info_checked(&["yay", "does-not-exist"]).await {
+    ///     Ok(details) => println!("Found {} packages", details.len()),
+    ///     Err(ArchToolkitError::PackagesNotFound { missing }) => {
+    ///         println!("Not in the AUR: {}", missing.join(", "));
+    ///     }
+    ///     Err(e) => return Err(e),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn info_checked(&self, names: &[&str]) -> Result<Vec<AurPackageDetails>> {
+        info::info_checked(self.client, names).await
+    }
+
+    /// What: Check installed packages for newer versions in the AUR.
+    ///
+    /// Inputs:
+    /// - `installed`: `(name, installed_version)` pairs, e.g. from `index::get_foreign_packages()`
+    ///
+    /// Output:
+    /// - `Result<AurUpdateReport>` with available updates and packages missing from the AUR
+    ///
+    /// Details:
+    /// - AUR analog of `pacman -Qu`: fetches info for all names in one batched `info()` call
+    /// - Reports a package only if the AUR version is newer by `deps::compare_versions()`
+    /// - Installed packages without an AUR entry (deleted or renamed) are listed in `missing`
+    /// - Requires the `deps` feature
+    ///
+    /// # Errors
+    /// - Returns the same errors as `info()`
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arch_toolkit::ArchClient;
+    ///
+    /// # async fn example() -> arch_toolkit::error::Result<()> {
+    /// let client = ArchClient::new()?;
+    /// let report = client.aur().updates(&[("yay", "12.3.5-1"), ("paru", "2.0.3-1")]).await?;
+    /// for update in &report.updates {
+    ///     println!("{} {} -> {}", update.name, update.current, update.available);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "deps")]
+    pub async fn updates(&self, installed: &[(&str, &str)]) -> Result<AurUpdateReport> {
+        updates::updates(self, installed).await
+    }
+
+    /// What: Look up the package base (`pkgbase`) of an AUR package.
+    ///
+    /// Inputs:
+    /// - `name`: Package name.
+    ///
+    /// Output:
+    /// - `Ok(Some(base))` with the current package base; `Ok(None)` if the package is not in the AUR.
+    ///
+    /// Details:
+    /// - Uses the info endpoint (cached if info caching is enabled).
+    /// - Split packages share one package base, which names the AUR git repository.
+    ///
+    /// # Errors
+    /// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+    /// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
+    /// - Returns `Err(ArchToolkitError::AurRpc)` if the AUR RPC reports an error
+    /// - Returns `Err(ArchToolkitError::InvalidPackageName)` if the package name is invalid
+    pub async fn pkgbase_of(&self, name: &str) -> Result<Option<String>> {
+        info::pkgbase_of(self.client, name).await
+    }
+
+    /// What: Detect whether an AUR package's package base changed.
+    ///
+    /// Inputs:
+    /// - `installed_name`: Name of the installed package.
+    /// - `known_base`: Package base recorded locally (e.g., the name of the local git clone).
+    ///
+    /// Output:
+    /// - `Ok(Some(new_base))` if the current package base differs from `known_base`.
+    /// - `Ok(None)` if it is unchanged or the package is no longer in the AUR.
+    ///
+    /// Details:
+    /// - AUR helpers keeping local git clones use this to re-point or re-clone the repository.
+    ///
+    /// # Errors
+    /// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+    /// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
+    /// - Returns `Err(ArchToolkitError::AurRpc)` if the AUR RPC reports an error
+    /// - Returns `Err(ArchToolkitError::InvalidPackageName)` if the package name is invalid
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arch_toolkit::ArchClient;
+    ///
+    /// # async fn example() -> arch_toolkit::error::Result<()> {
+    /// let client = ArchClient::new()?;
+    /// if let Some(new_base) = client.aur().pkgbase_changed("foo-git", "foo").await? {
+    ///     println!("Package base moved to {new_base}; re-clone required");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn pkgbase_changed(
+        &self,
+        installed_name: &str,
+        known_base: &str,
+    ) -> Result<Option<String>> {
+        info::pkgbase_changed(self.client, installed_name, known_base).await
+    }
+
     /// What: Fetch AUR package comments by scraping the AUR package page.
     ///
     /// Inputs:
@@ -124,12 +512,121 @@ impl<'a> Aur<'a> {
     ///
     /// # Errors
     /// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+    /// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
     /// - Returns `Err(ArchToolkitError::InvalidInput)` if the URL is not from archlinux.org
+    /// - Returns `Err(ArchToolkitError::ServiceUnavailable)` if the circuit breaker is open
     /// - Returns `Err(ArchToolkitError::Parse)` if HTML parsing fails
     pub async fn comments(&self, pkgname: &str) -> Result<Vec<AurComment>> {
         comments::comments(self.client, pkgname).await
     }
 
+    /// What: Fetch comments for several AUR packages in one call.
+    ///
+    /// Inputs:
+    /// - `pkgnames`: Package names to fetch comments for.
+    ///
+    /// Output:
+    /// - One `(pkgname, result)` pair per requested package, in input order.
+    ///
+    /// Details:
+    /// - Packages are fetched one after another, honoring the archlinux.org rate limiter
+    ///   instead of flooding the server.
+    /// - A failing package does not fail the batch: its pair carries the same error
+    ///   `comments()` would return.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example() -> arch_toolkit::error::Result<()> {
+    /// let client = arch_toolkit::ArchClient::new()?;
+    /// for (pkgname, result) in client.aur().comments_many(&["yay", "paru"]).await {
+    ///     match result {
+    ///         Ok(comments) => println!("{pkgname}: {} comments", comments.len()),
+    ///         Err(e) => eprintln!("{pkgname}: {e}"),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn comments_many(&self, pkgnames: &[&str]) -> Vec<(String, Result<Vec<AurComment>>)> {
+        utils::fetch_each(pkgnames, |pkgname| comments::comments(self.client, pkgname)).await
+    }
+
+    /// What: Fetch AUR package comments with a timeout override and cancellation.
+    ///
+    /// Inputs:
+    /// - `pkgname`: Package name to fetch comments for.
+    /// - `timeout`: Per-request timeout override (`None` keeps the default timeout).
+    /// - `cancel`: Optional token that aborts the call when cancelled.
+    ///
+    /// Output:
+    /// - `Result<Vec<AurComment>>` with parsed comments; `Err` on failure.
+    ///
+    /// Details:
+    /// - Same as `comments()`, with the timeout applied to this call's HTTP requests only.
+    /// - On cancellation the in-flight request is dropped and its rate limiter permit released;
+    ///   a cancelled circuit breaker probe lets the next request probe instead.
+    ///
+    /// # Errors
+    /// - Returns the same errors as `comments()`
+    /// - Returns `Err(ArchToolkitError::Cancelled)` if `cancel` fires before the call completes
+    pub async fn comments_with(
+        &self,
+        pkgname: &str,
+        timeout: Option<Duration>,
+        cancel: Option<&CancellationToken>,
+    ) -> Result<Vec<AurComment>> {
+        cancellable(
+            cancel,
+            comments::comments_with(self.client, pkgname, timeout),
+        )
+        .await
+    }
+
+    /// What: Fetch one page of AUR package comments.
+    ///
+    /// Inputs:
+    /// - `pkgname`: Package name to fetch comments for.
+    /// - `page`: Zero-based page index; page `0` holds the latest comments.
+    ///
+    /// Output:
+    /// - `Result<Vec<AurComment>>` with the page's comments (pinned first, then newest first).
+    ///
+    /// Details:
+    /// - Requests 10 comments per page via the `O`/`PP` query parameters.
+    /// - Pinned comments are shown on every page.
+    /// - Applies rate limiting per page request; pages are not cached.
+    ///
+    /// # Errors
+    /// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+    /// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
+    /// - Returns `Err(ArchToolkitError::Parse)` if HTML parsing fails
+    /// - Returns `Err(ArchToolkitError::InvalidPackageName)` if package name is invalid
+    pub async fn comments_paginated(&self, pkgname: &str, page: usize) -> Result<Vec<AurComment>> {
+        comments::comments_paginated(self.client, pkgname, page).await
+    }
+
+    /// What: Fetch all AUR package comments by walking comment pages.
+    ///
+    /// Inputs:
+    /// - `pkgname`: Package name to fetch comments for.
+    ///
+    /// Output:
+    /// - `Result<Vec<AurComment>>` with all comments (pinned first, then newest first).
+    ///
+    /// Details:
+    /// - Requests pages until one yields no new comments.
+    /// - Comments are deduplicated by ID across pages.
+    /// - Stops at `max_comments` comments (client setting, default 1000).
+    ///
+    /// # Errors
+    /// - Returns `Err(ArchToolkitError::Network)` if any page request fails
+    /// - Returns `Err(ArchToolkitError::Parse)` if HTML parsing fails
+    /// - Returns `Err(ArchToolkitError::InvalidPackageName)` if package name is invalid
+    pub async fn all_comments(&self, pkgname: &str) -> Result<Vec<AurComment>> {
+        comments::all_comments(self.client, pkgname).await
+    }
+
     /// What: Fetch PKGBUILD content for an AUR package.
     ///
     /// Inputs:
@@ -146,11 +643,189 @@ impl<'a> Aur<'a> {
     ///
     /// # Errors
     /// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+    /// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
     /// - Returns `Err(ArchToolkitError::InvalidInput)` if the URL is not from archlinux.org
+    /// - Returns `Err(ArchToolkitError::ServiceUnavailable)` if the circuit breaker is open
     /// - Returns `Err(ArchToolkitError::Parse)` if rate limiter mutex is poisoned
     pub async fn pkgbuild(&self, package: &str) -> Result<String> {
         pkgbuild::pkgbuild(self.client, package).await
     }
+
+    /// What: Fetch PKGBUILD content with a timeout override and cancellation.
+    ///
+    /// Inputs:
+    /// - `package`: Package name to fetch PKGBUILD for.
+    /// - `timeout`: Per-request timeout override (`None` keeps the default timeout).
+    /// - `cancel`: Optional token that aborts the call when cancelled.
+    ///
+    /// Output:
+    /// - `Result<String>` with PKGBUILD text; `Err` on network or lookup failure.
+    ///
+    /// Details:
+    /// - Same as `pkgbuild()`, with the timeout applied to this call's HTTP requests only.
+    /// - On cancellation the in-flight request is dropped and its rate limiter permit released;
+    ///   a cancelled circuit breaker probe lets the next request probe instead.
+    ///
+    /// # Errors
+    /// - Returns the same errors as `pkgbuild()`
+    /// - Returns `Err(ArchToolkitError::Cancelled)` if `cancel` fires before the call completes
+    pub async fn pkgbuild_with(
+        &self,
+        package: &str,
+        timeout: Option<Duration>,
+        cancel: Option<&CancellationToken>,
+    ) -> Result<String> {
+        cancellable(
+            cancel,
+            pkgbuild::pkgbuild_with(self.client, package, timeout),
+        )
+        .await
+    }
+
+    /// What: Fetch and parse the PKGBUILD of an AUR package.
+    ///
+    /// Inputs:
+    /// - `package`: Package name to fetch PKGBUILD for.
+    ///
+    /// Output:
+    /// - `Result<ParsedPkgbuild>` with names, version, dependency arrays, sources and checksums.
+    ///
+    /// Details:
+    /// - Parses the result of `pkgbuild()` with `deps::parse_pkgbuild()`
+    /// - The parsed form is cached separately when pkgbuild caching is enabled
+    /// - Requires the `deps` feature
+    ///
+    /// # Errors
+    /// - Returns the same errors as `pkgbuild()`
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arch_toolkit::ArchClient;
+    ///
+    /// # async fn example() -> arch_toolkit::error::Result<()> {
+    /// let client = ArchClient::new()?;
+    /// let parsed = client.aur().pkgbuild_parsed("yay").await?;
+    /// println!("{} {}-{}", parsed.pkgname.join(" "), parsed.pkgver, parsed.pkgrel);
+    /// println!("makedepends: {:?}", parsed.makedepends);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "deps")]
+    pub async fn pkgbuild_parsed(&self, package: &str) -> Result<ParsedPkgbuild> {
+        pkgbuild::pkgbuild_parsed(self.client, package).await
+    }
+
+    /// What: Fetch `.SRCINFO` content for an AUR package.
+    ///
+    /// Inputs:
+    /// - `package`: Package base name to fetch `.SRCINFO` for.
+    ///
+    /// Output:
+    /// - `Result<String>` with raw `.SRCINFO` text.
+    ///
+    /// Details:
+    /// - Uses the client's rate limiting, retry policy (pkgbuild flag), and cache.
+    /// - Parse the result with `arch_toolkit::deps::parse_srcinfo` (requires `deps`).
+    ///
+    /// # Errors
+    /// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+    /// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
+    /// - Returns `Err(ArchToolkitError::Parse)` if the response is not a `.SRCINFO`
+    /// - Returns `Err(ArchToolkitError::InvalidPackageName)` if package name is invalid
+    pub async fn srcinfo(&self, package: &str) -> Result<String> {
+        srcinfo::srcinfo(self.client, package).await
+    }
+
+    /// What: Fetch the id of the latest commit of an AUR package's git repository.
+    ///
+    /// Inputs:
+    /// - `package`: Package base name.
+    ///
+    /// Output:
+    /// - `Result<String>` with the 40-character hex commit id of the package's `aur.git` ref.
+    ///
+    /// Details:
+    /// - Reads only the header of `/cgit/aur.git/patch/?h={package}`.
+    /// - Uses the client's rate limiting and retry policy (pkgbuild flag); never cached.
+    /// - Store the id next to a cached PKGBUILD and compare it later (e.g. via
+    ///   `ResolverConfig::pkgbuild_hash` and `ResolverConfig::aur_commit`) to detect
+    ///   stale copies.
+    ///
+    /// # Errors
+    /// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+    /// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
+    /// - Returns `Err(ArchToolkitError::Parse)` if no commit header is found
+    /// - Returns `Err(ArchToolkitError::InvalidPackageName)` if package name is invalid
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arch_toolkit::ArchClient;
+    ///
+    /// # async fn example() -> arch_toolkit::error::Result<()> {
+    /// let client = ArchClient::new()?;
+    /// let commit = client.aur().pkgbuild_commit("yay").await?;
+    /// println!("yay is at {commit}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn pkgbuild_commit(&self, package: &str) -> Result<String> {
+        commit::pkgbuild_commit(self.client, package).await
+    }
+
+    /// What: Fetch the names of all packages currently in the AUR.
+    ///
+    /// Inputs:
+    /// - None
+    ///
+    /// Output:
+    /// - `Result<Vec<String>>` with every AUR package name; `Err` on network or decode failure.
+    ///
+    /// Details:
+    /// - Downloads `https://aur.archlinux.org/packages.gz`
+    /// - Decompresses the response body as a stream, keeping memory bounded
+    /// - Decompressed output is capped at 64 MiB
+    /// - Applies rate limiting for archlinux.org requests
+    ///
+    /// # Errors
+    /// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+    /// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
+    /// - Returns `Err(ArchToolkitError::InvalidInput)` if the URL is not from archlinux.org
+    /// - Returns `Err(ArchToolkitError::ServiceUnavailable)` if the circuit breaker is open
+    /// - Returns `Err(ArchToolkitError::Parse)` if decompression fails or exceeds the size limit
+    pub async fn all_package_names(&self) -> Result<Vec<String>> {
+        names::all_package_names(self.client).await
+    }
+}
+
+/// What: Run an AUR operation that can be aborted through a cancellation token.
+///
+/// Inputs:
+/// - `cancel`: Optional token; `None` runs `operation` to completion.
+/// - `operation`: The operation future.
+///
+/// Output:
+/// - The operation's result, or `Err(ArchToolkitError::Cancelled)` once the token fires.
+///
+/// Details:
+/// - Cancellation drops `operation`, which aborts the HTTP request and releases any
+///   rate limiter permit it holds.
+/// - If the operation was the half-open circuit breaker probe, dropping its permit frees the
+///   probe slot, so the circuit does not stay half-open with no probe in flight.
+#[cfg(feature = "aur")]
+async fn cancellable<T>(
+    cancel: Option<&CancellationToken>,
+    operation: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    let Some(cancel) = cancel else {
+        return operation.await;
+    };
+    tokio::select! {
+        biased;
+        () = cancel.cancelled() => Err(ArchToolkitError::Cancelled),
+        result = operation => result,
+    }
 }
 
 #[cfg(feature = "aur")]
@@ -173,6 +848,27 @@ impl AurApi for Aur<'_> {
         search::search(self.client, query).await
     }
 
+    /// What: Fetch one page of AUR search results.
+    ///
+    /// Inputs:
+    /// - `query`: Search query string
+    /// - `offset`: Index of the first result to return
+    /// - `limit`: Maximum number of results to return (`0` means all remaining results)
+    ///
+    /// Output:
+    /// - `Result<SearchPage>` with the requested slice and the total result count
+    ///
+    /// Details:
+    /// - Delegates to the underlying search module function
+    async fn search_paginated(
+        &self,
+        query: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Result<SearchPage> {
+        search::search_paginated(self.client, query, offset, limit).await
+    }
+
     /// What: Fetch detailed information for one or more AUR packages.
     ///
     /// Inputs:
@@ -215,3 +911,110 @@ impl AurApi for Aur<'_> {
         pkgbuild::pkgbuild(self.client, package).await
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "aur")]
+mod cancellation_tests {
+    use super::*;
+    use crate::client::{admit_request, local_limiter, make_half_open};
+    use std::time::Instant;
+
+    #[tokio::test]
+    /// What: Cancel the half-open circuit breaker probe.
+    ///
+    /// Inputs:
+    /// - Local limiter whose circuit is half-open, and an operation holding the probe that
+    ///   never completes.
+    ///
+    /// Output:
+    /// - The operation fails with `Cancelled` and the next request is admitted as the probe.
+    async fn test_cancelled_probe_does_not_wedge_circuit() {
+        let limiter = local_limiter();
+        let now = Instant::now();
+        make_half_open(limiter, now);
+
+        let cancel = CancellationToken::new();
+        let probe = admit_request(limiter, now).expect("probe admitted");
+        let operation = async move {
+            let _probe = probe;
+            std::future::pending::<Result<()>>().await
+        };
+        let trigger = cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            trigger.cancel();
+        });
+        let result = cancellable(Some(&cancel), operation).await;
+
+        assert!(matches!(result, Err(ArchToolkitError::Cancelled)));
+        assert!(
+            admit_request(limiter, now).is_some(),
+            "a new probe runs after cancellation"
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "stream")]
+mod tests {
+    use super::*;
+    use crate::cache::{CacheConfigBuilder, cache_key_search};
+    use futures_util::StreamExt;
+    use std::time::Duration;
+
+    fn cached_package(name: &str) -> AurPackage {
+        AurPackage {
+            name: name.to_string(),
+            version: "1.0-1".to_string(),
+            description: format!("{name} description"),
+            popularity: Some(1.5),
+            out_of_date: None,
+            orphaned: false,
+            maintainer: Some("someone".to_string()),
+            num_votes: None,
+            last_modified: None,
+            first_submitted: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_stream_matches_search() {
+        let client = ArchClient::builder()
+            .cache_config(CacheConfigBuilder::new().enable_search(true).build())
+            .build()
+            .expect("client builds");
+        let packages = vec![
+            cached_package("yay"),
+            cached_package("yay-bin"),
+            cached_package("yay-git"),
+        ];
+        client
+            .cache()
+            .expect("cache enabled")
+            .set(&cache_key_search("yay"), &packages, Duration::from_mins(1))
+            .expect("cache populated");
+
+        let aur = client.aur();
+        let expected = aur.search("yay").await.expect("search succeeds");
+        let streamed: Vec<AurPackage> = aur
+            .search_stream("yay")
+            .map(|item| item.expect("stream item succeeds"))
+            .collect()
+            .await;
+
+        let names = |pkgs: &[AurPackage]| pkgs.iter().map(|p| p.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&streamed), names(&expected));
+        assert_eq!(names(&streamed), vec!["yay", "yay-bin", "yay-git"]);
+
+        let first_two: Vec<Result<AurPackage>> = aur.search_stream("yay").take(2).collect().await;
+        assert_eq!(first_two.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_search_stream_yields_error() {
+        let client = ArchClient::new().expect("client builds");
+        let items: Vec<Result<AurPackage>> = client.aur().search_stream("").collect().await;
+        assert_eq!(items.len(), 1);
+        assert!(items[0].is_err());
+    }
+}
@@ -0,0 +1,281 @@
+//! AUR package name list (`packages.gz`) functionality.
+
+use crate::client::{ArchClient, check_status, reset_archlinux_backoff};
+use crate::error::{ArchToolkitError, Result};
+use flate2::write::GzDecoder;
+use std::io::{self, Write};
+use tracing::{debug, warn};
+
+/// Maximum decompressed size accepted for gzip-compressed package lists (64 MiB).
+///
+/// The AUR `packages.gz` list decompresses to a few megabytes; anything far beyond that
+/// indicates a misbehaving server or a decompression bomb.
+pub const MAX_DECOMPRESSED_BYTES: usize = 64 * 1024 * 1024;
+
+/// What: Fetch the names of all packages currently in the AUR.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for the request.
+///
+/// Output:
+/// - `Result<Vec<String>>` with every package name listed in `packages.gz`.
+///
+/// Details:
+/// - Downloads `https://aur.archlinux.org/packages.gz`.
+/// - Decompresses the body as a stream, chunk by chunk, so the compressed body is never
+///   buffered in full.
+/// - Decompressed output is capped at `MAX_DECOMPRESSED_BYTES`.
+/// - Comment lines (starting with `#`) and blank lines are skipped.
+/// - Applies rate limiting for archlinux.org requests.
+///
+/// # Errors
+/// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+/// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
+/// - Returns `Err(ArchToolkitError::InvalidInput)` if the URL is not from archlinux.org
+/// - Returns `Err(ArchToolkitError::Parse)` if decompression fails or exceeds the size limit
+/// - Returns `Err(ArchToolkitError::ServiceUnavailable)` if the circuit breaker is open
+pub async fn all_package_names(client: &ArchClient) -> Result<Vec<String>> {
+    let url = format!("{}/packages.gz", client.aur_base_url());
+
+    debug!(url = %url, "fetching AUR package name list");
+
+    // Apply rate limiting for archlinux.org
+    let _permit = if client.is_aur_url(&url) {
+        client.archlinux_permit().await?
+    } else {
+        return Err(ArchToolkitError::InvalidInput(format!(
+            "Unexpected URL domain: {url}"
+        )));
+    };
+
+    let result = fetch_package_names(client, &url).await;
+    client.record_archlinux_result(&result);
+    let names = result?;
+
+    debug!(count = names.len(), "fetched AUR package name list");
+    Ok(names)
+}
+
+/// What: Download and decode the package name list.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for the request.
+/// - `url`: URL of `packages.gz`.
+///
+/// Output:
+/// - `Result<Vec<String>>` with the parsed package names.
+///
+/// Details:
+/// - Caller must hold the archlinux.org permit.
+async fn fetch_package_names(client: &ArchClient, url: &str) -> Result<Vec<String>> {
+    let mut response = match client.http_client().get(url).send().await {
+        Ok(resp) => {
+            reset_archlinux_backoff();
+            resp
+        }
+        Err(e) => {
+            warn!(error = %e, "AUR package name list request failed");
+            return Err(ArchToolkitError::Network(e));
+        }
+    };
+    response = check_status(response).inspect_err(|e| {
+        warn!(error = %e, "AUR package name list returned non-success status");
+    })?;
+
+    let mut decoder = GzipStreamDecoder::new(MAX_DECOMPRESSED_BYTES);
+    while let Some(chunk) = response.chunk().await.map_err(ArchToolkitError::Network)? {
+        decoder.push(&chunk)?;
+    }
+    Ok(parse_package_names(&decoder.finish()?))
+}
+
+/// What: Parse a newline-separated package name list.
+///
+/// Inputs:
+/// - `data`: Decompressed list contents.
+///
+/// Output:
+/// - Vector of trimmed package names.
+///
+/// Details:
+/// - Skips blank lines and `#` comment lines (the AUR list starts with a header comment).
+fn parse_package_names(data: &[u8]) -> Vec<String> {
+    String::from_utf8_lossy(data)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// What: Incremental gzip decoder fed with response body chunks.
+///
+/// Inputs:
+/// - Compressed chunks pushed in order via `push()`.
+///
+/// Output:
+/// - Full decompressed payload returned by `finish()`.
+///
+/// Details:
+/// - Only the decompressed output is held in memory; compressed chunks are dropped after use.
+/// - Fails as soon as the decompressed output would exceed the configured limit.
+pub struct GzipStreamDecoder {
+    /// Underlying decoder writing into a size-bounded buffer.
+    decoder: GzDecoder<BoundedBuffer>,
+}
+
+impl GzipStreamDecoder {
+    /// What: Create a decoder with a decompressed size limit.
+    ///
+    /// Inputs:
+    /// - `limit`: Maximum number of decompressed bytes accepted.
+    ///
+    /// Output:
+    /// - Empty `GzipStreamDecoder`.
+    ///
+    /// Details:
+    /// - Use `MAX_DECOMPRESSED_BYTES` unless a tighter bound is known.
+    #[must_use]
+    pub fn new(limit: usize) -> Self {
+        Self {
+            decoder: GzDecoder::new(BoundedBuffer {
+                data: Vec::new(),
+                limit,
+            }),
+        }
+    }
+
+    /// What: Feed the next compressed chunk to the decoder.
+    ///
+    /// Inputs:
+    /// - `chunk`: Compressed bytes, in stream order.
+    ///
+    /// Output:
+    /// - `Ok(())` when the chunk was consumed.
+    ///
+    /// # Errors
+    /// - Returns `Err(ArchToolkitError::Parse)` on corrupt gzip data or when the size limit is exceeded
+    pub fn push(&mut self, chunk: &[u8]) -> Result<()> {
+        self.decoder
+            .write_all(chunk)
+            .map_err(|e| ArchToolkitError::Parse(format!("gzip decompression failed: {e}")))
+    }
+
+    /// What: Finish decoding and return the decompressed payload.
+    ///
+    /// Inputs:
+    /// - None (consumes the decoder).
+    ///
+    /// Output:
+    /// - Decompressed bytes.
+    ///
+    /// # Errors
+    /// - Returns `Err(ArchToolkitError::Parse)` if the stream is truncated or the size limit is exceeded
+    pub fn finish(self) -> Result<Vec<u8>> {
+        self.decoder
+            .finish()
+            .map(|buffer| buffer.data)
+            .map_err(|e| ArchToolkitError::Parse(format!("gzip decompression failed: {e}")))
+    }
+}
+
+/// What: In-memory writer that rejects output beyond a byte limit.
+///
+/// Inputs:
+/// - Bytes written by the gzip decoder.
+///
+/// Output:
+/// - Accumulated bytes in `data`.
+///
+/// Details:
+/// - Returns an `io::Error` once `limit` would be exceeded, aborting decompression.
+struct BoundedBuffer {
+    /// Decompressed bytes collected so far.
+    data: Vec<u8>,
+    /// Maximum number of bytes accepted.
+    limit: usize,
+}
+
+impl Write for BoundedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.data.len() + buf.len() > self.limit {
+            return Err(io::Error::other(format!(
+                "decompressed size exceeds limit of {} bytes",
+                self.limit
+            )));
+        }
+        self.data.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use std::fmt::Write as _;
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).expect("gzip encode");
+        encoder.finish().expect("gzip finish")
+    }
+
+    fn fixture() -> String {
+        let mut list = String::from("# AUR package list, generated on Fri, 16 Oct 2026\n");
+        for i in 0..5000 {
+            let _ = writeln!(list, "package-{i}");
+        }
+        list
+    }
+
+    #[test]
+    fn test_gzip_stream_decoder_chunked_input() {
+        let plain = fixture();
+        let compressed = gzip(plain.as_bytes());
+
+        let mut decoder = GzipStreamDecoder::new(MAX_DECOMPRESSED_BYTES);
+        for chunk in compressed.chunks(7) {
+            decoder.push(chunk).expect("chunk accepted");
+        }
+        let output = decoder.finish().expect("stream complete");
+        assert_eq!(output, plain.as_bytes());
+
+        let names = parse_package_names(&output);
+        assert_eq!(names.len(), 5000);
+        assert_eq!(names.first().map(String::as_str), Some("package-0"));
+        assert_eq!(names.last().map(String::as_str), Some("package-4999"));
+    }
+
+    #[test]
+    fn test_gzip_stream_decoder_enforces_limit() {
+        let compressed = gzip(fixture().as_bytes());
+        let mut decoder = GzipStreamDecoder::new(1024);
+        let result = compressed
+            .chunks(64)
+            .try_for_each(|chunk| decoder.push(chunk));
+        let result = result.and_then(|()| decoder.finish().map(|_| ()));
+        assert!(matches!(result, Err(ArchToolkitError::Parse(_))));
+    }
+
+    #[test]
+    fn test_gzip_stream_decoder_truncated_input() {
+        let compressed = gzip(fixture().as_bytes());
+        let mut decoder = GzipStreamDecoder::new(MAX_DECOMPRESSED_BYTES);
+        decoder
+            .push(&compressed[..compressed.len() / 2])
+            .expect("partial chunk accepted");
+        assert!(decoder.finish().is_err());
+    }
+
+    #[test]
+    fn test_parse_package_names_skips_comments_and_blanks() {
+        let names = parse_package_names(b"# header\nyay\n\n  paru  \n");
+        assert_eq!(names, vec!["yay", "paru"]);
+    }
+}
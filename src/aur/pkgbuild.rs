@@ -1,17 +1,18 @@
 //! PKGBUILD fetching functionality.
 
-use crate::aur::utils::percent_encode;
+use crate::aur::utils::{instrumented, percent_encode};
 use crate::aur::validation::validate_package_name;
-use crate::cache::cache_key_pkgbuild;
-use crate::client::{
-    ArchClient, extract_retry_after, is_archlinux_url, rate_limit_archlinux,
-    reset_archlinux_backoff, retry_with_policy,
-};
+use crate::cache::CachedText;
+use crate::client::{ArchClient, check_status, extract_retry_after, reset_archlinux_backoff};
+#[cfg(feature = "deps")]
+use crate::deps::parse_pkgbuild;
 use crate::error::{ArchToolkitError, Result};
+#[cfg(feature = "deps")]
+use crate::types::ParsedPkgbuild;
 use reqwest::Client;
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
-use tracing::debug;
+use tracing::{debug, info_span};
 
 /// Rate limiter for PKGBUILD requests to avoid overwhelming AUR servers.
 ///
@@ -19,6 +20,28 @@ use tracing::debug;
 static PKGBUILD_RATE_LIMITER: Mutex<Option<Instant>> = Mutex::new(None);
 /// Minimum interval between PKGBUILD requests in milliseconds.
 const PKGBUILD_MIN_INTERVAL_MS: u64 = 200;
+/// Default timeout for PKGBUILD requests.
+const PKGBUILD_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long an expired PKGBUILD stays cached for `ETag` revalidation (24 hours).
+const PKGBUILD_STALE_RETENTION: Duration = Duration::from_hours(24);
+
+/// What: Outcome of a (possibly conditional) PKGBUILD request.
+///
+/// Inputs: None (returned by `perform_pkgbuild_request()`)
+///
+/// Output:
+/// - Either a new body with its `ETag`, or a `304 Not Modified` answer
+enum PkgbuildResponse {
+    /// Full response body.
+    Modified {
+        /// PKGBUILD text.
+        body: String,
+        /// `ETag` header, if present.
+        etag: Option<String>,
+    },
+    /// The cached body is still current.
+    NotModified,
+}
 
 /// What: Fetch PKGBUILD content for an AUR package.
 ///
@@ -36,32 +59,77 @@ const PKGBUILD_MIN_INTERVAL_MS: u64 = 200;
 /// - Returns raw PKGBUILD text
 /// - Uses retry policy if enabled for pkgbuild operations.
 /// - Checks cache before making network request if caching is enabled.
+/// - Caches the `ETag` alongside the body; once the TTL expires, sends `If-None-Match` and
+///   on `304 Not Modified` refreshes the TTL and returns the cached body.
 ///
 /// # Errors
 /// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+/// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
 /// - Returns `Err(ArchToolkitError::InvalidInput)` if the URL is not from archlinux.org
+/// - Returns `Err(ArchToolkitError::ServiceUnavailable)` if the circuit breaker is open
 /// - Returns `Err(ArchToolkitError::Parse)` if rate limiter mutex is poisoned
 /// - Returns `Err(ArchToolkitError::EmptyInput)` if package name is empty and strict mode is enabled
 /// - Returns `Err(ArchToolkitError::InvalidPackageName)` if package name is invalid
 /// - Returns `Err(ArchToolkitError::InputTooLong)` if package name exceeds maximum length
 pub async fn pkgbuild(client: &ArchClient, package: &str) -> Result<String> {
+    pkgbuild_with(client, package, None).await
+}
+
+/// What: Fetch PKGBUILD content with a per-request timeout override.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+/// - `package`: Package name to fetch PKGBUILD for.
+/// - `timeout`: Per-request timeout override (`None` uses the 10 second default).
+///
+/// Output:
+/// - `Result<String>` with PKGBUILD text when available; `Err` on network or lookup failure.
+///
+/// Details:
+/// - Same as `pkgbuild()`; the timeout applies to each HTTP attempt of this call only.
+/// - Runs inside an `aur_pkgbuild` tracing span with `operation` and `package` fields.
+///
+/// # Errors
+/// - Returns the same errors as `pkgbuild()`
+pub async fn pkgbuild_with(
+    client: &ArchClient,
+    package: &str,
+    timeout: Option<Duration>,
+) -> Result<String> {
+    let span = info_span!("aur_pkgbuild", operation = "pkgbuild", package = %package);
+    let operation = run_pkgbuild(client, package, timeout);
+    instrumented(client, "pkgbuild", package, span, operation).await
+}
+
+/// What: Validate, serve from cache or fetch a PKGBUILD; body of `pkgbuild_with()`.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+/// - `package`: Package name to fetch PKGBUILD for.
+/// - `timeout`: Per-request timeout override (`None` uses the 10 second default).
+///
+/// Output:
+/// - `Result<String>` with PKGBUILD text, or an error.
+async fn run_pkgbuild(
+    client: &ArchClient,
+    package: &str,
+    timeout: Option<Duration>,
+) -> Result<String> {
     // Validate input
     let validation_config = client.validation_config();
     validate_package_name(package, Some(validation_config))?;
     // Check cache if enabled
-    if let Some(cache_config) = client.cache_config()
-        && cache_config.enable_pkgbuild
-        && let Some(cache) = client.cache()
+    let stale = cached_pkgbuild(client, package);
+    if let Some(cached) = &stale
+        && cached.is_fresh()
     {
-        let cache_key = cache_key_pkgbuild(package);
-        if let Some(cached) = cache.get::<String>(&cache_key) {
-            debug!(package = %package, "cache hit for pkgbuild");
-            return Ok(cached);
-        }
+        debug!(package = %package, "cache hit for pkgbuild");
+        return Ok(cached.body.clone());
     }
 
     let url = format!(
-        "https://aur.archlinux.org/cgit/aur.git/plain/PKGBUILD?h={}",
+        "{}/cgit/aur.git/plain/PKGBUILD?h={}",
+        client.aur_base_url(),
         percent_encode(package)
     );
 
@@ -101,61 +169,153 @@ pub async fn pkgbuild(client: &ArchClient, package: &str) -> Result<String> {
     }
 
     // Apply rate limiting for archlinux.org
-    let _permit = if is_archlinux_url(&url) {
-        rate_limit_archlinux().await
+    let _permit = if client.is_aur_url(&url) {
+        client.archlinux_permit().await?
     } else {
         return Err(ArchToolkitError::InvalidInput(format!(
             "Unexpected URL domain: {url}"
         )));
     };
 
-    let retry_policy = client.retry_policy();
     let http_client = client.http_client();
 
     // Wrap the request in retry logic if enabled
-    let text = if retry_policy.enabled && retry_policy.retry_pkgbuild {
-        retry_with_policy(retry_policy, "pkgbuild", package, || async {
-            perform_pkgbuild_request(http_client, &url, package).await
+    let etag = stale.as_ref().and_then(|cached| cached.etag.as_deref());
+    let response = client
+        .execute_with_retry("pkgbuild", package, || async {
+            perform_pkgbuild_request(http_client, &url, package, etag, timeout).await
         })
-        .await?
-    } else {
-        perform_pkgbuild_request(http_client, &url, package).await?
-    };
+        .await?;
 
-    debug!(package = %package, len = text.len(), "PKGBUILD fetched successfully");
+    let (text, etag) = match (response, stale) {
+        (PkgbuildResponse::Modified { body, etag }, _) => {
+            debug!(package = %package, len = body.len(), "PKGBUILD fetched successfully");
+            (body, etag)
+        }
+        (PkgbuildResponse::NotModified, Some(cached)) => {
+            debug!(package = %package, "PKGBUILD not modified, reusing cached body");
+            (cached.body, cached.etag)
+        }
+        (PkgbuildResponse::NotModified, None) => {
+            return Err(ArchToolkitError::Parse(format!(
+                "unexpected 304 Not Modified for PKGBUILD of {package}"
+            )));
+        }
+    };
 
     // Store in cache if enabled
     if let Some(cache_config) = client.cache_config()
         && cache_config.enable_pkgbuild
         && let Some(cache) = client.cache()
     {
-        let cache_key = cache_key_pkgbuild(package);
-        let _ = cache.set(&cache_key, &text, cache_config.pkgbuild_ttl);
+        let cache_key = cache.keys().pkgbuild(package);
+        let ttl = cache_config.pkgbuild_ttl;
+        let entry = CachedText::new(text.clone(), etag, ttl);
+        let _ = cache.set(
+            &cache_key,
+            &entry,
+            ttl.saturating_add(PKGBUILD_STALE_RETENTION),
+        );
     }
 
     Ok(text)
 }
 
+/// What: Fetch and parse the PKGBUILD of an AUR package.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+/// - `package`: Package name to fetch PKGBUILD for.
+///
+/// Output:
+/// - `Result<ParsedPkgbuild>` with the parsed fields, or an error.
+///
+/// Details:
+/// - Fetches the text with `pkgbuild()` and parses it with `deps::parse_pkgbuild()`
+/// - The parsed form is cached separately (pkgbuild cache flag and TTL), so repeated calls
+///   skip both the request and the parsing
+///
+/// # Errors
+/// - Returns the same errors as `pkgbuild()`
+#[cfg(feature = "deps")]
+pub async fn pkgbuild_parsed(client: &ArchClient, package: &str) -> Result<ParsedPkgbuild> {
+    let validation_config = client.validation_config();
+    validate_package_name(package, Some(validation_config))?;
+    if let Some(cache_config) = client.cache_config()
+        && cache_config.enable_pkgbuild
+        && let Some(cache) = client.cache()
+        && let Some(cached) = cache.get::<ParsedPkgbuild>(&cache.keys().pkgbuild_parsed(package))
+    {
+        debug!(package = %package, "cache hit for parsed pkgbuild");
+        return Ok(cached);
+    }
+
+    let text = pkgbuild(client, package).await?;
+    let parsed = parse_pkgbuild(&text);
+
+    if let Some(cache_config) = client.cache_config()
+        && cache_config.enable_pkgbuild
+        && let Some(cache) = client.cache()
+    {
+        let cache_key = cache.keys().pkgbuild_parsed(package);
+        let _ = cache.set(&cache_key, &parsed, cache_config.pkgbuild_ttl);
+    }
+
+    Ok(parsed)
+}
+
+/// What: Look up a cached PKGBUILD, including entries past their TTL.
+///
+/// Inputs:
+/// - `client`: `ArchClient` whose cache is consulted.
+/// - `package`: Package name.
+///
+/// Output:
+/// - Cached entry if caching is enabled and an entry exists, fresh or stale.
+///
+/// Details:
+/// - Reports the lookup to the client's observer; only fresh entries count as hits.
+fn cached_pkgbuild(client: &ArchClient, package: &str) -> Option<CachedText> {
+    let cache_config = client.cache_config()?;
+    if !cache_config.enable_pkgbuild {
+        return None;
+    }
+    let cache = client.cache()?;
+    let cached = cache.get::<CachedText>(&cache.keys().pkgbuild(package));
+    let hit = cached.as_ref().is_some_and(CachedText::is_fresh);
+    client.observer().on_cache("pkgbuild", hit);
+    cached
+}
+
 /// What: Perform the actual PKGBUILD request without retry logic.
 ///
 /// Inputs:
 /// - `client`: HTTP client to use for requests.
 /// - `url`: URL to request.
+/// - `package`: Package name for error context.
+/// - `etag`: Cached `ETag` to send as `If-None-Match`, if any.
+/// - `timeout`: Per-request timeout override (`None` uses the 10 second default).
 ///
 /// Output:
-/// - `Result<String>` containing PKGBUILD text, or an error.
+/// - `Result<PkgbuildResponse>` with the body or a not-modified answer, or an error.
 ///
 /// Details:
 /// - Internal helper function that performs the HTTP request
 /// - Used by both retry and non-retry code paths
-async fn perform_pkgbuild_request(client: &Client, url: &str, package: &str) -> Result<String> {
+async fn perform_pkgbuild_request(
+    client: &Client,
+    url: &str,
+    package: &str,
+    etag: Option<&str>,
+    timeout: Option<Duration>,
+) -> Result<PkgbuildResponse> {
+    let mut request = client.get(url).timeout(timeout.unwrap_or(PKGBUILD_TIMEOUT));
+    if let Some(etag) = etag {
+        request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+    }
+
     // Fetch with timeout
-    let response = match client
-        .get(url)
-        .timeout(Duration::from_secs(10))
-        .send()
-        .await
-    {
+    let response = match request.send().await {
         Ok(resp) => {
             reset_archlinux_backoff();
             resp
@@ -169,13 +329,18 @@ async fn perform_pkgbuild_request(client: &Client, url: &str, package: &str) ->
     // Check for Retry-After header before consuming response
     let _retry_after = extract_retry_after(&response);
 
-    let response = match response.error_for_status() {
-        Ok(resp) => resp,
-        Err(e) => {
-            debug!(error = %e, package = %package, "PKGBUILD returned non-success status");
-            return Err(ArchToolkitError::pkgbuild_failed(package, e));
-        }
-    };
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+        return Ok(PkgbuildResponse::NotModified);
+    }
+    let etag = response
+        .headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(ToString::to_string);
+
+    let response = check_status(response).inspect_err(|e| {
+        debug!(error = %e, package = %package, "PKGBUILD returned non-success status");
+    })?;
 
     let text = match response.text().await {
         Ok(text) => text,
@@ -185,7 +350,7 @@ async fn perform_pkgbuild_request(client: &Client, url: &str, package: &str) ->
         }
     };
 
-    Ok(text)
+    Ok(PkgbuildResponse::Modified { body: text, etag })
 }
 
 #[cfg(test)]
@@ -218,4 +383,215 @@ mod tests {
             "Error message should indicate pkgbuild operation: {error_msg}"
         );
     }
+
+    #[tokio::test]
+    /// What: Revalidate an expired cached PKGBUILD with `If-None-Match`.
+    ///
+    /// Inputs:
+    /// - Mock server returning the PKGBUILD with an `ETag`, then `304` for that `ETag`.
+    /// - Memory-only cache with a zero TTL so the first entry is immediately stale.
+    ///
+    /// Output:
+    /// - Both calls return the same body; the second uses a conditional request.
+    async fn test_pkgbuild_revalidates_with_etag() {
+        use crate::cache::CacheConfigBuilder;
+        use crate::client::ArchClient;
+        use std::time::Duration;
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/cgit/aur.git/plain/PKGBUILD"))
+            .and(header("If-None-Match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/cgit/aur.git/plain/PKGBUILD"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"v1\"")
+                    .set_body_string("pkgname=foo\n"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ArchClient::builder()
+            .aur_base_url(server.uri())
+            .cache_config(
+                CacheConfigBuilder::new()
+                    .enable_pkgbuild(true)
+                    .pkgbuild_ttl(Duration::ZERO)
+                    .build(),
+            )
+            .build()
+            .expect("client builds");
+
+        let first = super::pkgbuild(&client, "foo")
+            .await
+            .expect("initial fetch succeeds");
+        let second = super::pkgbuild(&client, "foo")
+            .await
+            .expect("revalidation succeeds");
+        assert_eq!(first, "pkgname=foo\n");
+        assert_eq!(second, first);
+    }
+
+    #[cfg(feature = "deps")]
+    #[tokio::test]
+    /// What: Fetch a parsed PKGBUILD and serve repeated calls from the parsed cache.
+    ///
+    /// Inputs:
+    /// - Mock server answering exactly one PKGBUILD request.
+    /// - Client with pkgbuild caching enabled.
+    ///
+    /// Output:
+    /// - Both calls return the same parsed fields; only one request reaches the server.
+    async fn test_pkgbuild_parsed_is_cached() {
+        use crate::cache::CacheConfigBuilder;
+        use crate::client::ArchClient;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/cgit/aur.git/plain/PKGBUILD"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("pkgname=parsed\npkgver=1.0\npkgrel=2\ndepends=('glibc')\n"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ArchClient::builder()
+            .aur_base_url(server.uri())
+            .cache_config(CacheConfigBuilder::new().enable_pkgbuild(true).build())
+            .build()
+            .expect("client builds");
+
+        let first = client
+            .aur()
+            .pkgbuild_parsed("parsed")
+            .await
+            .expect("fetch succeeds");
+        assert_eq!(first.pkgname, ["parsed"]);
+        assert_eq!(first.pkgver, "1.0");
+        assert_eq!(first.pkgrel, "2");
+        assert_eq!(first.depends, ["glibc"]);
+
+        let second = client
+            .aur()
+            .pkgbuild_parsed("parsed")
+            .await
+            .expect("cached fetch succeeds");
+        assert_eq!(second, first);
+    }
+
+    #[tokio::test]
+    /// What: Cancel a slow PKGBUILD request and verify the rate limiter permit is released.
+    ///
+    /// Inputs:
+    /// - Mock server delaying `slow` by 10 seconds and answering `fast` immediately.
+    /// - Cancellation token fired 100ms after the slow request starts.
+    ///
+    /// Output:
+    /// - The slow call fails with `Cancelled` long before the delay; the next call succeeds.
+    async fn test_pkgbuild_with_cancellation_releases_permit() {
+        use crate::aur::CancellationToken;
+        use crate::client::ArchClient;
+        use std::time::{Duration, Instant};
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/cgit/aur.git/plain/PKGBUILD"))
+            .and(query_param("h", "slow"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("pkgname=slow\n")
+                    .set_delay(Duration::from_secs(10)),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/cgit/aur.git/plain/PKGBUILD"))
+            .and(query_param("h", "fast"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("pkgname=fast\n"))
+            .mount(&server)
+            .await;
+
+        let client = ArchClient::builder()
+            .aur_base_url(server.uri())
+            .retry_enabled(false)
+            .build()
+            .expect("client builds");
+        let aur = client.aur();
+
+        let cancel = CancellationToken::new();
+        let trigger = cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            trigger.cancel();
+        });
+
+        let started = Instant::now();
+        let result = aur.pkgbuild_with("slow", None, Some(&cancel)).await;
+        assert!(matches!(result, Err(ArchToolkitError::Cancelled)));
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        let fast = tokio::time::timeout(
+            Duration::from_secs(30),
+            aur.pkgbuild_with("fast", None, None),
+        )
+        .await
+        .expect("permit was released")
+        .expect("fast fetch succeeds");
+        assert_eq!(fast, "pkgname=fast\n");
+    }
+
+    #[tokio::test]
+    /// What: Apply a per-call timeout override to a slow PKGBUILD request.
+    ///
+    /// Inputs:
+    /// - Mock server delaying the response by 10 seconds; 200ms timeout override.
+    ///
+    /// Output:
+    /// - The call fails with a network error well before the default 10 second timeout.
+    async fn test_pkgbuild_with_timeout_override() {
+        use crate::client::ArchClient;
+        use std::time::{Duration, Instant};
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/cgit/aur.git/plain/PKGBUILD"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("pkgname=timeout\n")
+                    .set_delay(Duration::from_secs(10)),
+            )
+            .mount(&server)
+            .await;
+
+        let client = ArchClient::builder()
+            .aur_base_url(server.uri())
+            .retry_enabled(false)
+            .build()
+            .expect("client builds");
+
+        let started = Instant::now();
+        let result =
+            super::pkgbuild_with(&client, "timeout", Some(Duration::from_millis(200))).await;
+        assert!(matches!(
+            result,
+            Err(ArchToolkitError::PkgbuildFailed { .. })
+        ));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
 }
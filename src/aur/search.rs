@@ -1,42 +1,176 @@
 //! AUR search functionality.
 
-use crate::aur::utils::{percent_encode, s};
+use crate::aur::url::{AUR_RPC_VERSION, build_rpc_url};
+use crate::aur::utils::{check_rpc_error, instrumented, s, u64_of, with_timeout};
 use crate::aur::validation::validate_search_query;
-use crate::cache::cache_key_search;
-use crate::client::{
-    ArchClient, extract_retry_after, is_archlinux_url, rate_limit_archlinux,
-    reset_archlinux_backoff, retry_with_policy,
-};
+use crate::client::{ArchClient, check_status, extract_retry_after, reset_archlinux_backoff};
 use crate::error::{ArchToolkitError, Result};
-use crate::types::AurPackage;
+use crate::types::{AurPackage, SearchPage};
 use reqwest::Client;
 use serde_json::Value;
-use tracing::{debug, warn};
+use std::time::Duration;
+use tracing::{debug, info_span, warn};
 
-/// What: Search for packages in the AUR by name.
+/// Maximum number of results returned by `search()` (AUR default).
+const MAX_SEARCH_RESULTS: usize = 200;
+
+/// What: Field the AUR RPC search matches the query against.
+///
+/// Inputs:
+/// - Passed to `search_by()` to select the RPC `by` parameter.
+///
+/// Output:
+/// - Determines the `by` value sent to the AUR RPC search endpoint.
+///
+/// Details:
+/// - `NameDesc` is the default and matches both package names and descriptions.
+/// - Dependency fields (`Depends`, `MakeDepends`, ...) match package names exactly.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SearchBy {
+    /// Match package names only (`name`).
+    Name,
+    /// Match package names and descriptions (`name-desc`).
+    #[default]
+    NameDesc,
+    /// Match the maintainer username (`maintainer`).
+    Maintainer,
+    /// Match packages depending on the query (`depends`).
+    Depends,
+    /// Match packages with the query as a make dependency (`makedepends`).
+    MakeDepends,
+    /// Match packages with the query as an optional dependency (`optdepends`).
+    OptDepends,
+    /// Match packages providing the query (`provides`).
+    Provides,
+    /// Match packages conflicting with the query (`conflicts`).
+    Conflicts,
+    /// Match package keywords (`keywords`).
+    Keywords,
+}
+
+impl SearchBy {
+    /// What: Get the AUR RPC `by` parameter value.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - Parameter value as expected by the AUR RPC (e.g., `"name-desc"`).
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::NameDesc => "name-desc",
+            Self::Maintainer => "maintainer",
+            Self::Depends => "depends",
+            Self::MakeDepends => "makedepends",
+            Self::OptDepends => "optdepends",
+            Self::Provides => "provides",
+            Self::Conflicts => "conflicts",
+            Self::Keywords => "keywords",
+        }
+    }
+}
+
+/// What: Search for packages in the AUR by name and description.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+/// - `query`: Search query string.
+///
+/// Output:
+/// - `Result<Vec<AurPackage>>` containing search results, or an error.
+///
+/// Details:
+/// - Equivalent to `search_by(client, query, SearchBy::NameDesc)`.
+///
+/// # Errors
+/// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+/// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
+/// - Returns `Err(ArchToolkitError::AurRpc)` if the AUR RPC reports an error
+/// - Returns `Err(ArchToolkitError::InvalidInput)` if the URL is not from archlinux.org
+/// - Returns `Err(ArchToolkitError::ServiceUnavailable)` if the circuit breaker is open
+/// - Returns `Err(ArchToolkitError::EmptyInput)` if query is empty and strict mode is enabled
+/// - Returns `Err(ArchToolkitError::InputTooLong)` if query exceeds maximum length
+pub async fn search(client: &ArchClient, query: &str) -> Result<Vec<AurPackage>> {
+    search_by(client, query, SearchBy::NameDesc).await
+}
+
+/// What: Search for packages in the AUR by a specific field.
 ///
 /// Inputs:
 /// - `client`: `ArchClient` to use for requests.
 /// - `query`: Search query string.
+/// - `by`: Field to match the query against.
 ///
 /// Output:
 /// - `Result<Vec<AurPackage>>` containing search results, or an error.
 ///
 /// Details:
-/// - Uses AUR RPC v5 search endpoint.
+/// - Uses AUR RPC v5 search endpoint with the `by` parameter.
 /// - Limits results to 200 packages (AUR default).
 /// - Percent-encodes the query string for URL safety.
 /// - Applies rate limiting for archlinux.org requests.
 /// - Returns empty vector if no results found (not an error).
 /// - Uses retry policy if enabled for search operations.
-/// - Checks cache before making network request if caching is enabled.
+/// - Checks cache before making network request if caching is enabled; the cache key
+///   includes the search field.
 ///
 /// # Errors
 /// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+/// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
+/// - Returns `Err(ArchToolkitError::AurRpc)` if the AUR RPC reports an error
 /// - Returns `Err(ArchToolkitError::InvalidInput)` if the URL is not from archlinux.org
+/// - Returns `Err(ArchToolkitError::ServiceUnavailable)` if the circuit breaker is open
 /// - Returns `Err(ArchToolkitError::EmptyInput)` if query is empty and strict mode is enabled
 /// - Returns `Err(ArchToolkitError::InputTooLong)` if query exceeds maximum length
-pub async fn search(client: &ArchClient, query: &str) -> Result<Vec<AurPackage>> {
+pub async fn search_by(client: &ArchClient, query: &str, by: SearchBy) -> Result<Vec<AurPackage>> {
+    search_by_with(client, query, by, None).await
+}
+
+/// What: Search for packages in the AUR with a per-request timeout override.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+/// - `query`: Search query string.
+/// - `by`: Field to match the query against.
+/// - `timeout`: Per-request timeout override (`None` uses the client timeout).
+///
+/// Output:
+/// - `Result<Vec<AurPackage>>` containing search results, or an error.
+///
+/// Details:
+/// - Same as `search_by()`; the timeout applies to each HTTP attempt of this call only.
+/// - Runs inside an `aur_search` tracing span with `operation`, `query` and `by` fields.
+///
+/// # Errors
+/// - Returns the same errors as `search_by()`
+pub async fn search_by_with(
+    client: &ArchClient,
+    query: &str,
+    by: SearchBy,
+    timeout: Option<Duration>,
+) -> Result<Vec<AurPackage>> {
+    let span = info_span!("aur_search", operation = "search", query = %query, by = by.as_str());
+    let operation = run_search_by(client, query, by, timeout);
+    instrumented(client, "search", query, span, operation).await
+}
+
+/// What: Validate, serve from cache or fetch a search; body of `search_by_with()`.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+/// - `query`: Search query string.
+/// - `by`: Field to match the query against.
+/// - `timeout`: Per-request timeout override.
+///
+/// Output:
+/// - `Result<Vec<AurPackage>>` containing search results, or an error.
+async fn run_search_by(
+    client: &ArchClient,
+    query: &str,
+    by: SearchBy,
+    timeout: Option<Duration>,
+) -> Result<Vec<AurPackage>> {
     // Validate input
     let validation_config = client.validation_config();
     let trimmed_query = validate_search_query(query, Some(validation_config))?;
@@ -51,21 +185,145 @@ pub async fn search(client: &ArchClient, query: &str) -> Result<Vec<AurPackage>>
         && cache_config.enable_search
         && let Some(cache) = client.cache()
     {
-        let cache_key = cache_key_search(trimmed_query);
-        if let Some(cached) = cache.get::<Vec<AurPackage>>(&cache_key) {
-            debug!(query = trimmed_query, "cache hit for search");
+        let cache_key = cache.keys().search_by(trimmed_query, by);
+        let cached = cache.get::<Vec<AurPackage>>(&cache_key);
+        client.observer().on_cache("search", cached.is_some());
+        if let Some(cached) = cached {
+            debug!(
+                query = trimmed_query,
+                by = by.as_str(),
+                "cache hit for search"
+            );
             return Ok(cached);
         }
     }
 
-    let encoded_query = percent_encode(trimmed_query);
-    let url = format!("https://aur.archlinux.org/rpc/v5/search?by=name&arg={encoded_query}");
+    let mut result = fetch_search_results(client, trimmed_query, by, timeout).await?;
+    result.truncate(MAX_SEARCH_RESULTS);
+
+    // Store in cache if enabled
+    if let Some(cache_config) = client.cache_config()
+        && cache_config.enable_search
+        && let Some(cache) = client.cache()
+    {
+        let cache_key = cache.keys().search_by(trimmed_query, by);
+        let _ = cache.set(&cache_key, &result, cache_config.search_ttl);
+    }
+
+    Ok(result)
+}
+
+/// What: Fetch one page of AUR search results.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+/// - `query`: Search query string.
+/// - `offset`: Index of the first result to return.
+/// - `limit`: Maximum number of results to return (`0` means all remaining results).
+///
+/// Output:
+/// - `Result<SearchPage>` with the requested slice and the total result count.
+///
+/// Details:
+/// - The AUR RPC v5 does not paginate, so the full, uncapped result set is fetched once
+///   and cached under `CacheKeys::search_all()`; later pages are served from the cache.
+/// - Caching follows the search settings (`enable_search`, `search_ttl`).
+/// - An `offset` at or beyond the total yields an empty page, not an error.
+///
+/// # Errors
+/// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+/// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
+/// - Returns `Err(ArchToolkitError::AurRpc)` if the AUR RPC reports an error
+/// - Returns `Err(ArchToolkitError::InvalidInput)` if the URL is not from archlinux.org
+/// - Returns `Err(ArchToolkitError::ServiceUnavailable)` if the circuit breaker is open
+/// - Returns `Err(ArchToolkitError::EmptyInput)` if query is empty and strict mode is enabled
+/// - Returns `Err(ArchToolkitError::InputTooLong)` if query exceeds maximum length
+pub async fn search_paginated(
+    client: &ArchClient,
+    query: &str,
+    offset: usize,
+    limit: usize,
+) -> Result<SearchPage> {
+    let results = search_all(client, query).await?;
+    Ok(SearchPage::from_results(&results, offset, limit))
+}
+
+/// What: Fetch the full, uncapped search result set.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+/// - `query`: Search query string.
+///
+/// Output:
+/// - `Result<Vec<AurPackage>>` with every result returned by the RPC.
+///
+/// Details:
+/// - Checks and populates the cache under `CacheKeys::search_all()` if search caching is enabled.
+async fn search_all(client: &ArchClient, query: &str) -> Result<Vec<AurPackage>> {
+    let validation_config = client.validation_config();
+    let trimmed_query = validate_search_query(query, Some(validation_config))?;
 
-    debug!(query = trimmed_query, url = %url, "searching AUR");
+    if trimmed_query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    if let Some(cache_config) = client.cache_config()
+        && cache_config.enable_search
+        && let Some(cache) = client.cache()
+    {
+        let cache_key = cache.keys().search_all(trimmed_query);
+        let cached = cache.get::<Vec<AurPackage>>(&cache_key);
+        client.observer().on_cache("search", cached.is_some());
+        if let Some(cached) = cached {
+            debug!(query = trimmed_query, "cache hit for full search results");
+            return Ok(cached);
+        }
+    }
+
+    let result = fetch_search_results(client, trimmed_query, SearchBy::NameDesc, None).await?;
+
+    if let Some(cache_config) = client.cache_config()
+        && cache_config.enable_search
+        && let Some(cache) = client.cache()
+    {
+        let cache_key = cache.keys().search_all(trimmed_query);
+        let _ = cache.set(&cache_key, &result, cache_config.search_ttl);
+    }
+
+    Ok(result)
+}
+
+/// What: Run the search request for an already validated query.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+/// - `trimmed_query`: Validated, trimmed search query.
+/// - `by`: Field to match the query against.
+/// - `timeout`: Per-request timeout override (`None` uses the client timeout).
+///
+/// Output:
+/// - `Result<Vec<AurPackage>>` with all results returned by the RPC.
+///
+/// Details:
+/// - Applies rate limiting and the retry policy; does not touch the cache.
+async fn fetch_search_results(
+    client: &ArchClient,
+    trimmed_query: &str,
+    by: SearchBy,
+    timeout: Option<Duration>,
+) -> Result<Vec<AurPackage>> {
+    let url = build_rpc_url(
+        client.aur_base_url(),
+        AUR_RPC_VERSION,
+        "search",
+        &[("by", by.as_str()), ("arg", trimmed_query)],
+    );
+
+    debug!(query = trimmed_query, by = by.as_str(), url = %url, "searching AUR");
 
     // Apply rate limiting for archlinux.org
-    let _permit = if is_archlinux_url(&url) {
-        rate_limit_archlinux().await
+    let _permit = if client.is_aur_url(&url) {
+        client.archlinux_permit().await?
     } else {
         // For non-archlinux.org URLs, we don't need rate limiting
         // This shouldn't happen for AUR search, but handle gracefully
@@ -74,29 +332,14 @@ pub async fn search(client: &ArchClient, query: &str) -> Result<Vec<AurPackage>>
         )));
     };
 
-    let retry_policy = client.retry_policy();
     let http_client = client.http_client();
 
     // Wrap the request in retry logic if enabled
-    let result = if retry_policy.enabled && retry_policy.retry_search {
-        retry_with_policy(retry_policy, "search", trimmed_query, || async {
-            perform_search_request(http_client, &url, trimmed_query).await
+    client
+        .execute_with_retry("search", trimmed_query, || async {
+            perform_search_request(http_client, &url, trimmed_query, timeout).await
         })
         .await
-    } else {
-        perform_search_request(http_client, &url, trimmed_query).await
-    }?;
-
-    // Store in cache if enabled
-    if let Some(cache_config) = client.cache_config()
-        && cache_config.enable_search
-        && let Some(cache) = client.cache()
-    {
-        let cache_key = cache_key_search(trimmed_query);
-        let _ = cache.set(&cache_key, &result, cache_config.search_ttl);
-    }
-
-    Ok(result)
 }
 
 /// What: Perform the actual search request without retry logic.
@@ -105,6 +348,7 @@ pub async fn search(client: &ArchClient, query: &str) -> Result<Vec<AurPackage>>
 /// - `client`: HTTP client to use for requests.
 /// - `url`: URL to request.
 /// - `query`: Search query for error context.
+/// - `timeout`: Per-request timeout override.
 ///
 /// Output:
 /// - `Result<Vec<AurPackage>>` containing search results, or an error.
@@ -116,8 +360,9 @@ async fn perform_search_request(
     client: &Client,
     url: &str,
     query: &str,
+    timeout: Option<Duration>,
 ) -> Result<Vec<AurPackage>> {
-    let response = match client.get(url).send().await {
+    let response = match with_timeout(client.get(url), timeout).send().await {
         Ok(resp) => {
             reset_archlinux_backoff();
             resp
@@ -131,15 +376,9 @@ async fn perform_search_request(
     // Check for Retry-After header before consuming response
     let _retry_after = extract_retry_after(&response);
 
-    let response = match response.error_for_status() {
-        Ok(resp) => resp,
-        Err(e) => {
-            warn!(error = %e, query = %query, "AUR search returned non-success status");
-            // If we have retry_after, we could use it, but error_for_status consumes the response
-            // For now, the retry logic will handle exponential backoff
-            return Err(ArchToolkitError::search_failed(query, e));
-        }
-    };
+    let response = check_status(response).inspect_err(|e| {
+        warn!(error = %e, query = %query, "AUR search returned non-success status");
+    })?;
 
     let json: Value = match response.json().await {
         Ok(json) => json,
@@ -150,56 +389,79 @@ async fn perform_search_request(
             return Err(ArchToolkitError::search_failed(query, e));
         }
     };
+    check_rpc_error(&json).inspect_err(|e| {
+        warn!(error = %e, query = %query, "AUR search returned an RPC error");
+    })?;
 
-    let mut packages = Vec::new();
+    let packages: Vec<AurPackage> = json
+        .get("results")
+        .and_then(Value::as_array)
+        .map(|results| results.iter().filter_map(parse_search_result).collect())
+        .unwrap_or_default();
 
-    if let Some(results) = json.get("results").and_then(Value::as_array) {
-        for pkg in results.iter().take(200) {
-            let name = s(pkg, "Name");
-            if name.is_empty() {
-                continue;
-            }
+    debug!(count = packages.len(), "AUR search completed");
 
-            let version = s(pkg, "Version");
-            let description = s(pkg, "Description");
-            let popularity = pkg.get("Popularity").and_then(Value::as_f64);
-
-            // Extract OutOfDate timestamp (i64 or null)
-            let out_of_date = pkg
-                .get("OutOfDate")
-                .and_then(Value::as_i64)
-                .and_then(|ts| u64::try_from(ts).ok())
-                .filter(|&ts| ts > 0);
-
-            // Extract Maintainer and determine if orphaned (empty or null means orphaned)
-            let maintainer_str = s(pkg, "Maintainer");
-            let maintainer = if maintainer_str.is_empty() {
-                None
-            } else {
-                Some(maintainer_str)
-            };
-            let orphaned = maintainer.is_none();
-
-            packages.push(AurPackage {
-                name,
-                version,
-                description,
-                popularity,
-                out_of_date,
-                orphaned,
-                maintainer,
-            });
-        }
+    Ok(packages)
+}
+
+/// What: Convert one AUR RPC search result object into an `AurPackage`.
+///
+/// Inputs:
+/// - `pkg`: JSON object from the RPC `results` array.
+///
+/// Output:
+/// - `Some(AurPackage)`, or `None` if the entry has no name.
+///
+/// Details:
+/// - `OutOfDate`, `LastModified` and `FirstSubmitted` must be positive timestamps; `null` or `0`
+///   map to `None`.
+/// - An empty or `null` `Maintainer` marks the package as orphaned.
+fn parse_search_result(pkg: &Value) -> Option<AurPackage> {
+    let name = s(pkg, "Name");
+    if name.is_empty() {
+        return None;
     }
 
-    debug!(count = packages.len(), "AUR search completed");
+    // Extract OutOfDate timestamp (i64 or null)
+    let out_of_date = pkg
+        .get("OutOfDate")
+        .and_then(Value::as_i64)
+        .filter(|&ts| ts > 0);
 
-    Ok(packages)
+    // Extract Maintainer and determine if orphaned (empty or null means orphaned)
+    let maintainer_str = s(pkg, "Maintainer");
+    let maintainer = if maintainer_str.is_empty() {
+        None
+    } else {
+        Some(maintainer_str)
+    };
+    let orphaned = maintainer.is_none();
+
+    Some(AurPackage {
+        name,
+        version: s(pkg, "Version"),
+        description: s(pkg, "Description"),
+        popularity: pkg.get("Popularity").and_then(Value::as_f64),
+        out_of_date,
+        orphaned,
+        maintainer,
+        num_votes: u64_of(pkg, &["NumVotes"]),
+        last_modified: pkg
+            .get("LastModified")
+            .and_then(Value::as_i64)
+            .filter(|&ts| ts > 0),
+        first_submitted: pkg
+            .get("FirstSubmitted")
+            .and_then(Value::as_i64)
+            .filter(|&ts| ts > 0),
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::aur::url::AUR_BASE_URL;
+    use crate::cache::{cache_key_search_all, cache_key_search_by};
     use crate::error::ArchToolkitError;
     use serde_json::json;
 
@@ -257,42 +519,7 @@ mod tests {
             .get("results")
             .and_then(Value::as_array)
             .expect("test JSON should have results array");
-        let mut packages = Vec::new();
-
-        for pkg in results {
-            let name = s(pkg, "Name");
-            if name.is_empty() {
-                continue;
-            }
-
-            let version = s(pkg, "Version");
-            let description = s(pkg, "Description");
-            let popularity = pkg.get("Popularity").and_then(Value::as_f64);
-
-            let out_of_date = pkg
-                .get("OutOfDate")
-                .and_then(Value::as_i64)
-                .and_then(|ts| u64::try_from(ts).ok())
-                .filter(|&ts| ts > 0);
-
-            let maintainer_str = s(pkg, "Maintainer");
-            let maintainer = if maintainer_str.is_empty() {
-                None
-            } else {
-                Some(maintainer_str)
-            };
-            let orphaned = maintainer.is_none();
-
-            packages.push(AurPackage {
-                name,
-                version,
-                description,
-                popularity,
-                out_of_date,
-                orphaned,
-                maintainer,
-            });
-        }
+        let packages: Vec<AurPackage> = results.iter().filter_map(parse_search_result).collect();
 
         assert_eq!(packages.len(), 2);
         assert_eq!(packages[0].name, "yay");
@@ -302,4 +529,328 @@ mod tests {
         assert!(packages[1].orphaned);
         assert_eq!(packages[1].out_of_date, Some(1_234_567_890));
     }
+
+    /// Captured AUR RPC v5 search response (trimmed to two results).
+    const RPC_SEARCH_FIXTURE: &str = r#"{"resultcount":2,"results":[{"Description":"Yet another yogurt. Pacman wrapper and AUR helper written in go.","FirstSubmitted":1475688004,"ID":1663553,"LastModified":1733839215,"Maintainer":"jguer","Name":"yay","NumVotes":2473,"OutOfDate":null,"PackageBase":"yay","PackageBaseID":115973,"Popularity":24.218716,"URL":"https://github.com/Jguer/yay","URLPath":"/cgit/aur.git/snapshot/yay.tar.gz","Version":"12.4.2-1"},{"Description":"Tool to inspect packages","FirstSubmitted":1525000000,"ID":900001,"LastModified":1600000000,"Maintainer":null,"Name":"pkg-inspect","NumVotes":3,"OutOfDate":1650000000,"PackageBase":"pkg-inspect","PackageBaseID":400001,"Popularity":0,"URL":null,"URLPath":"/cgit/aur.git/snapshot/pkg-inspect.tar.gz","Version":"0.3.1-2"}],"type":"search","version":5}"#;
+
+    #[test]
+    /// What: Parse out-of-date flag and maintainer from a captured RPC response.
+    ///
+    /// Inputs:
+    /// - `RPC_SEARCH_FIXTURE` with a maintained package and a flagged, orphaned one.
+    ///
+    /// Output:
+    /// - `out_of_date`/`maintainer` populated; `is_orphaned()` only true for the orphan.
+    /// - Serialized packages use the RPC field names and round-trip.
+    fn test_search_parses_out_of_date_and_maintainer_fixture() {
+        let json: Value = serde_json::from_str(RPC_SEARCH_FIXTURE).expect("fixture is valid JSON");
+        let results = json
+            .get("results")
+            .and_then(Value::as_array)
+            .expect("fixture has results");
+        let packages: Vec<AurPackage> = results.iter().filter_map(parse_search_result).collect();
+
+        assert_eq!(packages[0].maintainer.as_deref(), Some("jguer"));
+        assert_eq!(packages[0].out_of_date, None);
+        assert!(!packages[0].is_orphaned());
+        assert_eq!(packages[1].maintainer, None);
+        assert_eq!(packages[1].out_of_date, Some(1_650_000_000));
+        assert!(packages[1].is_orphaned());
+
+        let serialized = serde_json::to_value(&packages[1]).expect("serializes");
+        assert_eq!(serialized["OutOfDate"], 1_650_000_000);
+        let round_trip: AurPackage = serde_json::from_value(serialized).expect("deserializes");
+        assert_eq!(round_trip, packages[1]);
+
+        let legacy: AurPackage = serde_json::from_value(json!({
+            "name": "yay",
+            "version": "12.4.2-1",
+            "description": "",
+            "out_of_date": 1_650_000_000,
+            "maintainer": "jguer"
+        }))
+        .expect("legacy field names deserialize");
+        assert_eq!(legacy.out_of_date, Some(1_650_000_000));
+        assert!(!legacy.is_orphaned());
+    }
+
+    #[test]
+    /// What: Read submission and modification times and filter for new packages.
+    ///
+    /// Inputs:
+    /// - `RPC_SEARCH_FIXTURE` with packages submitted in 2016 and 2018.
+    ///
+    /// Output:
+    /// - Timestamps survive parsing and serialization; only the 2018 package is newer than
+    ///   2017-01-01.
+    fn test_submitted_at_and_filter_new_since_fixture() {
+        use crate::aur::filter_new_since;
+        use chrono::{DateTime, TimeZone, Utc};
+
+        let json: Value = serde_json::from_str(RPC_SEARCH_FIXTURE).expect("fixture is valid JSON");
+        let packages: Vec<AurPackage> = json["results"]
+            .as_array()
+            .expect("fixture has results")
+            .iter()
+            .filter_map(parse_search_result)
+            .collect();
+
+        assert_eq!(packages[0].first_submitted, Some(1_475_688_004));
+        assert_eq!(
+            packages[0].submitted_at(),
+            DateTime::from_timestamp(1_475_688_004, 0)
+        );
+        assert_eq!(
+            packages[0].modified_at(),
+            DateTime::from_timestamp(1_733_839_215, 0)
+        );
+        let round_trip: AurPackage =
+            serde_json::from_value(serde_json::to_value(&packages[1]).expect("serializes"))
+                .expect("deserializes");
+        assert_eq!(round_trip.first_submitted, Some(1_525_000_000));
+
+        let since = Utc
+            .with_ymd_and_hms(2017, 1, 1, 0, 0, 0)
+            .single()
+            .expect("valid date");
+        let new: Vec<&str> = filter_new_since(&packages, since)
+            .iter()
+            .map(|pkg| pkg.name.as_str())
+            .collect();
+        assert_eq!(new, ["pkg-inspect"]);
+
+        let at_submission = packages[1].submitted_at().expect("timestamp is known");
+        assert_eq!(filter_new_since(&packages, at_submission).len(), 1);
+        assert!(filter_new_since(&page_fixture(3), since).is_empty());
+    }
+
+    fn page_fixture(count: usize) -> Vec<AurPackage> {
+        (0..count)
+            .map(|i| AurPackage {
+                name: format!("pkg-{i}"),
+                version: "1.0-1".to_string(),
+                description: String::new(),
+                popularity: None,
+                out_of_date: None,
+                orphaned: false,
+                maintainer: None,
+                num_votes: None,
+                last_modified: None,
+                first_submitted: None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_search_paginated_serves_pages_from_cache() {
+        use crate::cache::CacheConfigBuilder;
+        use std::time::Duration;
+
+        let client = ArchClient::builder()
+            .cache_config(CacheConfigBuilder::new().enable_search(true).build())
+            .build()
+            .expect("client builds");
+        client
+            .cache()
+            .expect("cache enabled")
+            .set(
+                &cache_key_search_all("lib"),
+                &page_fixture(250),
+                Duration::from_mins(1),
+            )
+            .expect("cache populated");
+
+        let page = search_paginated(&client, "lib", 200, 20)
+            .await
+            .expect("page served from cache");
+        assert_eq!(page.total, 250);
+        assert_eq!(page.offset, 200);
+        assert_eq!(page.items.len(), 20);
+        assert_eq!(page.items[0].name, "pkg-200");
+
+        let rest = search_paginated(&client, "lib", 240, 0)
+            .await
+            .expect("page served from cache");
+        assert_eq!(rest.items.len(), 10);
+        assert_eq!(rest.items[9].name, "pkg-249");
+    }
+
+    #[tokio::test]
+    /// What: Send search requests to a configured AUR base URL.
+    ///
+    /// Inputs:
+    /// - Mock server standing in for a self-hosted `aurweb` instance.
+    ///
+    /// Output:
+    /// - The search hits `/rpc/v5/search` on the mock server and parses its results.
+    async fn test_search_uses_configured_aur_base_url() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/rpc/v5/search"))
+            .and(query_param("arg", "yay"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "results": [{ "Name": "yay", "Version": "12.3.4" }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ArchClient::builder()
+            .aur_base_url(format!("{}/", server.uri()))
+            .build()
+            .expect("client builds");
+        assert_eq!(client.aur_base_url(), server.uri());
+
+        let results = search(&client, "yay").await.expect("search succeeds");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "yay");
+    }
+
+    #[tokio::test]
+    /// What: Surface an RPC error body as `AurRpc` instead of empty results.
+    ///
+    /// Inputs:
+    /// - Mock server answering the search with `{"type":"error", ...}` and HTTP 200.
+    ///
+    /// Output:
+    /// - `Err(AurRpc)` carrying the RPC's message.
+    async fn test_search_rpc_error_response() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/rpc/v5/search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "error": "Too many package results.",
+                "resultcount": 0,
+                "results": [],
+                "type": "error",
+                "version": 5
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ArchClient::builder()
+            .aur_base_url(server.uri())
+            .build()
+            .expect("client builds");
+
+        match search(&client, "li").await {
+            Err(ArchToolkitError::AurRpc { message }) => {
+                assert_eq!(message, "Too many package results.");
+            }
+            other => panic!("expected AurRpc error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    /// What: Emit operation and attempt spans with context fields for a search.
+    ///
+    /// Inputs:
+    /// - Mock server answering one search for `yay`.
+    ///
+    /// Output:
+    /// - Logs carry the `aur_search` span with `operation`/`query`, a nested `attempt` span,
+    ///   and a final event with the outcome and elapsed time.
+    async fn test_search_emits_tracing_spans() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/rpc/v5/search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "results": [{ "Name": "yay", "Version": "12.3.4" }]
+            })))
+            .mount(&server)
+            .await;
+
+        let client = ArchClient::builder()
+            .aur_base_url(server.uri())
+            .build()
+            .expect("client builds");
+
+        search(&client, "yay").await.expect("search succeeds");
+
+        assert!(logs_contain(
+            "aur_search{operation=\"search\" query=yay by=\"name-desc\"}"
+        ));
+        assert!(logs_contain(
+            "attempt{operation=\"search\" context=yay attempt=1}"
+        ));
+        assert!(logs_contain(
+            "operation finished outcome=\"ok\" elapsed_ms="
+        ));
+    }
+
+    #[test]
+    fn test_search_by_param_values() {
+        assert_eq!(SearchBy::default(), SearchBy::NameDesc);
+        assert_eq!(SearchBy::NameDesc.as_str(), "name-desc");
+        assert_eq!(SearchBy::Maintainer.as_str(), "maintainer");
+        assert_eq!(SearchBy::MakeDepends.as_str(), "makedepends");
+
+        let url = build_rpc_url(
+            AUR_BASE_URL,
+            AUR_RPC_VERSION,
+            "search",
+            &[("by", SearchBy::Depends.as_str()), ("arg", "libfoo")],
+        );
+        assert!(
+            url.ends_with("/rpc/v5/search?by=depends&arg=libfoo"),
+            "{url}"
+        );
+    }
+
+    #[test]
+    fn test_search_by_cache_keys_do_not_collide() {
+        assert_eq!(cache_key_search_by("foo", SearchBy::NameDesc), "search:foo");
+        assert_eq!(
+            cache_key_search_by("foo", SearchBy::Maintainer),
+            "search_by:maintainer:foo"
+        );
+        assert_ne!(
+            cache_key_search_by("foo", SearchBy::Name),
+            cache_key_search_by("foo", SearchBy::Maintainer)
+        );
+        // Query text shaped like a field key must not reuse that field's results
+        assert_ne!(
+            cache_key_search_by("by=maintainer:foo", SearchBy::NameDesc),
+            cache_key_search_by("foo", SearchBy::Maintainer)
+        );
+        let keys = crate::cache::CacheKeys::new("mytool");
+        assert_ne!(
+            keys.search_by("by=maintainer:foo", SearchBy::NameDesc),
+            keys.search_by("foo", SearchBy::Maintainer)
+        );
+    }
+
+    #[test]
+    fn test_search_page_offset_past_end_is_empty() {
+        let results = page_fixture(5);
+        let page = SearchPage::from_results(&results, 5, 10);
+        assert!(page.items.is_empty());
+        assert_eq!(page.total, 5);
+        assert_eq!(page.offset, 5);
+
+        let page = SearchPage::from_results(&results, 50, 0);
+        assert!(page.items.is_empty());
+    }
+
+    #[test]
+    fn test_search_page_zero_limit_returns_remaining() {
+        let results = page_fixture(5);
+        let page = SearchPage::from_results(&results, 2, 0);
+        let names: Vec<&str> = page.items.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["pkg-2", "pkg-3", "pkg-4"]);
+
+        let page = SearchPage::from_results(&results, 3, 100);
+        assert_eq!(page.items.len(), 2);
+    }
 }
@@ -0,0 +1,166 @@
+//! `.SRCINFO` fetching functionality.
+
+use crate::aur::utils::percent_encode;
+use crate::aur::validation::validate_package_name;
+use crate::client::{ArchClient, check_status, reset_archlinux_backoff};
+use crate::error::{ArchToolkitError, Result};
+use reqwest::Client;
+use std::time::Duration;
+use tracing::debug;
+
+/// What: Fetch `.SRCINFO` content for an AUR package.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+/// - `package`: Package base name to fetch `.SRCINFO` for.
+///
+/// Output:
+/// - `Result<String>` with raw `.SRCINFO` text.
+///
+/// Details:
+/// - Fetches from `{aur_base_url}/cgit/aur.git/plain/.SRCINFO?h={package}`.
+/// - Applies archlinux.org rate limiting and the retry policy for pkgbuild operations.
+/// - Cached under `CacheKeys::srcinfo()` using the pkgbuild cache settings.
+///
+/// # Errors
+/// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+/// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
+/// - Returns `Err(ArchToolkitError::Parse)` if the response is empty or an HTML page
+/// - Returns `Err(ArchToolkitError::InvalidInput)` if the URL is not from the AUR
+/// - Returns `Err(ArchToolkitError::ServiceUnavailable)` if the circuit breaker is open
+/// - Returns `Err(ArchToolkitError::InvalidPackageName)` if package name is invalid
+pub async fn srcinfo(client: &ArchClient, package: &str) -> Result<String> {
+    let validation_config = client.validation_config();
+    validate_package_name(package, Some(validation_config))?;
+
+    // Check cache if enabled
+    if let Some(cache_config) = client.cache_config()
+        && cache_config.enable_pkgbuild
+        && let Some(cache) = client.cache()
+    {
+        let cache_key = cache.keys().srcinfo(package);
+        if let Some(cached) = cache.get::<String>(&cache_key) {
+            debug!(package = %package, "cache hit for srcinfo");
+            return Ok(cached);
+        }
+    }
+
+    let url = format!(
+        "{}/cgit/aur.git/plain/.SRCINFO?h={}",
+        client.aur_base_url(),
+        percent_encode(package)
+    );
+
+    debug!(package = %package, url = %url, "fetching .SRCINFO");
+
+    // Apply rate limiting for archlinux.org
+    let _permit = if client.is_aur_url(&url) {
+        client.archlinux_permit().await?
+    } else {
+        return Err(ArchToolkitError::InvalidInput(format!(
+            "Unexpected URL domain: {url}"
+        )));
+    };
+
+    let http_client = client.http_client();
+
+    let text = client
+        .execute_with_retry("srcinfo", package, || async {
+            perform_srcinfo_request(http_client, &url, package).await
+        })
+        .await?;
+
+    // Store in cache if enabled
+    if let Some(cache_config) = client.cache_config()
+        && cache_config.enable_pkgbuild
+        && let Some(cache) = client.cache()
+    {
+        let cache_key = cache.keys().srcinfo(package);
+        let _ = cache.set(&cache_key, &text, cache_config.pkgbuild_ttl);
+    }
+
+    Ok(text)
+}
+
+/// What: Perform the actual `.SRCINFO` request without retry logic.
+///
+/// Inputs:
+/// - `client`: HTTP client to use for requests.
+/// - `url`: URL to request.
+/// - `package`: Package name for logging.
+///
+/// Output:
+/// - `Result<String>` containing `.SRCINFO` text, or an error.
+///
+/// Details:
+/// - Rejects empty bodies and HTML error pages served by cgit for unknown packages.
+async fn perform_srcinfo_request(client: &Client, url: &str, package: &str) -> Result<String> {
+    let response = client
+        .get(url)
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| {
+            debug!(error = %e, package = %package, ".SRCINFO request failed");
+            ArchToolkitError::Network(e)
+        })?;
+    reset_archlinux_backoff();
+
+    let response = check_status(response).inspect_err(|e| {
+        debug!(error = %e, package = %package, ".SRCINFO returned non-success status");
+    })?;
+    let text = response.text().await.map_err(ArchToolkitError::Network)?;
+
+    let trimmed = text.trim_start();
+    if trimmed.is_empty() {
+        return Err(ArchToolkitError::Parse(format!(
+            "Empty .SRCINFO for {package}"
+        )));
+    }
+    if trimmed.starts_with("<html") || trimmed.starts_with("<!DOCTYPE") {
+        return Err(ArchToolkitError::Parse(format!(
+            "Received HTML page instead of .SRCINFO for {package}"
+        )));
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CacheConfigBuilder;
+
+    #[tokio::test]
+    /// What: Serve `.SRCINFO` through the client with caching.
+    ///
+    /// Inputs:
+    /// - Mock server answering the cgit `.SRCINFO` endpoint exactly once.
+    ///
+    /// Output:
+    /// - Both calls return the body; the second is served from the cache.
+    async fn test_srcinfo_fetches_and_caches() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let body = "pkgbase = foo\n\tpkgver = 1.0\n\tmakedepends = cmake\n\npkgname = foo\n";
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/cgit/aur.git/plain/.SRCINFO"))
+            .and(query_param("h", "foo"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ArchClient::builder()
+            .aur_base_url(server.uri())
+            .cache_config(CacheConfigBuilder::new().enable_pkgbuild(true).build())
+            .build()
+            .expect("client builds");
+
+        let first = srcinfo(&client, "foo").await.expect("fetch succeeds");
+        let second = srcinfo(&client, "foo").await.expect("cache hit");
+        assert_eq!(first, body);
+        assert_eq!(second, body);
+    }
+}
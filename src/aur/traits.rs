@@ -1,7 +1,8 @@
 //! Traits for AUR operations, enabling testability via mock implementations.
 
+use crate::aur::utils::fetch_each;
 use crate::error::Result;
-use crate::types::{AurComment, AurPackage, AurPackageDetails};
+use crate::types::{AurComment, AurPackage, AurPackageDetails, SearchPage};
 use async_trait::async_trait;
 
 /// What: Trait for AUR operations, enabling testability via mock implementations.
@@ -30,6 +31,29 @@ pub trait AurApi: Send + Sync {
     /// - Returns empty vector if no results found (not an error)
     async fn search(&self, query: &str) -> Result<Vec<AurPackage>>;
 
+    /// What: Fetch one page of AUR search results.
+    ///
+    /// Inputs:
+    /// - `query`: Search query string
+    /// - `offset`: Index of the first result to return
+    /// - `limit`: Maximum number of results to return (`0` means all remaining results)
+    ///
+    /// Output:
+    /// - `Result<SearchPage>` with the requested slice and the total result count
+    ///
+    /// Details:
+    /// - Default implementation slices the results of `search()`
+    /// - An `offset` at or beyond the total yields an empty page, not an error
+    async fn search_paginated(
+        &self,
+        query: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Result<SearchPage> {
+        let results = self.search(query).await?;
+        Ok(SearchPage::from_results(&results, offset, limit))
+    }
+
     /// What: Fetch detailed information for one or more AUR packages.
     ///
     /// Inputs:
@@ -56,6 +80,21 @@ pub trait AurApi: Send + Sync {
     /// - Comments are sorted by date (latest first)
     async fn comments(&self, pkgname: &str) -> Result<Vec<AurComment>>;
 
+    /// What: Fetch comments for several AUR packages in one call.
+    ///
+    /// Inputs:
+    /// - `pkgnames`: Package names to fetch comments for
+    ///
+    /// Output:
+    /// - One `(pkgname, result)` pair per requested package, in input order
+    ///
+    /// Details:
+    /// - Default implementation calls `comments()` for each package, one at a time
+    /// - A failing package does not fail the batch; its error is returned in its pair
+    async fn comments_many(&self, pkgnames: &[&str]) -> Vec<(String, Result<Vec<AurComment>>)> {
+        fetch_each(pkgnames, |pkgname| self.comments(pkgname)).await
+    }
+
     /// What: Fetch PKGBUILD content for an AUR package.
     ///
     /// Inputs:
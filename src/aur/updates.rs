@@ -0,0 +1,138 @@
+//! AUR update detection for installed foreign packages.
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use tracing::debug;
+
+use super::info::missing_packages;
+use super::traits::AurApi;
+use crate::deps::compare_versions;
+use crate::error::Result;
+use crate::types::{AurUpdate, AurUpdateReport};
+
+/// What: Compare installed package versions against the AUR.
+///
+/// Inputs:
+/// - `api`: AUR API used for the info lookup.
+/// - `installed`: `(name, installed_version)` pairs, e.g. from `index::get_foreign_packages()`.
+///
+/// Output:
+/// - `Result<AurUpdateReport>` with newer versions in `updates` and unknown names in `missing`.
+///
+/// Details:
+/// - Looks up all names with a single `AurApi::info()` call (which chunks large requests)
+/// - Versions are compared with `deps::compare_versions()`; equal or older AUR versions
+///   are not reported
+/// - Duplicate names are checked once, using the first installed version
+/// - An empty `installed` list returns an empty report without a request
+///
+/// # Errors
+/// - Returns the same errors as `AurApi::info()`
+pub async fn updates(api: &dyn AurApi, installed: &[(&str, &str)]) -> Result<AurUpdateReport> {
+    let mut seen = HashSet::new();
+    let installed: Vec<(&str, &str)> = installed
+        .iter()
+        .copied()
+        .filter(|(name, _)| seen.insert(*name))
+        .collect();
+    if installed.is_empty() {
+        return Ok(AurUpdateReport::default());
+    }
+
+    let names: Vec<&str> = installed.iter().map(|(name, _)| *name).collect();
+    let details = api.info(&names).await?;
+
+    let updates = installed
+        .iter()
+        .filter_map(|(name, current)| {
+            let pkg = details.iter().find(|pkg| pkg.name == *name)?;
+            (compare_versions(&pkg.version, current) == Ordering::Greater).then(|| AurUpdate {
+                name: (*name).to_string(),
+                current: (*current).to_string(),
+                available: pkg.version.clone(),
+            })
+        })
+        .collect::<Vec<_>>();
+    let missing = missing_packages(&names, &details);
+    debug!(
+        checked = names.len(),
+        updates = updates.len(),
+        missing = missing.len(),
+        "computed AUR updates"
+    );
+
+    Ok(AurUpdateReport { updates, missing })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aur::MockAurApi;
+    use crate::types::AurPackageDetails;
+
+    fn details(name: &str, version: &str) -> AurPackageDetails {
+        AurPackageDetails {
+            name: name.to_string(),
+            version: version.to_string(),
+            ..AurPackageDetails::default()
+        }
+    }
+
+    #[tokio::test]
+    /// What: Report newer AUR versions and packages missing from the AUR.
+    ///
+    /// Inputs:
+    /// - Installed `paru` (outdated), `yay` (current), `spotify` (installed newer than AUR),
+    ///   `gone-pkg` (not in the AUR), and a duplicate `paru` entry.
+    /// - Mocked info response for the deduplicated names.
+    ///
+    /// Output:
+    /// - `paru` in `updates`, `gone-pkg` in `missing`, one info call.
+    async fn test_updates_reports_newer_and_missing() {
+        let names = ["paru", "yay", "spotify", "gone-pkg"];
+        let mock = MockAurApi::new().with_info_result(
+            &names,
+            Ok(vec![
+                details("paru", "2.0.4-1"),
+                details("yay", "12.4.2-1"),
+                details("spotify", "1:1.2.40-1"),
+            ]),
+        );
+        let installed = [
+            ("paru", "2.0.3-2"),
+            ("yay", "12.4.2-1"),
+            ("spotify", "1:1.2.48-1"),
+            ("gone-pkg", "0.1-1"),
+            ("paru", "1.0-1"),
+        ];
+
+        let report = updates(&mock, &installed).await.expect("info succeeds");
+
+        assert_eq!(
+            report.updates,
+            vec![AurUpdate {
+                name: "paru".to_string(),
+                current: "2.0.3-2".to_string(),
+                available: "2.0.4-1".to_string(),
+            }]
+        );
+        assert_eq!(report.missing, vec!["gone-pkg"]);
+        assert_eq!(mock.call_count("info"), 1);
+    }
+
+    #[tokio::test]
+    /// What: Skip the info request when nothing is installed.
+    ///
+    /// Inputs:
+    /// - Empty installed list.
+    ///
+    /// Output:
+    /// - Empty report and no info call.
+    async fn test_updates_empty_input() {
+        let mock = MockAurApi::new();
+        let report = updates(&mock, &[]).await.expect("empty input");
+        assert_eq!(report, AurUpdateReport::default());
+        assert_eq!(mock.call_count("info"), 0);
+    }
+}
@@ -0,0 +1,89 @@
+//! URL construction helpers for AUR RPC requests.
+
+use crate::aur::utils::percent_encode;
+
+/// Base URL of the official AUR web interface and RPC endpoint.
+pub const AUR_BASE_URL: &str = "https://aur.archlinux.org";
+
+/// AUR RPC interface version used for all requests.
+pub const AUR_RPC_VERSION: u32 = 5;
+
+/// What: Build an AUR RPC URL with percent-encoded query parameters.
+///
+/// Inputs:
+/// - `base`: Base URL of the AUR instance (e.g., `https://aur.archlinux.org`).
+/// - `version`: RPC interface version (e.g., `5`).
+/// - `type_`: RPC request type (e.g., `search`, `info`).
+/// - `params`: Ordered query parameters as key/value pairs.
+///
+/// Output:
+/// - Returns the full URL in the form `{base}/rpc/v{version}/{type_}?k1=v1&k2=v2`.
+///
+/// Details:
+/// - Values are percent-encoded with `percent_encode`; keys are emitted verbatim so array
+///   notation such as `arg[]` reaches the server unchanged.
+/// - Repeated keys are preserved in order, as required for multi-package `info` requests.
+/// - A trailing slash on `base` is ignored; no `?` is appended when `params` is empty.
+pub fn build_rpc_url(base: &str, version: u32, type_: &str, params: &[(&str, &str)]) -> String {
+    let mut url = format!("{}/rpc/v{version}/{type_}", base.trim_end_matches('/'));
+    for (i, (key, value)) in params.iter().enumerate() {
+        url.push(if i == 0 { '?' } else { '&' });
+        url.push_str(key);
+        url.push('=');
+        url.push_str(&percent_encode(value));
+    }
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_rpc_url_single_param() {
+        let url = build_rpc_url(
+            AUR_BASE_URL,
+            AUR_RPC_VERSION,
+            "search",
+            &[("by", "name"), ("arg", "yay")],
+        );
+        assert_eq!(
+            url,
+            "https://aur.archlinux.org/rpc/v5/search?by=name&arg=yay"
+        );
+    }
+
+    #[test]
+    fn test_build_rpc_url_repeated_keys() {
+        let url = build_rpc_url(
+            AUR_BASE_URL,
+            AUR_RPC_VERSION,
+            "info",
+            &[("arg[]", "yay"), ("arg[]", "paru"), ("arg[]", "pacman")],
+        );
+        assert_eq!(
+            url,
+            "https://aur.archlinux.org/rpc/v5/info?arg[]=yay&arg[]=paru&arg[]=pacman"
+        );
+    }
+
+    #[test]
+    fn test_build_rpc_url_encodes_special_characters() {
+        let url = build_rpc_url(
+            "https://aur.archlinux.org/",
+            AUR_RPC_VERSION,
+            "info",
+            &[("arg[]", "lib32-gcc++"), ("arg[]", "a b&c=d")],
+        );
+        assert_eq!(
+            url,
+            "https://aur.archlinux.org/rpc/v5/info?arg[]=lib32-gcc%2B%2B&arg[]=a%20b%26c%3Dd"
+        );
+    }
+
+    #[test]
+    fn test_build_rpc_url_no_params() {
+        let url = build_rpc_url(AUR_BASE_URL, 5, "suggest", &[]);
+        assert_eq!(url, "https://aur.archlinux.org/rpc/v5/suggest");
+    }
+}
@@ -1,7 +1,116 @@
 //! Utility functions for AUR operations.
 
+use crate::client::ArchClient;
+use crate::error::{ArchToolkitError, Result};
+use crate::observer::Outcome;
+use crate::types::AurPackage;
+use chrono::{DateTime, Utc};
+use futures_util::future::join_all;
 use serde_json::Value;
+use std::cmp::Ordering;
 use std::fmt::Write;
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tracing::{Instrument, Span, info};
+
+/// What: Run an AUR operation inside a tracing span and report it to the client's observer.
+///
+/// Inputs:
+/// - `client`: `ArchClient` whose `Observer` is notified.
+/// - `op`: Operation name (`search`, `info`, `comments`, `pkgbuild`).
+/// - `ctx`: Query or package name(s) of the call.
+/// - `span`: Operation span carrying `operation` and `package`/`query` fields.
+/// - `operation`: The operation future.
+///
+/// Output:
+/// - The operation's result, unchanged.
+///
+/// Details:
+/// - Everything logged by the operation, including retry attempt spans, is nested in `span`.
+/// - Emits a final `info` event with `outcome` (`ok`/`error`) and `elapsed_ms`.
+/// - Calls `Observer::on_request_start()` before and `Observer::on_request_end()` after
+///   the operation.
+pub(crate) async fn instrumented<T>(
+    client: &ArchClient,
+    op: &str,
+    ctx: &str,
+    span: Span,
+    operation: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    let observer = client.observer();
+    observer.on_request_start(op, ctx);
+    let started = Instant::now();
+    let result = operation.instrument(span.clone()).await;
+    let elapsed = started.elapsed();
+    let elapsed_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+    span.in_scope(|| match &result {
+        Ok(_) => info!(outcome = "ok", elapsed_ms, "operation finished"),
+        Err(error) => info!(outcome = "error", elapsed_ms, error = %error, "operation finished"),
+    });
+    let outcome = if result.is_ok() {
+        Outcome::Success
+    } else {
+        Outcome::Error
+    };
+    observer.on_request_end(op, ctx, outcome, elapsed);
+    result
+}
+
+/// What: Apply an optional per-request timeout to a request builder.
+///
+/// Inputs:
+/// - `request`: Request being built.
+/// - `timeout`: Timeout override; `None` keeps the existing timeout.
+///
+/// Output:
+/// - The request builder, with the override applied if given.
+///
+/// Details:
+/// - Overrides the client-wide timeout for this request only.
+pub(crate) fn with_timeout(
+    request: reqwest::RequestBuilder,
+    timeout: Option<Duration>,
+) -> reqwest::RequestBuilder {
+    match timeout {
+        Some(timeout) => request.timeout(timeout),
+        None => request,
+    }
+}
+
+/// Number of per-package requests a batched fetch keeps in flight.
+///
+/// archlinux.org requests are serialized by the shared rate limiter anyway, so batches run
+/// one package at a time; raising this only helps backends without that limit.
+pub(crate) const BATCH_CONCURRENCY: usize = 1;
+
+/// What: Run a per-package fetch for every name and collect the individual results.
+///
+/// Inputs:
+/// - `names`: Package names to fetch, in order.
+/// - `fetch`: Fetch for a single package.
+///
+/// Output:
+/// - One `(name, result)` pair per input name, in input order.
+///
+/// Details:
+/// - A failing package does not stop the batch; its error is returned in its pair.
+/// - Names are fetched in groups of `BATCH_CONCURRENCY`; a group completes before the next
+///   one starts.
+pub(crate) async fn fetch_each<'a, T, F, Fut>(
+    names: &'a [&'a str],
+    fetch: F,
+) -> Vec<(String, Result<T>)>
+where
+    F: Fn(&'a str) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut results = Vec::with_capacity(names.len());
+    for chunk in names.chunks(BATCH_CONCURRENCY) {
+        let fetched = join_all(chunk.iter().map(|&name| fetch(name))).await;
+        results.extend(chunk.iter().map(ToString::to_string).zip(fetched));
+    }
+    results
+}
 
 /// What: Percent-encode a string for use in URLs according to RFC 3986.
 ///
@@ -53,6 +162,34 @@ pub fn s(v: &Value, key: &str) -> String {
         .to_string()
 }
 
+/// What: Reject AUR RPC error responses.
+///
+/// Inputs:
+/// - `json`: Decoded RPC response body.
+///
+/// Output:
+/// - `Ok(())` for any response whose `type` is not `"error"`.
+///
+/// Details:
+/// - The RPC reports failures (too many results, unknown request type, ...) with HTTP 200 and
+///   `{"type":"error","error":"..."}`; without this check they would read as empty results.
+/// - Only `type` and `error` are inspected, so unknown or changed fields (`resultcount`,
+///   `version`, ...) never fail a response.
+///
+/// # Errors
+/// - Returns `Err(ArchToolkitError::AurRpc)` with the RPC's message for error responses
+pub fn check_rpc_error(json: &Value) -> Result<()> {
+    if json.get("type").and_then(Value::as_str) != Some("error") {
+        return Ok(());
+    }
+    let message = json
+        .get("error")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown error")
+        .to_string();
+    Err(ArchToolkitError::AurRpc { message })
+}
+
 /// What: Extract a string value from a JSON object by key with fallback keys.
 ///
 /// Inputs:
@@ -139,3 +276,214 @@ pub fn u64_of(v: &Value, keys: &[&str]) -> Option<u64> {
     }
     None
 }
+
+/// Field to sort AUR package lists by.
+///
+/// Used with `sort_packages()`. Each field has a fixed direction suited for display;
+/// ties are broken by package name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SortField {
+    /// Most popular first (packages without a score last).
+    Popularity,
+    /// Most voted first.
+    Votes,
+    /// Most recently modified first.
+    LastModified,
+    /// Alphabetical by name.
+    Name,
+    /// Packages that are not flagged out-of-date first.
+    OutOfDate,
+}
+
+/// What: Sort AUR packages in place by the given field.
+///
+/// Inputs:
+/// - `packages`: Packages to sort (e.g., search results).
+/// - `by`: Field to sort by.
+///
+/// Output:
+/// - Reorders `packages` in place.
+///
+/// Details:
+/// - Uses the `AurPackage` sort key accessors; unknown values sort as zero.
+/// - The sort is stable and ties are broken by name, so the order is deterministic.
+///
+/// # Example
+///
+/// ```
+/// use arch_toolkit::aur::utils::{SortField, sort_packages};
+/// use arch_toolkit::AurPackage;
+///
+/// let pkg = |name: &str, popularity: f64| AurPackage {
+///     name: name.into(),
+///     version: "1.0-1".into(),
+///     description: String::new(),
+///     popularity: Some(popularity),
+///     out_of_date: None,
+///     orphaned: false,
+///     maintainer: None,
+///     num_votes: None,
+///     last_modified: None,
+///     first_submitted: None,
+/// };
+/// let mut packages = vec![pkg("paru", 2.5), pkg("yay", 3.0)];
+/// sort_packages(&mut packages, SortField::Popularity);
+/// assert_eq!(packages[0].name, "yay");
+/// ```
+pub fn sort_packages(packages: &mut [AurPackage], by: SortField) {
+    packages.sort_by(|a, b| compare_packages(a, b, by).then_with(|| a.name.cmp(&b.name)));
+}
+
+/// What: Select packages first submitted to the AUR at or after a point in time.
+///
+/// Inputs:
+/// - `pkgs`: Packages to filter, e.g. search results.
+/// - `since`: Earliest submission time to keep.
+///
+/// Output:
+/// - References to the packages submitted at or after `since`, in input order.
+///
+/// Details:
+/// - Packages without a `first_submitted` timestamp are skipped.
+///
+/// # Example
+///
+/// ```
+/// use arch_toolkit::aur::filter_new_since;
+/// use arch_toolkit::AurPackage;
+/// use chrono::DateTime;
+///
+/// let pkg = |name: &str, first_submitted: i64| AurPackage {
+///     name: name.into(),
+///     version: "1.0-1".into(),
+///     description: String::new(),
+///     popularity: None,
+///     out_of_date: None,
+///     orphaned: false,
+///     maintainer: None,
+///     num_votes: None,
+///     last_modified: None,
+///     first_submitted: Some(first_submitted),
+/// };
+/// let packages = vec![pkg("old", 1_600_000_000), pkg("new", 1_750_000_000)];
+/// let since = DateTime::from_timestamp(1_700_000_000, 0).expect("valid timestamp");
+/// let new: Vec<&str> = filter_new_since(&packages, since)
+///     .iter()
+///     .map(|p| p.name.as_str())
+///     .collect();
+/// assert_eq!(new, ["new"]);
+/// ```
+#[must_use]
+pub fn filter_new_since(pkgs: &[AurPackage], since: DateTime<Utc>) -> Vec<&AurPackage> {
+    pkgs.iter()
+        .filter(|pkg| {
+            pkg.submitted_at()
+                .is_some_and(|submitted| submitted >= since)
+        })
+        .collect()
+}
+
+/// What: Compare two packages by a single sort field.
+///
+/// Inputs:
+/// - `a`, `b`: Packages to compare.
+/// - `by`: Field to compare.
+///
+/// Output:
+/// - Ordering of `a` relative to `b` in the field's display direction.
+fn compare_packages(a: &AurPackage, b: &AurPackage, by: SortField) -> Ordering {
+    match by {
+        SortField::Popularity => b.popularity_key().total_cmp(&a.popularity_key()),
+        SortField::Votes => b.votes_key().cmp(&a.votes_key()),
+        SortField::LastModified => b.last_modified_key().cmp(&a.last_modified_key()),
+        SortField::Name => a.name.cmp(&b.name),
+        SortField::OutOfDate => a.out_of_date_key().cmp(&b.out_of_date_key()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pkg(name: &str) -> AurPackage {
+        AurPackage {
+            name: name.to_string(),
+            version: "1.0-1".to_string(),
+            description: String::new(),
+            popularity: None,
+            out_of_date: None,
+            orphaned: false,
+            maintainer: None,
+            num_votes: None,
+            last_modified: None,
+            first_submitted: None,
+        }
+    }
+
+    fn names(packages: &[AurPackage]) -> Vec<&str> {
+        packages.iter().map(|p| p.name.as_str()).collect()
+    }
+
+    #[test]
+    /// What: Sort a result list by each field.
+    ///
+    /// Inputs:
+    /// - Three packages with distinct popularity, votes, modification time, and flags.
+    ///
+    /// Output:
+    /// - Popularity, votes, and modification time descending; name ascending;
+    ///   flagged packages last.
+    fn test_sort_packages_by_each_field() {
+        let mut a = pkg("alpha");
+        a.popularity = Some(0.5);
+        a.num_votes = Some(300);
+        a.last_modified = Some(1_700_000_000);
+        a.out_of_date = Some(1_700_000_100);
+        let mut b = pkg("bravo");
+        b.popularity = Some(4.2);
+        b.num_votes = Some(10);
+        b.last_modified = Some(1_600_000_000);
+        let mut c = pkg("charlie");
+        c.num_votes = Some(50);
+        let mut packages = vec![c, a, b];
+
+        sort_packages(&mut packages, SortField::Popularity);
+        assert_eq!(names(&packages), vec!["bravo", "alpha", "charlie"]);
+        sort_packages(&mut packages, SortField::Votes);
+        assert_eq!(names(&packages), vec!["alpha", "charlie", "bravo"]);
+        sort_packages(&mut packages, SortField::LastModified);
+        assert_eq!(names(&packages), vec!["alpha", "bravo", "charlie"]);
+        sort_packages(&mut packages, SortField::Name);
+        assert_eq!(names(&packages), vec!["alpha", "bravo", "charlie"]);
+        sort_packages(&mut packages, SortField::OutOfDate);
+        assert_eq!(names(&packages), vec!["bravo", "charlie", "alpha"]);
+    }
+
+    #[test]
+    /// What: Map RPC error responses to `AurRpc` and accept everything else.
+    fn test_check_rpc_error() {
+        let error = serde_json::json!({
+            "error": "Too many package results.",
+            "resultcount": 0,
+            "results": [],
+            "type": "error",
+            "version": 5
+        });
+        match check_rpc_error(&error) {
+            Err(ArchToolkitError::AurRpc { message }) => {
+                assert_eq!(message, "Too many package results.");
+            }
+            other => panic!("expected AurRpc error, got {other:?}"),
+        }
+
+        let ok = serde_json::json!({
+            "resultcount": "1",
+            "results": [{ "Name": "yay" }],
+            "type": "search",
+            "version": 6,
+            "warning": "new field"
+        });
+        assert!(check_rpc_error(&ok).is_ok());
+        assert!(check_rpc_error(&serde_json::json!({ "results": [] })).is_ok());
+    }
+}
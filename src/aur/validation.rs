@@ -15,6 +15,8 @@ static DEFAULT_VALIDATION_CONFIG: LazyLock<ValidationConfig> =
 ///
 /// Details:
 /// - Controls validation strictness for empty inputs
+/// - Controls package name strictness: AUR naming rules (default) or `lenient`,
+///   which only rejects characters that would break URL construction
 /// - Configures maximum length limits for inputs
 /// - Can be customized via `ArchClientBuilder`
 #[derive(Debug, Clone)]
@@ -25,6 +27,9 @@ pub struct ValidationConfig {
     pub max_query_length: usize,
     /// Maximum package name length in characters (default: 127).
     pub max_package_name_length: usize,
+    /// Only reject package names that would break URL construction instead of
+    /// enforcing the AUR naming rules (default: false).
+    pub lenient: bool,
 }
 
 impl Default for ValidationConfig {
@@ -33,6 +38,7 @@ impl Default for ValidationConfig {
             strict_empty: true,
             max_query_length: 256,
             max_package_name_length: 127,
+            lenient: false,
         }
     }
 }
@@ -52,6 +58,11 @@ impl Default for ValidationConfig {
 ///   - Cannot start with hyphen (`-`) or period (`.`)
 ///   - Must be non-empty
 ///   - Maximum length: 127 characters (default, configurable)
+/// - With `ValidationConfig::lenient`, naming rules are relaxed to URL safety:
+///   - Rejects `/`, `\`, `?`, `#`, `%`, `&`, `=`, whitespace, and control characters
+///   - Rejects the path segments `.` and `..`
+///   - Empty and length checks still apply
+/// - Validation runs before any request is made, so invalid names never reach a URL
 /// - Returns the input string on success for method chaining
 ///
 /// # Errors
@@ -84,6 +95,10 @@ pub fn validate_package_name<'a>(
         });
     }
 
+    if config.lenient {
+        return validate_url_safe_name(name);
+    }
+
     // Check for invalid starting characters
     if name.starts_with('-') {
         return Err(ArchToolkitError::InvalidPackageName {
@@ -119,6 +134,45 @@ pub fn validate_package_name<'a>(
     Ok(name)
 }
 
+/// What: Validate that a package name can be safely embedded in a URL.
+///
+/// Inputs:
+/// - `name`: Non-empty package name to validate
+///
+/// Output:
+/// - `Result<&str>` containing the validated name, or an error
+///
+/// Details:
+/// - Used by `validate_package_name()` in lenient mode
+///
+/// # Errors
+/// - Returns `Err(ArchToolkitError::InvalidPackageName)` if name is a dot segment or
+///   contains a URL-breaking character
+fn validate_url_safe_name(name: &str) -> Result<&str> {
+    if name == "." || name == ".." {
+        return Err(ArchToolkitError::InvalidPackageName {
+            name: name.to_string(),
+            reason: "package name cannot be a relative path segment".to_string(),
+        });
+    }
+
+    if let Some((idx, ch)) = name.char_indices().find(|&(_, ch)| {
+        ch.is_whitespace()
+            || ch.is_control()
+            || matches!(ch, '/' | '\\' | '?' | '#' | '%' | '&' | '=')
+    }) {
+        return Err(ArchToolkitError::InvalidPackageName {
+            name: name.to_string(),
+            reason: format!(
+                "package name contains character '{}' at position {idx} that is not allowed in URLs",
+                ch.escape_debug()
+            ),
+        });
+    }
+
+    Ok(name)
+}
+
 /// What: Validate multiple package names.
 ///
 /// Inputs:
@@ -343,6 +397,76 @@ mod tests {
         }
     }
 
+    #[test]
+    /// What: Reject path traversal and URL-breaking names in both modes.
+    ///
+    /// Inputs:
+    /// - Names with slashes, spaces, query/fragment characters, and dot segments.
+    ///
+    /// Output:
+    /// - `InvalidPackageName` in strict (default) and lenient mode.
+    fn test_validate_package_name_url_breaking_rejected_in_both_modes() {
+        let lenient = ValidationConfig {
+            lenient: true,
+            ..Default::default()
+        };
+        let invalid = [
+            "../../etc/passwd",
+            "pkg name",
+            "pkg?h=other",
+            "pkg#frag",
+            "pkg&x=1",
+            "pkg%2F",
+            "pkg\\name",
+            "pkg\tname",
+            "..",
+            ".",
+        ];
+
+        for name in &invalid {
+            for config in [None, Some(&lenient)] {
+                match validate_package_name(name, config) {
+                    Err(ArchToolkitError::InvalidPackageName { .. }) => {}
+                    other => panic!("Expected InvalidPackageName for '{name}', got {other:?}"),
+                }
+            }
+        }
+    }
+
+    #[test]
+    /// What: Accept names outside the AUR naming rules in lenient mode only.
+    ///
+    /// Inputs:
+    /// - Uppercase, leading hyphen/period, and non-ASCII names.
+    ///
+    /// Output:
+    /// - Rejected by default, accepted in lenient mode; length and empty checks still apply.
+    fn test_validate_package_name_lenient_mode() {
+        let lenient = ValidationConfig {
+            lenient: true,
+            ..Default::default()
+        };
+        for name in ["Invalid", "-leading", ".hidden", "pkg!", "paquet-é"] {
+            assert!(
+                validate_package_name(name, None).is_err(),
+                "'{name}' should be rejected in strict mode"
+            );
+            assert_eq!(
+                validate_package_name(name, Some(&lenient)).expect("lenient accepts name"),
+                name
+            );
+        }
+
+        assert!(matches!(
+            validate_package_name(&"A".repeat(128), Some(&lenient)),
+            Err(ArchToolkitError::InputTooLong { .. })
+        ));
+        assert!(matches!(
+            validate_package_name("", Some(&lenient)),
+            Err(ArchToolkitError::EmptyInput { .. })
+        ));
+    }
+
     #[test]
     fn test_validate_package_names_valid() {
         let names = &["yay", "paru", "linux-zen"];
@@ -463,5 +587,6 @@ mod tests {
         assert!(config.strict_empty);
         assert_eq!(config.max_query_length, 256);
         assert_eq!(config.max_package_name_length, 127);
+        assert!(!config.lenient);
     }
 }
@@ -0,0 +1,412 @@
+//! Blocking (synchronous) facade over the async AUR client.
+//!
+//! Intended for CLI tools and scripts that do not run a Tokio runtime. Each blocking client
+//! owns a current-thread runtime and drives the async operations to completion on the calling
+//! thread, similar to `reqwest::blocking`.
+//!
+//! # Panics
+//!
+//! Calling any method from within an async runtime (e.g. inside a `#[tokio::main]` function)
+//! panics, because Tokio does not allow starting a runtime from within a runtime. Dropping a
+//! blocking client inside an async context panics for the same reason. Async code should use
+//! `ArchClient` directly.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use arch_toolkit::ArchClient;
+//!
+//! let client = ArchClient::new()?.blocking()?;
+//! let packages = client.search("yay")?;
+//! println!("Found {} packages", packages.len());
+//! # Ok::<(), arch_toolkit::error::ArchToolkitError>(())
+//! ```
+
+use tokio::runtime::{Builder, Runtime};
+
+use crate::aur::AurApi;
+use crate::client::ArchClient;
+use crate::error::{ArchToolkitError, Result};
+use crate::types::{AurComment, AurPackage, AurPackageDetails};
+
+/// What: Build the current-thread runtime used by blocking clients.
+///
+/// Inputs: None
+///
+/// Output:
+/// - A runtime with I/O and timer drivers enabled
+///
+/// # Errors
+/// - Returns `Err(ArchToolkitError::Runtime)` if the runtime cannot be created
+fn build_runtime() -> Result<Runtime> {
+    Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(ArchToolkitError::Runtime)
+}
+
+/// What: Blocking wrapper around an `ArchClient`.
+///
+/// Inputs: None (created via `ArchClient::blocking()`)
+///
+/// Output: Client whose AUR methods return results directly instead of futures
+///
+/// Details:
+/// - Methods mirror `ArchClient::aur()` and share its caching, rate limiting and retries
+/// - Panics if a method is called from within an async runtime (see the module docs)
+#[derive(Debug)]
+pub struct BlockingArchClient {
+    /// Wrapped async client.
+    client: ArchClient,
+    /// Runtime driving the async operations.
+    runtime: Runtime,
+}
+
+impl BlockingArchClient {
+    /// What: Wrap a client with a new current-thread runtime.
+    ///
+    /// Inputs:
+    /// - `client`: Configured async client
+    ///
+    /// Output:
+    /// - `Result<BlockingArchClient>`
+    ///
+    /// # Errors
+    /// - Returns `Err(ArchToolkitError::Runtime)` if the runtime cannot be created
+    pub(crate) fn new(client: ArchClient) -> Result<Self> {
+        Ok(Self {
+            client,
+            runtime: build_runtime()?,
+        })
+    }
+
+    /// What: Get the wrapped async client.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - Reference to the `ArchClient`, e.g. for `cache_stats()` or `invalidate_cache()`
+    #[must_use]
+    pub const fn client(&self) -> &ArchClient {
+        &self.client
+    }
+
+    /// What: Search for packages in the AUR, blocking until the results arrive.
+    ///
+    /// Inputs:
+    /// - `query`: Search query string
+    ///
+    /// Output:
+    /// - `Result<Vec<AurPackage>>` as returned by `Aur::search()`
+    ///
+    /// # Errors
+    /// - Same as `Aur::search()`
+    ///
+    /// # Panics
+    /// - Panics if called from within an async runtime
+    pub fn search(&self, query: &str) -> Result<Vec<AurPackage>> {
+        self.runtime.block_on(self.client.aur().search(query))
+    }
+
+    /// What: Fetch detailed information for packages, blocking until the results arrive.
+    ///
+    /// Inputs:
+    /// - `names`: Package names
+    ///
+    /// Output:
+    /// - `Result<Vec<AurPackageDetails>>` as returned by `Aur::info()`
+    ///
+    /// # Errors
+    /// - Same as `Aur::info()`
+    ///
+    /// # Panics
+    /// - Panics if called from within an async runtime
+    pub fn info(&self, names: &[&str]) -> Result<Vec<AurPackageDetails>> {
+        self.runtime.block_on(self.client.aur().info(names))
+    }
+
+    /// What: Fetch the comments of a package, blocking until they arrive.
+    ///
+    /// Inputs:
+    /// - `pkgname`: Package name
+    ///
+    /// Output:
+    /// - `Result<Vec<AurComment>>` as returned by `Aur::comments()`
+    ///
+    /// # Errors
+    /// - Same as `Aur::comments()`
+    ///
+    /// # Panics
+    /// - Panics if called from within an async runtime
+    pub fn comments(&self, pkgname: &str) -> Result<Vec<AurComment>> {
+        self.runtime.block_on(self.client.aur().comments(pkgname))
+    }
+
+    /// What: Fetch the PKGBUILD of a package, blocking until it arrives.
+    ///
+    /// Inputs:
+    /// - `package`: Package name
+    ///
+    /// Output:
+    /// - `Result<String>` as returned by `Aur::pkgbuild()`
+    ///
+    /// # Errors
+    /// - Same as `Aur::pkgbuild()`
+    ///
+    /// # Panics
+    /// - Panics if called from within an async runtime
+    pub fn pkgbuild(&self, package: &str) -> Result<String> {
+        self.runtime.block_on(self.client.aur().pkgbuild(package))
+    }
+}
+
+/// What: Blocking wrapper around any `AurApi` implementation.
+///
+/// Inputs: None (created via `BlockingAurApi::new()`)
+///
+/// Output: Synchronous `search`, `info`, `comments` and `pkgbuild` methods
+///
+/// Details:
+/// - Lets synchronous code be tested against `MockAurApi` or another custom implementation
+/// - Panics if a method is called from within an async runtime (see the module docs)
+///
+/// # Example
+///
+/// ```
+/// use arch_toolkit::MockAurApi;
+/// use arch_toolkit::blocking::BlockingAurApi;
+///
+/// let mock = MockAurApi::new().with_pkgbuild_result("yay", Ok("pkgname=yay".to_string()));
+/// let api = BlockingAurApi::new(mock)?;
+/// assert_eq!(api.pkgbuild("yay")?, "pkgname=yay");
+/// # Ok::<(), arch_toolkit::error::ArchToolkitError>(())
+/// ```
+#[derive(Debug)]
+pub struct BlockingAurApi<A> {
+    /// Wrapped async implementation.
+    api: A,
+    /// Runtime driving the async operations.
+    runtime: Runtime,
+}
+
+impl<A: AurApi> BlockingAurApi<A> {
+    /// What: Wrap an `AurApi` implementation with a new current-thread runtime.
+    ///
+    /// Inputs:
+    /// - `api`: Async implementation, e.g. `MockAurApi`
+    ///
+    /// Output:
+    /// - `Result<BlockingAurApi<A>>`
+    ///
+    /// # Errors
+    /// - Returns `Err(ArchToolkitError::Runtime)` if the runtime cannot be created
+    pub fn new(api: A) -> Result<Self> {
+        Ok(Self {
+            api,
+            runtime: build_runtime()?,
+        })
+    }
+
+    /// What: Get the wrapped implementation.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - Reference to the wrapped `AurApi`
+    #[must_use]
+    pub const fn api(&self) -> &A {
+        &self.api
+    }
+
+    /// What: Search for packages, blocking until the results arrive.
+    ///
+    /// Inputs:
+    /// - `query`: Search query string
+    ///
+    /// Output:
+    /// - `Result<Vec<AurPackage>>` as returned by `AurApi::search()`
+    ///
+    /// # Errors
+    /// - Same as the wrapped `AurApi::search()`
+    ///
+    /// # Panics
+    /// - Panics if called from within an async runtime
+    pub fn search(&self, query: &str) -> Result<Vec<AurPackage>> {
+        self.runtime.block_on(self.api.search(query))
+    }
+
+    /// What: Fetch detailed information for packages, blocking until the results arrive.
+    ///
+    /// Inputs:
+    /// - `names`: Package names
+    ///
+    /// Output:
+    /// - `Result<Vec<AurPackageDetails>>` as returned by `AurApi::info()`
+    ///
+    /// # Errors
+    /// - Same as the wrapped `AurApi::info()`
+    ///
+    /// # Panics
+    /// - Panics if called from within an async runtime
+    pub fn info(&self, names: &[&str]) -> Result<Vec<AurPackageDetails>> {
+        self.runtime.block_on(self.api.info(names))
+    }
+
+    /// What: Fetch the comments of a package, blocking until they arrive.
+    ///
+    /// Inputs:
+    /// - `pkgname`: Package name
+    ///
+    /// Output:
+    /// - `Result<Vec<AurComment>>` as returned by `AurApi::comments()`
+    ///
+    /// # Errors
+    /// - Same as the wrapped `AurApi::comments()`
+    ///
+    /// # Panics
+    /// - Panics if called from within an async runtime
+    pub fn comments(&self, pkgname: &str) -> Result<Vec<AurComment>> {
+        self.runtime.block_on(self.api.comments(pkgname))
+    }
+
+    /// What: Fetch the PKGBUILD of a package, blocking until it arrives.
+    ///
+    /// Inputs:
+    /// - `package`: Package name
+    ///
+    /// Output:
+    /// - `Result<String>` as returned by `AurApi::pkgbuild()`
+    ///
+    /// # Errors
+    /// - Same as the wrapped `AurApi::pkgbuild()`
+    ///
+    /// # Panics
+    /// - Panics if called from within an async runtime
+    pub fn pkgbuild(&self, package: &str) -> Result<String> {
+        self.runtime.block_on(self.api.pkgbuild(package))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aur::MockAurApi;
+
+    fn package(name: &str) -> AurPackage {
+        AurPackage {
+            name: name.to_string(),
+            version: "12.0.0-1".to_string(),
+            description: "AUR helper".to_string(),
+            popularity: None,
+            out_of_date: None,
+            orphaned: false,
+            maintainer: Some("user".to_string()),
+            num_votes: None,
+            last_modified: None,
+            first_submitted: None,
+        }
+    }
+
+    fn mock() -> MockAurApi {
+        MockAurApi::new()
+            .with_search_result("yay", Ok(vec![package("yay"), package("yay-bin")]))
+            .with_info_result(
+                &["yay"],
+                Ok(vec![AurPackageDetails {
+                    name: "yay".to_string(),
+                    version: "12.0.0-1".to_string(),
+                    ..Default::default()
+                }]),
+            )
+            .with_comments_result(
+                "yay",
+                Ok(vec![AurComment {
+                    id: None,
+                    author: "alice".to_string(),
+                    date: "2024-01-01 00:00 (UTC)".to_string(),
+                    date_timestamp: None,
+                    date_url: None,
+                    content: "Works great".to_string(),
+                    pinned: false,
+                    numeric_id: None,
+                    edited: None,
+                    deleted: false,
+                }]),
+            )
+            .with_pkgbuild_result("yay", Ok("pkgname=yay\npkgver=12.0.0\n".to_string()))
+    }
+
+    #[test]
+    fn blocking_search_returns_mock_results() {
+        let api = BlockingAurApi::new(mock()).expect("runtime");
+        let names: Vec<String> = api
+            .search("yay")
+            .expect("search succeeds")
+            .into_iter()
+            .map(|pkg| pkg.name)
+            .collect();
+        assert_eq!(names, ["yay", "yay-bin"]);
+        assert!(api.search("unknown").is_err());
+    }
+
+    #[test]
+    fn blocking_info_returns_mock_results() {
+        let api = BlockingAurApi::new(mock()).expect("runtime");
+        let details = api.info(&["yay"]).expect("info succeeds");
+        assert_eq!(details.len(), 1);
+        assert_eq!(details[0].version, "12.0.0-1");
+    }
+
+    #[test]
+    fn blocking_comments_returns_mock_results() {
+        let api = BlockingAurApi::new(mock()).expect("runtime");
+        let comments = api.comments("yay").expect("comments succeed");
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].author, "alice");
+    }
+
+    #[test]
+    fn blocking_pkgbuild_returns_mock_results() {
+        let api = BlockingAurApi::new(mock()).expect("runtime");
+        let pkgbuild = api.pkgbuild("yay").expect("pkgbuild succeeds");
+        assert!(pkgbuild.starts_with("pkgname=yay"));
+    }
+
+    #[test]
+    fn blocking_errors_pass_through() {
+        let mock = MockAurApi::new().with_pkgbuild_result(
+            "broken",
+            Err(ArchToolkitError::PackageNotFound {
+                package: "broken".to_string(),
+            }),
+        );
+        let api = BlockingAurApi::new(mock).expect("runtime");
+        assert!(matches!(
+            api.pkgbuild("broken"),
+            Err(ArchToolkitError::PackageNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn arch_client_blocking_wraps_client() {
+        let client = ArchClient::builder()
+            .user_agent("blocking-test/1.0")
+            .build()
+            .expect("client builds")
+            .blocking()
+            .expect("runtime");
+        assert!(client.client().cache_stats().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "runtime")]
+    fn blocking_call_inside_runtime_panics() {
+        let api = BlockingAurApi::new(mock()).expect("runtime");
+        let outer = Builder::new_current_thread()
+            .build()
+            .expect("outer runtime");
+        outer.block_on(async {
+            let _ = api.pkgbuild("yay");
+        });
+    }
+}
@@ -14,9 +14,12 @@ use std::time::Duration;
 /// - Configures TTL for each operation type
 /// - Sets memory cache size limits
 /// - Controls disk cache enable/disable
+/// - `enabled = false` turns the whole cache into a no-op regardless of the per-operation flags
 #[derive(Debug, Clone)]
 #[allow(clippy::struct_excessive_bools)] // Per-operation flags are necessary for fine-grained control
 pub struct CacheConfig {
+    /// Whether the cache stores and serves anything at all (default: true).
+    pub enabled: bool,
     /// Whether search operation caching is enabled (default: false).
     pub enable_search: bool,
     /// TTL for search cache entries (default: 5 minutes).
@@ -33,25 +36,44 @@ pub struct CacheConfig {
     pub enable_pkgbuild: bool,
     /// TTL for pkgbuild cache entries (default: 1 hour).
     pub pkgbuild_ttl: Duration,
+    /// Whether news feed caching is enabled (default: false).
+    pub enable_news: bool,
+    /// TTL for news feed cache entries (default: 30 minutes).
+    pub news_ttl: Duration,
+    /// Whether security advisory caching is enabled (default: false).
+    pub enable_advisories: bool,
+    /// TTL for security advisory cache entries (default: 1 hour).
+    pub advisories_ttl: Duration,
     /// Maximum number of entries in memory cache per operation (default: 100).
     pub memory_cache_size: usize,
     /// Whether disk cache is enabled (default: false).
     pub enable_disk_cache: bool,
+    /// Maximum total size of the disk cache in bytes (default: `None`, unbounded).
+    pub max_disk_bytes: Option<u64>,
+    /// Prefix applied to all cache keys (default: empty, no prefix).
+    pub namespace: String,
 }
 
 impl Default for CacheConfig {
     fn default() -> Self {
         Self {
+            enabled: true,
             enable_search: false,
-            search_ttl: Duration::from_secs(300), // 5 minutes
+            search_ttl: Duration::from_mins(5),
             enable_info: false,
-            info_ttl: Duration::from_secs(900), // 15 minutes
+            info_ttl: Duration::from_mins(15),
             enable_comments: false,
-            comments_ttl: Duration::from_secs(600), // 10 minutes
+            comments_ttl: Duration::from_mins(10),
             enable_pkgbuild: false,
-            pkgbuild_ttl: Duration::from_secs(3600), // 1 hour
+            pkgbuild_ttl: Duration::from_hours(1),
+            enable_news: false,
+            news_ttl: Duration::from_mins(30),
+            enable_advisories: false,
+            advisories_ttl: Duration::from_hours(1),
             memory_cache_size: 100,
             enable_disk_cache: false,
+            max_disk_bytes: None,
+            namespace: String::new(),
         }
     }
 }
@@ -92,6 +114,50 @@ impl CacheConfigBuilder {
         }
     }
 
+    /// What: Create a builder for a cache that never stores or serves values.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - `CacheConfigBuilder` with `enabled` set to false
+    ///
+    /// Details:
+    /// - Useful in tests that must hit the network deterministically while keeping a configured client
+    /// - Overrides the per-operation `enable_*` flags: operations still consult the cache when their
+    ///   flag is set, but every lookup misses and every store is dropped
+    /// - No disk cache directory is created, even if `enable_disk_cache` is set
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use arch_toolkit::CacheConfigBuilder;
+    ///
+    /// let config = CacheConfigBuilder::disabled().enable_info(true).build();
+    /// assert!(!config.enabled);
+    /// ```
+    #[must_use]
+    pub fn disabled() -> Self {
+        Self::new().enabled(false)
+    }
+
+    /// What: Enable or disable the cache as a whole.
+    ///
+    /// Inputs:
+    /// - `enabled`: Whether the cache stores and serves values
+    ///
+    /// Output:
+    /// - `&mut Self` for method chaining
+    ///
+    /// Details:
+    /// - Default: true
+    /// - When false, the cache is a no-op regardless of the per-operation flags
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)] // Cannot be const: mutates self
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.config.enabled = enabled;
+        self
+    }
+
     /// What: Enable or disable search operation caching.
     ///
     /// Inputs:
@@ -228,6 +294,76 @@ impl CacheConfigBuilder {
         self
     }
 
+    /// What: Enable or disable news feed caching.
+    ///
+    /// Inputs:
+    /// - `enable`: Whether to enable news caching
+    ///
+    /// Output:
+    /// - `&mut Self` for method chaining
+    ///
+    /// Details:
+    /// - Default: false
+    /// - Only used with the `news` feature
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)] // Cannot be const: mutates self
+    pub fn enable_news(mut self, enable: bool) -> Self {
+        self.config.enable_news = enable;
+        self
+    }
+
+    /// What: Set TTL for news feed cache entries.
+    ///
+    /// Inputs:
+    /// - `ttl`: Time-to-live duration
+    ///
+    /// Output:
+    /// - `&mut Self` for method chaining
+    ///
+    /// Details:
+    /// - Default: 30 minutes
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)] // Cannot be const: mutates self and uses Duration
+    pub fn news_ttl(mut self, ttl: Duration) -> Self {
+        self.config.news_ttl = ttl;
+        self
+    }
+
+    /// What: Enable or disable security advisory caching.
+    ///
+    /// Inputs:
+    /// - `enable`: Whether to enable advisory caching
+    ///
+    /// Output:
+    /// - `&mut Self` for method chaining
+    ///
+    /// Details:
+    /// - Default: false
+    /// - Only used with the `news` feature
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)] // Cannot be const: mutates self
+    pub fn enable_advisories(mut self, enable: bool) -> Self {
+        self.config.enable_advisories = enable;
+        self
+    }
+
+    /// What: Set TTL for security advisory cache entries.
+    ///
+    /// Inputs:
+    /// - `ttl`: Time-to-live duration
+    ///
+    /// Output:
+    /// - `&mut Self` for method chaining
+    ///
+    /// Details:
+    /// - Default: 1 hour
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)] // Cannot be const: mutates self and uses Duration
+    pub fn advisories_ttl(mut self, ttl: Duration) -> Self {
+        self.config.advisories_ttl = ttl;
+        self
+    }
+
     /// What: Set maximum number of entries in memory cache per operation.
     ///
     /// Inputs:
@@ -262,6 +398,42 @@ impl CacheConfigBuilder {
         self
     }
 
+    /// What: Cap the total size of the disk cache.
+    ///
+    /// Inputs:
+    /// - `bytes`: Maximum total size of cache files in bytes
+    ///
+    /// Output:
+    /// - `&mut Self` for method chaining
+    ///
+    /// Details:
+    /// - Default: unbounded
+    /// - Least-recently-accessed entries are evicted when a write exceeds the cap
+    /// - Only applies when the disk cache is enabled
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)] // Cannot be const: mutates self
+    pub fn max_disk_bytes(mut self, bytes: u64) -> Self {
+        self.config.max_disk_bytes = Some(bytes);
+        self
+    }
+
+    /// What: Set a namespace prefixed to all cache keys.
+    ///
+    /// Inputs:
+    /// - `namespace`: Key prefix, e.g. the name of the tool using the cache
+    ///
+    /// Output:
+    /// - `&mut Self` for method chaining
+    ///
+    /// Details:
+    /// - Default: empty (keys are not prefixed)
+    /// - Keys become `"{namespace}:{key}"`, so tools sharing a disk cache directory don't collide
+    #[must_use]
+    pub fn namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.config.namespace = namespace.into();
+        self
+    }
+
     /// What: Build the `CacheConfig` with configured settings.
     ///
     /// Inputs: None
@@ -292,34 +464,52 @@ mod tests {
     #[test]
     fn test_cache_config_default() {
         let config = CacheConfig::default();
+        assert!(config.enabled);
         assert!(!config.enable_search);
         assert!(!config.enable_info);
         assert!(!config.enable_comments);
         assert!(!config.enable_pkgbuild);
+        assert!(!config.enable_news);
+        assert!(!config.enable_advisories);
         assert!(!config.enable_disk_cache);
         assert_eq!(config.memory_cache_size, 100);
-        assert_eq!(config.search_ttl, Duration::from_secs(300));
-        assert_eq!(config.info_ttl, Duration::from_secs(900));
-        assert_eq!(config.comments_ttl, Duration::from_secs(600));
-        assert_eq!(config.pkgbuild_ttl, Duration::from_secs(3600));
+        assert_eq!(config.max_disk_bytes, None);
+        assert!(config.namespace.is_empty());
+        assert_eq!(config.search_ttl, Duration::from_mins(5));
+        assert_eq!(config.info_ttl, Duration::from_mins(15));
+        assert_eq!(config.comments_ttl, Duration::from_mins(10));
+        assert_eq!(config.pkgbuild_ttl, Duration::from_hours(1));
+        assert_eq!(config.news_ttl, Duration::from_mins(30));
+        assert_eq!(config.advisories_ttl, Duration::from_hours(1));
     }
 
     #[test]
     fn test_cache_config_builder() {
         let config = CacheConfigBuilder::new()
             .enable_search(true)
-            .search_ttl(Duration::from_secs(600))
+            .search_ttl(Duration::from_mins(10))
             .enable_info(true)
-            .info_ttl(Duration::from_secs(1800))
+            .info_ttl(Duration::from_mins(30))
             .memory_cache_size(200)
             .enable_disk_cache(true)
+            .max_disk_bytes(64 * 1024 * 1024)
+            .namespace("mytool")
             .build();
 
         assert!(config.enable_search);
         assert!(config.enable_info);
-        assert_eq!(config.search_ttl, Duration::from_secs(600));
-        assert_eq!(config.info_ttl, Duration::from_secs(1800));
+        assert_eq!(config.search_ttl, Duration::from_mins(10));
+        assert_eq!(config.info_ttl, Duration::from_mins(30));
         assert_eq!(config.memory_cache_size, 200);
         assert!(config.enable_disk_cache);
+        assert_eq!(config.max_disk_bytes, Some(64 * 1024 * 1024));
+        assert_eq!(config.namespace, "mytool");
+    }
+
+    #[test]
+    fn test_cache_config_disabled() {
+        let config = CacheConfigBuilder::disabled().enable_search(true).build();
+        assert!(!config.enabled);
+        assert!(config.enable_search);
     }
 }
@@ -1,11 +1,17 @@
 //! Disk cache implementation for persistent caching.
 
+use super::stats::CacheCounters;
 use super::{Cache, CacheError};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+/// Cache subdirectories, one per operation type.
+const SUBDIRS: [&str; 5] = ["search", "info", "comments", "pkgbuild", "srcinfo"];
+
 /// What: Disk cache entry with serialized data and metadata.
 ///
 /// Inputs: None (created internally)
@@ -37,11 +43,18 @@ struct DiskCacheEntry {
 /// - Stores cache entries as JSON files on disk
 /// - Cache directory: `~/.cache/arch-toolkit/`
 /// - Supports TTL-based expiration
+/// - Optional size cap with least-recently-used eviction (file mtime marks the last access)
 /// - Thread-safe via internal synchronization
 #[derive(Debug)]
 pub struct DiskCache {
     /// Base directory for cache files.
     pub(crate) cache_dir: PathBuf,
+    /// Maximum total size of cache files in bytes (`None` = unbounded).
+    pub(crate) max_bytes: Option<u64>,
+    /// Shared statistics counters (hits and size-cap evictions).
+    pub(crate) counters: Arc<CacheCounters>,
+    /// Key namespace stripped before routing keys to subdirectories (empty = none).
+    pub(crate) namespace: String,
 }
 
 impl DiskCache {
@@ -61,11 +74,179 @@ impl DiskCache {
         fs::create_dir_all(&cache_dir)?;
 
         // Create subdirectories for each operation type
-        for subdir in ["search", "info", "comments", "pkgbuild"] {
+        for subdir in SUBDIRS {
             fs::create_dir_all(cache_dir.join(subdir))?;
         }
 
-        Ok(Self { cache_dir })
+        Ok(Self {
+            cache_dir,
+            max_bytes: None,
+            counters: Arc::new(CacheCounters::default()),
+            namespace: String::new(),
+        })
+    }
+
+    /// What: Cap the total size of the disk cache.
+    ///
+    /// Inputs:
+    /// - `max_bytes`: Maximum total size of cache files in bytes (`None` = unbounded)
+    ///
+    /// Output:
+    /// - `DiskCache` with the size cap applied
+    ///
+    /// Details:
+    /// - When a `set` pushes the total size over the cap, least-recently-accessed files
+    ///   are deleted until the cache fits again
+    #[must_use]
+    pub const fn with_max_bytes(mut self, max_bytes: Option<u64>) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// What: Set the key namespace used by `CacheKeys`.
+    ///
+    /// Inputs:
+    /// - `namespace`: Namespace prefix of the keys (empty = none)
+    ///
+    /// Output:
+    /// - `DiskCache` that routes namespaced keys like unprefixed ones
+    ///
+    /// Details:
+    /// - Namespaced entries land in the usual operation subdirectory with a `{namespace}@`
+    ///   filename prefix; sanitized keys never contain `@`, so they can't collide
+    #[must_use]
+    pub fn with_namespace(mut self, namespace: &str) -> Self {
+        self.namespace = namespace.to_string();
+        self
+    }
+
+    /// What: Share statistics counters with the cache.
+    ///
+    /// Inputs:
+    /// - `counters`: Counters to update on hits and evictions
+    ///
+    /// Output:
+    /// - `DiskCache` reporting into `counters`
+    ///
+    /// Details:
+    /// - Used by `CacheWrapper` so all layers report into one set of counters
+    #[must_use]
+    pub fn with_counters(mut self, counters: Arc<CacheCounters>) -> Self {
+        self.counters = counters;
+        self
+    }
+
+    /// What: Compute the total size of all cache files.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - `io::Result<u64>` with the summed size of all cache entry files in bytes
+    ///
+    /// # Errors
+    /// - Returns `Err` if a cache subdirectory cannot be read
+    #[allow(dead_code)] // Public API method for size inspection
+    pub fn current_size(&self) -> io::Result<u64> {
+        Ok(self.entry_files()?.iter().map(|f| f.size).sum())
+    }
+
+    /// What: List all cache entry files with size and last access time.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - `io::Result<Vec<EntryFile>>` with one record per `.json` file
+    ///
+    /// Details:
+    /// - Missing subdirectories are skipped
+    /// - Files whose metadata cannot be read are skipped
+    fn entry_files(&self) -> io::Result<Vec<EntryFile>> {
+        let mut files = Vec::new();
+        for subdir in SUBDIRS {
+            let dir = self.cache_dir.join(subdir);
+            if !dir.exists() {
+                continue;
+            }
+            for entry in fs::read_dir(&dir)? {
+                let entry = entry?;
+                let path = entry.path();
+                if path.extension() != Some(std::ffi::OsStr::new("json")) {
+                    continue;
+                }
+                if let Ok(metadata) = entry.metadata() {
+                    files.push(EntryFile {
+                        accessed: metadata.modified().unwrap_or(UNIX_EPOCH),
+                        size: metadata.len(),
+                        path,
+                    });
+                }
+            }
+        }
+        Ok(files)
+    }
+
+    /// What: Delete least-recently-accessed files until the cache fits its size cap.
+    ///
+    /// Inputs:
+    /// - `keep`: Path of the entry just written, which is never evicted
+    ///
+    /// Output:
+    /// - `io::Result<usize>` with the number of evicted files
+    ///
+    /// Details:
+    /// - No-op without a size cap
+    /// - Files are ordered by mtime (oldest first), ties broken by path
+    /// - If `keep` alone exceeds the cap, the cache may stay over the cap
+    fn evict_to_fit(&self, keep: &Path) -> io::Result<usize> {
+        let Some(max_bytes) = self.max_bytes else {
+            return Ok(0);
+        };
+        let mut files = self.entry_files()?;
+        let mut total: u64 = files.iter().map(|f| f.size).sum();
+        if total <= max_bytes {
+            return Ok(0);
+        }
+
+        files.sort_by(|a, b| {
+            a.accessed
+                .cmp(&b.accessed)
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        let mut evicted = 0;
+        for file in files {
+            if total <= max_bytes {
+                break;
+            }
+            if file.path == keep {
+                continue;
+            }
+            match fs::remove_file(&file.path) {
+                Ok(()) => {
+                    total = total.saturating_sub(file.size);
+                    evicted += 1;
+                }
+                Err(e) => {
+                    tracing::warn!(path = %file.path.display(), error = %e, "failed to evict disk cache entry");
+                }
+            }
+        }
+        tracing::debug!(evicted, total, max_bytes, "disk cache eviction finished");
+        Ok(evicted)
+    }
+
+    /// What: Mark a cache file as recently accessed.
+    ///
+    /// Inputs:
+    /// - `path`: Cache file that was read
+    ///
+    /// Output: None
+    ///
+    /// Details:
+    /// - Sets the file mtime to now; failures are ignored (the entry just ages faster)
+    fn touch(path: &Path) {
+        if let Ok(file) = fs::File::options().append(true).open(path) {
+            let _ = file.set_modified(SystemTime::now());
+        }
     }
 
     /// What: Get the cache directory path.
@@ -103,40 +284,63 @@ impl DiskCache {
     /// - Creates safe filename from key (replaces invalid chars)
     /// - Returns path in appropriate subdirectory
     fn get_file_path(&self, key: &str) -> PathBuf {
+        let full_key = key;
+        let (namespace, key) = if self.namespace.is_empty() {
+            (None, key)
+        } else {
+            key.strip_prefix(self.namespace.as_str())
+                .and_then(|rest| rest.strip_prefix(':'))
+                .map_or((None, key), |rest| (Some(self.namespace.as_str()), rest))
+        };
         let (subdir, key_part) = key
             .strip_prefix("search:")
             .map(|rest| ("search", rest))
             .or_else(|| key.strip_prefix("info:").map(|rest| ("info", rest)))
             .or_else(|| key.strip_prefix("comments:").map(|rest| ("comments", rest)))
             .or_else(|| key.strip_prefix("pkgbuild:").map(|rest| ("pkgbuild", rest)))
+            .or_else(|| key.strip_prefix("srcinfo:").map(|rest| ("srcinfo", rest)))
             .unwrap_or(("search", key));
 
         // Create safe filename (replace invalid chars with underscore)
-        let safe_filename = key_part
-            .chars()
-            .map(|c| {
-                if c.is_alphanumeric() || c == '-' || c == '_' || c == '.' {
-                    c
-                } else {
-                    '_'
-                }
-            })
-            .collect::<String>();
+        let safe_filename = Self::sanitize(key_part);
 
         // Use hash for very long keys to avoid filesystem limits
         let filename = if safe_filename.len() > 200 {
             use std::collections::hash_map::DefaultHasher;
             use std::hash::{Hash, Hasher};
             let mut hasher = DefaultHasher::new();
-            key.hash(&mut hasher);
+            full_key.hash(&mut hasher);
             format!("{:x}", hasher.finish())
         } else {
             safe_filename
         };
+        let filename = match namespace {
+            Some(namespace) => format!("{}@{filename}", Self::sanitize(namespace)),
+            None => filename,
+        };
 
         self.cache_dir.join(subdir).join(format!("{filename}.json"))
     }
 
+    /// What: Make a string safe to use in a filename.
+    ///
+    /// Inputs:
+    /// - `part`: Key or namespace text
+    ///
+    /// Output:
+    /// - String with every character except alphanumerics, `-`, `_`, and `.` replaced by `_`
+    fn sanitize(part: &str) -> String {
+        part.chars()
+            .map(|c| {
+                if c.is_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    }
+
     /// What: Check if a cache entry is expired.
     ///
     /// Inputs:
@@ -151,7 +355,7 @@ impl DiskCache {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .map_or(0, |d| d.as_secs());
-        entry.cached_at + entry.ttl_seconds < now
+        entry.cached_at.saturating_add(entry.ttl_seconds) < now
     }
 
     /// What: Clean up expired entries from disk cache.
@@ -166,7 +370,7 @@ impl DiskCache {
     /// - Logs errors but doesn't fail
     #[allow(dead_code)] // Public API method for manual cleanup
     pub fn cleanup_expired(&self) {
-        for subdir in ["search", "info", "comments", "pkgbuild"] {
+        for subdir in SUBDIRS {
             let dir = self.cache_dir.join(subdir);
             if let Ok(entries) = fs::read_dir(&dir) {
                 for entry in entries.flatten() {
@@ -184,6 +388,21 @@ impl DiskCache {
     }
 }
 
+/// What: Cache file considered for eviction.
+///
+/// Inputs: None (collected by `DiskCache::entry_files()`)
+///
+/// Output:
+/// - Path, size, and last access time of one cache entry file
+struct EntryFile {
+    /// Path of the cache file.
+    path: PathBuf,
+    /// File size in bytes.
+    size: u64,
+    /// Last access time (file mtime).
+    accessed: SystemTime,
+}
+
 impl<K, V> Cache<K, V> for DiskCache
 where
     K: AsRef<str>,
@@ -213,7 +432,12 @@ where
         }
 
         // Deserialize value
-        serde_json::from_str(&entry.data).ok()
+        let value = serde_json::from_str(&entry.data).ok()?;
+        if self.max_bytes.is_some() {
+            Self::touch(&path);
+        }
+        self.counters.record_hit();
+        Some(value)
     }
 
     /// What: Store a value in the disk cache.
@@ -230,6 +454,8 @@ where
     /// - Serializes value to JSON
     /// - Creates cache entry with timestamp and TTL
     /// - Writes to disk atomically (write to temp file, then rename)
+    /// - Evicts least-recently-accessed entries when over the size cap; eviction errors
+    ///   are logged and do not fail the write
     fn set(&self, key: &K, value: &V, ttl: Duration) -> Result<(), CacheError> {
         let path = self.get_file_path(key.as_ref());
         let data =
@@ -253,6 +479,11 @@ where
         fs::write(&temp_path, json).map_err(CacheError::Io)?;
         fs::rename(&temp_path, &path).map_err(CacheError::Io)?;
 
+        match self.evict_to_fit(&path) {
+            Ok(evicted) => self.counters.record_evictions(evicted as u64),
+            Err(e) => tracing::warn!(error = %e, "disk cache eviction failed"),
+        }
+
         Ok(())
     }
 
@@ -285,7 +516,7 @@ where
     /// Details:
     /// - Removes all JSON files from cache subdirectories
     fn clear(&self) -> Result<(), CacheError> {
-        for subdir in ["search", "info", "comments", "pkgbuild"] {
+        for subdir in SUBDIRS {
             let dir = self.cache_dir.join(subdir);
             if dir.exists() {
                 for entry in fs::read_dir(&dir).map_err(CacheError::Io)? {
@@ -315,12 +546,17 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let cache_dir = temp_dir.path().join("cache");
         fs::create_dir_all(&cache_dir).unwrap();
-        for subdir in ["search", "info", "comments", "pkgbuild"] {
+        for subdir in SUBDIRS {
             fs::create_dir_all(cache_dir.join(subdir)).unwrap();
         }
 
         // Create a DiskCache with the test directory
-        let cache = DiskCache { cache_dir };
+        let cache = DiskCache {
+            cache_dir,
+            max_bytes: None,
+            counters: Arc::new(CacheCounters::default()),
+            namespace: String::new(),
+        };
 
         (cache, temp_dir)
     }
@@ -337,7 +573,7 @@ mod tests {
         assert!(<DiskCache as Cache<String, String>>::get(&cache, &key).is_none());
 
         // Set value
-        <DiskCache as Cache<String, String>>::set(&cache, &key, &value, Duration::from_secs(60))
+        <DiskCache as Cache<String, String>>::set(&cache, &key, &value, Duration::from_mins(1))
             .unwrap();
 
         // Get value
@@ -353,7 +589,7 @@ mod tests {
         let key = "test_key".to_string();
         let value = "test_value".to_string();
 
-        <DiskCache as Cache<String, String>>::set(&cache, &key, &value, Duration::from_secs(60))
+        <DiskCache as Cache<String, String>>::set(&cache, &key, &value, Duration::from_mins(1))
             .unwrap();
         assert!(<DiskCache as Cache<String, String>>::get(&cache, &key).is_some());
 
@@ -372,14 +608,14 @@ mod tests {
             &cache,
             &"key1".to_string(),
             &value,
-            Duration::from_secs(60),
+            Duration::from_mins(1),
         )
         .unwrap();
         <DiskCache as Cache<String, String>>::set(
             &cache,
             &"key2".to_string(),
             &value,
-            Duration::from_secs(60),
+            Duration::from_mins(1),
         )
         .unwrap();
 
@@ -388,4 +624,79 @@ mod tests {
         assert!(<DiskCache as Cache<String, String>>::get(&cache, &"key1".to_string()).is_none());
         assert!(<DiskCache as Cache<String, String>>::get(&cache, &"key2".to_string()).is_none());
     }
+
+    /// What: Evict least-recently-accessed entries when the size cap is exceeded.
+    ///
+    /// Inputs:
+    /// - Three entries with staggered mtimes; the oldest one is read (touched) afterwards.
+    /// - A cap that fits three entries, then a fourth write.
+    ///
+    /// Output:
+    /// - The least-recently-accessed entry is evicted; the touched one survives.
+    #[test]
+    fn test_disk_cache_evicts_least_recently_accessed() {
+        let (cache, _temp_dir) = create_test_cache();
+        let value = "x".repeat(100);
+        let ttl = Duration::from_mins(1);
+        let set = |key: &str| {
+            <DiskCache as Cache<String, String>>::set(&cache, &key.to_string(), &value, ttl)
+                .expect("set succeeds");
+        };
+
+        let base = SystemTime::now() - Duration::from_hours(1);
+        for (i, key) in ["info:a", "info:b", "info:c"].into_iter().enumerate() {
+            set(key);
+            let file = fs::File::options()
+                .append(true)
+                .open(cache.get_file_path(key))
+                .expect("entry exists");
+            file.set_modified(base + Duration::from_secs(60 * i as u64))
+                .expect("set mtime");
+        }
+        let entry_size = cache.current_size().expect("size") / 3;
+
+        // Reading "a" makes it the most recently accessed entry
+        let cache = DiskCache {
+            max_bytes: Some(entry_size * 3),
+            ..cache
+        };
+        assert!(<DiskCache as Cache<String, String>>::get(&cache, &"info:a".to_string()).is_some());
+
+        <DiskCache as Cache<String, String>>::set(&cache, &"info:d".to_string(), &value, ttl)
+            .expect("set succeeds");
+
+        assert!(cache.current_size().expect("size") <= entry_size * 3);
+        let exists = |key: &str| cache.get_file_path(key).exists();
+        assert!(exists("info:a"));
+        assert!(!exists("info:b"));
+        assert!(exists("info:c"));
+        assert!(exists("info:d"));
+        assert_eq!(cache.counters.snapshot().evictions, 1);
+    }
+
+    #[test]
+    /// What: Namespaced keys are routed to the operation subdirectory without collisions.
+    ///
+    /// Inputs:
+    /// - Disk cache with namespace `mytool`; a namespaced key and an unprefixed key that
+    ///   sanitizes to the same text.
+    ///
+    /// Output:
+    /// - Both land in `search/` under distinct filenames.
+    fn test_disk_cache_namespaced_paths() {
+        let (cache, _temp_dir) = create_test_cache();
+        let cache = cache.with_namespace("mytool");
+
+        let namespaced = cache.get_file_path("mytool:search:vim");
+        let plain = cache.get_file_path("search:mytool_search_vim");
+        assert_eq!(
+            namespaced,
+            cache.cache_dir.join("search").join("mytool@vim.json")
+        );
+        assert_ne!(namespaced, plain);
+        assert_eq!(
+            cache.get_file_path("search:vim"),
+            cache.cache_dir.join("search").join("vim.json")
+        );
+    }
 }
@@ -1,11 +1,15 @@
 //! In-memory LRU cache implementation.
 
+use super::stats::CacheCounters;
 use super::{Cache, CacheError};
 use lru::LruCache;
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+/// Longest time-to-live an entry is kept, so `Instant` arithmetic cannot overflow.
+const MAX_TTL: Duration = Duration::from_hours(100 * 365 * 24);
+
 /// What: Cache entry with value and expiration timestamp.
 ///
 /// Inputs: None (created internally)
@@ -39,6 +43,8 @@ struct CacheEntry<V> {
 pub struct MemoryCache {
     /// Internal LRU cache wrapped in mutex for thread safety.
     cache: Arc<Mutex<LruCache<String, CacheEntry<Vec<u8>>>>>,
+    /// Shared statistics counters (hits and LRU evictions).
+    counters: Arc<CacheCounters>,
 }
 
 impl MemoryCache {
@@ -59,9 +65,26 @@ impl MemoryCache {
                 std::num::NonZeroUsize::new(capacity.max(1))
                     .expect("capacity.max(1) should always be >= 1"),
             ))),
+            counters: Arc::new(CacheCounters::default()),
         }
     }
 
+    /// What: Share statistics counters with the cache.
+    ///
+    /// Inputs:
+    /// - `counters`: Counters to update on hits and evictions
+    ///
+    /// Output:
+    /// - `MemoryCache` reporting into `counters`
+    ///
+    /// Details:
+    /// - Used by `CacheWrapper` so all layers report into one set of counters
+    #[must_use]
+    pub fn with_counters(mut self, counters: Arc<CacheCounters>) -> Self {
+        self.counters = counters;
+        self
+    }
+
     /// What: Clean up expired entries from cache.
     ///
     /// Inputs: None
@@ -128,6 +151,7 @@ where
         // Clone the serialized value before dropping the guard
         let value = entry.value.clone();
         drop(cache); // Early drop of guard
+        self.counters.record_hit();
 
         // Deserialize value
         serde_json::from_slice(&value).ok()
@@ -145,13 +169,13 @@ where
     ///
     /// Details:
     /// - Serializes value to JSON bytes
-    /// - Stores with expiration timestamp
-    /// - May evict least recently used entry if cache is full
+    /// - Stores with expiration timestamp; `ttl` is capped at 100 years
+    /// - May evict least recently used entry if cache is full; evictions are counted
     fn set(&self, key: &K, value: &V, ttl: Duration) -> Result<(), CacheError> {
         let serialized =
             serde_json::to_vec(value).map_err(|e| CacheError::Serialization(e.to_string()))?;
 
-        let expires_at = Instant::now() + ttl;
+        let expires_at = Instant::now() + ttl.min(MAX_TTL);
         let entry = CacheEntry {
             value: serialized,
             expires_at,
@@ -163,7 +187,12 @@ where
                 Ok(guard) => guard,
                 Err(poisoned) => poisoned.into_inner(),
             };
-            cache.put(key_string, entry);
+            // `push` returns the replaced entry for an existing key, or the evicted LRU entry
+            if let Some((old_key, _)) = cache.push(key_string.clone(), entry)
+                && old_key != key_string
+            {
+                self.counters.record_evictions(1);
+            }
         }
         Ok(())
     }
@@ -234,7 +263,7 @@ mod tests {
             &cache,
             &key,
             &value,
-            StdDuration::from_secs(60),
+            StdDuration::from_mins(1),
         )
         .unwrap();
 
@@ -243,6 +272,24 @@ mod tests {
         assert_eq!(retrieved, Some(value));
     }
 
+    // Allow unwrap in tests - test failures should panic
+    #[allow(clippy::unwrap_used)]
+    #[test]
+    fn test_memory_cache_max_ttl() {
+        let cache = MemoryCache::new(10);
+        let key = "test_key".to_string();
+        let value = "test_value".to_string();
+
+        // An unbounded TTL must not overflow the expiration timestamp
+        <MemoryCache as Cache<String, String>>::set(&cache, &key, &value, StdDuration::MAX)
+            .unwrap();
+
+        assert_eq!(
+            <MemoryCache as Cache<String, String>>::get(&cache, &key),
+            Some(value)
+        );
+    }
+
     // Allow unwrap in tests - test failures should panic
     #[allow(clippy::unwrap_used)]
     #[test]
@@ -284,14 +331,14 @@ mod tests {
             &cache,
             &"key1".to_string(),
             &value1,
-            StdDuration::from_secs(60),
+            StdDuration::from_mins(1),
         )
         .unwrap();
         <MemoryCache as Cache<String, String>>::set(
             &cache,
             &"key2".to_string(),
             &value2,
-            StdDuration::from_secs(60),
+            StdDuration::from_mins(1),
         )
         .unwrap();
 
@@ -303,7 +350,7 @@ mod tests {
             &cache,
             &"key3".to_string(),
             &value3,
-            StdDuration::from_secs(60),
+            StdDuration::from_mins(1),
         )
         .unwrap();
 
@@ -311,6 +358,7 @@ mod tests {
         assert!(<MemoryCache as Cache<String, String>>::get(&cache, &"key1".to_string()).is_some());
         assert!(<MemoryCache as Cache<String, String>>::get(&cache, &"key2".to_string()).is_none());
         assert!(<MemoryCache as Cache<String, String>>::get(&cache, &"key3".to_string()).is_some());
+        assert_eq!(cache.counters.snapshot().evictions, 1);
     }
 
     // Allow unwrap in tests - test failures should panic
@@ -325,7 +373,7 @@ mod tests {
             &cache,
             &key,
             &value,
-            StdDuration::from_secs(60),
+            StdDuration::from_mins(1),
         )
         .unwrap();
         assert!(<MemoryCache as Cache<String, String>>::get(&cache, &key).is_some());
@@ -345,14 +393,14 @@ mod tests {
             &cache,
             &"key1".to_string(),
             &value,
-            StdDuration::from_secs(60),
+            StdDuration::from_mins(1),
         )
         .unwrap();
         <MemoryCache as Cache<String, String>>::set(
             &cache,
             &"key2".to_string(),
             &value,
-            StdDuration::from_secs(60),
+            StdDuration::from_mins(1),
         )
         .unwrap();
 
@@ -380,7 +428,7 @@ mod tests {
                         &cache_clone,
                         &key,
                         &value,
-                        StdDuration::from_secs(60),
+                        StdDuration::from_mins(1),
                     )
                     .unwrap();
                 }
@@ -10,9 +10,13 @@ mod config;
 mod disk;
 #[cfg(feature = "aur")]
 mod memory;
+#[cfg(feature = "aur")]
+mod stats;
 
 #[cfg(feature = "aur")]
 pub use config::{CacheConfig, CacheConfigBuilder};
+#[cfg(feature = "aur")]
+pub use stats::CacheStats;
 
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
@@ -149,6 +153,48 @@ pub fn cache_key_search(query: &str) -> String {
     format!("search:{trimmed}")
 }
 
+/// What: Generate cache key for a search on a specific field.
+///
+/// Inputs:
+/// - `query`: Search query string
+/// - `by`: Field searched
+///
+/// Output:
+/// - `String` containing normalized cache key
+///
+/// Details:
+/// - Default (`SearchBy::NameDesc`) searches use `cache_key_search()`
+/// - Other fields use the format `"search_by:{by}:{query}"`; the separate `search_by:`
+///   namespace keeps them from colliding with name/description searches for any query text
+#[cfg(feature = "aur")]
+#[must_use]
+pub fn cache_key_search_by(query: &str, by: crate::aur::SearchBy) -> String {
+    if by == crate::aur::SearchBy::NameDesc {
+        return cache_key_search(query);
+    }
+    let trimmed = query.trim();
+    format!("search_by:{}:{trimmed}", by.as_str())
+}
+
+/// What: Generate cache key for the uncapped search result set.
+///
+/// Inputs:
+/// - `query`: Search query string
+///
+/// Output:
+/// - `String` containing normalized cache key
+///
+/// Details:
+/// - Normalizes query by trimming whitespace
+/// - Format: `"search_all:{query}"`
+/// - Used by paginated search; distinct from `cache_key_search()`, which holds capped results
+#[cfg(feature = "aur")]
+#[must_use]
+pub fn cache_key_search_all(query: &str) -> String {
+    let trimmed = query.trim();
+    format!("search_all:{trimmed}")
+}
+
 /// What: Generate cache key for info operation.
 ///
 /// Inputs:
@@ -200,8 +246,287 @@ pub fn cache_key_pkgbuild(package: &str) -> String {
     format!("pkgbuild:{package}")
 }
 
+/// What: Generate cache key for a parsed PKGBUILD.
+///
+/// Inputs:
+/// - `package`: Package name
+///
+/// Output:
+/// - `String` containing normalized cache key
+///
+/// Details:
+/// - Format: `"pkgbuild_parsed:{package}"`
+#[cfg(feature = "aur")]
+#[must_use]
+pub fn cache_key_pkgbuild_parsed(package: &str) -> String {
+    format!("pkgbuild_parsed:{package}")
+}
+
+/// What: Generate cache key for srcinfo operation.
+///
+/// Inputs:
+/// - `package`: Package name
+///
+/// Output:
+/// - `String` containing normalized cache key
+///
+/// Details:
+/// - Format: `"srcinfo:{package}"`
+#[cfg(feature = "aur")]
+#[must_use]
+pub fn cache_key_srcinfo(package: &str) -> String {
+    format!("srcinfo:{package}")
+}
+
+/// What: Generate cache key for the Arch Linux news feed.
+///
+/// Inputs: None
+///
+/// Output:
+/// - `String` containing the cache key
+///
+/// Details:
+/// - Format: `"news"` (the feed has a single entry)
+#[cfg(feature = "aur")]
+#[must_use]
+pub fn cache_key_news() -> String {
+    "news".to_string()
+}
+
+/// What: Generate cache key for the Arch security tracker advisory list.
+///
+/// Inputs: None
+///
+/// Output:
+/// - `String` containing the cache key
+///
+/// Details:
+/// - Format: `"advisories"` (the full list is cached as a single entry)
+#[cfg(feature = "aur")]
+#[must_use]
+pub fn cache_key_advisories() -> String {
+    "advisories".to_string()
+}
+
+/// What: Cache key generator bound to an optional namespace.
+///
+/// Inputs: None (created via `CacheKeys::new()` or `default()`)
+///
+/// Output:
+/// - `CacheKeys` whose methods return the cache keys used by the AUR operations
+///
+/// Details:
+/// - Lets several tools share one disk cache directory without colliding on keys like `search:vim`
+/// - A non-empty namespace prefixes every key as `"{namespace}:{key}"`
+/// - An empty namespace (the default) yields the same keys as the `cache_key_*` functions
+///
+/// # Example
+///
+/// ```
+/// use arch_toolkit::cache::CacheKeys;
+///
+/// assert_eq!(CacheKeys::default().search("vim"), "search:vim");
+/// assert_eq!(CacheKeys::new("mytool").search("vim"), "mytool:search:vim");
+/// ```
+#[cfg(feature = "aur")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CacheKeys {
+    /// Prefix applied to every key (empty = no prefix).
+    namespace: String,
+}
+
+#[cfg(feature = "aur")]
+impl CacheKeys {
+    /// What: Create a key generator for a namespace.
+    ///
+    /// Inputs:
+    /// - `namespace`: Key prefix (empty string = no prefix)
+    ///
+    /// Output:
+    /// - `CacheKeys` instance
+    #[must_use]
+    pub fn new(namespace: impl Into<String>) -> Self {
+        Self {
+            namespace: namespace.into(),
+        }
+    }
+
+    /// What: Get the namespace keys are prefixed with.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - Namespace string (empty if keys are not prefixed)
+    #[must_use]
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// What: Apply the namespace prefix to an unprefixed key.
+    ///
+    /// Inputs:
+    /// - `key`: Key produced by a `cache_key_*` function
+    ///
+    /// Output:
+    /// - Namespaced key, or `key` unchanged if the namespace is empty
+    fn prefixed(&self, key: String) -> String {
+        if self.namespace.is_empty() {
+            key
+        } else {
+            format!("{}:{key}", self.namespace)
+        }
+    }
+
+    /// What: Key for a search query (see `cache_key_search()`).
+    #[must_use]
+    pub fn search(&self, query: &str) -> String {
+        self.prefixed(cache_key_search(query))
+    }
+
+    /// What: Key for a search on a specific field (see `cache_key_search_by()`).
+    #[must_use]
+    pub fn search_by(&self, query: &str, by: crate::aur::SearchBy) -> String {
+        self.prefixed(cache_key_search_by(query, by))
+    }
+
+    /// What: Key for the uncapped search result set (see `cache_key_search_all()`).
+    #[must_use]
+    pub fn search_all(&self, query: &str) -> String {
+        self.prefixed(cache_key_search_all(query))
+    }
+
+    /// What: Key for an info request (see `cache_key_info()`).
+    #[must_use]
+    pub fn info(&self, names: &[&str]) -> String {
+        self.prefixed(cache_key_info(names))
+    }
+
+    /// What: Key for package comments (see `cache_key_comments()`).
+    #[must_use]
+    pub fn comments(&self, pkgname: &str) -> String {
+        self.prefixed(cache_key_comments(pkgname))
+    }
+
+    /// What: Key for a PKGBUILD (see `cache_key_pkgbuild()`).
+    #[must_use]
+    pub fn pkgbuild(&self, package: &str) -> String {
+        self.prefixed(cache_key_pkgbuild(package))
+    }
+
+    /// What: Key for a parsed PKGBUILD (see `cache_key_pkgbuild_parsed()`).
+    #[must_use]
+    pub fn pkgbuild_parsed(&self, package: &str) -> String {
+        self.prefixed(cache_key_pkgbuild_parsed(package))
+    }
+
+    /// What: Key for a `.SRCINFO` (see `cache_key_srcinfo()`).
+    #[must_use]
+    pub fn srcinfo(&self, package: &str) -> String {
+        self.prefixed(cache_key_srcinfo(package))
+    }
+
+    /// What: Key for the news feed (see `cache_key_news()`).
+    #[must_use]
+    pub fn news(&self) -> String {
+        self.prefixed(cache_key_news())
+    }
+
+    /// What: Key for the security advisory list (see `cache_key_advisories()`).
+    #[must_use]
+    pub fn advisories(&self) -> String {
+        self.prefixed(cache_key_advisories())
+    }
+}
+
+/// What: Cached text body with its HTTP validator for conditional requests.
+///
+/// Inputs: None (created via `CachedText::new()`)
+///
+/// Output:
+/// - `CachedText` stored in the cache instead of a bare `String`
+///
+/// Details:
+/// - `fresh_until_ms` tracks the logical TTL; the cache entry itself is kept longer so an
+///   expired body can be revalidated with `If-None-Match` instead of re-downloaded.
+/// - Timestamps are wall-clock milliseconds so entries survive in the disk cache.
+#[cfg(feature = "aur")]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedText {
+    /// Cached response body.
+    pub body: String,
+    /// `ETag` header returned with the body, if any.
+    pub etag: Option<String>,
+    /// Unix timestamp in milliseconds until which the body is considered fresh.
+    pub fresh_until_ms: u64,
+}
+
+#[cfg(feature = "aur")]
+impl CachedText {
+    /// What: Create a cached body that is fresh for `ttl`.
+    ///
+    /// Inputs:
+    /// - `body`: Response body
+    /// - `etag`: `ETag` header value, if the server sent one
+    /// - `ttl`: Freshness duration
+    ///
+    /// Output:
+    /// - `CachedText` fresh until now + `ttl`
+    #[must_use]
+    pub fn new(body: String, etag: Option<String>, ttl: Duration) -> Self {
+        Self {
+            body,
+            etag,
+            fresh_until_ms: now_millis().saturating_add(duration_millis(ttl)),
+        }
+    }
+
+    /// What: Check whether the body is still within its TTL.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - `true` if the body can be served without revalidation
+    #[must_use]
+    pub fn is_fresh(&self) -> bool {
+        now_millis() < self.fresh_until_ms
+    }
+}
+
+/// What: Current wall-clock time in milliseconds since the Unix epoch.
+///
+/// Inputs: None
+///
+/// Output:
+/// - Milliseconds since the epoch, or 0 if the clock is before the epoch
+#[cfg(feature = "aur")]
+fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, duration_millis)
+}
+
+/// What: Convert a duration to whole milliseconds, saturating at `u64::MAX`.
+///
+/// Inputs:
+/// - `duration`: Duration to convert
+///
+/// Output:
+/// - Milliseconds as `u64`
+#[cfg(feature = "aur")]
+fn duration_millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+#[cfg(feature = "aur")]
+use lru::LruCache;
 #[cfg(feature = "aur")]
 use memory::MemoryCache;
+#[cfg(feature = "aur")]
+use stats::CacheCounters;
+#[cfg(feature = "aur")]
+use std::collections::BTreeSet;
+#[cfg(feature = "aur")]
+use std::sync::{Arc, Mutex};
 
 #[cfg(feature = "cache-disk")]
 #[cfg(feature = "aur")]
@@ -219,9 +544,20 @@ use disk::DiskCache;
 /// - Optionally uses disk cache if enabled in config
 /// - Checks memory cache first, then disk cache
 /// - Writes to both caches when storing
+/// - Tracks hits, misses, sets, and evictions across both layers (see `stats()`)
+/// - When the config is disabled, every lookup misses and every store is dropped
 #[cfg(feature = "aur")]
 #[derive(Debug)]
 pub struct CacheWrapper {
+    /// Whether the cache stores and serves values (`CacheConfig::enabled`).
+    enabled: bool,
+    /// Key generator for the configured namespace.
+    keys: CacheKeys,
+    /// Secondary index: package name -> info cache keys whose name list contains it,
+    /// bounded to `memory_cache_size` names (least recently indexed names are evicted).
+    info_index: Mutex<LruCache<String, BTreeSet<String>>>,
+    /// Statistics counters shared with the cache layers.
+    counters: Arc<CacheCounters>,
     /// In-memory LRU cache.
     memory: MemoryCache,
     /// Optional disk cache.
@@ -241,28 +577,64 @@ impl CacheWrapper {
     ///
     /// Details:
     /// - Always creates memory cache
-    /// - Creates disk cache if enabled in config
+    /// - Creates disk cache if enabled in config (never for a disabled config)
     /// - Returns error if disk cache creation fails
     ///
     /// # Errors
     /// - Returns `Err(CacheError::Io)` if disk cache directory creation fails
     pub fn new(config: &CacheConfig) -> Result<Self, CacheError> {
-        let memory = MemoryCache::new(config.memory_cache_size);
+        let counters = Arc::new(CacheCounters::default());
+        let info_index = Mutex::new(LruCache::new(
+            std::num::NonZeroUsize::new(config.memory_cache_size)
+                .unwrap_or(std::num::NonZeroUsize::MIN),
+        ));
+        let memory =
+            MemoryCache::new(config.memory_cache_size).with_counters(Arc::clone(&counters));
         #[cfg(feature = "cache-disk")]
         {
-            let disk = if config.enable_disk_cache {
-                Some(DiskCache::new().map_err(CacheError::Io)?)
+            let disk = if config.enabled && config.enable_disk_cache {
+                Some(
+                    DiskCache::new()
+                        .map_err(CacheError::Io)?
+                        .with_max_bytes(config.max_disk_bytes)
+                        .with_namespace(&config.namespace)
+                        .with_counters(Arc::clone(&counters)),
+                )
             } else {
                 None
             };
-            Ok(Self { memory, disk })
+            Ok(Self {
+                enabled: config.enabled,
+                keys: CacheKeys::new(config.namespace.clone()),
+                info_index,
+                counters,
+                memory,
+                disk,
+            })
         }
         #[cfg(not(feature = "cache-disk"))]
         {
-            Ok(Self { memory })
+            Ok(Self {
+                enabled: config.enabled,
+                keys: CacheKeys::new(config.namespace.clone()),
+                info_index,
+                counters,
+                memory,
+            })
         }
     }
 
+    /// What: Get the key generator for this cache's namespace.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - `CacheKeys` configured with `CacheConfig::namespace`
+    #[must_use]
+    pub const fn keys(&self) -> &CacheKeys {
+        &self.keys
+    }
+
     /// What: Get a value from cache (checks memory first, then disk).
     ///
     /// Inputs:
@@ -275,11 +647,17 @@ impl CacheWrapper {
     /// - Checks memory cache first (fastest)
     /// - Falls back to disk cache if memory miss
     /// - Promotes disk cache hits to memory cache
+    /// - Counts a miss when neither layer has the key
+    /// - Always misses when the cache is disabled
     #[must_use]
     pub fn get<V>(&self, key: &str) -> Option<V>
     where
         V: Clone + Serialize + for<'de> Deserialize<'de>,
     {
+        if !self.enabled {
+            self.counters.record_miss();
+            return None;
+        }
         let key_str = key.to_string();
         // Try memory cache first
         if let Some(value) = <MemoryCache as Cache<String, V>>::get(&self.memory, &key_str) {
@@ -296,11 +674,12 @@ impl CacheWrapper {
                 &self.memory,
                 &key_str,
                 &value,
-                Duration::from_secs(300),
+                Duration::from_mins(5),
             );
             return Some(value);
         }
 
+        self.counters.record_miss();
         None
     }
 
@@ -318,6 +697,8 @@ impl CacheWrapper {
     /// - Writes to memory cache (always)
     /// - Writes to disk cache if enabled
     /// - Errors in disk cache don't prevent memory cache write
+    /// - Counts one set per call, regardless of how many layers are written
+    /// - No-op returning `Ok(())` when the cache is disabled
     ///
     /// # Errors
     /// - Returns `Err(CacheError::Serialization)` if value serialization fails
@@ -325,6 +706,9 @@ impl CacheWrapper {
     where
         V: Clone + Serialize + for<'de> Deserialize<'de>,
     {
+        if !self.enabled {
+            return Ok(());
+        }
         let key_str = key.to_string();
         // Always write to memory cache
         <MemoryCache as Cache<String, V>>::set(&self.memory, &key_str, value, ttl)?;
@@ -335,6 +719,94 @@ impl CacheWrapper {
             let _ = <DiskCache as Cache<String, V>>::set(disk, &key_str, value, ttl);
         }
 
+        self.counters.record_set();
+        Ok(())
+    }
+
+    /// What: Snapshot the cache statistics.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - `CacheStats` with hits, misses, sets, and evictions since creation
+    ///
+    /// Details:
+    /// - Counters are cumulative; `clear()` and `invalidate()` do not reset them
+    #[must_use]
+    pub fn stats(&self) -> CacheStats {
+        self.counters.snapshot()
+    }
+
+    /// What: Store an info result and index its key under every package name.
+    ///
+    /// Inputs:
+    /// - `names`: Package names the info request was made for
+    /// - `value`: Info result to cache
+    /// - `ttl`: Time-to-live duration
+    ///
+    /// Output:
+    /// - `Result<(), CacheError>` indicating success or failure
+    ///
+    /// Details:
+    /// - Stores under `keys().info(names)` like `set()`
+    /// - Records the key for each name so `invalidate_info_package()` can find it
+    /// - The index holds at most `memory_cache_size` names; entries for evicted names
+    ///   are no longer found by `invalidate_info_package()` and expire by TTL
+    /// - No-op when the cache is disabled
+    ///
+    /// # Errors
+    /// - Returns `Err(CacheError::Serialization)` if value serialization fails
+    pub fn set_info<V>(&self, names: &[&str], value: &V, ttl: Duration) -> Result<(), CacheError>
+    where
+        V: Clone + Serialize + for<'de> Deserialize<'de>,
+    {
+        if !self.enabled {
+            return Ok(());
+        }
+        let key = self.keys.info(names);
+        self.set(&key, value, ttl)?;
+        let mut index = match self.info_index.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        for name in names {
+            index
+                .get_or_insert_mut((*name).to_string(), BTreeSet::new)
+                .insert(key.clone());
+        }
+        drop(index);
+        Ok(())
+    }
+
+    /// What: Invalidate every info cache entry that includes a package.
+    ///
+    /// Inputs:
+    /// - `name`: Package name
+    ///
+    /// Output:
+    /// - `Result<(), CacheError>` indicating success or failure
+    ///
+    /// Details:
+    /// - Removes all entries stored via `set_info()` whose name list contains `name`
+    /// - Always removes the single-package key `keys().info(&[name])`, which also covers
+    ///   disk entries written by earlier processes; multi-package disk entries from earlier
+    ///   processes and entries whose names were evicted from the bounded index are not
+    ///   found and expire by TTL
+    ///
+    /// # Errors
+    /// - Returns `Err(CacheError::Io)` if disk cache file removal fails (disk cache only)
+    pub fn invalidate_info_package(&self, name: &str) -> Result<(), CacheError> {
+        let mut keys = {
+            let mut index = match self.info_index.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            index.pop(name).unwrap_or_default()
+        };
+        keys.insert(self.keys.info(&[name]));
+        for key in keys {
+            self.invalidate(&key)?;
+        }
         Ok(())
     }
 
@@ -370,12 +842,16 @@ impl CacheWrapper {
     /// - `Result<(), CacheError>` indicating success or failure
     ///
     /// Details:
-    /// - Clears memory cache
+    /// - Clears memory cache and the info key index
     /// - Clears disk cache if enabled
     ///
     /// # Errors
     /// - Returns `Err(CacheError::Io)` if disk cache cleanup fails (disk cache only)
     pub fn clear(&self) -> Result<(), CacheError> {
+        match self.info_index.lock() {
+            Ok(mut guard) => guard.clear(),
+            Err(poisoned) => poisoned.into_inner().clear(),
+        }
         <MemoryCache as Cache<String, ()>>::clear(&self.memory)?;
         #[cfg(feature = "cache-disk")]
         if let Some(ref disk) = self.disk {
@@ -384,3 +860,148 @@ impl CacheWrapper {
         Ok(())
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "aur")]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// What: Count a miss, a set, and a hit on the cache wrapper.
+    ///
+    /// Inputs:
+    /// - Memory-only cache; lookup of a missing key, then set and lookup again.
+    ///
+    /// Output:
+    /// - One miss, one set, one hit, and no evictions.
+    fn test_cache_wrapper_stats_miss_then_hit() {
+        let config = CacheConfigBuilder::new().enable_info(true).build();
+        let cache = CacheWrapper::new(&config).expect("cache builds");
+
+        assert!(cache.get::<String>("info:foo").is_none());
+        cache
+            .set("info:foo", &"bar".to_string(), Duration::from_mins(1))
+            .expect("set succeeds");
+        assert_eq!(cache.get::<String>("info:foo").as_deref(), Some("bar"));
+
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                hits: 1,
+                misses: 1,
+                sets: 1,
+                evictions: 0,
+            }
+        );
+        assert!((cache.stats().hit_rate() - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    /// What: A disabled cache never serves a stored value.
+    ///
+    /// Inputs:
+    /// - Disabled config with info caching enabled; set then get a key.
+    ///
+    /// Output:
+    /// - Lookup misses and no set is counted.
+    fn test_cache_wrapper_disabled_never_serves() {
+        let config = CacheConfigBuilder::disabled().enable_info(true).build();
+        let cache = CacheWrapper::new(&config).expect("cache builds");
+
+        cache
+            .set("info:foo", &"bar".to_string(), Duration::from_mins(1))
+            .expect("set is a no-op");
+        assert!(cache.get::<String>("info:foo").is_none());
+        assert_eq!(cache.stats().sets, 0);
+        assert_eq!(cache.stats().misses, 1);
+    }
+
+    #[test]
+    /// What: Namespaced keys are prefixed; the default namespace keeps the old keys.
+    ///
+    /// Inputs:
+    /// - `CacheKeys` with and without a namespace.
+    ///
+    /// Output:
+    /// - Unprefixed keys match the `cache_key_*` functions; prefixed keys differ.
+    fn test_cache_keys_namespace() {
+        let plain = CacheKeys::default();
+        assert_eq!(plain.search("vim"), cache_key_search("vim"));
+        assert_eq!(plain.info(&["b", "a"]), cache_key_info(&["a", "b"]));
+        assert_eq!(plain.srcinfo("foo"), cache_key_srcinfo("foo"));
+        assert_eq!(
+            plain.pkgbuild_parsed("foo"),
+            cache_key_pkgbuild_parsed("foo")
+        );
+
+        let keys = CacheKeys::new("mytool");
+        assert_eq!(keys.search("vim"), "mytool:search:vim");
+        assert_eq!(
+            keys.search_by("foo", crate::aur::SearchBy::Maintainer),
+            "mytool:search_by:maintainer:foo"
+        );
+        assert_eq!(keys.info(&["b", "a"]), "mytool:info:a,b");
+        assert_eq!(keys.pkgbuild("foo"), "mytool:pkgbuild:foo");
+    }
+
+    #[test]
+    /// What: Invalidating one package removes every info entry that includes it.
+    ///
+    /// Inputs:
+    /// - Info entries for `["a", "b"]` and `["c"]`; invalidate `"a"`.
+    ///
+    /// Output:
+    /// - The `["a", "b"]` entry is gone; the `["c"]` entry remains.
+    fn test_invalidate_info_package() {
+        let config = CacheConfigBuilder::new().enable_info(true).build();
+        let cache = CacheWrapper::new(&config).expect("cache builds");
+        let ttl = Duration::from_mins(1);
+
+        cache
+            .set_info(&["b", "a"], &"ab".to_string(), ttl)
+            .expect("set succeeds");
+        cache
+            .set_info(&["c"], &"c".to_string(), ttl)
+            .expect("set succeeds");
+        cache
+            .invalidate_info_package("a")
+            .expect("invalidate succeeds");
+
+        assert!(cache.get::<String>(&cache_key_info(&["a", "b"])).is_none());
+        assert_eq!(
+            cache.get::<String>(&cache_key_info(&["c"])).as_deref(),
+            Some("c")
+        );
+    }
+
+    #[test]
+    /// What: The info key index never grows past the memory cache size.
+    ///
+    /// Inputs:
+    /// - Memory cache size 2; info entries for three packages.
+    ///
+    /// Output:
+    /// - The index holds two names; the least recently indexed one is evicted.
+    fn test_info_index_is_bounded() {
+        let config = CacheConfigBuilder::new()
+            .enable_info(true)
+            .memory_cache_size(2)
+            .build();
+        let cache = CacheWrapper::new(&config).expect("cache builds");
+        let ttl = Duration::from_mins(1);
+
+        for name in ["a", "b", "c"] {
+            cache
+                .set_info(&[name], &name.to_string(), ttl)
+                .expect("set succeeds");
+        }
+
+        let (len, has_a, has_c) = {
+            let index = cache.info_index.lock().expect("index lock");
+            (index.len(), index.contains("a"), index.contains("c"))
+        };
+        assert_eq!(len, 2);
+        assert!(!has_a);
+        assert!(has_c);
+    }
+}
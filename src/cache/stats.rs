@@ -0,0 +1,110 @@
+//! Cache statistics counters.
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// What: Snapshot of cache activity counters.
+///
+/// Inputs: None (created via `CacheWrapper::stats()` or `ArchClient::cache_stats()`)
+///
+/// Output:
+/// - Counter values at the time of the snapshot
+///
+/// Details:
+/// - `hits` counts lookups served by the memory or disk layer
+/// - `misses` counts lookups no layer could serve
+/// - `sets` counts values stored through the cache
+/// - `evictions` counts entries dropped to make room (memory LRU and disk size cap);
+///   TTL expirations are not evictions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct CacheStats {
+    /// Number of lookups served from cache.
+    pub hits: u64,
+    /// Number of lookups not found in cache.
+    pub misses: u64,
+    /// Number of values stored in cache.
+    pub sets: u64,
+    /// Number of entries evicted to make room.
+    pub evictions: u64,
+}
+
+impl CacheStats {
+    /// What: Fraction of lookups served from cache.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - Hit rate in `0.0..=1.0`, or `0.0` if there were no lookups
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Counter values stay far below 2^52
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// What: Shared atomic counters updated by the cache layers.
+///
+/// Inputs: None (created via `CacheCounters::default()`)
+///
+/// Output:
+/// - Counters that can be incremented concurrently and read via `snapshot()`
+///
+/// Details:
+/// - Shared between `CacheWrapper` and its layers through an `Arc`
+/// - Uses relaxed ordering; counters are statistics, not synchronization
+#[derive(Debug, Default)]
+pub struct CacheCounters {
+    /// Lookups served from cache.
+    hits: AtomicU64,
+    /// Lookups not found in cache.
+    misses: AtomicU64,
+    /// Values stored in cache.
+    sets: AtomicU64,
+    /// Entries evicted to make room.
+    evictions: AtomicU64,
+}
+
+impl CacheCounters {
+    /// What: Record a cache hit.
+    pub fn record_hit(&self) {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// What: Record a cache miss.
+    pub fn record_miss(&self) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// What: Record a stored value.
+    pub fn record_set(&self) {
+        self.sets.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// What: Record evicted entries.
+    ///
+    /// Inputs:
+    /// - `count`: Number of entries evicted
+    pub fn record_evictions(&self, count: u64) {
+        self.evictions.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// What: Read the current counter values.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - `CacheStats` snapshot
+    pub fn snapshot(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            sets: self.sets.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+        }
+    }
+}
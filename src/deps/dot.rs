@@ -0,0 +1,296 @@
+//! Graphviz DOT export for dependency graphs.
+//!
+//! Renders `DependencyResolution` and `ReverseDependencyReport` results as `digraph`
+//! documents, e.g. for `dot -Tsvg`. Nodes are colored by `DependencyStatus`.
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use crate::types::dependency::{
+    Dependency, DependencyKind, DependencyResolution, DependencyStatus, ReverseDependencyReport,
+};
+
+/// What: Render a dependency resolution as a Graphviz DOT graph.
+///
+/// Inputs:
+/// - `resolution`: Result of `DependencyResolver::resolve()`.
+///
+/// Output:
+/// - A `digraph dependencies` document with one node per dependency.
+///
+/// Details:
+/// - Edges point from a package to the dependency it requires, following both
+///   `required_by` (parent -> dependency) and `depends_on` (dependency -> child).
+/// - Packages that only appear as parents (the resolved roots) keep the default white fill.
+/// - Optional dependencies use dashed edges from their parents.
+/// - Fill colors: installed green, to-install blue, to-upgrade yellow, conflict red,
+///   missing gray.
+///
+/// # Example
+///
+/// ```
+/// use arch_toolkit::deps::to_dot;
+/// use arch_toolkit::DependencyResolution;
+///
+/// let dot = to_dot(&DependencyResolution::default());
+/// assert!(dot.starts_with("digraph dependencies {"));
+/// ```
+#[must_use]
+pub fn to_dot(resolution: &DependencyResolution) -> String {
+    let mut edges: BTreeSet<(&str, &str, bool)> = BTreeSet::new();
+    for dep in &resolution.dependencies {
+        let optional = dep.kind == DependencyKind::Opt;
+        for parent in &dep.required_by {
+            edges.insert((parent, &dep.name, optional));
+        }
+        for child in &dep.depends_on {
+            edges.insert((&dep.name, child, false));
+        }
+    }
+
+    render("dependencies", &resolution.dependencies, &[], &edges)
+}
+
+/// What: Render a reverse dependency report as a Graphviz DOT graph.
+///
+/// Inputs:
+/// - `report`: Result of `ReverseDependencyAnalyzer::analyze()`.
+///
+/// Output:
+/// - A `digraph reverse_dependencies` document with one node per dependent and per target.
+///
+/// Details:
+/// - Edges point from a dependent to the package it depends on, following `depends_on`
+///   (the parents recorded during traversal). `required_by` (the removal targets) is used for
+///   dependents without recorded parents.
+/// - Removal targets are drawn with a bold outline; optional dependents use dashed edges.
+///
+/// # Example
+///
+/// ```
+/// use arch_toolkit::deps::reverse_to_dot;
+/// use arch_toolkit::ReverseDependencyReport;
+///
+/// let dot = reverse_to_dot(&ReverseDependencyReport::default());
+/// assert!(dot.starts_with("digraph reverse_dependencies {"));
+/// ```
+#[must_use]
+pub fn reverse_to_dot(report: &ReverseDependencyReport) -> String {
+    let mut edges: BTreeSet<(&str, &str, bool)> = BTreeSet::new();
+    for dep in &report.dependents {
+        let optional = dep.kind == DependencyKind::Opt;
+        let targets = if dep.depends_on.is_empty() {
+            &dep.required_by
+        } else {
+            &dep.depends_on
+        };
+        for target in targets {
+            edges.insert((&dep.name, target, optional));
+        }
+    }
+
+    let roots: Vec<&str> = report
+        .summaries
+        .iter()
+        .map(|summary| summary.package.as_str())
+        .collect();
+    render("reverse_dependencies", &report.dependents, &roots, &edges)
+}
+
+/// What: Write the DOT document shared by both exporters.
+///
+/// Inputs:
+/// - `graph`: Graph identifier.
+/// - `deps`: Packages drawn as filled nodes.
+/// - `roots`: Additional packages drawn with a bold outline.
+/// - `edges`: Deduplicated `(from, to, optional)` edges.
+///
+/// Output:
+/// - The complete DOT document.
+fn render(
+    graph: &str,
+    deps: &[Dependency],
+    roots: &[&str],
+    edges: &BTreeSet<(&str, &str, bool)>,
+) -> String {
+    let mut out = format!("digraph {graph} {{\n");
+    out.push_str("  rankdir=LR;\n");
+    out.push_str("  node [shape=box, style=filled, fillcolor=white];\n");
+
+    for root in roots {
+        let _ = writeln!(out, "  {} [style=\"filled,bold\"];", quote(root));
+    }
+    for dep in deps {
+        let _ = writeln!(
+            out,
+            "  {} [fillcolor={}, tooltip={}];",
+            quote(&dep.name),
+            status_color(&dep.status),
+            quote(&dep.status.to_string())
+        );
+    }
+    for (from, to, optional) in edges {
+        let style = if *optional { " [style=dashed]" } else { "" };
+        let _ = writeln!(out, "  {} -> {}{style};", quote(from), quote(to));
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// What: Pick the node fill color for a dependency status.
+const fn status_color(status: &DependencyStatus) -> &'static str {
+    match status {
+        DependencyStatus::Installed { .. } => "palegreen",
+        DependencyStatus::ToInstall => "lightblue",
+        DependencyStatus::ToUpgrade { .. } => "yellow",
+        DependencyStatus::Conflict { .. } => "red",
+        DependencyStatus::Missing => "gray",
+    }
+}
+
+/// What: Quote a string as a DOT identifier.
+///
+/// Details:
+/// - Escapes backslashes, double quotes and line breaks so any package name is a valid ID.
+fn quote(name: &str) -> String {
+    let mut quoted = String::with_capacity(name.len() + 2);
+    quoted.push('"');
+    for c in name.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => {}
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::dependency::{DependencySource, InstallReason, ReverseDependencySummary};
+
+    fn dep(name: &str, status: DependencyStatus, required_by: &[&str]) -> Dependency {
+        Dependency {
+            name: name.to_string(),
+            version_req: String::new(),
+            status,
+            source: DependencySource::Aur,
+            required_by: required_by.iter().map(ToString::to_string).collect(),
+            depends_on: Vec::new(),
+            is_core: false,
+            is_system: false,
+            kind: DependencyKind::Runtime,
+            explicit: false,
+            install_reason: InstallReason::Unknown,
+            satisfied_by: None,
+        }
+    }
+
+    #[test]
+    /// What: Render a small resolution and check header, nodes, colors and edges.
+    ///
+    /// Inputs:
+    /// - `app` requires `lib` (to install) and `tool` (to upgrade); `lib` requires `tool`.
+    ///
+    /// Output:
+    /// - One node line per dependency and one edge line per relationship.
+    fn to_dot_renders_nodes_and_edges() {
+        let mut lib = dep("lib", DependencyStatus::ToInstall, &["app"]);
+        lib.depends_on = vec!["tool".to_string()];
+        let tool = dep(
+            "tool",
+            DependencyStatus::ToUpgrade {
+                current: "1".to_string(),
+                required: "2".to_string(),
+            },
+            &["app", "lib"],
+        );
+        let resolution = DependencyResolution {
+            dependencies: vec![lib, tool],
+            ..Default::default()
+        };
+
+        let dot = to_dot(&resolution);
+
+        assert!(dot.starts_with("digraph dependencies {\n"));
+        assert!(dot.trim_end().ends_with('}'));
+        assert_eq!(
+            dot.matches("fillcolor=").count(),
+            3,
+            "default + one per dependency"
+        );
+        assert!(dot.contains("\"lib\" [fillcolor=lightblue"));
+        assert!(dot.contains("\"tool\" [fillcolor=yellow"));
+        let edges: Vec<&str> = dot
+            .lines()
+            .filter(|line| line.contains("\" -> \""))
+            .collect();
+        assert_eq!(
+            edges,
+            [
+                "  \"app\" -> \"lib\";",
+                "  \"app\" -> \"tool\";",
+                "  \"lib\" -> \"tool\";",
+            ]
+        );
+    }
+
+    #[test]
+    /// What: Render a reverse report with optional dependents and bold targets.
+    ///
+    /// Inputs:
+    /// - `app` requires target `lib`, `suite` requires `app`, `viewer` optionally uses `lib`.
+    ///
+    /// Output:
+    /// - Conflict-colored dependents, a bold target node and a dashed optional edge.
+    fn reverse_to_dot_renders_dependents() {
+        let reason = || DependencyStatus::Conflict {
+            reason: "requires lib".to_string(),
+        };
+        let app = dep("app", reason(), &["lib"]);
+        let mut suite = dep("suite", reason(), &["lib"]);
+        suite.depends_on = vec!["app".to_string()];
+        let mut viewer = dep("viewer", reason(), &["lib"]);
+        viewer.kind = DependencyKind::Opt;
+        let report = ReverseDependencyReport {
+            dependents: vec![app, suite, viewer],
+            summaries: vec![ReverseDependencySummary {
+                package: "lib".to_string(),
+                ..Default::default()
+            }],
+            cycles: Vec::new(),
+        };
+
+        let dot = reverse_to_dot(&report);
+
+        assert!(dot.starts_with("digraph reverse_dependencies {\n"));
+        assert!(dot.contains("  \"lib\" [style=\"filled,bold\"];"));
+        assert_eq!(dot.matches("fillcolor=red").count(), 3);
+        let edges: Vec<&str> = dot
+            .lines()
+            .filter(|line| line.contains("\" -> \""))
+            .collect();
+        assert_eq!(
+            edges,
+            [
+                "  \"app\" -> \"lib\";",
+                "  \"suite\" -> \"app\";",
+                "  \"viewer\" -> \"lib\" [style=dashed];",
+            ]
+        );
+    }
+
+    #[test]
+    /// What: Ensure special characters in names produce valid quoted identifiers.
+    fn quote_escapes_special_characters() {
+        assert_eq!(quote("gtk+3"), "\"gtk+3\"");
+        assert_eq!(quote("a\"b"), "\"a\\\"b\"");
+        assert_eq!(quote("a\\b"), "\"a\\\\b\"");
+        assert_eq!(quote("a\nb"), "\"a\\nb\"");
+    }
+}
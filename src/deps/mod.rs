@@ -13,7 +13,8 @@
 //! arch-toolkit = { version = "0.1.2", features = ["deps"] }
 //! ```
 //!
-//! Some functions require the `aur` feature for AUR integration:
+//! Some functions require the `aur` feature for AUR integration and async variants of the
+//! reverse dependency helpers (`ReverseDependencyAnalyzer::analyze_async`):
 //!
 //! ```toml
 //! [dependencies]
@@ -29,6 +30,11 @@
 //! - **Package Querying**: Query installed packages, upgradable packages, and package versions
 //! - **Dependency Resolution**: Resolve dependencies for packages from official repos, AUR, or local packages
 //! - **Reverse Dependency Analysis**: Find all packages that depend on a given package
+//! - **Transaction Planning**: Combine both directions into a dry-run summary of an
+//!   install/remove transaction
+//! - **Command Execution**: Route `pacman` and AUR helper calls through a [`CommandRunner`],
+//!   e.g. to resolve against canned output in tests
+//! - **Graph Export**: Render resolutions and reverse dependency reports as Graphviz DOT
 //!
 //! All functions gracefully degrade when pacman is unavailable, returning empty sets or `None`
 //! as appropriate rather than failing.
@@ -170,39 +176,68 @@
 //! - [`examples/resolve_example.rs`](https://github.com/Firstp1ck/arch-toolkit/blob/main/examples/resolve_example.rs) - Dependency resolution examples
 //! - [`examples/reverse_example.rs`](https://github.com/Firstp1ck/arch-toolkit/blob/main/examples/reverse_example.rs) - Reverse dependency analysis examples
 
+mod dot;
 mod parse;
 mod pkgbuild;
 mod query;
+mod rebuild;
 mod resolve;
 mod reverse;
+mod runner;
 mod source;
 mod srcinfo;
+mod transaction;
 mod version;
 
+pub use dot::{reverse_to_dot, to_dot};
 // Re-export parsing functions
 pub use parse::{parse_dep_spec, parse_pacman_si_conflicts, parse_pacman_si_deps};
-pub use pkgbuild::{parse_pkgbuild_conflicts, parse_pkgbuild_deps};
+#[cfg(feature = "sandbox")]
+pub(crate) use pkgbuild::parse_array_content;
+pub use pkgbuild::{
+    parse_pkgbuild, parse_pkgbuild_arch_deps, parse_pkgbuild_array, parse_pkgbuild_conflicts,
+    parse_pkgbuild_deps,
+};
 pub use query::{
     get_available_version, get_installed_packages, get_installed_version, get_provided_packages,
-    get_upgradable_packages, is_package_installed_or_provided,
+    get_upgradable_packages, is_package_installed_or_provided, try_get_installed_packages,
+    try_get_upgradable_packages,
 };
+pub use rebuild::rebuild_candidates;
 pub use resolve::{
     DependencyResolver, batch_fetch_official_deps, determine_status, fetch_package_conflicts,
 };
 pub use reverse::{
-    ReverseDependencyAnalyzer, get_installed_required_by, has_installed_required_by,
+    ReverseDependencyAnalyzer, get_installed_conflicts, get_installed_replaces,
+    get_installed_required_by, has_installed_required_by, try_get_installed_required_by,
+    try_has_installed_required_by,
+};
+#[cfg(test)]
+pub(crate) use runner::MockRunner;
+pub use runner::{CommandRunner, SystemRunner};
+pub use source::{determine_dependency_source, is_system_package, is_system_package_with};
+pub use srcinfo::{
+    parse_srcinfo, parse_srcinfo_arch_deps, parse_srcinfo_conflicts, parse_srcinfo_deps,
 };
-pub use source::{determine_dependency_source, is_system_package};
-pub use srcinfo::{parse_srcinfo, parse_srcinfo_conflicts, parse_srcinfo_deps};
+pub use transaction::{plan_transaction, plan_transaction_with};
 pub use version::{
-    compare_versions, extract_major_component, is_major_version_bump, version_satisfies,
+    compare_versions, extract_major_component, is_major_version_bump, provide_satisfies,
+    version_satisfies,
 };
 
 // AUR integration (requires aur feature)
 #[cfg(feature = "aur")]
+pub use query::{last_upgrade_time, parse_last_upgrade_time};
+#[cfg(feature = "aur")]
+pub use resolve::enhance_with_srcinfo_async;
+#[cfg(feature = "aur")]
+pub use reverse::{get_installed_required_by_async, has_installed_required_by_async};
+#[cfg(feature = "aur")]
 pub use srcinfo::fetch_srcinfo;
 
 // Re-export types from types module
 pub use crate::types::dependency::{
-    DependencyResolution, ResolverConfig, ReverseDependencyReport, ReverseDependencySummary,
+    DEFAULT_MAX_PACKAGES, DependencyResolution, ResolveOverrides, ResolverConfig,
+    ReverseDependencyReport, ReverseDependencySummary, ReverseOptions, ReverseProgress,
+    SourceConfig, TransactionPlan,
 };
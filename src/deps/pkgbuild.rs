@@ -5,11 +5,101 @@
 //!
 //! The parser extracts dependency arrays (depends, makedepends, checkdepends, optdepends)
 //! and conflicts from PKGBUILD content, handling both single-line and multi-line
-//! bash array syntax.
+//! bash array syntax. Other arrays (e.g. `source` and checksums) can be read with
+//! `parse_pkgbuild_array`, and `parse_pkgbuild` collects the common fields into a
+//! `ParsedPkgbuild`.
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use crate::deps::parse::parse_dep_spec;
+use crate::types::{ArchDependencies, DependencyArrays, ParsedPkgbuild};
+
+/// Checksum arrays recognized by `parse_pkgbuild()`, see `makepkg`'s `INTEGRITY_CHECK`.
+const CHECKSUM_ARRAYS: [&str; 8] = [
+    "cksums",
+    "md5sums",
+    "sha1sums",
+    "sha224sums",
+    "sha256sums",
+    "sha384sums",
+    "sha512sums",
+    "b2sums",
+];
+
+/// What: Parse the commonly used fields of a PKGBUILD into a typed structure.
+///
+/// Inputs:
+/// - `pkgbuild`: Raw PKGBUILD file content.
+///
+/// Output:
+/// - `ParsedPkgbuild` with names, version, dependency arrays, sources and checksums.
+///
+/// Details:
+/// - Dependency arrays and conflicts are parsed with `parse_pkgbuild_deps()` and
+///   `parse_pkgbuild_conflicts()` (expanded, filtered, deduplicated)
+/// - `pkgname` may be a scalar or an array (split packages); for split packages `$pkgname`
+///   expands to the first name, like in bash
+/// - `source` entries are expanded where all referenced variables are known and kept
+///   verbatim otherwise; checksums are not expanded
+/// - Only checksum arrays that are present are included in `checksums`
+/// - Missing scalar fields are left empty
+///
+/// # Example
+///
+/// ```
+/// use arch_toolkit::deps::parse_pkgbuild;
+///
+/// let parsed = parse_pkgbuild(
+///     "pkgname=foo\npkgver=1.2\npkgrel=1\nsource=(\"https://example.com/$pkgname-$pkgver.tar.gz\")\n",
+/// );
+/// assert_eq!(parsed.pkgname, ["foo"]);
+/// assert_eq!(parsed.source, ["https://example.com/foo-1.2.tar.gz"]);
+/// ```
+#[must_use]
+pub fn parse_pkgbuild(pkgbuild: &str) -> ParsedPkgbuild {
+    let mut variables = collect_variables(pkgbuild);
+    let expand = |value: String, variables: &HashMap<String, String>| {
+        expand_variables(&value, variables).unwrap_or(value)
+    };
+
+    let mut pkgname: Vec<String> = parse_pkgbuild_array(pkgbuild, "pkgname")
+        .into_iter()
+        .map(|name| expand(name, &variables))
+        .collect();
+    if let Some(first) = pkgname.first() {
+        variables
+            .entry("pkgname".to_string())
+            .or_insert_with(|| first.clone());
+    } else if let Some(name) = variables.get("pkgname") {
+        pkgname.push(name.clone());
+    }
+
+    let (depends, makedepends, checkdepends, optdepends) = parse_pkgbuild_deps(pkgbuild);
+    let source = parse_pkgbuild_array(pkgbuild, "source")
+        .into_iter()
+        .map(|entry| expand(entry, &variables))
+        .collect();
+    let checksums = CHECKSUM_ARRAYS
+        .iter()
+        .filter_map(|&field| {
+            let values = parse_pkgbuild_array(pkgbuild, field);
+            (!values.is_empty()).then(|| (field.to_string(), values))
+        })
+        .collect();
+
+    ParsedPkgbuild {
+        pkgname,
+        pkgver: variables.get("pkgver").cloned().unwrap_or_default(),
+        pkgrel: variables.get("pkgrel").cloned().unwrap_or_default(),
+        depends,
+        makedepends,
+        checkdepends,
+        optdepends,
+        conflicts: parse_pkgbuild_conflicts(pkgbuild),
+        source,
+        checksums,
+    }
+}
 
 /// What: Parse dependencies from PKGBUILD content.
 ///
@@ -30,8 +120,12 @@ use crate::deps::parse::parse_dep_spec;
 ///       'bar>=1.2'
 ///   )
 ///   ```
+/// - Expands `$var` and `${var}` from simple top-level assignments (`_pyver=3.12`);
+///   entries with unresolvable references are dropped
 /// - Filters out .so files (virtual packages) and invalid package names
 /// - Only parses specific dependency fields (depends, makedepends, checkdepends, optdepends)
+/// - Architecture-specific arrays (`depends_x86_64`) are skipped; use
+///   `parse_pkgbuild_arch_deps()` for those
 /// - Deduplicates dependencies (returns unique list)
 #[allow(clippy::case_sensitive_file_extension_comparisons)]
 #[must_use]
@@ -47,6 +141,7 @@ pub fn parse_pkgbuild_deps(pkgbuild: &str) -> (Vec<String>, Vec<String>, Vec<Str
     let mut seen_checkdepends = HashSet::new();
     let mut seen_optdepends = HashSet::new();
 
+    let variables = collect_variables(pkgbuild);
     let lines: Vec<&str> = pkgbuild.lines().collect();
     let mut i = 0;
 
@@ -138,8 +233,9 @@ pub fn parse_pkgbuild_deps(pkgbuild: &str) -> (Vec<String>, Vec<String>, Vec<Str
                             return None;
                         }
 
-                        if is_valid_dependency(dep_trimmed) {
-                            Some(dep_trimmed.to_string())
+                        let dep_expanded = expand_variables(dep_trimmed, &variables)?;
+                        if is_valid_dependency(&dep_expanded) {
+                            Some(dep_expanded)
                         } else {
                             None
                         }
@@ -186,6 +282,78 @@ pub fn parse_pkgbuild_deps(pkgbuild: &str) -> (Vec<String>, Vec<String>, Vec<Str
     (depends, makedepends, checkdepends, optdepends)
 }
 
+/// What: Parse dependencies from PKGBUILD content, keeping architecture-specific arrays apart.
+///
+/// Inputs:
+/// - `pkgbuild`: Raw PKGBUILD file content.
+///
+/// Output:
+/// - Returns `ArchDependencies` with the generic arrays and one entry per architecture.
+///
+/// Details:
+/// - Generic arrays are parsed exactly like `parse_pkgbuild_deps()`
+/// - `depends_<arch>`, `makedepends_<arch>`, `checkdepends_<arch>` and `optdepends_<arch>`
+///   arrays (including `+=` appends) are collected under `<arch>`
+/// - Entries are expanded, filtered and deduplicated like the generic arrays
+/// - Use `ArchDependencies::deps_for_arch()` to get the runtime dependencies for one
+///   architecture
+#[must_use]
+pub fn parse_pkgbuild_arch_deps(pkgbuild: &str) -> ArchDependencies {
+    let (depends, makedepends, checkdepends, optdepends) = parse_pkgbuild_deps(pkgbuild);
+    let mut by_arch: BTreeMap<String, DependencyArrays> = BTreeMap::new();
+    let mut seen_keys = HashSet::new();
+    let variables = collect_variables(pkgbuild);
+
+    for line in pkgbuild.lines() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        let key = key.strip_suffix('+').map_or(key, |stripped| stripped);
+        let Some((field, arch)) = key.split_once('_') else {
+            continue;
+        };
+        if arch.is_empty()
+            || !value.trim_start().starts_with('(')
+            || !matches!(
+                field,
+                "depends" | "makedepends" | "checkdepends" | "optdepends"
+            )
+            || !seen_keys.insert(key)
+        {
+            continue;
+        }
+
+        // parse_pkgbuild_array collects every declaration and append of this key at once
+        let arrays = by_arch.entry(arch.to_string()).or_default();
+        let Some(target) = arrays.field_mut(field) else {
+            continue;
+        };
+        for dep in parse_pkgbuild_array(pkgbuild, key) {
+            let Some(dep) = expand_variables(dep.trim(), &variables) else {
+                continue;
+            };
+            if is_valid_dependency(&dep) && !target.contains(&dep) {
+                target.push(dep);
+            }
+        }
+    }
+
+    ArchDependencies {
+        generic: DependencyArrays {
+            depends,
+            makedepends,
+            checkdepends,
+            optdepends,
+        },
+        by_arch,
+    }
+}
+
 /// What: Parse conflicts from PKGBUILD content.
 ///
 /// Inputs:
@@ -205,6 +373,7 @@ pub fn parse_pkgbuild_deps(pkgbuild: &str) -> (Vec<String>, Vec<String>, Vec<Str
 ///       'bar'
 ///   )
 ///   ```
+/// - Expands variables like `parse_pkgbuild_deps()`
 /// - Filters out .so files (virtual packages) and invalid package names
 /// - Extracts package names from version constraints (e.g., "jujutsu-git>=1.0" -> "jujutsu-git")
 /// - Deduplicates conflicts (returns unique list)
@@ -214,6 +383,7 @@ pub fn parse_pkgbuild_conflicts(pkgbuild: &str) -> Vec<String> {
     let mut conflicts = Vec::new();
     let mut seen = HashSet::new();
 
+    let variables = collect_variables(pkgbuild);
     let lines: Vec<&str> = pkgbuild.lines().collect();
     let mut i = 0;
 
@@ -301,10 +471,11 @@ pub fn parse_pkgbuild_conflicts(pkgbuild: &str) -> Vec<String> {
                             return None;
                         }
 
-                        if is_valid_dependency(conflict_trimmed) {
+                        let conflict_expanded = expand_variables(conflict_trimmed, &variables)?;
+                        if is_valid_dependency(&conflict_expanded) {
                             // Extract package name (remove version constraints if present)
                             // Use a simple approach: split on version operators
-                            let spec = parse_dep_spec(conflict_trimmed);
+                            let spec = parse_dep_spec(&conflict_expanded);
                             if !spec.name.is_empty() && seen.insert(spec.name.clone()) {
                                 Some(spec.name)
                             } else {
@@ -325,6 +496,173 @@ pub fn parse_pkgbuild_conflicts(pkgbuild: &str) -> Vec<String> {
     conflicts
 }
 
+/// What: Parse the values of any bash array field from PKGBUILD content.
+///
+/// Inputs:
+/// - `pkgbuild`: Raw PKGBUILD file content.
+/// - `field`: Array name, e.g. `source` or `sha256sums`.
+///
+/// Output:
+/// - Array values in declaration order, with quotes removed.
+///
+/// Details:
+/// - Handles single-line and multi-line arrays, plus `field+=` appends
+/// - Unlike the dependency parsers, values are neither filtered nor deduplicated,
+///   so positional arrays (`source` and its checksum arrays) stay aligned
+/// - Comment lines inside multi-line arrays are skipped
+/// - Variables are not expanded
+#[must_use]
+pub fn parse_pkgbuild_array(pkgbuild: &str, field: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut lines = pkgbuild.lines();
+
+    while let Some(line) = lines.next() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        let base_key = key.strip_suffix('+').map_or(key, |stripped| stripped);
+        let value = value.trim();
+        if base_key != field || !value.starts_with('(') {
+            continue;
+        }
+
+        // Accumulate lines until the array's closing parenthesis is found
+        let mut array = value.to_string();
+        while find_matching_closing_paren(&array).is_none() {
+            let Some(next_line) = lines.next() else {
+                break;
+            };
+            let next_line = next_line.trim();
+            if !next_line.starts_with('#') {
+                array.push(' ');
+                array.push_str(next_line);
+            }
+        }
+
+        let content = find_matching_closing_paren(&array).map_or_else(
+            || &array[1..],
+            |closing_paren_pos| &array[1..closing_paren_pos],
+        );
+        values.extend(parse_array_content(content));
+    }
+
+    values
+}
+
+/// What: Collect simple top-level variable assignments from a PKGBUILD.
+///
+/// Inputs:
+/// - `pkgbuild`: Raw PKGBUILD file content.
+///
+/// Output:
+/// - Map of variable name to value, e.g. `_pyver` -> `3.12`.
+///
+/// Details:
+/// - Only unindented `name=value` lines outside function bodies are considered
+/// - Quotes around the value are removed; values may reference earlier variables
+/// - Arrays, command substitutions and values with unresolvable references are skipped
+fn collect_variables(pkgbuild: &str) -> HashMap<String, String> {
+    let mut variables = HashMap::new();
+    let mut function_depth = 0usize;
+
+    for line in pkgbuild.lines() {
+        let trimmed = line.trim();
+        if trimmed.ends_with('{') {
+            function_depth += 1;
+            continue;
+        }
+        if trimmed.starts_with('}') {
+            function_depth = function_depth.saturating_sub(1);
+            continue;
+        }
+        if function_depth > 0 || line.starts_with(char::is_whitespace) {
+            continue;
+        }
+
+        let Some((name, value)) = trimmed.split_once('=') else {
+            continue;
+        };
+        if !is_variable_name(name) || value.starts_with('(') || value.contains("$(") {
+            continue;
+        }
+        let value = value.split(" #").next().unwrap_or_default().trim();
+        let value = match value.as_bytes() {
+            [b'\'', .., b'\''] => Some(value[1..value.len() - 1].to_string()),
+            [b'"', .., b'"'] => expand_variables(&value[1..value.len() - 1], &variables),
+            _ if value.contains(char::is_whitespace) => None,
+            _ => expand_variables(value, &variables),
+        };
+        if let Some(value) = value {
+            variables.insert(name.to_string(), value);
+        }
+    }
+
+    variables
+}
+
+/// What: Substitute `$var` and `${var}` references in a value.
+///
+/// Inputs:
+/// - `value`: Text containing variable references.
+/// - `variables`: Known variables from `collect_variables()`.
+///
+/// Output:
+/// - `Some(String)` with all references replaced, or `None` if a variable is unknown or
+///   uses parameter expansion beyond `${var}` (e.g. `${var%.*}`).
+///
+/// Details:
+/// - A `$` not followed by a variable name is kept literally.
+fn expand_variables(value: &str, variables: &HashMap<String, String>) -> Option<String> {
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(pos) = rest.find('$') {
+        expanded.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+        let (name, remaining) = if let Some(braced) = after.strip_prefix('{') {
+            let (name, remaining) = braced.split_once('}')?;
+            if !is_variable_name(name) {
+                return None;
+            }
+            (name, remaining)
+        } else {
+            let len = after
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .unwrap_or(after.len());
+            after.split_at(len)
+        };
+        if name.is_empty() {
+            expanded.push('$');
+        } else {
+            expanded.push_str(variables.get(name)?);
+        }
+        rest = remaining;
+    }
+
+    expanded.push_str(rest);
+    Some(expanded)
+}
+
+/// What: Check whether a string is a valid shell variable name.
+///
+/// Inputs:
+/// - `name`: Candidate name.
+///
+/// Output:
+/// - `true` for names like `pkgname` or `_pyver`.
+fn is_variable_name(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
 /// What: Find the position of the matching closing parenthesis in a string.
 ///
 /// Inputs:
@@ -377,7 +715,8 @@ fn find_matching_closing_paren(s: &str) -> Option<usize> {
 /// Details:
 /// - Handles both quoted ('foo') and unquoted (foo) dependencies.
 /// - Splits on whitespace for unquoted values.
-fn parse_array_content(content: &str) -> Vec<String> {
+/// - Also used by the `sandbox` module to tokenize shell command lines.
+pub fn parse_array_content(content: &str) -> Vec<String> {
     let mut deps = Vec::new();
     let mut in_quotes = false;
     let mut quote_char = '\0';
@@ -495,6 +834,133 @@ fn is_valid_dependency(dep: &str) -> bool {
 mod tests {
     use super::*;
 
+    // === parse_pkgbuild_array tests ===
+
+    #[test]
+    /// What: Parse positional arrays in order, across lines and appends.
+    ///
+    /// Inputs:
+    /// - Single-line, multi-line (with comment), and `+=` declarations.
+    ///
+    /// Output:
+    /// - Values in declaration order, duplicates kept, other fields ignored.
+    fn test_parse_pkgbuild_array_positional() {
+        let pkgbuild = r#"
+source=("$pkgname-$pkgver.tar.gz::https://example.com/v$pkgver.tar.gz"
+        # local patch
+        'fix (build).patch')
+source+=(extra.conf)
+sha256sums=('SKIP' 'SKIP'
+            'abc123')
+b2sums=('SKIP')
+"#;
+
+        assert_eq!(
+            parse_pkgbuild_array(pkgbuild, "source"),
+            vec![
+                "$pkgname-$pkgver.tar.gz::https://example.com/v$pkgver.tar.gz",
+                "fix (build).patch",
+                "extra.conf",
+            ]
+        );
+        assert_eq!(
+            parse_pkgbuild_array(pkgbuild, "sha256sums"),
+            vec!["SKIP", "SKIP", "abc123"]
+        );
+        assert_eq!(parse_pkgbuild_array(pkgbuild, "b2sums"), vec!["SKIP"]);
+        assert!(parse_pkgbuild_array(pkgbuild, "md5sums").is_empty());
+    }
+
+    #[test]
+    /// What: Parse a representative PKGBUILD into `ParsedPkgbuild`.
+    ///
+    /// Inputs:
+    /// - PKGBUILD with variables, multi-line arrays, appends, comments and two checksum arrays.
+    ///
+    /// Output:
+    /// - Every field is populated; variables in sources are expanded.
+    fn test_parse_pkgbuild_fields() {
+        let pkgbuild = r#"
+# Maintainer: Someone <someone@example.com>
+_pkgname=foo
+pkgname=foo-bin
+pkgver=2.4.1
+pkgrel=3
+arch=('x86_64')
+depends=('glibc' 'openssl>=3.0')
+makedepends=(
+    'cmake'
+    # build system
+    'ninja'
+)
+checkdepends=('python-pytest')
+optdepends=('bash-completion: completions')
+conflicts=("${_pkgname}" 'foo-git>=1.0')
+source=("https://example.com/${_pkgname}-$pkgver.tar.gz"
+        'config.patch')
+source+=("$pkgname.desktop")
+sha256sums=('abc123' 'SKIP' 'def456')
+b2sums=('SKIP' 'SKIP' 'SKIP')
+
+package() {
+    install -Dm755 foo "$pkgdir/usr/bin/foo"
+}
+"#;
+
+        let parsed = parse_pkgbuild(pkgbuild);
+
+        assert_eq!(parsed.pkgname, ["foo-bin"]);
+        assert_eq!(parsed.pkgver, "2.4.1");
+        assert_eq!(parsed.pkgrel, "3");
+        assert_eq!(parsed.depends, ["glibc", "openssl>=3.0"]);
+        assert_eq!(parsed.makedepends, ["cmake", "ninja"]);
+        assert_eq!(parsed.checkdepends, ["python-pytest"]);
+        assert_eq!(parsed.optdepends, ["bash-completion: completions"]);
+        assert_eq!(parsed.conflicts, ["foo", "foo-git"]);
+        assert_eq!(
+            parsed.source,
+            [
+                "https://example.com/foo-2.4.1.tar.gz",
+                "config.patch",
+                "foo-bin.desktop",
+            ]
+        );
+        assert_eq!(parsed.checksums.len(), 2);
+        assert_eq!(parsed.checksums["sha256sums"], ["abc123", "SKIP", "def456"]);
+        assert_eq!(parsed.checksums["b2sums"], ["SKIP", "SKIP", "SKIP"]);
+    }
+
+    #[test]
+    /// What: Parse split package names and keep unresolvable sources verbatim.
+    ///
+    /// Inputs:
+    /// - PKGBUILD with a `pkgname` array and a source using an unknown variable.
+    ///
+    /// Output:
+    /// - All names are listed, `$pkgname` expands to the first one.
+    fn test_parse_pkgbuild_split_package() {
+        let pkgbuild = r#"
+pkgbase=foo
+pkgname=('foo-core' 'foo-extras')
+pkgver=1.0
+source=("$pkgname-$pkgver.tar.gz" "$(echo generated).patch" "$_unknown.txt")
+"#;
+
+        let parsed = parse_pkgbuild(pkgbuild);
+
+        assert_eq!(parsed.pkgname, ["foo-core", "foo-extras"]);
+        assert!(parsed.pkgrel.is_empty());
+        assert_eq!(
+            parsed.source,
+            [
+                "foo-core-1.0.tar.gz",
+                "$(echo generated).patch",
+                "$_unknown.txt"
+            ]
+        );
+        assert!(parsed.checksums.is_empty());
+    }
+
     // === parse_pkgbuild_deps tests ===
 
     #[test]
@@ -756,6 +1222,88 @@ depends=('quoted' unquoted 'another-quoted' unquoted2)
         assert!(depends.contains(&"unquoted2".to_string()));
     }
 
+    #[test]
+    fn test_parse_pkgbuild_deps_expands_variables() {
+        let pkgbuild = r#"
+pkgbase=python-foo
+pkgname=("$pkgbase" "$pkgbase-docs")
+_pyver=3.12
+_name='foo'
+_common="${_name}-common"
+depends=("python${_pyver}" "$_common" "${_name}-data>=1.0")
+makedepends=("python-$_build" 'cmake' "${_pyver%.*}-tools")
+conflicts=("$_name-git")
+
+package() {
+    local _pyver=9.9
+    depends+=("python-$_name")
+}
+"#;
+
+        let (depends, makedepends, ..) = parse_pkgbuild_deps(pkgbuild);
+        assert_eq!(
+            depends,
+            vec!["python3.12", "foo-common", "foo-data>=1.0", "python-foo"]
+        );
+        // Undefined variables and unsupported expansions drop the entry
+        assert_eq!(makedepends, vec!["cmake"]);
+        assert_eq!(parse_pkgbuild_conflicts(pkgbuild), vec!["foo-git"]);
+    }
+
+    #[test]
+    fn test_parse_pkgbuild_deps_expands_pkgname() {
+        let pkgbuild = r"
+pkgname=foo
+pkgver=1.0.0
+depends=($pkgname-common 'glibc')
+";
+
+        let (depends, ..) = parse_pkgbuild_deps(pkgbuild);
+        assert_eq!(depends, vec!["foo-common", "glibc"]);
+    }
+
+    // === parse_pkgbuild_arch_deps tests ===
+
+    #[test]
+    fn test_parse_pkgbuild_arch_deps() {
+        let pkgbuild = r"
+pkgname=test-package
+arch=('x86_64' 'aarch64')
+depends=('glibc' 'zlib')
+depends_x86_64=('intel-media-driver')
+depends_aarch64=(
+    'raspberrypi-firmware'
+    'zlib'
+)
+makedepends=('cmake')
+makedepends_aarch64=('clang')
+package() {
+    depends_aarch64+=('libfoo.so' 'linux-rpi')
+}
+";
+
+        let deps = parse_pkgbuild_arch_deps(pkgbuild);
+        assert_eq!(deps.generic.depends, vec!["glibc", "zlib"]);
+        assert_eq!(deps.generic.makedepends, vec!["cmake"]);
+        assert_eq!(
+            deps.by_arch["aarch64"].depends,
+            vec!["raspberrypi-firmware", "zlib", "linux-rpi"]
+        );
+        assert_eq!(
+            deps.deps_for_arch("aarch64"),
+            vec!["glibc", "zlib", "raspberrypi-firmware", "linux-rpi"]
+        );
+        assert_eq!(
+            deps.deps_for_arch("x86_64"),
+            vec!["glibc", "zlib", "intel-media-driver"]
+        );
+        assert_eq!(deps.for_arch("aarch64").makedepends, vec!["cmake", "clang"]);
+
+        // The generic parser does not mix architecture-specific arrays in
+        let (depends, ..) = parse_pkgbuild_deps(pkgbuild);
+        assert_eq!(depends, vec!["glibc", "zlib"]);
+    }
+
     // === parse_pkgbuild_conflicts tests ===
 
     #[test]
@@ -1,13 +1,120 @@
 //! Package querying functions for dependency resolution.
 //!
 //! This module provides functions to query the pacman database for installed packages,
-//! upgradable packages, provided packages, and package versions. All functions gracefully
-//! degrade when pacman is unavailable, returning empty sets or None as appropriate.
+//! upgradable packages, provided packages, and package versions. Most functions gracefully
+//! degrade when pacman is unavailable, returning empty sets or None as appropriate; the
+//! `try_*` variants report the failure as an `ArchToolkitError` instead.
 
+use crate::deps::reverse::{parse_key_value_output, split_ws_or_none};
+use crate::deps::runner::{CommandRunner, SystemRunner};
 use crate::error::{ArchToolkitError, Result};
+use crate::types::ProvidedPackage;
+#[cfg(feature = "aur")]
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
 use std::collections::HashSet;
 use std::hash::BuildHasher;
-use std::process::{Command, Stdio};
+use std::io::ErrorKind;
+use std::process::Output;
+
+/// Path of pacman's transaction log.
+#[cfg(feature = "aur")]
+const PACMAN_LOG_PATH: &str = "/var/log/pacman.log";
+
+/// Log message pacman writes when `pacman -Syu` starts.
+#[cfg(feature = "aur")]
+const FULL_UPGRADE_MARKER: &str = "[PACMAN] starting full system upgrade";
+
+/// What: Run pacman with a locale-independent environment and capture its output.
+///
+/// Inputs:
+/// - `runner`: Executes the command.
+/// - `args`: Arguments passed to `pacman`.
+///
+/// Output:
+/// - Returns the raw process output, whatever the exit status.
+///
+/// Details:
+/// - Sets `LC_ALL=C` and `LANG=C` for consistent locale-independent output.
+///
+/// # Errors
+///
+/// - Returns `Err(ArchToolkitError::PacmanUnavailable)` when the pacman binary cannot be found.
+/// - Returns `Err(ArchToolkitError::Parse)` when pacman cannot be started for another reason.
+pub(super) fn run_pacman(runner: &dyn CommandRunner, args: &[&str]) -> Result<Output> {
+    tracing::debug!("Running: pacman {}", args.join(" "));
+    runner
+        .run("pacman", args)
+        .map_err(|e| spawn_error(args, &e))
+}
+
+/// What: Run pacman and return its stdout, treating a non-zero exit status as an error.
+///
+/// Inputs:
+/// - `runner`: Executes the command.
+/// - `args`: Arguments passed to `pacman`.
+///
+/// Output:
+/// - Returns stdout decoded lossily as UTF-8.
+///
+/// # Errors
+///
+/// - Returns the errors of `run_pacman`.
+/// - Returns the error built by `exit_error` when pacman exits with a non-zero status.
+pub(super) fn pacman_stdout(runner: &dyn CommandRunner, args: &[&str]) -> Result<String> {
+    let output = run_pacman(runner, args)?;
+    if !output.status.success() {
+        return Err(exit_error(
+            args,
+            output.status.code(),
+            &String::from_utf8_lossy(&output.stderr),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// What: Map a failure to start pacman to an `ArchToolkitError`.
+///
+/// Inputs:
+/// - `args`: Arguments pacman was invoked with.
+/// - `err`: I/O error returned when spawning the process.
+///
+/// Output:
+/// - `PacmanUnavailable` for `ErrorKind::NotFound`, `Parse` otherwise.
+fn spawn_error(args: &[&str], err: &std::io::Error) -> ArchToolkitError {
+    let command = format!("pacman {}", args.join(" "));
+    if err.kind() == ErrorKind::NotFound {
+        ArchToolkitError::PacmanUnavailable { command }
+    } else {
+        ArchToolkitError::Parse(format!("Failed to execute {command}: {err}"))
+    }
+}
+
+/// What: Map a non-zero pacman exit status to an `ArchToolkitError`.
+///
+/// Inputs:
+/// - `args`: Arguments pacman was invoked with.
+/// - `code`: Exit code, if the process was not killed by a signal.
+/// - `stderr`: Captured standard error output.
+///
+/// Output:
+/// - `PackageNotFound` when stderr reports `error: package 'name' was not found`.
+/// - `Parse` with the command, exit code and stderr otherwise.
+fn exit_error(args: &[&str], code: Option<i32>, stderr: &str) -> ArchToolkitError {
+    let missing = stderr.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix("error: package '")?
+            .strip_suffix("' was not found")
+            .map(str::to_string)
+    });
+    if let Some(package) = missing {
+        return ArchToolkitError::PackageNotFound { package };
+    }
+    ArchToolkitError::Parse(format!(
+        "pacman {} exited with status {code:?}: {}",
+        args.join(" "),
+        stderr.trim()
+    ))
+}
 
 /// What: Enumerate all currently installed packages on the system.
 ///
@@ -19,9 +126,8 @@ use std::process::{Command, Stdio};
 /// - Returns `Ok(HashSet::new())` on failure (graceful degradation).
 ///
 /// Details:
-/// - Uses pacman's quiet format to obtain trimmed names.
+/// - Lenient wrapper around `try_get_installed_packages`.
 /// - Logs errors for diagnostics but returns empty set to avoid blocking dependency checks.
-/// - Sets `LC_ALL=C` and `LANG=C` for consistent locale-independent output.
 ///
 /// # Errors
 ///
@@ -37,45 +143,68 @@ use std::process::{Command, Stdio};
 /// println!("Found {} installed packages", installed.len());
 /// ```
 pub fn get_installed_packages() -> Result<HashSet<String>> {
-    tracing::debug!("Running: pacman -Qq");
-    let output = Command::new("pacman")
-        .args(["-Qq"])
-        .env("LC_ALL", "C")
-        .env("LANG", "C")
-        .stdin(Stdio::null())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output();
+    Ok(try_get_installed_packages().unwrap_or_else(|e| {
+        tracing::error!("Failed to list installed packages: {}", e);
+        HashSet::new()
+    }))
+}
 
-    match output {
-        Ok(output) => {
-            if output.status.success() {
-                let text = String::from_utf8_lossy(&output.stdout);
-                let packages: HashSet<String> = text
-                    .lines()
-                    .map(|s| s.trim().to_string())
-                    .filter(|s| !s.is_empty())
-                    .collect();
-                tracing::debug!(
-                    "Successfully retrieved {} installed packages",
-                    packages.len()
-                );
-                Ok(packages)
-            } else {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                tracing::error!(
-                    "pacman -Qq failed with status {:?}: {}",
-                    output.status.code(),
-                    stderr
-                );
-                Ok(HashSet::new())
-            }
-        }
-        Err(e) => {
-            tracing::error!("Failed to execute pacman -Qq: {}", e);
-            Ok(HashSet::new())
-        }
-    }
+/// What: Enumerate all currently installed packages, reporting pacman failures.
+///
+/// Inputs:
+/// - (none): Invokes `pacman -Qq` to query the local database.
+///
+/// Output:
+/// - Returns `Ok(HashSet<String>)` containing package names installed on the machine.
+///
+/// Details:
+/// - Uses pacman's quiet format to obtain trimmed names.
+/// - Sets `LC_ALL=C` and `LANG=C` for consistent locale-independent output.
+///
+/// # Errors
+///
+/// - Returns `Err(ArchToolkitError::PacmanUnavailable)` when pacman is not installed.
+/// - Returns `Err(ArchToolkitError::Parse)` when pacman fails to start or exits with an error.
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::deps::try_get_installed_packages;
+/// use arch_toolkit::error::ArchToolkitError;
+///
+/// match try_get_installed_packages() {
+///     Ok(installed) => println!("Found {} installed packages", installed.len()),
+///     Err(ArchToolkitError::PacmanUnavailable { .. }) => println!("Not an Arch system"),
+///     Err(e) => eprintln!("pacman failed: {e}"),
+/// }
+/// ```
+pub fn try_get_installed_packages() -> Result<HashSet<String>> {
+    installed_packages(&SystemRunner)
+}
+
+/// What: Enumerate installed packages through a command runner.
+///
+/// Inputs:
+/// - `runner`: Executes `pacman -Qq`.
+///
+/// Output:
+/// - Installed package names.
+///
+/// # Errors
+///
+/// - Returns the errors of `try_get_installed_packages`.
+pub(super) fn installed_packages(runner: &dyn CommandRunner) -> Result<HashSet<String>> {
+    let text = pacman_stdout(runner, &["-Qq"])?;
+    let packages: HashSet<String> = text
+        .lines()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    tracing::debug!(
+        "Successfully retrieved {} installed packages",
+        packages.len()
+    );
+    Ok(packages)
 }
 
 /// What: Collect names of packages that have upgrades available via pacman.
@@ -88,10 +217,7 @@ pub fn get_installed_packages() -> Result<HashSet<String>> {
 /// - Returns `Ok(HashSet::new())` on failure (graceful degradation).
 ///
 /// Details:
-/// - Parses output format: "name old-version -> new-version" or just "name" for AUR packages.
-/// - Extracts package name (everything before first space or "->").
-/// - Gracefully handles command failures by returning an empty set to avoid blocking dependency checks.
-/// - Sets `LC_ALL=C` and `LANG=C` for consistent locale-independent output.
+/// - Lenient wrapper around `try_get_upgradable_packages`.
 ///
 /// # Errors
 ///
@@ -107,89 +233,214 @@ pub fn get_installed_packages() -> Result<HashSet<String>> {
 /// println!("Found {} upgradable packages", upgradable.len());
 /// ```
 pub fn get_upgradable_packages() -> Result<HashSet<String>> {
-    tracing::debug!("Running: pacman -Qu");
-    let output = Command::new("pacman")
-        .args(["-Qu"])
-        .env("LC_ALL", "C")
-        .env("LANG", "C")
-        .stdin(Stdio::null())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output();
+    Ok(try_get_upgradable_packages().unwrap_or_else(|e| {
+        tracing::debug!(
+            "Failed to list upgradable packages: {} (assuming no upgrades)",
+            e
+        );
+        HashSet::new()
+    }))
+}
 
-    match output {
-        Ok(output) => {
-            if output.status.success() {
-                let text = String::from_utf8_lossy(&output.stdout);
-                // pacman -Qu outputs "name old-version -> new-version" or just "name" for AUR packages
-                let packages: HashSet<String> = text
-                    .lines()
-                    .filter_map(|line| {
-                        let line = line.trim();
-                        if line.is_empty() {
-                            return None;
-                        }
-                        // Extract package name (everything before space or "->")
-                        Some(line.find(' ').map_or_else(
-                            || line.to_string(),
-                            |space_pos| line[..space_pos].trim().to_string(),
-                        ))
-                    })
-                    .collect();
-                tracing::debug!(
-                    "Successfully retrieved {} upgradable packages",
-                    packages.len()
-                );
-                Ok(packages)
-            } else {
-                // No upgradable packages or error - return empty set
-                tracing::debug!("pacman -Qu returned non-zero status (no upgrades or error)");
-                Ok(HashSet::new())
-            }
-        }
-        Err(e) => {
-            tracing::debug!("Failed to execute pacman -Qu: {} (assuming no upgrades)", e);
-            Ok(HashSet::new())
+/// What: Collect names of upgradable packages, reporting pacman failures.
+///
+/// Inputs:
+/// - (none): Reads upgrade information by invoking `pacman -Qu`.
+///
+/// Output:
+/// - Returns `Ok(HashSet<String>)` containing package names that pacman reports as upgradable.
+///
+/// Details:
+/// - Parses both "name old-version -> new-version" and bare "name" lines.
+/// - pacman exits with status 1 and no stderr output when nothing is upgradable; that case
+///   yields an empty set rather than an error.
+///
+/// # Errors
+///
+/// - Returns `Err(ArchToolkitError::PacmanUnavailable)` when pacman is not installed.
+/// - Returns `Err(ArchToolkitError::Parse)` when pacman fails to start or reports an error.
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::deps::try_get_upgradable_packages;
+///
+/// let upgradable = try_get_upgradable_packages()?;
+/// println!("Found {} upgradable packages", upgradable.len());
+/// # Ok::<(), arch_toolkit::error::ArchToolkitError>(())
+/// ```
+pub fn try_get_upgradable_packages() -> Result<HashSet<String>> {
+    upgradable_packages(&SystemRunner)
+}
+
+/// What: Collect names of upgradable packages through a command runner.
+///
+/// Inputs:
+/// - `runner`: Executes `pacman -Qu`.
+///
+/// Output:
+/// - Upgradable package names.
+///
+/// # Errors
+///
+/// - Returns the errors of `try_get_upgradable_packages`.
+pub(super) fn upgradable_packages(runner: &dyn CommandRunner) -> Result<HashSet<String>> {
+    let args = ["-Qu"];
+    let output = run_pacman(runner, &args)?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if output.status.code() == Some(1) && stderr.trim().is_empty() {
+            tracing::debug!("pacman -Qu reported no upgrades");
+            return Ok(HashSet::new());
         }
+        return Err(exit_error(&args, output.status.code(), &stderr));
     }
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    let packages: HashSet<String> = text
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() {
+                return None;
+            }
+            // Extract package name (everything before space or "->")
+            Some(line.find(' ').map_or_else(
+                || line.to_string(),
+                |space_pos| line[..space_pos].trim().to_string(),
+            ))
+        })
+        .collect();
+    tracing::debug!(
+        "Successfully retrieved {} upgradable packages",
+        packages.len()
+    );
+    Ok(packages)
 }
 
-/// What: Build an empty provides set (for API compatibility).
+/// What: Collect the versioned provides of all installed packages.
 ///
 /// Inputs:
 /// - `installed`: Set of installed package names (unused, kept for API compatibility).
 ///
 /// Output:
-/// - Returns an empty set (provides are now checked lazily).
+/// - Returns one `ProvidedPackage` per `Provides` entry of an installed package
+///   (e.g. `rust` with version `1.70` from `provides=('rust=1.70')`).
+/// - Returns an empty set on failure (graceful degradation).
 ///
 /// Details:
-/// - This function is kept for API compatibility but no longer builds the full provides set.
-/// - Provides are now checked on-demand using `is_package_installed_or_provided()` for better performance.
-/// - This avoids querying all installed packages upfront, which was very slow.
+/// - Runs a single `pacman -Qi` for all installed packages and parses the `Provides` fields.
+/// - Sets `LC_ALL=C` and `LANG=C` for consistent locale-independent output.
+/// - Used by `determine_status()` to compare provided versions against requirements; without
+///   it (an empty set) the provides of each dependency are looked up individually.
 ///
 /// # Example
 ///
-/// ```
+/// ```no_run
 /// use arch_toolkit::deps::{get_installed_packages, get_provided_packages};
 ///
 /// let installed = get_installed_packages().unwrap();
-/// let provided = get_provided_packages(&installed);
-/// assert!(provided.is_empty()); // Always returns empty set
+/// for provide in get_provided_packages(&installed) {
+///     println!("{} {}", provide.name, provide.version.as_deref().unwrap_or("(unversioned)"));
+/// }
 /// ```
 #[must_use]
 pub fn get_provided_packages<S: BuildHasher + Default>(
     _installed: &HashSet<String, S>,
-) -> HashSet<String> {
-    // Return empty set - provides are now checked lazily on-demand
-    // This avoids querying all installed packages upfront, which was very slow
-    HashSet::default()
+) -> HashSet<ProvidedPackage> {
+    provided_packages(&SystemRunner)
+}
+
+/// What: Collect the versioned provides of all installed packages through a command runner.
+///
+/// Inputs:
+/// - `runner`: Executes `pacman -Qi`.
+///
+/// Output:
+/// - Provides of installed packages; empty on failure.
+pub(super) fn provided_packages(runner: &dyn CommandRunner) -> HashSet<ProvidedPackage> {
+    match runner.run("pacman", &["-Qi"]) {
+        Ok(output) if output.status.success() => {
+            let provided = parse_provided_packages(&String::from_utf8_lossy(&output.stdout));
+            tracing::debug!("Found {} provides of installed packages", provided.len());
+            provided
+        }
+        Ok(output) => {
+            tracing::debug!(
+                "pacman -Qi failed with status {:?}, assuming no provides",
+                output.status.code()
+            );
+            HashSet::new()
+        }
+        Err(e) => {
+            tracing::debug!("Failed to execute pacman -Qi: {} (assuming no provides)", e);
+            HashSet::new()
+        }
+    }
+}
+
+/// What: Look up the installed package providing a name, querying only that name.
+///
+/// Inputs:
+/// - `runner`: Executes `pacman -Qi`.
+/// - `name`: Provided name (e.g. `rust`).
+///
+/// Output:
+/// - The `Provides` entries for `name` of the installed package satisfying it; empty if no
+///   installed package provides `name` or pacman fails.
+///
+/// Details:
+/// - `pacman -Qi <name>` falls back to the installed package satisfying `name` when no
+///   package of that name is installed, so a single cheap query replaces scanning the
+///   provides of every installed package.
+/// - Only the first satisfier pacman finds is reported.
+pub(super) fn provides_of(runner: &dyn CommandRunner, name: &str) -> Vec<ProvidedPackage> {
+    match runner.run("pacman", &["-Qi", name]) {
+        Ok(output) if output.status.success() => {
+            parse_provided_packages(&String::from_utf8_lossy(&output.stdout))
+                .into_iter()
+                .filter(|provide| provide.name == name)
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// What: Parse the `Provides` fields of `pacman -Qi` output for several packages.
+///
+/// Inputs:
+/// - `text`: Output of `pacman -Qi`, one blank-line separated block per package.
+///
+/// Output:
+/// - Returns the provided names with their versions, if any, and the declaring package.
+fn parse_provided_packages(text: &str) -> HashSet<ProvidedPackage> {
+    text.split("\n\n")
+        .flat_map(|block| {
+            let fields = parse_key_value_output(block);
+            let provider = fields.get("Name").cloned();
+            split_ws_or_none(fields.get("Provides"))
+                .into_iter()
+                .map(move |entry| match entry.split_once('=') {
+                    Some((name, version)) => ProvidedPackage {
+                        name: name.to_string(),
+                        version: Some(version.to_string()),
+                        provider: provider.clone(),
+                    },
+                    None => ProvidedPackage {
+                        name: entry,
+                        version: None,
+                        provider: provider.clone(),
+                    },
+                })
+        })
+        .collect()
 }
 
 /// What: Check if a specific package name is provided by any installed package (lazy check).
 ///
 /// Inputs:
+/// - `runner`: Executes `pacman -Qqo`.
 /// - `name`: Package name to check.
-/// - `installed`: Set of installed package names (unused, kept for API compatibility).
 ///
 /// Output:
 /// - Returns `Some(package_name)` if the name is provided by an installed package, `None` otherwise.
@@ -198,20 +449,10 @@ pub fn get_provided_packages<S: BuildHasher + Default>(
 /// - Uses `pacman -Qqo` to efficiently check if any installed package provides the name.
 /// - This is much faster than querying all packages upfront.
 /// - Returns the name of the providing package for debugging purposes.
-fn check_if_provided<S: BuildHasher>(
-    name: &str,
-    _installed: &HashSet<String, S>,
-) -> Option<String> {
+pub(super) fn check_if_provided(runner: &dyn CommandRunner, name: &str) -> Option<String> {
     // Use pacman -Qqo to check which package provides this name
     // This is efficient - pacman does the lookup internally
-    let output = Command::new("pacman")
-        .args(["-Qqo", name])
-        .env("LC_ALL", "C")
-        .env("LANG", "C")
-        .stdin(Stdio::null())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output();
+    let output = runner.run("pacman", &["-Qqo", name]);
 
     match output {
         Ok(output) if output.status.success() => {
@@ -231,15 +472,15 @@ fn check_if_provided<S: BuildHasher>(
 /// Inputs:
 /// - `name`: Package name to check.
 /// - `installed`: Set of directly installed package names.
-/// - `provided`: Set of package names provided by installed packages (unused, kept for API compatibility).
+/// - `provided`: Provides of installed packages from `get_provided_packages()`.
 ///
 /// Output:
 /// - Returns `true` if the package is directly installed or provided by an installed package.
 ///
 /// Details:
-/// - First checks if the package is directly installed.
-/// - Then lazily checks if it's provided by any installed package using `pacman -Qqo`.
-/// - This handles cases like `rustup` providing `rust` efficiently without querying all packages upfront.
+/// - First checks if the package is directly installed, then whether `provided` contains it.
+/// - Falls back to a lazy `pacman -Qqo` check, so an empty `provided` set still works.
+/// - This handles cases like `rustup` providing `rust`.
 ///
 /// # Example
 ///
@@ -255,15 +496,34 @@ fn check_if_provided<S: BuildHasher>(
 pub fn is_package_installed_or_provided<S: BuildHasher>(
     name: &str,
     installed: &HashSet<String, S>,
-    _provided: &HashSet<String, S>,
+    provided: &HashSet<ProvidedPackage, S>,
+) -> bool {
+    installed_or_provided(&SystemRunner, name, installed, provided)
+}
+
+/// What: Check if a package is installed or provided, querying pacman through a runner.
+///
+/// Inputs:
+/// - `runner`: Executes the lazy `pacman -Qqo` check.
+/// - `name`: Package name to check.
+/// - `installed`: Set of directly installed package names.
+/// - `provided`: Provides of installed packages.
+///
+/// Output:
+/// - `true` if the package is installed or provided by an installed package.
+pub(super) fn installed_or_provided<S: BuildHasher>(
+    runner: &dyn CommandRunner,
+    name: &str,
+    installed: &HashSet<String, S>,
+    provided: &HashSet<ProvidedPackage, S>,
 ) -> bool {
     // First check if directly installed
-    if installed.contains(name) {
+    if installed.contains(name) || provided.iter().any(|provide| provide.name == name) {
         return true;
     }
 
-    // Lazy check if provided by any installed package (much faster than building full set upfront)
-    check_if_provided(name, installed).is_some()
+    // Lazy check if provided by any installed package
+    check_if_provided(runner, name).is_some()
 }
 
 /// What: Retrieve the locally installed version of a package.
@@ -297,14 +557,24 @@ pub fn is_package_installed_or_provided<S: BuildHasher>(
 /// # Ok::<(), arch_toolkit::error::ArchToolkitError>(())
 /// ```
 pub fn get_installed_version(name: &str) -> Result<String> {
-    let output = Command::new("pacman")
-        .args(["-Q", name])
-        .env("LC_ALL", "C")
-        .env("LANG", "C")
-        .stdin(Stdio::null())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
+    installed_version(&SystemRunner, name)
+}
+
+/// What: Retrieve the locally installed version of a package through a command runner.
+///
+/// Inputs:
+/// - `runner`: Executes `pacman -Q`.
+/// - `name`: Package to query.
+///
+/// Output:
+/// - Installed version without revision suffix.
+///
+/// # Errors
+///
+/// - Returns the errors of `get_installed_version`.
+pub(super) fn installed_version(runner: &dyn CommandRunner, name: &str) -> Result<String> {
+    let output = runner
+        .run("pacman", &["-Q", name])
         .map_err(|e| ArchToolkitError::Parse(format!("pacman -Q failed: {e}")))?;
 
     if !output.status.success() {
@@ -355,15 +625,19 @@ pub fn get_installed_version(name: &str) -> Result<String> {
 /// ```
 #[must_use]
 pub fn get_available_version(name: &str) -> Option<String> {
-    let output = Command::new("pacman")
-        .args(["-Si", name])
-        .env("LC_ALL", "C")
-        .env("LANG", "C")
-        .stdin(Stdio::null())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
-        .ok()?;
+    available_version(&SystemRunner, name)
+}
+
+/// What: Query the repositories for the latest version of a package through a command runner.
+///
+/// Inputs:
+/// - `runner`: Executes `pacman -Si`.
+/// - `name`: Package name.
+///
+/// Output:
+/// - Available version without revision suffix, or `None` on failure.
+pub(super) fn available_version(runner: &dyn CommandRunner, name: &str) -> Option<String> {
+    let output = runner.run("pacman", &["-Si", name]).ok()?;
 
     if !output.status.success() {
         return None;
@@ -383,6 +657,76 @@ pub fn get_available_version(name: &str) -> Option<String> {
     None
 }
 
+/// What: Determine when the last full system upgrade was started.
+///
+/// Inputs:
+/// - (none): Reads `/var/log/pacman.log`.
+///
+/// Output:
+/// - `Some(DateTime<Utc>)` of the most recent `pacman -Syu`, or `None` if unknown.
+///
+/// Details:
+/// - Parses the log with `parse_last_upgrade_time()`.
+/// - Degrades gracefully: returns `None` when the log is missing or unreadable (non-Arch
+///   systems, restricted permissions, rotated or truncated logs) or contains no upgrade.
+///   Callers should then fall back to a fixed window, e.g. news from the last 30 days.
+/// - Requires the `aur` feature (for date handling).
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::deps::last_upgrade_time;
+///
+/// match last_upgrade_time() {
+///     Some(time) => println!("Last upgrade: {time}"),
+///     None => println!("Last upgrade time unknown"),
+/// }
+/// ```
+#[cfg(feature = "aur")]
+#[must_use]
+pub fn last_upgrade_time() -> Option<DateTime<Utc>> {
+    match std::fs::read(PACMAN_LOG_PATH) {
+        Ok(bytes) => parse_last_upgrade_time(&String::from_utf8_lossy(&bytes)),
+        Err(e) => {
+            tracing::debug!(error = %e, path = PACMAN_LOG_PATH, "cannot read pacman log");
+            None
+        }
+    }
+}
+
+/// What: Find the most recent full system upgrade in pacman log content.
+///
+/// Inputs:
+/// - `log`: Content of `pacman.log`.
+///
+/// Output:
+/// - `Some(DateTime<Utc>)` of the last "starting full system upgrade" entry, or `None`.
+///
+/// Details:
+/// - Supports current timestamps (`[2025-06-21T12:15:41+0200]`) and the pre-5.2
+///   format without offset (`[2019-01-01 12:00]`), which is interpreted as local time.
+/// - Entries with unparsable timestamps are skipped in favor of earlier ones.
+#[cfg(feature = "aur")]
+#[must_use]
+pub fn parse_last_upgrade_time(log: &str) -> Option<DateTime<Utc>> {
+    log.lines()
+        .rev()
+        .filter(|line| line.contains(FULL_UPGRADE_MARKER))
+        .find_map(|line| {
+            let timestamp = line.strip_prefix('[')?.split(']').next()?;
+            DateTime::parse_from_str(timestamp, "%Y-%m-%dT%H:%M:%S%z")
+                .map(|time| time.with_timezone(&Utc))
+                .ok()
+                .or_else(|| {
+                    let naive = NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M").ok()?;
+                    Local
+                        .from_local_datetime(&naive)
+                        .earliest()
+                        .map(|time| time.with_timezone(&Utc))
+                })
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -458,10 +802,51 @@ mod tests {
     }
 
     #[test]
-    fn test_get_provided_packages_returns_empty() {
-        let installed = HashSet::from(["pacman".to_string()]);
-        let provided = get_provided_packages(&installed);
-        assert!(provided.is_empty());
+    fn test_parse_provided_packages() {
+        let text = "Name            : rustup
+Version         : 1.27.1-1
+Provides        : rust=1.70  cargo  rust-src
+Depends On      : curl
+
+Name            : glibc
+Version         : 2.40-1
+Provides        : None
+
+Name            : pipewire-pulse
+Provides        : pulse-native-provider
+                  pulseaudio=17.0
+";
+
+        let provided = parse_provided_packages(text);
+        assert_eq!(provided.len(), 5);
+        assert!(provided.contains(&ProvidedPackage {
+            name: "rust".to_string(),
+            version: Some("1.70".to_string()),
+            provider: Some("rustup".to_string()),
+        }));
+        assert!(provided.contains(&ProvidedPackage {
+            name: "cargo".to_string(),
+            version: None,
+            provider: Some("rustup".to_string()),
+        }));
+        assert!(provided.contains(&ProvidedPackage {
+            name: "pulseaudio".to_string(),
+            version: Some("17.0".to_string()),
+            provider: Some("pipewire-pulse".to_string()),
+        }));
+    }
+
+    #[test]
+    fn test_is_package_installed_or_provided_uses_provides() {
+        let installed = HashSet::from(["rustup".to_string()]);
+        let provided = HashSet::from([ProvidedPackage {
+            name: "rust".to_string(),
+            version: Some("1.70".to_string()),
+            provider: Some("rustup".to_string()),
+        }]);
+        assert!(is_package_installed_or_provided(
+            "rust", &installed, &provided
+        ));
     }
 
     #[test]
@@ -520,4 +905,87 @@ mod tests {
             println!("Available pacman version: {version}");
         }
     }
+
+    #[cfg(feature = "aur")]
+    #[test]
+    /// What: Find the most recent full upgrade in a pacman log.
+    ///
+    /// Inputs:
+    /// - Log with two upgrades, a sync-only transaction, and a malformed timestamp.
+    ///
+    /// Output:
+    /// - Time of the last well-formed upgrade entry, converted to UTC.
+    fn test_parse_last_upgrade_time() {
+        let log = "\
+[2025-06-01T08:00:00+0200] [PACMAN] Running 'pacman -Syu'
+[2025-06-01T08:00:00+0200] [PACMAN] synchronizing package lists
+[2025-06-01T08:00:01+0200] [PACMAN] starting full system upgrade
+[2025-06-01T08:00:30+0200] [ALPM] upgraded linux (6.14.8-1 -> 6.15.1-1)
+[2025-06-10T20:30:12+0200] [PACMAN] starting full system upgrade
+[2025-06-11T09:00:00+0200] [PACMAN] Running 'pacman -S ripgrep'
+[garbage] [PACMAN] starting full system upgrade
+";
+        let time = parse_last_upgrade_time(log).expect("upgrade entry present");
+        assert_eq!(time.to_rfc3339(), "2025-06-10T18:30:12+00:00");
+
+        assert_eq!(
+            parse_last_upgrade_time(
+                "[2025-06-11T09:00:00+0200] [PACMAN] Running 'pacman -S ripgrep'\n"
+            ),
+            None
+        );
+        assert!(
+            parse_last_upgrade_time("[2019-01-01 12:00] [PACMAN] starting full system upgrade")
+                .is_some()
+        );
+    }
+
+    #[test]
+    /// What: Map spawn failures to errors.
+    ///
+    /// Inputs:
+    /// - `NotFound` and `PermissionDenied` I/O errors.
+    ///
+    /// Output:
+    /// - `PacmanUnavailable` with the command line for `NotFound`, `Parse` otherwise.
+    fn test_spawn_error_mapping() {
+        let err = spawn_error(&["-Qq"], &std::io::Error::from(ErrorKind::NotFound));
+        assert!(matches!(
+            err,
+            ArchToolkitError::PacmanUnavailable { ref command } if command == "pacman -Qq"
+        ));
+
+        let err = spawn_error(&["-Qq"], &std::io::Error::from(ErrorKind::PermissionDenied));
+        assert!(matches!(err, ArchToolkitError::Parse(_)));
+    }
+
+    #[test]
+    /// What: Map non-zero pacman exits to errors.
+    ///
+    /// Inputs:
+    /// - stderr reporting a missing package, and a generic database error.
+    ///
+    /// Output:
+    /// - `PackageNotFound` naming the package, and `Parse` carrying the exit code and stderr.
+    fn test_exit_error_mapping() {
+        let err = exit_error(
+            &["-Qi", "nope"],
+            Some(1),
+            "error: package 'nope' was not found\n",
+        );
+        assert!(matches!(
+            err,
+            ArchToolkitError::PackageNotFound { ref package } if package == "nope"
+        ));
+
+        let err = exit_error(&["-Qq"], Some(1), "error: could not open database\n");
+        match err {
+            ArchToolkitError::Parse(message) => {
+                assert!(message.contains("pacman -Qq"));
+                assert!(message.contains("Some(1)"));
+                assert!(message.contains("could not open database"));
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
 }
@@ -0,0 +1,251 @@
+//! Rebuild detection after library upgrades.
+//!
+//! This module implements the "checkrebuild" workflow: when an upgrade changes the soname
+//! a package provides (e.g., `libfoo.so=1-64` becomes `libfoo.so=2-64`), installed foreign
+//! (typically AUR) packages linked against the old soname must be rebuilt.
+
+use crate::deps::reverse::{parse_key_value_output, split_ws_or_none};
+use crate::deps::runner::{CommandRunner, SystemRunner};
+use crate::error::{ArchToolkitError, Result};
+use crate::types::dependency::Upgrade;
+use crate::types::is_soname;
+use std::collections::{BTreeSet, HashMap};
+
+/// What: Determine which installed foreign packages need a rebuild after upgrades.
+///
+/// Inputs:
+/// - `upgraded`: Upgrades with their provides lists before and after.
+/// - `installed_foreign`: Names of installed foreign packages (e.g., from `pacman -Qmq`).
+///
+/// Output:
+/// - Returns `Ok(Vec<String>)` with sorted, deduplicated package names to rebuild.
+///
+/// Details:
+/// - A soname is considered changed when an old provides entry of the form `lib*.so=...`
+///   is absent from the new provides list.
+/// - Runs a single `pacman -Qi` over `installed_foreign` and inspects each package's
+///   "Depends On" field for the old soname (exact match, including version).
+/// - Returns an empty list without invoking pacman when no soname changed.
+/// - Sets `LC_ALL=C` and `LANG=C` for consistent locale-independent output.
+///
+/// # Errors
+///
+/// - Returns `Err(ArchToolkitError::Parse)` if pacman cannot be executed, or if it fails
+///   without producing any package information.
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::deps::rebuild_candidates;
+/// use arch_toolkit::types::Upgrade;
+///
+/// let upgrade = Upgrade {
+///     name: "icu".into(),
+///     old_version: "74.2-1".into(),
+///     new_version: "75.1-1".into(),
+///     old_provides: vec!["libicuuc.so=74-64".into()],
+///     new_provides: vec!["libicuuc.so=75-64".into()],
+/// };
+/// let foreign = vec!["my-aur-app".to_string()];
+/// let rebuild = rebuild_candidates(&[upgrade], &foreign)?;
+/// println!("Rebuild: {rebuild:?}");
+/// # Ok::<(), arch_toolkit::error::ArchToolkitError>(())
+/// ```
+pub fn rebuild_candidates(
+    upgraded: &[Upgrade],
+    installed_foreign: &[String],
+) -> Result<Vec<String>> {
+    if installed_foreign.is_empty() || upgraded.iter().all(|u| changed_sonames(u).is_empty()) {
+        return Ok(Vec::new());
+    }
+
+    let foreign_depends = fetch_depends(installed_foreign)?;
+    Ok(find_rebuild_candidates(upgraded, &foreign_depends))
+}
+
+/// What: Collect soname provides removed by an upgrade.
+///
+/// Inputs:
+/// - `upgrade`: Upgrade with old and new provides lists.
+///
+/// Output:
+/// - Old soname provides (e.g., `libfoo.so=1-64`) not present after the upgrade.
+///
+/// Details:
+/// - Only provides whose name ends with `.so` are considered sonames.
+fn changed_sonames(upgrade: &Upgrade) -> Vec<&str> {
+    upgrade
+        .old_provides
+        .iter()
+        .map(|p| p.trim())
+        .filter(|p| is_soname(p))
+        .filter(|p| !upgrade.new_provides.iter().any(|n| n.trim() == *p))
+        .collect()
+}
+
+/// What: Match changed sonames against the dependencies of foreign packages.
+///
+/// Inputs:
+/// - `upgraded`: Upgrades with their provides lists before and after.
+/// - `foreign_depends`: Map of foreign package name to its raw "Depends On" entries.
+///
+/// Output:
+/// - Sorted, deduplicated names of foreign packages depending on a removed soname.
+///
+/// Details:
+/// - Upgraded packages themselves are never reported.
+fn find_rebuild_candidates(
+    upgraded: &[Upgrade],
+    foreign_depends: &HashMap<String, Vec<String>>,
+) -> Vec<String> {
+    let old_sonames: Vec<&str> = upgraded.iter().flat_map(changed_sonames).collect();
+    if old_sonames.is_empty() {
+        return Vec::new();
+    }
+
+    let mut candidates = BTreeSet::new();
+    for (pkg, depends) in foreign_depends {
+        if upgraded.iter().any(|u| u.name == *pkg) {
+            continue;
+        }
+        if depends.iter().any(|dep| old_sonames.contains(&dep.trim())) {
+            tracing::debug!("{} depends on a removed soname and needs a rebuild", pkg);
+            candidates.insert(pkg.clone());
+        }
+    }
+    candidates.into_iter().collect()
+}
+
+/// What: Query "Depends On" entries for installed packages via `pacman -Qi`.
+///
+/// Inputs:
+/// - `names`: Installed package names to query.
+///
+/// Output:
+/// - Map of package name to raw "Depends On" entries (soname entries preserved).
+///
+/// Details:
+/// - Uses a single `pacman -Qi` invocation; packages pacman does not know are skipped.
+fn fetch_depends(names: &[String]) -> Result<HashMap<String, Vec<String>>> {
+    tracing::debug!("Running: pacman -Qi {} packages", names.len());
+    let mut args = vec!["-Qi"];
+    args.extend(names.iter().map(String::as_str));
+    let output = SystemRunner
+        .run("pacman", &args)
+        .map_err(|e| ArchToolkitError::Parse(format!("pacman -Qi failed: {e}")))?;
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    let depends = parse_depends_blocks(&text);
+    if !output.status.success() && depends.is_empty() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(ArchToolkitError::Parse(format!(
+            "pacman -Qi exited with {:?}: {}",
+            output.status, stderr
+        )));
+    }
+    Ok(depends)
+}
+
+/// What: Parse multi-package `pacman -Qi` output into per-package depends lists.
+///
+/// Inputs:
+/// - `text`: Raw stdout containing one or more blank-line separated package blocks.
+///
+/// Output:
+/// - Map of package name to raw "Depends On" entries.
+fn parse_depends_blocks(text: &str) -> HashMap<String, Vec<String>> {
+    text.split("\n\n")
+        .filter_map(|block| {
+            let map = parse_key_value_output(block);
+            let name = map.get("Name")?.clone();
+            Some((name, split_ws_or_none(map.get("Depends On"))))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn libfoo_bump() -> Upgrade {
+        Upgrade {
+            name: "libfoo".into(),
+            old_version: "1.4-1".into(),
+            new_version: "2.0-1".into(),
+            old_provides: vec!["libfoo.so=1-64".into(), "foo-headers".into()],
+            new_provides: vec!["libfoo.so=2-64".into(), "foo-headers".into()],
+        }
+    }
+
+    #[test]
+    /// What: Detect a soname change and flag the dependent AUR package.
+    ///
+    /// Inputs:
+    /// - `libfoo` upgrade from `libfoo.so=1-64` to `libfoo.so=2-64`.
+    /// - Foreign packages where only `foo-viewer-git` links against the old soname.
+    ///
+    /// Output:
+    /// - Only `foo-viewer-git` is returned.
+    fn find_rebuild_candidates_flags_dependent_aur_package() {
+        let foreign = HashMap::from([
+            (
+                "foo-viewer-git".to_string(),
+                vec!["glibc".to_string(), "libfoo.so=1-64".to_string()],
+            ),
+            (
+                "unrelated-bin".to_string(),
+                vec!["glibc".to_string(), "libbar.so=3-64".to_string()],
+            ),
+            ("foo-unversioned".to_string(), vec!["libfoo".to_string()]),
+        ]);
+
+        let rebuild = find_rebuild_candidates(&[libfoo_bump()], &foreign);
+        assert_eq!(rebuild, vec!["foo-viewer-git"]);
+    }
+
+    #[test]
+    /// What: Ignore upgrades whose soname provides did not change.
+    ///
+    /// Inputs:
+    /// - Upgrade with identical soname provides before and after.
+    ///
+    /// Output:
+    /// - No rebuild candidates and no changed sonames.
+    fn find_rebuild_candidates_ignores_unchanged_soname() {
+        let mut upgrade = libfoo_bump();
+        upgrade.new_provides = upgrade.old_provides.clone();
+        assert!(changed_sonames(&upgrade).is_empty());
+
+        let foreign = HashMap::from([(
+            "foo-viewer-git".to_string(),
+            vec!["libfoo.so=1-64".to_string()],
+        )]);
+        assert!(find_rebuild_candidates(&[upgrade], &foreign).is_empty());
+    }
+
+    #[test]
+    /// What: Parse multi-package `pacman -Qi` output keeping soname dependencies.
+    ///
+    /// Inputs:
+    /// - Two package blocks separated by a blank line, one with "None" depends.
+    ///
+    /// Output:
+    /// - Map with raw depends entries, including `.so` entries.
+    fn parse_depends_blocks_keeps_soname_entries() {
+        let text = "Name            : foo-viewer-git\nDepends On      : glibc  libfoo.so=1-64\n\nName            : standalone\nDepends On      : None\n";
+        let map = parse_depends_blocks(text);
+        assert_eq!(
+            map.get("foo-viewer-git"),
+            Some(&vec!["glibc".to_string(), "libfoo.so=1-64".to_string()])
+        );
+        assert_eq!(map.get("standalone"), Some(&Vec::new()));
+    }
+
+    #[test]
+    fn rebuild_candidates_short_circuits_without_soname_change() {
+        let mut upgrade = libfoo_bump();
+        upgrade.new_provides = upgrade.old_provides.clone();
+        let result = rebuild_candidates(&[upgrade], &["foo-viewer-git".to_string()]);
+        assert!(result.is_ok_and(|v| v.is_empty()));
+    }
+}
@@ -3,24 +3,35 @@
 //! This module provides functions to resolve dependencies for packages, determine
 //! dependency status, and handle batch operations for efficient dependency resolution.
 
+#[cfg(feature = "aur")]
+use crate::client::ArchClient;
 use crate::deps::parse::{parse_dep_spec, parse_pacman_si_conflicts, parse_pacman_si_deps};
-use crate::deps::pkgbuild::parse_pkgbuild_deps;
+use crate::deps::pkgbuild::{parse_pkgbuild_conflicts, parse_pkgbuild_deps};
 use crate::deps::query::{
-    get_available_version, get_installed_packages, get_installed_version, get_provided_packages,
-    get_upgradable_packages, is_package_installed_or_provided,
+    available_version, check_if_provided, installed_or_provided, installed_packages,
+    installed_version, pacman_stdout, provides_of, upgradable_packages,
 };
-use crate::deps::source::{determine_dependency_source, is_system_package};
-use crate::deps::version::version_satisfies;
+use crate::deps::reverse::{parse_key_value_output, split_ws_or_none};
+use crate::deps::runner::{CommandRunner, SystemRunner};
+use crate::deps::source::{dependency_source, is_system_package, is_system_package_with};
+use crate::deps::srcinfo::parse_srcinfo_deps;
+use crate::deps::version::{compare_versions, version_satisfies};
 use crate::error::Result;
 use crate::types::dependency::{
-    Dependency, DependencySource, DependencyStatus, PackageRef, PackageSource, ResolverConfig,
+    Dependency, DependencyKind, DependencySource, DependencyStatus, InstallReason, PackageRef,
+    PackageSource, ProvidedPackage, ResolveOverrides, ResolverConfig, SourceConfig,
 };
-use std::collections::{HashMap, HashSet};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::hash::BuildHasher;
-use std::process::{Command, Stdio};
+#[cfg(feature = "aur")]
+use std::io::{self, ErrorKind};
+#[cfg(feature = "aur")]
+use std::process::Output;
+use std::sync::Mutex;
 
 /// Type alias for PKGBUILD cache callback function.
-type PkgbuildCacheFn = dyn Fn(&str) -> Option<String> + Send + Sync;
+type PkgbuildCacheFn<'a> = dyn Fn(&str) -> Option<String> + Send + Sync + 'a;
 
 /// What: Evaluate a dependency's installation status relative to required versions.
 ///
@@ -28,7 +39,7 @@ type PkgbuildCacheFn = dyn Fn(&str) -> Option<String> + Send + Sync;
 /// - `name`: Dependency package identifier.
 /// - `version_req`: Optional version constraint string (e.g., `>=1.2`).
 /// - `installed`: Set of names currently installed on the system.
-/// - `provided`: Set of package names provided by installed packages.
+/// - `provided`: Provides of installed packages (see `get_provided_packages()`).
 /// - `upgradable`: Set of names pacman reports as upgradable.
 ///
 /// Output:
@@ -36,6 +47,10 @@ type PkgbuildCacheFn = dyn Fn(&str) -> Option<String> + Send + Sync;
 ///
 /// Details:
 /// - Combines local database queries with helper functions to capture upgrade requirements.
+/// - A dependency that is not installed itself but appears in `provided` is checked against
+///   the provided versions: `provides=('rust=1.70')` satisfies `rust>=1.60`, an older
+///   provided version yields `ToUpgrade`, and an unversioned provide cannot satisfy a
+///   versioned requirement (`ToInstall`), as in pacman.
 /// - Uses `is_package_installed_or_provided()` to check if package is available.
 /// - Uses `get_installed_version()` and `get_available_version()` for version checking.
 /// - Uses `version_satisfies()` for version requirement validation.
@@ -57,11 +72,54 @@ pub fn determine_status<S: BuildHasher>(
     name: &str,
     version_req: &str,
     installed: &HashSet<String, S>,
-    provided: &HashSet<String, S>,
+    provided: &HashSet<ProvidedPackage, S>,
+    upgradable: &HashSet<String, S>,
+) -> DependencyStatus {
+    dependency_status(
+        name,
+        version_req,
+        installed,
+        provided,
+        upgradable,
+        &SystemRunner,
+    )
+}
+
+/// What: Evaluate a dependency's status, querying pacman through a command runner.
+///
+/// Inputs:
+/// - `name`, `version_req`, `installed`, `provided`, `upgradable`: As for `determine_status`.
+/// - `runner`: Executes the pacman version and provides queries.
+///
+/// Output:
+/// - `DependencyStatus` as described for `determine_status`.
+fn dependency_status<S: BuildHasher>(
+    name: &str,
+    version_req: &str,
+    installed: &HashSet<String, S>,
+    provided: &HashSet<ProvidedPackage, S>,
     upgradable: &HashSet<String, S>,
+    runner: &dyn CommandRunner,
 ) -> DependencyStatus {
+    // Satisfied through a provide of another package: compare the provided versions
+    if !installed.contains(name) {
+        let providers = matching_provides(runner, name, provided);
+        let mut versions: Vec<Option<&str>> = providers
+            .iter()
+            .map(|provide| provide.version.as_deref())
+            .collect();
+        if !versions.is_empty() {
+            // Highest version first, unversioned provides last
+            versions.sort_by(|a, b| match (a, b) {
+                (Some(a), Some(b)) => compare_versions(b, a),
+                _ => b.is_some().cmp(&a.is_some()),
+            });
+            return provided_status(&versions, version_req);
+        }
+    }
+
     // Check if package is installed or provided by an installed package
-    if !is_package_installed_or_provided(name, installed, provided) {
+    if !installed_or_provided(runner, name, installed, provided) {
         return DependencyStatus::ToInstall;
     }
 
@@ -71,7 +129,7 @@ pub fn determine_status<S: BuildHasher>(
     // If version requirement is specified, check if it matches
     if !version_req.is_empty() {
         // Try to get installed version
-        if let Ok(installed_version) = get_installed_version(name) {
+        if let Ok(installed_version) = installed_version(runner, name) {
             // Check if version requirement is satisfied
             if !version_satisfies(&installed_version, version_req) {
                 return DependencyStatus::ToUpgrade {
@@ -83,7 +141,7 @@ pub fn determine_status<S: BuildHasher>(
             if is_upgradable {
                 // Get available version from pacman -Si if possible
                 let available_version =
-                    get_available_version(name).unwrap_or_else(|| "newer".to_string());
+                    available_version(runner, name).unwrap_or_else(|| "newer".to_string());
                 return DependencyStatus::ToUpgrade {
                     current: installed_version,
                     required: available_version,
@@ -97,10 +155,10 @@ pub fn determine_status<S: BuildHasher>(
 
     // Installed but no version check needed - check if upgradable
     if is_upgradable {
-        match get_installed_version(name) {
+        match installed_version(runner, name) {
             Ok(current_version) => {
                 let available_version =
-                    get_available_version(name).unwrap_or_else(|| "newer".to_string());
+                    available_version(runner, name).unwrap_or_else(|| "newer".to_string());
                 return DependencyStatus::ToUpgrade {
                     current: current_version,
                     required: available_version,
@@ -116,7 +174,7 @@ pub fn determine_status<S: BuildHasher>(
     }
 
     // Installed and up-to-date - get actual version
-    get_installed_version(name).map_or_else(
+    installed_version(runner, name).map_or_else(
         |_| DependencyStatus::Installed {
             version: "installed".to_string(),
         },
@@ -124,6 +182,115 @@ pub fn determine_status<S: BuildHasher>(
     )
 }
 
+/// What: Provides of installed packages for one dependency name.
+///
+/// Inputs:
+/// - `runner`: Executes the per-name `pacman -Qi` lookup.
+/// - `name`: Dependency name.
+/// - `provided`: Known provides of installed packages (may be empty).
+///
+/// Output:
+/// - The entries of `provided` for `name`, or those reported by `pacman -Qi <name>` when
+///   `provided` has none.
+fn matching_provides<S: BuildHasher>(
+    runner: &dyn CommandRunner,
+    name: &str,
+    provided: &HashSet<ProvidedPackage, S>,
+) -> Vec<ProvidedPackage> {
+    let known: Vec<ProvidedPackage> = provided
+        .iter()
+        .filter(|provide| provide.name == name)
+        .cloned()
+        .collect();
+    if known.is_empty() {
+        provides_of(runner, name)
+    } else {
+        known
+    }
+}
+
+/// What: Name the installed package that satisfies a dependency through its provides.
+///
+/// Inputs:
+/// - `runner`: Executes the per-name `pacman -Qi` lookup and the `pacman -Qqo` check.
+/// - `name`: Dependency name.
+/// - `version_req`: Version constraint string (may be empty).
+/// - `installed`: Set of directly installed package names.
+/// - `provided`: Provides of installed packages.
+///
+/// Output:
+/// - `Some(provider)` if `name` is not installed itself but provided by an installed package
+///   satisfying `version_req`, `None` otherwise.
+///
+/// Details:
+/// - Only a provider whose provided version satisfies `version_req` counts; among equals the
+///   alphabetically first provider wins, so the result is deterministic.
+/// - Looks up `name` with `pacman -Qi` when `provided` has no entry for it, then falls back
+///   to `pacman -Qqo`.
+fn satisfying_provider<S: BuildHasher>(
+    runner: &dyn CommandRunner,
+    name: &str,
+    version_req: &str,
+    installed: &HashSet<String, S>,
+    provided: &HashSet<ProvidedPackage, S>,
+) -> Option<String> {
+    if installed.contains(name) {
+        return None;
+    }
+    let providers = matching_provides(runner, name, provided);
+    let mut candidates: Vec<&ProvidedPackage> = providers
+        .iter()
+        .filter(|provide| provide.provider.is_some())
+        .collect();
+    if candidates.is_empty() {
+        return check_if_provided(runner, name);
+    }
+    candidates.sort_by(|a, b| a.provider.cmp(&b.provider));
+    candidates
+        .iter()
+        .find(|provide| {
+            version_req.is_empty()
+                || provide
+                    .version
+                    .as_deref()
+                    .is_some_and(|version| version_satisfies(version, version_req))
+        })
+        .and_then(|provide| provide.provider.clone())
+}
+
+/// What: Status of a dependency that is only available through provides.
+///
+/// Inputs:
+/// - `versions`: Provided versions for the dependency name, highest first, `None` last.
+/// - `version_req`: Version constraint string (may be empty).
+///
+/// Output:
+/// - `Installed` with the best satisfying provided version, `ToUpgrade` if only older
+///   versions are provided, or `ToInstall` if no provide carries a version.
+fn provided_status(versions: &[Option<&str>], version_req: &str) -> DependencyStatus {
+    let best = versions.first().copied().flatten();
+    if version_req.is_empty() {
+        return DependencyStatus::Installed {
+            version: best.unwrap_or("installed").to_string(),
+        };
+    }
+    if let Some(version) = versions
+        .iter()
+        .flatten()
+        .find(|version| version_satisfies(version, version_req))
+    {
+        return DependencyStatus::Installed {
+            version: (*version).to_string(),
+        };
+    }
+    best.map_or(DependencyStatus::ToInstall, |current| {
+        DependencyStatus::ToUpgrade {
+            current: current.to_string(),
+            required: version_req.to_string(),
+        }
+    })
+}
+
 /// What: Batch fetch dependency lists for multiple official packages using `pacman -Si`.
 ///
 /// Inputs:
@@ -154,45 +321,13 @@ pub fn batch_fetch_official_deps(names: &[&str]) -> HashMap<String, Vec<String>>
     for chunk in names.chunks(BATCH_SIZE) {
         let mut args = vec!["-Si"];
         args.extend(chunk.iter().copied());
-        match Command::new("pacman")
-            .args(&args)
-            .env("LC_ALL", "C")
-            .env("LANG", "C")
-            .stdin(Stdio::null())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-        {
+        match SystemRunner.run("pacman", &args) {
             Ok(output) if output.status.success() => {
                 let text = String::from_utf8_lossy(&output.stdout);
-                // Parse multi-package output: packages are separated by blank lines
-                let mut package_blocks = Vec::new();
-                let mut current_block = String::new();
-                for line in text.lines() {
-                    if line.trim().is_empty() {
-                        if !current_block.is_empty() {
-                            package_blocks.push(current_block.clone());
-                            current_block.clear();
-                        }
-                    } else {
-                        current_block.push_str(line);
-                        current_block.push('\n');
-                    }
-                }
-                if !current_block.is_empty() {
-                    package_blocks.push(current_block);
-                }
-
                 // Parse each block to extract package name and dependencies
-                for block in package_blocks {
-                    let dep_names = parse_pacman_si_deps(&block);
-                    // Extract package name from block
-                    if let Some(name_line) =
-                        block.lines().find(|l| l.trim_start().starts_with("Name"))
-                        && let Some((_, name)) = name_line.split_once(':')
-                    {
-                        let pkg_name = name.trim().to_string();
-                        result_map.insert(pkg_name, dep_names);
+                for block in split_info_blocks(&text) {
+                    if let Some(pkg_name) = block_package_name(&block) {
+                        result_map.insert(pkg_name, parse_pacman_si_deps(&block));
                     }
                 }
             }
@@ -206,9 +341,311 @@ pub fn batch_fetch_official_deps(names: &[&str]) -> HashMap<String, Vec<String>>
     result_map
 }
 
+/// What: Split multi-package `pacman -Si`/`-Qi` output into per-package blocks.
+///
+/// Inputs:
+/// - `text`: Raw pacman output; packages are separated by blank lines.
+///
+/// Output:
+/// - One string per package block, each line terminated by a newline.
+fn split_info_blocks(text: &str) -> Vec<String> {
+    let mut package_blocks = Vec::new();
+    let mut current_block = String::new();
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            if !current_block.is_empty() {
+                package_blocks.push(current_block.clone());
+                current_block.clear();
+            }
+        } else {
+            current_block.push_str(line);
+            current_block.push('\n');
+        }
+    }
+    if !current_block.is_empty() {
+        package_blocks.push(current_block);
+    }
+    package_blocks
+}
+
+/// What: Read the "Name" field of a single pacman info block.
+///
+/// Inputs:
+/// - `block`: One package block from `pacman -Si`/`-Qi` output.
+///
+/// Output:
+/// - The package name, or `None` when the block has no "Name" field.
+fn block_package_name(block: &str) -> Option<String> {
+    let name_line = block.lines().find(|l| l.trim_start().starts_with("Name"))?;
+    let (_, name) = name_line.split_once(':')?;
+    Some(name.trim().to_string())
+}
+
+/// Package database read by a cached pacman query.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum PacmanDb {
+    /// Sync databases (`pacman -Si`).
+    Sync,
+    /// Local database of installed packages (`pacman -Qi`).
+    Local,
+}
+
+impl PacmanDb {
+    /// What: pacman flag querying this database.
+    ///
+    /// Output:
+    /// - `-Si` for the sync databases, `-Qi` for the local database.
+    const fn flag(self) -> &'static str {
+        match self {
+            Self::Sync => "-Si",
+            Self::Local => "-Qi",
+        }
+    }
+}
+
+/// Fields of one package parsed from `pacman -Si`/`-Qi` output.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct PacmanFields {
+    /// "Depends On" entries.
+    depends: Vec<String>,
+    /// "Conflicts With" package names.
+    conflicts: Vec<String>,
+    /// "Groups" the package belongs to.
+    groups: Vec<String>,
+}
+
+impl PacmanFields {
+    /// What: Parse the fields of a single package block.
+    ///
+    /// Inputs:
+    /// - `block`: Output of pacman for one package.
+    ///
+    /// Output:
+    /// - Dependency, conflict and group lists of the package.
+    fn parse(block: &str) -> Self {
+        Self {
+            depends: parse_pacman_si_deps(block),
+            conflicts: parse_pacman_si_conflicts(block),
+            groups: split_ws_or_none(parse_key_value_output(block).get("Groups")),
+        }
+    }
+}
+
+/// Command runner replaying the output of command lines it has already run.
+///
+/// `DependencyResolver::resolve_async()` resolves again after each `.SRCINFO` prefetch pass;
+/// the resolver only runs read-only queries, so repeating them for already resolved packages
+/// would spawn the same processes again. Failures to start a program are replayed as well.
+#[cfg(feature = "aur")]
+struct MemoizedRunner<'a> {
+    /// Runs command lines not seen before.
+    inner: &'a dyn CommandRunner,
+    /// Output or start failure by command line.
+    outputs: Mutex<HashMap<Vec<String>, MemoizedOutput>>,
+}
+
+/// Output of a memoized command line, or the kind and message of its start failure.
+#[cfg(feature = "aur")]
+type MemoizedOutput = std::result::Result<Output, (ErrorKind, String)>;
+
+#[cfg(feature = "aur")]
+impl<'a> MemoizedRunner<'a> {
+    /// What: Wrap a runner without recorded outputs.
+    ///
+    /// Inputs:
+    /// - `inner`: Runner executing command lines not seen before.
+    ///
+    /// Output:
+    /// - Runner with an empty output memo.
+    fn new(inner: &'a dyn CommandRunner) -> Self {
+        Self {
+            inner,
+            outputs: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[cfg(feature = "aur")]
+impl CommandRunner for MemoizedRunner<'_> {
+    fn run(&self, program: &str, args: &[&str]) -> io::Result<Output> {
+        let key: Vec<String> = std::iter::once(program)
+            .chain(args.iter().copied())
+            .map(str::to_string)
+            .collect();
+        let result = self
+            .outputs
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .entry(key)
+            .or_insert_with(|| {
+                self.inner
+                    .run(program, args)
+                    .map_err(|e| (e.kind(), e.to_string()))
+            })
+            .clone();
+        result.map_err(|(kind, message)| io::Error::new(kind, message))
+    }
+}
+
+/// Memoized pacman package queries shared by one dependency resolution.
+///
+/// Resolution and conflict detection both read `pacman -Si`/`-Qi`; routing them through
+/// this cache spawns pacman at most once per package and database. Failed lookups are
+/// memoized as well.
+struct PacmanQueryCache<'a> {
+    /// Executes pacman and the AUR helpers.
+    runner: &'a dyn CommandRunner,
+    /// Parsed fields by database and package name; `None` records a failed lookup.
+    entries: RefCell<HashMap<(PacmanDb, String), Option<PacmanFields>>>,
+}
+
+impl<'a> PacmanQueryCache<'a> {
+    /// What: Create an empty cache.
+    ///
+    /// Inputs:
+    /// - `runner`: Executes the pacman queries.
+    ///
+    /// Output:
+    /// - Cache without entries.
+    fn new(runner: &'a dyn CommandRunner) -> Self {
+        Self {
+            runner,
+            entries: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// What: Command runner used by this cache.
+    ///
+    /// Output:
+    /// - The runner, for commands that are not cached (helpers, status queries).
+    fn runner(&self) -> &'a dyn CommandRunner {
+        self.runner
+    }
+
+    /// What: Look up a package, querying pacman on the first request only.
+    ///
+    /// Inputs:
+    /// - `db`: Database to query.
+    /// - `name`: Package name.
+    ///
+    /// Output:
+    /// - Parsed fields, or `None` if the package could not be queried.
+    fn fields(&self, db: PacmanDb, name: &str) -> Option<PacmanFields> {
+        let key = (db, name.to_string());
+        if let Some(cached) = self.entries.borrow().get(&key) {
+            return cached.clone();
+        }
+        let fields = match pacman_stdout(self.runner, &[db.flag(), name]) {
+            Ok(text) => Some(PacmanFields::parse(&text)),
+            Err(e) => {
+                tracing::debug!("pacman {} {} failed: {}", db.flag(), name, e);
+                None
+            }
+        };
+        self.entries.borrow_mut().insert(key, fields.clone());
+        fields
+    }
+
+    /// What: Fill the cache for several sync packages with batched `pacman -Si` calls.
+    ///
+    /// Inputs:
+    /// - `names`: Package names to prefetch.
+    ///
+    /// Details:
+    /// - Queries in chunks of 50 to avoid command-line length limits.
+    /// - Stops at the first failing chunk; remaining packages are looked up individually later.
+    fn prefetch_sync(&self, names: &[&str]) {
+        const BATCH_SIZE: usize = 50;
+        let pending: Vec<&str> = {
+            let entries = self.entries.borrow();
+            names
+                .iter()
+                .copied()
+                .filter(|name| !entries.contains_key(&(PacmanDb::Sync, (*name).to_string())))
+                .collect()
+        };
+
+        for chunk in pending.chunks(BATCH_SIZE) {
+            let mut args = vec![PacmanDb::Sync.flag()];
+            args.extend(chunk.iter().copied());
+            let text = match pacman_stdout(self.runner, &args) {
+                Ok(text) => text,
+                Err(e) => {
+                    tracing::debug!("Batched pacman -Si failed: {}", e);
+                    break;
+                }
+            };
+            let mut entries = self.entries.borrow_mut();
+            for block in split_info_blocks(&text) {
+                if let Some(pkg_name) = block_package_name(&block) {
+                    entries.insert(
+                        (PacmanDb::Sync, pkg_name),
+                        Some(PacmanFields::parse(&block)),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// What: Flag resolved dependencies that are protected system packages.
+///
+/// Inputs:
+/// - `deps`: Resolved dependencies to update.
+/// - `config`: Protected groups and package names.
+/// - `offline`: Whether sync database queries are disabled.
+/// - `pacman`: Query cache providing package groups.
+/// - `installed`: Set of locally installed packages.
+///
+/// Output:
+/// - Sets `is_system` for core packages and packages matched by `is_system_package_with()`.
+///
+/// Details:
+/// - Groups come from the pacman fields already cached by the resolution. Missing official
+///   packages are fetched with batched `pacman -Si` queries (AUR packages are left out so a
+///   batch does not fail), falling back to `pacman -Qi` for installed packages missing from
+///   the sync databases (and for all packages offline).
+/// - Without protected groups no groups are queried.
+fn mark_system_packages<S: BuildHasher>(
+    deps: &mut [Dependency],
+    config: &SourceConfig,
+    offline: bool,
+    pacman: &PacmanQueryCache,
+    installed: &HashSet<String, S>,
+) {
+    let check_groups = !config.protected_groups.is_empty();
+    if check_groups && !offline {
+        let names: Vec<&str> = deps
+            .iter()
+            .filter(|dep| matches!(dep.source, DependencySource::Official { .. }))
+            .map(|dep| dep.name.as_str())
+            .collect();
+        pacman.prefetch_sync(&names);
+    }
+    for dep in deps {
+        let groups = if check_groups {
+            (!offline && matches!(dep.source, DependencySource::Official { .. }))
+                .then(|| pacman.fields(PacmanDb::Sync, &dep.name))
+                .flatten()
+                .or_else(|| {
+                    installed
+                        .contains(&dep.name)
+                        .then(|| pacman.fields(PacmanDb::Local, &dep.name))
+                        .flatten()
+                })
+                .map(|fields| fields.groups)
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        dep.is_system = dep.is_core || is_system_package_with(&dep.name, &groups, config);
+    }
+}
+
 /// What: Check if a command is available in PATH.
 ///
 /// Inputs:
+/// - `runner`: Executes the command.
 /// - `cmd`: Command name to check.
 ///
 /// Output:
@@ -216,14 +653,8 @@ pub fn batch_fetch_official_deps(names: &[&str]) -> HashMap<String, Vec<String>>
 ///
 /// Details:
 /// - Uses a simple version check to verify command availability.
-fn is_command_available(cmd: &str) -> bool {
-    Command::new(cmd)
-        .args(["--version"])
-        .stdin(Stdio::null())
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .output()
-        .is_ok()
+fn is_command_available(runner: &dyn CommandRunner, cmd: &str) -> bool {
+    runner.run(cmd, &["--version"]).is_ok()
 }
 
 /// What: Check if a package name should be filtered out (virtual package or self-reference).
@@ -251,9 +682,11 @@ fn should_filter_dependency(pkg_name: &str, parent_name: &str) -> bool {
 /// Inputs:
 /// - `dep_spec`: Dependency specification string (may include version requirements).
 /// - `parent_name`: Name of the package that requires this dependency.
+/// - `kind`: Dependency array the spec was declared in.
 /// - `installed`: Set of locally installed packages.
 /// - `provided`: Set of package names provided by installed packages.
 /// - `upgradable`: Set of packages flagged for upgrades.
+/// - `runner`: Executes the pacman status and repository queries.
 ///
 /// Output:
 /// - Returns Some(Dependency) if the dependency should be included, None if filtered.
@@ -261,13 +694,21 @@ fn should_filter_dependency(pkg_name: &str, parent_name: &str) -> bool {
 /// Details:
 /// - Parses the dependency spec, filters out virtual packages and self-references,
 ///   and determines status, source, and system package flags.
+/// - Optional dependencies have their `: description` suffix stripped.
 fn process_dependency_spec<S: BuildHasher>(
     dep_spec: &str,
     parent_name: &str,
+    kind: DependencyKind,
     installed: &HashSet<String, S>,
-    provided: &HashSet<String, S>,
+    provided: &HashSet<ProvidedPackage, S>,
     upgradable: &HashSet<String, S>,
+    runner: &dyn CommandRunner,
 ) -> Option<Dependency> {
+    let dep_spec = if kind == DependencyKind::Opt {
+        strip_optdepend_description(dep_spec)
+    } else {
+        dep_spec
+    };
     let spec = parse_dep_spec(dep_spec);
     let pkg_name = spec.name;
     let version_req = spec.version_req;
@@ -281,8 +722,21 @@ fn process_dependency_spec<S: BuildHasher>(
         return None;
     }
 
-    let status = determine_status(&pkg_name, &version_req, installed, provided, upgradable);
-    let (source, is_core) = determine_dependency_source(&pkg_name, installed);
+    let status = dependency_status(
+        &pkg_name,
+        &version_req,
+        installed,
+        provided,
+        upgradable,
+        runner,
+    );
+    let satisfied_by = match status {
+        DependencyStatus::Installed { .. } | DependencyStatus::ToUpgrade { .. } => {
+            satisfying_provider(runner, &pkg_name, &version_req, installed, provided)
+        }
+        _ => None,
+    };
+    let (source, is_core) = dependency_source(runner, &pkg_name, installed);
     let is_system = is_core || is_system_package(&pkg_name);
 
     Some(Dependency {
@@ -294,14 +748,44 @@ fn process_dependency_spec<S: BuildHasher>(
         depends_on: Vec::new(),
         is_core,
         is_system,
+        kind,
+        explicit: false,
+        install_reason: InstallReason::Unknown,
+        satisfied_by,
     })
 }
 
+/// What: Strip the description from an optional dependency entry.
+///
+/// Inputs:
+/// - `entry`: Optional dependency entry (e.g., `python: for helper scripts`).
+///
+/// Output:
+/// - The dependency spec without description (e.g., `python`).
+///
+/// Details:
+/// - The description separator is a colon followed by whitespace or the end of the entry,
+///   so epochs in version constraints (`foo>=1:2.0`) are preserved.
+fn strip_optdepend_description(entry: &str) -> &str {
+    let end = entry
+        .match_indices(':')
+        .map(|(pos, _)| pos)
+        .find(|&pos| {
+            entry[pos + 1..]
+                .chars()
+                .next()
+                .is_none_or(char::is_whitespace)
+        })
+        .unwrap_or(entry.len());
+    entry[..end].trim()
+}
+
 /// What: Process a list of dependency specs into `Dependency` records.
 ///
 /// Inputs:
 /// - `dep_specs`: Vector of dependency specification strings.
 /// - `parent_name`: Name of the package that requires these dependencies.
+/// - `kind`: Dependency array the specs were declared in.
 /// - `installed`: Set of locally installed packages.
 /// - `provided`: Set of package names provided by installed packages.
 /// - `upgradable`: Set of packages flagged for upgrades.
@@ -314,74 +798,124 @@ fn process_dependency_spec<S: BuildHasher>(
 fn process_dependency_specs<S: BuildHasher>(
     dep_specs: Vec<String>,
     parent_name: &str,
+    kind: DependencyKind,
     installed: &HashSet<String, S>,
-    provided: &HashSet<String, S>,
+    provided: &HashSet<ProvidedPackage, S>,
     upgradable: &HashSet<String, S>,
+    runner: &dyn CommandRunner,
 ) -> Vec<Dependency> {
     dep_specs
         .into_iter()
         .filter_map(|dep_spec| {
-            process_dependency_spec(&dep_spec, parent_name, installed, provided, upgradable)
+            process_dependency_spec(
+                &dep_spec,
+                parent_name,
+                kind,
+                installed,
+                provided,
+                upgradable,
+                runner,
+            )
         })
         .collect()
 }
 
+/// What: Process parsed dependency arrays into `Dependency` records.
+///
+/// Inputs:
+/// - `arrays`: Tuple of (depends, makedepends, checkdepends, optdepends) specs, as returned
+///   by `parse_pkgbuild_deps()` and `parse_srcinfo_deps()`.
+/// - `parent_name`: Name of the package that declares these dependencies.
+/// - `settings`: Effective settings providing the include flags.
+/// - `installed`: Set of locally installed packages.
+/// - `provided`: Set of package names provided by installed packages.
+/// - `upgradable`: Set of packages flagged for upgrades.
+///
+/// Output:
+/// - Returns `Dependency` records tagged with their `DependencyKind`.
+///
+/// Details:
+/// - `depends` is always processed; make, check, and optional dependencies only when the
+///   matching include flag is set.
+fn process_dependency_arrays<S: BuildHasher>(
+    arrays: (Vec<String>, Vec<String>, Vec<String>, Vec<String>),
+    parent_name: &str,
+    settings: &ResolveSettings,
+    installed: &HashSet<String, S>,
+    provided: &HashSet<ProvidedPackage, S>,
+    upgradable: &HashSet<String, S>,
+    runner: &dyn CommandRunner,
+) -> Vec<Dependency> {
+    let (depends, makedepends, checkdepends, optdepends) = arrays;
+    [
+        (depends, DependencyKind::Runtime, true),
+        (
+            makedepends,
+            DependencyKind::Make,
+            settings.include_makedepends,
+        ),
+        (
+            checkdepends,
+            DependencyKind::Check,
+            settings.include_checkdepends,
+        ),
+        (optdepends, DependencyKind::Opt, settings.include_optdepends),
+    ]
+    .into_iter()
+    .filter(|(_, _, include)| *include)
+    .flat_map(|(specs, kind, _)| {
+        process_dependency_specs(
+            specs,
+            parent_name,
+            kind,
+            installed,
+            provided,
+            upgradable,
+            runner,
+        )
+    })
+    .collect()
+}
+
 /// What: Resolve dependencies for a local package using pacman -Qi.
 ///
 /// Inputs:
 /// - `name`: Package name.
+/// - `pacman`: Query cache shared by the current resolution.
 /// - `installed`: Set of locally installed packages.
 /// - `provided`: Set of package names provided by installed packages.
 /// - `upgradable`: Set of packages flagged for upgrades.
 ///
 /// Output:
-/// - Returns a vector of `Dependency` records or an error string.
+/// - Returns a vector of `Dependency` records; empty when the package is not installed.
 ///
 /// Details:
 /// - Uses pacman -Qi to get dependency information for locally installed packages.
 fn resolve_local_package_deps<S: BuildHasher>(
     name: &str,
+    pacman: &PacmanQueryCache,
     installed: &HashSet<String, S>,
-    provided: &HashSet<String, S>,
+    provided: &HashSet<ProvidedPackage, S>,
     upgradable: &HashSet<String, S>,
-) -> Result<Vec<Dependency>> {
-    tracing::debug!("Running: pacman -Qi {} (local package)", name);
-    let output = Command::new("pacman")
-        .args(["-Qi", name])
-        .env("LC_ALL", "C")
-        .env("LANG", "C")
-        .stdin(Stdio::null())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
-        .map_err(|e| {
-            tracing::error!("Failed to execute pacman -Qi {}: {}", name, e);
-            crate::error::ArchToolkitError::Parse(format!("pacman -Qi failed: {e}"))
-        })?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        tracing::warn!(
-            "pacman -Qi {} failed with status {:?}: {}",
-            name,
-            output.status.code(),
-            stderr
-        );
-        return Ok(Vec::new());
-    }
-
-    let text = String::from_utf8_lossy(&output.stdout);
-    tracing::debug!("pacman -Qi {} output ({} bytes)", name, text.len());
-
-    let dep_names = parse_pacman_si_deps(&text);
+) -> Vec<Dependency> {
+    let Some(fields) = pacman.fields(PacmanDb::Local, name) else {
+        tracing::warn!("pacman -Qi {} failed (package not installed?)", name);
+        return Vec::new();
+    };
     tracing::debug!(
         "Parsed {} dependency names from pacman -Qi output",
-        dep_names.len()
+        fields.depends.len()
     );
 
-    Ok(process_dependency_specs(
-        dep_names, name, installed, provided, upgradable,
-    ))
+    process_dependency_specs(
+        fields.depends,
+        name,
+        DependencyKind::Runtime,
+        installed,
+        provided,
+        upgradable,
+        pacman.runner(),
+    )
 }
 
 /// What: Resolve dependencies for an official package using pacman -Si.
@@ -389,6 +923,7 @@ fn resolve_local_package_deps<S: BuildHasher>(
 /// Inputs:
 /// - `name`: Package name.
 /// - `repo`: Repository name (for logging).
+/// - `pacman`: Query cache shared by the current resolution.
 /// - `installed`: Set of locally installed packages.
 /// - `provided`: Set of package names provided by installed packages.
 /// - `upgradable`: Set of packages flagged for upgrades.
@@ -401,48 +936,28 @@ fn resolve_local_package_deps<S: BuildHasher>(
 fn resolve_official_package_deps<S: BuildHasher>(
     name: &str,
     repo: &str,
+    pacman: &PacmanQueryCache,
     installed: &HashSet<String, S>,
-    provided: &HashSet<String, S>,
+    provided: &HashSet<ProvidedPackage, S>,
     upgradable: &HashSet<String, S>,
 ) -> Result<Vec<Dependency>> {
-    tracing::debug!("Running: pacman -Si {} (repo: {})", name, repo);
-    let output = Command::new("pacman")
-        .args(["-Si", name])
-        .env("LC_ALL", "C")
-        .env("LANG", "C")
-        .stdin(Stdio::null())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
-        .map_err(|e| {
-            tracing::error!("Failed to execute pacman -Si {}: {}", name, e);
-            crate::error::ArchToolkitError::Parse(format!("pacman -Si failed: {e}"))
-        })?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        tracing::error!(
-            "pacman -Si {} failed with status {:?}: {}",
-            name,
-            output.status.code(),
-            stderr
-        );
-        return Err(crate::error::ArchToolkitError::Parse(format!(
-            "pacman -Si failed for {name}: {stderr}"
-        )));
-    }
-
-    let text = String::from_utf8_lossy(&output.stdout);
-    tracing::debug!("pacman -Si {} output ({} bytes)", name, text.len());
-
-    let dep_names = parse_pacman_si_deps(&text);
+    tracing::debug!("Resolving {} from pacman -Si (repo: {})", name, repo);
+    let fields = pacman.fields(PacmanDb::Sync, name).ok_or_else(|| {
+        crate::error::ArchToolkitError::Parse(format!("pacman -Si failed for {name}"))
+    })?;
     tracing::debug!(
         "Parsed {} dependency names from pacman -Si output",
-        dep_names.len()
+        fields.depends.len()
     );
 
     Ok(process_dependency_specs(
-        dep_names, name, installed, provided, upgradable,
+        fields.depends,
+        name,
+        DependencyKind::Runtime,
+        installed,
+        provided,
+        upgradable,
+        pacman.runner(),
     ))
 }
 
@@ -454,6 +969,7 @@ fn resolve_official_package_deps<S: BuildHasher>(
 /// - `installed`: Set of locally installed packages.
 /// - `provided`: Set of package names provided by installed packages.
 /// - `upgradable`: Set of packages flagged for upgrades.
+/// - `runner`: Executes the helper and pacman queries.
 ///
 /// Output:
 /// - Returns Some(Vec<Dependency>) if successful, None otherwise.
@@ -464,19 +980,12 @@ fn try_helper_resolution<S: BuildHasher>(
     helper: &str,
     name: &str,
     installed: &HashSet<String, S>,
-    provided: &HashSet<String, S>,
+    provided: &HashSet<ProvidedPackage, S>,
     upgradable: &HashSet<String, S>,
+    runner: &dyn CommandRunner,
 ) -> Option<Vec<Dependency>> {
     tracing::debug!("Trying {} -Si {} for dependency resolution", helper, name);
-    let output = Command::new(helper)
-        .args(["-Si", name])
-        .env("LC_ALL", "C")
-        .env("LANG", "C")
-        .stdin(Stdio::null())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
-        .ok()?;
+    let output = runner.run(helper, &["-Si", name]).ok()?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -503,7 +1012,15 @@ fn try_helper_resolution<S: BuildHasher>(
         name
     );
 
-    let deps = process_dependency_specs(dep_names, name, installed, provided, upgradable);
+    let deps = process_dependency_specs(
+        dep_names,
+        name,
+        DependencyKind::Runtime,
+        installed,
+        provided,
+        upgradable,
+        runner,
+    );
     Some(deps)
 }
 
@@ -512,58 +1029,142 @@ fn try_helper_resolution<S: BuildHasher>(
 /// Inputs:
 /// - `name`: Package name.
 /// - `deps`: Existing dependency list to enhance.
+/// - `srcinfo`: `.SRCINFO` content of `name`, if it was fetched.
+/// - `settings`: Effective settings providing the include flags.
 /// - `installed`: Set of locally installed packages.
 /// - `provided`: Set of package names provided by installed packages.
 /// - `upgradable`: Set of packages flagged for upgrades.
+/// - `runner`: Executes the pacman status and repository queries.
 ///
 /// Output:
-/// - Returns the enhanced dependency list.
+/// - Returns `deps` extended with the `.SRCINFO` dependencies not already present.
 ///
 /// Details:
-/// - Fetches and parses .SRCINFO to add missing depends entries.
-/// - Requires `feature = "aur"` to be enabled.
-#[cfg(feature = "aur")]
+/// - Make, check, and optional dependencies are included according to `settings`.
+/// - Returns `deps` unchanged without `.SRCINFO` content; the sync resolver cannot fetch it,
+///   `DependencyResolver::resolve_async()` prefetches it through an `ArchClient`.
+#[allow(clippy::too_many_arguments)] // Status sets, settings and runner are independent inputs
 fn enhance_with_srcinfo<S: BuildHasher>(
     name: &str,
-    deps: Vec<Dependency>,
-    _installed: &HashSet<String, S>,
-    _provided: &HashSet<String, S>,
-    _upgradable: &HashSet<String, S>,
+    mut deps: Vec<Dependency>,
+    srcinfo: Option<&str>,
+    settings: &ResolveSettings,
+    installed: &HashSet<String, S>,
+    provided: &HashSet<ProvidedPackage, S>,
+    upgradable: &HashSet<String, S>,
+    runner: &dyn CommandRunner,
 ) -> Vec<Dependency> {
-    // Note: fetch_srcinfo is async and requires a reqwest client, so we can't use it here
-    // in the sync context. This is a limitation - callers should fetch .SRCINFO separately
-    // if needed, or use the async API when available.
-    tracing::debug!(
-        "Skipping .SRCINFO enhancement for {} (requires async context)",
-        name
-    );
+    let Some(text) = srcinfo else {
+        tracing::debug!("No .SRCINFO available for {}, skipping enhancement", name);
+        return deps;
+    };
+
+    let mut added = 0;
+    for dep in process_dependency_arrays(
+        parse_srcinfo_deps(text),
+        name,
+        settings,
+        installed,
+        provided,
+        upgradable,
+        runner,
+    ) {
+        if !deps.iter().any(|existing| existing.name == dep.name) {
+            deps.push(dep);
+            added += 1;
+        }
+    }
+    tracing::debug!("Added {} dependencies from .SRCINFO for {}", added, name);
     deps
 }
 
-/// What: Enhance dependency list with .SRCINFO data (no-op when AUR feature is disabled).
+/// What: Enhance dependency list with .SRCINFO data fetched through an `ArchClient`.
 ///
 /// Inputs:
-/// - `name`: Package name (unused).
-/// - `deps`: Existing dependency list to return as-is.
-/// - `installed`: Set of locally installed packages (unused).
-/// - `provided`: Set of package names provided by installed packages (unused).
-/// - `upgradable`: Set of packages flagged for upgrades (unused).
-///
+/// - `client`: `ArchClient` used to fetch `.SRCINFO` (rate limiting, retries, caching).
+/// - `runner`: Executes the pacman status and repository queries.
+/// - `name`: AUR package name.
+/// - `deps`: Existing dependency list to extend.
+/// - `include_makedepends`: Whether to add `makedepends` entries.
+/// - `installed`: Set of locally installed packages.
+/// - `provided`: Set of package names provided by installed packages.
+/// - `upgradable`: Set of packages flagged for upgrades.
+///
 /// Output:
-/// - Returns the dependency list unchanged.
+/// - Returns `deps` extended with `.SRCINFO` runtime (and optionally build-time) dependencies.
 ///
 /// Details:
-/// - This is a no-op when `feature = "aur"` is not enabled.
-#[cfg(not(feature = "aur"))]
-#[allow(clippy::unused_parameters)]
-fn enhance_with_srcinfo<S: BuildHasher>(
-    _name: &str,
+/// - Async counterpart of the sync enhancement step, which cannot fetch `.SRCINFO`.
+/// - Adds `depends` (and `makedepends` with `include_makedepends`) entries not already
+///   present in `deps`.
+/// - Returns `deps` unchanged if `.SRCINFO` cannot be fetched.
+/// - Requires `feature = "aur"` to be enabled.
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::ArchClient;
+/// use arch_toolkit::deps::{SystemRunner, enhance_with_srcinfo_async};
+/// use std::collections::HashSet;
+///
+/// # async fn example() -> arch_toolkit::error::Result<()> {
+/// let client = ArchClient::new()?;
+/// let empty = HashSet::new();
+/// let no_provides = HashSet::new();
+/// let deps = enhance_with_srcinfo_async(
+///     &client,
+///     &SystemRunner,
+///     "yay",
+///     Vec::new(),
+///     true,
+///     &empty,
+///     &no_provides,
+///     &empty,
+/// )
+/// .await;
+/// for dep in deps {
+///     println!("{} ({})", dep.name, dep.status);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "aur")]
+#[allow(clippy::too_many_arguments)] // Client, runner, flag and status sets are independent inputs
+pub async fn enhance_with_srcinfo_async<S: BuildHasher + Sync>(
+    client: &ArchClient,
+    runner: &dyn CommandRunner,
+    name: &str,
     deps: Vec<Dependency>,
-    _installed: &HashSet<String, S>,
-    _provided: &HashSet<String, S>,
-    _upgradable: &HashSet<String, S>,
+    include_makedepends: bool,
+    installed: &HashSet<String, S>,
+    provided: &HashSet<ProvidedPackage, S>,
+    upgradable: &HashSet<String, S>,
 ) -> Vec<Dependency> {
-    deps
+    let text = match client.aur().srcinfo(name).await {
+        Ok(text) => text,
+        Err(e) => {
+            tracing::debug!("Skipping .SRCINFO enhancement for {}: {}", name, e);
+            return deps;
+        }
+    };
+
+    let overrides = ResolveOverrides {
+        include_makedepends: Some(include_makedepends),
+        include_checkdepends: Some(false),
+        include_optdepends: Some(false),
+        ..ResolveOverrides::default()
+    };
+    let settings = ResolveSettings::merge(&ResolverConfig::default(), overrides);
+    enhance_with_srcinfo(
+        name,
+        deps,
+        Some(&text),
+        &settings,
+        installed,
+        provided,
+        upgradable,
+        runner,
+    )
 }
 
 /// What: Fallback to cached PKGBUILD for dependency resolution.
@@ -571,21 +1172,26 @@ fn enhance_with_srcinfo<S: BuildHasher>(
 /// Inputs:
 /// - `name`: Package name.
 /// - `pkgbuild_cache`: Optional callback to fetch PKGBUILD from cache.
+/// - `settings`: Effective settings providing the include flags.
 /// - `installed`: Set of locally installed packages.
 /// - `provided`: Set of package names provided by installed packages.
 /// - `upgradable`: Set of packages flagged for upgrades.
+/// - `runner`: Executes the pacman status and repository queries.
 ///
 /// Output:
 /// - Returns a vector of `Dependency` records if `PKGBUILD` is found, empty vector otherwise.
 ///
 /// Details:
 /// - Attempts to use cached PKGBUILD when .SRCINFO is unavailable (offline fallback).
+/// - Make, check, and optional dependencies are included according to `settings`.
 fn fallback_to_pkgbuild<S: BuildHasher>(
     name: &str,
     pkgbuild_cache: Option<&PkgbuildCacheFn>,
+    settings: &ResolveSettings,
     installed: &HashSet<String, S>,
-    provided: &HashSet<String, S>,
+    provided: &HashSet<ProvidedPackage, S>,
     upgradable: &HashSet<String, S>,
+    runner: &dyn CommandRunner,
 ) -> Vec<Dependency> {
     let Some(pkgbuild_text) = pkgbuild_cache.and_then(|f| f(name)) else {
         tracing::debug!(
@@ -599,9 +1205,15 @@ fn fallback_to_pkgbuild<S: BuildHasher>(
         "Using cached PKGBUILD for {} to resolve dependencies (offline fallback)",
         name
     );
-    let (pkgbuild_depends, _, _, _) = parse_pkgbuild_deps(&pkgbuild_text);
-
-    let deps = process_dependency_specs(pkgbuild_depends, name, installed, provided, upgradable);
+    let deps = process_dependency_arrays(
+        parse_pkgbuild_deps(&pkgbuild_text),
+        name,
+        settings,
+        installed,
+        provided,
+        upgradable,
+        runner,
+    );
     tracing::info!(
         "Resolved {} dependencies from cached PKGBUILD for {}",
         deps.len(),
@@ -610,6 +1222,47 @@ fn fallback_to_pkgbuild<S: BuildHasher>(
     deps
 }
 
+/// What: Read a PKGBUILD from the configured cache, recording it when it is stale.
+///
+/// Inputs:
+/// - `name`: Package name.
+/// - `config`: Resolver configuration providing the cache and commit callbacks.
+/// - `stale`: Names of packages whose cached PKGBUILD is stale; `name` is added if so.
+///
+/// Output:
+/// - The cached PKGBUILD, or `None` if there is none.
+///
+/// Details:
+/// - The cache is stale when `pkgbuild_hash` and `aur_commit` both report a commit for
+///   `name` and the two differ. The resolver cannot refetch PKGBUILDs, so the stale copy is
+///   still used (its dependencies beat none) and `name` ends up in
+///   `DependencyResolution::stale_pkgbuilds` for the caller to refetch.
+/// - Without either commit callback (or commit) the cached PKGBUILD is used as-is.
+fn current_cached_pkgbuild(
+    name: &str,
+    config: &ResolverConfig,
+    stale: &Mutex<BTreeSet<String>>,
+) -> Option<String> {
+    let cache = config.pkgbuild_cache.as_ref()?;
+    if let (Some(pkgbuild_hash), Some(aur_commit)) = (&config.pkgbuild_hash, &config.aur_commit)
+        && let Some(cached) = pkgbuild_hash(name)
+        && let Some(current) = aur_commit(name)
+        && cached != current
+    {
+        tracing::warn!(
+            package = %name,
+            cached = %cached,
+            current = %current,
+            "Cached PKGBUILD is stale, using it until it is refetched"
+        );
+        stale
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .insert(name.to_string());
+    }
+    cache(name)
+}
+
 /// What: Resolve dependencies for an AUR package.
 ///
 /// Inputs:
@@ -618,28 +1271,40 @@ fn fallback_to_pkgbuild<S: BuildHasher>(
 /// - `provided`: Set of package names provided by installed packages.
 /// - `upgradable`: Set of packages flagged for upgrades.
 /// - `pkgbuild_cache`: Optional callback to fetch PKGBUILD from cache.
+/// - `srcinfo`: Prefetched `.SRCINFO` content by package name.
+/// - `settings`: Effective settings providing the include flags.
+/// - `runner`: Executes the helper and pacman queries.
 ///
 /// Output:
 /// - Returns a vector of `Dependency` records.
 ///
 /// Details:
-/// - Tries paru/yay first, then falls back to .SRCINFO and cached PKGBUILD.
+/// - Tries paru/yay first, then adds prefetched .SRCINFO dependencies and falls back to the
+///   cached PKGBUILD when neither produced any.
+/// - With `settings.offline`, helpers are skipped.
+/// - Helpers only report runtime dependencies; make, check, and optional dependencies
+///   come from .SRCINFO or the cached PKGBUILD.
+#[allow(clippy::too_many_arguments)] // Status sets, caches and settings are independent inputs
 fn resolve_aur_package_deps<S: BuildHasher>(
     name: &str,
     installed: &HashSet<String, S>,
-    provided: &HashSet<String, S>,
+    provided: &HashSet<ProvidedPackage, S>,
     upgradable: &HashSet<String, S>,
     pkgbuild_cache: Option<&PkgbuildCacheFn>,
+    srcinfo: &HashMap<String, String>,
+    settings: &ResolveSettings,
+    runner: &dyn CommandRunner,
 ) -> Vec<Dependency> {
     tracing::debug!(
         "Attempting to resolve AUR package: {} (will skip if not found)",
         name
     );
 
-    // Try paru first
-    let (mut deps, mut used_helper) = if is_command_available("paru")
+    // Try paru first (AUR helpers query the network, so never offline)
+    let (mut deps, mut used_helper) = if !settings.offline
+        && is_command_available(runner, "paru")
         && let Some(helper_deps) =
-            try_helper_resolution("paru", name, installed, provided, upgradable)
+            try_helper_resolution("paru", name, installed, provided, upgradable, runner)
     {
         (helper_deps, true)
     } else {
@@ -648,9 +1313,10 @@ fn resolve_aur_package_deps<S: BuildHasher>(
 
     // Try yay if paru didn't work
     if !used_helper
-        && is_command_available("yay")
+        && !settings.offline
+        && is_command_available(runner, "yay")
         && let Some(helper_deps) =
-            try_helper_resolution("yay", name, installed, provided, upgradable)
+            try_helper_resolution("yay", name, installed, provided, upgradable, runner)
     {
         deps = helper_deps;
         used_helper = true;
@@ -664,11 +1330,28 @@ fn resolve_aur_package_deps<S: BuildHasher>(
     }
 
     // Always try to enhance with .SRCINFO
-    deps = enhance_with_srcinfo(name, deps, installed, provided, upgradable);
+    deps = enhance_with_srcinfo(
+        name,
+        deps,
+        srcinfo.get(name).map(String::as_str),
+        settings,
+        installed,
+        provided,
+        upgradable,
+        runner,
+    );
 
     // Fallback to PKGBUILD if no dependencies were found
     if !used_helper && deps.is_empty() {
-        deps = fallback_to_pkgbuild(name, pkgbuild_cache, installed, provided, upgradable);
+        deps = fallback_to_pkgbuild(
+            name,
+            pkgbuild_cache,
+            settings,
+            installed,
+            provided,
+            upgradable,
+            runner,
+        );
     }
 
     deps
@@ -682,32 +1365,47 @@ fn resolve_aur_package_deps<S: BuildHasher>(
 /// - `installed`: Set of locally installed packages for status determination.
 /// - `provided`: Set of package names provided by installed packages.
 /// - `upgradable`: Set of packages flagged for upgrades, used to detect stale dependencies.
+/// - `pacman`: Query cache shared by the current resolution.
 /// - `pkgbuild_cache`: Optional callback to fetch PKGBUILD from cache.
+/// - `srcinfo`: Prefetched `.SRCINFO` content of AUR packages by name.
+/// - `settings`: Effective settings providing the include flags.
 ///
 /// Output:
 /// - Returns a vector of `Dependency` records or an error string when resolution fails.
 ///
 /// Details:
 /// - Invokes pacman or AUR helpers depending on source, filtering out virtual entries and self references.
+/// - With `settings.offline`, official packages are read from the local database (`pacman -Qi`).
+#[allow(clippy::too_many_arguments)] // Status sets, caches and settings are independent inputs
 fn resolve_package_deps<S: BuildHasher>(
     name: &str,
     source: &PackageSource,
     installed: &HashSet<String, S>,
-    provided: &HashSet<String, S>,
+    provided: &HashSet<ProvidedPackage, S>,
     upgradable: &HashSet<String, S>,
+    pacman: &PacmanQueryCache,
     pkgbuild_cache: Option<&PkgbuildCacheFn>,
+    srcinfo: &HashMap<String, String>,
+    settings: &ResolveSettings,
 ) -> Result<Vec<Dependency>> {
     let deps = match source {
         PackageSource::Official { repo, .. } => {
-            if repo == "local" {
-                resolve_local_package_deps(name, installed, provided, upgradable)?
+            if repo == "local" || settings.offline {
+                resolve_local_package_deps(name, pacman, installed, provided, upgradable)
             } else {
-                resolve_official_package_deps(name, repo, installed, provided, upgradable)?
+                resolve_official_package_deps(name, repo, pacman, installed, provided, upgradable)?
             }
         }
-        PackageSource::Aur => {
-            resolve_aur_package_deps(name, installed, provided, upgradable, pkgbuild_cache)
-        }
+        PackageSource::Aur => resolve_aur_package_deps(
+            name,
+            installed,
+            provided,
+            upgradable,
+            pkgbuild_cache,
+            srcinfo,
+            settings,
+            pacman.runner(),
+        ),
     };
 
     tracing::debug!("Resolved {} dependencies for package {}", deps.len(), name);
@@ -742,60 +1440,47 @@ fn resolve_package_deps<S: BuildHasher>(
 /// );
 /// println!("Found {} conflicts", conflicts.len());
 /// ```
+#[must_use]
 pub fn fetch_package_conflicts(name: &str, source: &PackageSource) -> Vec<String> {
+    package_conflicts(name, source, &PacmanQueryCache::new(&SystemRunner))
+}
+
+/// What: Fetch conflicts for a package, reading pacman output through a query cache.
+///
+/// Inputs:
+/// - `name`: Package identifier.
+/// - `source`: Source enum describing whether the package is official or AUR.
+/// - `pacman`: Query cache shared by the current resolution.
+///
+/// Output:
+/// - Returns a vector of conflicting package names, or empty vector on error.
+///
+/// Details:
+/// - Local packages are read with `pacman -Qi`, other official packages with `pacman -Si`.
+/// - AUR packages are queried through paru/yay (via the cache's runner) and are not cached.
+fn package_conflicts(name: &str, source: &PackageSource, pacman: &PacmanQueryCache) -> Vec<String> {
     match source {
         PackageSource::Official { repo, .. } => {
             // Handle local packages specially - use pacman -Qi instead of -Si
-            if repo == "local" {
-                tracing::debug!("Running: pacman -Qi {} (local package, conflicts)", name);
-                if let Ok(output) = Command::new("pacman")
-                    .args(["-Qi", name])
-                    .env("LC_ALL", "C")
-                    .env("LANG", "C")
-                    .stdin(Stdio::null())
-                    .stdout(Stdio::piped())
-                    .stderr(Stdio::piped())
-                    .output()
-                    && output.status.success()
-                {
-                    let text = String::from_utf8_lossy(&output.stdout);
-                    return parse_pacman_si_conflicts(&text);
-                }
-                return Vec::new();
-            }
-
-            // Use pacman -Si to get conflicts
-            tracing::debug!("Running: pacman -Si {} (conflicts)", name);
-            if let Ok(output) = Command::new("pacman")
-                .args(["-Si", name])
-                .env("LC_ALL", "C")
-                .env("LANG", "C")
-                .stdin(Stdio::null())
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .output()
-                && output.status.success()
-            {
-                let text = String::from_utf8_lossy(&output.stdout);
-                return parse_pacman_si_conflicts(&text);
-            }
-            Vec::new()
+            let db = if repo == "local" {
+                PacmanDb::Local
+            } else {
+                PacmanDb::Sync
+            };
+            pacman
+                .fields(db, name)
+                .map(|fields| fields.conflicts)
+                .unwrap_or_default()
         }
         PackageSource::Aur => {
             // Try paru/yay first
-            let has_paru = is_command_available("paru");
-            let has_yay = is_command_available("yay");
+            let runner = pacman.runner();
+            let has_paru = is_command_available(runner, "paru");
+            let has_yay = is_command_available(runner, "yay");
 
             if has_paru {
                 tracing::debug!("Trying paru -Si {} for conflicts", name);
-                if let Ok(output) = Command::new("paru")
-                    .args(["-Si", name])
-                    .env("LC_ALL", "C")
-                    .env("LANG", "C")
-                    .stdin(Stdio::null())
-                    .stdout(Stdio::piped())
-                    .stderr(Stdio::piped())
-                    .output()
+                if let Ok(output) = runner.run("paru", &["-Si", name])
                     && output.status.success()
                 {
                     let text = String::from_utf8_lossy(&output.stdout);
@@ -808,14 +1493,7 @@ pub fn fetch_package_conflicts(name: &str, source: &PackageSource) -> Vec<String
 
             if has_yay {
                 tracing::debug!("Trying yay -Si {} for conflicts", name);
-                if let Ok(output) = Command::new("yay")
-                    .args(["-Si", name])
-                    .env("LC_ALL", "C")
-                    .env("LANG", "C")
-                    .stdin(Stdio::null())
-                    .stdout(Stdio::piped())
-                    .stderr(Stdio::piped())
-                    .output()
+                if let Ok(output) = runner.run("yay", &["-Si", name])
                     && output.status.success()
                 {
                     let text = String::from_utf8_lossy(&output.stdout);
@@ -842,6 +1520,23 @@ pub fn fetch_package_conflicts(name: &str, source: &PackageSource) -> Vec<String
     }
 }
 
+/// What: Sort dependencies by status urgency, then by name.
+///
+/// Inputs:
+/// - `deps`: Dependencies to sort in place.
+///
+/// Output:
+/// - None; conflicts come first, then missing, to-install, to-upgrade, and installed.
+fn sort_by_priority(deps: &mut [Dependency]) {
+    deps.sort_by(|a, b| {
+        let priority_a = dependency_priority(&a.status);
+        let priority_b = dependency_priority(&b.status);
+        priority_a
+            .cmp(&priority_b)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+}
+
 /// What: Get priority value for dependency status (lower = more urgent).
 ///
 /// Inputs:
@@ -864,6 +1559,7 @@ const fn dependency_priority(status: &DependencyStatus) -> u8 {
 /// - `installed`: Set of installed package names.
 /// - `provided`: Set of provided packages.
 /// - `upgradable`: Set of upgradable package names.
+/// - `runner`: Executes the pacman status queries.
 /// - `deps`: Mutable reference to the dependency map to update.
 ///
 /// Output:
@@ -871,36 +1567,43 @@ const fn dependency_priority(status: &DependencyStatus) -> u8 {
 ///
 /// Details:
 /// - Merges status (keeps worst), version requirements (keeps more restrictive), and `required_by` lists.
+/// - `required_by` is kept sorted and deduplicated, regardless of how often a parent is merged.
 fn merge_dependency<S: BuildHasher>(
     dep: &Dependency,
     parent_name: &str,
     installed: &HashSet<String, S>,
-    provided: &HashSet<String, S>,
+    provided: &HashSet<ProvidedPackage, S>,
     upgradable: &HashSet<String, S>,
+    runner: &dyn CommandRunner,
     deps: &mut HashMap<String, Dependency>,
 ) {
     let dep_name = dep.name.clone();
 
-    // Check if dependency already exists and get its current state
-    let needs_required_by_update = deps
-        .get(&dep_name)
-        .is_none_or(|e| !e.required_by.contains(&parent_name.to_string()));
-
     // Update or create dependency entry
     let entry = deps.entry(dep_name.clone()).or_insert_with(|| Dependency {
         name: dep_name.clone(),
         version_req: dep.version_req.clone(),
         status: dep.status.clone(),
         source: dep.source.clone(),
-        required_by: vec![parent_name.to_string()],
+        required_by: Vec::new(),
         depends_on: Vec::new(),
         is_core: dep.is_core,
         is_system: dep.is_system,
+        kind: dep.kind,
+        explicit: dep.explicit,
+        install_reason: dep.install_reason,
+        satisfied_by: dep.satisfied_by.clone(),
     });
 
-    // Update required_by (add the parent if not already present)
-    if needs_required_by_update {
-        entry.required_by.push(parent_name.to_string());
+    // Keep the strongest kind (a runtime dependency stays runtime when also a make dependency)
+    entry.kind = entry.kind.min(dep.kind);
+
+    // Update required_by (insert the parent in sorted position if not already present)
+    if let Err(pos) = entry
+        .required_by
+        .binary_search_by(|p| p.as_str().cmp(parent_name))
+    {
+        entry.required_by.insert(pos, parent_name.to_string());
     }
 
     // Merge status (keep worst)
@@ -910,6 +1613,7 @@ fn merge_dependency<S: BuildHasher>(
         let new_priority = dependency_priority(&dep.status);
         if new_priority < existing_priority {
             entry.status = dep.status.clone();
+            entry.satisfied_by.clone_from(&dep.satisfied_by);
         }
     }
 
@@ -929,19 +1633,21 @@ fn merge_dependency<S: BuildHasher>(
             entry.version_req.clone_from(&dep.version_req);
         } else {
             // Check which version requirement is more restrictive
-            let existing_status = determine_status(
+            let existing_status = dependency_status(
                 &entry.name,
                 &entry.version_req,
                 installed,
                 provided,
                 upgradable,
+                runner,
             );
-            let new_status = determine_status(
+            let new_status = dependency_status(
                 &entry.name,
                 &dep.version_req,
                 installed,
                 provided,
                 upgradable,
+                runner,
             );
             let existing_req_priority = dependency_priority(&existing_status);
             let new_req_priority = dependency_priority(&new_status);
@@ -949,9 +1655,413 @@ fn merge_dependency<S: BuildHasher>(
             if new_req_priority < existing_req_priority {
                 entry.version_req.clone_from(&dep.version_req);
                 entry.status = new_status;
+                entry.satisfied_by.clone_from(&dep.satisfied_by);
+            }
+        }
+    }
+}
+
+/// What: Effective include flags and depth for a single resolve call.
+///
+/// Inputs:
+/// - Built from the stored `ResolverConfig` merged with call-time `ResolveOverrides`.
+///
+/// Output:
+/// - Flags and depth used for one resolution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)] // Independent resolution switches
+struct ResolveSettings {
+    /// Whether to include optional dependencies.
+    include_optdepends: bool,
+    /// Whether to include make dependencies.
+    include_makedepends: bool,
+    /// Whether to include check dependencies.
+    include_checkdepends: bool,
+    /// Maximum depth for transitive dependency resolution.
+    max_depth: usize,
+    /// Maximum number of unique dependencies to collect (0 = unlimited).
+    max_packages: usize,
+    /// Whether to avoid sync database queries and AUR helpers.
+    offline: bool,
+}
+
+impl ResolveSettings {
+    /// What: Merge call-time overrides onto the stored configuration.
+    ///
+    /// Inputs:
+    /// - `config`: Resolver configuration.
+    /// - `overrides`: Per-call overrides; `None` fields keep the configured value.
+    ///
+    /// Output:
+    /// - Effective settings for the call.
+    fn merge(config: &ResolverConfig, overrides: ResolveOverrides) -> Self {
+        Self {
+            include_optdepends: overrides
+                .include_optdepends
+                .unwrap_or(config.include_optdepends),
+            include_makedepends: overrides
+                .include_makedepends
+                .unwrap_or(config.include_makedepends),
+            include_checkdepends: overrides
+                .include_checkdepends
+                .unwrap_or(config.include_checkdepends),
+            max_depth: overrides.max_depth.unwrap_or(config.max_depth),
+            max_packages: config.max_packages,
+            offline: config.offline,
+        }
+    }
+}
+
+/// What: Prefetch the official (non-local) packages among the roots into the query cache.
+///
+/// Inputs:
+/// - `packages`: Root packages being resolved.
+/// - `settings`: Effective settings; nothing is fetched when `offline` is set.
+/// - `pacman`: Query cache shared by the current resolution.
+///
+/// Details:
+/// - Uses batched `pacman -Si` calls to reduce pacman command overhead; the cached results
+///   serve both dependency resolution and conflict detection.
+/// - Offline resolution reads the local database per package instead.
+fn prefetch_root_packages(
+    packages: &[PackageRef],
+    settings: &ResolveSettings,
+    pacman: &PacmanQueryCache,
+) {
+    if settings.offline {
+        return;
+    }
+    let official_packages: Vec<&str> = packages
+        .iter()
+        .filter_map(|pkg| {
+            if let PackageSource::Official { repo, .. } = &pkg.source {
+                if repo == "local" {
+                    None
+                } else {
+                    Some(pkg.name.as_str())
+                }
+            } else {
+                None
+            }
+        })
+        .collect();
+    if !official_packages.is_empty() {
+        pacman.prefetch_sync(&official_packages);
+    }
+}
+
+/// What: Read the declared conflicts of a root package.
+///
+/// Inputs:
+/// - `package`: Package whose conflicts are needed.
+/// - `settings`: Effective settings; `offline` avoids sync database and AUR helper queries.
+/// - `pacman`: Query cache shared by the current resolution.
+/// - `pkgbuild_cache`: Optional callback to fetch PKGBUILD from cache.
+///
+/// Output:
+/// - Conflicts as reported by `fetch_package_conflicts()`, or offline: from `pacman -Qi` for
+///   official packages (empty if not installed) and from the cached PKGBUILD for AUR packages.
+fn root_package_conflicts(
+    package: &PackageRef,
+    settings: &ResolveSettings,
+    pacman: &PacmanQueryCache,
+    pkgbuild_cache: Option<&PkgbuildCacheFn>,
+) -> Vec<String> {
+    if !settings.offline {
+        return package_conflicts(&package.name, &package.source, pacman);
+    }
+    match &package.source {
+        PackageSource::Official { .. } => pacman
+            .fields(PacmanDb::Local, &package.name)
+            .map(|fields| fields.conflicts)
+            .unwrap_or_default(),
+        PackageSource::Aur => pkgbuild_cache
+            .and_then(|f| f(&package.name))
+            .map(|pkgbuild| parse_pkgbuild_conflicts(&pkgbuild))
+            .unwrap_or_default(),
+    }
+}
+
+/// What: Detect conflicts of the root packages and mark them in the dependency map.
+///
+/// Inputs:
+/// - `packages`: Root packages being resolved.
+/// - `root_names`: Names of the root packages.
+/// - `installed`: Set of locally installed packages.
+/// - `provided`: Set of package names provided by installed packages.
+/// - `upgradable`: Set of packages flagged for upgrades.
+/// - `runner`: Executes the pacman status queries.
+/// - `deps`: Dependency map receiving a `Conflict` entry per conflicting package.
+/// - `conflicts_of`: Returns the declared conflicts of a root package.
+///
+/// Output:
+/// - Returns the deduplicated names of conflicting packages.
+///
+/// Details:
+/// - A conflict counts when the conflicting package is installed or is itself a root.
+#[allow(clippy::too_many_arguments)] // Status sets, runner and callback are independent inputs
+fn mark_root_conflicts<S: BuildHasher>(
+    packages: &[PackageRef],
+    root_names: &HashSet<String>,
+    installed: &HashSet<String, S>,
+    provided: &HashSet<ProvidedPackage, S>,
+    upgradable: &HashSet<String, S>,
+    runner: &dyn CommandRunner,
+    deps: &mut HashMap<String, Dependency>,
+    conflicts_of: impl Fn(&PackageRef) -> Vec<String>,
+) -> Vec<String> {
+    let mut conflicts: Vec<String> = Vec::new();
+    tracing::info!("Checking conflicts for {} package(s)", packages.len());
+    for package in packages {
+        let package_conflicts = conflicts_of(package);
+        for conflict_name in package_conflicts {
+            if installed.contains(&conflict_name) || root_names.contains(&conflict_name) {
+                if !conflicts.contains(&conflict_name) {
+                    conflicts.push(conflict_name.clone());
+                }
+                // Mark as conflict in dependency map
+                let dep = Dependency {
+                    name: conflict_name,
+                    version_req: String::new(),
+                    status: DependencyStatus::Conflict {
+                        reason: format!("Conflicts with {}", package.name),
+                    },
+                    source: DependencySource::Local,
+                    required_by: vec![package.name.clone()],
+                    depends_on: Vec::new(),
+                    is_core: false,
+                    is_system: false,
+                    kind: DependencyKind::Runtime,
+                    explicit: false,
+                    install_reason: InstallReason::Unknown,
+                    satisfied_by: None,
+                };
+                merge_dependency(
+                    &dep,
+                    &package.name,
+                    installed,
+                    provided,
+                    upgradable,
+                    runner,
+                    deps,
+                );
+            }
+        }
+    }
+    conflicts
+}
+
+/// What: Map a dependency to the package source used to resolve its own dependencies.
+///
+/// Inputs:
+/// - `dep`: Dependency discovered during resolution.
+///
+/// Output:
+/// - Returns `Some(PackageSource)` if the dependency should be expanded, `None` otherwise.
+///
+/// Details:
+/// - Only dependencies that need action (`ToInstall`/`ToUpgrade`) are expanded; installed
+///   packages already have their dependencies satisfied.
+/// - Local packages are never expanded.
+fn expandable_source(dep: &Dependency) -> Option<PackageSource> {
+    if !dep.status.needs_action() {
+        return None;
+    }
+    match &dep.source {
+        DependencySource::Official { repo } => Some(PackageSource::Official {
+            repo: repo.clone(),
+            arch: String::new(),
+        }),
+        DependencySource::Aur => Some(PackageSource::Aur),
+        DependencySource::Local => None,
+    }
+}
+
+/// What: Resolve dependencies of dependencies breadth-first, up to the configured depth.
+///
+/// Inputs:
+/// - `deps`: Dependency map holding the direct dependencies of the roots; extended in place.
+/// - `roots`: Names of the root packages, which are never expanded again.
+/// - `settings`: Effective settings providing `max_depth` and `max_packages`.
+/// - `installed`: Set of locally installed packages.
+/// - `provided`: Set of package names provided by installed packages.
+/// - `upgradable`: Set of packages flagged for upgrades.
+/// - `runner`: Executes the pacman status queries when merging.
+/// - `resolve`: Resolves the direct dependencies of one package from its source.
+///
+/// Output:
+/// - Returns the names of newly discovered dependencies with `Missing` status.
+///
+/// Details:
+/// - Direct dependencies are at depth 1; a dependency at depth `d` is expanded while
+///   `d <= max_depth`.
+/// - Each package is expanded at most once, so dependency cycles terminate.
+/// - New dependencies are no longer added once `deps` holds `max_packages` entries.
+/// - Fills `depends_on` of every expanded dependency.
+/// - Packages whose dependencies cannot be resolved are logged and skipped.
+#[allow(clippy::too_many_arguments)] // Status sets, runner and callback are independent inputs
+fn expand_transitive<S, F>(
+    deps: &mut HashMap<String, Dependency>,
+    roots: &HashSet<String>,
+    settings: &ResolveSettings,
+    installed: &HashSet<String, S>,
+    provided: &HashSet<ProvidedPackage, S>,
+    upgradable: &HashSet<String, S>,
+    runner: &dyn CommandRunner,
+    mut resolve: F,
+) -> Vec<String>
+where
+    S: BuildHasher,
+    F: FnMut(&str, &PackageSource) -> Result<Vec<Dependency>>,
+{
+    let mut missing = Vec::new();
+    let mut visited = roots.clone();
+    let mut direct: Vec<String> = deps
+        .keys()
+        .filter(|name| !roots.contains(*name))
+        .cloned()
+        .collect();
+    direct.sort();
+    let mut queue: VecDeque<(String, usize)> = direct.into_iter().map(|n| (n, 1)).collect();
+    let mut capped = false;
+
+    while let Some((name, depth)) = queue.pop_front() {
+        if depth > settings.max_depth {
+            break;
+        }
+        if !visited.insert(name.clone()) {
+            continue;
+        }
+        let Some(source) = deps.get(&name).and_then(expandable_source) else {
+            continue;
+        };
+        let children = match resolve(&name, &source) {
+            Ok(children) => children,
+            Err(e) => {
+                tracing::warn!("  Failed to resolve dependencies for {}: {}", name, e);
+                continue;
+            }
+        };
+
+        let mut depends_on = Vec::new();
+        for child in children {
+            let at_cap = settings.max_packages > 0 && deps.len() >= settings.max_packages;
+            if at_cap && !deps.contains_key(&child.name) {
+                capped = true;
+                continue;
+            }
+            if matches!(child.status, DependencyStatus::Missing) && !missing.contains(&child.name) {
+                missing.push(child.name.clone());
             }
+            if !visited.contains(&child.name) {
+                queue.push_back((child.name.clone(), depth + 1));
+            }
+            depends_on.push(child.name.clone());
+            merge_dependency(&child, &name, installed, provided, upgradable, runner, deps);
+        }
+        depends_on.sort();
+        depends_on.dedup();
+        if let Some(dep) = deps.get_mut(&name) {
+            dep.depends_on = depends_on;
+        }
+    }
+
+    if capped {
+        tracing::warn!(
+            "Transitive resolution stopped at {} packages (max_packages)",
+            settings.max_packages
+        );
+    }
+    missing
+}
+
+/// What: Find dependency cycles in a resolved dependency list.
+///
+/// Inputs:
+/// - `dependencies`: Resolved dependencies; edges follow `required_by` (parent -> dependency).
+///
+/// Output:
+/// - Sorted, deduplicated cycles, each rotated to start at its smallest package name.
+///
+/// Details:
+/// - Uses a depth-first search and reports one cycle per back edge found.
+fn find_cycles(dependencies: &[Dependency]) -> Vec<Vec<String>> {
+    let mut graph: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for dep in dependencies {
+        for parent in &dep.required_by {
+            graph
+                .entry(parent.as_str())
+                .or_default()
+                .push(dep.name.as_str());
+        }
+    }
+    find_graph_cycles(graph)
+}
+
+/// What: Find cycles in a package graph.
+///
+/// Inputs:
+/// - `graph`: Adjacency list by package name.
+///
+/// Output:
+/// - Sorted, deduplicated cycles in edge order, each rotated to start at its smallest package
+///   name.
+pub(super) fn find_graph_cycles(mut graph: BTreeMap<&str, Vec<&str>>) -> Vec<Vec<String>> {
+    for children in graph.values_mut() {
+        children.sort_unstable();
+        children.dedup();
+    }
+
+    let mut cycles = BTreeSet::new();
+    let mut done = HashSet::new();
+    let mut path = Vec::new();
+    for node in graph.keys() {
+        visit_for_cycles(node, &graph, &mut path, &mut done, &mut cycles);
+    }
+    cycles.into_iter().collect()
+}
+
+/// What: Depth-first visit recording cycles closed by back edges.
+///
+/// Inputs:
+/// - `node`: Package being visited.
+/// - `graph`: Adjacency list of the dependency graph.
+/// - `path`: Packages on the current search path.
+/// - `done`: Packages whose descendants were fully explored.
+/// - `cycles`: Collected cycles, normalized by rotation.
+///
+/// Output:
+/// - Updates `path`, `done`, and `cycles` in place.
+fn visit_for_cycles<'a>(
+    node: &'a str,
+    graph: &BTreeMap<&'a str, Vec<&'a str>>,
+    path: &mut Vec<&'a str>,
+    done: &mut HashSet<&'a str>,
+    cycles: &mut BTreeSet<Vec<String>>,
+) {
+    if done.contains(node) {
+        return;
+    }
+    if let Some(pos) = path.iter().position(|n| *n == node) {
+        let mut cycle: Vec<String> = path[pos..].iter().map(ToString::to_string).collect();
+        if let Some(min) = cycle
+            .iter()
+            .enumerate()
+            .min_by(|a, b| a.1.cmp(b.1))
+            .map(|(i, _)| i)
+        {
+            cycle.rotate_left(min);
         }
+        cycles.insert(cycle);
+        return;
+    }
+
+    path.push(node);
+    for child in graph.get(node).into_iter().flatten() {
+        visit_for_cycles(child, graph, path, done, cycles);
     }
+    path.pop();
+    done.insert(node);
 }
 
 /// Dependency resolver for batch package operations.
@@ -961,6 +2071,8 @@ fn merge_dependency<S: BuildHasher>(
 pub struct DependencyResolver {
     /// Resolver configuration.
     config: ResolverConfig,
+    /// Command runner for pacman and AUR helpers (`None` = `SystemRunner`).
+    runner: Option<Box<dyn CommandRunner>>,
 }
 
 impl DependencyResolver {
@@ -985,9 +2097,7 @@ impl DependencyResolver {
     /// ```
     #[must_use]
     pub fn new() -> Self {
-        Self {
-            config: ResolverConfig::default(),
-        }
+        Self::with_config(ResolverConfig::default())
     }
 
     /// What: Create a resolver with custom configuration.
@@ -1005,22 +2115,54 @@ impl DependencyResolver {
     ///
     /// ```no_run
     /// use arch_toolkit::deps::DependencyResolver;
-    /// use arch_toolkit::types::dependency::ResolverConfig;
+    /// use arch_toolkit::types::dependency::{ResolverConfig, SourceConfig};
     ///
     /// let config = ResolverConfig {
     ///     include_optdepends: true,
     ///     include_makedepends: false,
     ///     include_checkdepends: false,
     ///     max_depth: 0,
+    ///     max_packages: 1000,
     ///     pkgbuild_cache: None,
+    ///     pkgbuild_hash: None,
+    ///     aur_commit: None,
     ///     check_aur: false,
+    ///     offline: false,
+    ///     source_config: SourceConfig::default(),
     /// };
     /// let resolver = DependencyResolver::with_config(config);
     /// ```
     #[must_use]
     #[allow(clippy::missing_const_for_fn)] // ResolverConfig contains function pointer, can't be const
     pub fn with_config(config: ResolverConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            runner: None,
+        }
+    }
+
+    /// What: Run pacman and AUR helper commands through a custom runner.
+    ///
+    /// Inputs:
+    /// - `runner`: Command runner used for every query of this resolver.
+    ///
+    /// Output:
+    /// - Returns the resolver using `runner` instead of `SystemRunner`.
+    ///
+    /// Details:
+    /// - Lets tests and sandboxed callers supply canned `pacman -Si`/`-Qi` output.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arch_toolkit::deps::{DependencyResolver, SystemRunner};
+    ///
+    /// let resolver = DependencyResolver::new().with_runner(SystemRunner);
+    /// ```
+    #[must_use]
+    pub fn with_runner(mut self, runner: impl CommandRunner + 'static) -> Self {
+        self.runner = Some(Box::new(runner));
+        self
     }
 
     /// What: Resolve dependencies for a list of packages.
@@ -1033,7 +2175,12 @@ impl DependencyResolver {
     /// - Returns `Err(ArchToolkitError)` if resolution fails.
     ///
     /// Details:
-    /// - Resolves ONLY direct dependencies (non-recursive) for each package.
+    /// - Resolves direct dependencies, then dependencies of dependencies breadth-first while
+    ///   their depth does not exceed `max_depth` (0 = direct dependencies only).
+    /// - Only dependencies that need action are expanded; each is resolved at most once and
+    ///   gets its `depends_on` filled.
+    /// - Stops adding dependencies once `max_packages` unique dependencies were collected.
+    /// - Reports dependency cycles in `DependencyResolution::cycles`.
     /// - Merges duplicates by name, retaining the most severe status across all requesters.
     /// - Detects conflicts between packages being installed and already installed packages.
     /// - Sorts dependencies by priority (conflicts first, then missing, then to-install, then installed).
@@ -1070,137 +2217,267 @@ impl DependencyResolver {
         &self,
         packages: &[PackageRef],
     ) -> Result<crate::types::dependency::DependencyResolution> {
-        use crate::types::dependency::DependencyResolution;
-
-        if packages.is_empty() {
-            tracing::warn!("No packages provided for dependency resolution");
-            return Ok(DependencyResolution::default());
-        }
-
-        let mut deps: HashMap<String, Dependency> = HashMap::new();
-        let mut conflicts: Vec<String> = Vec::new();
-        let mut missing: Vec<String> = Vec::new();
-
-        // Get installed packages set
-        tracing::info!("Fetching list of installed packages...");
-        let installed = get_installed_packages()?;
-        tracing::info!("Found {} installed packages", installed.len());
-
-        // Get all provided packages (e.g., rustup provides rust)
-        // Note: Provides are checked lazily on-demand for performance, not built upfront
-        tracing::debug!(
-            "Provides will be checked lazily on-demand (not building full set for performance)"
-        );
-        let provided = get_provided_packages(&installed);
-
-        // Get list of upgradable packages to detect if dependencies need upgrades
-        let upgradable = get_upgradable_packages()?;
-        tracing::info!("Found {} upgradable packages", upgradable.len());
+        self.resolve_with(packages, ResolveOverrides::default())
+    }
 
-        // Initialize set of root packages (for tracking)
-        let root_names: HashSet<String> = packages.iter().map(|p| p.name.clone()).collect();
+    /// What: Resolve dependencies with per-call overrides of the resolver settings.
+    ///
+    /// Inputs:
+    /// - `packages`: Slice of `PackageRef` records to resolve dependencies for.
+    /// - `overrides`: Include flags and depth to use for this call only.
+    ///
+    /// Output:
+    /// - Returns `Ok(DependencyResolution)` like `resolve()`.
+    ///
+    /// Details:
+    /// - Overrides are merged onto the stored `ResolverConfig`; `None` fields keep the
+    ///   configured value. The stored configuration is not modified.
+    /// - Avoids rebuilding the resolver for one-off variations.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as `resolve()`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arch_toolkit::deps::{DependencyResolver, ResolveOverrides};
+    /// use arch_toolkit::{PackageRef, PackageSource};
+    ///
+    /// let resolver = DependencyResolver::new();
+    /// let packages = vec![PackageRef {
+    ///     name: "yay".into(),
+    ///     version: "12.3.5-1".into(),
+    ///     source: PackageSource::Aur,
+    /// }];
+    ///
+    /// // Runtime dependencies only, regardless of the resolver's configuration
+    /// let overrides = ResolveOverrides {
+    ///     include_makedepends: Some(false),
+    ///     include_checkdepends: Some(false),
+    ///     include_optdepends: Some(false),
+    ///     ..ResolveOverrides::default()
+    /// };
+    /// let result = resolver.resolve_with(&packages, overrides)?;
+    /// # Ok::<(), arch_toolkit::error::ArchToolkitError>(())
+    /// ```
+    pub fn resolve_with(
+        &self,
+        packages: &[PackageRef],
+        overrides: ResolveOverrides,
+    ) -> Result<crate::types::dependency::DependencyResolution> {
+        let runner = self.runner.as_deref().unwrap_or(&SystemRunner);
+        Ok(self.resolve_with_queries(
+            packages,
+            overrides,
+            &PacmanQueryCache::new(runner),
+            &HashMap::new(),
+        ))
+    }
 
-        // Check conflicts for packages being installed
-        tracing::info!("Checking conflicts for {} package(s)", packages.len());
-        for package in packages {
-            let package_conflicts = fetch_package_conflicts(&package.name, &package.source);
-            for conflict_name in package_conflicts {
-                if installed.contains(&conflict_name) || root_names.contains(&conflict_name) {
-                    if !conflicts.contains(&conflict_name) {
-                        conflicts.push(conflict_name.clone());
+    /// What: Resolve dependencies, adding AUR build dependencies from `.SRCINFO`.
+    ///
+    /// Inputs:
+    /// - `client`: `ArchClient` used to fetch `.SRCINFO` (rate limiting, retries, caching).
+    /// - `packages`: Slice of `PackageRef` records to resolve dependencies for.
+    ///
+    /// Output:
+    /// - Returns `Ok(DependencyResolution)` like `resolve()`.
+    ///
+    /// Details:
+    /// - Fetches `.SRCINFO` of the AUR roots, then of AUR dependencies found by each
+    ///   resolution pass (at most `max_depth` extra passes), so their `depends` and, per the
+    ///   include flags, `makedepends`/`checkdepends`/`optdepends` are resolved with the
+    ///   configured runner.
+    /// - Packages whose `.SRCINFO` cannot be fetched resolve as with `resolve()`.
+    /// - With `offline`, nothing is fetched and this equals `resolve()`.
+    /// - The pacman queries run on the calling task; their output is reused across passes,
+    ///   so each command line runs at most once.
+    /// - Requires `feature = "aur"` to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as `resolve()`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arch_toolkit::ArchClient;
+    /// use arch_toolkit::deps::DependencyResolver;
+    /// use arch_toolkit::{PackageRef, PackageSource};
+    ///
+    /// # async fn example() -> arch_toolkit::error::Result<()> {
+    /// let client = ArchClient::new()?;
+    /// let packages = vec![PackageRef {
+    ///     name: "yay".into(),
+    ///     version: "12.3.5-1".into(),
+    ///     source: PackageSource::Aur,
+    /// }];
+    ///
+    /// let result = DependencyResolver::new()
+    ///     .resolve_async(&client, &packages)
+    ///     .await?;
+    /// println!("Found {} dependencies", result.dependencies.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "aur")]
+    pub async fn resolve_async(
+        &self,
+        client: &ArchClient,
+        packages: &[PackageRef],
+    ) -> Result<crate::types::dependency::DependencyResolution> {
+        let runner = MemoizedRunner::new(self.runner.as_deref().unwrap_or(&SystemRunner));
+        let pacman = PacmanQueryCache::new(&runner);
+        let mut srcinfo: HashMap<String, String> = HashMap::new();
+        let mut fetched: HashSet<String> = HashSet::new();
+        let mut pending: Vec<String> = packages
+            .iter()
+            .filter(|package| matches!(package.source, PackageSource::Aur))
+            .map(|package| package.name.clone())
+            .collect();
+        let mut passes = 0;
+        loop {
+            if self.config.offline {
+                pending.clear();
+            }
+            for name in std::mem::take(&mut pending) {
+                if !fetched.insert(name.clone()) {
+                    continue;
+                }
+                match client.aur().srcinfo(&name).await {
+                    Ok(text) => {
+                        srcinfo.insert(name, text);
                     }
-                    // Mark as conflict in dependency map
-                    let dep = Dependency {
-                        name: conflict_name,
-                        version_req: String::new(),
-                        status: DependencyStatus::Conflict {
-                            reason: format!("Conflicts with {}", package.name),
-                        },
-                        source: DependencySource::Local,
-                        required_by: vec![package.name.clone()],
-                        depends_on: Vec::new(),
-                        is_core: false,
-                        is_system: false,
-                    };
-                    merge_dependency(
-                        &dep,
-                        &package.name,
-                        &installed,
-                        &provided,
-                        &upgradable,
-                        &mut deps,
-                    );
+                    Err(e) => tracing::debug!("No .SRCINFO for {}: {}", name, e),
                 }
             }
+
+            let resolution =
+                self.resolve_with_queries(packages, ResolveOverrides::default(), &pacman, &srcinfo);
+            pending = resolution
+                .dependencies
+                .iter()
+                .filter(|dep| {
+                    dep.source == DependencySource::Aur
+                        && !matches!(dep.status, DependencyStatus::Installed { .. })
+                        && !fetched.contains(&dep.name)
+                })
+                .map(|dep| dep.name.clone())
+                .collect();
+            if self.config.offline || pending.is_empty() || passes >= self.config.max_depth {
+                return Ok(resolution);
+            }
+            passes += 1;
         }
+    }
 
-        // Batch fetch official package dependencies to reduce pacman command overhead
-        let official_packages: Vec<&str> = packages
-            .iter()
-            .filter_map(|pkg| {
-                if let PackageSource::Official { repo, .. } = &pkg.source {
-                    if repo == "local" {
-                        None
-                    } else {
-                        Some(pkg.name.as_str())
-                    }
-                } else {
-                    None
-                }
-            })
-            .collect();
-        let batched_deps_cache = if official_packages.is_empty() {
-            HashMap::new()
-        } else {
-            batch_fetch_official_deps(&official_packages)
-        };
+    /// What: Resolve dependencies, reading pacman package info through a query cache.
+    ///
+    /// Inputs:
+    /// - `packages`: Slice of `PackageRef` records to resolve dependencies for.
+    /// - `overrides`: Include flags and depth to use for this call only.
+    /// - `pacman`: Query cache shared by dependency resolution and conflict detection.
+    /// - `srcinfo`: Prefetched `.SRCINFO` content of AUR packages by name.
+    ///
+    /// Output:
+    /// - Returns the `DependencyResolution`; pacman failures degrade to empty package sets.
+    fn resolve_with_queries(
+        &self,
+        packages: &[PackageRef],
+        overrides: ResolveOverrides,
+        pacman: &PacmanQueryCache,
+        srcinfo: &HashMap<String, String>,
+    ) -> crate::types::dependency::DependencyResolution {
+        use crate::types::dependency::DependencyResolution;
+
+        let settings = ResolveSettings::merge(&self.config, overrides);
+        tracing::debug!(?settings, "Resolving with effective settings");
+
+        if packages.is_empty() {
+            tracing::warn!("No packages provided for dependency resolution");
+            return DependencyResolution::default();
+        }
+
+        let mut deps: HashMap<String, Dependency> = HashMap::new();
+        let mut missing: Vec<String> = Vec::new();
+
+        let runner = pacman.runner();
+
+        // Get installed packages set
+        tracing::info!("Fetching list of installed packages...");
+        let installed = installed_packages(runner).unwrap_or_else(|e| {
+            tracing::error!("Failed to list installed packages: {}", e);
+            HashSet::new()
+        });
+        tracing::info!("Found {} installed packages", installed.len());
+
+        // Provides (e.g. rustup providing rust=1.70) are looked up per dependency name
+        // that is not installed itself, instead of scanning every installed package
+        let provided = HashSet::new();
+
+        // Get list of upgradable packages to detect if dependencies need upgrades
+        let upgradable = upgradable_packages(runner).unwrap_or_else(|e| {
+            tracing::debug!(
+                "Failed to list upgradable packages: {} (assuming no upgrades)",
+                e
+            );
+            HashSet::new()
+        });
+        tracing::info!("Found {} upgradable packages", upgradable.len());
+
+        // Initialize set of root packages (for tracking)
+        let root_names: HashSet<String> = packages.iter().map(|p| p.name.clone()).collect();
+
+        let stale_pkgbuilds = Mutex::new(BTreeSet::new());
+        let current_pkgbuild =
+            |name: &str| current_cached_pkgbuild(name, &self.config, &stale_pkgbuilds);
+        let pkgbuild_cache = self
+            .config
+            .pkgbuild_cache
+            .as_ref()
+            .map(|_| &current_pkgbuild as &PkgbuildCacheFn);
 
-        // Resolve ONLY direct dependencies (non-recursive)
-        // This is faster and avoids resolving transitive dependencies which can be slow and error-prone
+        // Batch fetch official root packages to reduce pacman command overhead
+        prefetch_root_packages(packages, &settings, pacman);
+
+        // Check conflicts for packages being installed
+        let conflicts = mark_root_conflicts(
+            packages,
+            &root_names,
+            &installed,
+            &provided,
+            &upgradable,
+            runner,
+            &mut deps,
+            |package| root_package_conflicts(package, &settings, pacman, pkgbuild_cache),
+        );
+
+        // Resolve direct dependencies of the root packages
         for package in packages {
-            // Check if we have batched results for this official package
-            let use_batched = matches!(package.source, PackageSource::Official { ref repo, .. } if repo != "local")
-                && batched_deps_cache.contains_key(package.name.as_str());
-
-            let resolved_deps = if use_batched {
-                // Use batched dependency list
-                let dep_names = batched_deps_cache
-                    .get(package.name.as_str())
-                    .cloned()
-                    .unwrap_or_default();
-                process_dependency_specs(
-                    dep_names,
-                    &package.name,
-                    &installed,
-                    &provided,
-                    &upgradable,
-                )
-            } else {
-                // Resolve individually
-                match resolve_package_deps(
-                    &package.name,
-                    &package.source,
-                    &installed,
-                    &provided,
-                    &upgradable,
-                    self.config
-                        .pkgbuild_cache
-                        .as_ref()
-                        .map(|f| f.as_ref() as &(dyn Fn(&str) -> Option<String> + Send + Sync)),
-                ) {
-                    Ok(deps) => deps,
-                    Err(e) => {
-                        tracing::warn!(
-                            "  Failed to resolve dependencies for {}: {}",
-                            package.name,
-                            e
-                        );
-                        // Mark as missing
-                        if !missing.contains(&package.name) {
-                            missing.push(package.name.clone());
-                        }
-                        continue;
+            let resolved_deps = match resolve_package_deps(
+                &package.name,
+                &package.source,
+                &installed,
+                &provided,
+                &upgradable,
+                pacman,
+                pkgbuild_cache,
+                srcinfo,
+                &settings,
+            ) {
+                Ok(deps) => deps,
+                Err(e) => {
+                    tracing::warn!(
+                        "  Failed to resolve dependencies for {}: {}",
+                        package.name,
+                        e
+                    );
+                    // Mark as missing
+                    if !missing.contains(&package.name) {
+                        missing.push(package.name.clone());
                     }
+                    continue;
                 }
             };
 
@@ -1222,31 +2499,78 @@ impl DependencyResolver {
                     &installed,
                     &provided,
                     &upgradable,
+                    runner,
                     &mut deps,
                 );
+            }
+        }
 
-                // DON'T recursively resolve dependencies - only show direct dependencies
-                // This prevents resolving transitive dependencies which can be slow and error-prone
+        // Resolve transitive dependencies up to max_depth
+        if settings.max_depth > 0 {
+            let transitive_missing = expand_transitive(
+                &mut deps,
+                &root_names,
+                &settings,
+                &installed,
+                &provided,
+                &upgradable,
+                runner,
+                |name, source| {
+                    resolve_package_deps(
+                        name,
+                        source,
+                        &installed,
+                        &provided,
+                        &upgradable,
+                        pacman,
+                        pkgbuild_cache,
+                        srcinfo,
+                        &settings,
+                    )
+                },
+            );
+            for name in transitive_missing {
+                if !missing.contains(&name) {
+                    missing.push(name);
+                }
             }
         }
 
         let mut result: Vec<Dependency> = deps.into_values().collect();
         tracing::info!("Total unique dependencies found: {}", result.len());
 
-        // Sort dependencies: conflicts first, then missing, then to-install, then installed
-        result.sort_by(|a, b| {
-            let priority_a = dependency_priority(&a.status);
-            let priority_b = dependency_priority(&b.status);
-            priority_a
-                .cmp(&priority_b)
-                .then_with(|| a.name.cmp(&b.name))
-        });
+        // Normalize required_by for deterministic output
+        for dep in &mut result {
+            dep.required_by.sort();
+            dep.required_by.dedup();
+        }
 
-        Ok(DependencyResolution {
+        mark_system_packages(
+            &mut result,
+            &self.config.source_config,
+            settings.offline,
+            pacman,
+            &installed,
+        );
+
+        sort_by_priority(&mut result);
+
+        let cycles = find_cycles(&result);
+        if !cycles.is_empty() {
+            tracing::warn!("Found {} dependency cycle(s)", cycles.len());
+        }
+
+        DependencyResolution {
             dependencies: result,
             conflicts,
             missing,
-        })
+            cycles,
+            stale_pkgbuilds: stale_pkgbuilds
+                .into_inner()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .into_iter()
+                .collect(),
+        }
     }
 }
 
@@ -1259,7 +2583,9 @@ impl Default for DependencyResolver {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::dependency::DependencyStatus;
+    use crate::deps::runner::MockRunner;
+    use crate::fixtures::extra_ref;
+    use crate::types::dependency::{DEFAULT_MAX_PACKAGES, DependencyStatus, SourceConfig};
 
     #[test]
     fn test_should_filter_dependency() {
@@ -1271,6 +2597,53 @@ mod tests {
         assert!(!should_filter_dependency("firefox", "package"));
     }
 
+    #[test]
+    /// What: Verify `merge_dependency` records a parent only once in `required_by`.
+    ///
+    /// Inputs:
+    /// - The same dependency merged twice for `app` (e.g., via batched and individual
+    ///   resolution) and once for `another-app`.
+    ///
+    /// Output:
+    /// - `required_by` lists each parent once, sorted.
+    fn test_merge_dependency_deduplicates_required_by() {
+        let installed = HashSet::new();
+        let provided = HashSet::new();
+        let upgradable = HashSet::new();
+        let mut deps = HashMap::new();
+        let dep = Dependency {
+            name: "glibc".to_string(),
+            version_req: String::new(),
+            status: DependencyStatus::ToInstall,
+            source: DependencySource::Official {
+                repo: "core".to_string(),
+            },
+            required_by: vec!["app".to_string()],
+            depends_on: Vec::new(),
+            is_core: true,
+            is_system: false,
+            kind: DependencyKind::Runtime,
+            explicit: false,
+            install_reason: InstallReason::Unknown,
+            satisfied_by: None,
+        };
+
+        for parent in ["app", "another-app", "app"] {
+            merge_dependency(
+                &dep,
+                parent,
+                &installed,
+                &provided,
+                &upgradable,
+                &MockRunner::default(),
+                &mut deps,
+            );
+        }
+
+        let merged = deps.get("glibc").expect("glibc merged");
+        assert_eq!(merged.required_by, vec!["another-app", "app"]);
+    }
+
     #[test]
     fn test_determine_status_not_installed() {
         let installed = HashSet::new();
@@ -1281,6 +2654,91 @@ mod tests {
         assert!(matches!(status, DependencyStatus::ToInstall));
     }
 
+    #[test]
+    fn test_determine_status_versioned_provides() {
+        let installed = HashSet::from(["rustup".to_string()]);
+        let upgradable = HashSet::new();
+        let provide = |name: &str, version: Option<&str>| ProvidedPackage {
+            name: name.to_string(),
+            version: version.map(str::to_string),
+            provider: Some("rustup".to_string()),
+        };
+        let provided = HashSet::from([
+            provide("rust", Some("1.70")),
+            provide("rust", Some("1.58")),
+            provide("cargo", None),
+            provide("java-runtime", Some("17")),
+        ]);
+
+        assert_eq!(
+            determine_status("rust", ">=1.60", &installed, &provided, &upgradable),
+            DependencyStatus::Installed {
+                version: "1.70".to_string()
+            }
+        );
+        assert_eq!(
+            determine_status("rust", "", &installed, &provided, &upgradable),
+            DependencyStatus::Installed {
+                version: "1.70".to_string()
+            }
+        );
+        assert_eq!(
+            determine_status("java-runtime", ">=21", &installed, &provided, &upgradable),
+            DependencyStatus::ToUpgrade {
+                current: "17".to_string(),
+                required: ">=21".to_string()
+            }
+        );
+        // An unversioned provide never satisfies a versioned requirement
+        assert_eq!(
+            determine_status("cargo", ">=1.60", &installed, &provided, &upgradable),
+            DependencyStatus::ToInstall
+        );
+        assert_eq!(
+            determine_status("cargo", "", &installed, &provided, &upgradable),
+            DependencyStatus::Installed {
+                version: "installed".to_string()
+            }
+        );
+    }
+
+    #[test]
+    /// What: Name the installed package whose provides satisfy a dependency.
+    ///
+    /// Inputs:
+    /// - `rust` provided by two installed packages at different versions, `cron` only known to
+    ///   `pacman -Qqo`.
+    ///
+    /// Output:
+    /// - The provider matching the version requirement, the `-Qqo` fallback, and `None` for
+    ///   packages installed under their own name or when no provider meets the requirement.
+    fn test_satisfying_provider() {
+        let installed = HashSet::from(["rustup".to_string(), "rust-nightly".to_string()]);
+        let provided = HashSet::from([
+            ProvidedPackage {
+                name: "rust".to_string(),
+                version: Some("1.58".to_string()),
+                provider: Some("rust-nightly".to_string()),
+            },
+            ProvidedPackage {
+                name: "rust".to_string(),
+                version: Some("1.70".to_string()),
+                provider: Some("rustup".to_string()),
+            },
+        ]);
+        let runner = MockRunner::default().ok("pacman -Qqo cron", "cronie\n");
+
+        let provider_of = |name: &str, version_req: &str| {
+            satisfying_provider(&runner, name, version_req, &installed, &provided)
+        };
+        assert_eq!(provider_of("rust", ">=1.60").as_deref(), Some("rustup"));
+        assert_eq!(provider_of("rust", "").as_deref(), Some("rust-nightly"));
+        assert_eq!(provider_of("rust", ">=1.80"), None);
+        assert_eq!(provider_of("cron", "").as_deref(), Some("cronie"));
+        assert_eq!(provider_of("rustup", ""), None);
+        assert_eq!(provider_of("nonexistent", ""), None);
+    }
+
     #[test]
     fn test_batch_fetch_official_deps_parsing() {
         // Test parsing logic with sample output
@@ -1346,8 +2804,13 @@ mod tests {
             include_makedepends: true,
             include_checkdepends: true,
             max_depth: 2,
+            max_packages: 1000,
             pkgbuild_cache: None,
+            pkgbuild_hash: None,
+            aur_commit: None,
             check_aur: true,
+            offline: false,
+            source_config: SourceConfig::default(),
         };
         let resolver = DependencyResolver::with_config(config);
         assert_eq!(resolver.config.max_depth, 2);
@@ -1355,6 +2818,309 @@ mod tests {
         assert!(resolver.config.check_aur);
     }
 
+    #[test]
+    /// What: Verify call-time overrides merge onto the stored configuration.
+    ///
+    /// Inputs:
+    /// - Config enabling make and check dependencies with depth 2.
+    /// - Overrides disabling make dependencies only.
+    ///
+    /// Output:
+    /// - Make dependencies disabled; other settings taken from the config.
+    fn test_resolve_settings_override_disables_makedepends() {
+        let config = ResolverConfig {
+            include_makedepends: true,
+            include_checkdepends: true,
+            max_depth: 2,
+            ..ResolverConfig::default()
+        };
+        let overrides = ResolveOverrides {
+            include_makedepends: Some(false),
+            ..ResolveOverrides::default()
+        };
+
+        let settings = ResolveSettings::merge(&config, overrides);
+        assert_eq!(
+            settings,
+            ResolveSettings {
+                include_optdepends: false,
+                include_makedepends: false,
+                include_checkdepends: true,
+                max_depth: 2,
+                max_packages: DEFAULT_MAX_PACKAGES,
+                offline: false,
+            }
+        );
+        // The stored configuration is untouched
+        assert!(config.include_makedepends);
+        assert!(ResolveSettings::merge(&config, ResolveOverrides::default()).include_makedepends);
+    }
+
+    /// What: Mock PKGBUILD cache serving a small AUR dependency graph.
+    ///
+    /// Details:
+    /// - `att-app -> att-liba, att-libb`, `att-liba -> att-libc -> att-liba` (cycle),
+    ///   `att-libb -> att-libd -> att-libe`.
+    fn graph_pkgbuild(name: &str) -> Option<String> {
+        let depends = match name {
+            "att-app" => "'att-liba' 'att-libb'",
+            "att-liba" => "'att-libc'",
+            "att-libc" => "'att-liba'",
+            "att-libb" => "'att-libd'",
+            "att-libd" => "'att-libe'",
+            "att-libe" => "",
+            _ => return None,
+        };
+        Some(format!("pkgname={name}\npkgver=1.0\ndepends=({depends})\n"))
+    }
+
+    /// What: Resolve the mock graph rooted at `att-app` through the PKGBUILD cache callback.
+    fn resolve_graph(max_depth: usize, max_packages: usize) -> HashMap<String, Dependency> {
+        let cache: &PkgbuildCacheFn = &graph_pkgbuild;
+        let runner = MockRunner::default();
+        let empty = HashSet::new();
+        let no_provides = HashSet::new();
+        let roots = HashSet::from(["att-app".to_string()]);
+        let settings = ResolveSettings {
+            include_optdepends: false,
+            include_makedepends: false,
+            include_checkdepends: false,
+            max_depth,
+            max_packages,
+            offline: false,
+        };
+        let mut deps = HashMap::new();
+        for dep in fallback_to_pkgbuild(
+            "att-app",
+            Some(cache),
+            &settings,
+            &empty,
+            &no_provides,
+            &empty,
+            &runner,
+        ) {
+            merge_dependency(
+                &dep,
+                "att-app",
+                &empty,
+                &no_provides,
+                &empty,
+                &runner,
+                &mut deps,
+            );
+        }
+        let missing = expand_transitive(
+            &mut deps,
+            &roots,
+            &settings,
+            &empty,
+            &no_provides,
+            &empty,
+            &runner,
+            |name, _source| {
+                Ok(fallback_to_pkgbuild(
+                    name,
+                    Some(cache),
+                    &settings,
+                    &empty,
+                    &no_provides,
+                    &empty,
+                    &runner,
+                ))
+            },
+        );
+        assert!(missing.is_empty());
+        deps
+    }
+
+    fn sorted_names(deps: &HashMap<String, Dependency>) -> Vec<&str> {
+        let mut names: Vec<&str> = deps.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    /// What: Stop transitive expansion at `max_depth`.
+    ///
+    /// Inputs:
+    /// - Mock graph resolved with `max_depth = 1`.
+    ///
+    /// Output:
+    /// - Dependencies up to depth 2; depth-2 packages are not expanded.
+    fn test_expand_transitive_honors_max_depth() {
+        let deps = resolve_graph(1, 0);
+        assert_eq!(
+            sorted_names(&deps),
+            vec!["att-liba", "att-libb", "att-libc", "att-libd"]
+        );
+        assert_eq!(deps["att-liba"].depends_on, vec!["att-libc"]);
+        assert_eq!(deps["att-libb"].depends_on, vec!["att-libd"]);
+        assert!(deps["att-libc"].depends_on.is_empty());
+    }
+
+    #[test]
+    /// What: Resolve the full graph, terminate on the cycle, and report it once.
+    ///
+    /// Inputs:
+    /// - Mock graph resolved with `max_depth = 10`.
+    ///
+    /// Output:
+    /// - All five dependencies with `depends_on` filled; one cycle `att-liba -> att-libc`.
+    fn test_expand_transitive_full_graph_reports_cycle() {
+        let deps = resolve_graph(10, 0);
+        assert_eq!(
+            sorted_names(&deps),
+            vec!["att-liba", "att-libb", "att-libc", "att-libd", "att-libe"]
+        );
+        assert_eq!(deps["att-libc"].depends_on, vec!["att-liba"]);
+        assert_eq!(deps["att-liba"].required_by, vec!["att-app", "att-libc"]);
+        assert_eq!(deps["att-libd"].depends_on, vec!["att-libe"]);
+
+        let resolved: Vec<Dependency> = deps.into_values().collect();
+        assert_eq!(
+            find_cycles(&resolved),
+            vec![vec!["att-liba".to_string(), "att-libc".to_string()]]
+        );
+    }
+
+    #[test]
+    /// What: Cap the number of collected dependencies.
+    ///
+    /// Inputs:
+    /// - Mock graph resolved with `max_depth = 10` and `max_packages = 3`.
+    ///
+    /// Output:
+    /// - Exactly three dependencies collected.
+    fn test_expand_transitive_caps_total_packages() {
+        let deps = resolve_graph(10, 3);
+        assert_eq!(
+            sorted_names(&deps),
+            vec!["att-liba", "att-libb", "att-libc"]
+        );
+    }
+
+    #[test]
+    /// What: Include make, check, and optional dependencies from a cached PKGBUILD per flag.
+    ///
+    /// Inputs:
+    /// - Cached PKGBUILD declaring one dependency in each array; the optional one has a
+    ///   `: description` suffix.
+    /// - Settings with all include flags disabled, then enabled.
+    ///
+    /// Output:
+    /// - Runtime dependencies only by default; all four kinds, with bare names, when enabled.
+    fn test_fallback_to_pkgbuild_honors_include_flags() {
+        fn cache(name: &str) -> Option<String> {
+            (name == "att-tool").then(|| {
+                "pkgname=att-tool\n\
+                 depends=('att-runtime')\n\
+                 makedepends=('att-make')\n\
+                 checkdepends=('att-check')\n\
+                 optdepends=('att-opt: for extra features')\n"
+                    .to_string()
+            })
+        }
+        let cache: &PkgbuildCacheFn = &cache;
+        let empty = HashSet::new();
+        let no_provides = HashSet::new();
+        let mut settings = ResolveSettings {
+            include_optdepends: false,
+            include_makedepends: false,
+            include_checkdepends: false,
+            max_depth: 0,
+            max_packages: 0,
+            offline: false,
+        };
+
+        let kinds = |settings: &ResolveSettings| -> Vec<(String, DependencyKind)> {
+            fallback_to_pkgbuild(
+                "att-tool",
+                Some(cache),
+                settings,
+                &empty,
+                &no_provides,
+                &empty,
+                &MockRunner::default(),
+            )
+            .into_iter()
+            .map(|dep| (dep.name, dep.kind))
+            .collect()
+        };
+        assert_eq!(
+            kinds(&settings),
+            vec![("att-runtime".to_string(), DependencyKind::Runtime)]
+        );
+
+        settings.include_makedepends = true;
+        settings.include_checkdepends = true;
+        settings.include_optdepends = true;
+        assert_eq!(
+            kinds(&settings),
+            vec![
+                ("att-runtime".to_string(), DependencyKind::Runtime),
+                ("att-make".to_string(), DependencyKind::Make),
+                ("att-check".to_string(), DependencyKind::Check),
+                ("att-opt".to_string(), DependencyKind::Opt),
+            ]
+        );
+    }
+
+    #[test]
+    /// What: Keep using a cached PKGBUILD whose commit differs from the AUR's latest commit.
+    ///
+    /// Inputs:
+    /// - Counting cache callback, cached commit `aaa`, AUR commit `aaa` then `bbb`.
+    ///
+    /// Output:
+    /// - Matching and mismatched commits both return the cached PKGBUILD; only the mismatch
+    ///   records the package as stale. A missing AUR commit keeps the cache usable.
+    fn test_current_cached_pkgbuild_detects_stale_hash() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let reads = Arc::new(AtomicUsize::new(0));
+        let cache_reads = Arc::clone(&reads);
+        let config = |aur_commit: Option<&'static str>| ResolverConfig {
+            pkgbuild_cache: Some(Box::new({
+                let cache_reads = Arc::clone(&cache_reads);
+                move |_: &str| {
+                    cache_reads.fetch_add(1, Ordering::SeqCst);
+                    Some("depends=('att-runtime')\n".to_string())
+                }
+            })),
+            pkgbuild_hash: Some(Box::new(|_: &str| Some("aaa".to_string()))),
+            aur_commit: Some(Box::new(move |_: &str| aur_commit.map(ToString::to_string))),
+            ..ResolverConfig::default()
+        };
+
+        let stale = Mutex::new(BTreeSet::new());
+        let stale_names = || stale.lock().expect("stale lock").clone();
+
+        assert!(current_cached_pkgbuild("att-tool", &config(Some("aaa")), &stale).is_some());
+        assert_eq!(reads.load(Ordering::SeqCst), 1);
+        assert!(stale_names().is_empty());
+
+        assert!(current_cached_pkgbuild("att-tool", &config(Some("bbb")), &stale).is_some());
+        assert_eq!(reads.load(Ordering::SeqCst), 2, "stale cache is still read");
+        assert_eq!(stale_names(), BTreeSet::from(["att-tool".to_string()]));
+
+        let stale = Mutex::new(BTreeSet::new());
+        assert!(current_cached_pkgbuild("att-tool", &config(None), &stale).is_some());
+        assert_eq!(reads.load(Ordering::SeqCst), 3);
+        assert!(stale.lock().expect("stale lock").is_empty());
+    }
+
+    #[test]
+    fn test_strip_optdepend_description() {
+        assert_eq!(strip_optdepend_description("python: for scripts"), "python");
+        assert_eq!(strip_optdepend_description("python:"), "python");
+        assert_eq!(strip_optdepend_description("python"), "python");
+        assert_eq!(
+            strip_optdepend_description("foo>=1:2.0: desc"),
+            "foo>=1:2.0"
+        );
+    }
+
     #[test]
     fn test_dependency_resolver_resolve_empty() {
         let resolver = DependencyResolver::new();
@@ -1366,6 +3132,312 @@ mod tests {
         assert_eq!(result.missing.len(), 0);
     }
 
+    const CQ_APP_SI: &str = "Repository      : extra\nName            : cq-app\nVersion         : 1.0-1\nDepends On      : cq-lib  glibc>=2.40\nConflicts With  : cq-old\n";
+    const CQ_LIB_SI: &str = "Repository      : extra\nName            : cq-lib\nVersion         : 2.0-1\nDepends On      : None\nConflicts With  : None\n";
+    const GLIBC_SI: &str = "Repository      : core\nName            : glibc\nVersion         : 2.41-1\nDepends On      : None\nConflicts With  : None\n";
+
+    /// What: Runner answering the pacman queries of a resolution of `cq-app`.
+    ///
+    /// Details:
+    /// - `glibc` 2.41 is installed; `cq-app` and `cq-lib` are in `extra`; nothing is upgradable.
+    fn cq_runner() -> MockRunner {
+        MockRunner::default()
+            .ok("pacman -Qq", "glibc\n")
+            .ok(
+                "pacman -Qi",
+                "Name            : glibc\nProvides        : None\n",
+            )
+            .respond("pacman -Qu", 1, "", "")
+            .ok("pacman -Q glibc", "glibc 2.41-1\n")
+            .ok("pacman -Si cq-app", CQ_APP_SI)
+            .ok("pacman -Si cq-lib", CQ_LIB_SI)
+            .ok("pacman -Si glibc", GLIBC_SI)
+            .ok(
+                "pacman -Si cq-app cq-lib",
+                &format!("{CQ_APP_SI}\n{CQ_LIB_SI}"),
+            )
+    }
+
+    #[test]
+    /// What: Drive a full resolution against mocked pacman output.
+    ///
+    /// Inputs:
+    /// - Root `cq-app` depending on `cq-lib` and `glibc>=2.40`, resolved one level deep.
+    ///
+    /// Output:
+    /// - `cq-lib` to install from `extra`, `glibc` installed from `core`, and `cq-lib`
+    ///   expanded from the cached `pacman -Si` output.
+    fn test_resolve_with_mock_runner() {
+        let runner = cq_runner();
+        let config = ResolverConfig {
+            max_depth: 1,
+            ..ResolverConfig::default()
+        };
+        let resolver = DependencyResolver::with_config(config).with_runner(runner);
+
+        let result = resolver
+            .resolve(&[extra_ref("cq-app")])
+            .expect("resolution succeeds");
+
+        let summary: Vec<(&str, &DependencyStatus, &DependencySource)> = result
+            .dependencies
+            .iter()
+            .map(|dep| (dep.name.as_str(), &dep.status, &dep.source))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (
+                    "cq-lib",
+                    &DependencyStatus::ToInstall,
+                    &DependencySource::Official {
+                        repo: "extra".to_string()
+                    }
+                ),
+                (
+                    "glibc",
+                    &DependencyStatus::Installed {
+                        version: "2.41".to_string()
+                    },
+                    &DependencySource::Official {
+                        repo: "core".to_string()
+                    }
+                ),
+            ]
+        );
+        assert!(result.conflicts.is_empty());
+        assert!(result.missing.is_empty());
+    }
+
+    #[test]
+    /// What: Resolve a virtual dependency satisfied by an installed provider.
+    ///
+    /// Inputs:
+    /// - Root `cron-job` depending on `cron`, which only the installed `cronie` provides.
+    ///
+    /// Output:
+    /// - `cron` is installed, records `cronie` as `satisfied_by`, and nothing is missing.
+    /// - Only `cron` is looked up; the provides of all installed packages are not scanned.
+    fn test_resolve_virtual_dependency_satisfied_by_provider() {
+        let runner = MockRunner::default()
+            .ok("pacman -Qq", "cronie\n")
+            .ok(
+                "pacman -Qi cron",
+                "Name            : cronie\nProvides        : cron\n",
+            )
+            .respond("pacman -Qu", 1, "", "")
+            .ok(
+                "pacman -Si cron-job",
+                "Repository      : extra\nName            : cron-job\nVersion         : 1.0-1\nDepends On      : cron\nConflicts With  : None\n",
+            );
+        let config = ResolverConfig {
+            max_depth: 1,
+            ..ResolverConfig::default()
+        };
+        let result = DependencyResolver::with_config(config).resolve_with_queries(
+            &[extra_ref("cron-job")],
+            ResolveOverrides::default(),
+            &PacmanQueryCache::new(&runner),
+            &HashMap::new(),
+        );
+
+        let cron = result
+            .dependencies
+            .iter()
+            .find(|dep| dep.name == "cron")
+            .expect("cron resolved as dependency");
+        assert!(matches!(cron.status, DependencyStatus::Installed { .. }));
+        assert_eq!(cron.satisfied_by.as_deref(), Some("cronie"));
+        assert!(result.missing.is_empty());
+        assert!(!runner.calls().contains(&"pacman -Qi".to_string()));
+    }
+
+    #[test]
+    /// What: Resolve an AUR package from a stale cached PKGBUILD.
+    ///
+    /// Inputs:
+    /// - Offline AUR root `att-tool` whose cached PKGBUILD (commit `aaa`) depends on
+    ///   `att-runtime` while the AUR reports commit `bbb`; `att-runtime`'s cache is current.
+    ///
+    /// Output:
+    /// - `att-runtime` is still reported as a dependency.
+    /// - `att-tool` is reported in `stale_pkgbuilds`.
+    fn test_resolve_uses_stale_cached_pkgbuild() {
+        let runner = MockRunner::default()
+            .ok("pacman -Qq", "")
+            .ok("pacman -Qi", "")
+            .respond("pacman -Qu", 1, "", "");
+        let config = ResolverConfig {
+            offline: true,
+            max_depth: 1,
+            pkgbuild_cache: Some(Box::new(|_: &str| {
+                Some("depends=('att-runtime')\n".to_string())
+            })),
+            pkgbuild_hash: Some(Box::new(|_: &str| Some("aaa".to_string()))),
+            aur_commit: Some(Box::new(|name: &str| {
+                Some(if name == "att-tool" { "bbb" } else { "aaa" }.to_string())
+            })),
+            ..ResolverConfig::default()
+        };
+        let resolver = DependencyResolver::with_config(config).with_runner(runner);
+        let root = PackageRef {
+            name: "att-tool".to_string(),
+            version: "1.0-1".to_string(),
+            source: PackageSource::Aur,
+        };
+
+        let result = resolver.resolve(&[root]).expect("resolution succeeds");
+
+        assert!(
+            result
+                .dependencies
+                .iter()
+                .any(|dep| dep.name == "att-runtime"),
+            "stale PKGBUILD dependencies are kept"
+        );
+        assert_eq!(result.stale_pkgbuilds, vec!["att-tool".to_string()]);
+    }
+
+    #[test]
+    /// What: Flag dependencies in a custom protected group as system packages.
+    ///
+    /// Inputs:
+    /// - Root `steam-launcher` depending on `lib32-gcc-libs` (group `multilib-devel`) and
+    ///   `zenity`, resolved with and without `multilib-devel` protected.
+    ///
+    /// Output:
+    /// - `lib32-gcc-libs` is `is_system` only with the custom group; `zenity` never is.
+    /// - The default config runs no group query.
+    fn test_resolve_flags_custom_protected_group() {
+        const LIB32_SI: &str = "Repository      : multilib\nName            : lib32-gcc-libs\nGroups          : multilib-devel\nDepends On      : None\n";
+        const ZENITY_SI: &str = "Repository      : extra\nName            : zenity\nGroups          : None\nDepends On      : None\n";
+        let runner = || {
+            MockRunner::default()
+                .ok("pacman -Qq", "")
+                .ok("pacman -Qi", "")
+                .respond("pacman -Qu", 1, "", "")
+                .ok(
+                    "pacman -Si steam-launcher",
+                    "Repository      : multilib\nName            : steam-launcher\nDepends On      : lib32-gcc-libs  zenity\nConflicts With  : None\n",
+                )
+                .ok("pacman -Si lib32-gcc-libs", LIB32_SI)
+                .ok("pacman -Si zenity", ZENITY_SI)
+                .ok(
+                    "pacman -Si lib32-gcc-libs zenity",
+                    &format!("{LIB32_SI}\n{ZENITY_SI}"),
+                )
+        };
+        let system_flags = |source_config: SourceConfig| {
+            let config = ResolverConfig {
+                source_config,
+                ..ResolverConfig::default()
+            };
+            DependencyResolver::with_config(config)
+                .with_runner(runner())
+                .resolve(&[extra_ref("steam-launcher")])
+                .expect("resolution succeeds")
+                .dependencies
+                .into_iter()
+                .map(|dep| (dep.name, dep.is_system))
+                .collect::<Vec<_>>()
+        };
+
+        let default_runner = runner();
+        let pacman = PacmanQueryCache::new(&default_runner);
+        DependencyResolver::new().resolve_with_queries(
+            &[extra_ref("steam-launcher")],
+            ResolveOverrides::default(),
+            &pacman,
+            &HashMap::new(),
+        );
+        assert!(
+            !default_runner
+                .calls()
+                .contains(&"pacman -Si lib32-gcc-libs zenity".to_string()),
+            "default config queries no groups"
+        );
+
+        let mut protected = SourceConfig::default();
+        protected
+            .protected_groups
+            .push("multilib-devel".to_string());
+        assert_eq!(
+            system_flags(protected),
+            [
+                ("lib32-gcc-libs".to_string(), true),
+                ("zenity".to_string(), false)
+            ]
+        );
+        assert_eq!(
+            system_flags(SourceConfig::default()),
+            [
+                ("lib32-gcc-libs".to_string(), false),
+                ("zenity".to_string(), false)
+            ]
+        );
+    }
+
+    #[test]
+    /// What: Share pacman queries between conflict detection and dependency resolution.
+    ///
+    /// Inputs:
+    /// - Two official roots, `cq-app` depending on `cq-lib`, answered by a recording runner.
+    ///
+    /// Output:
+    /// - A single batched `pacman -Si` invocation serves conflicts and dependencies of both.
+    fn test_resolve_reuses_pacman_queries() {
+        let runner = cq_runner();
+        let pacman = PacmanQueryCache::new(&runner);
+
+        let result = DependencyResolver::new().resolve_with_queries(
+            &[extra_ref("cq-app"), extra_ref("cq-lib")],
+            ResolveOverrides::default(),
+            &pacman,
+            &HashMap::new(),
+        );
+
+        let info_queries: Vec<String> = runner
+            .calls()
+            .into_iter()
+            .filter(|call| call.starts_with("pacman -Si cq-app"))
+            .collect();
+        assert_eq!(info_queries, ["pacman -Si cq-app cq-lib"]);
+        let lib = result
+            .dependencies
+            .iter()
+            .find(|dep| dep.name == "cq-lib")
+            .expect("cq-lib resolved as dependency");
+        assert_eq!(lib.required_by, ["cq-app"]);
+        assert!(result.missing.is_empty());
+    }
+
+    #[test]
+    /// What: Memoize failed pacman lookups.
+    ///
+    /// Inputs:
+    /// - Repeated lookups of an unknown package, and of a package already fetched in a batch.
+    ///
+    /// Output:
+    /// - One invocation for the unknown package; none for the prefetched package.
+    fn test_pacman_query_cache_memoizes_misses() {
+        let runner = cq_runner();
+        let pacman = PacmanQueryCache::new(&runner);
+
+        assert_eq!(pacman.fields(PacmanDb::Sync, "cq-nope"), None);
+        assert_eq!(pacman.fields(PacmanDb::Sync, "cq-nope"), None);
+
+        pacman.prefetch_sync(&["cq-app", "cq-lib"]);
+        let fields = pacman
+            .fields(PacmanDb::Sync, "cq-app")
+            .expect("cq-app prefetched");
+        assert_eq!(fields.depends, ["cq-lib", "glibc>=2.40"]);
+        assert_eq!(fields.conflicts, ["cq-old"]);
+        assert_eq!(
+            runner.calls(),
+            ["pacman -Si cq-nope", "pacman -Si cq-app cq-lib"]
+        );
+    }
+
     // Integration tests that require pacman - these are ignored by default
     #[test]
     #[ignore = "Requires pacman to be available"]
@@ -1386,4 +3458,226 @@ mod tests {
             assert!(!result.dependencies.is_empty());
         }
     }
+
+    #[cfg(feature = "aur")]
+    #[tokio::test]
+    /// What: Add `.SRCINFO` runtime and build-time dependencies via `ArchClient`.
+    ///
+    /// Inputs:
+    /// - Mock `.SRCINFO` with `depends = glibc` and `makedepends = cmake`.
+    /// - Existing dependency list already containing `glibc`, with and without make
+    ///   dependencies requested.
+    ///
+    /// Output:
+    /// - `cmake` is added once, only with `include_makedepends`; `glibc` is not duplicated.
+    async fn test_enhance_with_srcinfo_async_adds_makedepends() {
+        use crate::client::ArchClient;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/cgit/aur.git/plain/.SRCINFO"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "pkgbase = foo\n\tdepends = glibc\n\tmakedepends = cmake\n\npkgname = foo\n",
+            ))
+            .mount(&server)
+            .await;
+        let client = ArchClient::builder()
+            .aur_base_url(server.uri())
+            .build()
+            .expect("client builds");
+
+        let installed: HashSet<String> = ["glibc", "cmake"]
+            .iter()
+            .map(|s| (*s).to_string())
+            .collect();
+        let empty = HashSet::new();
+        let no_provides = HashSet::new();
+        let runner = MockRunner::default();
+        let existing = process_dependency_specs(
+            vec!["glibc".to_string()],
+            "foo",
+            DependencyKind::Runtime,
+            &installed,
+            &no_provides,
+            &empty,
+            &runner,
+        );
+        let names = |include_makedepends: bool| {
+            let existing = existing.clone();
+            let (client, runner, installed) = (&client, &runner, &installed);
+            let (no_provides, empty) = (&no_provides, &empty);
+            async move {
+                enhance_with_srcinfo_async(
+                    client,
+                    runner,
+                    "foo",
+                    existing,
+                    include_makedepends,
+                    installed,
+                    no_provides,
+                    empty,
+                )
+                .await
+                .into_iter()
+                .map(|dep| dep.name)
+                .collect::<Vec<_>>()
+            }
+        };
+        assert_eq!(names(true).await, ["glibc", "cmake"]);
+        assert_eq!(names(false).await, ["glibc"]);
+    }
+
+    #[cfg(feature = "aur")]
+    #[tokio::test]
+    /// What: Resolve AUR build dependencies from `.SRCINFO` through the configured runner.
+    ///
+    /// Inputs:
+    /// - AUR root `foo` whose mock `.SRCINFO` lists `depends = att-lib` and
+    ///   `makedepends = att-make`, resolved with make dependencies included.
+    /// - Mock runner where `att-lib` is installed and `att-make` is in `extra`.
+    ///
+    /// Output:
+    /// - `resolve()` finds nothing; `resolve_async()` reports both dependencies.
+    async fn test_resolve_async_uses_srcinfo() {
+        use crate::client::ArchClient;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/cgit/aur.git/plain/.SRCINFO"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "pkgbase = foo\n\tdepends = att-lib\n\tmakedepends = att-make\n\npkgname = foo\n",
+            ))
+            .mount(&server)
+            .await;
+        let client = ArchClient::builder()
+            .aur_base_url(server.uri())
+            .build()
+            .expect("client builds");
+        let resolver = || {
+            let runner = MockRunner::default()
+                .ok("pacman -Qq", "att-lib\n")
+                .ok("pacman -Qi", "Name            : att-lib\n")
+                .respond("pacman -Qu", 1, "", "")
+                .ok(
+                    "pacman -Si att-make",
+                    "Repository      : extra\nName            : att-make\nVersion         : 1.0-1\n",
+                );
+            let config = ResolverConfig {
+                include_makedepends: true,
+                ..ResolverConfig::default()
+            };
+            DependencyResolver::with_config(config).with_runner(runner)
+        };
+        let root = PackageRef {
+            name: "foo".to_string(),
+            version: "1.0-1".to_string(),
+            source: PackageSource::Aur,
+        };
+
+        let sync = resolver()
+            .resolve(std::slice::from_ref(&root))
+            .expect("resolution succeeds");
+        assert!(sync.dependencies.is_empty());
+
+        let result = resolver()
+            .resolve_async(&client, &[root])
+            .await
+            .expect("resolution succeeds");
+        let mut names: Vec<&str> = result
+            .dependencies
+            .iter()
+            .map(|dep| dep.name.as_str())
+            .collect();
+        names.sort_unstable();
+        assert_eq!(names, ["att-lib", "att-make"]);
+    }
+
+    #[cfg(feature = "aur")]
+    #[tokio::test]
+    /// What: Share pacman queries across the `.SRCINFO` prefetch passes of `resolve_async()`.
+    ///
+    /// Inputs:
+    /// - AUR root `foo` depending on `att-make` (in `extra`) and the AUR package `att-aur`,
+    ///   whose `.SRCINFO` also depends on `att-make`; one extra pass allowed.
+    ///
+    /// Output:
+    /// - `att-aur`'s dependencies are resolved in the second pass, and no command line runs
+    ///   twice across the passes.
+    async fn test_resolve_async_reuses_pacman_queries() {
+        use crate::client::ArchClient;
+        use std::sync::Arc;
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        /// Runner forwarding to a mock that the test keeps access to.
+        struct Shared(Arc<MockRunner>);
+        impl CommandRunner for Shared {
+            fn run(&self, program: &str, args: &[&str]) -> std::io::Result<std::process::Output> {
+                self.0.run(program, args)
+            }
+        }
+
+        let server = MockServer::start().await;
+        for (name, body) in [
+            (
+                "foo",
+                "pkgbase = foo\n\tdepends = att-make\n\tdepends = att-aur\n\npkgname = foo\n",
+            ),
+            (
+                "att-aur",
+                "pkgbase = att-aur\n\tdepends = att-make\n\npkgname = att-aur\n",
+            ),
+        ] {
+            Mock::given(method("GET"))
+                .and(path("/cgit/aur.git/plain/.SRCINFO"))
+                .and(query_param("h", name))
+                .respond_with(ResponseTemplate::new(200).set_body_string(body))
+                .mount(&server)
+                .await;
+        }
+        let client = ArchClient::builder()
+            .aur_base_url(server.uri())
+            .build()
+            .expect("client builds");
+        let runner = Arc::new(
+            MockRunner::default()
+                .ok("pacman -Qq", "")
+                .ok("pacman -Qi", "")
+                .respond("pacman -Qu", 1, "", "")
+                .ok(
+                    "pacman -Si att-make",
+                    "Repository      : extra\nName            : att-make\nVersion         : 1.0-1\n",
+                ),
+        );
+        let config = ResolverConfig {
+            max_depth: 1,
+            ..ResolverConfig::default()
+        };
+        let resolver =
+            DependencyResolver::with_config(config).with_runner(Shared(Arc::clone(&runner)));
+        let root = PackageRef {
+            name: "foo".to_string(),
+            version: "1.0-1".to_string(),
+            source: PackageSource::Aur,
+        };
+
+        let result = resolver
+            .resolve_async(&client, &[root])
+            .await
+            .expect("resolution succeeds");
+
+        let att_make = result
+            .dependencies
+            .iter()
+            .find(|dep| dep.name == "att-make")
+            .expect("att-make resolved");
+        assert!(att_make.required_by.contains(&"att-aur".to_string()));
+        let calls = runner.calls();
+        let unique: HashSet<&String> = calls.iter().collect();
+        assert_eq!(unique.len(), calls.len(), "repeated commands: {calls:?}");
+    }
 }
@@ -4,21 +4,35 @@
 //! that depend on packages being removed. It uses breadth-first search (BFS) traversal
 //! with `pacman -Qi` queries to build a complete dependency graph.
 
-use crate::deps::query::get_installed_packages;
-use crate::error::{ArchToolkitError, Result};
+use crate::deps::query::{installed_packages, pacman_stdout};
+use crate::deps::resolve::find_graph_cycles;
+use crate::deps::runner::{CommandRunner, SystemRunner};
+use crate::deps::source::is_system_package_with;
+use crate::error::ArchToolkitError;
+use crate::error::Result;
 use crate::types::dependency::{
-    Dependency, DependencySource, DependencyStatus, PackageRef, ReverseDependencyReport,
-    ReverseDependencySummary,
+    Dependency, DependencyKind, DependencySource, DependencyStatus, InstallReason, PackageRef,
+    ReverseDependencyReport, ReverseDependencySummary, ReverseOptions, ReverseProgress,
+    SourceConfig,
 };
-use std::collections::{BTreeMap, HashMap, HashSet, VecDeque, hash_map::Entry};
-use std::process::{Command, Stdio};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque, hash_map::Entry};
+use std::sync::Arc;
+
+/// Number of expanded packages between two progress reports of `analyze_with_progress()`.
+const PROGRESS_INTERVAL: usize = 50;
 
 /// Reverse dependency analyzer for removal operations.
 ///
 /// This struct provides the main entry point for analyzing reverse dependencies
 /// for packages being removed. It performs BFS traversal to find all packages
 /// that depend on the removal targets.
-pub struct ReverseDependencyAnalyzer;
+#[derive(Clone)]
+pub struct ReverseDependencyAnalyzer {
+    /// Command runner for pacman (`None` = `SystemRunner`), shared with clones.
+    runner: Option<Arc<dyn CommandRunner>>,
+    /// Packages and groups flagged as `is_system` in reports (`None` = `SourceConfig::default()`).
+    source_config: Option<SourceConfig>,
+}
 
 impl ReverseDependencyAnalyzer {
     /// What: Create a new reverse dependency analyzer.
@@ -41,7 +55,54 @@ impl ReverseDependencyAnalyzer {
     /// ```
     #[must_use]
     pub const fn new() -> Self {
-        Self
+        Self {
+            runner: None,
+            source_config: None,
+        }
+    }
+
+    /// What: Run pacman commands through a custom runner.
+    ///
+    /// Inputs:
+    /// - `runner`: Command runner used for every `pacman -Qi` query of this analyzer.
+    ///
+    /// Output:
+    /// - Returns the analyzer using `runner` instead of `SystemRunner`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arch_toolkit::deps::{ReverseDependencyAnalyzer, SystemRunner};
+    ///
+    /// let analyzer = ReverseDependencyAnalyzer::new().with_runner(SystemRunner);
+    /// ```
+    #[must_use]
+    pub fn with_runner(mut self, runner: impl CommandRunner + 'static) -> Self {
+        self.runner = Some(Arc::new(runner));
+        self
+    }
+
+    /// What: Classify system packages with custom protected groups and packages.
+    ///
+    /// Inputs:
+    /// - `config`: Protected groups and package names deciding `Dependency::is_system`.
+    ///
+    /// Output:
+    /// - Returns the analyzer using `config` instead of `SourceConfig::default()`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arch_toolkit::deps::{ReverseDependencyAnalyzer, SourceConfig};
+    ///
+    /// let mut config = SourceConfig::default();
+    /// config.protected_groups.push("multilib-devel".to_string());
+    /// let analyzer = ReverseDependencyAnalyzer::new().with_source_config(config);
+    /// ```
+    #[must_use]
+    pub fn with_source_config(mut self, config: SourceConfig) -> Self {
+        self.source_config = Some(config);
+        self
     }
 
     /// What: Analyze reverse dependencies for packages being removed.
@@ -55,13 +116,16 @@ impl ReverseDependencyAnalyzer {
     /// Details:
     /// - Performs breadth-first search (BFS) traversal using `pacman -Qi` metadata.
     /// - Aggregates per-root relationships to track direct vs transitive dependents.
-    /// - Only analyzes installed packages (skips uninstalled packages).
+    /// - Only analyzes installed packages (skips packages pacman reports as not found).
+    /// - Reports cycles among the traversed "Required By" relations in `cycles`; they do not
+    ///   change the reported dependents.
     /// - Returns empty report if no packages provided or all packages are uninstalled.
     ///
     /// # Errors
     ///
-    /// This function can return an `ArchToolkitError` if underlying `pacman` commands fail
-    /// or if parsing their output encounters unexpected formats.
+    /// - Returns `Err(ArchToolkitError::PacmanUnavailable)` when pacman is not installed.
+    /// - Returns `Err(ArchToolkitError::Parse)` when `pacman -Qi` fails for a target for any
+    ///   reason other than the package not being installed.
     ///
     /// # Example
     ///
@@ -86,6 +150,173 @@ impl ReverseDependencyAnalyzer {
     /// # Ok::<(), arch_toolkit::error::ArchToolkitError>(())
     /// ```
     pub fn analyze(&self, packages: &[PackageRef]) -> Result<ReverseDependencyReport> {
+        self.analyze_with(packages, &ReverseOptions::default())
+    }
+
+    /// What: Analyze reverse dependencies without blocking the async runtime.
+    ///
+    /// Inputs:
+    /// - `packages`: A slice of `PackageRef` instances for packages being removed.
+    ///
+    /// Output:
+    /// - Returns the same `ReverseDependencyReport` as `analyze()`.
+    ///
+    /// Details:
+    /// - Runs `analyze()` on a clone of the analyzer with `tokio::task::spawn_blocking`, so the
+    ///   `pacman -Qi` queries of the BFS do not stall the executor.
+    /// - Requires `feature = "aur"` to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// - Returns the errors of `analyze()`.
+    /// - Returns `Err(ArchToolkitError::Parse)` if the blocking task fails.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arch_toolkit::deps::ReverseDependencyAnalyzer;
+    /// use arch_toolkit::{PackageRef, PackageSource};
+    ///
+    /// # async fn example() -> arch_toolkit::error::Result<()> {
+    /// let packages = vec![PackageRef {
+    ///     name: "qt5-base".into(),
+    ///     version: "5.15.10".into(),
+    ///     source: PackageSource::Official {
+    ///         repo: "extra".into(),
+    ///         arch: "x86_64".into(),
+    ///     },
+    /// }];
+    ///
+    /// let report = ReverseDependencyAnalyzer::new().analyze_async(&packages).await?;
+    /// println!("{} packages would be affected", report.dependents.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "aur")]
+    pub async fn analyze_async(&self, packages: &[PackageRef]) -> Result<ReverseDependencyReport> {
+        let analyzer = self.clone();
+        let packages = packages.to_vec();
+        tokio::task::spawn_blocking(move || analyzer.analyze(&packages))
+            .await
+            .map_err(|e| ArchToolkitError::Parse(format!("Blocking task failed: {e}")))?
+    }
+
+    /// What: Analyze reverse dependencies with a depth limit and optional dependents.
+    ///
+    /// Inputs:
+    /// - `packages`: Slice of `PackageRef` to analyze for removal.
+    /// - `opts`: Traversal options (maximum depth, whether to include optional dependents).
+    ///
+    /// Output:
+    /// - Returns a `ReverseDependencyReport` like `analyze()`.
+    ///
+    /// Details:
+    /// - `max_depth` stops the traversal once dependents are that far from a target; `Some(1)`
+    ///   reports direct dependents only.
+    /// - With `include_optional`, packages listed under "Optional For" are reported with
+    ///   `DependencyKind::Opt` unless they also hard-depend on the target. Their own dependents
+    ///   are not followed, since removing the target does not break them.
+    ///
+    /// # Errors
+    ///
+    /// Same as `analyze()`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arch_toolkit::deps::{ReverseDependencyAnalyzer, ReverseOptions};
+    /// use arch_toolkit::{PackageRef, PackageSource};
+    ///
+    /// let packages = vec![PackageRef {
+    ///     name: "qt5-base".into(),
+    ///     version: "5.15.10".into(),
+    ///     source: PackageSource::Official {
+    ///         repo: "extra".into(),
+    ///         arch: "x86_64".into(),
+    ///     },
+    /// }];
+    /// let opts = ReverseOptions {
+    ///     max_depth: Some(1),
+    ///     include_optional: true,
+    /// };
+    ///
+    /// let report = ReverseDependencyAnalyzer::new().analyze_with(&packages, &opts)?;
+    /// println!("{} direct dependents", report.dependents.len());
+    /// # Ok::<(), arch_toolkit::error::ArchToolkitError>(())
+    /// ```
+    pub fn analyze_with(
+        &self,
+        packages: &[PackageRef],
+        opts: &ReverseOptions,
+    ) -> Result<ReverseDependencyReport> {
+        self.run(packages, opts, &mut |_| {})
+    }
+
+    /// What: Analyze reverse dependencies while reporting traversal progress.
+    ///
+    /// Inputs:
+    /// - `packages`: Slice of `PackageRef` to analyze for removal.
+    /// - `progress`: Callback receiving a `ReverseProgress` as the traversal advances.
+    ///
+    /// Output:
+    /// - Returns the same `ReverseDependencyReport` as `analyze()`.
+    ///
+    /// Details:
+    /// - `progress` is called after every `PROGRESS_INTERVAL` (50) expanded packages, so large
+    ///   graphs such as the dependents of `glibc` can drive a live counter without per-package
+    ///   overhead.
+    /// - A final report with `queued == 0` and an empty `current` is always sent once the
+    ///   traversal finishes; its `visited` equals `report.dependents.len()`.
+    ///
+    /// # Errors
+    ///
+    /// Same as `analyze()`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arch_toolkit::deps::ReverseDependencyAnalyzer;
+    /// use arch_toolkit::{PackageRef, PackageSource};
+    ///
+    /// let packages = vec![PackageRef {
+    ///     name: "glibc".into(),
+    ///     version: "2.41".into(),
+    ///     source: PackageSource::Official {
+    ///         repo: "core".into(),
+    ///         arch: "x86_64".into(),
+    ///     },
+    /// }];
+    ///
+    /// let report = ReverseDependencyAnalyzer::new().analyze_with_progress(&packages, |p| {
+    ///     eprint!("\r{} dependents found, {} queued", p.visited, p.queued);
+    /// })?;
+    /// println!("\n{} packages would be affected", report.dependents.len());
+    /// # Ok::<(), arch_toolkit::error::ArchToolkitError>(())
+    /// ```
+    pub fn analyze_with_progress(
+        &self,
+        packages: &[PackageRef],
+        mut progress: impl FnMut(ReverseProgress),
+    ) -> Result<ReverseDependencyReport> {
+        self.run(packages, &ReverseOptions::default(), &mut progress)
+    }
+
+    /// What: Run the reverse dependency analysis shared by the public entry points.
+    ///
+    /// Inputs:
+    /// - `packages`: Slice of `PackageRef` to analyze for removal.
+    /// - `opts`: Traversal options.
+    /// - `progress`: Callback receiving traversal progress.
+    ///
+    /// Output:
+    /// - Returns the `ReverseDependencyReport`, or the error of the first target pacman could
+    ///   not query.
+    fn run(
+        &self,
+        packages: &[PackageRef],
+        opts: &ReverseOptions,
+        progress: &mut dyn FnMut(ReverseProgress),
+    ) -> Result<ReverseDependencyReport> {
         tracing::info!(
             "Starting reverse dependency resolution for {} target(s)",
             packages.len()
@@ -95,7 +326,8 @@ impl ReverseDependencyAnalyzer {
             return Ok(ReverseDependencyReport::default());
         }
 
-        let mut state = ReverseResolverState::new(packages);
+        let runner = self.runner.as_deref().unwrap_or(&SystemRunner);
+        let mut state = ReverseResolverState::new(packages, runner);
 
         for target in packages {
             let root = target.name.trim();
@@ -103,7 +335,7 @@ impl ReverseDependencyAnalyzer {
                 continue;
             }
 
-            if state.pkg_info(root).is_none() {
+            if state.target_info(root)?.is_none() {
                 tracing::warn!(
                     "Skipping reverse dependency walk for {} (not installed)",
                     root
@@ -111,28 +343,29 @@ impl ReverseDependencyAnalyzer {
                 continue;
             }
 
-            let mut visited: HashSet<String> = HashSet::new();
-            visited.insert(root.to_string());
-
-            let mut queue: VecDeque<(String, usize)> = VecDeque::new();
-            queue.push_back((root.to_string(), 0));
-
-            while let Some((current, depth)) = queue.pop_front() {
-                let Some(info) = state.pkg_info(&current) else {
-                    continue;
-                };
-
-                for dependent in info.required_by.iter().filter(|name| !name.is_empty()) {
-                    state.update_entry(dependent, &current, root, depth + 1);
-
-                    if visited.insert(dependent.clone()) {
-                        queue.push_back((dependent.clone(), depth + 1));
-                    }
-                }
-            }
+            state.walk(root, opts, progress);
         }
 
-        let ReverseResolverState { aggregated, .. } = state;
+        progress(ReverseProgress {
+            visited: state.aggregated.len(),
+            queued: 0,
+            current: String::new(),
+        });
+
+        let ReverseResolverState {
+            aggregated, edges, ..
+        } = state;
+        let cycles = find_graph_cycles(
+            edges
+                .iter()
+                .map(|(name, dependents)| {
+                    (
+                        name.as_str(),
+                        dependents.iter().map(String::as_str).collect(),
+                    )
+                })
+                .collect(),
+        );
 
         let mut summary_map: HashMap<String, ReverseDependencySummary> = HashMap::new();
         for entry in aggregated.values() {
@@ -167,9 +400,10 @@ impl ReverseDependencyAnalyzer {
         let mut summaries: Vec<ReverseDependencySummary> = summary_map.into_values().collect();
         summaries.sort_by(|a, b| a.package.cmp(&b.package));
 
+        let source_config = self.source_config.clone().unwrap_or_default();
         let mut dependencies: Vec<Dependency> = aggregated
             .into_iter()
-            .map(|(name, entry)| convert_entry(name, entry))
+            .map(|(name, entry)| convert_entry(name, entry, &source_config))
             .collect();
         dependencies.sort_by(|a, b| a.name.cmp(&b.name));
 
@@ -181,6 +415,7 @@ impl ReverseDependencyAnalyzer {
         Ok(ReverseDependencyReport {
             dependents: dependencies,
             summaries,
+            cycles,
         })
     }
 }
@@ -201,7 +436,9 @@ impl Default for ReverseDependencyAnalyzer {
 ///
 /// Details:
 /// - Encapsulates shared collections so helper methods can mutate state without leaking implementation details.
-struct ReverseResolverState {
+struct ReverseResolverState<'a> {
+    /// Executes the `pacman -Qi` queries.
+    runner: &'a dyn CommandRunner,
     /// Aggregated reverse dependency entries by package name.
     aggregated: HashMap<String, AggregatedEntry>,
     /// Cache of package information by package name.
@@ -210,26 +447,34 @@ struct ReverseResolverState {
     missing: HashSet<String>,
     /// Set of target package names for reverse dependency resolution.
     target_names: HashSet<String>,
+    /// Number of packages whose dependents have been expanded, across all roots.
+    expanded: usize,
+    /// "Required By" edges seen while expanding packages (package -> dependents).
+    edges: BTreeMap<String, BTreeSet<String>>,
 }
 
-impl ReverseResolverState {
+impl<'a> ReverseResolverState<'a> {
     /// What: Initialize traversal state for the provided removal targets.
     ///
     /// Inputs:
     /// - `targets`: Packages selected for removal.
+    /// - `runner`: Executes the `pacman -Qi` queries.
     ///
     /// Output:
     /// - Returns a state object preloaded with target name bookkeeping.
     ///
     /// Details:
     /// - Prepares aggregation maps and caches so subsequent queries can avoid redundant pacman calls.
-    fn new(targets: &[PackageRef]) -> Self {
+    fn new(targets: &[PackageRef], runner: &'a dyn CommandRunner) -> Self {
         let target_names = targets.iter().map(|pkg| pkg.name.clone()).collect();
         Self {
+            runner,
             aggregated: HashMap::new(),
             cache: HashMap::new(),
             missing: HashSet::new(),
             target_names,
+            expanded: 0,
+            edges: BTreeMap::new(),
         }
     }
 
@@ -251,7 +496,7 @@ impl ReverseResolverState {
             return None;
         }
 
-        match fetch_pkg_info(name) {
+        match fetch_pkg_info(self.runner, name) {
             Ok(info) => {
                 self.cache.insert(name.to_string(), info.clone());
                 Some(info)
@@ -264,6 +509,102 @@ impl ReverseResolverState {
         }
     }
 
+    /// What: Fetch and cache package information for a removal target.
+    ///
+    /// Inputs:
+    /// - `name`: Removal target whose metadata should be retrieved via `pacman -Qi`.
+    ///
+    /// Output:
+    /// - `Ok(Some(info))` when installed, `Ok(None)` when pacman reports it as not found.
+    ///
+    /// Details:
+    /// - Unlike `pkg_info()`, other pacman failures are returned, so a failed query cannot be
+    ///   mistaken for a target without dependents.
+    ///
+    /// # Errors
+    /// - Returns the errors of `fetch_pkg_info()` other than `PackageNotFound`.
+    fn target_info(&mut self, name: &str) -> Result<Option<PkgInfo>> {
+        if let Some(info) = self.cache.get(name) {
+            return Ok(Some(info.clone()));
+        }
+        if self.missing.contains(name) {
+            return Ok(None);
+        }
+
+        match fetch_pkg_info(self.runner, name) {
+            Ok(info) => {
+                self.cache.insert(name.to_string(), info.clone());
+                Ok(Some(info))
+            }
+            Err(ArchToolkitError::PackageNotFound { .. }) => {
+                self.missing.insert(name.to_string());
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// What: Breadth-first walk of the reverse dependency graph below one removal target.
+    ///
+    /// Inputs:
+    /// - `root`: Removal target to start from.
+    /// - `opts`: Depth limit and optional-dependent handling.
+    /// - `progress`: Receives a `ReverseProgress` every `PROGRESS_INTERVAL` expanded packages.
+    ///
+    /// Output:
+    /// - Records every reached dependent via `update_entry`.
+    ///
+    /// Details:
+    /// - Optional dependents are recorded but not expanded further.
+    fn walk(
+        &mut self,
+        root: &str,
+        opts: &ReverseOptions,
+        progress: &mut dyn FnMut(ReverseProgress),
+    ) {
+        let mut visited: HashSet<String> = HashSet::new();
+        visited.insert(root.to_string());
+
+        let mut queue: VecDeque<(String, usize)> = VecDeque::new();
+        queue.push_back((root.to_string(), 0));
+
+        while let Some((current, depth)) = queue.pop_front() {
+            if opts.max_depth.is_some_and(|max| depth >= max) {
+                continue;
+            }
+            let Some(info) = self.pkg_info(&current) else {
+                continue;
+            };
+
+            for dependent in info.required_by.iter().filter(|name| !name.is_empty()) {
+                self.update_entry(dependent, &current, root, depth + 1, false);
+                self.edges
+                    .entry(current.clone())
+                    .or_default()
+                    .insert(dependent.clone());
+
+                if visited.insert(dependent.clone()) {
+                    queue.push_back((dependent.clone(), depth + 1));
+                }
+            }
+
+            if opts.include_optional {
+                for dependent in info.optional_for.iter().filter(|name| !name.is_empty()) {
+                    self.update_entry(dependent, &current, root, depth + 1, true);
+                }
+            }
+
+            self.expanded += 1;
+            if self.expanded.is_multiple_of(PROGRESS_INTERVAL) {
+                progress(ReverseProgress {
+                    visited: self.aggregated.len(),
+                    queued: queue.len(),
+                    current,
+                });
+            }
+        }
+    }
+
     /// What: Update aggregation records to reflect a discovered reverse dependency relationship.
     ///
     /// Inputs:
@@ -271,13 +612,21 @@ impl ReverseResolverState {
     /// - `parent`: Immediate package causing the dependency (may be empty).
     /// - `root`: Root removal target currently being explored.
     /// - `depth`: Distance from the root in the traversal.
+    /// - `optional`: Whether `dependent` only optionally depends on `parent`.
     ///
     /// Output:
     /// - Mutates internal maps to capture per-root relationships and selection flags.
     ///
     /// Details:
     /// - Consolidates metadata per dependent package while preserving shortest depth and parent sets per root.
-    fn update_entry(&mut self, dependent: &str, parent: &str, root: &str, depth: usize) {
+    fn update_entry(
+        &mut self,
+        dependent: &str,
+        parent: &str,
+        root: &str,
+        depth: usize,
+        optional: bool,
+    ) {
         if dependent.eq_ignore_ascii_case(root) {
             return;
         }
@@ -299,6 +648,7 @@ impl ReverseResolverState {
                     .entry(root.to_string())
                     .or_insert_with(RootRelation::new);
                 relation.record(parent, depth);
+                relation.required |= !optional;
             }
             Entry::Vacant(slot) => {
                 let mut data = AggregatedEntry {
@@ -306,10 +656,12 @@ impl ReverseResolverState {
                     per_root: HashMap::new(),
                     selected_for_removal: selected,
                 };
-                data.per_root
+                let relation = data
+                    .per_root
                     .entry(root.to_string())
-                    .or_insert_with(RootRelation::new)
-                    .record(parent, depth);
+                    .or_insert_with(RootRelation::new);
+                relation.record(parent, depth);
+                relation.required |= !optional;
                 slot.insert(data);
             }
         }
@@ -339,8 +691,14 @@ struct PkgInfo {
     groups: Vec<String>,
     /// Packages that require this package.
     required_by: Vec<String>,
-    /// Whether package was explicitly installed.
-    explicit: bool,
+    /// Packages that list this package as an optional dependency ("Optional For" field).
+    optional_for: Vec<String>,
+    /// Packages this package conflicts with ("Conflicts With" field).
+    conflicts: Vec<String>,
+    /// Packages this package replaces ("Replaces" field).
+    replaces: Vec<String>,
+    /// Why the package is installed ("Install Reason" field).
+    install_reason: InstallReason,
 }
 
 /// What: Aggregated view of a dependent package across all removal roots.
@@ -379,6 +737,8 @@ struct RootRelation {
     parents: HashSet<String>,
     /// Minimum depth from the removal root to this package.
     min_depth: usize,
+    /// Whether any recorded edge is a hard dependency (false for optional-only dependents).
+    required: bool,
 }
 
 impl RootRelation {
@@ -396,6 +756,7 @@ impl RootRelation {
         Self {
             parents: HashSet::new(),
             min_depth: usize::MAX,
+            required: false,
         }
     }
 
@@ -439,13 +800,14 @@ impl RootRelation {
 /// Inputs:
 /// - `name`: Canonical dependent package name.
 /// - `entry`: Aggregated structure containing metadata and per-root relations.
+/// - `source_config`: Protected groups and packages deciding `is_system`.
 ///
 /// Output:
 /// - Returns a `Dependency` tailored for preflight summaries with conflict reasoning.
 ///
 /// Details:
 /// - Merges parent sets, sorts presentation fields, and infers system/core flags for display.
-fn convert_entry(name: String, entry: AggregatedEntry) -> Dependency {
+fn convert_entry(name: String, entry: AggregatedEntry, source_config: &SourceConfig) -> Dependency {
     let AggregatedEntry {
         info,
         per_root,
@@ -458,8 +820,12 @@ fn convert_entry(name: String, entry: AggregatedEntry) -> Dependency {
         repo,
         groups,
         required_by: _,
-        explicit,
+        optional_for: _,
+        conflicts: _,
+        replaces: _,
+        install_reason,
     } = info;
+    let explicit = install_reason == InstallReason::Explicit;
 
     let mut required_by: Vec<String> = per_root.keys().cloned().collect();
     required_by.sort();
@@ -477,7 +843,9 @@ fn convert_entry(name: String, entry: AggregatedEntry) -> Dependency {
         let mut parents: Vec<String> = relation.parents.iter().cloned().collect();
         parents.sort();
 
-        if depth <= 1 {
+        if !relation.required {
+            reason_parts.push(format!("optionally uses {root}"));
+        } else if depth <= 1 {
             reason_parts.push(format!("requires {root}"));
         } else {
             let via = if parents.is_empty() {
@@ -516,11 +884,13 @@ fn convert_entry(name: String, entry: AggregatedEntry) -> Dependency {
     let is_core = repo
         .as_deref()
         .is_some_and(|r| r.eq_ignore_ascii_case("core"));
-    let is_system = groups
-        .iter()
-        .any(|g| matches!(g.as_str(), "base" | "base-devel"));
-
     let display_name = if pkg_name.is_empty() { name } else { pkg_name };
+    let is_system = is_system_package_with(&display_name, &groups, source_config);
+    let kind = if per_root.values().any(|relation| relation.required) {
+        DependencyKind::Runtime
+    } else {
+        DependencyKind::Opt
+    };
 
     Dependency {
         name: display_name,
@@ -531,12 +901,17 @@ fn convert_entry(name: String, entry: AggregatedEntry) -> Dependency {
         depends_on,
         is_core,
         is_system,
+        kind,
+        explicit,
+        install_reason,
+        satisfied_by: None,
     }
 }
 
 /// What: Query pacman for detailed information about an installed package.
 ///
 /// Inputs:
+/// - `runner`: Executes the command.
 /// - `name`: Package name passed to `pacman -Qi`.
 ///
 /// Output:
@@ -545,48 +920,53 @@ fn convert_entry(name: String, entry: AggregatedEntry) -> Dependency {
 /// Details:
 /// - Parses key-value fields such as repository, groups, and required-by lists for downstream processing.
 /// - Sets `LC_ALL=C` and `LANG=C` for consistent locale-independent output.
-fn fetch_pkg_info(name: &str) -> Result<PkgInfo> {
-    tracing::debug!("Running: pacman -Qi {}", name);
-    let output = Command::new("pacman")
-        .args(["-Qi", name])
-        .env("LC_ALL", "C")
-        .env("LANG", "C")
-        .stdin(Stdio::null())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
-        .map_err(|e| ArchToolkitError::Parse(format!("pacman -Qi {name} failed: {e}")))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(ArchToolkitError::Parse(format!(
-            "pacman -Qi {name} exited with {:?}: {}",
-            output.status, stderr
-        )));
-    }
-
-    let text = String::from_utf8_lossy(&output.stdout);
-    let map = parse_key_value_output(&text);
+/// - Fails with `PacmanUnavailable` when pacman is missing and `PackageNotFound` when the
+///   package is not installed.
+fn fetch_pkg_info(runner: &dyn CommandRunner, name: &str) -> Result<PkgInfo> {
+    let text = pacman_stdout(runner, &["-Qi", name])?;
+    Ok(parse_pkg_info(name, &text))
+}
+
+/// What: Build a `PkgInfo` snapshot from raw `pacman -Qi` output.
+///
+/// Inputs:
+/// - `name`: Package name used as a fallback when the output lacks a "Name" field.
+/// - `text`: Raw stdout from `pacman -Qi`.
+///
+/// Output:
+/// - Returns a `PkgInfo` populated from the parsed key-value fields.
+///
+/// Details:
+/// - List fields ("Groups", "Required By", "Optional For", "Conflicts With", "Replaces") treat
+///   "None" as empty.
+/// - Separated from `fetch_pkg_info` so parsing can be tested without invoking pacman.
+fn parse_pkg_info(name: &str, text: &str) -> PkgInfo {
+    let map = parse_key_value_output(text);
 
     let required_by = split_ws_or_none(map.get("Required By"));
+    let optional_for = split_ws_or_none(map.get("Optional For"));
     let groups = split_ws_or_none(map.get("Groups"));
+    let conflicts = split_ws_or_none(map.get("Conflicts With"));
+    let replaces = split_ws_or_none(map.get("Replaces"));
     let version = map.get("Version").cloned().unwrap_or_default();
     let repo = map.get("Repository").cloned();
     let install_reason = map
         .get("Install Reason")
-        .cloned()
-        .unwrap_or_default()
-        .to_lowercase();
-    let explicit = install_reason.contains("explicit");
+        .map_or(InstallReason::Unknown, |value| {
+            InstallReason::from_pacman(value)
+        });
 
-    Ok(PkgInfo {
+    PkgInfo {
         name: map.get("Name").cloned().unwrap_or_else(|| name.to_string()),
         version,
         repo,
         groups,
         required_by,
-        explicit,
-    })
+        optional_for,
+        conflicts,
+        replaces,
+        install_reason,
+    }
 }
 
 /// What: Parse pacman key-value output into a searchable map.
@@ -599,7 +979,7 @@ fn fetch_pkg_info(name: &str) -> Result<PkgInfo> {
 ///
 /// Details:
 /// - Handles indented continuation lines by appending them to the most recently parsed key.
-fn parse_key_value_output(text: &str) -> BTreeMap<String, String> {
+pub(super) fn parse_key_value_output(text: &str) -> BTreeMap<String, String> {
     let mut map: BTreeMap<String, String> = BTreeMap::new();
     let mut last_key: Option<String> = None;
 
@@ -637,7 +1017,7 @@ fn parse_key_value_output(text: &str) -> BTreeMap<String, String> {
 ///
 /// Details:
 /// - Trims surrounding whitespace before evaluating the contents to avoid spurious blank entries.
-fn split_ws_or_none(field: Option<&String>) -> Vec<String> {
+pub(super) fn split_ws_or_none(field: Option<&String>) -> Vec<String> {
     field.map_or_else(Vec::new, |value| {
         let trimmed = value.trim();
         if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("none") {
@@ -676,18 +1056,73 @@ fn split_ws_or_none(field: Option<&String>) -> Vec<String> {
 /// ```
 #[must_use]
 pub fn has_installed_required_by(name: &str) -> bool {
-    let Ok(installed) = get_installed_packages() else {
-        tracing::debug!("Failed to get installed packages for has_installed_required_by");
-        return false;
-    };
+    try_has_installed_required_by(name).unwrap_or_else(|err| {
+        tracing::debug!("Failed to check installed dependents of {}: {}", name, err);
+        false
+    })
+}
 
-    match fetch_pkg_info(name) {
-        Ok(info) => info
-            .required_by
-            .iter()
-            .any(|pkg| installed.contains(pkg.as_str())),
+/// What: Check if a package has installed dependents, reporting pacman failures.
+///
+/// Inputs:
+/// - `name`: Package name to check.
+///
+/// Output:
+/// - Returns `Ok(true)` if at least one package in the "Required By" field is installed.
+///
+/// Details:
+/// - Same query as `has_installed_required_by`, without the graceful degradation.
+///
+/// # Errors
+///
+/// - Returns `Err(ArchToolkitError::PacmanUnavailable)` when pacman is not installed.
+/// - Returns `Err(ArchToolkitError::PackageNotFound)` when `name` is not installed.
+/// - Returns `Err(ArchToolkitError::Parse)` for other pacman failures.
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::deps::try_has_installed_required_by;
+///
+/// if try_has_installed_required_by("glibc")? {
+///     println!("glibc has installed dependents");
+/// }
+/// # Ok::<(), arch_toolkit::error::ArchToolkitError>(())
+/// ```
+pub fn try_has_installed_required_by(name: &str) -> Result<bool> {
+    Ok(!installed_required_by(&SystemRunner, name)?.is_empty())
+}
+
+/// What: Check if a package has installed dependents without blocking the async runtime.
+///
+/// Inputs:
+/// - `name`: Package name to check.
+///
+/// Output:
+/// - Returns the same result as `has_installed_required_by`.
+///
+/// Details:
+/// - Runs the pacman queries with `tokio::task::spawn_blocking`.
+/// - Gracefully degrades to `false`, also when the blocking task fails.
+/// - Requires `feature = "aur"` to be enabled.
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::deps::has_installed_required_by_async;
+///
+/// # async fn example() {
+/// if has_installed_required_by_async("glibc").await {
+///     println!("glibc has installed dependents");
+/// }
+/// # }
+/// ```
+#[cfg(feature = "aur")]
+pub async fn has_installed_required_by_async(name: &str) -> bool {
+    match installed_required_by_async(Arc::new(SystemRunner), name).await {
+        Ok(dependents) => !dependents.is_empty(),
         Err(err) => {
-            tracing::debug!("Failed to query pacman -Qi {}: {}", name, err);
+            tracing::debug!("Failed to check installed dependents of {}: {}", name, err);
             false
         }
     }
@@ -717,18 +1152,182 @@ pub fn has_installed_required_by(name: &str) -> bool {
 /// ```
 #[must_use]
 pub fn get_installed_required_by(name: &str) -> Vec<String> {
-    let Ok(installed) = get_installed_packages() else {
-        tracing::debug!("Failed to get installed packages for get_installed_required_by");
-        return Vec::new();
-    };
+    try_get_installed_required_by(name).unwrap_or_else(|err| {
+        tracing::debug!("Failed to get installed dependents of {}: {}", name, err);
+        Vec::new()
+    })
+}
 
-    match fetch_pkg_info(name) {
-        Ok(info) => info
-            .required_by
-            .iter()
-            .filter(|pkg| installed.contains(pkg.as_str()))
-            .cloned()
-            .collect(),
+/// What: Get the installed packages that depend on a package, reporting pacman failures.
+///
+/// Inputs:
+/// - `name`: Package name to check.
+///
+/// Output:
+/// - Returns the installed packages listed in the "Required By" field of `name`.
+///
+/// Details:
+/// - Same query as `get_installed_required_by`, without the graceful degradation.
+///
+/// # Errors
+///
+/// - Returns `Err(ArchToolkitError::PacmanUnavailable)` when pacman is not installed.
+/// - Returns `Err(ArchToolkitError::PackageNotFound)` when `name` is not installed.
+/// - Returns `Err(ArchToolkitError::Parse)` for other pacman failures.
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::deps::try_get_installed_required_by;
+///
+/// let dependents = try_get_installed_required_by("glibc")?;
+/// println!("Found {} installed dependents", dependents.len());
+/// # Ok::<(), arch_toolkit::error::ArchToolkitError>(())
+/// ```
+pub fn try_get_installed_required_by(name: &str) -> Result<Vec<String>> {
+    installed_required_by(&SystemRunner, name)
+}
+
+/// What: Get the installed dependents of a package without blocking the async runtime.
+///
+/// Inputs:
+/// - `name`: Package name to check.
+///
+/// Output:
+/// - Returns the same packages as `get_installed_required_by`.
+///
+/// Details:
+/// - Runs the pacman queries with `tokio::task::spawn_blocking`.
+/// - Gracefully degrades to an empty vector, also when the blocking task fails.
+/// - Requires `feature = "aur"` to be enabled.
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::deps::get_installed_required_by_async;
+///
+/// # async fn example() {
+/// let dependents = get_installed_required_by_async("glibc").await;
+/// println!("Found {} installed dependents", dependents.len());
+/// # }
+/// ```
+#[cfg(feature = "aur")]
+pub async fn get_installed_required_by_async(name: &str) -> Vec<String> {
+    installed_required_by_async(Arc::new(SystemRunner), name)
+        .await
+        .unwrap_or_else(|err| {
+            tracing::debug!("Failed to get installed dependents of {}: {}", name, err);
+            Vec::new()
+        })
+}
+
+/// What: Get the installed packages in the "Required By" field of a package.
+///
+/// Inputs:
+/// - `runner`: Executes `pacman -Qq` and `pacman -Qi`.
+/// - `name`: Package name to check.
+///
+/// Output:
+/// - Installed dependents of `name`, in pacman's order.
+///
+/// # Errors
+///
+/// - Returns the errors of `try_get_installed_required_by`.
+fn installed_required_by(runner: &dyn CommandRunner, name: &str) -> Result<Vec<String>> {
+    let installed = installed_packages(runner)?;
+    let info = fetch_pkg_info(runner, name)?;
+    Ok(info
+        .required_by
+        .into_iter()
+        .filter(|pkg| installed.contains(pkg.as_str()))
+        .collect())
+}
+
+/// What: Run `installed_required_by` on the blocking thread pool.
+///
+/// Inputs:
+/// - `runner`: Executes the pacman queries on the blocking thread.
+/// - `name`: Package name to check.
+///
+/// Output:
+/// - Installed dependents of `name`.
+///
+/// # Errors
+///
+/// - Returns the errors of `installed_required_by`.
+/// - Returns `Err(ArchToolkitError::Parse)` if the blocking task fails.
+#[cfg(feature = "aur")]
+async fn installed_required_by_async(
+    runner: Arc<dyn CommandRunner>,
+    name: &str,
+) -> Result<Vec<String>> {
+    let name = name.to_string();
+    tokio::task::spawn_blocking(move || installed_required_by(runner.as_ref(), &name))
+        .await
+        .map_err(|e| ArchToolkitError::Parse(format!("Blocking task failed: {e}")))?
+}
+
+/// What: Get the "Conflicts With" entries of an installed package.
+///
+/// Inputs:
+/// - `name`: Installed package name to query.
+///
+/// Output:
+/// - Returns the conflict entries as reported by pacman, or an empty vector on failure.
+///
+/// Details:
+/// - Runs `pacman -Qi` and parses the "Conflicts With" field.
+/// - Entries are returned verbatim and may carry version constraints (e.g., "foo<2.0").
+/// - Returns an empty vector when the field is "None", the package is not installed, or
+///   pacman is unavailable.
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::deps::get_installed_conflicts;
+///
+/// let conflicts = get_installed_conflicts("vim");
+/// println!("vim conflicts with: {:?}", conflicts);
+/// ```
+#[must_use]
+pub fn get_installed_conflicts(name: &str) -> Vec<String> {
+    match fetch_pkg_info(&SystemRunner, name) {
+        Ok(info) => info.conflicts,
+        Err(err) => {
+            tracing::debug!("Failed to query pacman -Qi {}: {}", name, err);
+            Vec::new()
+        }
+    }
+}
+
+/// What: Get the "Replaces" entries of an installed package.
+///
+/// Inputs:
+/// - `name`: Installed package name to query.
+///
+/// Output:
+/// - Returns the packages replaced by `name`, or an empty vector on failure.
+///
+/// Details:
+/// - Runs `pacman -Qi` and parses the "Replaces" field.
+/// - Useful for removal impact analysis: removing a package that replaces another may warrant
+///   reinstalling the replaced package.
+/// - Returns an empty vector when the field is "None", the package is not installed, or
+///   pacman is unavailable.
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::deps::get_installed_replaces;
+///
+/// for replaced in get_installed_replaces("neovim") {
+///     println!("Removing neovim may warrant reinstalling {replaced}");
+/// }
+/// ```
+#[must_use]
+pub fn get_installed_replaces(name: &str) -> Vec<String> {
+    match fetch_pkg_info(&SystemRunner, name) {
+        Ok(info) => info.replaces,
         Err(err) => {
             tracing::debug!("Failed to query pacman -Qi {}: {}", name, err);
             Vec::new()
@@ -739,6 +1338,7 @@ pub fn get_installed_required_by(name: &str) -> Vec<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::deps::runner::MockRunner;
     use crate::types::dependency::PackageSource;
 
     fn pkg_ref(name: &str) -> PackageRef {
@@ -759,7 +1359,10 @@ mod tests {
             repo: Some("extra".into()),
             groups: Vec::new(),
             required_by: Vec::new(),
-            explicit: false,
+            optional_for: Vec::new(),
+            conflicts: Vec::new(),
+            replaces: Vec::new(),
+            install_reason: InstallReason::Dependency,
         }
     }
 
@@ -778,10 +1381,11 @@ mod tests {
     ///   package linked to a specified root.
     fn update_entry_tracks_root_relations_and_selection() {
         let targets = vec![pkg_ref("root"), pkg_ref("app")];
-        let mut state = ReverseResolverState::new(&targets);
+        let runner = MockRunner::default();
+        let mut state = ReverseResolverState::new(&targets, &runner);
         state.cache.insert("app".into(), pkg_info_stub("app"));
 
-        state.update_entry("app", "root", "root", 1);
+        state.update_entry("app", "root", "root", 1, false);
 
         let entry = state
             .aggregated
@@ -812,9 +1416,11 @@ mod tests {
     fn convert_entry_produces_conflict_reason_and_flags() {
         let mut relation_a = RootRelation::new();
         relation_a.record("root", 1);
+        relation_a.required = true;
         let mut relation_b = RootRelation::new();
         relation_b.record("parent_x", 2);
         relation_b.record("parent_y", 2);
+        relation_b.required = true;
 
         let entry = AggregatedEntry {
             info: PkgInfo {
@@ -823,13 +1429,20 @@ mod tests {
                 repo: Some("core".into()),
                 groups: vec!["base".into()],
                 required_by: Vec::new(),
-                explicit: true,
+                optional_for: Vec::new(),
+                conflicts: Vec::new(),
+                replaces: Vec::new(),
+                install_reason: InstallReason::Explicit,
             },
             per_root: HashMap::from([("root".into(), relation_a), ("other".into(), relation_b)]),
             selected_for_removal: true,
         };
 
-        let info = convert_entry("dep".into(), entry);
+        let config = SourceConfig {
+            protected_groups: vec!["base".into()],
+            ..SourceConfig::default()
+        };
+        let info = convert_entry("dep".into(), entry, &config);
         let DependencyStatus::Conflict { reason } = &info.status else {
             panic!("expected conflict status");
         };
@@ -842,6 +1455,36 @@ mod tests {
         assert!(info.is_core);
         assert!(info.is_system);
         assert_eq!(info.name, "dep_alias");
+        assert!(info.explicit);
+        assert_eq!(info.install_reason, InstallReason::Explicit);
+    }
+
+    #[test]
+    /// What: Confirm `convert_entry` exposes the install reason of dependency-installed packages.
+    ///
+    /// Inputs:
+    /// - `entry`: Aggregated entry for a package installed as a dependency.
+    ///
+    /// Output:
+    /// - `explicit` is false, `install_reason` is `Dependency`, and the reason string does not
+    ///   mention an explicit install.
+    fn convert_entry_reports_dependency_install_reason() {
+        let mut relation = RootRelation::new();
+        relation.record("root", 1);
+        relation.required = true;
+        let entry = AggregatedEntry {
+            info: pkg_info_stub("lib"),
+            per_root: HashMap::from([("root".into(), relation)]),
+            selected_for_removal: false,
+        };
+
+        let info = convert_entry("lib".into(), entry, &SourceConfig::default());
+        assert!(!info.explicit);
+        assert_eq!(info.install_reason, InstallReason::Dependency);
+        let DependencyStatus::Conflict { reason } = &info.status else {
+            panic!("expected conflict status");
+        };
+        assert!(!reason.contains("explicitly installed"));
     }
 
     #[test]
@@ -867,6 +1510,34 @@ mod tests {
         assert_eq!(map.get("Required By"), Some(&"foo bar".to_string()));
     }
 
+    #[test]
+    /// What: Ensure `parse_pkg_info` extracts "Conflicts With" and "Replaces" lists.
+    ///
+    /// Inputs:
+    /// - `sample`: `pacman -Qi` block with populated conflicts/replaces and a "None" groups field.
+    ///
+    /// Output:
+    /// - Conflicts and replaces populate as vectors; "None" fields yield empty vectors.
+    ///
+    /// Details:
+    /// - Covers wrapped continuation lines and the "None" sentinel for both fields.
+    fn parse_pkg_info_extracts_conflicts_and_replaces() {
+        let sample = "Name            : neovim\nVersion         : 0.10.0-1\nGroups          : None\nRequired By     : None\nConflicts With  : vim-runtime  gvim<9.0\n                  vi\nReplaces        : vim-minimal\nInstall Reason  : Explicitly installed\n";
+        let info = parse_pkg_info("neovim", sample);
+        assert!(info.optional_for.is_empty());
+        assert_eq!(info.name, "neovim");
+        assert_eq!(info.conflicts, vec!["vim-runtime", "gvim<9.0", "vi"]);
+        assert_eq!(info.replaces, vec!["vim-minimal"]);
+        assert!(info.groups.is_empty());
+        assert_eq!(info.install_reason, InstallReason::Explicit);
+
+        let sample = "Name            : bash\nConflicts With  : None\nReplaces        : None\n";
+        let info = parse_pkg_info("bash", sample);
+        assert_eq!(info.install_reason, InstallReason::Unknown);
+        assert!(info.conflicts.is_empty());
+        assert!(info.replaces.is_empty());
+    }
+
     #[test]
     /// What: Validate whitespace splitting helper ignores empty and "none" values.
     ///
@@ -914,4 +1585,359 @@ mod tests {
         assert_eq!(relation.min_depth(), 1);
         assert_eq!(relation.parents.len(), 2);
     }
+
+    #[test]
+    /// What: Fail the analysis when pacman cannot query a removal target.
+    ///
+    /// Inputs:
+    /// - Runner without pacman; runner reporting `ghost` as not found.
+    ///
+    /// Output:
+    /// - `PacmanUnavailable` instead of an empty report; a target that is not installed is
+    ///   skipped.
+    fn analyze_propagates_pacman_failures() {
+        let err = ReverseDependencyAnalyzer::new()
+            .with_runner(MockRunner::default())
+            .analyze(&[pkg_ref("rv-lib")])
+            .expect_err("pacman is unavailable");
+        assert!(matches!(err, ArchToolkitError::PacmanUnavailable { .. }));
+
+        let runner = MockRunner::default().respond(
+            "pacman -Qi ghost",
+            1,
+            "",
+            "error: package 'ghost' was not found\n",
+        );
+        let report = ReverseDependencyAnalyzer::new()
+            .with_runner(runner)
+            .analyze(&[pkg_ref("ghost")])
+            .expect("missing target is skipped");
+        assert!(report.dependents.is_empty());
+    }
+
+    #[test]
+    /// What: Drive a full reverse dependency analysis against mocked `pacman -Qi` output.
+    ///
+    /// Inputs:
+    /// - `rv-lib` required by `rv-app`, which is required by `rv-suite`.
+    ///
+    /// Output:
+    /// - One direct and one transitive dependent.
+    fn analyze_with_mock_runner() {
+        let runner = MockRunner::default()
+            .ok(
+                "pacman -Qi rv-lib",
+                "Name            : rv-lib\nVersion         : 1.0-1\nRequired By     : rv-app\n",
+            )
+            .ok(
+                "pacman -Qi rv-app",
+                "Name            : rv-app\nVersion         : 2.0-1\nRequired By     : rv-suite\n",
+            )
+            .ok(
+                "pacman -Qi rv-suite",
+                "Name            : rv-suite\nVersion         : 3.0-1\nRequired By     : None\n",
+            );
+        let analyzer = ReverseDependencyAnalyzer::new().with_runner(runner);
+
+        let report = analyzer
+            .analyze(&[pkg_ref("rv-lib")])
+            .expect("analysis succeeds");
+
+        let names: Vec<&str> = report.dependents.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["rv-app", "rv-suite"]);
+        assert_eq!(report.summaries.len(), 1);
+        assert_eq!(report.summaries[0].direct_dependents, 1);
+        assert_eq!(report.summaries[0].transitive_dependents, 1);
+        assert!(report.cycles.is_empty());
+    }
+
+    #[test]
+    /// What: Flag dependents in a custom protected group as system packages.
+    ///
+    /// Inputs:
+    /// - `lib32-glibc` required by `lib32-gcc-libs` (group `multilib-devel`) and `wine`.
+    ///
+    /// Output:
+    /// - Only `lib32-gcc-libs` is `is_system`, and only once `multilib-devel` is protected.
+    fn analyze_flags_custom_protected_group() {
+        let runner = || {
+            MockRunner::default()
+                .ok(
+                    "pacman -Qi lib32-glibc",
+                    "Name            : lib32-glibc\nRequired By     : lib32-gcc-libs  wine\n",
+                )
+                .ok(
+                    "pacman -Qi lib32-gcc-libs",
+                    "Name            : lib32-gcc-libs\nGroups          : multilib-devel\nRequired By     : None\n",
+                )
+                .ok(
+                    "pacman -Qi wine",
+                    "Name            : wine\nGroups          : None\nRequired By     : None\n",
+                )
+        };
+        let system_flags = |analyzer: ReverseDependencyAnalyzer| {
+            analyzer
+                .analyze(&[pkg_ref("lib32-glibc")])
+                .expect("analysis succeeds")
+                .dependents
+                .into_iter()
+                .map(|dep| (dep.name, dep.is_system))
+                .collect::<Vec<_>>()
+        };
+
+        let mut config = SourceConfig::default();
+        config.protected_groups.push("multilib-devel".to_string());
+        let protected = ReverseDependencyAnalyzer::new()
+            .with_runner(runner())
+            .with_source_config(config);
+        assert_eq!(
+            system_flags(protected),
+            [
+                ("lib32-gcc-libs".to_string(), true),
+                ("wine".to_string(), false)
+            ]
+        );
+
+        let default = ReverseDependencyAnalyzer::new().with_runner(runner());
+        assert_eq!(
+            system_flags(default),
+            [
+                ("lib32-gcc-libs".to_string(), false),
+                ("wine".to_string(), false)
+            ]
+        );
+    }
+
+    /// What: Mocked graph for option tests.
+    ///
+    /// Details:
+    /// - `rv-lib` is required by `rv-app` and optional for `rv-viewer`; `rv-app` is required by
+    ///   `rv-suite`.
+    fn optional_graph_runner() -> MockRunner {
+        MockRunner::default()
+            .ok(
+                "pacman -Qi rv-lib",
+                "Name            : rv-lib\nRequired By     : rv-app\nOptional For    : rv-viewer\n",
+            )
+            .ok(
+                "pacman -Qi rv-app",
+                "Name            : rv-app\nRequired By     : rv-suite\nOptional For    : None\n",
+            )
+            .ok(
+                "pacman -Qi rv-suite",
+                "Name            : rv-suite\nRequired By     : None\nOptional For    : None\n",
+            )
+            .ok(
+                "pacman -Qi rv-viewer",
+                "Name            : rv-viewer\nRequired By     : None\nOptional For    : None\n",
+            )
+    }
+
+    #[test]
+    /// What: Ensure `max_depth` stops the traversal.
+    ///
+    /// Inputs:
+    /// - Mocked graph from `optional_graph_runner` with `max_depth: Some(1)`.
+    ///
+    /// Output:
+    /// - Only the direct dependent `rv-app` is reported; `rv-suite` is never queried.
+    fn analyze_with_limits_depth() {
+        let analyzer = ReverseDependencyAnalyzer::new().with_runner(optional_graph_runner());
+        let opts = ReverseOptions {
+            max_depth: Some(1),
+            include_optional: false,
+        };
+
+        let report = analyzer
+            .analyze_with(&[pkg_ref("rv-lib")], &opts)
+            .expect("analysis succeeds");
+
+        let names: Vec<&str> = report.dependents.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["rv-app"]);
+        assert_eq!(report.summaries[0].direct_dependents, 1);
+        assert_eq!(report.summaries[0].transitive_dependents, 0);
+    }
+
+    #[test]
+    /// What: Ensure optional dependents are reported only when requested.
+    ///
+    /// Inputs:
+    /// - Mocked graph from `optional_graph_runner`, analyzed with default and
+    ///   `include_optional` options.
+    ///
+    /// Output:
+    /// - Defaults skip `rv-viewer`; with `include_optional` it is reported as `Opt` while the
+    ///   hard dependents stay `Runtime`.
+    fn analyze_with_includes_optional_dependents() {
+        let analyzer = ReverseDependencyAnalyzer::new().with_runner(optional_graph_runner());
+        let targets = [pkg_ref("rv-lib")];
+
+        let report = analyzer.analyze(&targets).expect("analysis succeeds");
+        let names: Vec<&str> = report.dependents.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["rv-app", "rv-suite"]);
+
+        let opts = ReverseOptions {
+            max_depth: None,
+            include_optional: true,
+        };
+        let report = analyzer
+            .analyze_with(&targets, &opts)
+            .expect("analysis succeeds");
+        let kinds: Vec<(&str, DependencyKind)> = report
+            .dependents
+            .iter()
+            .map(|d| (d.name.as_str(), d.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ("rv-app", DependencyKind::Runtime),
+                ("rv-suite", DependencyKind::Runtime),
+                ("rv-viewer", DependencyKind::Opt),
+            ]
+        );
+        let DependencyStatus::Conflict { reason } = &report.dependents[2].status else {
+            panic!("expected conflict status");
+        };
+        assert!(reason.contains("optionally uses rv-lib"));
+        assert_eq!(report.summaries[0].direct_dependents, 2);
+    }
+
+    #[test]
+    /// What: Report a two-package "Required By" cycle once.
+    ///
+    /// Inputs:
+    /// - `cy-a` and `cy-b` require each other; `cy-app` requires `cy-b`. Both cycle members are
+    ///   removal targets, so the cycle is reached from two roots.
+    ///
+    /// Output:
+    /// - `cycles` holds `["cy-a", "cy-b"]` exactly once and the dependents are unchanged.
+    fn analyze_reports_cycle_once() {
+        let runner = MockRunner::default()
+            .ok(
+                "pacman -Qi cy-a",
+                "Name            : cy-a\nRequired By     : cy-b\n",
+            )
+            .ok(
+                "pacman -Qi cy-b",
+                "Name            : cy-b\nRequired By     : cy-a  cy-app\n",
+            )
+            .ok(
+                "pacman -Qi cy-app",
+                "Name            : cy-app\nRequired By     : None\n",
+            );
+        let analyzer = ReverseDependencyAnalyzer::new().with_runner(runner);
+
+        let report = analyzer
+            .analyze(&[pkg_ref("cy-a"), pkg_ref("cy-b")])
+            .expect("analysis succeeds");
+
+        assert_eq!(report.cycles, [["cy-a", "cy-b"]]);
+        let names: Vec<&str> = report.dependents.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["cy-a", "cy-app", "cy-b"]);
+    }
+
+    #[test]
+    /// What: Report progress while walking a long dependency chain.
+    ///
+    /// Inputs:
+    /// - Chain `chain-0` <- `chain-1` <- ... of `PROGRESS_INTERVAL + 10` dependents.
+    ///
+    /// Output:
+    /// - One interval report during the walk, then a final report whose `visited` matches the
+    ///   number of report dependents.
+    fn analyze_with_progress_reports_counts() {
+        let len = PROGRESS_INTERVAL + 10;
+        let mut runner = MockRunner::default();
+        for i in 0..=len {
+            let required_by = if i == len {
+                "None".to_string()
+            } else {
+                format!("chain-{}", i + 1)
+            };
+            runner = runner.ok(
+                &format!("pacman -Qi chain-{i}"),
+                &format!("Name            : chain-{i}\nRequired By     : {required_by}\n"),
+            );
+        }
+        let analyzer = ReverseDependencyAnalyzer::new().with_runner(runner);
+
+        let mut reports = Vec::new();
+        let report = analyzer
+            .analyze_with_progress(&[pkg_ref("chain-0")], |p| reports.push(p))
+            .expect("analysis succeeds");
+
+        assert_eq!(report.dependents.len(), len);
+        assert_eq!(reports.len(), 2);
+        assert_eq!(
+            reports[0].current,
+            format!("chain-{}", PROGRESS_INTERVAL - 1)
+        );
+        assert_eq!(reports[0].visited, PROGRESS_INTERVAL);
+        assert_eq!(reports[0].queued, 1);
+        assert_eq!(
+            reports[1],
+            ReverseProgress {
+                visited: report.dependents.len(),
+                queued: 0,
+                current: String::new(),
+            }
+        );
+    }
+
+    #[cfg(feature = "aur")]
+    #[tokio::test]
+    /// What: Compare `analyze_async` with `analyze` on the same mocked graph.
+    ///
+    /// Inputs:
+    /// - Analyzer on `optional_graph_runner`, removing `rv-lib`.
+    ///
+    /// Output:
+    /// - Both reports list the same dependents and summaries.
+    async fn analyze_async_matches_sync() {
+        let analyzer = ReverseDependencyAnalyzer::new().with_runner(optional_graph_runner());
+        let targets = [pkg_ref("rv-lib")];
+
+        let sync = analyzer.analyze(&targets).expect("sync analysis succeeds");
+        let from_async = analyzer
+            .analyze_async(&targets)
+            .await
+            .expect("async analysis succeeds");
+
+        assert_eq!(
+            serde_json::to_value(&from_async).expect("report serializes"),
+            serde_json::to_value(&sync).expect("report serializes")
+        );
+        assert_eq!(from_async.dependents.len(), 2);
+    }
+
+    #[cfg(feature = "aur")]
+    #[tokio::test]
+    /// What: Compare the async "Required By" query with the sync one.
+    ///
+    /// Inputs:
+    /// - `rv-lib` required by the installed `rv-app` and the uninstalled `rv-gone`.
+    ///
+    /// Output:
+    /// - Both list only `rv-app`; a missing pacman fails both the same way.
+    async fn installed_required_by_async_matches_sync() {
+        let runner = MockRunner::default()
+            .ok("pacman -Qq", "rv-lib\nrv-app\n")
+            .ok(
+                "pacman -Qi rv-lib",
+                "Name            : rv-lib\nRequired By     : rv-app  rv-gone\n",
+            );
+        let sync = installed_required_by(&runner, "rv-lib").expect("sync query succeeds");
+        let from_async = installed_required_by_async(Arc::new(runner), "rv-lib")
+            .await
+            .expect("async query succeeds");
+        assert_eq!(from_async, sync);
+        assert_eq!(from_async, ["rv-app"]);
+
+        let missing = installed_required_by_async(Arc::new(MockRunner::default()), "rv-lib").await;
+        assert!(matches!(
+            missing,
+            Err(ArchToolkitError::PacmanUnavailable { .. })
+        ));
+    }
 }
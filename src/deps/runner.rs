@@ -0,0 +1,136 @@
+//! Command execution abstraction for dependency queries.
+//!
+//! Dependency resolution and reverse dependency analysis shell out to `pacman` and AUR
+//! helpers. Routing every invocation through a [`CommandRunner`] lets callers substitute
+//! canned output, e.g. to test resolution on machines without pacman.
+
+use std::io;
+use std::process::{Command, Output, Stdio};
+
+/// Executes external commands on behalf of the dependency functions.
+///
+/// Implementations must return the complete process output; a non-zero exit status is not
+/// an error at this level. Only failures to start the program are reported as `io::Error`
+/// (`ErrorKind::NotFound` when the program does not exist).
+pub trait CommandRunner: Send + Sync {
+    /// What: Run a program to completion and capture its output.
+    ///
+    /// Inputs:
+    /// - `program`: Program name, looked up in `PATH` (e.g. `pacman`, `paru`).
+    /// - `args`: Command-line arguments.
+    ///
+    /// Output:
+    /// - The exit status together with captured stdout and stderr.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `io::Error` when the program cannot be started.
+    fn run(&self, program: &str, args: &[&str]) -> io::Result<Output>;
+}
+
+/// Runs commands on the local system with `std::process::Command`.
+///
+/// Sets `LC_ALL=C` and `LANG=C` for consistent locale-independent output, closes stdin and
+/// captures stdout and stderr.
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::deps::{CommandRunner, SystemRunner};
+///
+/// let output = SystemRunner.run("pacman", &["-Qq"])?;
+/// println!("{}", String::from_utf8_lossy(&output.stdout));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemRunner;
+
+impl CommandRunner for SystemRunner {
+    fn run(&self, program: &str, args: &[&str]) -> io::Result<Output> {
+        Command::new(program)
+            .args(args)
+            .env("LC_ALL", "C")
+            .env("LANG", "C")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+    }
+}
+
+/// Canned command output for tests, recording every invocation.
+///
+/// Commands are matched on the full command line (`program arg1 arg2`). Unknown programs fail
+/// to start with `ErrorKind::NotFound`; unknown command lines of a known program exit with
+/// status 1 and no output.
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub struct MockRunner {
+    /// Exit code, stdout and stderr by command line.
+    responses: std::collections::HashMap<String, (i32, String, String)>,
+    /// Command lines run so far, in order.
+    calls: std::sync::Mutex<Vec<String>>,
+}
+
+#[cfg(test)]
+impl MockRunner {
+    /// What: Answer a command line with successful output.
+    ///
+    /// Inputs:
+    /// - `command`: Full command line, e.g. `pacman -Si foo`.
+    /// - `stdout`: Output printed by the command.
+    pub fn ok(self, command: &str, stdout: &str) -> Self {
+        self.respond(command, 0, stdout, "")
+    }
+
+    /// What: Answer a command line with an exit code and output.
+    ///
+    /// Inputs:
+    /// - `command`: Full command line.
+    /// - `code`: Exit code.
+    /// - `stdout`: Output printed by the command.
+    /// - `stderr`: Error output printed by the command.
+    pub fn respond(mut self, command: &str, code: i32, stdout: &str, stderr: &str) -> Self {
+        self.responses.insert(
+            command.to_string(),
+            (code, stdout.to_string(), stderr.to_string()),
+        );
+        self
+    }
+
+    /// What: Command lines run so far, in order.
+    pub fn calls(&self) -> Vec<String> {
+        self.calls.lock().expect("calls lock").clone()
+    }
+}
+
+#[cfg(test)]
+impl CommandRunner for MockRunner {
+    fn run(&self, program: &str, args: &[&str]) -> io::Result<Output> {
+        use std::os::unix::process::ExitStatusExt;
+
+        let command = std::iter::once(program)
+            .chain(args.iter().copied())
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.calls.lock().expect("calls lock").push(command.clone());
+
+        let known_program = self
+            .responses
+            .keys()
+            .any(|line| line.split(' ').next() == Some(program));
+        if !known_program {
+            return Err(io::Error::from(io::ErrorKind::NotFound));
+        }
+        let (code, stdout, stderr) =
+            self.responses
+                .get(&command)
+                .cloned()
+                .unwrap_or((1, String::new(), String::new()));
+        Ok(Output {
+            status: std::process::ExitStatus::from_raw(code << 8),
+            stdout: stdout.into_bytes(),
+            stderr: stderr.into_bytes(),
+        })
+    }
+}
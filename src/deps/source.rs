@@ -3,10 +3,12 @@
 //! This module provides functions to determine where a dependency package comes from
 //! (official repository, AUR, or local) and to identify critical system packages.
 
-use crate::types::dependency::DependencySource;
+use crate::deps::runner::{CommandRunner, SystemRunner};
+use crate::types::dependency::{DependencySource, SourceConfig};
 use std::collections::HashSet;
 use std::hash::BuildHasher;
-use std::process::{Command, Stdio};
+#[cfg(feature = "index")]
+use std::sync::OnceLock;
 
 /// What: Infer the origin repository for a dependency currently under analysis.
 ///
@@ -18,13 +20,14 @@ use std::process::{Command, Stdio};
 /// - Returns a tuple with the determined `DependencySource` and a flag indicating core membership.
 ///
 /// Details:
-/// - Prefers inspecting `pacman -Qi` metadata when the package is installed; otherwise defaults to heuristics.
-/// - For installed packages: uses `pacman -Qi` to read the "Repository" field.
-/// - For uninstalled packages: uses `pacman -Si` to check if it exists in official repositories.
-/// - Handles local packages (repo = "local" or empty) specially.
-/// - Downgrades gracefully to official classifications when the repository field cannot be read.
+/// - Looks the package up in the sync databases with `pacman -Si` and reads its "Repository" field.
+/// - Any repository enabled in `pacman.conf` counts as official, so third-party repositories
+///   (e.g. `chaotic-aur`) are reported as `Official { repo }` (requires the `index` feature to
+///   read `pacman.conf`; without it any repository other than `local` is accepted).
+/// - Uninstalled packages missing from every sync database are reported as AUR.
+/// - Installed packages missing from every sync database are foreign and reported as `Local`.
+/// - Downgrades gracefully to official classifications when pacman cannot be queried.
 /// - Sets `LC_ALL=C` and `LANG=C` for consistent locale-independent output.
-/// - Returns reasonable defaults when pacman is unavailable (graceful degradation).
 ///
 /// # Example
 ///
@@ -40,108 +43,180 @@ pub fn determine_dependency_source<S: BuildHasher>(
     name: &str,
     installed: &HashSet<String, S>,
 ) -> (DependencySource, bool) {
-    if !installed.contains(name) {
-        // Not installed - check if it exists in official repos first
-        // Only default to AUR if it's not found in official repos
-        let output = Command::new("pacman")
-            .args(["-Si", name])
-            .env("LC_ALL", "C")
-            .env("LANG", "C")
-            .stdin(Stdio::null())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::null())
-            .output();
+    dependency_source(&SystemRunner, name, installed)
+}
 
-        if let Ok(output) = output
-            && output.status.success()
-        {
-            // Package exists in official repos - determine which repo
-            let text = String::from_utf8_lossy(&output.stdout);
-            for line in text.lines() {
-                if line.starts_with("Repository")
-                    && let Some(colon_pos) = line.find(':')
-                {
-                    let repo = line[colon_pos + 1..].trim().to_lowercase();
-                    let is_core = repo == "core";
-                    return (DependencySource::Official { repo }, is_core);
-                }
-            }
-            // Found in official repos but couldn't determine repo - assume extra
-            return (
+/// What: Infer the origin repository of a dependency, querying pacman through a runner.
+///
+/// Inputs:
+/// - `runner`: Executes `pacman -Si`.
+/// - `name`: Candidate dependency package name.
+/// - `installed`: Set of locally installed package names.
+///
+/// Output:
+/// - Source and core membership as described for `determine_dependency_source`.
+pub(super) fn dependency_source<S: BuildHasher>(
+    runner: &dyn CommandRunner,
+    name: &str,
+    installed: &HashSet<String, S>,
+) -> (DependencySource, bool) {
+    match sync_repository(runner, name) {
+        SyncLookup::Found(repo) => classify_repository(repo, configured_repos()),
+        SyncLookup::FoundWithoutRepo => {
+            // Found in sync repos but couldn't determine repo - assume extra
+            (
                 DependencySource::Official {
                     repo: "extra".to_string(),
                 },
                 false,
+            )
+        }
+        SyncLookup::NotFound if installed.contains(name) => {
+            tracing::debug!(
+                "Package {} is installed but not in any sync repository - treating as local",
+                name
             );
+            (DependencySource::Local, false)
+        }
+        SyncLookup::NotFound => {
+            // Not found in sync repos - this could be:
+            // 1. A binary/script provided by a package (not a package itself) - should be Missing
+            // 2. A virtual package (.so file) - should be filtered out earlier
+            // 3. A real AUR package - but we can't distinguish without checking AUR
+            //
+            // IMPORTANT: We don't try AUR here because:
+            // - Most dependencies are from official repos or are binaries/scripts
+            // - Trying AUR for every unknown dependency causes unnecessary API calls
+            // - Real AUR packages should be explicitly specified by the user, not discovered as dependencies
+            // - If it's truly an AUR dependency, it will be marked as Missing and the user can handle it
+            tracing::debug!(
+                "Package {} not found in official repos and not installed - will be marked as Missing (skipping AUR check)",
+                name
+            );
+            // Return AUR but the resolve logic should check if it exists before trying API
+            (DependencySource::Aur, false)
+        }
+        SyncLookup::Unavailable => {
+            tracing::debug!(
+                "Could not determine repository for {}, assuming official",
+                name
+            );
+            // Default: assume official repository (most packages are)
+            let is_core = is_system_package(name);
+            (
+                DependencySource::Official {
+                    repo: if is_core {
+                        "core".to_string()
+                    } else {
+                        "extra".to_string()
+                    },
+                },
+                is_core,
+            )
         }
-        // Not found in official repos - this could be:
-        // 1. A binary/script provided by a package (not a package itself) - should be Missing
-        // 2. A virtual package (.so file) - should be filtered out earlier
-        // 3. A real AUR package - but we can't distinguish without checking AUR
-        //
-        // IMPORTANT: We don't try AUR here because:
-        // - Most dependencies are from official repos or are binaries/scripts
-        // - Trying AUR for every unknown dependency causes unnecessary API calls
-        // - Real AUR packages should be explicitly specified by the user, not discovered as dependencies
-        // - If it's truly an AUR dependency, it will be marked as Missing and the user can handle it
-        tracing::debug!(
-            "Package {} not found in official repos and not installed - will be marked as Missing (skipping AUR check)",
-            name
-        );
-        // Return AUR but the resolve logic should check if it exists before trying API
-        return (DependencySource::Aur, false);
     }
+}
 
-    // Package is installed - check which repository it came from
-    let output = Command::new("pacman")
-        .args(["-Qi", name])
-        .env("LC_ALL", "C")
-        .env("LANG", "C")
-        .stdin(Stdio::null())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output();
+/// What: Outcome of looking a package up in the sync databases.
+enum SyncLookup {
+    /// Found in the named repository.
+    Found(String),
+    /// Found, but the output had no "Repository" field.
+    FoundWithoutRepo,
+    /// pacman ran but no sync repository has the package.
+    NotFound,
+    /// pacman could not be executed.
+    Unavailable,
+}
 
-    match output {
+/// What: Look a package up in the sync databases with `pacman -Si`.
+///
+/// Inputs:
+/// - `runner`: Executes the command.
+/// - `name`: Package name.
+///
+/// Output:
+/// - `SyncLookup` describing where the package was found.
+fn sync_repository(runner: &dyn CommandRunner, name: &str) -> SyncLookup {
+    match runner.run("pacman", &["-Si", name]) {
         Ok(output) if output.status.success() => {
             let text = String::from_utf8_lossy(&output.stdout);
-            // Look for "Repository" field in pacman -Qi output
-            for line in text.lines() {
-                if line.starts_with("Repository")
-                    && let Some(colon_pos) = line.find(':')
-                {
-                    let repo = line[colon_pos + 1..].trim().to_lowercase();
-                    let is_core = repo == "core";
-                    // Handle local packages specially
-                    if repo == "local" || repo.is_empty() {
-                        return (DependencySource::Local, false);
-                    }
-                    return (DependencySource::Official { repo }, is_core);
-                }
-            }
+            parse_repository_field(&text).map_or(SyncLookup::FoundWithoutRepo, SyncLookup::Found)
         }
-        _ => {
-            // Fallback: try pacman -Q to see if it's installed
-            // If we can't determine repo, assume it's from an official repo
-            tracing::debug!(
-                "Could not determine repository for {}, assuming official",
-                name
-            );
+        Ok(_) => SyncLookup::NotFound,
+        Err(_) => SyncLookup::Unavailable,
+    }
+}
+
+/// What: Extract the "Repository" field from `pacman -Si` output.
+///
+/// Inputs:
+/// - `text`: `pacman -Si` output.
+///
+/// Output:
+/// - Lowercased repository name of the first entry, if present.
+fn parse_repository_field(text: &str) -> Option<String> {
+    text.lines().find_map(|line| {
+        if !line.starts_with("Repository") {
+            return None;
         }
+        let (_, repo) = line.split_once(':')?;
+        Some(repo.trim().to_lowercase())
+    })
+}
+
+/// What: Classify a repository name reported by pacman.
+///
+/// Inputs:
+/// - `repo`: Repository name.
+/// - `configured`: Repositories enabled in `pacman.conf`, if known.
+///
+/// Output:
+/// - `Official { repo }` for enabled repositories, `Local` otherwise; `true` for `core`.
+///
+/// Details:
+/// - Without a known repository list, every repository except `local` is official.
+fn classify_repository(
+    repo: String,
+    configured: Option<&HashSet<String>>,
+) -> (DependencySource, bool) {
+    let enabled = !repo.is_empty()
+        && repo != "local"
+        && configured.is_none_or(|repos| repos.is_empty() || repos.contains(&repo));
+    if !enabled {
+        return (DependencySource::Local, false);
     }
+    let is_core = repo == "core";
+    (DependencySource::Official { repo }, is_core)
+}
+
+/// What: Repositories enabled in the system `pacman.conf`.
+///
+/// Inputs: None
+///
+/// Output:
+/// - Lowercased repository names, or `None` if `pacman.conf` cannot be read.
+///
+/// Details:
+/// - Parsed once with `index::parse_pacman_conf()` and reused for the process lifetime.
+#[cfg(feature = "index")]
+fn configured_repos() -> Option<&'static HashSet<String>> {
+    static REPOS: OnceLock<Option<HashSet<String>>> = OnceLock::new();
+    REPOS
+        .get_or_init(|| match crate::index::parse_pacman_conf(None) {
+            Ok(conf) => Some(conf.repo_names().map(str::to_lowercase).collect()),
+            Err(e) => {
+                tracing::debug!("pacman.conf unavailable, accepting any sync repository: {e}");
+                None
+            }
+        })
+        .as_ref()
+}
 
-    // Default: assume official repository (most installed packages are)
-    let is_core = is_system_package(name);
-    (
-        DependencySource::Official {
-            repo: if is_core {
-                "core".to_string()
-            } else {
-                "extra".to_string()
-            },
-        },
-        is_core,
-    )
+/// What: Repositories enabled in the system `pacman.conf` (unknown without `index`).
+#[cfg(not(feature = "index"))]
+const fn configured_repos() -> Option<&'static HashSet<String>> {
+    None
 }
 
 /// What: Identify whether a dependency belongs to a curated list of critical system packages.
@@ -154,7 +229,8 @@ pub fn determine_dependency_source<S: BuildHasher>(
 ///
 /// Details:
 /// - Used to highlight packages whose removal or downgrade should be discouraged.
-/// - Checks against a curated list of critical system packages.
+/// - Checks against the protected packages of `SourceConfig::default()`; group membership is
+///   not known here, see `is_system_package_with()`.
 /// - Uses exact string matching (case-sensitive).
 ///
 /// # Example
@@ -168,23 +244,40 @@ pub fn determine_dependency_source<S: BuildHasher>(
 /// ```
 #[must_use]
 pub fn is_system_package(name: &str) -> bool {
-    // List of critical system packages
-    let system_packages = [
-        "glibc",
-        "linux",
-        "systemd",
-        "pacman",
-        "bash",
-        "coreutils",
-        "gcc",
-        "binutils",
-        "filesystem",
-        "util-linux",
-        "shadow",
-        "sed",
-        "grep",
-    ];
-    system_packages.contains(&name)
+    is_system_package_with(name, &[], &SourceConfig::default())
+}
+
+/// What: Identify a system package using configured protected groups and packages.
+///
+/// Inputs:
+/// - `name`: Package name.
+/// - `groups`: Groups the package belongs to (the "Groups" field of `pacman -Qi`/`-Si`).
+/// - `config`: Protected groups and package names.
+///
+/// Output:
+/// - `true` if `name` is a protected package or any of `groups` is a protected group.
+///
+/// Details:
+/// - Uses exact string matching (case-sensitive).
+///
+/// # Example
+///
+/// ```
+/// use arch_toolkit::deps::{SourceConfig, is_system_package_with};
+///
+/// let mut config = SourceConfig::default();
+/// config.protected_groups.push("multilib-devel".to_string());
+///
+/// let groups = vec!["multilib-devel".to_string()];
+/// assert!(is_system_package_with("lib32-gcc-libs", &groups, &config));
+/// assert!(!is_system_package_with("lib32-gcc-libs", &[], &config));
+/// ```
+#[must_use]
+pub fn is_system_package_with(name: &str, groups: &[String], config: &SourceConfig) -> bool {
+    config.protected_packages.iter().any(|pkg| pkg == name)
+        || groups
+            .iter()
+            .any(|group| config.protected_groups.contains(group))
 }
 
 #[cfg(test)]
@@ -222,6 +315,38 @@ mod tests {
         assert!(!is_system_package(""));
     }
 
+    #[test]
+    /// What: Flag members of a custom protected group as system packages.
+    ///
+    /// Inputs:
+    /// - Default config extended with `multilib-devel`, and a config protecting nothing.
+    ///
+    /// Output:
+    /// - Members of the added group are protected, `base-devel` members are not (the default
+    ///   protects no groups); an empty config protects nothing.
+    fn test_is_system_package_with_custom_groups() {
+        let mut config = SourceConfig::default();
+        config.protected_groups.push("multilib-devel".to_string());
+        let multilib = vec!["multilib-devel".to_string()];
+        let base = vec!["base-devel".to_string()];
+
+        assert!(is_system_package_with("lib32-gcc-libs", &multilib, &config));
+        assert!(!is_system_package_with("make", &base, &config));
+        assert!(is_system_package_with("glibc", &[], &config));
+        assert!(!is_system_package_with("firefox", &[], &config));
+        assert!(!is_system_package_with(
+            "lib32-gcc-libs",
+            &multilib,
+            &SourceConfig::default()
+        ));
+
+        let empty = SourceConfig {
+            protected_groups: Vec::new(),
+            protected_packages: Vec::new(),
+        };
+        assert!(!is_system_package_with("glibc", &base, &empty));
+    }
+
     #[test]
     /// What: Test `determine_dependency_source` with installed core package.
     ///
@@ -389,6 +514,59 @@ mod tests {
         }
     }
 
+    #[test]
+    /// What: Classify repositories using the enabled repository list.
+    ///
+    /// Inputs:
+    /// - `pacman -Si` output from a third-party repository, plus `local` and disabled repos.
+    ///
+    /// Output:
+    /// - Enabled repos (including third-party) are official; `local` and unknown repos are local.
+    fn test_classify_repository_third_party() {
+        let text = "Repository      : chaotic-aur\nName            : paru-bin\n";
+        let repo = parse_repository_field(text).expect("repository field");
+        let configured: HashSet<String> = ["core", "extra", "chaotic-aur"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        assert_eq!(
+            classify_repository(repo, Some(&configured)),
+            (
+                DependencySource::Official {
+                    repo: "chaotic-aur".to_string()
+                },
+                false
+            )
+        );
+        assert_eq!(
+            classify_repository("core".to_string(), Some(&configured)),
+            (
+                DependencySource::Official {
+                    repo: "core".to_string()
+                },
+                true
+            )
+        );
+        assert_eq!(
+            classify_repository("multilib".to_string(), Some(&configured)),
+            (DependencySource::Local, false)
+        );
+        assert_eq!(
+            classify_repository("local".to_string(), None),
+            (DependencySource::Local, false)
+        );
+        assert_eq!(
+            classify_repository("custom".to_string(), None),
+            (
+                DependencySource::Official {
+                    repo: "custom".to_string()
+                },
+                false
+            )
+        );
+    }
+
     // Integration tests that require pacman - these are ignored by default
     #[test]
     #[ignore = "Requires pacman to be available"]
@@ -6,11 +6,28 @@
 use std::collections::HashSet;
 
 use crate::deps::parse::parse_dep_spec;
-use crate::error::Result;
-use crate::types::SrcinfoData;
+use crate::types::{ArchDependencies, SplitPackage, SrcinfoData};
 
 #[cfg(feature = "aur")]
 use crate::aur::utils::percent_encode;
+#[cfg(feature = "aur")]
+use crate::error::Result;
+
+/// Fields a `pkgname` block can override, in `SplitPackage` field order.
+const PACKAGE_FIELDS: [&str; 4] = ["depends", "optdepends", "provides", "conflicts"];
+
+/// What: Check whether a .SRCINFO entry names a shared library (virtual package).
+///
+/// Inputs:
+/// - `value`: Entry value, e.g. `libfoo.so=1-64`.
+///
+/// Output:
+/// - `true` for `.so` names with or without version suffixes.
+#[allow(clippy::case_sensitive_file_extension_comparisons)]
+fn is_shared_library(value: &str) -> bool {
+    let value_lower = value.to_lowercase();
+    value_lower.ends_with(".so") || value_lower.contains(".so.") || value_lower.contains(".so=")
+}
 
 /// What: Parse dependencies from .SRCINFO content.
 ///
@@ -25,8 +42,8 @@ use crate::aur::utils::percent_encode;
 /// - Handles array fields that can appear multiple times.
 /// - Filters out virtual packages (.so files).
 /// - Deduplicates dependencies (returns unique list).
-/// - Handles architecture-specific dependencies (e.g., `depends_x86_64`).
-#[allow(clippy::case_sensitive_file_extension_comparisons)]
+/// - Merges architecture-specific dependencies (e.g., `depends_x86_64`) into the main
+///   arrays; use `parse_srcinfo_arch_deps()` to keep them apart.
 #[must_use]
 pub fn parse_srcinfo_deps(srcinfo: &str) -> (Vec<String>, Vec<String>, Vec<String>, Vec<String>) {
     let mut depends = Vec::new();
@@ -51,12 +68,8 @@ pub fn parse_srcinfo_deps(srcinfo: &str) -> (Vec<String>, Vec<String>, Vec<Strin
             let key = key.trim();
             let value = value.trim();
 
-            // Filter out virtual packages (.so files)
-            let value_lower = value.to_lowercase();
-            if value_lower.ends_with(".so")
-                || value_lower.contains(".so.")
-                || value_lower.contains(".so=")
-            {
+            // Filter out virtual packages (.so files) and empty overrides (`depends = `)
+            if value.is_empty() || is_shared_library(value) {
                 continue;
             }
 
@@ -66,25 +79,17 @@ pub fn parse_srcinfo_deps(srcinfo: &str) -> (Vec<String>, Vec<String>, Vec<Strin
                 .map_or(key, |underscore_pos| &key[..underscore_pos]);
 
             match base_key {
-                "depends" => {
-                    if seen_depends.insert(value.to_string()) {
-                        depends.push(value.to_string());
-                    }
+                "depends" if seen_depends.insert(value.to_string()) => {
+                    depends.push(value.to_string());
                 }
-                "makedepends" => {
-                    if seen_makedepends.insert(value.to_string()) {
-                        makedepends.push(value.to_string());
-                    }
+                "makedepends" if seen_makedepends.insert(value.to_string()) => {
+                    makedepends.push(value.to_string());
                 }
-                "checkdepends" => {
-                    if seen_checkdepends.insert(value.to_string()) {
-                        checkdepends.push(value.to_string());
-                    }
+                "checkdepends" if seen_checkdepends.insert(value.to_string()) => {
+                    checkdepends.push(value.to_string());
                 }
-                "optdepends" => {
-                    if seen_optdepends.insert(value.to_string()) {
-                        optdepends.push(value.to_string());
-                    }
+                "optdepends" if seen_optdepends.insert(value.to_string()) => {
+                    optdepends.push(value.to_string());
                 }
                 _ => {}
             }
@@ -94,6 +99,62 @@ pub fn parse_srcinfo_deps(srcinfo: &str) -> (Vec<String>, Vec<String>, Vec<Strin
     (depends, makedepends, checkdepends, optdepends)
 }
 
+/// What: Parse dependencies from .SRCINFO content, keeping architecture-specific arrays apart.
+///
+/// Inputs:
+/// - `srcinfo`: Raw .SRCINFO file content.
+///
+/// Output:
+/// - Returns `ArchDependencies` with the generic arrays and one entry per architecture.
+///
+/// Details:
+/// - `depends`, `makedepends`, `checkdepends` and `optdepends` go to `generic`;
+///   `<field>_<arch>` entries go to `by_arch[<arch>]`.
+/// - Filters out virtual packages (.so files) and deduplicates each array.
+/// - Use `ArchDependencies::deps_for_arch()` to get the runtime dependencies for one
+///   architecture.
+#[must_use]
+pub fn parse_srcinfo_arch_deps(srcinfo: &str) -> ArchDependencies {
+    let mut deps = ArchDependencies::default();
+
+    for line in srcinfo.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        let value = value.trim();
+        if value.is_empty() || is_shared_library(value) {
+            continue;
+        }
+
+        let arrays = match key.split_once('_') {
+            None => &mut deps.generic,
+            Some((field, arch))
+                if !arch.is_empty()
+                    && matches!(
+                        field,
+                        "depends" | "makedepends" | "checkdepends" | "optdepends"
+                    ) =>
+            {
+                deps.by_arch.entry(arch.to_string()).or_default()
+            }
+            Some(_) => continue,
+        };
+        let field = key.split_once('_').map_or(key, |(field, _)| field);
+        if let Some(target) = arrays.field_mut(field)
+            && !target.iter().any(|existing| existing == value)
+        {
+            target.push(value.to_string());
+        }
+    }
+
+    deps
+}
+
 /// What: Parse conflicts from .SRCINFO content.
 ///
 /// Inputs:
@@ -107,7 +168,6 @@ pub fn parse_srcinfo_deps(srcinfo: &str) -> (Vec<String>, Vec<String>, Vec<Strin
 /// - Handles array fields that can appear multiple times.
 /// - Filters out virtual packages (.so files) and extracts package names from version constraints.
 /// - Deduplicates conflicts (returns unique list).
-#[allow(clippy::case_sensitive_file_extension_comparisons)]
 #[must_use]
 pub fn parse_srcinfo_conflicts(srcinfo: &str) -> Vec<String> {
     let mut conflicts = Vec::new();
@@ -131,11 +191,7 @@ pub fn parse_srcinfo_conflicts(srcinfo: &str) -> Vec<String> {
 
             if base_key == "conflicts" {
                 // Filter out virtual packages (.so files)
-                let value_lower = value.to_lowercase();
-                if value_lower.ends_with(".so")
-                    || value_lower.contains(".so.")
-                    || value_lower.contains(".so=")
-                {
+                if is_shared_library(value) {
                     continue;
                 }
                 // Extract package name (remove version constraints if present)
@@ -162,9 +218,13 @@ pub fn parse_srcinfo_conflicts(srcinfo: &str) -> Vec<String> {
 /// - Parses all fields from .SRCINFO format including pkgbase, pkgname, pkgver, pkgrel.
 /// - Extracts all dependency types (depends, makedepends, checkdepends, optdepends).
 /// - Extracts conflicts, provides, and replaces arrays.
-/// - For split packages (multiple pkgname), uses the first pkgname found.
+/// - Top-level arrays merge the pkgbase block and every pkgname block.
+/// - `packages` holds one `SplitPackage` per pkgname; each inherits depends, optdepends,
+///   provides, and conflicts from the pkgbase block unless its own block overrides them.
+/// - `pkgname` is the first package name, for single-package callers.
 /// - Handles architecture-specific dependencies by merging into main arrays.
 /// - Returns default `SrcinfoData` with empty fields if content is malformed.
+/// - Use `SrcinfoData::to_string()` to serialize the result back to `.SRCINFO` format.
 #[must_use]
 pub fn parse_srcinfo(content: &str) -> SrcinfoData {
     let mut data = SrcinfoData::default();
@@ -177,6 +237,7 @@ pub fn parse_srcinfo(content: &str) -> SrcinfoData {
     data.checkdepends = checkdepends;
     data.optdepends = optdepends;
     data.conflicts = parse_srcinfo_conflicts(content);
+    data.packages = parse_split_packages(content);
 
     // Parse other fields
     let mut seen_provides = HashSet::new();
@@ -198,38 +259,32 @@ pub fn parse_srcinfo(content: &str) -> SrcinfoData {
                 .map_or(key, |underscore_pos| &key[..underscore_pos]);
 
             match base_key {
-                "pkgbase" => {
-                    if data.pkgbase.is_empty() {
+                "pkgbase"
+                    if data.pkgbase.is_empty() => {
                         data.pkgbase = value.to_string();
                     }
-                }
-                "pkgname" => {
+                "pkgname"
                     // For split packages, use the first pkgname found
-                    if !pkgname_found {
+                    if !pkgname_found => {
                         data.pkgname = value.to_string();
                         pkgname_found = true;
                     }
-                }
-                "pkgver" => {
-                    if data.pkgver.is_empty() {
+                "pkgver"
+                    if data.pkgver.is_empty() => {
                         data.pkgver = value.to_string();
                     }
-                }
-                "pkgrel" => {
-                    if data.pkgrel.is_empty() {
+                "pkgrel"
+                    if data.pkgrel.is_empty() => {
                         data.pkgrel = value.to_string();
                     }
-                }
-                "provides" => {
-                    if seen_provides.insert(value.to_string()) {
+                "provides"
+                    if !value.is_empty() && seen_provides.insert(value.to_string()) => {
                         data.provides.push(value.to_string());
                     }
-                }
-                "replaces" => {
-                    if seen_replaces.insert(value.to_string()) {
+                "replaces"
+                    if !value.is_empty() && seen_replaces.insert(value.to_string()) => {
                         data.replaces.push(value.to_string());
                     }
-                }
                 _ => {}
             }
         }
@@ -238,6 +293,95 @@ pub fn parse_srcinfo(content: &str) -> SrcinfoData {
     data
 }
 
+/// What: Parse the per-package blocks of .SRCINFO content.
+///
+/// Inputs:
+/// - `content`: Raw .SRCINFO file content.
+///
+/// Output:
+/// - One `SplitPackage` per `pkgname` line, in file order.
+///
+/// Details:
+/// - Lines before the first `pkgname` form the pkgbase block, whose values are inherited.
+/// - A field appearing in a package block replaces the inherited values entirely; an empty
+///   value (`depends = `) overrides the field to nothing.
+/// - Values are filtered and deduplicated like the top-level arrays.
+fn parse_split_packages(content: &str) -> Vec<SplitPackage> {
+    let mut base: [Option<Vec<String>>; 4] = Default::default();
+    let mut blocks: Vec<(String, [Option<Vec<String>>; 4])> = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        let value = value.trim();
+
+        if key == "pkgname" {
+            blocks.push((value.to_string(), Default::default()));
+            continue;
+        }
+        let base_key = key
+            .find('_')
+            .map_or(key, |underscore_pos| &key[..underscore_pos]);
+        let Some(field) = PACKAGE_FIELDS.iter().position(|name| *name == base_key) else {
+            continue;
+        };
+
+        let fields = blocks.last_mut().map_or(&mut base, |(_, fields)| fields);
+        let values = fields[field].get_or_insert_with(Vec::new);
+        if let Some(value) = package_field_value(base_key, value)
+            && !values.contains(&value)
+        {
+            values.push(value);
+        }
+    }
+
+    blocks
+        .into_iter()
+        .map(|(name, fields)| {
+            let effective = |field: usize| {
+                fields[field]
+                    .as_ref()
+                    .or(base[field].as_ref())
+                    .cloned()
+                    .unwrap_or_default()
+            };
+            SplitPackage {
+                name,
+                depends: effective(0),
+                optdepends: effective(1),
+                provides: effective(2),
+                conflicts: effective(3),
+            }
+        })
+        .collect()
+}
+
+/// What: Normalize a package field value the same way as the top-level arrays.
+///
+/// Inputs:
+/// - `key`: Field name without architecture suffix.
+/// - `value`: Trimmed entry value.
+///
+/// Output:
+/// - `None` for empty values and shared libraries in dependency fields; conflicts are reduced
+///   to the package name.
+fn package_field_value(key: &str, value: &str) -> Option<String> {
+    if value.is_empty() || (key != "provides" && is_shared_library(value)) {
+        return None;
+    }
+    if key == "conflicts" {
+        let name = parse_dep_spec(value).name;
+        return (!name.is_empty()).then_some(name);
+    }
+    Some(value.to_string())
+}
+
 /// What: Fetch .SRCINFO content for an AUR package using async HTTP.
 ///
 /// Inputs:
@@ -249,7 +393,7 @@ pub fn parse_srcinfo(content: &str) -> SrcinfoData {
 ///
 /// # Errors
 /// - Returns `Err` when HTTP request fails (network error or client error)
-/// - Returns `Err` when HTTP response status is not successful
+/// - Returns `Err(ArchToolkitError::Http)` when the server answers with a 4xx/5xx status
 /// - Returns `Err` when response body cannot be read
 /// - Returns `Err` when response is empty or contains HTML error page
 /// - Returns `Err` when response does not appear to be valid .SRCINFO format
@@ -257,6 +401,7 @@ pub fn parse_srcinfo(content: &str) -> SrcinfoData {
 /// Details:
 /// - Uses reqwest for async fetching with built-in timeout handling.
 /// - Validates that the response is not empty, not HTML, and contains .SRCINFO format markers.
+/// - Bypasses `ArchClient` rate limiting, retries, and caching; prefer `Aur::srcinfo()`.
 /// - Requires the `aur` feature to be enabled.
 #[cfg(feature = "aur")]
 pub async fn fetch_srcinfo(client: &reqwest::Client, name: &str) -> Result<String> {
@@ -273,13 +418,7 @@ pub async fn fetch_srcinfo(client: &reqwest::Client, name: &str) -> Result<Strin
         .send()
         .await
         .map_err(ArchToolkitError::Network)?;
-
-    if !response.status().is_success() {
-        return Err(ArchToolkitError::InvalidInput(format!(
-            "HTTP request failed with status: {}",
-            response.status()
-        )));
-    }
+    let response = crate::client::check_status(response)?;
 
     let text = response.text().await.map_err(ArchToolkitError::Network)?;
 
@@ -379,6 +518,37 @@ depends_aarch64 = arm-dep
         assert!(depends.contains(&"arm-dep".to_string()));
     }
 
+    #[test]
+    fn test_parse_srcinfo_arch_deps() {
+        let srcinfo = "pkgbase = test-package
+\tarch = x86_64
+\tarch = aarch64
+\tmakedepends = cmake
+\tmakedepends_aarch64 = clang
+\tdepends = glibc
+\tdepends_x86_64 = intel-media-driver
+\tdepends_x86_64 = libfoo.so
+\tdepends_aarch64 = raspberrypi-firmware
+\tdepends_aarch64 = glibc
+pkgname = test-package
+";
+
+        let deps = parse_srcinfo_arch_deps(srcinfo);
+        assert_eq!(deps.generic.depends, vec!["glibc"]);
+        assert_eq!(deps.generic.makedepends, vec!["cmake"]);
+        assert_eq!(
+            deps.by_arch.keys().collect::<Vec<_>>(),
+            vec!["aarch64", "x86_64"]
+        );
+        assert_eq!(deps.by_arch["x86_64"].depends, vec!["intel-media-driver"]);
+        assert_eq!(
+            deps.deps_for_arch("aarch64"),
+            vec!["glibc", "raspberrypi-firmware"]
+        );
+        assert_eq!(deps.for_arch("aarch64").makedepends, vec!["cmake", "clang"]);
+        assert_eq!(deps.deps_for_arch("riscv64"), vec!["glibc"]);
+    }
+
     #[test]
     fn test_parse_srcinfo_conflicts() {
         let srcinfo = r"
@@ -470,6 +640,42 @@ replaces = replaced-pkg
         assert!(data.replaces.contains(&"replaced-pkg".to_string()));
     }
 
+    #[test]
+    fn test_srcinfo_round_trip() {
+        let srcinfo = "pkgbase = example-base
+\tpkgdesc = Example package
+\tpkgver = 1.2.3
+\tpkgrel = 2
+\tcheckdepends = pytest
+\tmakedepends = cargo
+\tmakedepends = git
+\tdepends = glibc
+\tdepends = gcc-libs>=13
+\tdepends = openssl
+\toptdepends = bash-completion: completions
+\tprovides = example=1.2.3
+\tconflicts = example-git
+\treplaces = old-example
+
+pkgname = example
+";
+
+        let data = parse_srcinfo(srcinfo);
+        assert_eq!(data.depends, vec!["glibc", "gcc-libs>=13", "openssl"]);
+
+        let mut serialized = data.to_string();
+        assert!(serialized.starts_with("pkgbase = example-base\n\tpkgver = 1.2.3\n\tpkgrel = 2\n"));
+        assert!(serialized.ends_with("\n\npkgname = example\n"));
+        assert_eq!(parse_srcinfo(&serialized), data);
+
+        // Edit a field and round-trip again
+        let mut bumped = data;
+        bumped.pkgver = "1.2.4".to_string();
+        bumped.depends.push("zlib".to_string());
+        serialized = bumped.to_string();
+        assert_eq!(parse_srcinfo(&serialized), bumped);
+    }
+
     #[test]
     fn test_parse_srcinfo_split_packages() {
         let srcinfo = r"
@@ -486,6 +692,60 @@ pkgrel = 1
         assert_eq!(data.pkgbase, "split-package");
     }
 
+    #[test]
+    fn test_parse_srcinfo_split_package_overrides() {
+        let srcinfo = "pkgbase = foo
+\tpkgdesc = Foo tool and documentation
+\tpkgver = 2.1.0
+\tpkgrel = 1
+\tarch = x86_64
+\tmakedepends = cmake
+\tmakedepends = doxygen
+\tdepends = glibc
+\tdepends = libfoo.so=2-64
+\tdepends = zlib
+\toptdepends = foo-docs: documentation
+\tprovides = libfoo.so=2-64
+\tconflicts = foo-git>=2
+
+pkgname = foo
+
+pkgname = foo-docs
+\tpkgdesc = Documentation for foo
+\tarch = any
+\tdepends = 
+\toptdepends = 
+\tprovides = foo-documentation
+\tconflicts = 
+";
+
+        let data = parse_srcinfo(srcinfo);
+        assert_eq!(data.pkgname, "foo");
+        assert_eq!(data.depends, vec!["glibc", "zlib"]);
+        assert_eq!(data.provides, vec!["libfoo.so=2-64", "foo-documentation"]);
+        assert_eq!(
+            data.packages,
+            vec![
+                SplitPackage {
+                    name: "foo".to_string(),
+                    depends: vec!["glibc".to_string(), "zlib".to_string()],
+                    optdepends: vec!["foo-docs: documentation".to_string()],
+                    provides: vec!["libfoo.so=2-64".to_string()],
+                    conflicts: vec!["foo-git".to_string()],
+                },
+                SplitPackage {
+                    name: "foo-docs".to_string(),
+                    provides: vec!["foo-documentation".to_string()],
+                    ..SplitPackage::default()
+                },
+            ]
+        );
+
+        let serialized = data.to_string();
+        assert!(serialized.contains("\npkgname = foo-docs\n\tdepends = \n"));
+        assert_eq!(parse_srcinfo(&serialized), data);
+    }
+
     #[test]
     fn test_parse_srcinfo_comments_and_blank_lines() {
         let srcinfo = r"
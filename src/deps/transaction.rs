@@ -0,0 +1,333 @@
+//! Dry-run planning of install/remove transactions.
+
+use std::collections::{BTreeSet, HashSet};
+
+use crate::deps::{DependencyResolver, ReverseDependencyAnalyzer};
+use crate::error::Result;
+use crate::types::dependency::{
+    DependencyResolution, DependencyStatus, PackageRef, ReverseDependencyReport, TransactionPlan,
+};
+
+/// What: Plan a transaction without changing the system.
+///
+/// Inputs:
+/// - `install`: Packages to install or upgrade.
+/// - `remove`: Packages to remove.
+///
+/// Output:
+/// - `TransactionPlan` with the dependencies to pull in, the removals, the installed packages
+///   blocking them, the merged conflicts, and the packages that could not be found.
+///
+/// Details:
+/// - Runs `DependencyResolver::resolve()` on `install` and `ReverseDependencyAnalyzer::analyze()`
+///   on `remove` with default settings; see `plan_transaction_with()` for custom ones.
+///
+/// # Errors
+/// - Returns errors from `DependencyResolver::resolve()` or `ReverseDependencyAnalyzer::analyze()`
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::deps::plan_transaction;
+/// use arch_toolkit::{PackageRef, PackageSource};
+///
+/// let official = |name: &str| PackageRef {
+///     name: name.into(),
+///     version: String::new(),
+///     source: PackageSource::Official {
+///         repo: "extra".into(),
+///         arch: "x86_64".into(),
+///     },
+/// };
+///
+/// let plan = plan_transaction(&[official("pipewire-pulse")], &[official("pulseaudio")])?;
+/// println!(
+///     "{} to install, {} to upgrade, {} blocking removal, {} conflicts",
+///     plan.to_install.len(),
+///     plan.to_upgrade.len(),
+///     plan.blocked_removals.len(),
+///     plan.conflicts.len()
+/// );
+/// # Ok::<(), arch_toolkit::error::ArchToolkitError>(())
+/// ```
+pub fn plan_transaction(install: &[PackageRef], remove: &[PackageRef]) -> Result<TransactionPlan> {
+    plan_transaction_with(
+        &DependencyResolver::new(),
+        &ReverseDependencyAnalyzer::new(),
+        install,
+        remove,
+    )
+}
+
+/// What: Plan a transaction with a configured resolver and analyzer.
+///
+/// Inputs:
+/// - `resolver`: Resolver for the dependencies of `install`.
+/// - `analyzer`: Analyzer for the dependents of `remove`.
+/// - `install`: Packages to install or upgrade.
+/// - `remove`: Packages to remove.
+///
+/// Output:
+/// - `TransactionPlan` like `plan_transaction()`.
+///
+/// Details:
+/// - `to_install`/`to_upgrade` hold the `ToInstall`/`ToUpgrade` dependencies of `install`; the
+///   requested packages themselves are not listed.
+/// - `blocked_removals` are dependents of `remove` that are not removal targets themselves.
+/// - Resolver conflicts with a package that is being removed are dropped, since the removal
+///   resolves them; dependencies of `install` that are being removed are added as conflicts.
+///
+/// # Errors
+/// - Returns errors from `DependencyResolver::resolve()` or `ReverseDependencyAnalyzer::analyze()`,
+///   e.g. `PacmanUnavailable` when the removal targets cannot be queried; a plan without
+///   removals is never returned for a failed reverse dependency check
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::deps::{
+///     DependencyResolver, ResolverConfig, ReverseDependencyAnalyzer, plan_transaction_with,
+/// };
+///
+/// let resolver = DependencyResolver::with_config(ResolverConfig {
+///     include_optdepends: true,
+///     ..ResolverConfig::default()
+/// });
+/// let plan = plan_transaction_with(&resolver, &ReverseDependencyAnalyzer::new(), &[], &[])?;
+/// assert!(plan.conflicts.is_empty());
+/// # Ok::<(), arch_toolkit::error::ArchToolkitError>(())
+/// ```
+pub fn plan_transaction_with(
+    resolver: &DependencyResolver,
+    analyzer: &ReverseDependencyAnalyzer,
+    install: &[PackageRef],
+    remove: &[PackageRef],
+) -> Result<TransactionPlan> {
+    let resolution = resolver.resolve(install)?;
+    let report = analyzer.analyze(remove)?;
+    Ok(merge_plan(resolution, report, remove))
+}
+
+/// What: Merge a forward resolution and a removal report into a transaction plan.
+///
+/// Inputs:
+/// - `resolution`: Dependencies of the packages to install.
+/// - `report`: Dependents of the packages to remove.
+/// - `remove`: Packages to remove.
+///
+/// Output:
+/// - The combined `TransactionPlan`.
+fn merge_plan(
+    resolution: DependencyResolution,
+    report: ReverseDependencyReport,
+    remove: &[PackageRef],
+) -> TransactionPlan {
+    let mut removed = HashSet::new();
+    let to_remove: Vec<String> = remove
+        .iter()
+        .map(|pkg| pkg.name.clone())
+        .filter(|name| removed.insert(name.clone()))
+        .collect();
+
+    let mut conflicts: BTreeSet<String> = resolution
+        .conflicts
+        .into_iter()
+        .filter(|name| !removed.contains(name))
+        .collect();
+
+    let mut missing: BTreeSet<String> = resolution.missing.into_iter().collect();
+    let mut plan = TransactionPlan::default();
+    for dep in resolution.dependencies {
+        if removed.contains(&dep.name) {
+            if !matches!(dep.status, DependencyStatus::Conflict { .. }) {
+                conflicts.insert(dep.name);
+            }
+            continue;
+        }
+        match dep.status {
+            DependencyStatus::ToInstall => plan.to_install.push(dep),
+            DependencyStatus::ToUpgrade { .. } => plan.to_upgrade.push(dep),
+            DependencyStatus::Missing => {
+                missing.insert(dep.name);
+            }
+            DependencyStatus::Installed { .. } | DependencyStatus::Conflict { .. } => {}
+        }
+    }
+
+    plan.blocked_removals = report
+        .dependents
+        .into_iter()
+        .filter(|dep| !removed.contains(&dep.name))
+        .collect();
+    plan.to_remove = to_remove;
+    plan.conflicts = conflicts.into_iter().collect();
+    plan.missing = missing.into_iter().collect();
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deps::runner::MockRunner;
+    use crate::fixtures::{dep, extra_ref};
+    use crate::types::dependency::ResolverConfig;
+
+    /// What: Runner answering the pacman queries of a transaction installing `cq-app`.
+    ///
+    /// Details:
+    /// - `glibc` 2.41, `cq-old` and `legacy-tool` are installed; `legacy-tool` requires `glibc`.
+    /// - `cq-app` depends on `cq-lib` and `glibc>=2.40` and conflicts with `cq-old`.
+    fn transaction_runner() -> MockRunner {
+        MockRunner::default()
+            .ok("pacman -Qq", "glibc\ncq-old\nlegacy-tool\n")
+            .ok(
+                "pacman -Qi",
+                "Name            : glibc\nProvides        : None\n",
+            )
+            .respond("pacman -Qu", 1, "", "")
+            .ok("pacman -Q glibc", "glibc 2.41-1\n")
+            .ok(
+                "pacman -Si cq-app",
+                "Repository      : extra\nName            : cq-app\nVersion         : 1.0-1\nDepends On      : cq-lib  glibc>=2.40\nConflicts With  : cq-old\n",
+            )
+            .ok(
+                "pacman -Si cq-lib",
+                "Repository      : extra\nName            : cq-lib\nVersion         : 2.0-1\nDepends On      : None\nConflicts With  : None\n",
+            )
+            .ok(
+                "pacman -Qi glibc",
+                "Name            : glibc\nVersion         : 2.41-1\nRequired By     : legacy-tool\n",
+            )
+            .ok(
+                "pacman -Qi cq-old",
+                "Name            : cq-old\nVersion         : 0.9-1\nRequired By     : None\n",
+            )
+            .ok(
+                "pacman -Qi legacy-tool",
+                "Name            : legacy-tool\nVersion         : 1.0-1\nRequired By     : None\n",
+            )
+    }
+
+    #[test]
+    /// What: Plan installing `cq-app` while removing `cq-old` and `glibc`.
+    ///
+    /// Inputs:
+    /// - Install `cq-app`; remove `cq-old` (which `cq-app` conflicts with) twice and `glibc`.
+    ///
+    /// Output:
+    /// - `cq-lib` to install, deduplicated removals, `legacy-tool` blocking the removal of
+    ///   `glibc`, and `glibc` as the only conflict since removing `cq-old` resolves its conflict.
+    fn test_plan_transaction_with_mock_runner() {
+        let resolver = DependencyResolver::with_config(ResolverConfig {
+            max_depth: 1,
+            ..ResolverConfig::default()
+        })
+        .with_runner(transaction_runner());
+        let analyzer = ReverseDependencyAnalyzer::new().with_runner(transaction_runner());
+
+        let plan = plan_transaction_with(
+            &resolver,
+            &analyzer,
+            &[extra_ref("cq-app")],
+            &[extra_ref("cq-old"), extra_ref("glibc"), extra_ref("cq-old")],
+        )
+        .expect("planning succeeds");
+
+        let names = |deps: &[crate::types::dependency::Dependency]| {
+            deps.iter().map(|dep| dep.name.clone()).collect::<Vec<_>>()
+        };
+        assert_eq!(names(&plan.to_install), ["cq-lib"]);
+        assert!(plan.to_upgrade.is_empty());
+        assert_eq!(plan.to_remove, ["cq-old", "glibc"]);
+        assert_eq!(names(&plan.blocked_removals), ["legacy-tool"]);
+        assert_eq!(plan.conflicts, ["glibc"]);
+        assert!(plan.missing.is_empty());
+    }
+
+    #[test]
+    /// What: Report packages that cannot be found instead of dropping them.
+    ///
+    /// Inputs:
+    /// - Install `cq-app`, depending on `cq-lib`, and the unknown `cq-nope`.
+    ///
+    /// Output:
+    /// - `cq-lib` to install; `cq-nope` listed as missing.
+    fn test_plan_transaction_reports_missing() {
+        let runner = MockRunner::default()
+            .ok("pacman -Qq", "")
+            .ok("pacman -Qi", "")
+            .respond("pacman -Qu", 1, "", "")
+            .ok(
+                "pacman -Si cq-app",
+                "Repository      : extra\nName            : cq-app\nVersion         : 1.0-1\nDepends On      : cq-lib\nConflicts With  : None\n",
+            )
+            .ok(
+                "pacman -Si cq-lib",
+                "Repository      : extra\nName            : cq-lib\nVersion         : 2.0-1\nDepends On      : None\nConflicts With  : None\n",
+            );
+        let resolver = DependencyResolver::new().with_runner(runner);
+
+        let plan = plan_transaction_with(
+            &resolver,
+            &ReverseDependencyAnalyzer::new().with_runner(MockRunner::default()),
+            &[extra_ref("cq-app"), extra_ref("cq-nope")],
+            &[],
+        )
+        .expect("planning succeeds");
+
+        let to_install: Vec<&str> = plan
+            .to_install
+            .iter()
+            .map(|dep| dep.name.as_str())
+            .collect();
+        assert_eq!(to_install, ["cq-lib"]);
+        assert_eq!(plan.missing, ["cq-nope"]);
+    }
+
+    #[test]
+    /// What: Fail planning when the removal targets cannot be queried.
+    ///
+    /// Inputs:
+    /// - Remove `cq-lib` with an analyzer whose runner has no pacman.
+    ///
+    /// Output:
+    /// - `PacmanUnavailable` instead of a plan without removals or blockers.
+    fn test_plan_transaction_fails_without_pacman() {
+        let err = plan_transaction_with(
+            &DependencyResolver::new().with_runner(MockRunner::default()),
+            &ReverseDependencyAnalyzer::new().with_runner(MockRunner::default()),
+            &[],
+            &[extra_ref("cq-lib")],
+        )
+        .expect_err("the reverse dependency check must run");
+
+        assert!(matches!(
+            err,
+            crate::error::ArchToolkitError::PacmanUnavailable { .. }
+        ));
+    }
+
+    #[test]
+    /// What: Merge missing names and dependencies with `Missing` status.
+    ///
+    /// Inputs:
+    /// - Resolution listing `ghost` as missing and `phantom`/`ghost` with `Missing` status.
+    ///
+    /// Output:
+    /// - `missing` holds `ghost` and `phantom` once each; nothing is to be installed.
+    fn test_merge_plan_collects_missing() {
+        let resolution = DependencyResolution {
+            dependencies: vec![
+                dep("phantom", DependencyStatus::Missing),
+                dep("ghost", DependencyStatus::Missing),
+            ],
+            missing: vec!["ghost".to_string()],
+            ..DependencyResolution::default()
+        };
+
+        let plan = merge_plan(resolution, ReverseDependencyReport::default(), &[]);
+
+        assert!(plan.to_install.is_empty());
+        assert_eq!(plan.missing, ["ghost", "phantom"]);
+    }
+}
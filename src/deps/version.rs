@@ -6,6 +6,8 @@
 
 use std::cmp::Ordering;
 
+use crate::deps::parse::parse_dep_spec;
+
 /// What: Normalize a version string by stripping the pkgrel suffix.
 ///
 /// Inputs:
@@ -43,14 +45,15 @@ fn normalize_version(version: &str) -> String {
 /// - Returns `Ordering::Greater` if `a > b`.
 ///
 /// Details:
-/// - Splits versions on `.` and `-` into segments.
-/// - Compares segments pairwise:
-///   - If both are numeric: compares as numbers.
-///   - If one is numeric and one is text: numeric < text (pacman behavior).
-///   - If both are text: lexicographic comparison.
-/// - Missing segments are treated as "0".
-/// - First non-equal segment determines the result.
-/// - This algorithm matches pacman's `alpm_version_cmp` behavior.
+/// - Implements pacman's `vercmp` (`alpm_pkg_vercmp`) on `[epoch:]pkgver[-pkgrel]` strings.
+/// - Epoch takes precedence: a higher epoch always wins; a missing epoch is `0`.
+/// - `pkgver` is then compared segment by segment (see `rpmvercmp()`):
+///   - Segments are runs of digits or letters; other characters separate them.
+///   - Numeric segments compare as numbers and are newer than alphabetic segments.
+///   - Alphabetic segments compare lexicographically.
+///   - A trailing alphabetic segment is older than nothing (`1.0a < 1.0`), any other extra
+///     segment is newer (`1.0.0 > 1.0`).
+/// - `pkgrel` breaks ties only when both versions have one (`1.0-2 > 1.0-1`, `1.0 == 1.0-1`).
 ///
 /// # Example
 ///
@@ -60,99 +63,163 @@ fn normalize_version(version: &str) -> String {
 ///
 /// assert_eq!(compare_versions("1.2.3", "1.2.4"), Ordering::Less);
 /// assert_eq!(compare_versions("2.0.0", "1.9.9"), Ordering::Greater);
-/// assert_eq!(compare_versions("1.0", "1.0.0"), Ordering::Equal);
+/// assert_eq!(compare_versions("1.0.0", "1.0"), Ordering::Greater);
+/// assert_eq!(compare_versions("1:1.0", "2.0"), Ordering::Greater);
+/// assert_eq!(compare_versions("1.0-2", "1.0-1"), Ordering::Greater);
 /// assert_eq!(compare_versions("1.2.3alpha", "1.2.3beta"), Ordering::Less);
 /// assert_eq!(compare_versions("1.2.3", "1.2.3alpha"), Ordering::Greater);
 /// ```
 #[must_use]
 pub fn compare_versions(a: &str, b: &str) -> Ordering {
-    let a_normalized = normalize_version(a);
-    let b_normalized = normalize_version(b);
-
-    let a_parts: Vec<&str> = a_normalized.split(['.', '-']).collect();
-    let b_parts: Vec<&str> = b_normalized.split(['.', '-']).collect();
-    let len = a_parts.len().max(b_parts.len());
-
-    for idx in 0..len {
-        let a_seg = a_parts.get(idx).copied().unwrap_or("0");
-        let b_seg = b_parts.get(idx).copied().unwrap_or("0");
-
-        // Extract numeric prefix from each segment
-        let a_num_end = a_seg
-            .char_indices()
-            .find(|(_, c)| !c.is_ascii_digit())
-            .map_or(a_seg.len(), |(i, _)| i);
-        let b_num_end = b_seg
-            .char_indices()
-            .find(|(_, c)| !c.is_ascii_digit())
-            .map_or(b_seg.len(), |(i, _)| i);
-
-        let a_num_str = &a_seg[..a_num_end];
-        let b_num_str = &b_seg[..b_num_end];
-        let a_suffix = &a_seg[a_num_end..];
-        let b_suffix = &b_seg[b_num_end..];
-
-        // Try to parse numeric prefixes
-        match (a_num_str.parse::<i64>(), b_num_str.parse::<i64>()) {
-            (Ok(a_num), Ok(b_num)) => {
-                // Both have numeric prefixes, compare numbers first
-                match a_num.cmp(&b_num) {
-                    Ordering::Equal => {
-                        // Numeric prefixes equal, compare suffixes
-                        // Empty suffix > non-empty suffix (pacman: "3" > "3alpha")
-                        match (a_suffix.is_empty(), b_suffix.is_empty()) {
-                            (true, true) => {}                         // Both empty, continue
-                            (true, false) => return Ordering::Greater, // "3" > "3alpha"
-                            (false, true) => return Ordering::Less,    // "3alpha" < "3"
-                            (false, false) => {
-                                // Both have suffixes, compare lexicographically
-                                match a_suffix.cmp(b_suffix) {
-                                    Ordering::Equal => {}
-                                    ord => return ord,
-                                }
-                            }
+    if a == b {
+        return Ordering::Equal;
+    }
+    let (a_epoch, a_ver, a_rel) = split_evr(a);
+    let (b_epoch, b_ver, b_rel) = split_evr(b);
+
+    rpmvercmp(a_epoch, b_epoch)
+        .then_with(|| rpmvercmp(a_ver, b_ver))
+        .then_with(|| match (a_rel, b_rel) {
+            (Some(a_rel), Some(b_rel)) => rpmvercmp(a_rel, b_rel),
+            _ => Ordering::Equal,
+        })
+}
+
+/// What: Split a version into epoch, pkgver, and pkgrel.
+///
+/// Inputs:
+/// - `evr`: Version string (`[epoch:]pkgver[-pkgrel]`).
+///
+/// Output:
+/// - `(epoch, pkgver, pkgrel)`; epoch defaults to `"0"`, pkgrel is `None` without a `-`.
+///
+/// Details:
+/// - Mirrors pacman's `parseEVR`: the epoch is a leading run of digits followed by `:`, and
+///   pkgrel is everything after the last `-`.
+fn split_evr(evr: &str) -> (&str, &str, Option<&str>) {
+    let digits = evr.bytes().take_while(u8::is_ascii_digit).count();
+    let (epoch, rest) = match evr[digits..].strip_prefix(':') {
+        Some(rest) if digits > 0 => (&evr[..digits], rest),
+        Some(rest) => ("0", rest),
+        None => ("0", evr),
+    };
+    match rest.rsplit_once('-') {
+        Some((version, release)) => (epoch, version, Some(release)),
+        None => (epoch, rest, None),
+    }
+}
+
+/// What: Compare two version fragments with pacman's `rpmvercmp` algorithm.
+///
+/// Inputs:
+/// - `a`: Left-hand fragment (epoch, pkgver, or pkgrel).
+/// - `b`: Right-hand fragment.
+///
+/// Output:
+/// - Ordering of `a` relative to `b`.
+///
+/// Details:
+/// - Compares alternating numeric and alphabetic segments; numbers ignore leading zeros and
+///   are not limited in size.
+/// - Differing separator lengths decide the comparison (`1..0 > 1.0`), as in pacman.
+fn rpmvercmp(a: &str, b: &str) -> Ordering {
+    if a == b {
+        return Ordering::Equal;
+    }
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let (mut i, mut j) = (0, 0);
+
+    while i < a.len() && j < b.len() {
+        let (start_a, start_b) = (i, j);
+        while i < a.len() && !a[i].is_ascii_alphanumeric() {
+            i += 1;
+        }
+        while j < b.len() && !b[j].is_ascii_alphanumeric() {
+            j += 1;
+        }
+        if i >= a.len() || j >= b.len() {
+            break;
+        }
+        if i - start_a != j - start_b {
+            return (i - start_a).cmp(&(j - start_b));
+        }
+
+        let numeric = a[i].is_ascii_digit();
+        let segment_end = |s: &[u8], start: usize| {
+            start
+                + s[start..]
+                    .iter()
+                    .take_while(|c| {
+                        if numeric {
+                            c.is_ascii_digit()
+                        } else {
+                            c.is_ascii_alphabetic()
                         }
-                    }
-                    ord => return ord,
-                }
-            }
-            (Ok(_), Err(_)) => {
-                // a has numeric prefix, b doesn't: numeric < text (pacman behavior)
-                return Ordering::Less;
-            }
-            (Err(_), Ok(_)) => {
-                // a doesn't have numeric prefix, b does: text > numeric (pacman behavior)
-                return Ordering::Greater;
-            }
-            (Err(_), Err(_)) => {
-                // Neither has numeric prefix, compare lexicographically
-                match a_seg.cmp(b_seg) {
-                    Ordering::Equal => {}
-                    ord => return ord,
-                }
-            }
+                    })
+                    .count()
+        };
+        let (end_a, end_b) = (segment_end(a, i), segment_end(b, j));
+        let (segment_a, segment_b) = (&a[i..end_a], &b[j..end_b]);
+
+        // Segments of different types: numeric segments are always newer
+        if segment_b.is_empty() {
+            return if numeric {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            };
         }
+
+        let ord = if numeric {
+            let zeros = |seg: &[u8]| seg.iter().take_while(|c| **c == b'0').count();
+            let (num_a, num_b) = (
+                &segment_a[zeros(segment_a)..],
+                &segment_b[zeros(segment_b)..],
+            );
+            num_a.len().cmp(&num_b.len()).then_with(|| num_a.cmp(num_b))
+        } else {
+            segment_a.cmp(segment_b)
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+        i = end_a;
+        j = end_b;
     }
 
-    Ordering::Equal
+    let (rest_a, rest_b) = (a.get(i), b.get(j));
+    if rest_a.is_none() && rest_b.is_none() {
+        return Ordering::Equal;
+    }
+    // A remaining alphabetic segment never beats an empty string
+    if (rest_a.is_none() && !rest_b.is_some_and(u8::is_ascii_alphabetic))
+        || rest_a.is_some_and(u8::is_ascii_alphabetic)
+    {
+        Ordering::Less
+    } else {
+        Ordering::Greater
+    }
 }
 
 /// What: Check if a version satisfies a version requirement.
 ///
 /// Inputs:
-/// - `version`: Version string to check (e.g., "1.2.3").
-/// - `requirement`: Version requirement with operator (e.g., ">=1.2.0", "=2.0", "<3.0").
+/// - `version`: Version string to check (e.g., "1.2.3", "1:2.0-1").
+/// - `requirement`: Version requirement with operator (e.g., ">=1.2.0", "=2.0", "< 3.0").
 ///
 /// Output:
 /// - Returns `true` if the version satisfies the requirement.
-/// - Returns `false` if the version does not satisfy the requirement.
-/// - Returns `true` if requirement is empty or has no operator (no constraint).
+/// - Returns `true` if requirement is empty or has no operator (no constraint, e.g. a bare
+///   dependency name).
+/// - Returns `false` if the requirement is malformed (unknown operator, missing version, or
+///   a version containing whitespace).
 ///
 /// Details:
-/// - Supports operators: `>=`, `<=`, `=`, `>`, `<`.
-/// - Uses `compare_versions()` for proper version comparison (not string comparison).
-/// - Automatically normalizes versions (strips pkgrel) before comparison.
-/// - Empty or invalid requirement strings default to `true` (no constraint).
+/// - Supports operators: `>=`, `<=`, `=`, `==` (alias of `=`), `>`, `<`.
+/// - Whitespace around the operator is ignored, so `parse_dep_spec("python >= 3.10")` yields a
+///   usable requirement.
+/// - Uses `compare_versions()` (pacman `vercmp`, including epochs), never string comparison.
+/// - pkgrel is only compared when both sides have one, so `>=1.2.3` accepts any `1.2.3-N`.
 ///
 /// # Example
 ///
@@ -166,44 +233,86 @@ pub fn compare_versions(a: &str, b: &str) -> Ordering {
 /// assert!(!version_satisfies("1.4", ">1.5"));
 /// assert!(version_satisfies("1.5", "=1.5"));
 /// assert!(!version_satisfies("1.6", "<1.5"));
+/// assert!(version_satisfies("1:1.0", "> 2.0")); // Epoch wins
 /// assert!(version_satisfies("2.0", "")); // Empty requirement = satisfied
+/// assert!(!version_satisfies("2.0", "=>1.0")); // Malformed
 /// ```
 #[must_use]
 pub fn version_satisfies(version: &str, requirement: &str) -> bool {
+    let requirement = requirement.trim();
     // Empty requirement means no constraint
     if requirement.is_empty() {
         return true;
     }
 
-    // Try to extract operator and version
-    let (op, req_version) = if let Some(rest) = requirement.strip_prefix(">=") {
-        (">=", rest)
-    } else if let Some(rest) = requirement.strip_prefix("<=") {
-        ("<=", rest)
-    } else if let Some(rest) = requirement.strip_prefix("=") {
-        ("=", rest)
-    } else if let Some(rest) = requirement.strip_prefix(">") {
-        (">", rest)
-    } else if let Some(rest) = requirement.strip_prefix("<") {
-        ("<", rest)
-    } else {
+    let op_len = requirement
+        .find(|c| !matches!(c, '<' | '>' | '=' | '!'))
+        .unwrap_or(requirement.len());
+    if op_len == 0 {
         // No operator found, assume satisfied (no constraint)
         return true;
-    };
-
-    // Use proper version comparison
-    let comparison = compare_versions(version, req_version);
+    }
+    let (op, req_version) = requirement.split_at(op_len);
+    let req_version = req_version.trim();
+    if req_version.is_empty() || req_version.contains(char::is_whitespace) {
+        return false;
+    }
 
+    let comparison = compare_versions(version.trim(), req_version);
     match op {
-        ">=" => matches!(comparison, Ordering::Equal | Ordering::Greater),
-        "<=" => matches!(comparison, Ordering::Equal | Ordering::Less),
-        "=" => comparison == Ordering::Equal,
+        ">=" => comparison != Ordering::Less,
+        "<=" => comparison != Ordering::Greater,
+        "=" | "==" => comparison == Ordering::Equal,
         ">" => comparison == Ordering::Greater,
         "<" => comparison == Ordering::Less,
-        _ => true, // Unknown operator, assume satisfied
+        _ => false, // Unknown operator, malformed constraint
     }
 }
 
+/// What: Check if a package provide satisfies a dependency specification.
+///
+/// Inputs:
+/// - `provide`: Provide entry, optionally versioned (e.g., "libfoo=1.2", "sh").
+/// - `dep_spec`: Dependency specification (e.g., "libfoo>=1.0", "sh").
+///
+/// Output:
+/// - Returns `true` if the provide names the dependency and its version satisfies the constraint.
+///
+/// Details:
+/// - Names must match exactly.
+/// - An unversioned dependency is satisfied by any provide with a matching name.
+/// - A versioned dependency is only satisfied by a versioned provide, matching pacman's
+///   behavior (an unversioned provide cannot satisfy `libfoo>=1.0`).
+/// - The provide's version is evaluated with `version_satisfies()`.
+///
+/// # Example
+///
+/// ```
+/// use arch_toolkit::deps::provide_satisfies;
+///
+/// assert!(provide_satisfies("libfoo=1.2", "libfoo>=1.0"));
+/// assert!(!provide_satisfies("libfoo=1.2", "libfoo>=2.0"));
+/// assert!(!provide_satisfies("libbar=1.2", "libfoo>=1.0"));
+/// assert!(provide_satisfies("sh", "sh"));
+/// assert!(!provide_satisfies("libfoo", "libfoo>=1.0"));
+/// ```
+#[must_use]
+pub fn provide_satisfies(provide: &str, dep_spec: &str) -> bool {
+    let dep = parse_dep_spec(dep_spec);
+    let (provide_name, provide_version) = match provide.split_once('=') {
+        Some((name, version)) => (name.trim(), Some(version.trim())),
+        None => (provide.trim(), None),
+    };
+
+    if provide_name != dep.name {
+        return false;
+    }
+    if dep.version_req.is_empty() {
+        return true;
+    }
+    provide_version.is_some_and(|version| version_satisfies(version, &dep.version_req))
+}
+
 /// What: Extract the leading numeric component from a version string.
 ///
 /// Inputs:
@@ -298,20 +407,59 @@ mod tests {
     }
 
     #[test]
-    fn test_compare_versions_missing_segments() {
-        // Missing segments treated as "0"
-        assert_eq!(compare_versions("1.0", "1.0.0"), Ordering::Equal);
-        assert_eq!(compare_versions("1.2", "1.2.0"), Ordering::Equal);
-        assert_eq!(compare_versions("1", "1.0.0"), Ordering::Equal);
+    fn test_compare_versions_extra_segments() {
+        // Extra numeric segments are newer (vercmp: 1.0 < 1.0.0)
+        assert_eq!(compare_versions("1.0", "1.0.0"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.0", "1.0"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0", "1.0.1"), Ordering::Less);
         assert_eq!(compare_versions("1.2", "1.2.1"), Ordering::Less);
     }
 
     #[test]
     fn test_compare_versions_pkgrel() {
-        // Pkgrel should be stripped before comparison
-        assert_eq!(compare_versions("1.2.3-1", "1.2.3-2"), Ordering::Equal);
+        // Pkgrel breaks ties only when both versions have one
+        assert_eq!(compare_versions("1.2.3-1", "1.2.3-2"), Ordering::Less);
+        assert_eq!(compare_versions("1.2.3-10", "1.2.3-9"), Ordering::Greater);
         assert_eq!(compare_versions("1.2.3-1", "1.2.3"), Ordering::Equal);
         assert_eq!(compare_versions("1.2.3-10", "1.2.4-1"), Ordering::Less);
+        assert_eq!(compare_versions("1.0-1.1", "1.0-1"), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_compare_versions_epoch() {
+        // Higher epoch always wins; missing epoch is 0
+        assert_eq!(compare_versions("1:1.0", "2.0"), Ordering::Greater);
+        assert_eq!(compare_versions("2.0", "1:1.0"), Ordering::Less);
+        assert_eq!(compare_versions("0:1.0", "1.0"), Ordering::Equal);
+        assert_eq!(compare_versions("2:1.0-1", "1:9.9-9"), Ordering::Greater);
+        assert_eq!(compare_versions("1:1.0-2", "1:1.0-1"), Ordering::Greater);
+        assert_eq!(compare_versions(":1.0", "1.0"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_compare_versions_vercmp_cases() {
+        // Known `vercmp` results, checked in both directions
+        let cases = [
+            ("1.0", "1.0.1", Ordering::Less),
+            ("1:1.0", "2.0", Ordering::Greater),
+            ("1.0a", "1.0", Ordering::Less),
+            ("1.0.0", "1.0", Ordering::Greater),
+            ("1.0a", "1.0b", Ordering::Less),
+            ("1.0.1", "1.0a", Ordering::Greater),
+            ("1.0", "1.0rc1", Ordering::Greater),
+            ("1.0rc1", "1.0.1", Ordering::Less),
+            ("1.001", "1.1", Ordering::Equal),
+            ("1..0", "1.0", Ordering::Greater),
+            (
+                "18446744073709551616",
+                "18446744073709551615",
+                Ordering::Greater,
+            ),
+        ];
+        for (a, b, expected) in cases {
+            assert_eq!(compare_versions(a, b), expected, "vercmp {a} {b}");
+            assert_eq!(compare_versions(b, a), expected.reverse(), "vercmp {b} {a}");
+        }
     }
 
     #[test]
@@ -339,7 +487,7 @@ mod tests {
     fn test_compare_versions_edge_cases() {
         // Edge cases
         assert_eq!(compare_versions("", ""), Ordering::Equal);
-        assert_eq!(compare_versions("0", "0.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("0", "0.0.0"), Ordering::Less);
         assert_eq!(compare_versions("10.0.0", "9.9.9"), Ordering::Greater);
         assert_eq!(compare_versions("1.10.0", "1.9.9"), Ordering::Greater);
     }
@@ -366,7 +514,7 @@ mod tests {
         assert!(version_satisfies("1.5", "=1.5"));
         assert!(!version_satisfies("1.6", "=1.5"));
         assert!(!version_satisfies("1.4", "=1.5"));
-        assert!(version_satisfies("1.5.0", "=1.5"));
+        assert!(!version_satisfies("1.5.0", "=1.5"));
     }
 
     #[test]
@@ -402,11 +550,63 @@ mod tests {
 
     #[test]
     fn test_version_satisfies_pkgrel() {
-        // Pkgrel should be normalized before comparison
+        // Pkgrel is only compared when both sides have one
         assert!(version_satisfies("1.2.3-1", ">=1.2.3"));
         assert!(version_satisfies("1.2.3-10", ">=1.2.3"));
         assert!(version_satisfies("1.2.3", ">=1.2.3-1"));
-        assert!(version_satisfies("1.2.3-5", "=1.2.3-1")); // Both normalized to 1.2.3
+        assert!(version_satisfies("1.2.3-5", "=1.2.3"));
+        assert!(!version_satisfies("1.2.3-5", "=1.2.3-1"));
+        assert!(version_satisfies("1.2.3-1", "<1.2.3-2"));
+    }
+
+    #[test]
+    fn test_version_satisfies_operator_table_with_epochs() {
+        // (version, requirement, expected)
+        let cases = [
+            ("1:1.0-1", ">=2.0", true),
+            ("1:1.0-1", ">= 1:1.0", true),
+            ("1:1.0-1", ">=2:0.1", false),
+            ("1:1.0-1", "<=1:1.0-1", true),
+            ("1:1.0-2", "<=1:1.0-1", false),
+            ("2.0", "<= 1:0.1", true),
+            ("1:1.0", "=1:1.0", true),
+            ("1:1.0", "=1.0", false),
+            ("1:1.0-3", "== 1:1.0", true),
+            ("1:1.0", "==1:1.1", false),
+            ("1:0.5", ">9.9", true),
+            ("1:0.5", ">1:0.5", false),
+            ("9.9", "<1:0.1", true),
+            ("1:0.1", "<1:0.1", false),
+            ("1:1.0", "", true),
+            ("1:1.0", "   ", true),
+        ];
+        for (version, requirement, expected) in cases {
+            assert_eq!(
+                version_satisfies(version, requirement),
+                expected,
+                "{version} {requirement}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_version_satisfies_whitespace_spec() {
+        let spec = parse_dep_spec("python >= 3.10");
+        assert_eq!(spec.name, "python");
+        assert!(version_satisfies("3.11.9-1", &spec.version_req));
+        assert!(!version_satisfies("3.9.1-1", &spec.version_req));
+        assert!(version_satisfies(" 3.10 ", " >=  3.10 "));
+    }
+
+    #[test]
+    fn test_version_satisfies_malformed() {
+        // Malformed constraints are never satisfied
+        assert!(!version_satisfies("1.0", ">="));
+        assert!(!version_satisfies("1.0", "= "));
+        assert!(!version_satisfies("1.0", "=>1.0"));
+        assert!(!version_satisfies("1.0", "<>1.0"));
+        assert!(!version_satisfies("1.0", "!=2.0"));
+        assert!(!version_satisfies("1.0", ">=1.0 2.0"));
     }
 
     #[test]
@@ -448,4 +648,25 @@ mod tests {
         assert!(is_major_version_bump("1.2.3-1", "2.0.0-1"));
         assert!(!is_major_version_bump("1.2.3-1", "1.3.0-1"));
     }
+
+    #[test]
+    fn test_provide_satisfies_matching_name_satisfied_version() {
+        assert!(provide_satisfies("libfoo=1.2", "libfoo>=1.0"));
+        assert!(provide_satisfies("libfoo=1.2-3", "libfoo=1.2"));
+        assert!(provide_satisfies("libfoo=1.2", "libfoo"));
+    }
+
+    #[test]
+    fn test_provide_satisfies_matching_name_unsatisfied_version() {
+        assert!(!provide_satisfies("libfoo=1.2", "libfoo>=2.0"));
+        assert!(!provide_satisfies("libfoo=1.2", "libfoo<1.0"));
+        // Unversioned provides cannot satisfy a versioned dependency
+        assert!(!provide_satisfies("libfoo", "libfoo>=1.0"));
+    }
+
+    #[test]
+    fn test_provide_satisfies_non_matching_name() {
+        assert!(!provide_satisfies("libbar=1.2", "libfoo>=1.0"));
+        assert!(!provide_satisfies("libfoo-git=1.2", "libfoo"));
+    }
 }
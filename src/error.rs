@@ -13,10 +13,12 @@ pub enum ArchToolkitError {
     /// Note: For AUR operations, prefer using operation-specific error variants
     /// (`SearchFailed`, `InfoFailed`, `CommentsFailed`, `PkgbuildFailed`) to preserve context.
     /// This variant is retained for client initialization and non-AUR operations.
+    #[cfg(feature = "aur")]
     #[error("Network error: {0}")]
     Network(reqwest::Error),
 
     /// AUR search operation failed.
+    #[cfg(feature = "aur")]
     #[error("AUR search failed for query '{query}': {source}")]
     SearchFailed {
         /// The search query that failed.
@@ -27,6 +29,7 @@ pub enum ArchToolkitError {
     },
 
     /// AUR info fetch operation failed.
+    #[cfg(feature = "aur")]
     #[error("AUR info fetch failed for packages [{packages}]: {source}")]
     InfoFailed {
         /// Comma-separated list of package names that failed.
@@ -37,6 +40,7 @@ pub enum ArchToolkitError {
     },
 
     /// AUR comments fetch operation failed.
+    #[cfg(feature = "aur")]
     #[error("AUR comments fetch failed for package '{package}': {source}")]
     CommentsFailed {
         /// The package name that failed.
@@ -47,6 +51,7 @@ pub enum ArchToolkitError {
     },
 
     /// PKGBUILD fetch operation failed.
+    #[cfg(feature = "aur")]
     #[error("PKGBUILD fetch failed for package '{package}': {source}")]
     PkgbuildFailed {
         /// The package name that failed.
@@ -56,6 +61,10 @@ pub enum ArchToolkitError {
         source: reqwest::Error,
     },
 
+    /// The async runtime backing a blocking client could not be started.
+    #[error("Failed to start async runtime: {0}")]
+    Runtime(#[source] std::io::Error),
+
     /// JSON parsing error.
     #[error("JSON parsing error: {0}")]
     Json(#[from] serde_json::Error),
@@ -64,6 +73,25 @@ pub enum ArchToolkitError {
     #[error("Parse error: {0}")]
     Parse(String),
 
+    /// The server answered with a 4xx or 5xx status code.
+    ///
+    /// Lets callers tell a missing resource (404) apart from a server-side failure (5xx)
+    /// worth retrying later. Transport failures stay in the network error variants.
+    #[error("HTTP {status} from {url}")]
+    Http {
+        /// The HTTP status code returned by the server.
+        status: u16,
+        /// The requested URL.
+        url: String,
+    },
+
+    /// The AUR RPC answered with an error response (`{"type":"error","error":"..."}`).
+    #[error("AUR RPC error: {message}")]
+    AurRpc {
+        /// Error message reported by the RPC.
+        message: String,
+    },
+
     /// Rate limiting error with optional retry-after information.
     #[error("Rate limited by server{0}", .retry_after.map(|s| format!(" (retry after {s}s)")).unwrap_or_default())]
     RateLimited {
@@ -71,6 +99,21 @@ pub enum ArchToolkitError {
         retry_after: Option<u64>,
     },
 
+    /// archlinux.org is treated as down: the circuit breaker is open.
+    #[error("archlinux.org service unavailable: circuit breaker is open")]
+    ServiceUnavailable,
+
+    /// The operation was cancelled through its cancellation token.
+    #[error("Operation was cancelled")]
+    Cancelled,
+
+    /// pacman could not be executed (command not found).
+    #[error("pacman is not available (running '{command}'); is this an Arch Linux system?")]
+    PacmanUnavailable {
+        /// The pacman command line that failed to start.
+        command: String,
+    },
+
     /// Package not found (enhanced with package name).
     #[error("Package '{package}' not found")]
     PackageNotFound {
@@ -78,6 +121,20 @@ pub enum ArchToolkitError {
         package: String,
     },
 
+    /// One or more requested packages were not found.
+    #[error("Packages not found: {}", .missing.join(", "))]
+    PackagesNotFound {
+        /// Requested package names without a result, in request order.
+        missing: Vec<String>,
+    },
+
+    /// Removal refused because installed packages depend on the removal targets.
+    #[error("Removal blocked: required by {}", .dependents.join(", "))]
+    RemovalBlocked {
+        /// Installed packages that depend on the removal targets, sorted by name.
+        dependents: Vec<String>,
+    },
+
     /// Invalid input parameter.
     #[error("Invalid input: {0}")]
     InvalidInput(String),
@@ -132,6 +189,7 @@ impl ArchToolkitError {
     /// Details:
     /// - Convenience constructor for search operation errors
     /// - Preserves both the query and the underlying error
+    #[cfg(feature = "aur")]
     #[must_use]
     pub fn search_failed(query: impl Into<String>, source: reqwest::Error) -> Self {
         Self::SearchFailed {
@@ -153,6 +211,7 @@ impl ArchToolkitError {
     /// - Convenience constructor for info operation errors
     /// - Formats package names as comma-separated string
     /// - Preserves both the package names and the underlying error
+    #[cfg(feature = "aur")]
     #[must_use]
     pub fn info_failed(packages: &[&str], source: reqwest::Error) -> Self {
         Self::InfoFailed {
@@ -173,6 +232,7 @@ impl ArchToolkitError {
     /// Details:
     /// - Convenience constructor for comments operation errors
     /// - Preserves both the package name and the underlying error
+    #[cfg(feature = "aur")]
     #[must_use]
     pub fn comments_failed(package: impl Into<String>, source: reqwest::Error) -> Self {
         Self::CommentsFailed {
@@ -193,6 +253,7 @@ impl ArchToolkitError {
     /// Details:
     /// - Convenience constructor for pkgbuild operation errors
     /// - Preserves both the package name and the underlying error
+    #[cfg(feature = "aur")]
     #[must_use]
     pub fn pkgbuild_failed(package: impl Into<String>, source: reqwest::Error) -> Self {
         Self::PkgbuildFailed {
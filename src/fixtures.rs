@@ -0,0 +1,51 @@
+//! Package fixtures shared by the unit tests of several modules.
+
+use crate::types::dependency::{
+    Dependency, DependencyKind, DependencySource, DependencyStatus, InstallReason, PackageRef,
+    PackageSource,
+};
+
+/// What: Build an official `extra` package reference.
+///
+/// Inputs:
+/// - `name`: Package name.
+///
+/// Output:
+/// - `PackageRef` for `name` 1.0-1 from `extra` on `x86_64`.
+pub fn extra_ref(name: &str) -> PackageRef {
+    PackageRef {
+        name: name.to_string(),
+        version: "1.0-1".to_string(),
+        source: PackageSource::Official {
+            repo: "extra".to_string(),
+            arch: "x86_64".to_string(),
+        },
+    }
+}
+
+/// What: Build a runtime dependency from `extra` with the given status.
+///
+/// Inputs:
+/// - `name`: Package name.
+/// - `status`: Dependency status.
+///
+/// Output:
+/// - `Dependency` without version requirement or relations.
+pub fn dep(name: &str, status: DependencyStatus) -> Dependency {
+    Dependency {
+        name: name.into(),
+        version_req: String::new(),
+        status,
+        source: DependencySource::Official {
+            repo: "extra".into(),
+        },
+        required_by: Vec::new(),
+        depends_on: Vec::new(),
+        is_core: false,
+        is_system: false,
+        kind: DependencyKind::Runtime,
+        explicit: false,
+        install_reason: InstallReason::Unknown,
+        satisfied_by: None,
+    }
+}
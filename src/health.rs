@@ -1,13 +1,21 @@
 //! Health check functionality for archlinux.org services.
 
+use crate::aur::AUR_BASE_URL;
+use crate::client::{archlinux_backoff_state, extract_retry_after};
 use crate::error::Result;
 use crate::types::{HealthStatus, ServiceStatus};
 use reqwest::Client;
 use std::time::{Duration, Instant};
 use tracing::debug;
 
-/// Health check endpoint (minimal AUR RPC request).
-const HEALTH_CHECK_URL: &str = "https://aur.archlinux.org/rpc/v5/info";
+/// Path of the RPC health probe (minimal AUR RPC request).
+const RPC_PROBE_PATH: &str = "/rpc/v5/info";
+
+/// Path of the web frontend health probe (package listing used by comment scraping).
+const WEB_PROBE_PATH: &str = "/packages/";
+
+/// Path of the cgit health probe (plain file endpoint used for PKGBUILD and `.SRCINFO`).
+const CGIT_PROBE_PATH: &str = "/cgit/aur.git/plain/PKGBUILD?h=yay";
 
 /// Default timeout for health checks (shorter than regular operations).
 const DEFAULT_HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
@@ -15,129 +23,264 @@ const DEFAULT_HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
 /// Latency threshold for "degraded" status (2 seconds).
 const DEGRADED_LATENCY_THRESHOLD: Duration = Duration::from_secs(2);
 
-/// What: Perform health check against AUR API.
+/// What: Perform health check against the AUR RPC API, web frontend, and cgit.
 ///
 /// Inputs:
-/// - `client`: HTTP client to use for the request
+/// - `client`: HTTP client to use for the requests
+/// - `timeout`: Optional timeout override (uses default if None)
+///
+/// Output:
+/// - `Result<HealthStatus>` with per-service status and latency
+///
+/// Details:
+/// - Probes `https://aur.archlinux.org`; see `check_health_at()` for the probes and statuses
+///
+/// # Errors
+/// - Never returns an error - always returns `Ok(HealthStatus)` with appropriate status
+/// - Network errors are represented as `ServiceStatus::Unreachable` or `ServiceStatus::Timeout`
+pub async fn check_health(client: &Client, timeout: Option<Duration>) -> Result<HealthStatus> {
+    check_health_at(client, AUR_BASE_URL, timeout).await
+}
+
+/// What: Perform health check against an AUR instance.
+///
+/// Inputs:
+/// - `client`: HTTP client to use for the requests
+/// - `base_url`: AUR base URL, e.g. `https://aur.archlinux.org`
 /// - `timeout`: Optional timeout override (uses default if None)
 ///
 /// Output:
-/// - `Result<HealthStatus>` with service status and latency
+/// - `Result<HealthStatus>` with per-service status and latency
 ///
 /// Details:
-/// - Uses minimal RPC request (info with no packages)
-/// - Validates response is valid JSON with expected structure
-/// - Measures round-trip latency
-/// - Returns `HealthStatus` with appropriate `ServiceStatus` based on:
+/// - Probes three endpoints concurrently, each under the same timeout:
+///   - `rpc`: minimal RPC request (info with no packages), validated as an RPC JSON answer
+///   - `web`: the `/packages/` page served by the web frontend (used for comments)
+///   - `cgit`: a plain PKGBUILD served by cgit (used for PKGBUILD and `.SRCINFO`)
+/// - Each probe is classified as:
 ///   - Success + latency < 2s = `Healthy`
 ///   - Success + latency >= 2s = `Degraded`
-///   - HTTP error = `Unreachable`
+///   - Invalid RPC response body = `Degraded`
+///   - HTTP 429 = `RateLimited`, with `retry_after` from the `Retry-After` header
+///   - Other HTTP error = `Unreachable`
 ///   - Timeout = `Timeout`
+/// - `latency` is the RPC probe latency
+/// - Reports the shared archlinux.org rate limiter's backoff and failure count
 ///
 /// # Errors
 /// - Never returns an error - always returns `Ok(HealthStatus)` with appropriate status
 /// - Network errors are represented as `ServiceStatus::Unreachable` or `ServiceStatus::Timeout`
-pub async fn check_health(client: &Client, timeout: Option<Duration>) -> Result<HealthStatus> {
-    let start = Instant::now();
-    let checked_at = start;
-
+pub async fn check_health_at(
+    client: &Client,
+    base_url: &str,
+    timeout: Option<Duration>,
+) -> Result<HealthStatus> {
+    let checked_at = Instant::now();
     let timeout_duration = timeout.unwrap_or(DEFAULT_HEALTH_CHECK_TIMEOUT);
+    let base_url = base_url.trim_end_matches('/');
 
-    // Create a request with health-check-specific timeout
-    let result = client
-        .get(HEALTH_CHECK_URL)
-        .timeout(timeout_duration)
-        .send()
-        .await;
-
-    let latency = start.elapsed();
-
-    match result {
-        Ok(response) => {
-            // Check HTTP status
-            if !response.status().is_success() {
-                debug!(
-                    status = %response.status(),
-                    latency_ms = latency.as_millis(),
-                    "health check returned non-success status"
-                );
-                return Ok(HealthStatus {
-                    aur_api: ServiceStatus::Unreachable,
-                    latency: Some(latency),
-                    checked_at,
-                });
-            }
+    let rpc_url = format!("{base_url}{RPC_PROBE_PATH}");
+    let web_url = format!("{base_url}{WEB_PROBE_PATH}");
+    let cgit_url = format!("{base_url}{CGIT_PROBE_PATH}");
+    let (rpc, web, cgit) = tokio::join!(
+        probe(client, &rpc_url, timeout_duration, true),
+        probe(client, &web_url, timeout_duration, false),
+        probe(client, &cgit_url, timeout_duration, false),
+    );
+
+    Ok(health_status(
+        &rpc,
+        &web,
+        &cgit,
+        archlinux_backoff_state(),
+        checked_at,
+    ))
+}
+
+/// What: Outcome of a single health probe.
+struct ProbeResult {
+    /// Classified service status.
+    status: ServiceStatus,
+    /// Round-trip latency of the probe.
+    latency: Duration,
+    /// `Retry-After` seconds from a 429 answer, if any.
+    retry_after: Option<u64>,
+}
+
+/// What: Build a `HealthStatus` including the current rate limiter state.
+///
+/// Inputs:
+/// - `rpc`, `web`, `cgit`: Probe results for each service
+/// - `backoff`: `(current_backoff_ms, consecutive_failures)` of the rate limiter
+/// - `checked_at`: When the check started
+///
+/// Output:
+/// - `HealthStatus` with the given backoff and failure count
+///
+/// Details:
+/// - `retry_after` is taken from the first rate-limited probe (RPC, then web, then cgit)
+fn health_status(
+    rpc: &ProbeResult,
+    web: &ProbeResult,
+    cgit: &ProbeResult,
+    backoff: (u64, u32),
+    checked_at: Instant,
+) -> HealthStatus {
+    let (current_backoff_ms, consecutive_failures) = backoff;
+    HealthStatus {
+        rpc: rpc.status,
+        web: web.status,
+        cgit: cgit.status,
+        latency: Some(rpc.latency),
+        checked_at,
+        current_backoff_ms,
+        consecutive_failures,
+        retry_after: rpc.retry_after.or(web.retry_after).or(cgit.retry_after),
+    }
+}
 
-            // Validate response body is valid JSON
-            match response.json::<serde_json::Value>().await {
-                Ok(json) => {
-                    // Verify it's a valid AUR RPC response
-                    let is_valid = json.get("version").is_some() && json.get("type").is_some();
-
-                    if !is_valid {
-                        debug!(
-                            latency_ms = latency.as_millis(),
-                            "health check response missing expected fields"
-                        );
-                        return Ok(HealthStatus {
-                            aur_api: ServiceStatus::Degraded,
-                            latency: Some(latency),
-                            checked_at,
-                        });
-                    }
-
-                    // Determine status based on latency
-                    let status = if latency > DEGRADED_LATENCY_THRESHOLD {
-                        ServiceStatus::Degraded
-                    } else {
-                        ServiceStatus::Healthy
-                    };
-
-                    debug!(
-                        latency_ms = latency.as_millis(),
-                        ?status,
-                        "health check completed"
-                    );
-
-                    Ok(HealthStatus {
-                        aur_api: status,
-                        latency: Some(latency),
-                        checked_at,
-                    })
-                }
-                Err(e) => {
-                    debug!(
-                        error = %e,
-                        latency_ms = latency.as_millis(),
-                        "health check failed to parse response"
-                    );
-                    Ok(HealthStatus {
-                        aur_api: ServiceStatus::Degraded,
-                        latency: Some(latency),
-                        checked_at,
-                    })
-                }
+/// What: Probe one endpoint and classify its status.
+///
+/// Inputs:
+/// - `client`: HTTP client to use for the request
+/// - `url`: Endpoint to probe
+/// - `timeout`: Request timeout
+/// - `expect_rpc`: Whether the body must be an AUR RPC JSON answer
+///
+/// Output:
+/// - `ProbeResult` with status, latency, and retry-after
+///
+/// Details:
+/// - See `check_health_at()` for the classification rules
+async fn probe(client: &Client, url: &str, timeout: Duration, expect_rpc: bool) -> ProbeResult {
+    let start = Instant::now();
+    let result = client.get(url).timeout(timeout).send().await;
+    let mut latency = start.elapsed();
+    let mut retry_after = None;
+
+    let status = match result {
+        Ok(response) if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS => {
+            retry_after = extract_retry_after(&response);
+            ServiceStatus::RateLimited
+        }
+        Ok(response) if !response.status().is_success() => {
+            debug!(url, status = %response.status(), "health probe returned non-success status");
+            ServiceStatus::Unreachable
+        }
+        Ok(response) if expect_rpc => {
+            let valid = response
+                .json::<serde_json::Value>()
+                .await
+                .is_ok_and(|json| json.get("version").is_some() && json.get("type").is_some());
+            latency = start.elapsed();
+            if !valid {
+                debug!(url, "health probe response is not a valid RPC answer");
+                ServiceStatus::Degraded
+            } else if latency > DEGRADED_LATENCY_THRESHOLD {
+                ServiceStatus::Degraded
+            } else {
+                ServiceStatus::Healthy
             }
         }
+        Ok(_) if latency > DEGRADED_LATENCY_THRESHOLD => ServiceStatus::Degraded,
+        Ok(_) => ServiceStatus::Healthy,
+        Err(e) if e.is_timeout() => ServiceStatus::Timeout,
         Err(e) => {
-            let status = if e.is_timeout() {
-                ServiceStatus::Timeout
-            } else {
-                ServiceStatus::Unreachable
-            };
-
-            debug!(
-                error = %e,
-                ?status,
-                latency_ms = latency.as_millis(),
-                "health check failed"
-            );
-
-            Ok(HealthStatus {
-                aur_api: status,
-                latency: Some(latency),
-                checked_at,
-            })
+            debug!(url, error = %e, "health probe failed");
+            ServiceStatus::Unreachable
+        }
+    };
+
+    debug!(
+        url,
+        latency_ms = latency.as_millis(),
+        ?status,
+        "health probe completed"
+    );
+    ProbeResult {
+        status,
+        latency,
+        retry_after,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{backoff_state, increase_backoff, local_limiter};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// What: Build a probe result with the given status and retry-after.
+    const fn probe_result(status: ServiceStatus, retry_after: Option<u64>) -> ProbeResult {
+        ProbeResult {
+            status,
+            latency: Duration::from_millis(20),
+            retry_after,
         }
     }
+
+    #[test]
+    /// What: Report the rate limiter's backoff state in the health status.
+    ///
+    /// Inputs:
+    /// - Local limiter seeded with a 1 s `Retry-After` backoff.
+    ///
+    /// Output:
+    /// - Status carries the 1000 ms backoff, one failure, and the probe's retry-after.
+    fn test_health_status_reports_backoff_state() {
+        let limiter = local_limiter();
+        increase_backoff(limiter, Some(1), None);
+        let status = health_status(
+            &probe_result(ServiceStatus::RateLimited, Some(30)),
+            &probe_result(ServiceStatus::Healthy, None),
+            &probe_result(ServiceStatus::Healthy, None),
+            backoff_state(limiter),
+            Instant::now(),
+        );
+
+        assert_eq!(status.current_backoff_ms, 1000);
+        assert_eq!(status.consecutive_failures, 1);
+        assert_eq!(status.retry_after, Some(30));
+        assert!(status.is_healthy());
+    }
+
+    #[tokio::test]
+    /// What: Probe RPC, web, and cgit separately.
+    ///
+    /// Inputs:
+    /// - Mock server with a healthy RPC, a rate-limited web frontend, and a failing cgit.
+    ///
+    /// Output:
+    /// - Distinct per-service statuses, the web probe's retry-after, and an unhealthy overall status.
+    async fn test_check_health_probes_each_service() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(RPC_PROBE_PATH))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "version": 5, "type": "multiinfo", "resultcount": 0, "results": []
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(WEB_PROBE_PATH))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "30"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/cgit/aur.git/plain/PKGBUILD"))
+            .respond_with(ResponseTemplate::new(502))
+            .mount(&server)
+            .await;
+
+        let status = check_health_at(&Client::new(), &server.uri(), None)
+            .await
+            .expect("health check never fails");
+
+        assert_eq!(status.rpc, ServiceStatus::Healthy);
+        assert_eq!(status.web, ServiceStatus::RateLimited);
+        assert_eq!(status.cgit, ServiceStatus::Unreachable);
+        assert_eq!(status.retry_after, Some(30));
+        assert!(!status.is_healthy());
+    }
 }
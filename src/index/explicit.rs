@@ -9,7 +9,8 @@ use crate::types::index::InstalledPackagesMode;
 /// What: Query pacman for explicitly installed packages and optionally update a cache.
 ///
 /// Inputs:
-/// - `mode`: Filter mode determining which packages to query (`LeafOnly` or `AllExplicit`).
+/// - `mode`: Filter mode determining which packages to query (`LeafOnly`, `AllExplicit`, or
+///   `ForeignExplicit`).
 /// - `cache`: Optional mutable reference to a `HashSet<String>` to update with results.
 ///
 /// Output:
@@ -19,6 +20,7 @@ use crate::types::index::InstalledPackagesMode;
 /// Details:
 /// - Uses `pacman -Qetq` for `LeafOnly` mode (explicitly installed AND not required).
 /// - Uses `pacman -Qeq` for `AllExplicit` mode (all explicitly installed).
+/// - Uses `pacman -Qemq` for `ForeignExplicit` mode (explicitly installed, not in any sync db).
 /// - If `cache` is provided, updates it with the results.
 /// - Sets `LC_ALL=C` and `LANG=C` for consistent locale-independent output.
 /// - Logs errors for diagnostics but returns empty set to avoid blocking operations.
@@ -46,6 +48,7 @@ pub fn refresh_explicit_cache(
     let args: &[&str] = match mode {
         InstalledPackagesMode::LeafOnly => &["-Qetq"], // explicitly installed AND not required
         InstalledPackagesMode::AllExplicit => &["-Qeq"], // all explicitly installed
+        InstalledPackagesMode::ForeignExplicit => &["-Qemq"], // explicitly installed, not in sync dbs
     };
 
     tracing::debug!("Running: pacman {:?}", args);
@@ -101,7 +104,8 @@ pub fn refresh_explicit_cache(
 /// What: Query pacman for explicitly installed packages asynchronously and optionally update a cache.
 ///
 /// Inputs:
-/// - `mode`: Filter mode determining which packages to query (`LeafOnly` or `AllExplicit`).
+/// - `mode`: Filter mode determining which packages to query (`LeafOnly`, `AllExplicit`, or
+///   `ForeignExplicit`).
 /// - `cache`: Optional mutable reference to a `HashSet<String>` to update with results.
 ///
 /// Output:
@@ -152,7 +156,7 @@ pub async fn refresh_explicit_cache_async(
 ///
 /// Inputs:
 /// - `name`: Package name to check.
-/// - `mode`: Filter mode for query type (`LeafOnly` or `AllExplicit`).
+/// - `mode`: Filter mode for query type (`LeafOnly`, `AllExplicit`, or `ForeignExplicit`).
 /// - `cache`: Optional reference to a `HashSet<String>` containing explicit package names.
 ///
 /// Output:
@@ -188,6 +192,7 @@ pub fn is_explicit(
     let args: &[&str] = match mode {
         InstalledPackagesMode::LeafOnly => &["-Qet", name],
         InstalledPackagesMode::AllExplicit => &["-Qe", name],
+        InstalledPackagesMode::ForeignExplicit => &["-Qem", name],
     };
 
     tracing::debug!("Running: pacman {:?}", args);
@@ -209,6 +214,92 @@ pub fn is_explicit(
     }
 }
 
+/// What: Query pacman for explicitly installed foreign packages with their versions.
+///
+/// Inputs: None
+///
+/// Output:
+/// - Returns `Ok(Vec<(String, String)>)` of `(name, version)` pairs in pacman's (alphabetical) order.
+/// - Returns `Ok(Vec::new())` on failure (graceful degradation).
+///
+/// Details:
+/// - Uses `pacman -Qem`: explicitly installed packages not found in any sync database, which
+///   are usually AUR packages to update from the AUR.
+/// - pacman exits with a failure status when no foreign packages are installed; this is treated
+///   as an empty result.
+/// - Sets `LC_ALL=C` and `LANG=C` for consistent locale-independent output.
+///
+/// # Errors
+///
+/// This function does not return errors - it gracefully degrades by returning an empty list.
+/// Errors are logged using `tracing::error` for diagnostics.
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::index::get_foreign_packages;
+///
+/// for (name, version) in get_foreign_packages().unwrap() {
+///     println!("{name} {version}");
+/// }
+/// ```
+pub fn get_foreign_packages() -> Result<Vec<(String, String)>> {
+    let args = ["-Qem"];
+    tracing::debug!("Running: pacman {:?}", args);
+    let output = Command::new("pacman")
+        .args(args)
+        .env("LC_ALL", "C")
+        .env("LANG", "C")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output();
+
+    let packages = match output {
+        Ok(output) => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            if output.status.success() || stdout.trim().is_empty() {
+                let packages = parse_foreign_packages(&stdout);
+                tracing::debug!("Successfully retrieved {} foreign packages", packages.len());
+                packages
+            } else {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                tracing::error!(
+                    "pacman {:?} failed with status {:?}: {}",
+                    args,
+                    output.status.code(),
+                    stderr
+                );
+                Vec::new()
+            }
+        }
+        Err(e) => {
+            tracing::error!("Failed to execute pacman {:?}: {}", args, e);
+            Vec::new()
+        }
+    };
+
+    Ok(packages)
+}
+
+/// What: Parse `pacman -Qem` output into name/version pairs.
+///
+/// Inputs:
+/// - `text`: Output with one `name version` entry per line.
+///
+/// Output:
+/// - `(name, version)` pairs in input order; blank or malformed lines are skipped.
+fn parse_foreign_packages(text: &str) -> Vec<(String, String)> {
+    text.lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let name = parts.next()?;
+            let version = parts.next()?;
+            Some((name.to_string(), version.to_string()))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -297,6 +388,57 @@ mod tests {
         let _result_all = is_explicit("vim", InstalledPackagesMode::AllExplicit, None);
     }
 
+    #[test]
+    /// What: Verify `parse_foreign_packages` reads the `pacman -Qem` format.
+    ///
+    /// Inputs:
+    /// - `-Qem` output with epoch versions, a blank line, and a name-only line.
+    ///
+    /// Output:
+    /// - Name/version pairs in order; malformed lines are skipped.
+    ///
+    /// Details:
+    /// - Tests the parser without requiring pacman.
+    fn parse_foreign_packages_reads_qem_output() {
+        let output = "paru-bin 2.0.4-1\nvisual-studio-code-bin 1:1.95.3-1\n\nbroken-line\n  yay 12.4.2-1  \n";
+        assert_eq!(
+            parse_foreign_packages(output),
+            vec![
+                ("paru-bin".to_string(), "2.0.4-1".to_string()),
+                (
+                    "visual-studio-code-bin".to_string(),
+                    "1:1.95.3-1".to_string()
+                ),
+                ("yay".to_string(), "12.4.2-1".to_string()),
+            ]
+        );
+        assert!(parse_foreign_packages("").is_empty());
+    }
+
+    #[test]
+    #[ignore = "Requires pacman to be available"]
+    /// What: Verify `get_foreign_packages` agrees with `ForeignExplicit` mode.
+    ///
+    /// Inputs:
+    /// - Real pacman database.
+    ///
+    /// Output:
+    /// - Foreign package names match `refresh_explicit_cache(ForeignExplicit)` and carry versions.
+    ///
+    /// Details:
+    /// - Result depends on system state; may be empty on systems without AUR packages.
+    fn get_foreign_packages_matches_foreign_explicit_mode() {
+        let foreign = get_foreign_packages().expect("graceful degradation");
+        let names = refresh_explicit_cache(InstalledPackagesMode::ForeignExplicit, None)
+            .expect("graceful degradation");
+
+        assert_eq!(foreign.len(), names.len());
+        for (name, version) in &foreign {
+            assert!(names.contains(name));
+            assert!(!version.is_empty());
+        }
+    }
+
     #[cfg(feature = "index")]
     #[tokio::test]
     /// What: Verify `refresh_explicit_cache_async` works asynchronously.
@@ -1,12 +1,33 @@
 //! Official repository index fetching functions for the index module.
 
 use std::process::{Command, Stdio};
+use std::time::SystemTime;
 
 use crate::error::{ArchToolkitError, Result};
 use crate::types::index::{OfficialIndex, OfficialPackage};
 
 #[cfg(feature = "aur")]
-use crate::client::{ArchClient, rate_limit_archlinux};
+use crate::client::{ArchClient, check_status, rate_limit_archlinux};
+
+/// Arch Packages API search endpoint used by the API fetch.
+#[cfg(feature = "aur")]
+const PACKAGES_SEARCH_URL: &str = "https://archlinux.org/packages/search/json/";
+
+/// Official repositories indexed, in pacman's default priority order.
+const OFFICIAL_REPOS: [&str; 3] = ["core", "extra", "multilib"];
+
+/// Architecture fetched from the API by default.
+#[cfg(feature = "aur")]
+const DEFAULT_ARCH: &str = "x86_64";
+
+/// One page of Arch Packages API search results.
+#[cfg(feature = "aur")]
+struct ApiPage {
+    /// Packages listed on the page.
+    packages: Vec<OfficialPackage>,
+    /// Total number of pages of the search (1 when the API omits it).
+    num_pages: u64,
+}
 
 /// What: Fetch the official package index using `pacman -Sl`.
 ///
@@ -49,6 +70,8 @@ pub fn fetch_official_index() -> Result<OfficialIndex> {
 ///
 /// Details:
 /// - Tries `pacman -Sl` first (fast, local, no network required).
+/// - `pacman -Sl` lists no descriptions; use `fill_api_metadata()` (requires `aur`) to add them
+///   from the Arch Packages API.
 /// - Falls back to Arch Packages API if pacman is unavailable or fails.
 /// - API method requires `aur` feature and network access.
 /// - Rebuilds name index after fetching for O(1) lookups.
@@ -89,7 +112,7 @@ pub async fn fetch_official_index_async() -> Result<OfficialIndex> {
     {
         let client = crate::client::ArchClient::new()
             .map_err(|e| ArchToolkitError::Parse(format!("Failed to create HTTP client: {e}")))?;
-        fetch_via_api(&client).await
+        fetch_via_api(&client, PACKAGES_SEARCH_URL, &[DEFAULT_ARCH, "any"]).await
     }
 
     #[cfg(not(feature = "aur"))]
@@ -120,10 +143,9 @@ pub async fn fetch_official_index_async() -> Result<OfficialIndex> {
 ///
 /// - Returns `Err(ArchToolkitError::Parse)` if pacman is unavailable or output cannot be parsed.
 fn fetch_via_pacman() -> Result<OfficialIndex> {
-    let repos = ["core", "extra", "multilib"];
     let mut pkgs = Vec::new();
 
-    for repo in &repos {
+    for repo in &OFFICIAL_REPOS {
         tracing::debug!("Running: pacman -Sl {}", repo);
         let output = Command::new("pacman")
             .args(["-Sl", repo])
@@ -134,14 +156,13 @@ fn fetch_via_pacman() -> Result<OfficialIndex> {
             .stderr(Stdio::piped())
             .output()
             .map_err(|e| {
-                ArchToolkitError::Parse(format!("Failed to execute pacman -Sl {}: {}", repo, e))
+                ArchToolkitError::Parse(format!("Failed to execute pacman -Sl {repo}: {e}"))
             })?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
             return Err(ArchToolkitError::Parse(format!(
-                "pacman -Sl {} failed: {}",
-                repo, stderr
+                "pacman -Sl {repo} failed: {stderr}"
             )));
         }
 
@@ -168,6 +189,9 @@ fn fetch_via_pacman() -> Result<OfficialIndex> {
                 arch: String::new(), // Not available from -Sl
                 version: version.to_string(),
                 description: String::new(), // Not available from -Sl
+                packager: String::new(),    // Not available from -Sl
+                download_size: 0,
+                installed_size: 0,
             });
         }
     }
@@ -178,6 +202,7 @@ fn fetch_via_pacman() -> Result<OfficialIndex> {
 
     let mut index = OfficialIndex {
         pkgs,
+        fetched_at: Some(SystemTime::now()),
         name_to_idx: std::collections::HashMap::new(),
     };
     index.rebuild_name_index();
@@ -186,44 +211,98 @@ fn fetch_via_pacman() -> Result<OfficialIndex> {
     Ok(index)
 }
 
+/// What: Fetch the official package index of one architecture from the Arch Packages API.
+///
+/// Inputs:
+/// - `arch`: Architecture to index (e.g., `x86_64`, `aarch64`).
+///
+/// Output:
+/// - `Result<OfficialIndex>` with the packages built for `arch` plus architecture-independent
+///   (`any`) packages of core, extra and multilib, with name index rebuilt.
+///
+/// Details:
+/// - Always queries the API, never pacman: the local sync databases only cover the host
+///   architecture.
+/// - Fetches every page of each repository and architecture, following `num_pages`.
+/// - A package listed by several repositories is kept once, from the first repository in
+///   core, extra, multilib order.
+/// - archlinux.org only publishes `x86_64` packages, so other architectures yield their
+///   `any` packages plus whatever the API reports for them.
+///
+/// # Errors
+///
+/// - Returns `Err(ArchToolkitError::InvalidInput)` if `arch` is empty.
+/// - Returns `Err(ArchToolkitError::Http)` if the API answers with a 4xx/5xx status.
+/// - Returns `Err(ArchToolkitError::Parse)` if HTTP requests fail or responses are invalid.
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::index::fetch_official_index_for_arch;
+///
+/// # async fn example() -> Result<(), arch_toolkit::error::ArchToolkitError> {
+/// let index = fetch_official_index_for_arch("aarch64").await?;
+/// assert!(index.pkgs.iter().all(|pkg| pkg.arch == "aarch64" || pkg.arch == "any"));
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "aur")]
+pub async fn fetch_official_index_for_arch(arch: &str) -> Result<OfficialIndex> {
+    let arch = arch.trim();
+    if arch.is_empty() {
+        return Err(ArchToolkitError::InvalidInput(
+            "architecture must not be empty".to_string(),
+        ));
+    }
+    let client = ArchClient::new()?;
+    let archs: &[&str] = if arch == "any" {
+        &["any"]
+    } else {
+        &[arch, "any"]
+    };
+    fetch_via_api(&client, PACKAGES_SEARCH_URL, archs).await
+}
+
 /// What: Fetch official packages from Arch Packages API.
 ///
 /// Inputs:
 /// - `client`: HTTP client for making requests (must have `aur` feature enabled).
+/// - `search_url`: Search endpoint, normally `PACKAGES_SEARCH_URL`.
+/// - `archs`: Architectures to fetch.
 ///
 /// Output:
 /// - `Ok(OfficialIndex)` with packages from API, deduplicated and indexed.
 /// - `Err` if API requests fail or responses cannot be parsed.
 ///
 /// Details:
-/// - Fetches from `https://archlinux.org/packages/search/json/` endpoint.
-/// - Paginates through all results for each repository (core, extra, multilib).
+/// - Paginates through all results for each repository (core, extra, multilib) and
+///   architecture until `num_pages` is reached.
 /// - Parses JSON response structure with package metadata.
 /// - Uses rate limiting via `rate_limit_archlinux()`.
-/// - Deduplicates packages by `(repo, name)` tuple.
+/// - Deduplicates packages by name with `dedup_across_repos()`.
 /// - Rebuilds name index after fetching.
 ///
 /// # Errors
 ///
+/// - Returns `Err(ArchToolkitError::Http)` if the API answers with a 4xx/5xx status.
 /// - Returns `Err(ArchToolkitError::Parse)` if HTTP requests fail or response structure is invalid.
 /// - Returns `Err(ArchToolkitError::Json)` if JSON parsing fails.
 #[cfg(feature = "aur")]
-async fn fetch_via_api(client: &ArchClient) -> Result<OfficialIndex> {
-    let repos = ["core", "extra", "multilib"];
-    let archs = ["x86_64", "any"];
+async fn fetch_via_api(
+    client: &ArchClient,
+    search_url: &str,
+    archs: &[&str],
+) -> Result<OfficialIndex> {
     let limit = 250; // API limit per page
     let mut pkgs = Vec::new();
 
-    for repo in &repos {
-        for arch in &archs {
+    for repo in &OFFICIAL_REPOS {
+        for arch in archs {
             let mut page = 1;
             let mut has_more = true;
 
             while has_more {
-                let url = format!(
-                    "https://archlinux.org/packages/search/json/?repo={}&arch={}&limit={}&page={}",
-                    repo, arch, limit, page
-                );
+                let url = format!("{search_url}?repo={repo}&arch={arch}&limit={limit}&page={page}");
 
                 tracing::debug!(
                     repo = repo,
@@ -237,91 +316,27 @@ async fn fetch_via_api(client: &ArchClient) -> Result<OfficialIndex> {
 
                 let response = client.http_client().get(&url).send().await.map_err(|e| {
                     ArchToolkitError::Parse(format!(
-                        "Failed to fetch packages from API (repo={}, arch={}, page={}): {}",
-                        repo, arch, page, e
+                        "Failed to fetch packages from API (repo={repo}, arch={arch}, page={page}): {e}"
                     ))
                 })?;
 
-                let status = response.status();
-                if !status.is_success() {
-                    return Err(ArchToolkitError::Parse(format!(
-                        "API returned error status {} for repo={}, arch={}, page={}",
-                        status, repo, arch, page
-                    )));
-                }
+                let response = check_status(response)?;
 
                 let json: serde_json::Value = response.json().await.map_err(|e| {
-                    ArchToolkitError::Parse(format!("Failed to parse JSON response: {}", e))
+                    ArchToolkitError::Parse(format!("Failed to parse JSON response: {e}"))
                 })?;
 
-                // Parse results array
-                let results = json
-                    .get("results")
-                    .and_then(|v| v.as_array())
-                    .ok_or_else(|| {
-                        ArchToolkitError::Parse(format!(
-                            "Invalid API response: missing 'results' array for repo={}, arch={}, page={}",
-                            repo, arch, page
-                        ))
-                    })?;
-
-                for result in results {
-                    let pkgname =
-                        result
-                            .get("pkgname")
-                            .and_then(|v| v.as_str())
-                            .ok_or_else(|| {
-                                ArchToolkitError::Parse(format!(
-                                    "Invalid API response: missing 'pkgname' field"
-                                ))
-                            })?;
-
-                    let repo_name = result.get("repo").and_then(|v| v.as_str()).unwrap_or(repo);
-
-                    let arch_name = result.get("arch").and_then(|v| v.as_str()).unwrap_or(arch);
-
-                    let version = result
-                        .get("pkgver")
-                        .and_then(|v| v.as_str())
-                        .map(|v| {
-                            let rel = result.get("pkgrel").and_then(|r| r.as_str()).unwrap_or("");
-                            if rel.is_empty() {
-                                v.to_string()
-                            } else {
-                                format!("{}-{}", v, rel)
-                            }
-                        })
-                        .unwrap_or_default();
-
-                    let description = result
-                        .get("pkgdesc")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or_default()
-                        .to_string();
-
-                    pkgs.push(OfficialPackage {
-                        name: pkgname.to_string(),
-                        repo: repo_name.to_string(),
-                        arch: arch_name.to_string(),
-                        version,
-                        description,
-                    });
-                }
-
-                // Check if there are more pages
-                let num_pages = json.get("num_pages").and_then(|v| v.as_u64()).unwrap_or(1);
-                has_more = page < num_pages;
+                let parsed = parse_api_page(&json, repo, arch)?;
+                pkgs.extend(parsed.packages);
+                has_more = page < parsed.num_pages;
                 page += 1;
             }
         }
     }
 
-    // Deduplicate by (repo, name)
-    pkgs.sort_by(|a, b| a.repo.cmp(&b.repo).then(a.name.cmp(&b.name)));
-    pkgs.dedup_by(|a, b| a.repo == b.repo && a.name == b.name);
-
     let mut index = OfficialIndex {
-        pkgs,
+        pkgs: dedup_across_repos(pkgs),
+        fetched_at: Some(SystemTime::now()),
         name_to_idx: std::collections::HashMap::new(),
     };
     index.rebuild_name_index();
@@ -330,10 +345,339 @@ async fn fetch_via_api(client: &ArchClient) -> Result<OfficialIndex> {
     Ok(index)
 }
 
+/// What: Keep one entry per package name.
+///
+/// Inputs:
+/// - `pkgs`: Packages in fetch order (repositories in priority order).
+///
+/// Output:
+/// - The first entry of every name, sorted by `(repo, name)`.
+///
+/// Details:
+/// - Mirrors pacman, which installs a package from the first repository listing it.
+#[cfg(feature = "aur")]
+fn dedup_across_repos(pkgs: Vec<OfficialPackage>) -> Vec<OfficialPackage> {
+    let mut seen = std::collections::HashSet::new();
+    let mut pkgs: Vec<OfficialPackage> = pkgs
+        .into_iter()
+        .filter(|pkg| seen.insert(pkg.name.clone()))
+        .collect();
+    pkgs.sort_by(|a, b| a.repo.cmp(&b.repo).then(a.name.cmp(&b.name)));
+    pkgs
+}
+
+/// What: Parse one page of Arch Packages API search results.
+///
+/// Inputs:
+/// - `json`: Decoded response of `PACKAGES_SEARCH_URL`.
+/// - `repo`: Repository that was queried, used when a result has no `repo` field.
+/// - `arch`: Architecture that was queried, used when a result has no `arch` field.
+///
+/// Output:
+/// - The page's packages with repository, description and metadata, and the page count.
+///
+/// Details:
+/// - Versions are `pkgver-pkgrel`; `compressed_size` becomes the download size.
+///
+/// # Errors
+///
+/// - Returns `Err(ArchToolkitError::Parse)` if `results` or a result's `pkgname` is missing.
+#[cfg(feature = "aur")]
+fn parse_api_page(json: &serde_json::Value, repo: &str, arch: &str) -> Result<ApiPage> {
+    let results = json
+        .get("results")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| {
+            ArchToolkitError::Parse(format!(
+                "Invalid API response: missing 'results' array for repo={repo}, arch={arch}"
+            ))
+        })?;
+
+    let str_field = |result: &serde_json::Value, key: &str| {
+        result
+            .get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+    let size_field = |result: &serde_json::Value, key: &str| {
+        result
+            .get(key)
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or_default()
+    };
+
+    let packages = results
+        .iter()
+        .map(|result| {
+            let pkgname = result
+                .get("pkgname")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| {
+                    ArchToolkitError::Parse(
+                        "Invalid API response: missing 'pkgname' field".to_string(),
+                    )
+                })?;
+            let version = result
+                .get("pkgver")
+                .and_then(|v| v.as_str())
+                .map(|v| {
+                    let rel = result.get("pkgrel").and_then(|r| r.as_str()).unwrap_or("");
+                    if rel.is_empty() {
+                        v.to_string()
+                    } else {
+                        format!("{v}-{rel}")
+                    }
+                })
+                .unwrap_or_default();
+
+            Ok(OfficialPackage {
+                name: pkgname.to_string(),
+                repo: result
+                    .get("repo")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or(repo)
+                    .to_string(),
+                arch: result
+                    .get("arch")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or(arch)
+                    .to_string(),
+                version,
+                description: str_field(result, "pkgdesc"),
+                packager: str_field(result, "packager"),
+                download_size: size_field(result, "compressed_size"),
+                installed_size: size_field(result, "installed_size"),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let num_pages = json
+        .get("num_pages")
+        .and_then(serde_json::Value::as_u64)
+        .unwrap_or(1);
+
+    Ok(ApiPage {
+        packages,
+        num_pages,
+    })
+}
+
+/// What: Fill the metadata `pacman -Sl` does not list from the Arch Packages API.
+///
+/// Inputs:
+/// - `client`: Client used for the API requests.
+/// - `index`: Index fetched via pacman, updated in place.
+///
+/// Output:
+/// - `Ok(())` once descriptions, architectures, packagers and sizes were copied from the API.
+///
+/// Details:
+/// - Opt-in: crawls every page of the Packages API for core, extra and multilib, which takes
+///   dozens of rate-limited archlinux.org requests.
+/// - Packages are matched by repository and name; the locally synced version is kept.
+/// - Requires `feature = "aur"` to be enabled.
+///
+/// # Errors
+///
+/// - Returns `Err(ArchToolkitError::Http)` if the API answers with a 4xx/5xx status, or
+///   `Err(ArchToolkitError::Parse)` if HTTP requests fail or responses are invalid;
+///   `index` is left unchanged.
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::ArchClient;
+/// use arch_toolkit::index::{fetch_official_index_async, fill_api_metadata};
+///
+/// # async fn example() -> Result<(), arch_toolkit::error::ArchToolkitError> {
+/// let client = ArchClient::new()?;
+/// let mut index = fetch_official_index_async().await?;
+/// fill_api_metadata(&client, &mut index).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "aur")]
+pub async fn fill_api_metadata(client: &ArchClient, index: &mut OfficialIndex) -> Result<()> {
+    let api = fetch_via_api(client, PACKAGES_SEARCH_URL, &[DEFAULT_ARCH, "any"]).await?;
+    merge_api_metadata(index, api);
+    Ok(())
+}
+
+/// What: Copy API metadata onto packages of a pacman-fetched index.
+///
+/// Inputs:
+/// - `index`: Index fetched via pacman, updated in place.
+/// - `api`: Index fetched from the Arch Packages API.
+///
+/// Details:
+/// - Packages are matched by `(repo, name)`; only empty fields and zero sizes are filled, so
+///   the locally synced version is kept.
+#[cfg(feature = "aur")]
+fn merge_api_metadata(index: &mut OfficialIndex, api: OfficialIndex) {
+    let mut by_key: std::collections::HashMap<(String, String), OfficialPackage> = api
+        .pkgs
+        .into_iter()
+        .map(|pkg| ((pkg.repo.clone(), pkg.name.clone()), pkg))
+        .collect();
+    for pkg in &mut index.pkgs {
+        let Some(api_pkg) = by_key.remove(&(pkg.repo.clone(), pkg.name.clone())) else {
+            continue;
+        };
+        if pkg.description.is_empty() {
+            pkg.description = api_pkg.description;
+        }
+        if pkg.arch.is_empty() {
+            pkg.arch = api_pkg.arch;
+        }
+        if pkg.packager.is_empty() {
+            pkg.packager = api_pkg.packager;
+        }
+        if pkg.download_size == 0 {
+            pkg.download_size = api_pkg.download_size;
+        }
+        if pkg.installed_size == 0 {
+            pkg.installed_size = api_pkg.installed_size;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Page 1 of 3 of `/packages/search/json/?repo=core&arch=x86_64`, trimmed to two results.
+    #[cfg(feature = "aur")]
+    const CORE_PAGE: &str = r#"{
+        "version": 2,
+        "limit": 250,
+        "valid": true,
+        "num_pages": 3,
+        "page": 1,
+        "results": [
+            {
+                "pkgname": "pacman",
+                "pkgbase": "pacman",
+                "repo": "core",
+                "arch": "x86_64",
+                "pkgver": "7.0.0.r6.gc685ae6",
+                "pkgrel": "2",
+                "epoch": 0,
+                "pkgdesc": "A library-based package manager with dependency support",
+                "url": "https://www.archlinux.org/pacman/",
+                "compressed_size": 930117,
+                "installed_size": 4758417,
+                "packager": "Morten Linderud <foxboron@archlinux.org>",
+                "groups": ["base-devel"],
+                "flag_date": null
+            },
+            {
+                "pkgname": "tzdata",
+                "pkgbase": "tzdata",
+                "repo": "core",
+                "arch": "any",
+                "pkgver": "2025b",
+                "pkgrel": "1",
+                "pkgdesc": "Sources for time zone and daylight saving time data",
+                "compressed_size": 191233,
+                "installed_size": 1697457,
+                "packager": "Andreas Radke <andyrtr@archlinux.org>"
+            }
+        ]
+    }"#;
+
+    #[cfg(feature = "aur")]
+    #[test]
+    /// What: Parse a captured Arch Packages API page.
+    ///
+    /// Inputs:
+    /// - `CORE_PAGE`: first of three pages with an `x86_64` and an `any` package.
+    ///
+    /// Output:
+    /// - Repository, description, version, architecture and sizes are populated, and the page
+    ///   count is read from `num_pages`.
+    fn parse_api_page_populates_repo_and_description() {
+        let json: serde_json::Value = serde_json::from_str(CORE_PAGE).expect("valid fixture");
+
+        let page = parse_api_page(&json, "core", "x86_64").expect("page parses");
+
+        assert_eq!(page.num_pages, 3);
+        assert_eq!(page.packages.len(), 2);
+        let pacman = &page.packages[0];
+        assert_eq!(pacman.name, "pacman");
+        assert_eq!(pacman.repo, "core");
+        assert_eq!(
+            pacman.description,
+            "A library-based package manager with dependency support"
+        );
+        assert_eq!(pacman.version, "7.0.0.r6.gc685ae6-2");
+        assert_eq!(pacman.download_size, 930_117);
+        let tzdata = &page.packages[1];
+        assert_eq!(tzdata.repo, "core");
+        assert_eq!(tzdata.arch, "any");
+        assert_eq!(
+            tzdata.description,
+            "Sources for time zone and daylight saving time data"
+        );
+
+        let missing = serde_json::json!({ "results": [{ "repo": "core" }] });
+        assert!(parse_api_page(&missing, "core", "x86_64").is_err());
+        let single = serde_json::json!({ "results": [] });
+        assert_eq!(
+            parse_api_page(&single, "core", "x86_64")
+                .expect("empty page parses")
+                .num_pages,
+            1
+        );
+    }
+
+    #[cfg(feature = "aur")]
+    #[test]
+    /// What: Fill pacman index metadata from an API index.
+    ///
+    /// Inputs:
+    /// - Pacman index with `pacman` in `core` and a package unknown to the API.
+    ///
+    /// Output:
+    /// - `pacman` gains description and packager but keeps its local version; the other
+    ///   package is untouched.
+    fn merge_api_metadata_fills_missing_fields() {
+        let local = |name: &str, version: &str| OfficialPackage {
+            name: name.to_string(),
+            repo: "core".to_string(),
+            arch: String::new(),
+            version: version.to_string(),
+            description: String::new(),
+            packager: String::new(),
+            download_size: 0,
+            installed_size: 0,
+        };
+        let mut index = OfficialIndex::new(vec![local("pacman", "7.0.0-1"), local("custom", "1")]);
+        let json: serde_json::Value = serde_json::from_str(CORE_PAGE).expect("valid fixture");
+        let api = OfficialIndex::new(
+            parse_api_page(&json, "core", "x86_64")
+                .expect("page parses")
+                .packages,
+        );
+
+        merge_api_metadata(&mut index, api);
+
+        let pacman = index
+            .find_package_by_name("pacman")
+            .expect("pacman indexed");
+        assert_eq!(pacman.version, "7.0.0-1");
+        assert_eq!(pacman.arch, "x86_64");
+        assert_eq!(
+            pacman.description,
+            "A library-based package manager with dependency support"
+        );
+        assert_eq!(pacman.packager, "Morten Linderud <foxboron@archlinux.org>");
+        let custom = index
+            .find_package_by_name("custom")
+            .expect("custom indexed");
+        assert!(custom.description.is_empty());
+    }
+
     #[test]
     /// What: Verify `fetch_via_pacman` parses pacman output correctly.
     ///
@@ -353,13 +697,10 @@ mod tests {
         let result = fetch_via_pacman();
         // Result depends on system state (pacman may or may not be available)
         // We just verify it doesn't panic and returns a Result
-        match result {
-            Ok(index) => {
-                assert!(!index.pkgs.is_empty() || index.pkgs.is_empty()); // Always true, just checking structure
-            }
-            Err(_) => {
-                // Pacman unavailable, which is acceptable
-            }
+        if let Ok(index) = result {
+            assert!(!index.pkgs.is_empty() || index.pkgs.is_empty()); // Always true, just checking structure
+        } else {
+            // Pacman unavailable, which is acceptable
         }
     }
 
@@ -387,7 +728,7 @@ mod tests {
             Err(e) => {
                 // Both methods failed, which is acceptable in test environment
                 // Error should be descriptive
-                let error_msg = format!("{}", e);
+                let error_msg = format!("{e}");
                 assert!(!error_msg.is_empty());
             }
         }
@@ -409,14 +750,160 @@ mod tests {
         let result = fetch_official_index_async().await;
         // Result depends on system state
         // We just verify it returns a Result and doesn't panic
-        match result {
-            Ok(index) => {
-                // Success
-                assert!(index.pkgs.is_empty() || !index.pkgs.is_empty());
-            }
-            Err(_) => {
-                // Both methods failed, which is acceptable in test environment
-            }
+        if let Ok(index) = result {
+            // Success
+            assert!(index.pkgs.is_empty() || !index.pkgs.is_empty());
+        } else {
+            // Both methods failed, which is acceptable in test environment
+        }
+    }
+
+    #[cfg(feature = "aur")]
+    #[tokio::test]
+    /// What: Report an error status of the Packages API as `ArchToolkitError::Http`.
+    ///
+    /// Inputs:
+    /// - Mock server answering every search with 503.
+    ///
+    /// Output:
+    /// - `Http` error carrying status 503.
+    async fn fetch_via_api_maps_error_status_to_http() {
+        use crate::client::RetryPolicy;
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let client = ArchClient::builder()
+            .retry_policy(RetryPolicy {
+                circuit_breaker_threshold: 0,
+                ..RetryPolicy::default()
+            })
+            .build()
+            .expect("client builds");
+        let err = fetch_via_api(
+            &client,
+            &format!("{}/packages/search/json/", server.uri()),
+            &["any"],
+        )
+        .await
+        .expect_err("error status fails the fetch");
+
+        assert!(
+            matches!(err, ArchToolkitError::Http { status: 503, .. }),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    #[cfg(feature = "aur")]
+    #[tokio::test]
+    /// What: Fetch a paginated, architecture-filtered index from a mock Packages API.
+    ///
+    /// Inputs:
+    /// - Mock server with two `aarch64` pages for core, an `any` page for core and extra where
+    ///   extra repeats core's `tzdata`, and empty pages for everything else.
+    ///
+    /// Output:
+    /// - Packages of every page are merged, `tzdata` is kept once from core, and no `x86_64`
+    ///   page is requested.
+    async fn fetch_via_api_merges_pages_and_filters_arch() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let page = |num_pages: u32, results: &[(&str, &str, &str)]| {
+            let results: Vec<serde_json::Value> = results
+                .iter()
+                .map(|(name, repo, arch)| {
+                    serde_json::json!({
+                        "pkgname": name,
+                        "repo": repo,
+                        "arch": arch,
+                        "pkgver": "1.0",
+                        "pkgrel": "1",
+                        "pkgdesc": format!("{name} package"),
+                    })
+                })
+                .collect();
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "num_pages": num_pages, "results": results }))
+        };
+
+        let server = MockServer::start().await;
+        let search = "/packages/search/json/";
+        for (repo, arch, page_no, response) in [
+            (
+                "core",
+                "aarch64",
+                "1",
+                page(2, &[("pacman", "core", "aarch64")]),
+            ),
+            (
+                "core",
+                "aarch64",
+                "2",
+                page(2, &[("glibc", "core", "aarch64")]),
+            ),
+            ("core", "any", "1", page(1, &[("tzdata", "core", "any")])),
+            (
+                "extra",
+                "any",
+                "1",
+                page(
+                    1,
+                    &[("tzdata", "extra", "any"), ("python-six", "extra", "any")],
+                ),
+            ),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(search))
+                .and(query_param("repo", repo))
+                .and(query_param("arch", arch))
+                .and(query_param("page", page_no))
+                .respond_with(response)
+                .expect(1)
+                .mount(&server)
+                .await;
         }
+        Mock::given(method("GET"))
+            .and(query_param("arch", "x86_64"))
+            .respond_with(page(1, &[]))
+            .expect(0)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(search))
+            .respond_with(page(1, &[]))
+            .with_priority(10)
+            .mount(&server)
+            .await;
+
+        let client = ArchClient::new().expect("client builds");
+        let index = fetch_via_api(
+            &client,
+            &format!("{}{search}", server.uri()),
+            &["aarch64", "any"],
+        )
+        .await
+        .expect("index is fetched");
+
+        let entries: Vec<(&str, &str, &str)> = index
+            .pkgs
+            .iter()
+            .map(|pkg| (pkg.repo.as_str(), pkg.name.as_str(), pkg.arch.as_str()))
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                ("core", "glibc", "aarch64"),
+                ("core", "pacman", "aarch64"),
+                ("core", "tzdata", "any"),
+                ("extra", "python-six", "any"),
+            ]
+        );
+        assert!(index.find_package_by_name("python-six").is_some());
     }
 }
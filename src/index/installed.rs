@@ -1,10 +1,121 @@
 //! Installed package query functions for the index module.
 
 use std::collections::HashSet;
-use std::process::{Command, Stdio};
+use std::io::{self, ErrorKind};
+use std::process::{Command, Output, Stdio};
 
 use crate::error::{ArchToolkitError, Result};
 
+/// What: Run pacman on the local system.
+///
+/// Inputs:
+/// - `args`: Arguments passed to `pacman`.
+///
+/// Output:
+/// - The process output, whatever the exit status.
+///
+/// Details:
+/// - Sets `LC_ALL=C` and `LANG=C` for consistent locale-independent output.
+///
+/// # Errors
+///
+/// Returns an `io::Error` when pacman cannot be started (`ErrorKind::NotFound` when missing).
+fn system_pacman(args: &[&str]) -> io::Result<Output> {
+    tracing::debug!("Running: pacman {}", args.join(" "));
+    Command::new("pacman")
+        .args(args)
+        .env("LC_ALL", "C")
+        .env("LANG", "C")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+}
+
+/// What: Map a failure to start pacman to an `ArchToolkitError`.
+///
+/// Inputs:
+/// - `args`: Arguments pacman was invoked with.
+/// - `err`: I/O error returned when spawning the process.
+///
+/// Output:
+/// - `PacmanUnavailable` for `ErrorKind::NotFound`, `Parse` otherwise.
+fn spawn_error(args: &[&str], err: &io::Error) -> ArchToolkitError {
+    let command = format!("pacman {}", args.join(" "));
+    if err.kind() == ErrorKind::NotFound {
+        ArchToolkitError::PacmanUnavailable { command }
+    } else {
+        ArchToolkitError::Parse(format!("Failed to execute {command}: {err}"))
+    }
+}
+
+/// What: List installed packages through an injectable pacman runner.
+///
+/// Inputs:
+/// - `pacman`: Runs pacman with the given arguments (`system_pacman` outside tests).
+///
+/// Output:
+/// - Installed package names from `pacman -Qq`.
+///
+/// # Errors
+///
+/// - Returns `Err(ArchToolkitError::PacmanUnavailable)` when pacman is not installed.
+/// - Returns `Err(ArchToolkitError::Parse)` when pacman fails to start or exits with an error.
+fn installed_packages_with(
+    pacman: &dyn Fn(&[&str]) -> io::Result<Output>,
+) -> Result<HashSet<String>> {
+    let args = ["-Qq"];
+    let output = pacman(&args).map_err(|e| spawn_error(&args, &e))?;
+    if !output.status.success() {
+        return Err(ArchToolkitError::Parse(format!(
+            "pacman -Qq exited with status {:?}: {}",
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    let packages: HashSet<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    tracing::debug!(
+        "Successfully retrieved {} installed packages",
+        packages.len()
+    );
+    Ok(packages)
+}
+
+/// What: Check whether a package is installed through an injectable pacman runner.
+///
+/// Inputs:
+/// - `name`: Package name to check.
+/// - `pacman`: Runs pacman with the given arguments (`system_pacman` outside tests).
+///
+/// Output:
+/// - `true` if `pacman -Q name` succeeds, `false` if pacman reports the package as not found.
+///
+/// # Errors
+///
+/// - Returns `Err(ArchToolkitError::PacmanUnavailable)` when pacman is not installed.
+/// - Returns `Err(ArchToolkitError::Parse)` when pacman fails to start or fails for another
+///   reason than an unknown package.
+fn is_installed_with(name: &str, pacman: &dyn Fn(&[&str]) -> io::Result<Output>) -> Result<bool> {
+    let args = ["-Q", name];
+    let output = pacman(&args).map_err(|e| spawn_error(&args, &e))?;
+    if output.status.success() {
+        return Ok(true);
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stderr.contains("was not found") {
+        return Ok(false);
+    }
+    Err(ArchToolkitError::Parse(format!(
+        "pacman -Q {name} exited with status {:?}: {}",
+        output.status.code(),
+        stderr.trim()
+    )))
+}
+
 /// What: Query pacman for all installed packages and optionally update a cache.
 ///
 /// Inputs:
@@ -18,7 +129,8 @@ use crate::error::{ArchToolkitError, Result};
 /// - Uses `pacman -Qq` to query the local database.
 /// - If `cache` is provided, updates it with the results.
 /// - Sets `LC_ALL=C` and `LANG=C` for consistent locale-independent output.
-/// - Logs errors for diagnostics but returns empty set to avoid blocking operations.
+/// - Logs errors for diagnostics but returns empty set to avoid blocking operations; use
+///   `get_installed_packages()` to receive them instead.
 ///
 /// # Errors
 ///
@@ -37,45 +149,10 @@ use crate::error::{ArchToolkitError, Result};
 /// ```
 #[allow(clippy::implicit_hasher)]
 pub fn refresh_installed_cache(cache: Option<&mut HashSet<String>>) -> Result<HashSet<String>> {
-    tracing::debug!("Running: pacman -Qq");
-    let output = Command::new("pacman")
-        .args(["-Qq"])
-        .env("LC_ALL", "C")
-        .env("LANG", "C")
-        .stdin(Stdio::null())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output();
-
-    let packages = match output {
-        Ok(output) => {
-            if output.status.success() {
-                let text = String::from_utf8_lossy(&output.stdout);
-                let packages: HashSet<String> = text
-                    .lines()
-                    .map(|s| s.trim().to_string())
-                    .filter(|s| !s.is_empty())
-                    .collect();
-                tracing::debug!(
-                    "Successfully retrieved {} installed packages",
-                    packages.len()
-                );
-                packages
-            } else {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                tracing::error!(
-                    "pacman -Qq failed with status {:?}: {}",
-                    output.status.code(),
-                    stderr
-                );
-                HashSet::new()
-            }
-        }
-        Err(e) => {
-            tracing::error!("Failed to execute pacman -Qq: {}", e);
-            HashSet::new()
-        }
-    };
+    let packages = installed_packages_with(&system_pacman).unwrap_or_else(|e| {
+        tracing::error!("Failed to list installed packages: {}", e);
+        HashSet::new()
+    });
 
     // Update cache if provided
     if let Some(cache_ref) = cache {
@@ -145,7 +222,8 @@ pub async fn refresh_installed_cache_async(
 /// Details:
 /// - If `cache` is provided, checks membership in the cache (O(1) lookup).
 /// - If `cache` is `None`, queries pacman directly using `pacman -Q`.
-/// - Gracefully degrades: returns `false` on error.
+/// - Gracefully degrades: returns `false` on error; see `is_installed_strict()` to tell a
+///   missing package from a missing pacman.
 ///
 /// # Example
 ///
@@ -160,28 +238,51 @@ pub async fn refresh_installed_cache_async(
 #[must_use]
 #[allow(clippy::implicit_hasher)]
 pub fn is_installed(name: &str, cache: Option<&HashSet<String>>) -> bool {
-    if let Some(cache_ref) = cache {
-        return cache_ref.contains(name);
-    }
-
-    // Query pacman directly if no cache
-    tracing::debug!("Running: pacman -Q {}", name);
-    let output = Command::new("pacman")
-        .args(["-Q", name])
-        .env("LC_ALL", "C")
-        .env("LANG", "C")
-        .stdin(Stdio::null())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output();
+    is_installed_strict(name, cache).unwrap_or_else(|e| {
+        tracing::error!("Failed to check whether {} is installed: {}", name, e);
+        false
+    })
+}
 
-    match output {
-        Ok(output) => output.status.success(),
-        Err(e) => {
-            tracing::error!("Failed to execute pacman -Q {}: {}", name, e);
-            false
-        }
+/// What: Check if a package is installed, reporting pacman failures.
+///
+/// Inputs:
+/// - `name`: Package name to check.
+/// - `cache`: Optional reference to a `HashSet<String>` containing installed package names.
+///
+/// Output:
+/// - Returns `Ok(true)` if the package is installed, `Ok(false)` if it is not.
+///
+/// Details:
+/// - If `cache` is provided, checks membership in the cache and never fails.
+/// - If `cache` is `None`, queries pacman directly using `pacman -Q`.
+/// - Installed state is local, so there is no API fallback.
+///
+/// # Errors
+///
+/// - Returns `Err(ArchToolkitError::PacmanUnavailable)` when pacman is not installed.
+/// - Returns `Err(ArchToolkitError::Parse)` when pacman fails to start or fails for another
+///   reason than an unknown package.
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::error::ArchToolkitError;
+/// use arch_toolkit::index::is_installed_strict;
+///
+/// match is_installed_strict("vim", None) {
+///     Ok(true) => println!("vim is installed"),
+///     Ok(false) => println!("vim is not installed"),
+///     Err(ArchToolkitError::PacmanUnavailable { .. }) => println!("Not an Arch system"),
+///     Err(e) => eprintln!("pacman failed: {e}"),
+/// }
+/// ```
+#[allow(clippy::implicit_hasher)]
+pub fn is_installed_strict(name: &str, cache: Option<&HashSet<String>>) -> Result<bool> {
+    if let Some(cache_ref) = cache {
+        return Ok(cache_ref.contains(name));
     }
+    is_installed_with(name, &system_pacman)
 }
 
 /// What: Query pacman directly for all installed packages without caching.
@@ -191,17 +292,17 @@ pub fn is_installed(name: &str, cache: Option<&HashSet<String>>) -> bool {
 ///
 /// Output:
 /// - Returns `Ok(HashSet<String>)` containing all installed package names.
-/// - Returns `Ok(HashSet::new())` on failure (graceful degradation).
 ///
 /// Details:
 /// - Direct query to pacman, no caching involved.
-/// - Reuses the same logic as `refresh_installed_cache` but without cache parameter.
+/// - Unlike `refresh_installed_cache`, failures are reported instead of yielding an empty set.
+/// - Installed state is local, so there is no API fallback.
 /// - Sets `LC_ALL=C` and `LANG=C` for consistent locale-independent output.
 ///
 /// # Errors
 ///
-/// This function does not return errors - it gracefully degrades by returning an empty set.
-/// Errors are logged using `tracing::error` for diagnostics.
+/// - Returns `Err(ArchToolkitError::PacmanUnavailable)` when pacman is not installed.
+/// - Returns `Err(ArchToolkitError::Parse)` when pacman fails to start or exits with an error.
 ///
 /// # Example
 ///
@@ -212,7 +313,7 @@ pub fn is_installed(name: &str, cache: Option<&HashSet<String>>) -> bool {
 /// println!("Found {} installed packages", packages.len());
 /// ```
 pub fn get_installed_packages() -> Result<HashSet<String>> {
-    refresh_installed_cache(None)
+    installed_packages_with(&system_pacman)
 }
 
 #[cfg(test)]
@@ -290,20 +391,95 @@ mod tests {
     }
 
     #[test]
-    /// What: Verify `get_installed_packages` returns `HashSet`.
+    /// What: Verify `get_installed_packages` returns `HashSet` or reports a missing pacman.
     ///
     /// Inputs:
     /// - None: Direct query to pacman.
     ///
     /// Output:
-    /// - Returns `Ok(HashSet<String>)` (may be empty if pacman unavailable).
+    /// - Returns `Ok(HashSet<String>)`, or `PacmanUnavailable` if pacman is not installed.
     ///
     /// Details:
-    /// - Tests that function returns correct type and handles errors gracefully.
+    /// - Tests that function returns correct type without depending on the system state.
     fn get_installed_packages_returns_hashset() {
-        let result = get_installed_packages();
-        assert!(result.is_ok());
-        // Result may be empty if pacman unavailable, which is graceful degradation
+        match get_installed_packages() {
+            Ok(_) | Err(ArchToolkitError::PacmanUnavailable { .. }) => {}
+            Err(e) => panic!("unexpected error: {e}"),
+        }
+    }
+
+    /// What: Build a pacman runner answering every invocation with one exit code and output.
+    fn canned_pacman(
+        code: i32,
+        stdout: &'static str,
+        stderr: &'static str,
+    ) -> impl Fn(&[&str]) -> io::Result<Output> {
+        move |_args| {
+            use std::os::unix::process::ExitStatusExt;
+            Ok(Output {
+                status: std::process::ExitStatus::from_raw(code << 8),
+                stdout: stdout.as_bytes().to_vec(),
+                stderr: stderr.as_bytes().to_vec(),
+            })
+        }
+    }
+
+    /// What: Pacman runner failing to start like a missing binary.
+    fn missing_pacman(_args: &[&str]) -> io::Result<Output> {
+        Err(io::Error::from(ErrorKind::NotFound))
+    }
+
+    #[test]
+    /// What: Verify installed queries report a missing pacman.
+    ///
+    /// Inputs:
+    /// - Runner failing with `ErrorKind::NotFound`.
+    ///
+    /// Output:
+    /// - `PacmanUnavailable` naming the command from both the list and the strict check.
+    fn installed_queries_report_missing_pacman() {
+        let err = installed_packages_with(&missing_pacman).expect_err("pacman is missing");
+        assert!(
+            matches!(&err, ArchToolkitError::PacmanUnavailable { command } if command == "pacman -Qq"),
+            "unexpected error: {err}"
+        );
+
+        let err = is_installed_with("vim", &missing_pacman).expect_err("pacman is missing");
+        assert!(
+            matches!(&err, ArchToolkitError::PacmanUnavailable { command } if command == "pacman -Q vim"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    /// What: Verify installed queries through a canned runner.
+    ///
+    /// Inputs:
+    /// - Successful `-Qq` listing, an unknown package, and an unrelated pacman failure.
+    ///
+    /// Output:
+    /// - Names are listed, the unknown package is not installed, and the failure is an error.
+    fn installed_queries_with_canned_runner() {
+        let packages = installed_packages_with(&canned_pacman(0, "vim\ngit\n\n", ""))
+            .expect("listing succeeds");
+        assert_eq!(
+            packages,
+            HashSet::from(["vim".to_string(), "git".to_string()])
+        );
+
+        assert!(is_installed_with("vim", &canned_pacman(0, "vim 9.1-1\n", "")).expect("query"));
+        let not_found = canned_pacman(1, "", "error: package 'nope' was not found\n");
+        assert!(!is_installed_with("nope", &not_found).expect("query"));
+
+        let broken = canned_pacman(1, "", "error: could not open database\n");
+        assert!(matches!(
+            installed_packages_with(&broken),
+            Err(ArchToolkitError::Parse(_))
+        ));
+        assert!(matches!(
+            is_installed_with("vim", &broken),
+            Err(ArchToolkitError::Parse(_))
+        ));
     }
 
     #[cfg(feature = "index")]
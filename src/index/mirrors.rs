@@ -0,0 +1,269 @@
+//! Mirrorlist parsing and mirror probing for the index module.
+
+use crate::types::index::Mirror;
+#[cfg(feature = "aur")]
+use crate::types::index::MirrorProbe;
+
+#[cfg(feature = "aur")]
+use crate::client::ArchClient;
+#[cfg(feature = "aur")]
+use futures_util::{StreamExt, stream};
+#[cfg(feature = "aur")]
+use std::time::Instant;
+
+/// Maximum number of mirrors probed concurrently.
+///
+/// Mirrors are independent third-party hosts (not archlinux.org), so they are not subject
+/// to the archlinux.org rate limiter.
+#[cfg(feature = "aur")]
+pub const MIRROR_PROBE_CONCURRENCY: usize = 8;
+
+/// Repository whose database is requested when probing mirrors.
+#[cfg(feature = "aur")]
+const PROBE_REPO: &str = "core";
+
+/// What: Parse mirror entries from pacman mirrorlist contents.
+///
+/// Inputs:
+/// - `text`: Mirrorlist contents (e.g., `/etc/pacman.d/mirrorlist`).
+///
+/// Output:
+/// - Active mirrors in file order.
+///
+/// Details:
+/// - Only uncommented `Server = ...` lines are returned; commented-out servers are skipped.
+/// - URL templates keep their `$repo` and `$arch` placeholders.
+#[must_use]
+pub fn parse_mirrorlist(text: &str) -> Vec<Mirror> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            if key.trim() != "Server" {
+                return None;
+            }
+            let url = value.trim();
+            (!url.is_empty()).then(|| Mirror {
+                url: url.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// What: Probe mirrors for reachability and latency.
+///
+/// Inputs:
+/// - `client`: `ArchClient` whose HTTP client (and timeout) is used.
+/// - `mirrors`: Mirrors to probe.
+/// - `arch`: Architecture substituted for `$arch` (e.g., `x86_64`).
+///
+/// Output:
+/// - One `MirrorProbe` per mirror, in the same order as `mirrors`.
+///
+/// Details:
+/// - Sends a `HEAD` request for the `core` database (`$repo` = `core`) of each mirror.
+/// - Latency is measured until the response headers arrive.
+/// - At most `MIRROR_PROBE_CONCURRENCY` probes run at once.
+/// - Failures are reported as unreachable probes rather than errors.
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::ArchClient;
+/// use arch_toolkit::index::{parse_mirrorlist, probe_mirrors};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = ArchClient::new()?;
+/// let text = std::fs::read_to_string("/etc/pacman.d/mirrorlist")?;
+/// let mirrors = parse_mirrorlist(&text);
+/// let mut probes = probe_mirrors(&client, &mirrors, "x86_64").await;
+/// probes.retain(|p| p.reachable);
+/// probes.sort_by_key(|p| p.latency);
+/// if let Some(fastest) = probes.first() {
+///     println!("Fastest mirror: {}", fastest.url);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "aur")]
+pub async fn probe_mirrors(
+    client: &ArchClient,
+    mirrors: &[Mirror],
+    arch: &str,
+) -> Vec<MirrorProbe> {
+    stream::iter(mirrors)
+        .map(|mirror| probe_mirror(client, mirror, arch))
+        .buffered(MIRROR_PROBE_CONCURRENCY)
+        .collect()
+        .await
+}
+
+/// What: Probe a single mirror's database URL.
+///
+/// Inputs:
+/// - `client`: `ArchClient` for the request.
+/// - `mirror`: Mirror to probe.
+/// - `arch`: Architecture substituted for `$arch`.
+///
+/// Output:
+/// - `MirrorProbe` describing the outcome.
+#[cfg(feature = "aur")]
+async fn probe_mirror(client: &ArchClient, mirror: &Mirror, arch: &str) -> MirrorProbe {
+    let url = mirror_db_url(mirror, PROBE_REPO, arch);
+    let start = Instant::now();
+
+    match client.http_client().head(&url).send().await {
+        Ok(response) if response.status().is_success() => {
+            let last_modified = response
+                .headers()
+                .get(reqwest::header::LAST_MODIFIED)
+                .and_then(|v| v.to_str().ok())
+                .map(ToString::to_string);
+            MirrorProbe {
+                url,
+                reachable: true,
+                latency: Some(start.elapsed()),
+                last_modified,
+            }
+        }
+        Ok(response) => {
+            tracing::debug!(url = %url, status = %response.status(), "mirror probe failed");
+            MirrorProbe {
+                url,
+                reachable: false,
+                latency: None,
+                last_modified: None,
+            }
+        }
+        Err(e) => {
+            tracing::debug!(url = %url, error = %e, "mirror probe failed");
+            MirrorProbe {
+                url,
+                reachable: false,
+                latency: None,
+                last_modified: None,
+            }
+        }
+    }
+}
+
+/// What: Build the database URL for a repository on a mirror.
+///
+/// Inputs:
+/// - `mirror`: Mirror with a URL template.
+/// - `repo`: Repository name substituted for `$repo`.
+/// - `arch`: Architecture substituted for `$arch`.
+///
+/// Output:
+/// - Full URL of `<repo>.db` on the mirror.
+#[cfg(feature = "aur")]
+fn mirror_db_url(mirror: &Mirror, repo: &str, arch: &str) -> String {
+    let base = mirror.url.replace("$repo", repo).replace("$arch", arch);
+    format!("{}/{repo}.db", base.trim_end_matches('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// What: Verify `parse_mirrorlist` returns only active server entries.
+    ///
+    /// Inputs:
+    /// - Mirrorlist with comments, a commented-out server, and two active servers.
+    ///
+    /// Output:
+    /// - The two active servers in file order.
+    fn parse_mirrorlist_skips_commented_servers() {
+        let text = "## Germany\n#Server = https://disabled.example/$repo/os/$arch\nServer = https://a.example/$repo/os/$arch\n\nServer=https://b.example/archlinux/$repo/os/$arch\n";
+        let mirrors = parse_mirrorlist(text);
+        assert_eq!(
+            mirrors,
+            vec![
+                Mirror {
+                    url: "https://a.example/$repo/os/$arch".to_string()
+                },
+                Mirror {
+                    url: "https://b.example/archlinux/$repo/os/$arch".to_string()
+                },
+            ]
+        );
+    }
+
+    #[cfg(feature = "aur")]
+    #[test]
+    fn mirror_db_url_substitutes_placeholders() {
+        let mirror = Mirror {
+            url: "https://a.example/$repo/os/$arch/".to_string(),
+        };
+        assert_eq!(
+            mirror_db_url(&mirror, "core", "x86_64"),
+            "https://a.example/core/os/x86_64/core.db"
+        );
+    }
+
+    #[cfg(feature = "aur")]
+    #[tokio::test]
+    /// What: Probe two mock mirrors with different latency.
+    ///
+    /// Inputs:
+    /// - A fast mirror, a mirror delayed by 200ms, and an unreachable mirror (404).
+    ///
+    /// Output:
+    /// - Probes in input order; the slow mirror reports higher latency; the 404 mirror is
+    ///   unreachable.
+    ///
+    /// Details:
+    /// - The fast mirror's `Last-Modified` header is reported.
+    async fn probe_mirrors_measures_latency() {
+        use std::time::Duration;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let fast = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/core/os/x86_64/core.db"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Last-Modified", "Fri, 16 Oct 2026 08:00:00 GMT"),
+            )
+            .mount(&fast)
+            .await;
+
+        let slow = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/core/os/x86_64/core.db"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(200)))
+            .mount(&slow)
+            .await;
+
+        let missing = MockServer::start().await;
+
+        let mirrors: Vec<Mirror> = [&fast, &slow, &missing]
+            .iter()
+            .map(|server| Mirror {
+                url: format!("{}/$repo/os/$arch", server.uri()),
+            })
+            .collect();
+
+        let client = ArchClient::new().expect("client");
+        let probes = probe_mirrors(&client, &mirrors, "x86_64").await;
+
+        assert_eq!(probes.len(), 3);
+        assert!(probes[0].url.starts_with(&fast.uri()));
+        assert!(probes[0].reachable);
+        assert!(probes[1].reachable);
+        assert!(!probes[2].reachable);
+        assert_eq!(probes[2].latency, None);
+        assert_eq!(
+            probes[0].last_modified.as_deref(),
+            Some("Fri, 16 Oct 2026 08:00:00 GMT")
+        );
+
+        let fast_latency = probes[0].latency.expect("fast latency");
+        let slow_latency = probes[1].latency.expect("slow latency");
+        assert!(slow_latency >= Duration::from_millis(200));
+        assert!(slow_latency > fast_latency);
+    }
+}
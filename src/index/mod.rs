@@ -5,7 +5,11 @@
 //! - **Installed Package Queries** - Query installed packages using `pacman -Q*` commands
 //! - **Explicit Package Tracking** - Track explicitly installed packages with different modes
 //! - **Official Repository Queries** - Search and query official Arch Linux repositories
-//! - **Index Fetching** - Fetch official package index from pacman or Arch Packages API
+//! - **Index Fetching** - Fetch official package index from pacman or Arch Packages API,
+//!   optionally persisted on disk (`fetch_official_index_cached`, requires `cache-disk`)
+//! - **Repository Configuration** - Parse `pacman.conf` for enabled repositories and mirrors
+//! - **Combined Search** - Search official repositories and the AUR together
+//!   (`ArchClient::search_all`, `search_all_with`, requires `aur`)
 //!
 //! # Features
 //!
@@ -23,7 +27,10 @@
 //! arch-toolkit = { version = "0.2", features = ["index", "fuzzy-search"] }
 //! ```
 //!
-//! For API fallback when pacman is unavailable, enable the `aur` feature:
+//! For API fallback when pacman is unavailable, enable the `aur` feature. This covers official
+//! repository queries only; installed package queries need pacman and report
+//! `ArchToolkitError::PacmanUnavailable` without it (`get_installed_packages`,
+//! `is_installed_strict`):
 //!
 //! ```toml
 //! [dependencies]
@@ -102,7 +109,9 @@
 //! ## Search Official Packages
 //!
 //! ```no_run
-//! use arch_toolkit::index::{fetch_official_index_async, search_official};
+//! use arch_toolkit::index::{
+//!     FuzzyOptions, fetch_official_index_async, search_official, search_official_with,
+//! };
 //!
 //! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
 //! // Fetch the official index
@@ -119,6 +128,10 @@
 //! for result in fuzzy_results {
 //!     println!("{} (score: {:?})", result.package.name, result.fuzzy_score);
 //! }
+//!
+//! // Ranked fuzzy search: drop weak matches and keep the top 10
+//! let opts = FuzzyOptions { threshold: 50, limit: 10, ..FuzzyOptions::default() };
+//! let top = search_official_with(&index, "rg", &opts);
 //! # Ok(())
 //! # }
 //! ```
@@ -137,24 +150,62 @@
 mod explicit;
 mod fetch;
 mod installed;
+mod mirrors;
+mod pacman_conf;
+#[cfg(feature = "cache-disk")]
+mod persist;
 mod query;
+mod soname;
+#[cfg(feature = "aur")]
+mod unified;
 
 // Re-export types from types module
 pub use crate::types::index::{
-    IndexQueryResult, InstalledPackagesMode, OfficialIndex, OfficialPackage,
+    FuzzyOptions, IndexQueryResult, InstalledPackagesMode, Mirror, MirrorProbe, OfficialIndex,
+    OfficialPackage, PacmanConf, PacmanRepo, ResultSource, SonameChange, SonameSnapshot,
+    UnifiedResult,
 };
 
 // Re-export installed functions
 pub use installed::{
-    get_installed_packages, is_installed, refresh_installed_cache, refresh_installed_cache_async,
+    get_installed_packages, is_installed, is_installed_strict, refresh_installed_cache,
+    refresh_installed_cache_async,
 };
 
 // Re-export explicit functions
-pub use explicit::{is_explicit, refresh_explicit_cache, refresh_explicit_cache_async};
+pub use explicit::{
+    get_foreign_packages, is_explicit, refresh_explicit_cache, refresh_explicit_cache_async,
+};
 
 // Re-export query functions
-pub use query::{all_official, search_official};
+pub use query::{
+    all_official, get_official, packages_by_packager, search_official, search_official_with,
+};
+
+// Re-export combined official + AUR search
+#[cfg(feature = "aur")]
+pub(crate) use unified::search_all;
+#[cfg(feature = "aur")]
+pub use unified::search_all_with;
+
+// Re-export mirror functions
+pub use mirrors::parse_mirrorlist;
+#[cfg(feature = "aur")]
+pub use mirrors::{MIRROR_PROBE_CONCURRENCY, probe_mirrors};
+
+// Re-export pacman.conf parsing
+pub use pacman_conf::{PACMAN_CONF_PATH, parse_pacman_conf};
+
+// Re-export soname snapshot functions
+pub use soname::snapshot_sonames;
 
 // Re-export fetch functions
 #[cfg(feature = "index")]
 pub use fetch::{fetch_official_index, fetch_official_index_async};
+#[cfg(feature = "aur")]
+pub use fetch::{fetch_official_index_for_arch, fill_api_metadata};
+#[cfg(feature = "cache-disk")]
+pub use persist::{
+    OFFICIAL_INDEX_SCHEMA_VERSION, fetch_official_index_cached, load_official_index,
+    official_index_cache_path, save_official_index,
+};
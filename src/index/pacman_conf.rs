@@ -0,0 +1,240 @@
+//! `pacman.conf` parsing for the index module.
+
+use std::fs;
+use std::path::Path;
+
+use crate::error::{ArchToolkitError, Result};
+use crate::types::index::{Mirror, PacmanConf, PacmanRepo};
+
+/// Default location of the pacman configuration file.
+pub const PACMAN_CONF_PATH: &str = "/etc/pacman.conf";
+
+/// Maximum `Include` nesting depth, guarding against include cycles.
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// What: Section of `pacman.conf` that subsequent lines belong to.
+#[derive(Clone, Copy)]
+enum Section {
+    /// Before the first section header.
+    None,
+    /// `[options]`
+    Options,
+    /// A repository, by index into `PacmanConf::repos`.
+    Repo(usize),
+}
+
+/// What: Parse `pacman.conf` into enabled repositories and options.
+///
+/// Inputs:
+/// - `path`: Configuration file to read; `None` reads `/etc/pacman.conf`.
+///
+/// Output:
+/// - `Ok(PacmanConf)` with repositories in file order.
+///
+/// Details:
+/// - Every section other than `[options]` is an enabled repository; commented-out sections
+///   are ignored
+/// - `Include = <file>` lines are expanded in place (nested includes are followed), so
+///   `Include = /etc/pacman.d/mirrorlist` adds that mirrorlist's servers to the repository
+/// - Unreadable include files are skipped with a warning; glob patterns are not expanded
+/// - Text after `#` is treated as a comment, as pacman does
+///
+/// # Errors
+/// - Returns `Err(ArchToolkitError::Parse)` if the configuration file cannot be read
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::index::parse_pacman_conf;
+///
+/// let conf = parse_pacman_conf(None)?;
+/// for repo in &conf.repos {
+///     println!("[{}] {} servers", repo.name, repo.servers.len());
+/// }
+/// # Ok::<(), arch_toolkit::error::ArchToolkitError>(())
+/// ```
+pub fn parse_pacman_conf(path: Option<&Path>) -> Result<PacmanConf> {
+    let path = path.unwrap_or_else(|| Path::new(PACMAN_CONF_PATH));
+    let text = fs::read_to_string(path)
+        .map_err(|e| ArchToolkitError::Parse(format!("Failed to read {}: {e}", path.display())))?;
+
+    let mut conf = PacmanConf::default();
+    let mut section = Section::None;
+    parse_lines(&text, &mut conf, &mut section, 0);
+    Ok(conf)
+}
+
+/// What: Apply `pacman.conf` lines to the configuration being built.
+///
+/// Inputs:
+/// - `text`: File contents.
+/// - `conf`: Configuration being built.
+/// - `section`: Current section, updated by section headers (also across includes).
+/// - `depth`: Current include nesting depth.
+fn parse_lines(text: &str, conf: &mut PacmanConf, section: &mut Section, depth: usize) {
+    for line in text.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            *section = if name == "options" {
+                Section::Options
+            } else {
+                conf.repos.push(PacmanRepo {
+                    name: name.trim().to_string(),
+                    ..PacmanRepo::default()
+                });
+                Section::Repo(conf.repos.len() - 1)
+            };
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .map_or((line, ""), |(key, value)| (key.trim(), value.trim()));
+
+        if key == "Include" {
+            include_file(value, conf, section, depth);
+            continue;
+        }
+
+        match *section {
+            Section::None => {
+                tracing::debug!(key, "ignoring pacman.conf entry outside a section");
+            }
+            Section::Options => {
+                if key == "ParallelDownloads" {
+                    conf.parallel_downloads = value.parse().ok();
+                }
+                conf.options.insert(key.to_string(), value.to_string());
+            }
+            Section::Repo(idx) => {
+                let repo = &mut conf.repos[idx];
+                match key {
+                    "Server" if !value.is_empty() => repo.servers.push(Mirror {
+                        url: value.to_string(),
+                    }),
+                    "SigLevel" => repo.sig_level = Some(value.to_string()),
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// What: Expand an `Include` directive.
+///
+/// Inputs:
+/// - `path`: Included file path.
+/// - `conf`: Configuration being built.
+/// - `section`: Current section.
+/// - `depth`: Current include nesting depth.
+///
+/// Details:
+/// - Unreadable files and includes nested deeper than `MAX_INCLUDE_DEPTH` are skipped.
+fn include_file(path: &str, conf: &mut PacmanConf, section: &mut Section, depth: usize) {
+    if depth >= MAX_INCLUDE_DEPTH {
+        tracing::warn!(path, "pacman.conf include nesting too deep, skipping");
+        return;
+    }
+    match fs::read_to_string(path) {
+        Ok(text) => parse_lines(&text, conf, section, depth + 1),
+        Err(e) => tracing::warn!(path, error = %e, "failed to read pacman.conf include"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// What: Parse a config with options, repositories, and mirrorlist includes.
+    ///
+    /// Inputs:
+    /// - `pacman.conf` with `[options]`, core/extra via an included mirrorlist, a custom repo
+    ///   with its own server, a commented-out repo, and a missing include.
+    ///
+    /// Output:
+    /// - Enabled repos in order with expanded servers; options and `ParallelDownloads` parsed.
+    fn test_parse_pacman_conf_with_includes() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let mirrorlist = dir.path().join("mirrorlist");
+        fs::write(
+            &mirrorlist,
+            "## Germany\nServer = https://mirror.one/$repo/os/$arch\n\
+             #Server = https://disabled.example/$repo/os/$arch\n\
+             Server = https://mirror.two/$repo/os/$arch\n",
+        )
+        .expect("write mirrorlist");
+
+        let conf_path = dir.path().join("pacman.conf");
+        fs::write(
+            &conf_path,
+            format!(
+                "[options]\nHoldPkg = pacman glibc\nColor\nParallelDownloads = 5 # speed\n\
+                 SigLevel = Required DatabaseOptional\n\n\
+                 [core]\nInclude = {mirrors}\n\n\
+                 [extra]\nInclude = {mirrors}\n\n\
+                 #[multilib]\n#Include = {mirrors}\n\n\
+                 [chaotic-aur]\nSigLevel = Optional TrustAll\n\
+                 Server = https://cdn.chaotic.example/$repo/$arch\n\
+                 Include = {missing}\n",
+                mirrors = mirrorlist.display(),
+                missing = dir.path().join("missing").display(),
+            ),
+        )
+        .expect("write pacman.conf");
+
+        let conf = parse_pacman_conf(Some(&conf_path)).expect("config parses");
+
+        assert_eq!(
+            conf.repo_names().collect::<Vec<_>>(),
+            vec!["core", "extra", "chaotic-aur"]
+        );
+        let core = conf.repo("core").expect("core enabled");
+        assert_eq!(
+            core.servers,
+            vec![
+                Mirror {
+                    url: "https://mirror.one/$repo/os/$arch".to_string()
+                },
+                Mirror {
+                    url: "https://mirror.two/$repo/os/$arch".to_string()
+                },
+            ]
+        );
+        assert_eq!(core.sig_level, None);
+
+        let chaotic = conf.repo("chaotic-aur").expect("custom repo enabled");
+        assert_eq!(chaotic.servers.len(), 1);
+        assert_eq!(chaotic.sig_level.as_deref(), Some("Optional TrustAll"));
+        assert!(conf.repo("multilib").is_none());
+
+        assert_eq!(conf.parallel_downloads, Some(5));
+        assert_eq!(
+            conf.options.get("HoldPkg").map(String::as_str),
+            Some("pacman glibc")
+        );
+        assert_eq!(conf.options.get("Color").map(String::as_str), Some(""));
+    }
+
+    #[test]
+    /// What: Report an unreadable configuration file.
+    ///
+    /// Inputs:
+    /// - Path to a file that does not exist.
+    ///
+    /// Output:
+    /// - `ArchToolkitError::Parse`.
+    fn test_parse_pacman_conf_missing_file() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let err = parse_pacman_conf(Some(&dir.path().join("nope.conf")))
+            .expect_err("missing file is an error");
+        assert!(matches!(err, ArchToolkitError::Parse(_)));
+    }
+}
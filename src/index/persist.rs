@@ -0,0 +1,275 @@
+//! On-disk persistence of the official package index.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ArchToolkitError, Result};
+use crate::index::fetch_official_index_async;
+use crate::types::index::OfficialIndex;
+
+/// Schema version of the persisted index format.
+///
+/// Bump this whenever `OfficialIndex` or `OfficialPackage` change incompatibly; caches
+/// written with another version are ignored and re-fetched.
+pub const OFFICIAL_INDEX_SCHEMA_VERSION: u32 = 1;
+
+/// What: Versioned envelope around a persisted index.
+#[derive(Serialize, Deserialize)]
+struct StoredIndex<T> {
+    /// Schema version the index was written with.
+    version: u32,
+    /// The index (or `IgnoredAny` when only the version is checked).
+    index: T,
+}
+
+/// What: Default location of the persisted official index.
+///
+/// Inputs: None
+///
+/// Output:
+/// - `Some(PathBuf)` under the user cache directory, or `None` if it cannot be determined.
+///
+/// Details:
+/// - Path: `<cache dir>/arch-toolkit/official_index.v<schema>.json`, so each schema version
+///   uses its own file.
+#[must_use]
+pub fn official_index_cache_path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| {
+        dir.join("arch-toolkit").join(format!(
+            "official_index.v{OFFICIAL_INDEX_SCHEMA_VERSION}.json"
+        ))
+    })
+}
+
+/// What: Persist an official index to disk.
+///
+/// Inputs:
+/// - `index`: Index to store.
+/// - `path`: Destination file; parent directories are created.
+///
+/// Output:
+/// - `Ok(())` once the file is written.
+///
+/// Details:
+/// - Stores JSON tagged with `OFFICIAL_INDEX_SCHEMA_VERSION`.
+/// - Writes to a temporary file and renames it, so readers never see a partial index.
+///
+/// # Errors
+/// - Returns `Err(ArchToolkitError::Parse)` if the file cannot be written
+/// - Returns `Err(ArchToolkitError::Json)` if serialization fails
+pub fn save_official_index(index: &OfficialIndex, path: &Path) -> Result<()> {
+    let json = serde_json::to_vec(&StoredIndex {
+        version: OFFICIAL_INDEX_SCHEMA_VERSION,
+        index,
+    })?;
+    let io_err = |e: std::io::Error| {
+        ArchToolkitError::Parse(format!("Failed to write {}: {e}", path.display()))
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(io_err)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, json).map_err(io_err)?;
+    fs::rename(&tmp, path).map_err(io_err)
+}
+
+/// What: Load a persisted official index from disk.
+///
+/// Inputs:
+/// - `path`: File written by `save_official_index()`.
+///
+/// Output:
+/// - `Some(OfficialIndex)` with the name index rebuilt, or `None` if the file is missing,
+///   unreadable, corrupt, or written with a different schema version.
+#[must_use]
+pub fn load_official_index(path: &Path) -> Option<OfficialIndex> {
+    let data = fs::read(path).ok()?;
+    let header: StoredIndex<IgnoredAny> = serde_json::from_slice(&data)
+        .inspect_err(|e| tracing::debug!("Ignoring corrupt official index cache: {}", e))
+        .ok()?;
+    if header.version != OFFICIAL_INDEX_SCHEMA_VERSION {
+        tracing::debug!(
+            "Ignoring official index cache with schema version {} (expected {})",
+            header.version,
+            OFFICIAL_INDEX_SCHEMA_VERSION
+        );
+        return None;
+    }
+    let stored: StoredIndex<OfficialIndex> = serde_json::from_slice(&data).ok()?;
+    let mut index = stored.index;
+    index.rebuild_name_index();
+    Some(index)
+}
+
+/// What: Fetch the official index, reusing the on-disk copy while it is fresh.
+///
+/// Inputs:
+/// - `max_age`: Maximum age of the stored index before it is re-fetched.
+///
+/// Output:
+/// - `Result<OfficialIndex>` with `fetched_at` set.
+///
+/// Details:
+/// - Loads the index from `official_index_cache_path()` and returns it if younger than `max_age`
+/// - Otherwise fetches with `fetch_official_index_async()` and stores the result
+/// - If fetching fails but a stale index is stored, the stale index is returned (check
+///   `OfficialIndex::age()`)
+/// - Failing to store the index is logged and does not fail the call
+/// - Disk I/O runs on a blocking thread
+///
+/// # Errors
+/// - Returns the error from `fetch_official_index_async()` if fetching fails and no stored
+///   index exists
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::index::fetch_official_index_cached;
+/// use std::time::Duration;
+///
+/// # async fn example() -> Result<(), arch_toolkit::error::ArchToolkitError> {
+/// let index = fetch_official_index_cached(Duration::from_hours(6)).await?;
+/// if let Some(age) = index.age() {
+///     println!("index from {} hours ago", age.as_secs() / 3600);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn fetch_official_index_cached(max_age: Duration) -> Result<OfficialIndex> {
+    let Some(path) = official_index_cache_path() else {
+        tracing::debug!("No cache directory, fetching official index without persistence");
+        return fetch_official_index_async().await;
+    };
+
+    let load_path = path.clone();
+    let stored = tokio::task::spawn_blocking(move || load_official_index(&load_path))
+        .await
+        .map_err(|e| ArchToolkitError::Parse(format!("Blocking task failed: {e}")))?;
+    let stored = match stored {
+        Some(index) if index.is_fresh(max_age) => {
+            tracing::debug!(
+                "Using stored official index ({} packages)",
+                index.pkgs.len()
+            );
+            return Ok(index);
+        }
+        other => other,
+    };
+
+    match fetch_official_index_async().await {
+        Ok(index) => {
+            let (index, saved) = tokio::task::spawn_blocking(move || {
+                let saved = save_official_index(&index, &path);
+                (index, saved)
+            })
+            .await
+            .map_err(|e| ArchToolkitError::Parse(format!("Blocking task failed: {e}")))?;
+            if let Err(e) = saved {
+                tracing::warn!("Failed to store official index: {}", e);
+            }
+            Ok(index)
+        }
+        Err(e) => stored.map_or(Err(e), |index| {
+            tracing::warn!("Official index fetch failed, using stale stored index");
+            Ok(index)
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::index::OfficialPackage;
+    use std::time::SystemTime;
+
+    fn sample_index() -> OfficialIndex {
+        let mut index = OfficialIndex {
+            pkgs: vec![OfficialPackage {
+                name: "ripgrep".to_string(),
+                repo: "extra".to_string(),
+                arch: "x86_64".to_string(),
+                version: "14.1.0-1".to_string(),
+                description: "Fast grep".to_string(),
+                packager: String::new(),
+                download_size: 1_500_000,
+                installed_size: 4_000_000,
+            }],
+            fetched_at: Some(SystemTime::now()),
+            ..OfficialIndex::default()
+        };
+        index.rebuild_name_index();
+        index
+    }
+
+    #[test]
+    /// What: Round-trip an index through the versioned on-disk format.
+    ///
+    /// Inputs:
+    /// - Index with one package and a fetch time, saved to a temp dir.
+    ///
+    /// Output:
+    /// - Loaded index equals the original, with the name index rebuilt and freshness kept.
+    fn test_save_and_load_round_trip() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("nested").join("index.json");
+        let index = sample_index();
+
+        save_official_index(&index, &path).expect("index saved");
+        let loaded = load_official_index(&path).expect("index loaded");
+
+        assert_eq!(loaded.pkgs, index.pkgs);
+        assert_eq!(loaded.fetched_at, index.fetched_at);
+        assert!(loaded.find_package_by_name("RIPGREP").is_some());
+        assert_eq!(loaded.name_to_idx.len(), 1);
+        assert!(loaded.is_fresh(Duration::from_mins(1)));
+    }
+
+    #[test]
+    /// What: Ignore stored indexes from another schema version or corrupt files.
+    ///
+    /// Inputs:
+    /// - Envelope with version `OFFICIAL_INDEX_SCHEMA_VERSION + 1`, a truncated file, no file.
+    ///
+    /// Output:
+    /// - `None` in every case.
+    fn test_load_rejects_other_schema_versions() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("index.json");
+
+        let future = serde_json::to_vec(&StoredIndex {
+            version: OFFICIAL_INDEX_SCHEMA_VERSION + 1,
+            index: sample_index(),
+        })
+        .expect("serialize");
+        fs::write(&path, future).expect("write");
+        assert!(load_official_index(&path).is_none());
+
+        fs::write(&path, b"{\"version\":1,\"ind").expect("write");
+        assert!(load_official_index(&path).is_none());
+
+        assert!(load_official_index(&dir.path().join("missing.json")).is_none());
+    }
+
+    #[test]
+    /// What: Indexes without a fetch time are never fresh.
+    ///
+    /// Inputs:
+    /// - Default index and an index fetched two hours ago.
+    ///
+    /// Output:
+    /// - Unknown age is stale; the two-hour-old index is fresh only for larger max ages.
+    fn test_index_freshness() {
+        assert!(!OfficialIndex::default().is_fresh(Duration::from_hours(24)));
+
+        let index = OfficialIndex {
+            fetched_at: SystemTime::now().checked_sub(Duration::from_hours(2)),
+            ..OfficialIndex::default()
+        };
+        assert!(index.is_fresh(Duration::from_hours(3)));
+        assert!(!index.is_fresh(Duration::from_hours(1)));
+    }
+}
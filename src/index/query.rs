@@ -1,6 +1,6 @@
 //! Official repository package search functions for the index module.
 
-use crate::types::index::{IndexQueryResult, OfficialIndex, OfficialPackage};
+use crate::types::index::{FuzzyOptions, IndexQueryResult, OfficialIndex, OfficialPackage};
 
 /// What: Search the official index for packages whose names match `query`.
 ///
@@ -12,13 +12,13 @@ use crate::types::index::{IndexQueryResult, OfficialIndex, OfficialPackage};
 /// Output:
 /// - Vector of `IndexQueryResult` containing matched packages with optional fuzzy scores.
 /// - An empty or whitespace-only query returns an empty list.
-/// - When fuzzy mode is enabled, items are returned with scores for sorting.
 ///
 /// Details:
-/// - When `fuzzy` is `false`, performs a case-insensitive substring match on package names.
-/// - When `fuzzy` is `true`, uses fuzzy matching and returns items with match scores.
+/// - When `fuzzy` is `false`, performs a case-insensitive substring match on package names and
+///   returns results in index order.
+/// - When `fuzzy` is `true`, behaves like `search_official_with()` with `FuzzyOptions::default()`:
+///   every match is returned, ranked by normalized score.
 /// - Fuzzy matching requires the `fuzzy-search` feature flag; if not available, falls back to substring matching.
-/// - Results are not sorted; caller should sort by fuzzy score if needed.
 ///
 /// # Example
 ///
@@ -33,63 +33,147 @@ use crate::types::index::{IndexQueryResult, OfficialIndex, OfficialPackage};
 /// ```
 #[must_use]
 pub fn search_official(index: &OfficialIndex, query: &str, fuzzy: bool) -> Vec<IndexQueryResult> {
+    if fuzzy {
+        return search_official_with(index, query, &FuzzyOptions::default());
+    }
     let ql = query.trim();
     if ql.is_empty() {
         return Vec::new();
     }
+    substring_matches(index, ql, false)
+}
 
-    let mut results = Vec::new();
+/// What: Ranked fuzzy search of the official index with a score threshold and result limit.
+///
+/// Inputs:
+/// - `index`: Reference to the official package index to search.
+/// - `query`: Raw query string to match against package names.
+/// - `opts`: Score threshold, result limit, and case sensitivity.
+///
+/// Output:
+/// - Matches sorted by descending `fuzzy_score`, ties broken by package name, truncated to
+///   `opts.limit` (if non-zero).
+/// - An empty or whitespace-only query returns an empty list.
+///
+/// Details:
+/// - Uses the skim V2 algorithm from the `fuzzy-matcher` crate (the fzf-style scorer used by
+///   `skim`), which rewards consecutive characters and matches at word boundaries.
+/// - Raw skim scores grow with query length, so they are normalized to `0..=100` relative to
+///   the query matched against itself; `fuzzy_score` is therefore comparable across queries.
+/// - Matches scoring below `opts.threshold` are dropped.
+/// - Without the `fuzzy-search` feature, falls back to substring matching: results have no
+///   score, `threshold` is ignored, and results are sorted by name.
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::index::{FuzzyOptions, OfficialIndex, search_official_with};
+///
+/// let index = OfficialIndex::default();
+/// let opts = FuzzyOptions {
+///     threshold: 50,
+///     limit: 10,
+///     ..FuzzyOptions::default()
+/// };
+/// for result in search_official_with(&index, "rg", &opts) {
+///     println!("{} (score: {:?})", result.package.name, result.fuzzy_score);
+/// }
+/// ```
+#[must_use]
+pub fn search_official_with(
+    index: &OfficialIndex,
+    query: &str,
+    opts: &FuzzyOptions,
+) -> Vec<IndexQueryResult> {
+    let ql = query.trim();
+    if ql.is_empty() {
+        return Vec::new();
+    }
 
-    // Create fuzzy matcher if fuzzy matching is requested and available
     #[cfg(feature = "fuzzy-search")]
-    let fuzzy_matcher = if fuzzy {
-        Some(fuzzy_matcher::skim::SkimMatcherV2::default())
-    } else {
-        None
-    };
-
-    // If fuzzy-search feature is not available, always use substring matching
-    // (fuzzy parameter is part of API but ignored when feature disabled)
-    #[cfg(not(feature = "fuzzy-search"))]
-    let _ = fuzzy; // Acknowledge parameter exists but not used without feature
+    let mut results = fuzzy_matches(index, ql, opts);
     #[cfg(not(feature = "fuzzy-search"))]
-    let use_fuzzy = false;
-    #[cfg(feature = "fuzzy-search")]
-    let use_fuzzy = fuzzy;
-
-    for pkg in &index.pkgs {
-        let match_score = if use_fuzzy {
-            #[cfg(feature = "fuzzy-search")]
-            {
-                fuzzy_matcher.as_ref().and_then(|m| {
-                    use fuzzy_matcher::FuzzyMatcher;
-                    m.fuzzy_match(&pkg.name, ql)
-                })
-            }
-            #[cfg(not(feature = "fuzzy-search"))]
-            {
-                None
-            }
-        } else {
-            // Case-insensitive substring matching
-            let name_lower = pkg.name.to_lowercase();
-            let query_lower = ql.to_lowercase();
-            if name_lower.contains(&query_lower) {
-                Some(0) // Use 0 as placeholder score for substring matches
+    let mut results = substring_matches(index, ql, opts.case_sensitive);
+
+    results.sort_by(|a, b| {
+        b.fuzzy_score
+            .cmp(&a.fuzzy_score)
+            .then_with(|| a.package.name.cmp(&b.package.name))
+    });
+    if opts.limit > 0 {
+        results.truncate(opts.limit);
+    }
+    results
+}
+
+/// What: Collect packages whose names contain the query.
+///
+/// Inputs:
+/// - `index`: Official package index.
+/// - `query`: Trimmed, non-empty query.
+/// - `case_sensitive`: Whether to match case exactly.
+///
+/// Output:
+/// - Matches in index order, without fuzzy scores.
+fn substring_matches(
+    index: &OfficialIndex,
+    query: &str,
+    case_sensitive: bool,
+) -> Vec<IndexQueryResult> {
+    let query_lower = query.to_lowercase();
+    index
+        .pkgs
+        .iter()
+        .filter(|pkg| {
+            if case_sensitive {
+                pkg.name.contains(query)
             } else {
-                None
+                pkg.name.to_lowercase().contains(&query_lower)
             }
-        };
+        })
+        .map(|pkg| IndexQueryResult {
+            package: pkg.clone(),
+            fuzzy_score: None,
+        })
+        .collect()
+}
 
-        if let Some(score) = match_score {
-            results.push(IndexQueryResult {
-                package: pkg.clone(),
-                fuzzy_score: if use_fuzzy { Some(score) } else { None },
-            });
-        }
-    }
+/// What: Collect fuzzy matches scoring at least the threshold.
+///
+/// Inputs:
+/// - `index`: Official package index.
+/// - `query`: Trimmed, non-empty query.
+/// - `opts`: Threshold and case sensitivity.
+///
+/// Output:
+/// - Unsorted matches with normalized scores.
+#[cfg(feature = "fuzzy-search")]
+fn fuzzy_matches(index: &OfficialIndex, query: &str, opts: &FuzzyOptions) -> Vec<IndexQueryResult> {
+    use fuzzy_matcher::FuzzyMatcher;
+    use fuzzy_matcher::skim::SkimMatcherV2;
 
-    results
+    let matcher = if opts.case_sensitive {
+        SkimMatcherV2::default().respect_case()
+    } else {
+        SkimMatcherV2::default().ignore_case()
+    };
+    // Best achievable score for this query, used to normalize scores to 0..=100
+    let Some(perfect) = matcher.fuzzy_match(query, query).filter(|score| *score > 0) else {
+        return Vec::new();
+    };
+
+    index
+        .pkgs
+        .iter()
+        .filter_map(|pkg| {
+            let raw = matcher.fuzzy_match(&pkg.name, query)?;
+            let score = (raw.max(0) * 100 / perfect).min(100);
+            (score >= opts.threshold).then(|| IndexQueryResult {
+                package: pkg.clone(),
+                fuzzy_score: Some(score),
+            })
+        })
+        .collect()
 }
 
 /// What: Return all packages from the official index.
@@ -118,6 +202,119 @@ pub fn all_official(index: &OfficialIndex) -> Vec<OfficialPackage> {
     index.pkgs.clone()
 }
 
+/// What: Look up a single package of the official index by its exact name.
+///
+/// Inputs:
+/// - `index`: Reference to the official package index.
+/// - `name`: Exact package name (case-sensitive).
+///
+/// Output:
+/// - `Some(&OfficialPackage)` if a package is named exactly `name`, `None` otherwise.
+///
+/// Details:
+/// - Uses the `name_to_idx` map for an O(1) lookup; indexes built by `OfficialIndex::new()`,
+///   the fetch functions and `load_official_index()` have it populated.
+/// - Falls back to a linear scan only while `name_to_idx` is empty; call
+///   `rebuild_name_index()` after modifying `pkgs`.
+/// - Unlike `OfficialIndex::find_package_by_name()`, differently cased names do not match.
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::index::{fetch_official_index, get_official};
+///
+/// let index = fetch_official_index()?;
+/// if let Some(pkg) = get_official(&index, "ripgrep") {
+///     println!("{}/{} {}", pkg.repo, pkg.name, pkg.version);
+/// }
+/// # Ok::<(), arch_toolkit::error::ArchToolkitError>(())
+/// ```
+#[must_use]
+pub fn get_official<'a>(index: &'a OfficialIndex, name: &str) -> Option<&'a OfficialPackage> {
+    if index.name_to_idx.is_empty() {
+        return index.pkgs.iter().find(|pkg| pkg.name == name);
+    }
+    index
+        .name_to_idx
+        .get(&name.to_lowercase())
+        .and_then(|&idx| index.pkgs.get(idx))
+        .filter(|pkg| pkg.name == name)
+}
+
+/// What: Return all packages from the official index maintained by a given packager.
+///
+/// Inputs:
+/// - `index`: Reference to the official package index.
+/// - `packager`: Packager to match: full identity, display name, or email address.
+///
+/// Output:
+/// - Vector of references to packages whose `packager` field matches.
+/// - An empty or whitespace-only `packager` returns an empty list.
+///
+/// Details:
+/// - Packager fields use the `"Name <email>"` format; matching is case-insensitive against the
+///   full identity, the name part, or the email part.
+/// - Packages without packager metadata never match. Indexes built via `pacman -Sl` carry no
+///   packager information; use an API-fetched index for packager queries.
+/// - Order is preserved from the index.
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::index::{fetch_official_index_async, packages_by_packager};
+///
+/// # async fn example() -> Result<(), arch_toolkit::error::ArchToolkitError> {
+/// let index = fetch_official_index_async().await?;
+/// for pkg in packages_by_packager(&index, "jane@archlinux.org") {
+///     println!("{}/{}", pkg.repo, pkg.name);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn packages_by_packager<'a>(
+    index: &'a OfficialIndex,
+    packager: &str,
+) -> Vec<&'a OfficialPackage> {
+    let wanted = packager.trim();
+    if wanted.is_empty() {
+        return Vec::new();
+    }
+
+    index
+        .pkgs
+        .iter()
+        .filter(|pkg| packager_matches(&pkg.packager, wanted))
+        .collect()
+}
+
+/// What: Check whether a packager identity matches a query.
+///
+/// Inputs:
+/// - `identity`: Packager field value (e.g., `"Jane Doe <jane@archlinux.org>"`).
+/// - `wanted`: Trimmed, non-empty query.
+///
+/// Output:
+/// - `true` if the query equals the full identity, its name part, or its email part.
+///
+/// Details:
+/// - Comparisons are ASCII case-insensitive.
+fn packager_matches(identity: &str, wanted: &str) -> bool {
+    let identity = identity.trim();
+    if identity.is_empty() {
+        return false;
+    }
+    if identity.eq_ignore_ascii_case(wanted) {
+        return true;
+    }
+    let (name, email) = identity
+        .split_once('<')
+        .map_or((identity, ""), |(name, rest)| {
+            (name, rest.trim_end_matches('>'))
+        });
+    name.trim().eq_ignore_ascii_case(wanted) || email.trim().eq_ignore_ascii_case(wanted)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -132,6 +329,9 @@ mod tests {
                     arch: "x86_64".to_string(),
                     version: "14.0.0".to_string(),
                     description: "Fast grep tool".to_string(),
+                    packager: String::new(),
+                    download_size: 0,
+                    installed_size: 0,
                 },
                 OfficialPackage {
                     name: "vim".to_string(),
@@ -139,6 +339,9 @@ mod tests {
                     arch: "x86_64".to_string(),
                     version: "9.0".to_string(),
                     description: "Text editor".to_string(),
+                    packager: String::new(),
+                    download_size: 0,
+                    installed_size: 0,
                 },
                 OfficialPackage {
                     name: "pacman".to_string(),
@@ -146,14 +349,51 @@ mod tests {
                     arch: "x86_64".to_string(),
                     version: "6.1.0".to_string(),
                     description: "Package manager".to_string(),
+                    packager: String::new(),
+                    download_size: 0,
+                    installed_size: 0,
                 },
             ],
+            fetched_at: None,
             name_to_idx: std::collections::HashMap::new(),
         };
         index.rebuild_name_index();
         index
     }
 
+    #[test]
+    /// What: Look up packages by exact name.
+    ///
+    /// Inputs:
+    /// - Test index built with `OfficialIndex::new()`, and the same packages without name map.
+    ///
+    /// Output:
+    /// - Exact names return their record; prefixes, other cases and unknown names miss.
+    fn get_official_exact_match_and_miss() {
+        let index = OfficialIndex::new(create_test_index().pkgs);
+
+        let pkg = get_official(&index, "vim").expect("vim is indexed");
+        assert_eq!(pkg.repo, "extra");
+        assert_eq!(pkg.version, "9.0");
+        assert_eq!(
+            get_official(&index, "pacman").map(|pkg| pkg.repo.as_str()),
+            Some("core")
+        );
+        assert!(get_official(&index, "rip").is_none());
+        assert!(get_official(&index, "Vim").is_none());
+        assert!(get_official(&index, "neovim").is_none());
+
+        let unindexed = OfficialIndex {
+            pkgs: index.pkgs.clone(),
+            ..OfficialIndex::default()
+        };
+        assert_eq!(
+            get_official(&unindexed, "ripgrep").map(|pkg| pkg.name.as_str()),
+            Some("ripgrep")
+        );
+        assert!(get_official(&unindexed, "Ripgrep").is_none());
+    }
+
     #[test]
     /// What: Verify `search_official` returns empty vector for empty query.
     ///
@@ -239,6 +479,86 @@ mod tests {
         assert!(fuzzy_results[0].fuzzy_score.is_some()); // Has fuzzy score
     }
 
+    #[test]
+    /// What: Verify `search_official_with` drops matches below the threshold.
+    ///
+    /// Inputs:
+    /// - Distant query `"zzz"`, weak match `"vm"`, and exact query `"vim"` with a high threshold.
+    ///
+    /// Output:
+    /// - Only the exact match survives, with the maximum normalized score.
+    ///
+    /// Details:
+    /// - Only runs if fuzzy-search feature is enabled.
+    #[cfg(feature = "fuzzy-search")]
+    fn search_official_with_threshold_filters_distant_matches() {
+        let index = create_test_index();
+        let strict = FuzzyOptions {
+            threshold: 90,
+            ..FuzzyOptions::default()
+        };
+
+        assert!(search_official_with(&index, "zzz", &FuzzyOptions::default()).is_empty());
+        assert!(search_official_with(&index, "zzz", &strict).is_empty());
+
+        let weak = search_official_with(&index, "vm", &FuzzyOptions::default());
+        assert_eq!(weak.len(), 1);
+        assert!(weak[0].fuzzy_score.is_some_and(|score| score < 90));
+        assert!(search_official_with(&index, "vm", &strict).is_empty());
+
+        let exact = search_official_with(&index, "vim", &strict);
+        assert_eq!(exact.len(), 1);
+        assert_eq!(exact[0].package.name, "vim");
+        assert_eq!(exact[0].fuzzy_score, Some(100));
+    }
+
+    #[test]
+    /// What: Verify `search_official_with` ranks, limits, and respects case.
+    ///
+    /// Inputs:
+    /// - Query `"p"` matching `pacman` (prefix) and `ripgrep`, with and without a limit.
+    /// - Upper-case query with `case_sensitive` set.
+    ///
+    /// Output:
+    /// - Results sorted by descending score, truncated by `limit`; case-sensitive query misses.
+    ///
+    /// Details:
+    /// - Only runs if fuzzy-search feature is enabled.
+    #[cfg(feature = "fuzzy-search")]
+    fn search_official_with_ranks_and_limits() {
+        let index = create_test_index();
+
+        let results = search_official_with(&index, "p", &FuzzyOptions::default());
+        let names: Vec<&str> = results.iter().map(|r| r.package.name.as_str()).collect();
+        assert_eq!(names, vec!["pacman", "ripgrep"]);
+        assert!(
+            results
+                .windows(2)
+                .all(|pair| pair[0].fuzzy_score >= pair[1].fuzzy_score)
+        );
+
+        let limited = search_official_with(
+            &index,
+            "p",
+            &FuzzyOptions {
+                limit: 1,
+                ..FuzzyOptions::default()
+            },
+        );
+        assert_eq!(limited.len(), 1);
+        assert_eq!(limited[0].package.name, "pacman");
+
+        let case_sensitive = FuzzyOptions {
+            case_sensitive: true,
+            ..FuzzyOptions::default()
+        };
+        assert!(search_official_with(&index, "VIM", &case_sensitive).is_empty());
+        assert_eq!(
+            search_official_with(&index, "VIM", &FuzzyOptions::default()).len(),
+            1
+        );
+    }
+
     #[test]
     /// What: Verify `search_official` graceful degradation when fuzzy-search feature is disabled.
     ///
@@ -297,4 +617,35 @@ mod tests {
         let all = all_official(&index);
         assert!(all.is_empty());
     }
+
+    #[test]
+    /// What: Verify `packages_by_packager` returns only the given packager's packages.
+    ///
+    /// Inputs:
+    /// - Index where packages carry different packager identities.
+    ///
+    /// Output:
+    /// - Only matching packages, selected by full identity, name, or email.
+    ///
+    /// Details:
+    /// - Packages without packager metadata are never returned.
+    fn packages_by_packager_filters_by_packager() {
+        let mut index = create_test_index();
+        index.pkgs[0].packager = "Jane Doe <jane@archlinux.org>".to_string();
+        index.pkgs[1].packager = "John Roe <john@archlinux.org>".to_string();
+        index.pkgs[2].packager = "Jane Doe <jane@archlinux.org>".to_string();
+
+        let by_email = packages_by_packager(&index, "jane@archlinux.org");
+        let names: Vec<&str> = by_email.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["ripgrep", "pacman"]);
+
+        assert_eq!(packages_by_packager(&index, "jane doe").len(), 2);
+        assert_eq!(
+            packages_by_packager(&index, "John Roe <john@archlinux.org>").len(),
+            1
+        );
+        assert!(packages_by_packager(&index, "nobody").is_empty());
+        assert!(packages_by_packager(&index, "  ").is_empty());
+        assert!(packages_by_packager(&create_test_index(), "jane@archlinux.org").is_empty());
+    }
 }
@@ -0,0 +1,154 @@
+//! Installed soname snapshot functions for the index module.
+
+use std::collections::BTreeSet;
+use std::process::{Command, Stdio};
+
+use crate::error::{ArchToolkitError, Result};
+use crate::types::index::SonameSnapshot;
+use crate::types::is_soname;
+
+/// What: Capture the sonames provided by every installed package.
+///
+/// Inputs:
+/// - None: Executes `pacman -Qi` over the whole local database.
+///
+/// Output:
+/// - `Ok(SonameSnapshot)` mapping package names to their soname provides.
+/// - `Err` if pacman is unavailable or fails.
+///
+/// Details:
+/// - Only "Provides" entries naming a shared library (`*.so`, e.g., `libfoo.so=1-64`) are kept.
+/// - Persist the snapshot before an upgrade and diff it against a fresh one afterwards
+///   with `SonameSnapshot::diff()` to find changed sonames.
+/// - Sets `LC_ALL=C` and `LANG=C` for consistent locale-independent output.
+///
+/// # Errors
+///
+/// - Returns `Err(ArchToolkitError::Parse)` if pacman cannot be executed or exits with an error.
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::index::snapshot_sonames;
+///
+/// let before = snapshot_sonames()?;
+/// // ... run the upgrade ...
+/// let after = snapshot_sonames()?;
+/// for change in before.diff(&after) {
+///     println!("{}: -{:?} +{:?}", change.package, change.removed, change.added);
+/// }
+/// # Ok::<(), arch_toolkit::error::ArchToolkitError>(())
+/// ```
+pub fn snapshot_sonames() -> Result<SonameSnapshot> {
+    tracing::debug!("Running: pacman -Qi");
+    let output = Command::new("pacman")
+        .args(["-Qi"])
+        .env("LC_ALL", "C")
+        .env("LANG", "C")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|e| ArchToolkitError::Parse(format!("Failed to execute pacman -Qi: {e}")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(ArchToolkitError::Parse(format!(
+            "pacman -Qi failed: {stderr}"
+        )));
+    }
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    let snapshot = parse_soname_snapshot(&text);
+    tracing::debug!("Captured sonames for {} packages", snapshot.packages.len());
+    Ok(snapshot)
+}
+
+/// What: Build a soname snapshot from `pacman -Qi` output.
+///
+/// Inputs:
+/// - `text`: Raw stdout containing blank-line separated package blocks.
+///
+/// Output:
+/// - `SonameSnapshot` with soname provides per package.
+///
+/// Details:
+/// - Handles wrapped "Provides" values (indented continuation lines).
+/// - Packages without soname provides are omitted.
+fn parse_soname_snapshot(text: &str) -> SonameSnapshot {
+    let mut snapshot = SonameSnapshot::default();
+    let mut name: Option<String> = None;
+    let mut provides = String::new();
+    let mut in_provides = false;
+
+    for line in text.lines().chain(std::iter::once("")) {
+        if line.trim().is_empty() {
+            if let Some(pkg) = name.take() {
+                let sonames: BTreeSet<String> = provides
+                    .split_whitespace()
+                    .filter(|entry| is_soname(entry))
+                    .map(ToString::to_string)
+                    .collect();
+                if !sonames.is_empty() {
+                    snapshot.packages.insert(pkg, sonames);
+                }
+            }
+            provides.clear();
+            in_provides = false;
+            continue;
+        }
+
+        if line.starts_with(char::is_whitespace) {
+            if in_provides {
+                provides.push(' ');
+                provides.push_str(line.trim());
+            }
+            continue;
+        }
+
+        in_provides = false;
+        if let Some((key, value)) = line.split_once(':') {
+            match key.trim() {
+                "Name" => name = Some(value.trim().to_string()),
+                "Provides" => {
+                    provides = value.trim().to_string();
+                    in_provides = true;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    snapshot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// What: Verify `parse_soname_snapshot` extracts soname provides per package.
+    ///
+    /// Inputs:
+    /// - `pacman -Qi` output with a wrapped provides line, a "None" provides, and a
+    ///   non-soname provides entry.
+    ///
+    /// Output:
+    /// - Only packages providing sonames are recorded, with only soname entries.
+    ///
+    /// Details:
+    /// - Covers continuation lines and the "None" sentinel.
+    fn parse_soname_snapshot_extracts_sonames() {
+        let text = "Name            : glibc\nVersion         : 2.39-1\nProvides        : libc.so=6-64  libm.so=6-64\n                  ld-linux-x86-64.so=2-64\nDepends On      : linux-api-headers\n\nName            : vim\nProvides        : xxd  vi\n\nName            : bash\nProvides        : None\n";
+        let snapshot = parse_soname_snapshot(text);
+        assert_eq!(snapshot.packages.len(), 1);
+        let glibc: Vec<&str> = snapshot.packages["glibc"]
+            .iter()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(
+            glibc,
+            vec!["ld-linux-x86-64.so=2-64", "libc.so=6-64", "libm.so=6-64"]
+        );
+    }
+}
@@ -0,0 +1,223 @@
+//! Combined official repository and AUR search.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::aur::AurApi;
+use crate::client::ArchClient;
+use crate::error::Result;
+use crate::index::{fetch_official_index_async, search_official};
+use crate::types::AurPackage;
+use crate::types::index::{IndexQueryResult, OfficialIndex, ResultSource, UnifiedResult};
+
+/// What: Search the official repositories and the AUR in one call.
+///
+/// Inputs:
+/// - `client`: `ArchClient` used for the AUR search.
+/// - `query`: Search query.
+///
+/// Output:
+/// - Merged results as described in `search_all_with()`.
+///
+/// Details:
+/// - Fetches the official index and runs the AUR search concurrently
+///
+/// # Errors
+/// - Returns errors from `fetch_official_index_async()` or the AUR search
+pub async fn search_all(client: &ArchClient, query: &str) -> Result<Vec<UnifiedResult>> {
+    if query.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let aur = client.aur();
+    let (index, aur_results) = tokio::join!(fetch_official_index_async(), aur.search(query));
+    Ok(merge_results(
+        search_official(&index?, query, false),
+        &aur_results?,
+    ))
+}
+
+/// What: Search an official index and the AUR and merge the results.
+///
+/// Inputs:
+/// - `aur`: AUR API implementation (`ArchClient::aur()` or `MockAurApi`).
+/// - `index`: Official package index to search.
+/// - `query`: Search query.
+///
+/// Output:
+/// - One `UnifiedResult` per package name, sorted by name.
+/// - An empty or whitespace-only query returns an empty list without querying the AUR.
+///
+/// Details:
+/// - Official packages are matched with case-insensitive substring search on the name
+///   (`search_official()`); the AUR search uses its own matching rules
+/// - When a name exists in both sources the official entry wins and `in_aur` is set
+/// - If a name appears in several official repositories, the first repository in index order
+///   is kept
+///
+/// # Errors
+/// - Returns errors from the AUR search
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::ArchClient;
+/// use arch_toolkit::index::{OfficialIndex, ResultSource, search_all_with};
+///
+/// # async fn example() -> Result<(), arch_toolkit::error::ArchToolkitError> {
+/// let client = ArchClient::new()?;
+/// let index = OfficialIndex::default();
+/// for result in search_all_with(&client.aur(), &index, "vim").await? {
+///     match result.source {
+///         ResultSource::Official { repo } => println!("{repo}/{}", result.name),
+///         ResultSource::Aur => println!("aur/{}", result.name),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn search_all_with(
+    aur: &dyn AurApi,
+    index: &OfficialIndex,
+    query: &str,
+) -> Result<Vec<UnifiedResult>> {
+    if query.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let aur_results = aur.search(query).await?;
+    Ok(merge_results(
+        search_official(index, query, false),
+        &aur_results,
+    ))
+}
+
+/// What: Merge official and AUR matches into de-duplicated unified results.
+///
+/// Inputs:
+/// - `official`: Official index matches.
+/// - `aur`: AUR search results.
+///
+/// Output:
+/// - Results sorted by name, official entries preferred over AUR entries.
+fn merge_results(official: Vec<IndexQueryResult>, aur: &[AurPackage]) -> Vec<UnifiedResult> {
+    let aur_names: HashSet<&str> = aur.iter().map(|pkg| pkg.name.as_str()).collect();
+    let mut merged: HashMap<String, UnifiedResult> = HashMap::new();
+
+    for result in official {
+        let pkg = result.package;
+        let in_aur = aur_names.contains(pkg.name.as_str());
+        merged.entry(pkg.name.clone()).or_insert(UnifiedResult {
+            name: pkg.name,
+            version: pkg.version,
+            description: pkg.description,
+            source: ResultSource::Official { repo: pkg.repo },
+            in_aur,
+        });
+    }
+    for pkg in aur {
+        merged
+            .entry(pkg.name.clone())
+            .or_insert_with(|| UnifiedResult {
+                name: pkg.name.clone(),
+                version: pkg.version.clone(),
+                description: pkg.description.clone(),
+                source: ResultSource::Aur,
+                in_aur: true,
+            });
+    }
+
+    let mut results: Vec<UnifiedResult> = merged.into_values().collect();
+    results.sort_by(|a, b| a.name.cmp(&b.name));
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aur::MockAurApi;
+    use crate::types::index::OfficialPackage;
+
+    fn official(name: &str, repo: &str) -> OfficialPackage {
+        OfficialPackage {
+            name: name.to_string(),
+            repo: repo.to_string(),
+            arch: "x86_64".to_string(),
+            version: "1.0-1".to_string(),
+            description: format!("{name} from {repo}"),
+            packager: String::new(),
+            download_size: 0,
+            installed_size: 0,
+        }
+    }
+
+    fn aur(name: &str) -> AurPackage {
+        AurPackage {
+            name: name.to_string(),
+            version: "2.0-1".to_string(),
+            description: format!("{name} from the AUR"),
+            popularity: None,
+            out_of_date: None,
+            orphaned: false,
+            maintainer: Some("someone".to_string()),
+            num_votes: None,
+            last_modified: None,
+            first_submitted: None,
+        }
+    }
+
+    #[tokio::test]
+    /// What: Merge official and AUR matches for one query.
+    ///
+    /// Inputs:
+    /// - Fake index with `neovim` (extra, duplicated in testing) and unrelated `ripgrep`.
+    /// - `MockAurApi` returning `neovim` and `neovim-git`.
+    ///
+    /// Output:
+    /// - `neovim` from extra marked as also in the AUR, then `neovim-git` from the AUR.
+    async fn test_search_all_with_merges_sources() {
+        let mut index = OfficialIndex {
+            pkgs: vec![
+                official("neovim", "extra"),
+                official("ripgrep", "extra"),
+                official("neovim", "extra-testing"),
+            ],
+            ..OfficialIndex::default()
+        };
+        index.rebuild_name_index();
+        let mock = MockAurApi::new()
+            .with_search_result("neovim", Ok(vec![aur("neovim-git"), aur("neovim")]));
+
+        let results = search_all_with(&mock, &index, "neovim")
+            .await
+            .expect("search succeeds");
+
+        assert_eq!(
+            results,
+            vec![
+                UnifiedResult {
+                    name: "neovim".to_string(),
+                    version: "1.0-1".to_string(),
+                    description: "neovim from extra".to_string(),
+                    source: ResultSource::Official {
+                        repo: "extra".to_string()
+                    },
+                    in_aur: true,
+                },
+                UnifiedResult {
+                    name: "neovim-git".to_string(),
+                    version: "2.0-1".to_string(),
+                    description: "neovim-git from the AUR".to_string(),
+                    source: ResultSource::Aur,
+                    in_aur: true,
+                },
+            ]
+        );
+        assert_eq!(mock.call_count("search"), 1);
+
+        assert!(
+            search_all_with(&mock, &index, "  ")
+                .await
+                .expect("empty query")
+                .is_empty()
+        );
+        assert_eq!(mock.call_count("search"), 1);
+    }
+}
@@ -0,0 +1,389 @@
+//! Install command construction for pacman and AUR helpers.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::process::Command;
+
+use crate::types::{PackageRef, PackageSource};
+
+/// What: AUR helper used to install AUR packages.
+///
+/// Inputs: None (enum variant)
+///
+/// Output: Enum selecting the helper binary
+///
+/// Details:
+/// - Both helpers accept pacman's `-S` flags (`--needed`, `--noconfirm`, `--asdeps`)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AurHelper {
+    /// `paru`
+    #[default]
+    Paru,
+    /// `yay`
+    Yay,
+}
+
+impl AurHelper {
+    /// What: Binary name of the helper.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - `"paru"` or `"yay"`
+    #[must_use]
+    pub const fn program(self) -> &'static str {
+        match self {
+            Self::Paru => "paru",
+            Self::Yay => "yay",
+        }
+    }
+}
+
+impl fmt::Display for AurHelper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.program())
+    }
+}
+
+/// Options for building install commands.
+///
+/// Passed to `build_install_command()`. Flags apply to every emitted command.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InstallOptions {
+    /// Skip confirmation prompts (`--noconfirm`).
+    pub no_confirm: bool,
+    /// Do not reinstall up-to-date packages (`--needed`).
+    pub needed: bool,
+    /// Mark packages as installed as dependencies (`--asdeps`).
+    pub as_deps: bool,
+    /// Helper used for AUR packages.
+    pub helper: AurHelper,
+}
+
+/// What: A single install invocation as program and arguments.
+///
+/// Inputs: None (created by `build_install_command()`)
+///
+/// Output: Argv that can be inspected, rendered for a shell, or executed
+///
+/// Details:
+/// - `pacman -S` must run as root; callers are responsible for privilege escalation
+/// - AUR helpers must not run as root; they escalate on their own
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InstallCommand {
+    /// Program to run (`pacman`, `paru`, or `yay`).
+    pub program: String,
+    /// Arguments, excluding the program.
+    pub args: Vec<String>,
+}
+
+impl InstallCommand {
+    /// What: Full argument vector including the program.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - `Vec<&str>` starting with the program name
+    #[must_use]
+    pub fn argv(&self) -> Vec<&str> {
+        std::iter::once(self.program.as_str())
+            .chain(self.args.iter().map(String::as_str))
+            .collect()
+    }
+
+    /// What: Render the command as a shell-safe string.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - Space-separated argv; arguments with shell metacharacters are single-quoted
+    #[must_use]
+    pub fn to_shell_string(&self) -> String {
+        self.argv()
+            .into_iter()
+            .map(shell_quote)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// What: Convert into a `std::process::Command` ready to spawn.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - `Command` with program and arguments set
+    #[must_use]
+    pub fn to_command(&self) -> Command {
+        let mut command = Command::new(&self.program);
+        command.args(&self.args);
+        command
+    }
+}
+
+/// What: Build install commands for a set of packages.
+///
+/// Inputs:
+/// - `packages`: Packages to install; `source` decides which tool installs each one.
+/// - `opts`: Install flags and AUR helper selection.
+///
+/// Output:
+/// - Commands to run in order: `pacman -S` for official packages first, then the AUR helper
+///   for AUR packages. Empty if `packages` is empty.
+///
+/// Details:
+/// - Packages are grouped by source and emitted as separate commands, since pacman cannot
+///   install AUR packages and helpers must not run as root
+/// - Official packages are installed first so AUR builds can depend on them
+/// - Package order is preserved within each command; duplicate names are emitted once
+/// - Flags are emitted in the order `--needed`, `--asdeps`, `--noconfirm`
+///
+/// # Example
+///
+/// ```
+/// use arch_toolkit::install::{InstallOptions, build_install_command};
+/// use arch_toolkit::{PackageRef, PackageSource};
+///
+/// let packages = vec![PackageRef {
+///     name: "ripgrep".into(),
+///     version: String::new(),
+///     source: PackageSource::Official {
+///         repo: "extra".into(),
+///         arch: "x86_64".into(),
+///     },
+/// }];
+/// let opts = InstallOptions {
+///     needed: true,
+///     ..InstallOptions::default()
+/// };
+///
+/// let commands = build_install_command(&packages, &opts);
+/// assert_eq!(commands[0].argv(), ["pacman", "-S", "--needed", "--", "ripgrep"]);
+/// ```
+#[must_use]
+pub fn build_install_command(
+    packages: &[PackageRef],
+    opts: &InstallOptions,
+) -> Vec<InstallCommand> {
+    let mut seen = HashSet::new();
+    let (official, aur): (Vec<&PackageRef>, Vec<&PackageRef>) = packages
+        .iter()
+        .filter(|pkg| seen.insert(pkg.name.as_str()))
+        .partition(|pkg| matches!(pkg.source, PackageSource::Official { .. }));
+
+    [("pacman", official), (opts.helper.program(), aur)]
+        .into_iter()
+        .filter(|(_, group)| !group.is_empty())
+        .map(|(program, group)| InstallCommand {
+            program: program.to_string(),
+            args: install_args(&group, *opts),
+        })
+        .collect()
+}
+
+/// What: Build install commands and render them as a single shell string.
+///
+/// Inputs:
+/// - `packages`: Packages to install.
+/// - `opts`: Install flags and AUR helper selection.
+///
+/// Output:
+/// - Commands from `build_install_command()` joined with ` && `; empty if there is nothing
+///   to install
+#[must_use]
+pub fn build_install_shell_string(packages: &[PackageRef], opts: &InstallOptions) -> String {
+    build_install_command(packages, opts)
+        .iter()
+        .map(InstallCommand::to_shell_string)
+        .collect::<Vec<_>>()
+        .join(" && ")
+}
+
+/// What: Build `-S` arguments for one group of packages.
+///
+/// Inputs:
+/// - `packages`: Packages in the group.
+/// - `opts`: Install flags.
+///
+/// Output:
+/// - `-S`, the selected flags, `--`, then package names
+///
+/// Details:
+/// - `--` ends option parsing so a name starting with `-` cannot be read as a flag.
+fn install_args(packages: &[&PackageRef], opts: InstallOptions) -> Vec<String> {
+    let flags = [
+        (opts.needed, "--needed"),
+        (opts.as_deps, "--asdeps"),
+        (opts.no_confirm, "--noconfirm"),
+    ];
+    std::iter::once("-S")
+        .chain(
+            flags
+                .into_iter()
+                .filter_map(|(enabled, flag)| enabled.then_some(flag)),
+        )
+        .chain(std::iter::once("--"))
+        .map(str::to_string)
+        .chain(packages.iter().map(|pkg| pkg.name.clone()))
+        .collect()
+}
+
+/// What: Quote an argument for POSIX shells if needed.
+///
+/// Inputs:
+/// - `arg`: Argument to quote.
+///
+/// Output:
+/// - The argument unchanged if it only contains safe characters, otherwise single-quoted
+fn shell_quote(arg: &str) -> String {
+    let safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.+=:/@,".contains(c));
+    if safe {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn official(name: &str) -> PackageRef {
+        PackageRef {
+            name: name.to_string(),
+            version: String::new(),
+            source: PackageSource::Official {
+                repo: "extra".to_string(),
+                arch: "x86_64".to_string(),
+            },
+        }
+    }
+
+    fn aur(name: &str) -> PackageRef {
+        PackageRef {
+            name: name.to_string(),
+            version: String::new(),
+            source: PackageSource::Aur,
+        }
+    }
+
+    #[test]
+    /// What: Split mixed official and AUR packages into pacman and helper commands.
+    ///
+    /// Inputs:
+    /// - Interleaved official and AUR packages with a duplicate, all flags enabled, yay.
+    ///
+    /// Output:
+    /// - `pacman -S` with official packages first, then `yay -S` with AUR packages.
+    fn test_build_install_command_mixed_sources() {
+        let packages = vec![
+            official("ripgrep"),
+            aur("paru-bin"),
+            official("fd"),
+            aur("visual-studio-code-bin"),
+            official("ripgrep"),
+        ];
+        let opts = InstallOptions {
+            no_confirm: true,
+            needed: true,
+            as_deps: true,
+            helper: AurHelper::Yay,
+        };
+
+        let commands = build_install_command(&packages, &opts);
+        let argvs: Vec<Vec<&str>> = commands.iter().map(InstallCommand::argv).collect();
+        assert_eq!(
+            argvs,
+            vec![
+                vec![
+                    "pacman",
+                    "-S",
+                    "--needed",
+                    "--asdeps",
+                    "--noconfirm",
+                    "--",
+                    "ripgrep",
+                    "fd"
+                ],
+                vec![
+                    "yay",
+                    "-S",
+                    "--needed",
+                    "--asdeps",
+                    "--noconfirm",
+                    "--",
+                    "paru-bin",
+                    "visual-studio-code-bin"
+                ],
+            ]
+        );
+        assert_eq!(
+            build_install_shell_string(&packages, &opts),
+            "pacman -S --needed --asdeps --noconfirm -- ripgrep fd && \
+             yay -S --needed --asdeps --noconfirm -- paru-bin visual-studio-code-bin"
+        );
+    }
+
+    #[test]
+    /// What: Emit a single command when all packages share a source.
+    ///
+    /// Inputs:
+    /// - Only AUR packages with default options; only official packages; no packages.
+    ///
+    /// Output:
+    /// - One `paru -S` command, one `pacman -S` command, and no commands respectively.
+    fn test_build_install_command_single_source() {
+        let opts = InstallOptions::default();
+
+        let commands = build_install_command(&[aur("yay-bin")], &opts);
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].argv(), ["paru", "-S", "--", "yay-bin"]);
+
+        let commands = build_install_command(&[official("vim")], &opts);
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].argv(), ["pacman", "-S", "--", "vim"]);
+
+        assert!(build_install_command(&[], &opts).is_empty());
+        assert_eq!(build_install_shell_string(&[], &opts), "");
+    }
+
+    #[test]
+    /// What: Quote shell metacharacters when rendering commands.
+    ///
+    /// Inputs:
+    /// - Package names with a space and a single quote.
+    ///
+    /// Output:
+    /// - Unsafe arguments are single-quoted with embedded quotes escaped.
+    fn test_to_shell_string_quotes_arguments() {
+        let command = InstallCommand {
+            program: "pacman".to_string(),
+            args: vec!["-S".to_string(), "a b".to_string(), "it's".to_string()],
+        };
+        assert_eq!(command.to_shell_string(), r"pacman -S 'a b' 'it'\''s'");
+    }
+
+    #[test]
+    /// What: Keep package names that look like options out of option parsing.
+    ///
+    /// Inputs:
+    /// - Official and AUR packages named like pacman options.
+    ///
+    /// Output:
+    /// - `--` precedes the names in both commands.
+    fn test_build_install_command_separates_option_like_names() {
+        let packages = vec![official("--config=/tmp/x"), aur("--overwrite=*")];
+
+        let commands = build_install_command(&packages, &InstallOptions::default());
+        let argvs: Vec<Vec<&str>> = commands.iter().map(InstallCommand::argv).collect();
+        assert_eq!(
+            argvs,
+            vec![
+                vec!["pacman", "-S", "--", "--config=/tmp/x"],
+                vec!["paru", "-S", "--", "--overwrite=*"],
+            ]
+        );
+    }
+}
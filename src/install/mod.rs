@@ -0,0 +1,87 @@
+//! Installation planning for Arch Linux packages.
+//!
+//! This module provides types describing a set of packages to install:
+//!
+//! - **Install Plans** - Ordered package sets with optional version pinning
+//! - **Package Lists** - Export plans as `pacman -S -` compatible lists
+//! - **Install Commands** - `pacman -S` and AUR helper (`paru`/`yay`) invocations
+//! - **Removal Commands** - `pacman -R` invocations guarded by reverse dependency checks
+//! - **Transaction Summaries** - Counts and sizes for confirmation prompts (requires `index`)
+//!
+//! # Features
+//!
+//! This module requires the `install` feature flag to be enabled:
+//!
+//! ```toml
+//! [dependencies]
+//! arch-toolkit = { version = "0.2", features = ["install"] }
+//! ```
+//!
+//! # Examples
+//!
+//! ## Export a Package List
+//!
+//! ```
+//! use arch_toolkit::install::InstallPlan;
+//! use arch_toolkit::{PackageRef, PackageSource};
+//!
+//! let plan = InstallPlan::new(vec![PackageRef {
+//!     name: "ripgrep".into(),
+//!     version: "14.1.0-1".into(),
+//!     source: PackageSource::Official {
+//!         repo: "extra".into(),
+//!         arch: "x86_64".into(),
+//!     },
+//! }]);
+//!
+//! // Re-apply elsewhere with: pacman -S --needed - < packages.txt
+//! assert_eq!(plan.to_package_list(false), "ripgrep\n");
+//! assert_eq!(plan.to_package_list(true), "ripgrep=14.1.0-1\n");
+//! ```
+//!
+//! ## Build Install Commands
+//!
+//! ```
+//! use arch_toolkit::install::{AurHelper, InstallOptions, build_install_command};
+//! use arch_toolkit::{PackageRef, PackageSource};
+//!
+//! let packages = vec![
+//!     PackageRef {
+//!         name: "ripgrep".into(),
+//!         version: String::new(),
+//!         source: PackageSource::Official {
+//!             repo: "extra".into(),
+//!             arch: "x86_64".into(),
+//!         },
+//!     },
+//!     PackageRef {
+//!         name: "yay-bin".into(),
+//!         version: String::new(),
+//!         source: PackageSource::Aur,
+//!     },
+//! ];
+//! let opts = InstallOptions {
+//!     needed: true,
+//!     helper: AurHelper::Yay,
+//!     ..InstallOptions::default()
+//! };
+//!
+//! // Official packages go to pacman, AUR packages to the helper
+//! let commands = build_install_command(&packages, &opts);
+//! assert_eq!(commands[0].to_shell_string(), "pacman -S --needed -- ripgrep");
+//! assert_eq!(commands[1].to_shell_string(), "yay -S --needed -- yay-bin");
+//! ```
+
+mod command;
+mod plan;
+mod remove;
+#[cfg(feature = "index")]
+mod summary;
+
+pub use command::{
+    AurHelper, InstallCommand, InstallOptions, build_install_command, build_install_shell_string,
+};
+pub use plan::InstallPlan;
+pub use remove::{RemoveOptions, build_remove_command};
+#[cfg(feature = "index")]
+pub use summary::TransactionSummary;
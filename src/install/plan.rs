@@ -0,0 +1,131 @@
+//! Install plan type and package list export.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::PackageRef;
+
+/// What: Ordered set of packages to install.
+///
+/// Inputs:
+/// - Built from resolved packages via `InstallPlan::new()`.
+///
+/// Output:
+/// - Plan that can be exported with `to_package_list()`.
+///
+/// Details:
+/// - Package order is preserved as given.
+/// - Serializable so a resolved set can be snapshotted and re-applied elsewhere.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstallPlan {
+    /// Packages to install, in installation order.
+    pub packages: Vec<PackageRef>,
+}
+
+impl InstallPlan {
+    /// What: Create an install plan from a list of packages.
+    ///
+    /// Inputs:
+    /// - `packages`: Packages to install, in installation order.
+    ///
+    /// Output:
+    /// - `InstallPlan` holding the packages.
+    #[must_use]
+    pub const fn new(packages: Vec<PackageRef>) -> Self {
+        Self { packages }
+    }
+
+    /// What: Export the plan as a newline-delimited package list.
+    ///
+    /// Inputs:
+    /// - `with_versions`: Pin each package to its version (`name=version`).
+    ///
+    /// Output:
+    /// - One package per line, each line terminated by `\n`; empty string for an empty plan.
+    ///
+    /// Details:
+    /// - Compatible with `pacman -S - < list` workflows.
+    /// - Packages without a version are written as plain `name`, even when pinning.
+    /// - Duplicate package names are written once, at their first position.
+    /// - AUR packages are listed as well; pacman cannot install those from the list, so
+    ///   callers targeting pacman alone should filter them out of the plan first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use arch_toolkit::install::InstallPlan;
+    /// use arch_toolkit::{PackageRef, PackageSource};
+    ///
+    /// let plan = InstallPlan::new(vec![PackageRef {
+    ///     name: "vim".into(),
+    ///     version: "9.1.0-1".into(),
+    ///     source: PackageSource::Official {
+    ///         repo: "extra".into(),
+    ///         arch: "x86_64".into(),
+    ///     },
+    /// }]);
+    /// assert_eq!(plan.to_package_list(true), "vim=9.1.0-1\n");
+    /// ```
+    #[must_use]
+    pub fn to_package_list(&self, with_versions: bool) -> String {
+        let mut seen = HashSet::new();
+        let mut list = String::new();
+        for pkg in &self.packages {
+            if !seen.insert(pkg.name.as_str()) {
+                continue;
+            }
+            if with_versions && !pkg.version.is_empty() {
+                let _ = writeln!(list, "{}={}", pkg.name, pkg.version);
+            } else {
+                let _ = writeln!(list, "{}", pkg.name);
+            }
+        }
+        list
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PackageSource;
+
+    fn official(name: &str, version: &str) -> PackageRef {
+        PackageRef {
+            name: name.to_string(),
+            version: version.to_string(),
+            source: PackageSource::Official {
+                repo: "extra".to_string(),
+                arch: "x86_64".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    /// What: Verify the package list format with and without version pinning.
+    ///
+    /// Inputs:
+    /// - Plan with two versioned packages, one unversioned package, and a duplicate.
+    ///
+    /// Output:
+    /// - Newline-terminated names, or `name=version` when pinning.
+    ///
+    /// Details:
+    /// - Unversioned packages stay unpinned; duplicates are written once.
+    fn to_package_list_formats_names_and_versions() {
+        let plan = InstallPlan::new(vec![
+            official("ripgrep", "14.1.0-1"),
+            official("fd", "10.1.0-1"),
+            official("base-devel", ""),
+            official("ripgrep", "14.1.0-1"),
+        ]);
+
+        assert_eq!(plan.to_package_list(false), "ripgrep\nfd\nbase-devel\n");
+        assert_eq!(
+            plan.to_package_list(true),
+            "ripgrep=14.1.0-1\nfd=10.1.0-1\nbase-devel\n"
+        );
+        assert_eq!(InstallPlan::default().to_package_list(true), "");
+    }
+}
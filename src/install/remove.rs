@@ -0,0 +1,328 @@
+//! Removal command construction with reverse dependency checks.
+
+use std::collections::HashSet;
+
+use crate::deps::ReverseDependencyAnalyzer;
+use crate::error::{ArchToolkitError, Result};
+use crate::install::InstallCommand;
+use crate::types::PackageRef;
+use crate::types::dependency::ReverseDependencyReport;
+
+/// Options for building removal commands.
+///
+/// Passed to `build_remove_command()`. `cascade` takes precedence over `recursive_orphans`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)] // Independent pacman flags
+pub struct RemoveOptions {
+    /// Build the command even if installed packages depend on the targets.
+    pub force: bool,
+    /// Also remove every package depending on the targets (`-Rsc`).
+    pub cascade: bool,
+    /// Also remove dependencies that become orphaned (`-Rs`).
+    pub recursive_orphans: bool,
+    /// Skip confirmation prompts (`--noconfirm`).
+    pub no_confirm: bool,
+}
+
+/// What: Build a `pacman -R` command after checking reverse dependencies.
+///
+/// Inputs:
+/// - `packages`: Packages to remove.
+/// - `opts`: Removal mode and safety options.
+///
+/// Output:
+/// - `Ok(InstallCommand)` with the `pacman` argv, suitable for dry-run display.
+///
+/// Details:
+/// - Runs `ReverseDependencyAnalyzer::analyze()` on the targets first
+/// - Dependents that are themselves removal targets do not block removal
+/// - Flags: `-Rsc` with `cascade`, `-Rs` with `recursive_orphans`, otherwise `-R`
+/// - `cascade` removes the dependents as well, so it never blocks
+/// - Duplicate target names are emitted once after `--`, so a name starting with `-` cannot
+///   be read as a flag; the command must run as root
+///
+/// # Errors
+/// - Returns `Err(ArchToolkitError::EmptyInput)` if `packages` is empty
+/// - Returns `Err(ArchToolkitError::RemovalBlocked)` if installed packages depend on the
+///   targets and neither `force` nor `cascade` is set
+/// - Returns errors from `ReverseDependencyAnalyzer::analyze()` if pacman queries fail
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::install::{RemoveOptions, build_remove_command};
+/// use arch_toolkit::{PackageRef, PackageSource};
+///
+/// let packages = vec![PackageRef {
+///     name: "qt5-base".into(),
+///     version: String::new(),
+///     source: PackageSource::Official {
+///         repo: "extra".into(),
+///         arch: "x86_64".into(),
+///     },
+/// }];
+///
+/// match build_remove_command(&packages, &RemoveOptions::default()) {
+///     Ok(command) => println!("Would run: {}", command.to_shell_string()),
+///     Err(e) => eprintln!("{e}"),
+/// }
+/// ```
+pub fn build_remove_command(
+    packages: &[PackageRef],
+    opts: &RemoveOptions,
+) -> Result<InstallCommand> {
+    remove_command_with(&ReverseDependencyAnalyzer::new(), packages, *opts)
+}
+
+/// What: Build the removal command, checking reverse dependencies with `analyzer`.
+///
+/// Inputs:
+/// - `analyzer`: Reverse dependency analyzer run on the targets.
+/// - `packages`: Packages to remove.
+/// - `opts`: Removal mode and safety options.
+///
+/// Output:
+/// - Same as `build_remove_command()`.
+///
+/// # Errors
+/// - Same as `build_remove_command()`.
+fn remove_command_with(
+    analyzer: &ReverseDependencyAnalyzer,
+    packages: &[PackageRef],
+    opts: RemoveOptions,
+) -> Result<InstallCommand> {
+    if packages.is_empty() {
+        return Err(ArchToolkitError::EmptyInput {
+            field: "packages".to_string(),
+            message: "at least one package is required for removal".to_string(),
+        });
+    }
+    let report = analyzer.analyze(packages)?;
+    remove_command_from_report(packages, opts, &report)
+}
+
+/// What: Build the removal command from an existing reverse dependency report.
+///
+/// Inputs:
+/// - `packages`: Packages to remove.
+/// - `opts`: Removal mode and safety options.
+/// - `report`: Reverse dependency report for `packages`.
+///
+/// Output:
+/// - `Ok(InstallCommand)`, or `Err(ArchToolkitError::RemovalBlocked)` with external dependents
+fn remove_command_from_report(
+    packages: &[PackageRef],
+    opts: RemoveOptions,
+    report: &ReverseDependencyReport,
+) -> Result<InstallCommand> {
+    let mut seen = HashSet::new();
+    let targets: Vec<&str> = packages
+        .iter()
+        .map(|pkg| pkg.name.as_str())
+        .filter(|name| seen.insert(*name))
+        .collect();
+
+    if !opts.force && !opts.cascade {
+        let mut dependents: Vec<String> = report
+            .dependents
+            .iter()
+            .filter(|dep| !seen.contains(dep.name.as_str()))
+            .map(|dep| dep.name.clone())
+            .collect();
+        if !dependents.is_empty() {
+            dependents.sort();
+            dependents.dedup();
+            return Err(ArchToolkitError::RemovalBlocked { dependents });
+        }
+    }
+
+    let operation = if opts.cascade {
+        "-Rsc"
+    } else if opts.recursive_orphans {
+        "-Rs"
+    } else {
+        "-R"
+    };
+    let args = std::iter::once(operation)
+        .chain(opts.no_confirm.then_some("--noconfirm"))
+        .chain(std::iter::once("--"))
+        .chain(targets)
+        .map(str::to_string)
+        .collect();
+
+    Ok(InstallCommand {
+        program: "pacman".to_string(),
+        args,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deps::MockRunner;
+    use crate::types::{
+        Dependency, DependencyKind, DependencySource, DependencyStatus, InstallReason,
+        PackageSource,
+    };
+
+    fn official(name: &str) -> PackageRef {
+        PackageRef {
+            name: name.to_string(),
+            version: String::new(),
+            source: PackageSource::Official {
+                repo: "extra".to_string(),
+                arch: "x86_64".to_string(),
+            },
+        }
+    }
+
+    /// What: Build a report listing the given installed dependents.
+    fn report(dependents: &[&str]) -> ReverseDependencyReport {
+        ReverseDependencyReport {
+            dependents: dependents
+                .iter()
+                .map(|name| Dependency {
+                    name: (*name).to_string(),
+                    version_req: String::new(),
+                    status: DependencyStatus::Installed {
+                        version: "1.0-1".to_string(),
+                    },
+                    source: DependencySource::Local,
+                    required_by: vec!["qt5-base".to_string()],
+                    depends_on: Vec::new(),
+                    is_core: false,
+                    is_system: false,
+                    kind: DependencyKind::default(),
+                    explicit: false,
+                    install_reason: InstallReason::Unknown,
+                    satisfied_by: None,
+                })
+                .collect(),
+            summaries: Vec::new(),
+            cycles: Vec::new(),
+        }
+    }
+
+    #[test]
+    /// What: Refuse removal when installed packages depend on the targets.
+    ///
+    /// Inputs:
+    /// - `qt5-base` with dependents `vlc`, `qt5-svg` (also a target), and a duplicate `vlc`.
+    ///
+    /// Output:
+    /// - `RemovalBlocked` listing only `vlc`.
+    fn test_remove_blocked_by_dependents() {
+        let packages = [official("qt5-base"), official("qt5-svg")];
+        let err = remove_command_from_report(
+            &packages,
+            RemoveOptions::default(),
+            &report(&["vlc", "qt5-svg", "vlc"]),
+        )
+        .expect_err("dependents block removal");
+
+        match err {
+            ArchToolkitError::RemovalBlocked { dependents } => {
+                assert_eq!(dependents, vec!["vlc"]);
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+
+    #[test]
+    /// What: Select pacman flags from the removal options.
+    ///
+    /// Inputs:
+    /// - Dependents present with `force`, `cascade`, and `recursive_orphans`; none with defaults.
+    ///
+    /// Output:
+    /// - `-R`, `-Rs`, and `-Rsc` argv as inspectable vectors.
+    fn test_remove_command_flags() {
+        let packages = [official("qt5-base"), official("qt5-base")];
+        let blocked = report(&["vlc"]);
+
+        let command = remove_command_from_report(
+            &packages,
+            RemoveOptions {
+                force: true,
+                no_confirm: true,
+                ..RemoveOptions::default()
+            },
+            &blocked,
+        )
+        .expect("force overrides dependents");
+        assert_eq!(
+            command.argv(),
+            ["pacman", "-R", "--noconfirm", "--", "qt5-base"]
+        );
+
+        let command = remove_command_from_report(
+            &packages,
+            RemoveOptions {
+                cascade: true,
+                recursive_orphans: true,
+                ..RemoveOptions::default()
+            },
+            &blocked,
+        )
+        .expect("cascade removes dependents");
+        assert_eq!(command.argv(), ["pacman", "-Rsc", "--", "qt5-base"]);
+
+        let command = remove_command_from_report(
+            &packages,
+            RemoveOptions {
+                recursive_orphans: true,
+                ..RemoveOptions::default()
+            },
+            &report(&[]),
+        )
+        .expect("no dependents");
+        assert_eq!(command.argv(), ["pacman", "-Rs", "--", "qt5-base"]);
+    }
+
+    #[test]
+    /// What: Keep target names that look like options out of option parsing.
+    ///
+    /// Inputs:
+    /// - A target named `--dbpath=/tmp/x` with no dependents.
+    ///
+    /// Output:
+    /// - `--` precedes the target in the argv.
+    fn test_remove_command_separates_option_like_names() {
+        let command = remove_command_from_report(
+            &[official("--dbpath=/tmp/x")],
+            RemoveOptions::default(),
+            &report(&[]),
+        )
+        .expect("no dependents");
+        assert_eq!(command.argv(), ["pacman", "-R", "--", "--dbpath=/tmp/x"]);
+    }
+
+    #[test]
+    /// What: Refuse to build a command when the reverse dependency check cannot run.
+    ///
+    /// Inputs:
+    /// - Analyzer whose runner has no pacman.
+    ///
+    /// Output:
+    /// - `PacmanUnavailable` instead of a `pacman -R` command.
+    fn test_remove_fails_without_pacman() {
+        let analyzer = ReverseDependencyAnalyzer::new().with_runner(MockRunner::default());
+        let err = remove_command_with(&analyzer, &[official("qt5-base")], RemoveOptions::default())
+            .expect_err("the safety check must run");
+        assert!(matches!(err, ArchToolkitError::PacmanUnavailable { .. }));
+    }
+
+    #[test]
+    /// What: Reject an empty removal target list.
+    ///
+    /// Inputs:
+    /// - No packages.
+    ///
+    /// Output:
+    /// - `EmptyInput` without running pacman.
+    fn test_remove_empty_input() {
+        let err = build_remove_command(&[], &RemoveOptions::default())
+            .expect_err("empty input is rejected");
+        assert!(matches!(err, ArchToolkitError::EmptyInput { .. }));
+    }
+}
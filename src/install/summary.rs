@@ -0,0 +1,318 @@
+//! Transaction summary for confirmation prompts.
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::PackageRef;
+use crate::types::dependency::{DependencyResolution, DependencyStatus, ReverseDependencyReport};
+use crate::types::index::OfficialIndex;
+
+/// What: Aggregated counts and sizes of a pending transaction.
+///
+/// Inputs:
+/// - Built from the install targets, their dependency resolution, an optional removal report,
+///   and the official index via `TransactionSummary::from()`.
+///
+/// Output:
+/// - Everything a confirmation dialog shows: "X to install, Y to upgrade, Z to remove,
+///   total download N MB".
+///
+/// Details:
+/// - Sizes are in bytes and only cover packages found in the official index with size
+///   metadata; AUR packages and packages without sizes are listed in `unknown_size`.
+/// - Upgrades add the full download and installed size of the new version, not the change
+///   against the installed version.
+/// - Installed dependencies are not counted.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionSummary {
+    /// Number of packages to install.
+    pub to_install: usize,
+    /// Number of packages to upgrade.
+    pub to_upgrade: usize,
+    /// Number of packages to remove (targets and their dependents).
+    pub to_remove: usize,
+    /// Number of dependencies in conflict.
+    pub conflicts: usize,
+    /// Number of dependencies that could not be found.
+    pub missing: usize,
+    /// Total download size of packages to install or upgrade, in bytes.
+    pub download_size: u64,
+    /// Total installed size of packages to install or upgrade, in bytes (new versions in full).
+    pub installed_size: u64,
+    /// Total installed size of packages to remove, in bytes.
+    pub removed_size: u64,
+    /// Packages to install, upgrade, or remove whose size is unknown.
+    pub unknown_size: Vec<String>,
+}
+
+impl TransactionSummary {
+    /// What: Summarize a transaction for a confirmation prompt.
+    ///
+    /// Inputs:
+    /// - `targets`: Packages the user asked to install.
+    /// - `resolution`: Resolved dependencies of `targets`.
+    /// - `removal`: Optional reverse dependency report of the packages to remove.
+    /// - `index`: Official package index used to look up package sizes.
+    ///
+    /// Output:
+    /// - `TransactionSummary` with counts per status and size totals.
+    ///
+    /// Details:
+    /// - `to_install` counts `targets` plus `ToInstall` dependencies, and `to_upgrade` counts
+    ///   `ToUpgrade` dependencies; a name is counted and sized once. Targets are always counted
+    ///   as installs, since the resolution carries no status for them.
+    /// - `conflicts` counts `Conflict` dependencies plus `resolution.conflicts`, deduplicated by
+    ///   name.
+    /// - `missing` counts `Missing` dependencies plus `resolution.missing`, deduplicated by name.
+    /// - Removed packages are the report's targets plus their dependents, deduplicated by name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use arch_toolkit::install::TransactionSummary;
+    /// use arch_toolkit::types::index::OfficialIndex;
+    /// use arch_toolkit::DependencyResolution;
+    ///
+    /// let summary = TransactionSummary::from(
+    ///     &[],
+    ///     &DependencyResolution::default(),
+    ///     None,
+    ///     &OfficialIndex::default(),
+    /// );
+    /// assert_eq!(summary.to_install, 0);
+    /// ```
+    #[must_use]
+    pub fn from(
+        targets: &[PackageRef],
+        resolution: &DependencyResolution,
+        removal: Option<&ReverseDependencyReport>,
+        index: &OfficialIndex,
+    ) -> Self {
+        let mut summary = Self::default();
+        let mut installed = BTreeSet::new();
+        let mut conflicts = BTreeSet::new();
+        let mut missing = BTreeSet::new();
+        let mut unknown_size = BTreeSet::new();
+
+        let mut add_sizes = |summary: &mut Self, name: &str| match index.find_package_by_name(name)
+        {
+            Some(pkg) if pkg.download_size > 0 || pkg.installed_size > 0 => {
+                summary.download_size += pkg.download_size;
+                summary.installed_size += pkg.installed_size;
+            }
+            _ => {
+                unknown_size.insert(name.to_string());
+            }
+        };
+
+        for target in targets {
+            if installed.insert(target.name.as_str()) {
+                summary.to_install += 1;
+                add_sizes(&mut summary, &target.name);
+            }
+        }
+        for dep in &resolution.dependencies {
+            match dep.status {
+                DependencyStatus::ToInstall | DependencyStatus::ToUpgrade { .. }
+                    if !installed.insert(dep.name.as_str()) =>
+                {
+                    continue;
+                }
+                DependencyStatus::ToInstall => summary.to_install += 1,
+                DependencyStatus::ToUpgrade { .. } => summary.to_upgrade += 1,
+                DependencyStatus::Conflict { .. } => {
+                    conflicts.insert(dep.name.as_str());
+                }
+                DependencyStatus::Missing => {
+                    missing.insert(dep.name.as_str());
+                }
+                DependencyStatus::Installed { .. } => {}
+            }
+            if dep.status.needs_action() {
+                add_sizes(&mut summary, &dep.name);
+            }
+        }
+        conflicts.extend(resolution.conflicts.iter().map(String::as_str));
+        summary.conflicts = conflicts.len();
+        missing.extend(resolution.missing.iter().map(String::as_str));
+        summary.missing = missing.len();
+
+        if let Some(report) = removal {
+            let removed: BTreeSet<&str> = report
+                .summaries
+                .iter()
+                .map(|s| s.package.as_str())
+                .chain(report.dependents.iter().map(|d| d.name.as_str()))
+                .collect();
+            summary.to_remove = removed.len();
+            for name in removed {
+                match index.find_package_by_name(name) {
+                    Some(pkg) if pkg.installed_size > 0 => {
+                        summary.removed_size += pkg.installed_size;
+                    }
+                    _ => {
+                        unknown_size.insert(name.to_string());
+                    }
+                }
+            }
+        }
+
+        summary.unknown_size = unknown_size.into_iter().collect();
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{dep, extra_ref};
+    use crate::types::dependency::ReverseDependencySummary;
+    use crate::types::index::OfficialPackage;
+
+    fn pkg(name: &str, download_size: u64, installed_size: u64) -> OfficialPackage {
+        OfficialPackage {
+            name: name.into(),
+            repo: "extra".into(),
+            arch: "x86_64".into(),
+            version: "1.0-1".into(),
+            description: String::new(),
+            packager: String::new(),
+            download_size,
+            installed_size,
+        }
+    }
+
+    #[test]
+    /// What: Aggregate counts and sizes over a resolution with mixed statuses.
+    ///
+    /// Inputs:
+    /// - Two packages to install, one to upgrade, one installed, one conflict, one missing.
+    /// - Removal of `old-lib` with dependent `old-app`.
+    ///
+    /// Output:
+    /// - Counts per status and size totals from the index; sizeless packages reported.
+    fn transaction_summary_counts_mixed_statuses() {
+        let resolution = DependencyResolution {
+            dependencies: vec![
+                dep("ripgrep", DependencyStatus::ToInstall),
+                dep("fd", DependencyStatus::ToInstall),
+                dep(
+                    "glibc",
+                    DependencyStatus::ToUpgrade {
+                        current: "2.39-1".into(),
+                        required: "2.40-1".into(),
+                    },
+                ),
+                dep(
+                    "bash",
+                    DependencyStatus::Installed {
+                        version: "5.2-1".into(),
+                    },
+                ),
+                dep(
+                    "foo",
+                    DependencyStatus::Conflict {
+                        reason: "conflicts with bar".into(),
+                    },
+                ),
+                dep("ghost", DependencyStatus::Missing),
+            ],
+            conflicts: Vec::new(),
+            missing: vec!["ghost".into()],
+            cycles: Vec::new(),
+            stale_pkgbuilds: Vec::new(),
+        };
+        let removal = ReverseDependencyReport {
+            dependents: vec![dep("old-app", DependencyStatus::ToInstall)],
+            summaries: vec![ReverseDependencySummary {
+                package: "old-lib".into(),
+                direct_dependents: 1,
+                transitive_dependents: 0,
+                total_dependents: 1,
+            }],
+            cycles: Vec::new(),
+        };
+        let mut index = OfficialIndex {
+            pkgs: vec![
+                pkg("ripgrep", 1_500_000, 4_000_000),
+                pkg("glibc", 10_000_000, 48_000_000),
+                pkg("bash", 2_000_000, 9_000_000),
+                pkg("old-lib", 300_000, 1_000_000),
+                pkg("old-app", 200_000, 700_000),
+            ],
+            ..OfficialIndex::default()
+        };
+        index.rebuild_name_index();
+
+        let summary = TransactionSummary::from(&[], &resolution, Some(&removal), &index);
+        assert_eq!(summary.to_install, 2);
+        assert_eq!(summary.to_upgrade, 1);
+        assert_eq!(summary.to_remove, 2);
+        assert_eq!(summary.conflicts, 1);
+        assert_eq!(summary.missing, 1);
+        assert_eq!(summary.download_size, 11_500_000);
+        assert_eq!(summary.installed_size, 52_000_000);
+        assert_eq!(summary.removed_size, 1_700_000);
+        assert_eq!(summary.unknown_size, vec!["fd".to_string()]);
+    }
+
+    #[test]
+    /// What: Count a conflict once when it is both a dependency and a resolution conflict.
+    ///
+    /// Inputs:
+    /// - `foo` marked as a `Conflict` dependency and listed in `resolution.conflicts`, as the
+    ///   resolver reports root conflicts.
+    ///
+    /// Output:
+    /// - `conflicts` is 1.
+    fn transaction_summary_dedupes_conflicts() {
+        let resolution = DependencyResolution {
+            dependencies: vec![dep(
+                "foo",
+                DependencyStatus::Conflict {
+                    reason: "Conflicts with bar".into(),
+                },
+            )],
+            conflicts: vec!["foo".into()],
+            ..DependencyResolution::default()
+        };
+
+        let summary = TransactionSummary::from(&[], &resolution, None, &OfficialIndex::default());
+        assert_eq!(summary.conflicts, 1);
+    }
+
+    #[test]
+    /// What: Count and size the install targets alongside their dependencies.
+    ///
+    /// Inputs:
+    /// - Targets `ripgrep` (in the index) and `yay` (not in the index), plus `ripgrep` again.
+    /// - Dependency `pcre2` to install, and `ripgrep` repeated as a dependency.
+    ///
+    /// Output:
+    /// - `to_install` is 3, sizes cover `ripgrep` and `pcre2` once, `yay` has unknown size.
+    fn transaction_summary_counts_targets() {
+        let resolution = DependencyResolution {
+            dependencies: vec![
+                dep("pcre2", DependencyStatus::ToInstall),
+                dep("ripgrep", DependencyStatus::ToInstall),
+            ],
+            ..DependencyResolution::default()
+        };
+        let mut index = OfficialIndex {
+            pkgs: vec![
+                pkg("ripgrep", 1_500_000, 4_000_000),
+                pkg("pcre2", 800_000, 2_000_000),
+            ],
+            ..OfficialIndex::default()
+        };
+        index.rebuild_name_index();
+        let targets = [extra_ref("ripgrep"), extra_ref("yay"), extra_ref("ripgrep")];
+
+        let summary = TransactionSummary::from(&targets, &resolution, None, &index);
+        assert_eq!(summary.to_install, 3);
+        assert_eq!(summary.download_size, 2_300_000);
+        assert_eq!(summary.installed_size, 6_000_000);
+        assert_eq!(summary.unknown_size, vec!["yay".to_string()]);
+    }
+}
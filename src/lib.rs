@@ -9,9 +9,13 @@
 //! - `aur`: AUR search, package info, comments, and PKGBUILD fetching
 //! - `deps`: Dependency resolution, parsing, and reverse dependency analysis
 //! - `index`: Package database queries (installed and explicit package tracking)
-//! - `install`: Installation command building (planned)
-//! - `news`: News feeds and security advisories (planned)
-//! - `sandbox`: PKGBUILD security analysis (planned)
+//! - `install`: Install plans, package list export, and pacman/AUR helper install commands
+//! - `stream`: Streaming AUR search results (`Aur::search_stream`)
+//! - `news`: Arch Linux news feed and security advisories (`news::fetch_news`,
+//!   `news::security_advisories`)
+//! - `sandbox`: PKGBUILD security analysis (`sandbox::analyze_pkgbuild`)
+//! - `blocking`: Synchronous AUR client for callers without an async runtime
+//!   (`ArchClient::blocking`)
 //!
 //! # Examples
 //!
@@ -142,6 +146,9 @@ pub mod cache;
 #[cfg(feature = "aur")]
 pub mod health;
 
+#[cfg(feature = "aur")]
+pub mod observer;
+
 #[cfg(feature = "aur")]
 mod env;
 
@@ -151,6 +158,21 @@ pub mod deps;
 #[cfg(feature = "index")]
 pub mod index;
 
+#[cfg(feature = "install")]
+pub mod install;
+
+#[cfg(feature = "sandbox")]
+pub mod sandbox;
+
+#[cfg(feature = "news")]
+pub mod news;
+
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
+#[cfg(all(test, feature = "deps"))]
+mod fixtures;
+
 /// Prelude module for convenient imports.
 ///
 /// This module re-exports commonly used types, traits, and functions,
@@ -161,6 +183,7 @@ pub mod index;
 /// ```no_run
 /// use arch_toolkit::prelude::*;
 ///
+/// # #[cfg(feature = "aur")]
 /// # async fn example() -> Result<()> {
 /// let client = ArchClient::new()?;
 /// let packages: Vec<AurPackage> = client.aur().search("yay").await?;
@@ -171,34 +194,55 @@ pub mod prelude;
 
 // Re-export commonly used types
 pub use error::{ArchToolkitError as Error, Result};
-pub use types::{AurComment, AurPackage, AurPackageDetails};
+pub use types::{
+    AurComment, AurPackage, AurPackageDetails, AurUpdate, AurUpdateReport, SearchPage,
+};
 
 #[cfg(feature = "aur")]
 pub use types::{HealthStatus, ServiceStatus};
 
 #[cfg(feature = "deps")]
 pub use types::{
-    Dependency, DependencySource, DependencySpec, DependencyStatus, PackageRef, PackageSource,
-    ReverseDependencySummary, SrcinfoData,
+    ArchDependencies, Dependency, DependencyArrays, DependencyKind, DependencySource,
+    DependencySpec, DependencyStatus, FieldChange, InstallReason, PackageRef, PackageSource,
+    ParsedPkgbuild, PkgbuildDiff, ProvidedPackage, ReverseDependencySummary, SplitPackage,
+    SrcinfoData,
 };
 
+#[cfg(feature = "news")]
+pub use types::{Advisory, AdvisorySeverity, AdvisoryStatus, NewsItem};
+
 #[cfg(feature = "index")]
-pub use types::index::{IndexQueryResult, InstalledPackagesMode, OfficialIndex, OfficialPackage};
+pub use types::index::{
+    FuzzyOptions, IndexQueryResult, InstalledPackagesMode, OfficialIndex, OfficialPackage,
+    ResultSource, UnifiedResult,
+};
 
 #[cfg(feature = "deps")]
 pub use deps::{
     DependencyResolution, DependencyResolver, ResolverConfig, ReverseDependencyAnalyzer,
-    ReverseDependencyReport,
+    ReverseDependencyReport, ReverseOptions,
 };
 
 #[cfg(feature = "aur")]
-pub use aur::{AurApi, MockAurApi};
+pub use aur::{AurApi, MockAurApi, MockAurApiBuilder};
+
+#[cfg(feature = "aur")]
+pub use client::{
+    ArchClient, ArchClientBuilder, CacheInvalidator, CircuitState, ClientEvent, RetryPolicy,
+};
 
 #[cfg(feature = "aur")]
-pub use client::{ArchClient, ArchClientBuilder, CacheInvalidator, RetryPolicy};
+pub use cache::{CacheConfig, CacheConfigBuilder, CacheStats};
 
 #[cfg(feature = "aur")]
-pub use cache::{CacheConfig, CacheConfigBuilder};
+pub use observer::{NoopObserver, Observer, Outcome};
+
+#[cfg(feature = "blocking")]
+pub use blocking::BlockingArchClient;
 
 #[cfg(feature = "aur")]
 pub use aur::validation::ValidationConfig;
+
+#[cfg(feature = "timezone")]
+pub use chrono_tz::Tz;
@@ -0,0 +1,303 @@
+//! Arch security tracker advisory fetching and matching.
+
+use std::collections::HashMap;
+
+use reqwest::Client;
+use serde::Deserialize;
+use tracing::{debug, warn};
+
+use crate::client::{ArchClient, check_status, reset_archlinux_backoff};
+use crate::deps::get_installed_version;
+use crate::error::{ArchToolkitError, Result};
+use crate::index::refresh_installed_cache;
+use crate::types::{Advisory, AdvisorySeverity, AdvisoryStatus};
+
+/// URL of the Arch security tracker JSON listing of all advisory groups.
+pub const SECURITY_TRACKER_URL: &str = "https://security.archlinux.org/all.json";
+
+/// What: Advisory group as returned by the security tracker.
+#[derive(Deserialize)]
+struct RawAdvisory {
+    /// Group identifier (`AVG-...`).
+    name: String,
+    /// Affected package names.
+    #[serde(default)]
+    packages: Vec<String>,
+    /// Tracker severity.
+    severity: AdvisorySeverity,
+    /// Tracker status.
+    status: AdvisoryStatus,
+    /// Affected version.
+    #[serde(default)]
+    affected: String,
+    /// Fixed version, `null` while unfixed.
+    #[serde(default)]
+    fixed: Option<String>,
+}
+
+impl From<RawAdvisory> for Advisory {
+    fn from(raw: RawAdvisory) -> Self {
+        Self {
+            name: raw.name,
+            packages: raw.packages,
+            severity: raw.severity,
+            status: raw.status,
+            affected_version: raw.affected,
+            fixed_version: raw.fixed.filter(|fixed| !fixed.is_empty()),
+        }
+    }
+}
+
+/// What: Fetch all advisory groups from the Arch security tracker.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+///
+/// Output:
+/// - `Result<Vec<Advisory>>` in tracker order (newest group first).
+///
+/// Details:
+/// - Fetches `https://security.archlinux.org/all.json` and parses it with `parse_advisories()`
+/// - Applies the archlinux.org rate limiter and circuit breaker
+/// - Uses the client's retry policy under the operation name `"advisories"`
+/// - Caches the full list if `CacheConfig::enable_advisories` is set (TTL `advisories_ttl`)
+///
+/// # Errors
+/// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+/// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
+/// - Returns `Err(ArchToolkitError::ServiceUnavailable)` if the circuit breaker is open
+/// - Returns `Err(ArchToolkitError::Parse)` if the response is not valid tracker JSON
+pub async fn security_advisories(client: &ArchClient) -> Result<Vec<Advisory>> {
+    // Check cache if enabled
+    if let Some(cache_config) = client.cache_config()
+        && cache_config.enable_advisories
+        && let Some(cache) = client.cache()
+        && let Some(cached) = cache.get::<Vec<Advisory>>(&cache.keys().advisories())
+    {
+        debug!("cache hit for advisories");
+        return Ok(cached);
+    }
+
+    debug!(url = SECURITY_TRACKER_URL, "fetching security advisories");
+
+    // Apply rate limiting for archlinux.org
+    let _permit = client.archlinux_permit().await?;
+
+    let http_client = client.http_client();
+    let json = client
+        .execute_with_retry("advisories", "security tracker", || async {
+            perform_advisories_request(http_client, SECURITY_TRACKER_URL).await
+        })
+        .await?;
+    let advisories = parse_advisories(&json)?;
+
+    // Store in cache if enabled
+    if let Some(cache_config) = client.cache_config()
+        && cache_config.enable_advisories
+        && let Some(cache) = client.cache()
+    {
+        let _ = cache.set(
+            &cache.keys().advisories(),
+            &advisories,
+            cache_config.advisories_ttl,
+        );
+    }
+
+    Ok(advisories)
+}
+
+/// What: Fetch advisories that affect installed packages at their installed version.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+///
+/// Output:
+/// - `Result<Vec<Advisory>>` with the affecting advisories, in tracker order.
+///
+/// Details:
+/// - Fetches advisories with `security_advisories()` (cached if enabled)
+/// - Cross-references package names with `index::refresh_installed_cache()`
+/// - Checks each installed package's version with `Advisory::affects_version()`
+/// - pacman queries run on a blocking thread
+/// - Without pacman, no packages are installed and the result is empty
+///
+/// # Errors
+/// - Returns the same errors as `security_advisories()`
+/// - Returns `Err(ArchToolkitError::Parse)` if the blocking pacman task fails
+pub async fn advisories_for_installed(client: &ArchClient) -> Result<Vec<Advisory>> {
+    let advisories = security_advisories(client).await?;
+
+    tokio::task::spawn_blocking(move || {
+        let installed = refresh_installed_cache(None)?;
+        let mut versions: HashMap<String, Option<String>> = HashMap::new();
+        Ok(affecting_advisories(advisories, |name| {
+            if !installed.contains(name) {
+                return None;
+            }
+            versions
+                .entry(name.to_string())
+                .or_insert_with(|| get_installed_version(name).ok())
+                .clone()
+        }))
+    })
+    .await
+    .map_err(|e| ArchToolkitError::Parse(format!("Blocking task failed: {e}")))?
+}
+
+/// What: Keep advisories that affect an installed package version.
+///
+/// Inputs:
+/// - `advisories`: Advisories to filter.
+/// - `installed_version`: Returns the installed version of a package, or `None` if not installed.
+///
+/// Output:
+/// - Advisories where at least one package is installed at an affected version.
+fn affecting_advisories(
+    advisories: Vec<Advisory>,
+    mut installed_version: impl FnMut(&str) -> Option<String>,
+) -> Vec<Advisory> {
+    advisories
+        .into_iter()
+        .filter(|advisory| {
+            advisory.packages.iter().any(|name| {
+                installed_version(name).is_some_and(|version| advisory.affects_version(&version))
+            })
+        })
+        .collect()
+}
+
+/// What: Perform the security tracker HTTP request.
+///
+/// Inputs:
+/// - `client`: HTTP client.
+/// - `url`: Tracker JSON URL.
+///
+/// Output:
+/// - `Result<String>` with the raw JSON body.
+///
+/// # Errors
+/// - Returns `Err(ArchToolkitError::Network)` on request failure
+/// - Returns `Err(ArchToolkitError::Http)` on a 4xx/5xx status
+async fn perform_advisories_request(client: &Client, url: &str) -> Result<String> {
+    let response = client.get(url).send().await.map_err(|e| {
+        warn!(error = %e, "security tracker request failed");
+        ArchToolkitError::Network(e)
+    })?;
+    reset_archlinux_backoff();
+
+    let response = check_status(response).inspect_err(|e| {
+        warn!(error = %e, "security tracker returned non-success status");
+    })?;
+
+    response.text().await.map_err(ArchToolkitError::Network)
+}
+
+/// What: Parse the Arch security tracker advisory group listing.
+///
+/// Inputs:
+/// - `json`: JSON array of advisory groups, as served by the tracker.
+///
+/// Output:
+/// - `Result<Vec<Advisory>>` in input order.
+///
+/// Details:
+/// - Maps the tracker's `affected`/`fixed` fields to `affected_version`/`fixed_version`
+/// - Unknown severities and statuses map to `Unknown`; other tracker fields are ignored
+///
+/// # Errors
+/// - Returns `Err(ArchToolkitError::Parse)` if the JSON does not match the tracker format
+pub fn parse_advisories(json: &str) -> Result<Vec<Advisory>> {
+    let raw: Vec<RawAdvisory> = serde_json::from_str(json)
+        .map_err(|e| ArchToolkitError::Parse(format!("invalid security tracker JSON: {e}")))?;
+    Ok(raw.into_iter().map(Advisory::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Captured excerpt of `https://security.archlinux.org/all.json`.
+    const TRACKER_SAMPLE: &str = r#"[
+  {"name": "AVG-2843", "packages": ["openssl", "lib32-openssl"], "status": "Fixed",
+   "severity": "High", "type": "denial of service", "affected": "3.3.2-1",
+   "fixed": "3.4.0-1", "ticket": null, "issues": ["CVE-2024-9143"], "advisories": ["ASA-202411-1"]},
+  {"name": "AVG-2801", "packages": ["vim"], "status": "Vulnerable",
+   "severity": "Medium", "type": "arbitrary code execution", "affected": "9.1.0-1",
+   "fixed": null, "ticket": null, "issues": ["CVE-2024-0001"], "advisories": []},
+  {"name": "AVG-2790", "packages": ["curl"], "status": "Not affected",
+   "severity": "Unknown", "type": "unknown", "affected": "8.9.0-1",
+   "fixed": null, "ticket": null, "issues": [], "advisories": []},
+  {"name": "AVG-2777", "packages": ["zlib"], "status": "Awaiting triage",
+   "severity": "Severe", "type": "unknown", "affected": "1.3-1",
+   "fixed": "", "ticket": null, "issues": [], "advisories": []}
+]"#;
+
+    #[test]
+    /// What: Parse a captured security tracker sample.
+    ///
+    /// Inputs:
+    /// - Groups with fixed, unfixed, not-affected, and unrecognized status/severity values.
+    ///
+    /// Output:
+    /// - Fields mapped; unknown values become `Unknown`; empty `fixed` becomes `None`.
+    fn test_parse_advisories_sample() {
+        let advisories = parse_advisories(TRACKER_SAMPLE).expect("sample parses");
+        assert_eq!(advisories.len(), 4);
+
+        let openssl = &advisories[0];
+        assert_eq!(openssl.name, "AVG-2843");
+        assert_eq!(openssl.packages, vec!["openssl", "lib32-openssl"]);
+        assert_eq!(openssl.severity, AdvisorySeverity::High);
+        assert_eq!(openssl.status, AdvisoryStatus::Fixed);
+        assert_eq!(openssl.affected_version, "3.3.2-1");
+        assert_eq!(openssl.fixed_version.as_deref(), Some("3.4.0-1"));
+
+        assert_eq!(advisories[1].fixed_version, None);
+        assert_eq!(advisories[2].status, AdvisoryStatus::NotAffected);
+        assert_eq!(advisories[3].status, AdvisoryStatus::Unknown);
+        assert_eq!(advisories[3].severity, AdvisorySeverity::Unknown);
+        assert_eq!(advisories[3].fixed_version, None);
+    }
+
+    #[test]
+    /// What: Keep only advisories affecting installed package versions.
+    ///
+    /// Inputs:
+    /// - Sample advisories; openssl installed below the fix, vim and curl installed,
+    ///   zlib not installed.
+    ///
+    /// Output:
+    /// - openssl (below fix) and vim (unfixed) are returned; curl (not affected) is not.
+    fn test_affecting_advisories() {
+        let advisories = parse_advisories(TRACKER_SAMPLE).expect("sample parses");
+        let installed = HashMap::from([
+            ("openssl", "3.3.2-1"),
+            ("vim", "9.1.0-1"),
+            ("curl", "8.9.0-1"),
+        ]);
+
+        let affecting = affecting_advisories(advisories.clone(), |name| {
+            installed.get(name).map(|version| (*version).to_string())
+        });
+        let names: Vec<&str> = affecting.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["AVG-2843", "AVG-2801"]);
+
+        let upgraded = affecting_advisories(advisories, |name| {
+            (name == "openssl").then(|| "3.4.0-1".to_string())
+        });
+        assert!(upgraded.is_empty());
+    }
+
+    #[test]
+    /// What: Reject JSON that is not a tracker listing.
+    ///
+    /// Inputs:
+    /// - A JSON object instead of an array.
+    ///
+    /// Output:
+    /// - `ArchToolkitError::Parse`.
+    fn test_parse_advisories_invalid() {
+        let err = parse_advisories(r#"{"error": "not found"}"#).expect_err("object is rejected");
+        assert!(matches!(err, ArchToolkitError::Parse(_)));
+    }
+}
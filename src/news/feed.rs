@@ -0,0 +1,386 @@
+//! Arch Linux news RSS feed fetching and parsing.
+
+use chrono::{DateTime, Utc};
+use quick_xml::Reader;
+use quick_xml::events::Event;
+use reqwest::Client;
+use scraper::Html;
+use tracing::{debug, warn};
+
+use crate::client::{ArchClient, check_status, reset_archlinux_backoff};
+use crate::error::{ArchToolkitError, Result};
+use crate::types::NewsItem;
+
+/// URL of the Arch Linux news RSS feed.
+pub const NEWS_FEED_URL: &str = "https://archlinux.org/feeds/news/";
+
+/// What: Fetch the Arch Linux news feed.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+///
+/// Output:
+/// - `Result<Vec<NewsItem>>` sorted newest first.
+///
+/// Details:
+/// - Fetches `https://archlinux.org/feeds/news/` and parses it with `parse_news_feed()`
+/// - Applies the archlinux.org rate limiter and circuit breaker
+/// - Uses the client's retry policy under the operation name `"news"`
+/// - Checks the cache before making a request if `CacheConfig::enable_news` is set
+///
+/// # Errors
+/// - Returns `Err(ArchToolkitError::Network)` if the HTTP request fails
+/// - Returns `Err(ArchToolkitError::Http)` if the server answers with a 4xx/5xx status
+/// - Returns `Err(ArchToolkitError::ServiceUnavailable)` if the circuit breaker is open
+/// - Returns `Err(ArchToolkitError::Parse)` if the feed is not well-formed XML
+pub async fn fetch_news(client: &ArchClient) -> Result<Vec<NewsItem>> {
+    // Check cache if enabled
+    if let Some(cache_config) = client.cache_config()
+        && cache_config.enable_news
+        && let Some(cache) = client.cache()
+        && let Some(cached) = cache.get::<Vec<NewsItem>>(&cache.keys().news())
+    {
+        debug!("cache hit for news");
+        return Ok(cached);
+    }
+
+    debug!(url = NEWS_FEED_URL, "fetching Arch Linux news feed");
+
+    // Apply rate limiting for archlinux.org
+    let _permit = client.archlinux_permit().await?;
+
+    let http_client = client.http_client();
+    let xml = client
+        .execute_with_retry("news", "news feed", || async {
+            perform_news_request(http_client, NEWS_FEED_URL).await
+        })
+        .await?;
+    let items = parse_news_feed(&xml)?;
+
+    // Store in cache if enabled
+    if let Some(cache_config) = client.cache_config()
+        && cache_config.enable_news
+        && let Some(cache) = client.cache()
+    {
+        let _ = cache.set(&cache.keys().news(), &items, cache_config.news_ttl);
+    }
+
+    Ok(items)
+}
+
+/// What: Fetch news published after a given time.
+///
+/// Inputs:
+/// - `client`: `ArchClient` to use for requests.
+/// - `since`: Only items published strictly after this time are returned.
+///
+/// Output:
+/// - `Result<Vec<NewsItem>>` sorted newest first.
+///
+/// Details:
+/// - Filters the result of `fetch_news()`, so caching and retries apply
+/// - Pair with `deps::last_upgrade_time()` to list news since the last
+///   `pacman -Syu`, and `NewsItem::requires_intervention()` to highlight required actions
+///
+/// # Errors
+/// - Returns the same errors as `fetch_news()`
+pub async fn news_since(client: &ArchClient, since: DateTime<Utc>) -> Result<Vec<NewsItem>> {
+    let items = fetch_news(client).await?;
+    Ok(filter_since(items, since))
+}
+
+/// What: Keep news items published after a given time.
+///
+/// Inputs:
+/// - `items`: News items.
+/// - `since`: Exclusive lower bound.
+///
+/// Output:
+/// - Items with `published > since`, order preserved.
+fn filter_since(items: Vec<NewsItem>, since: DateTime<Utc>) -> Vec<NewsItem> {
+    items
+        .into_iter()
+        .filter(|item| item.published > since)
+        .collect()
+}
+
+/// What: Perform the news feed HTTP request.
+///
+/// Inputs:
+/// - `client`: HTTP client.
+/// - `url`: Feed URL.
+///
+/// Output:
+/// - `Result<String>` with the raw feed XML.
+///
+/// # Errors
+/// - Returns `Err(ArchToolkitError::Network)` on request failure
+/// - Returns `Err(ArchToolkitError::Http)` on a 4xx/5xx status
+async fn perform_news_request(client: &Client, url: &str) -> Result<String> {
+    let response = client.get(url).send().await.map_err(|e| {
+        warn!(error = %e, "news feed request failed");
+        ArchToolkitError::Network(e)
+    })?;
+    reset_archlinux_backoff();
+
+    let response = check_status(response).inspect_err(|e| {
+        warn!(error = %e, "news feed returned non-success status");
+    })?;
+
+    response.text().await.map_err(ArchToolkitError::Network)
+}
+
+/// What: RSS `<item>` child element currently being read.
+#[derive(Clone, Copy)]
+enum ItemField {
+    /// `<title>`
+    Title,
+    /// `<link>`
+    Link,
+    /// `<pubDate>`
+    PubDate,
+    /// `<description>`
+    Description,
+}
+
+/// What: Raw text collected for one RSS item.
+#[derive(Default)]
+struct RawItem {
+    /// `<title>` text.
+    title: String,
+    /// `<link>` text.
+    link: String,
+    /// `<pubDate>` text.
+    pub_date: String,
+    /// `<description>` text (HTML).
+    description: String,
+}
+
+impl RawItem {
+    /// What: Append text to a field.
+    fn push(&mut self, field: ItemField, text: &str) {
+        match field {
+            ItemField::Title => self.title.push_str(text),
+            ItemField::Link => self.link.push_str(text),
+            ItemField::PubDate => self.pub_date.push_str(text),
+            ItemField::Description => self.description.push_str(text),
+        }
+    }
+
+    /// What: Convert the raw item into a `NewsItem`.
+    ///
+    /// Output:
+    /// - `None` if the title or link is missing or the date cannot be parsed
+    fn into_news_item(self) -> Option<NewsItem> {
+        let title = self.title.trim();
+        let url = self.link.trim();
+        if title.is_empty() || url.is_empty() {
+            debug!(title, url, "skipping news item without title or link");
+            return None;
+        }
+        let Some(published) = parse_pub_date(&self.pub_date) else {
+            warn!(title, pub_date = %self.pub_date, "skipping news item with invalid pubDate");
+            return None;
+        };
+        Some(NewsItem {
+            title: title.to_string(),
+            url: url.to_string(),
+            published,
+            summary: html_to_text(&self.description),
+        })
+    }
+}
+
+/// What: Parse Arch Linux news RSS XML.
+///
+/// Inputs:
+/// - `xml`: RSS 2.0 document.
+///
+/// Output:
+/// - `Result<Vec<NewsItem>>` sorted newest first.
+///
+/// Details:
+/// - Reads `title`, `link`, `pubDate`, and `description` of every `<item>`
+/// - `pubDate` is parsed as RFC 2822 (e.g. `Tue, 10 Jun 2025 14:09:03 +0000`) and converted to UTC
+/// - Descriptions may be escaped HTML or CDATA; markup is stripped for `summary`
+/// - Items without title, link, or a valid `pubDate` are skipped
+///
+/// # Errors
+/// - Returns `Err(ArchToolkitError::Parse)` if the XML is malformed
+pub fn parse_news_feed(xml: &str) -> Result<Vec<NewsItem>> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut items = Vec::new();
+    let mut current: Option<RawItem> = None;
+    let mut field: Option<ItemField> = None;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| ArchToolkitError::Parse(format!("invalid news feed XML: {e}")))?;
+        match event {
+            Event::Start(start) => match start.local_name().as_ref() {
+                b"item" => current = Some(RawItem::default()),
+                b"title" => field = Some(ItemField::Title),
+                b"link" => field = Some(ItemField::Link),
+                b"pubDate" => field = Some(ItemField::PubDate),
+                b"description" => field = Some(ItemField::Description),
+                _ => field = None,
+            },
+            Event::Text(text) => {
+                if let (Some(item), Some(field)) = (current.as_mut(), field) {
+                    let text = text.unescape().map_err(|e| {
+                        ArchToolkitError::Parse(format!("invalid news feed text: {e}"))
+                    })?;
+                    item.push(field, &text);
+                }
+            }
+            Event::CData(data) => {
+                if let (Some(item), Some(field)) = (current.as_mut(), field) {
+                    item.push(field, &String::from_utf8_lossy(&data));
+                }
+            }
+            Event::End(end) => {
+                field = None;
+                if end.local_name().as_ref() == b"item"
+                    && let Some(item) = current.take().and_then(RawItem::into_news_item)
+                {
+                    items.push(item);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    items.sort_by_key(|item| std::cmp::Reverse(item.published));
+    Ok(items)
+}
+
+/// What: Parse an RSS `pubDate` into UTC.
+///
+/// Inputs:
+/// - `value`: RFC 2822 date string
+///
+/// Output:
+/// - `Some(DateTime<Utc>)`, or `None` if the date is invalid
+fn parse_pub_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(value.trim())
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+/// What: Convert an HTML fragment to whitespace-normalized plain text.
+///
+/// Inputs:
+/// - `html`: HTML fragment
+///
+/// Output:
+/// - Text content with runs of whitespace collapsed to single spaces
+fn html_to_text(html: &str) -> String {
+    let fragment = Html::parse_fragment(html);
+    let text: String = fragment.root_element().text().collect();
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// Captured excerpt of `https://archlinux.org/feeds/news/` (items reordered).
+    const FEED_SAMPLE: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:atom="http://www.w3.org/2005/Atom"><channel><title>Arch Linux: Recent news updates</title><link>https://archlinux.org/news/</link><description>The latest and greatest news from the Arch Linux distribution.</description><atom:link href="https://archlinux.org/feeds/news/" rel="self"></atom:link><language>en-us</language><lastBuildDate>Sat, 21 Jun 2025 10:15:41 +0000</lastBuildDate><item><title>Valkey to replace Redis in the [extra] Repository</title><link>https://archlinux.org/news/valkey-to-replace-redis-in-the-extra-repository/</link><description>&lt;p&gt;Valkey, a high-performance key/value datastore, will be replacing redis in the [extra] repository.&lt;/p&gt;
+&lt;p&gt;Users are advised to begin migrating to &lt;code&gt;valkey&lt;/code&gt;.&lt;/p&gt;</description><dc:creator xmlns:dc="http://purl.org/dc/elements/1.1/">Andrew Crerar</dc:creator><pubDate>Thu, 17 Apr 2025 14:36:52 +0000</pubDate><guid isPermaLink="false">tag:archlinux.org,2025-04-17:/news/valkey-to-replace-redis-in-the-extra-repository/</guid></item><item><title>linux-firmware &gt;= 20250613.12fe085f-5 upgrade requires manual intervention</title><link>https://archlinux.org/news/linux-firmware-2025061312fe085f-5-upgrade-requires-manual-intervention/</link><description>&lt;p&gt;With &lt;code&gt;20250613.12fe085f-5&lt;/code&gt;, we split our firmware into several vendor-focused packages.&lt;/p&gt;
+&lt;p&gt;To upgrade, please use:&lt;/p&gt;
+&lt;pre&gt;&lt;code&gt;pacman -Syu linux-firmware
+&lt;/code&gt;&lt;/pre&gt;</description><dc:creator xmlns:dc="http://purl.org/dc/elements/1.1/">Frederik Schwan</dc:creator><pubDate>Sat, 21 Jun 2025 12:15:41 +0200</pubDate><guid isPermaLink="false">tag:archlinux.org,2025-06-21:/news/linux-firmware-2025061312fe085f-5-upgrade-requires-manual-intervention/</guid></item><item><title>Transition to the new WoW64 wine and wine-staging</title><link>https://archlinux.org/news/transition-to-the-new-wow64-wine-and-wine-staging/</link><description><![CDATA[<p>As of <strong>wine 10.8-1</strong> the package is built as a pure WoW64 build.</p>]]></description><pubDate>Tue, 24 Jun 2025 18:40:27 GMT</pubDate></item><item><title>Broken item</title><link>https://archlinux.org/news/broken/</link><pubDate>not a date</pubDate></item></channel></rss>"#;
+
+    #[test]
+    /// What: Parse a captured news feed sample.
+    ///
+    /// Inputs:
+    /// - RSS with escaped HTML, CDATA, `+0200`/`GMT` dates, and an item with an invalid date.
+    ///
+    /// Output:
+    /// - Three items sorted newest first, dates converted to UTC, markup stripped from summaries.
+    fn test_parse_news_feed_sample() {
+        let items = parse_news_feed(FEED_SAMPLE).expect("sample feed parses");
+
+        let titles: Vec<&str> = items.iter().map(|item| item.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Transition to the new WoW64 wine and wine-staging",
+                "linux-firmware >= 20250613.12fe085f-5 upgrade requires manual intervention",
+                "Valkey to replace Redis in the [extra] Repository",
+            ]
+        );
+
+        let firmware = &items[1];
+        assert_eq!(
+            firmware.url,
+            "https://archlinux.org/news/linux-firmware-2025061312fe085f-5-upgrade-requires-manual-intervention/"
+        );
+        assert_eq!(
+            firmware.published,
+            Utc.with_ymd_and_hms(2025, 6, 21, 10, 15, 41)
+                .single()
+                .expect("valid date")
+        );
+        assert!(
+            firmware
+                .summary
+                .starts_with("With 20250613.12fe085f-5, we split")
+        );
+        assert!(firmware.summary.contains("pacman -Syu linux-firmware"));
+        assert!(!firmware.summary.contains('<'));
+
+        assert_eq!(
+            items[0].published,
+            Utc.with_ymd_and_hms(2025, 6, 24, 18, 40, 27)
+                .single()
+                .expect("valid date")
+        );
+        assert_eq!(
+            items[0].summary,
+            "As of wine 10.8-1 the package is built as a pure WoW64 build."
+        );
+        assert_eq!(items[2].published.timestamp(), 1_744_900_612);
+    }
+
+    #[test]
+    /// What: Keep only news published after the given time.
+    ///
+    /// Inputs:
+    /// - Sample feed filtered at the linux-firmware item's publication time.
+    ///
+    /// Output:
+    /// - Only the newer wine item (the bound is exclusive).
+    fn test_filter_since() {
+        let items = parse_news_feed(FEED_SAMPLE).expect("sample feed parses");
+        let since = items[1].published;
+
+        let recent = filter_since(items, since);
+        assert_eq!(recent.len(), 1);
+        assert!(
+            recent[0]
+                .title
+                .starts_with("Transition to the new WoW64 wine")
+        );
+    }
+
+    #[test]
+    /// What: Reject malformed XML.
+    ///
+    /// Inputs:
+    /// - Feed with mismatched closing tags.
+    ///
+    /// Output:
+    /// - `ArchToolkitError::Parse`.
+    fn test_parse_news_feed_malformed() {
+        let result = parse_news_feed("<rss><channel><item><title>x</link></item></channel></rss>");
+        assert!(matches!(result, Err(ArchToolkitError::Parse(_))));
+    }
+}
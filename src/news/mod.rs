@@ -0,0 +1,84 @@
+//! Arch Linux news, announcements, and security advisories.
+//!
+//! This module fetches the official Arch Linux news feed, which announces changes
+//! that may require attention when upgrading. `news_since()` narrows the feed to items
+//! published after a point in time, and `NewsItem::requires_intervention()` flags
+//! announcements that ask for manual action.
+//!
+//! Security advisories come from the Arch security tracker: `security_advisories()`
+//! lists every advisory group, and `advisories_for_installed()` keeps those affecting
+//! installed packages at their installed version.
+//!
+//! # Features
+//!
+//! This module requires the `news` feature flag to be enabled:
+//!
+//! ```toml
+//! [dependencies]
+//! arch-toolkit = { version = "0.2", features = ["news"] }
+//! ```
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use arch_toolkit::ArchClient;
+//! use arch_toolkit::news::fetch_news;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = ArchClient::new()?;
+//! for item in fetch_news(&client).await?.iter().take(3) {
+//!     println!("{} ({})", item.title, item.published.date_naive());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Warn About Manual Interventions Since the Last Upgrade
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! use arch_toolkit::ArchClient;
+//! use arch_toolkit::deps::last_upgrade_time;
+//! use arch_toolkit::news::news_since;
+//! use chrono::{Duration, Utc};
+//!
+//! let client = ArchClient::new()?;
+//! // Fall back to the last 30 days if pacman.log is unreadable
+//! let since = last_upgrade_time().unwrap_or_else(|| Utc::now() - Duration::days(30));
+//! for item in news_since(&client, since).await? {
+//!     if item.requires_intervention() {
+//!         println!("Action required: {} <{}>", item.title, item.url);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Check Installed Packages Against Security Advisories
+//!
+//! ```no_run
+//! use arch_toolkit::ArchClient;
+//! use arch_toolkit::news::advisories_for_installed;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = ArchClient::new()?;
+//! for advisory in advisories_for_installed(&client).await? {
+//!     println!(
+//!         "{} ({:?}): {} fixed in {}",
+//!         advisory.name,
+//!         advisory.severity,
+//!         advisory.packages.join(", "),
+//!         advisory.fixed_version.as_deref().unwrap_or("no release yet")
+//!     );
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+mod advisories;
+mod feed;
+
+pub use advisories::{
+    SECURITY_TRACKER_URL, advisories_for_installed, parse_advisories, security_advisories,
+};
+pub use feed::{NEWS_FEED_URL, fetch_news, news_since, parse_news_feed};
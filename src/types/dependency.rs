@@ -1,14 +1,22 @@
 //! Dependency-related data types for dependency resolution operations.
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
+use crate::error::Result;
+
 // === Enums ===
 
 /// Status of a dependency relative to the current system state.
 ///
 /// This enum represents the installation status and requirements for a dependency,
 /// used throughout the dependency resolution process to track what actions are needed.
+///
+/// Serialized with a `type` tag next to the variant fields, e.g.
+/// `{"type":"ToUpgrade","current":"1.0","required":"2.0"}` or `{"type":"Missing"}`.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
 pub enum DependencyStatus {
     /// Already installed and version matches requirement.
     Installed {
@@ -142,6 +150,85 @@ impl std::fmt::Display for DependencySource {
     }
 }
 
+/// Kind of a dependency, by the PKGBUILD array it is declared in.
+///
+/// Ordered from strongest to weakest requirement: a package that is both a runtime and a
+/// make dependency is reported as `Runtime`.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum DependencyKind {
+    /// Runtime dependency (`depends`).
+    #[default]
+    Runtime,
+    /// Build-time dependency (`makedepends`).
+    Make,
+    /// Test dependency (`checkdepends`).
+    Check,
+    /// Optional dependency (`optdepends`).
+    Opt,
+}
+
+impl std::fmt::Display for DependencyKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Runtime => write!(f, "Runtime"),
+            Self::Make => write!(f, "Make"),
+            Self::Check => write!(f, "Check"),
+            Self::Opt => write!(f, "Optional"),
+        }
+    }
+}
+
+/// Why an installed package is present, from pacman's "Install Reason" field.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum InstallReason {
+    /// Installed explicitly by the user.
+    Explicit,
+    /// Pulled in as a dependency of another package.
+    Dependency,
+    /// Not installed, or the reason was not queried.
+    #[default]
+    Unknown,
+}
+
+impl InstallReason {
+    /// What: Parse the value of pacman's "Install Reason" field.
+    ///
+    /// Inputs:
+    /// - `value`: Field value, e.g. "Explicitly installed" or
+    ///   "Installed as a dependency for another package".
+    ///
+    /// Output:
+    /// - The matching reason, or `Unknown` for unrecognized values.
+    ///
+    /// Details:
+    /// - Expects C-locale output, as produced by the `deps` queries.
+    #[must_use]
+    pub fn from_pacman(value: &str) -> Self {
+        let value = value.trim().to_lowercase();
+        if value.contains("explicit") {
+            Self::Explicit
+        } else if value.contains("dependency") {
+            Self::Dependency
+        } else {
+            Self::Unknown
+        }
+    }
+}
+
+impl std::fmt::Display for InstallReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Explicit => write!(f, "Explicit"),
+            Self::Dependency => write!(f, "Dependency"),
+            Self::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
 /// Package source for dependency resolution input.
 ///
 /// Used when specifying packages to resolve dependencies for, indicating whether
@@ -192,6 +279,19 @@ pub struct Dependency {
     pub is_core: bool,
     /// Whether this is a critical system package.
     pub is_system: bool,
+    /// Dependency array this dependency was declared in.
+    #[serde(default)]
+    pub kind: DependencyKind,
+    /// Whether this package was explicitly installed (reverse dependency analysis only).
+    #[serde(default)]
+    pub explicit: bool,
+    /// Why this package is installed (reverse dependency analysis only).
+    #[serde(default)]
+    pub install_reason: InstallReason,
+    /// Installed package that satisfies this dependency through its `provides` (e.g. `cronie`
+    /// for `cron`); `None` when the dependency is installed under its own name or not at all.
+    #[serde(default)]
+    pub satisfied_by: Option<String>,
 }
 
 /// Package reference for dependency resolution input.
@@ -208,6 +308,20 @@ pub struct PackageRef {
     pub source: PackageSource,
 }
 
+/// A name provided by an installed package, such as `rust=1.70` from `rustup`.
+///
+/// Built from the `Provides` field of installed packages by `deps::get_provided_packages()`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ProvidedPackage {
+    /// Provided name.
+    pub name: String,
+    /// Provided version, if the provide is versioned (`name=version`).
+    pub version: Option<String>,
+    /// Installed package declaring the provide, when known.
+    #[serde(default)]
+    pub provider: Option<String>,
+}
+
 /// Parsed dependency specification (name with optional version requirement).
 ///
 /// Result of parsing a dependency string like "python>=3.12" or "glibc".
@@ -287,19 +401,27 @@ impl std::fmt::Display for DependencySpec {
 ///
 /// Contains the list of packages that depend on the target packages, along with
 /// summary statistics for each target package.
-#[derive(Clone, Debug, Default)]
+///
+/// Serializes with the field names below; see `to_json()`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ReverseDependencyReport {
     /// Packages that depend on the target packages.
     pub dependents: Vec<Dependency>,
     /// Per-package summary statistics.
     pub summaries: Vec<ReverseDependencySummary>,
+    /// Cycles among the traversed "Required By" relations.
+    ///
+    /// Each cycle lists its members in edge order, starting from the alphabetically
+    /// smallest name; `["a", "b"]` means `a` is required by `b`, which is required by `a`.
+    #[serde(default)]
+    pub cycles: Vec<Vec<String>>,
 }
 
 /// Summary statistics for a single package's reverse dependencies.
 ///
 /// Used in reverse dependency analysis to summarize how many packages depend
 /// on a given package, broken down by direct and transitive dependents.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ReverseDependencySummary {
     /// Package name.
     pub package: String,
@@ -311,11 +433,53 @@ pub struct ReverseDependencySummary {
     pub total_dependents: usize,
 }
 
+/// Dry-run outcome of installing and removing a set of packages.
+///
+/// Built by `deps::plan_transaction()` from a forward dependency resolution of the packages
+/// to install and a reverse dependency report of the packages to remove.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TransactionPlan {
+    /// Dependencies that are not installed yet and would be pulled in.
+    pub to_install: Vec<Dependency>,
+    /// Installed dependencies that would be upgraded to satisfy a version requirement.
+    pub to_upgrade: Vec<Dependency>,
+    /// Names of the packages to remove, deduplicated, in request order.
+    pub to_remove: Vec<String>,
+    /// Installed packages depending on a removal target that are not removed themselves.
+    pub blocked_removals: Vec<Dependency>,
+    /// Package names in conflict, sorted and deduplicated.
+    pub conflicts: Vec<String>,
+    /// Packages to install or dependencies that could not be found, sorted and deduplicated.
+    ///
+    /// A plan with missing packages cannot be carried out as is.
+    #[serde(default)]
+    pub missing: Vec<String>,
+}
+
+/// Package upgrade description used for rebuild detection.
+///
+/// Captures the provides list of a package before and after an upgrade so that
+/// library soname changes (e.g., `libfoo.so=1-64` -> `libfoo.so=2-64`) can be detected.
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Upgrade {
+    /// Package name.
+    pub name: String,
+    /// Version installed before the upgrade.
+    pub old_version: String,
+    /// Version installed by the upgrade.
+    pub new_version: String,
+    /// Provides entries of the old version (including soname provides).
+    pub old_provides: Vec<String>,
+    /// Provides entries of the new version (including soname provides).
+    pub new_provides: Vec<String>,
+}
+
 /// Parsed .SRCINFO file data.
 ///
 /// Contains all dependency-related fields extracted from a .SRCINFO file,
 /// which is the machine-readable format generated from PKGBUILD files.
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+/// Its `Display` implementation writes the data back out in `.SRCINFO` format.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SrcinfoData {
     /// Package base name (may differ from pkgname for split packages).
     pub pkgbase: String,
@@ -339,12 +503,259 @@ pub struct SrcinfoData {
     pub provides: Vec<String>,
     /// Packages this package replaces.
     pub replaces: Vec<String>,
+    /// Packages built from this pkgbase, in `pkgname` order.
+    ///
+    /// Split packages produce several entries; `pkgname` is the first entry's name.
+    #[serde(default)]
+    pub packages: Vec<SplitPackage>,
+}
+
+/// A package built from a `.SRCINFO` pkgbase.
+///
+/// Fields hold the effective values for this package: the `pkgname` block's own entries if it
+/// overrides the field, otherwise the entries inherited from the pkgbase block.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SplitPackage {
+    /// Package name (`pkgname`).
+    pub name: String,
+    /// Runtime dependencies.
+    pub depends: Vec<String>,
+    /// Optional dependencies.
+    pub optdepends: Vec<String>,
+    /// Packages this package provides.
+    pub provides: Vec<String>,
+    /// Conflicting packages.
+    pub conflicts: Vec<String>,
+}
+
+/// Parsed PKGBUILD fields.
+///
+/// Built by `deps::parse_pkgbuild()` from the raw PKGBUILD text. Values are expanded from
+/// simple top-level variables (`$pkgver`, `${_commit}`) where possible.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParsedPkgbuild {
+    /// Package names; split packages declare several.
+    pub pkgname: Vec<String>,
+    /// Package version.
+    pub pkgver: String,
+    /// Package release number.
+    pub pkgrel: String,
+    /// Runtime dependencies.
+    pub depends: Vec<String>,
+    /// Build-time dependencies.
+    pub makedepends: Vec<String>,
+    /// Test dependencies.
+    pub checkdepends: Vec<String>,
+    /// Optional dependencies.
+    pub optdepends: Vec<String>,
+    /// Conflicting package names.
+    pub conflicts: Vec<String>,
+    /// Source entries in declaration order.
+    pub source: Vec<String>,
+    /// Checksum arrays keyed by array name (e.g. `sha256sums`), aligned with `source`.
+    pub checksums: BTreeMap<String, Vec<String>>,
+}
+
+/// A scalar PKGBUILD field whose value changed between two versions.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldChange {
+    /// Field name (`pkgname`, `pkgver` or `pkgrel`).
+    pub field: String,
+    /// Value in the old PKGBUILD.
+    pub old: String,
+    /// Value in the new PKGBUILD.
+    pub new: String,
+}
+
+/// Structured difference between two PKGBUILD versions.
+///
+/// Built by `aur::diff_pkgbuild()` to review an AUR update before building it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PkgbuildDiff {
+    /// Changed `pkgname`, `pkgver` and `pkgrel` values.
+    pub changed_fields: Vec<FieldChange>,
+    /// Dependencies present only in the new PKGBUILD.
+    pub added_dependencies: DependencyArrays,
+    /// Dependencies present only in the old PKGBUILD.
+    pub removed_dependencies: DependencyArrays,
+    /// Source entries present only in the new PKGBUILD.
+    pub added_sources: Vec<String>,
+    /// Source entries present only in the old PKGBUILD.
+    pub removed_sources: Vec<String>,
+    /// Whether any shell function (`build()`, `package()`, ...) was added, removed or modified.
+    pub has_script_changes: bool,
+    /// Names of the added, removed or modified functions, sorted.
+    pub changed_functions: Vec<String>,
+}
+
+/// Dependency arrays of a PKGBUILD or .SRCINFO for one architecture scope.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyArrays {
+    /// Runtime dependencies.
+    pub depends: Vec<String>,
+    /// Build-time dependencies.
+    pub makedepends: Vec<String>,
+    /// Test dependencies.
+    pub checkdepends: Vec<String>,
+    /// Optional dependencies.
+    pub optdepends: Vec<String>,
+}
+
+impl DependencyArrays {
+    /// What: Look up an array by its PKGBUILD field name.
+    ///
+    /// Inputs:
+    /// - `field`: Field name without architecture suffix (e.g. `makedepends`).
+    ///
+    /// Output:
+    /// - `Some(&mut Vec<String>)` for the four dependency fields, `None` otherwise.
+    pub(crate) fn field_mut(&mut self, field: &str) -> Option<&mut Vec<String>> {
+        match field {
+            "depends" => Some(&mut self.depends),
+            "makedepends" => Some(&mut self.makedepends),
+            "checkdepends" => Some(&mut self.checkdepends),
+            "optdepends" => Some(&mut self.optdepends),
+            _ => None,
+        }
+    }
+}
+
+/// Dependency arrays split into generic and architecture-specific entries.
+///
+/// Architecture-specific arrays are declared as `<field>_<arch>`, e.g. `depends_x86_64`
+/// or `makedepends_aarch64`, and only apply when building for that architecture.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchDependencies {
+    /// Arrays that apply to every architecture.
+    pub generic: DependencyArrays,
+    /// Architecture-specific arrays keyed by architecture name.
+    pub by_arch: BTreeMap<String, DependencyArrays>,
+}
+
+impl ArchDependencies {
+    /// What: Merge the generic and architecture-specific arrays for one architecture.
+    ///
+    /// Inputs:
+    /// - `arch`: Target architecture (e.g. `x86_64`, `aarch64`).
+    ///
+    /// Output:
+    /// - Returns the generic arrays followed by that architecture's entries.
+    ///
+    /// Details:
+    /// - Entries already present in the generic array are not repeated.
+    /// - Arrays of other architectures are ignored.
+    #[must_use]
+    pub fn for_arch(&self, arch: &str) -> DependencyArrays {
+        let mut merged = self.generic.clone();
+        if let Some(specific) = self.by_arch.get(arch) {
+            for (target, extra) in [
+                (&mut merged.depends, &specific.depends),
+                (&mut merged.makedepends, &specific.makedepends),
+                (&mut merged.checkdepends, &specific.checkdepends),
+                (&mut merged.optdepends, &specific.optdepends),
+            ] {
+                for dep in extra {
+                    if !target.contains(dep) {
+                        target.push(dep.clone());
+                    }
+                }
+            }
+        }
+        merged
+    }
+
+    /// What: Runtime dependencies for one architecture.
+    ///
+    /// Inputs:
+    /// - `arch`: Target architecture (e.g. `x86_64`, `aarch64`).
+    ///
+    /// Output:
+    /// - Returns generic `depends` merged with `depends_<arch>`.
+    ///
+    /// Details:
+    /// - Shorthand for `for_arch(arch).depends`.
+    #[must_use]
+    pub fn deps_for_arch(&self, arch: &str) -> Vec<String> {
+        self.for_arch(arch).depends
+    }
+}
+
+impl std::fmt::Display for SrcinfoData {
+    /// What: Serialize the data in canonical `.SRCINFO` format.
+    ///
+    /// Details:
+    /// - Writes the `pkgbase` block first, then one block per package, as
+    ///   `makepkg --printsrcinfo` does; fields are tab-indented `key = value` lines.
+    /// - Field order follows makepkg: `pkgver`, `pkgrel`, `checkdepends`, `makedepends`,
+    ///   `depends`, `optdepends`, `provides`, `conflicts`, `replaces`.
+    /// - Array fields emit one line per entry; empty fields are omitted.
+    /// - A package block repeats a field only where the package differs from the pkgbase
+    ///   values (an override to nothing is written as `key = `).
+    /// - Without `packages`, a single `pkgname` block is written if `pkgname` is set.
+    /// - An empty `pkgbase` is written as `pkgname`, since `.SRCINFO` always names a base.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pkgbase = if self.pkgbase.is_empty() {
+            &self.pkgname
+        } else {
+            &self.pkgbase
+        };
+        writeln!(f, "pkgbase = {pkgbase}")?;
+
+        for (key, value) in [("pkgver", &self.pkgver), ("pkgrel", &self.pkgrel)] {
+            if !value.is_empty() {
+                writeln!(f, "\t{key} = {value}")?;
+            }
+        }
+        let arrays = [
+            ("checkdepends", &self.checkdepends),
+            ("makedepends", &self.makedepends),
+            ("depends", &self.depends),
+            ("optdepends", &self.optdepends),
+            ("provides", &self.provides),
+            ("conflicts", &self.conflicts),
+            ("replaces", &self.replaces),
+        ];
+        for (key, values) in arrays {
+            for value in values {
+                writeln!(f, "\t{key} = {value}")?;
+            }
+        }
+
+        if self.packages.is_empty() && !self.pkgname.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "pkgname = {}", self.pkgname)?;
+        }
+        for package in &self.packages {
+            writeln!(f)?;
+            writeln!(f, "pkgname = {}", package.name)?;
+            let overrides = [
+                ("depends", &package.depends, &self.depends),
+                ("optdepends", &package.optdepends, &self.optdepends),
+                ("provides", &package.provides, &self.provides),
+                ("conflicts", &package.conflicts, &self.conflicts),
+            ];
+            for (key, values, inherited) in overrides {
+                if values == inherited {
+                    continue;
+                }
+                if values.is_empty() {
+                    writeln!(f, "\t{key} = ")?;
+                }
+                for value in values {
+                    writeln!(f, "\t{key} = {value}")?;
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Result of dependency resolution operation.
 ///
 /// Contains all resolved dependencies along with any conflicts or missing packages
 /// discovered during the resolution process.
+///
+/// Serializes with the field names below; see `to_json()`.
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct DependencyResolution {
     /// Resolved dependencies with status.
@@ -353,6 +764,278 @@ pub struct DependencyResolution {
     pub conflicts: Vec<String>,
     /// Packages that are missing.
     pub missing: Vec<String>,
+    /// Dependency cycles found during transitive resolution.
+    ///
+    /// Each cycle lists its members in edge order, starting from the alphabetically
+    /// smallest name; `["a", "b"]` means `a -> b -> a`.
+    #[serde(default)]
+    pub cycles: Vec<Vec<String>>,
+    /// AUR packages resolved from a cached PKGBUILD that is older than the AUR's latest commit.
+    ///
+    /// Filled when `ResolverConfig::pkgbuild_hash` and `ResolverConfig::aur_commit` report
+    /// different commits; the caller should refetch these PKGBUILDs and resolve again.
+    #[serde(default)]
+    pub stale_pkgbuilds: Vec<String>,
+}
+
+/// Internal inconsistency detected in a `DependencyResolution`.
+///
+/// Returned by `DependencyResolution::validate()` to flag contradictions in a
+/// resolution produced by the resolver or modified by caller pre-processing.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Inconsistency {
+    /// Package is listed in `missing` but has an installed status in `dependencies`.
+    MissingButInstalled {
+        /// Package name.
+        package: String,
+    },
+    /// Package is listed in `conflicts` but does not appear in `dependencies`.
+    ConflictNotInDependencies {
+        /// Package name.
+        package: String,
+    },
+    /// A `required_by` entry names a package that is neither a dependency nor a root.
+    UnknownRequiredBy {
+        /// Dependency carrying the `required_by` entry.
+        package: String,
+        /// Unknown package named in `required_by`.
+        required_by: String,
+    },
+    /// Package appears more than once in `dependencies`.
+    DuplicateDependency {
+        /// Package name.
+        package: String,
+    },
+}
+
+impl std::fmt::Display for Inconsistency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingButInstalled { package } => {
+                write!(f, "{package} is listed as missing but marked installed")
+            }
+            Self::ConflictNotInDependencies { package } => {
+                write!(f, "conflict {package} does not appear in dependencies")
+            }
+            Self::UnknownRequiredBy {
+                package,
+                required_by,
+            } => write!(f, "{package} is required by unknown package {required_by}"),
+            Self::DuplicateDependency { package } => {
+                write!(f, "{package} appears more than once in dependencies")
+            }
+        }
+    }
+}
+
+impl DependencyResolution {
+    /// What: Check the resolution for internal contradictions.
+    ///
+    /// Inputs:
+    /// - `self`: The resolution to validate.
+    ///
+    /// Output:
+    /// - Returns every inconsistency found; an empty vector means the resolution is consistent.
+    ///
+    /// Details:
+    /// - Flags packages listed in `missing` that have an installed status.
+    /// - Flags `conflicts` entries absent from `dependencies`.
+    /// - Flags duplicate dependency names.
+    /// - Does not check `required_by`, so `UnknownRequiredBy` is never reported here: those
+    ///   entries usually name the root packages passed to the resolver, which are not part of
+    ///   `dependencies`. Use `validate_with_roots()` to run that check as well.
+    #[must_use]
+    pub fn validate(&self) -> Vec<Inconsistency> {
+        let mut issues = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+
+        for dep in &self.dependencies {
+            if !seen.insert(dep.name.as_str()) {
+                issues.push(Inconsistency::DuplicateDependency {
+                    package: dep.name.clone(),
+                });
+            }
+        }
+
+        for name in &self.missing {
+            if self
+                .dependencies
+                .iter()
+                .any(|d| d.name == *name && d.status.is_installed())
+            {
+                issues.push(Inconsistency::MissingButInstalled {
+                    package: name.clone(),
+                });
+            }
+        }
+
+        for name in &self.conflicts {
+            if !seen.contains(name.as_str()) {
+                issues.push(Inconsistency::ConflictNotInDependencies {
+                    package: name.clone(),
+                });
+            }
+        }
+
+        issues
+    }
+
+    /// What: Check the resolution for contradictions, including `required_by` references.
+    ///
+    /// Inputs:
+    /// - `roots`: Names of the packages that were passed to the resolver.
+    ///
+    /// Output:
+    /// - Returns all inconsistencies reported by `validate()`, plus unknown `required_by` entries.
+    ///
+    /// Details:
+    /// - A `required_by` entry is valid when it names a dependency in the resolution or a root.
+    #[must_use]
+    pub fn validate_with_roots(&self, roots: &[&str]) -> Vec<Inconsistency> {
+        let mut issues = self.validate();
+        for dep in &self.dependencies {
+            for parent in &dep.required_by {
+                let known = roots.contains(&parent.as_str())
+                    || self.dependencies.iter().any(|d| d.name == *parent);
+                if !known {
+                    issues.push(Inconsistency::UnknownRequiredBy {
+                        package: dep.name.clone(),
+                        required_by: parent.clone(),
+                    });
+                }
+            }
+        }
+        issues
+    }
+
+    /// What: Serialize the resolution to JSON.
+    ///
+    /// Inputs:
+    /// - `self`: The resolution to export.
+    ///
+    /// Output:
+    /// - Compact JSON object with `dependencies`, `conflicts`, `missing` and `cycles`.
+    ///
+    /// Details:
+    /// - Field names match the Rust field names; dependency statuses carry a `type` tag.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ArchToolkitError::Json` if serialization fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use arch_toolkit::DependencyResolution;
+    ///
+    /// let json = DependencyResolution::default().to_json()?;
+    /// let parsed = DependencyResolution::from_json(&json)?;
+    /// assert!(parsed.dependencies.is_empty());
+    /// # Ok::<(), arch_toolkit::error::ArchToolkitError>(())
+    /// ```
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// What: Parse a resolution previously exported with `to_json()`.
+    ///
+    /// Inputs:
+    /// - `s`: JSON text.
+    ///
+    /// Output:
+    /// - The decoded resolution.
+    ///
+    /// Details:
+    /// - `cycles`, `kind`, `explicit` and `install_reason` may be omitted and take their
+    ///   defaults.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ArchToolkitError::Json` if the text is not a valid resolution.
+    pub fn from_json(s: &str) -> Result<Self> {
+        Ok(serde_json::from_str(s)?)
+    }
+}
+
+impl ReverseDependencyReport {
+    /// What: Serialize the report to JSON.
+    ///
+    /// Inputs:
+    /// - `self`: The report to export.
+    ///
+    /// Output:
+    /// - Compact JSON object with `dependents`, `summaries` and `cycles`.
+    ///
+    /// Details:
+    /// - Field names match the Rust field names; dependent statuses carry a `type` tag.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ArchToolkitError::Json` if serialization fails.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// What: Parse a report previously exported with `to_json()`.
+    ///
+    /// Inputs:
+    /// - `s`: JSON text.
+    ///
+    /// Output:
+    /// - The decoded report.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ArchToolkitError::Json` if the text is not a valid report.
+    pub fn from_json(s: &str) -> Result<Self> {
+        Ok(serde_json::from_str(s)?)
+    }
+}
+
+/// Default cap on unique dependencies collected by transitive resolution.
+pub const DEFAULT_MAX_PACKAGES: usize = 1000;
+
+/// Critical packages treated as system packages by `SourceConfig::default()`.
+const DEFAULT_PROTECTED_PACKAGES: [&str; 13] = [
+    "glibc",
+    "linux",
+    "systemd",
+    "pacman",
+    "bash",
+    "coreutils",
+    "gcc",
+    "binutils",
+    "filesystem",
+    "util-linux",
+    "shadow",
+    "sed",
+    "grep",
+];
+
+/// Classification of critical system packages.
+///
+/// Consulted by `deps::is_system_package_with()` to set `Dependency::is_system`, e.g. to keep
+/// `multilib-devel` members from being removed. The default protects a curated list of
+/// critical packages (`glibc`, `linux`, `pacman`, ...) and no groups: on current Arch, `base`
+/// and `base-devel` are metapackages rather than groups.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceConfig {
+    /// Package groups whose members are system packages.
+    pub protected_groups: Vec<String>,
+    /// Package names that are system packages regardless of their groups.
+    pub protected_packages: Vec<String>,
+}
+
+impl Default for SourceConfig {
+    fn default() -> Self {
+        Self {
+            protected_groups: Vec::new(),
+            protected_packages: DEFAULT_PROTECTED_PACKAGES
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
 }
 
 /// Configuration for dependency resolution.
@@ -361,7 +1044,8 @@ pub struct DependencyResolution {
 /// types of dependencies to include and how deep to traverse the dependency tree.
 ///
 /// Note: This struct does not implement `Clone` or `Debug` because it contains
-/// a function pointer (`pkgbuild_cache`) that cannot be cloned or debugged.
+/// function pointers (`pkgbuild_cache`, `pkgbuild_hash`, `aur_commit`) that cannot be
+/// cloned or debugged.
 #[allow(clippy::struct_excessive_bools, clippy::type_complexity)]
 pub struct ResolverConfig {
     /// Whether to include optional dependencies.
@@ -372,10 +1056,37 @@ pub struct ResolverConfig {
     pub include_checkdepends: bool,
     /// Maximum depth for transitive dependency resolution (0 = direct only).
     pub max_depth: usize,
+    /// Maximum number of unique dependencies to collect before transitive resolution
+    /// stops (0 = unlimited).
+    pub max_packages: usize,
     /// Custom callback for fetching PKGBUILD from cache (optional).
     pub pkgbuild_cache: Option<Box<dyn Fn(&str) -> Option<String> + Send + Sync>>,
+    /// Callback returning the `aur.git` commit id the cached PKGBUILD was taken from
+    /// (optional).
+    ///
+    /// Together with `aur_commit`, a cached PKGBUILD whose commit differs from the AUR's
+    /// latest commit is listed in `DependencyResolution::stale_pkgbuilds`; it is still used
+    /// for resolution until the caller refetches it.
+    pub pkgbuild_hash: Option<Box<dyn Fn(&str) -> Option<String> + Send + Sync>>,
+    /// Callback returning the latest `aur.git` commit id of a package (optional), e.g. as
+    /// looked up with `Aur::pkgbuild_commit()`.
+    pub aur_commit: Option<Box<dyn Fn(&str) -> Option<String> + Send + Sync>>,
     /// Whether to check AUR for missing dependencies.
     pub check_aur: bool,
+    /// Resolve from the local database and `pkgbuild_cache` only.
+    ///
+    /// Official packages are read with `pacman -Qi` instead of `pacman -Si`, and `paru`/`yay`
+    /// are never invoked; AUR packages resolve solely through `pkgbuild_cache`. Statuses are
+    /// less precise offline: dependencies of official packages that are not installed are not
+    /// found, root conflicts come only from installed or cached metadata, and `ToUpgrade`
+    /// versions reflect the last synced database.
+    pub offline: bool,
+    /// Packages and groups flagged as `is_system` in the resolution.
+    ///
+    /// Group membership is only queried when `protected_groups` is non-empty; with
+    /// `SourceConfig::default()` just the curated package list (and `core` packages) are
+    /// flagged, without extra pacman calls.
+    pub source_config: SourceConfig,
 }
 
 #[allow(clippy::derivable_impls)]
@@ -386,12 +1097,59 @@ impl Default for ResolverConfig {
             include_makedepends: false,
             include_checkdepends: false,
             max_depth: 0, // Direct dependencies only
+            max_packages: DEFAULT_MAX_PACKAGES,
             pkgbuild_cache: None,
+            pkgbuild_hash: None,
+            aur_commit: None,
             check_aur: false,
+            offline: false,
+            source_config: SourceConfig::default(),
         }
     }
 }
 
+/// Per-call overrides for dependency resolution settings.
+///
+/// Passed to `DependencyResolver::resolve_with()` to adjust include flags and depth for a
+/// single call. `None` fields fall back to the resolver's stored `ResolverConfig`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ResolveOverrides {
+    /// Override for `ResolverConfig::include_optdepends`.
+    pub include_optdepends: Option<bool>,
+    /// Override for `ResolverConfig::include_makedepends`.
+    pub include_makedepends: Option<bool>,
+    /// Override for `ResolverConfig::include_checkdepends`.
+    pub include_checkdepends: Option<bool>,
+    /// Override for `ResolverConfig::max_depth`.
+    pub max_depth: Option<usize>,
+}
+
+/// Options for reverse dependency analysis.
+///
+/// Passed to `ReverseDependencyAnalyzer::analyze_with()`. The default walks the complete
+/// reverse dependency graph and ignores optional dependents, like `analyze()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReverseOptions {
+    /// Maximum distance from a removal target (1 = direct dependents only, `None` = unlimited).
+    pub max_depth: Option<usize>,
+    /// Whether to report packages that only optionally depend on a target ("Optional For").
+    pub include_optional: bool,
+}
+
+/// Progress of a reverse dependency analysis.
+///
+/// Passed to the callback of `ReverseDependencyAnalyzer::analyze_with_progress()` while the
+/// breadth-first traversal advances.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReverseProgress {
+    /// Distinct dependents found so far; the final value is the number of report dependents.
+    pub visited: usize,
+    /// Packages waiting in the traversal queue of the current removal target.
+    pub queued: usize,
+    /// Package whose dependents were just expanded (empty in the final report).
+    pub current: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -505,6 +1263,20 @@ mod tests {
         assert_eq!(local.to_string(), "Local");
     }
 
+    #[test]
+    fn install_reason_from_pacman() {
+        assert_eq!(
+            InstallReason::from_pacman("Explicitly installed"),
+            InstallReason::Explicit
+        );
+        assert_eq!(
+            InstallReason::from_pacman("Installed as a dependency for another package"),
+            InstallReason::Dependency
+        );
+        assert_eq!(InstallReason::from_pacman(""), InstallReason::Unknown);
+        assert_eq!(InstallReason::default(), InstallReason::Unknown);
+    }
+
     #[test]
     fn package_source_display() {
         let official = PackageSource::Official {
@@ -577,6 +1349,10 @@ mod tests {
             depends_on: vec!["linux-api-headers".to_string()],
             is_core: true,
             is_system: true,
+            kind: DependencyKind::Runtime,
+            explicit: false,
+            install_reason: InstallReason::Unknown,
+            satisfied_by: None,
         };
 
         let json = serde_json::to_string(&dep).expect("serialization should succeed");
@@ -592,6 +1368,95 @@ mod tests {
         assert_eq!(dep.is_system, deserialized.is_system);
     }
 
+    #[test]
+    fn dependency_status_json_is_type_tagged() {
+        let status = DependencyStatus::ToUpgrade {
+            current: "1.0".to_string(),
+            required: "2.0".to_string(),
+        };
+        let value = serde_json::to_value(&status).expect("serialization should succeed");
+        assert_eq!(
+            value,
+            serde_json::json!({"type": "ToUpgrade", "current": "1.0", "required": "2.0"})
+        );
+        let value = serde_json::to_value(DependencyStatus::Missing).expect("serialization");
+        assert_eq!(value, serde_json::json!({"type": "Missing"}));
+    }
+
+    #[test]
+    fn json_roundtrip_resolution_with_every_status() {
+        let statuses = [
+            DependencyStatus::Installed {
+                version: "2.41".to_string(),
+            },
+            DependencyStatus::ToInstall,
+            DependencyStatus::ToUpgrade {
+                current: "1.0".to_string(),
+                required: "2.0".to_string(),
+            },
+            DependencyStatus::Conflict {
+                reason: "conflicts with foo".to_string(),
+            },
+            DependencyStatus::Missing,
+        ];
+        let resolution = DependencyResolution {
+            dependencies: statuses
+                .iter()
+                .enumerate()
+                .map(|(i, status)| Dependency {
+                    name: format!("dep{i}"),
+                    version_req: String::new(),
+                    status: status.clone(),
+                    source: DependencySource::Aur,
+                    required_by: vec!["root".to_string()],
+                    depends_on: Vec::new(),
+                    is_core: false,
+                    is_system: false,
+                    kind: DependencyKind::Make,
+                    explicit: false,
+                    install_reason: InstallReason::Unknown,
+                    satisfied_by: None,
+                })
+                .collect(),
+            conflicts: vec!["dep3".to_string()],
+            missing: vec!["dep4".to_string()],
+            cycles: vec![vec!["a".to_string(), "b".to_string()]],
+            stale_pkgbuilds: vec!["att-tool".to_string()],
+        };
+
+        let json = resolution.to_json().expect("export succeeds");
+        let parsed = DependencyResolution::from_json(&json).expect("import succeeds");
+
+        let got: Vec<&DependencyStatus> = parsed.dependencies.iter().map(|d| &d.status).collect();
+        assert_eq!(got, statuses.iter().collect::<Vec<_>>());
+        assert_eq!(parsed.conflicts, resolution.conflicts);
+        assert_eq!(parsed.missing, resolution.missing);
+        assert_eq!(parsed.cycles, resolution.cycles);
+        assert_eq!(parsed.stale_pkgbuilds, resolution.stale_pkgbuilds);
+        assert_eq!(parsed.dependencies[0].kind, DependencyKind::Make);
+        assert_eq!(parsed.to_json().expect("re-export succeeds"), json);
+    }
+
+    #[test]
+    fn json_roundtrip_reverse_report() {
+        let report = ReverseDependencyReport {
+            dependents: Vec::new(),
+            summaries: vec![ReverseDependencySummary {
+                package: "qt5-base".to_string(),
+                direct_dependents: 2,
+                transitive_dependents: 1,
+                total_dependents: 3,
+            }],
+            cycles: vec![vec!["a".to_string(), "b".to_string()]],
+        };
+
+        let json = report.to_json().expect("export succeeds");
+        let parsed = ReverseDependencyReport::from_json(&json).expect("import succeeds");
+        assert_eq!(parsed.summaries, report.summaries);
+        assert_eq!(parsed.cycles, report.cycles);
+        assert!(ReverseDependencyReport::from_json("{").is_err());
+    }
+
     #[test]
     fn serde_roundtrip_srcinfo_data() {
         let srcinfo = SrcinfoData {
@@ -606,6 +1471,7 @@ mod tests {
             conflicts: vec!["conflicting-pkg".to_string()],
             provides: vec!["provided-pkg".to_string()],
             replaces: vec!["replaced-pkg".to_string()],
+            packages: Vec::new(),
         };
 
         let json = serde_json::to_string(&srcinfo).expect("serialization should succeed");
@@ -640,4 +1506,84 @@ mod tests {
             serde_json::from_str(&json).expect("deserialization should succeed");
         assert_eq!(pkg_ref, deserialized);
     }
+
+    fn dep_with_status(name: &str, status: DependencyStatus, required_by: &[&str]) -> Dependency {
+        Dependency {
+            name: name.to_string(),
+            version_req: String::new(),
+            status,
+            source: DependencySource::Aur,
+            required_by: required_by.iter().map(ToString::to_string).collect(),
+            depends_on: Vec::new(),
+            is_core: false,
+            is_system: false,
+            kind: DependencyKind::Runtime,
+            explicit: false,
+            install_reason: InstallReason::Unknown,
+            satisfied_by: None,
+        }
+    }
+
+    #[test]
+    fn dependency_resolution_validate_consistent() {
+        let resolution = DependencyResolution {
+            dependencies: vec![
+                dep_with_status("glibc", DependencyStatus::ToInstall, &["app"]),
+                dep_with_status(
+                    "vi",
+                    DependencyStatus::Conflict {
+                        reason: "Conflicts with app".to_string(),
+                    },
+                    &["app"],
+                ),
+            ],
+            conflicts: vec!["vi".to_string()],
+            missing: vec!["libfoo".to_string()],
+            cycles: Vec::new(),
+            stale_pkgbuilds: Vec::new(),
+        };
+        assert!(resolution.validate().is_empty());
+        assert!(resolution.validate_with_roots(&["app"]).is_empty());
+    }
+
+    #[test]
+    fn dependency_resolution_validate_reports_each_inconsistency() {
+        let resolution = DependencyResolution {
+            dependencies: vec![
+                dep_with_status(
+                    "glibc",
+                    DependencyStatus::Installed {
+                        version: "2.39".to_string(),
+                    },
+                    &["app"],
+                ),
+                dep_with_status("python", DependencyStatus::ToInstall, &["ghost"]),
+                dep_with_status("python", DependencyStatus::ToInstall, &["app"]),
+            ],
+            conflicts: vec!["vi".to_string()],
+            missing: vec!["glibc".to_string()],
+            cycles: Vec::new(),
+            stale_pkgbuilds: Vec::new(),
+        };
+
+        let issues = resolution.validate();
+        assert_eq!(issues.len(), 3);
+        assert!(issues.contains(&Inconsistency::MissingButInstalled {
+            package: "glibc".to_string()
+        }));
+        assert!(issues.contains(&Inconsistency::ConflictNotInDependencies {
+            package: "vi".to_string()
+        }));
+        assert!(issues.contains(&Inconsistency::DuplicateDependency {
+            package: "python".to_string()
+        }));
+
+        let issues = resolution.validate_with_roots(&["app"]);
+        assert_eq!(issues.len(), 4);
+        assert!(issues.contains(&Inconsistency::UnknownRequiredBy {
+            package: "python".to_string(),
+            required_by: "ghost".to_string()
+        }));
+        assert!(issues.iter().all(|issue| !issue.to_string().is_empty()));
+    }
 }
@@ -11,14 +11,25 @@ use std::time::{Duration, Instant};
 /// Details:
 /// - Provides detailed health information for connection status UIs
 /// - Includes latency measurements for performance monitoring
+/// - Includes the rate limiter's backoff state, useful to decide whether to issue more requests
 #[derive(Debug, Clone)]
 pub struct HealthStatus {
-    /// Whether the AUR RPC API is reachable.
-    pub aur_api: ServiceStatus,
-    /// Measured latency to the AUR API (if reachable).
+    /// Status of the AUR RPC API (search, info).
+    pub rpc: ServiceStatus,
+    /// Status of the AUR web frontend (package pages, comments).
+    pub web: ServiceStatus,
+    /// Status of the AUR cgit plain endpoint (PKGBUILD, `.SRCINFO`).
+    pub cgit: ServiceStatus,
+    /// Measured latency of the AUR RPC probe.
     pub latency: Option<Duration>,
     /// Timestamp when health check was performed.
     pub checked_at: Instant,
+    /// Current archlinux.org rate-limiter backoff delay in milliseconds.
+    pub current_backoff_ms: u64,
+    /// Consecutive failures/rate limits recorded by the archlinux.org rate limiter.
+    pub consecutive_failures: u32,
+    /// `Retry-After` seconds sent with a 429 answer to a health probe, if any.
+    pub retry_after: Option<u64>,
 }
 
 /// What: Status of a single service endpoint.
@@ -30,6 +41,7 @@ pub struct HealthStatus {
 /// Details:
 /// - Healthy: Service is responding normally
 /// - Degraded: Service is slow but functional
+/// - `RateLimited`: Service is up but throttling us (HTTP 429)
 /// - Unreachable: Service returned an error
 /// - Timeout: Request timed out
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -38,6 +50,8 @@ pub enum ServiceStatus {
     Healthy,
     /// Service is degraded (slow response, partial functionality).
     Degraded,
+    /// Service is up but rate limiting our requests.
+    RateLimited,
     /// Service is unreachable.
     Unreachable,
     /// Health check timed out.
@@ -50,14 +64,15 @@ impl ServiceStatus {
     /// Inputs: None
     ///
     /// Output:
-    /// - `true` if the service is operational (Healthy or Degraded), `false` otherwise
+    /// - `true` if the service is operational (Healthy, Degraded, or `RateLimited`), `false` otherwise
     ///
     /// Details:
-    /// - Returns `true` for `Healthy` and `Degraded` statuses
+    /// - Returns `true` for `Healthy`, `Degraded`, and `RateLimited` statuses
     /// - Returns `false` for `Unreachable` and `Timeout` statuses
+    /// - A rate-limited service is up; check `HealthStatus::retry_after` before sending more requests
     #[must_use]
     pub const fn is_operational(&self) -> bool {
-        matches!(self, Self::Healthy | Self::Degraded)
+        matches!(self, Self::Healthy | Self::Degraded | Self::RateLimited)
     }
 }
 
@@ -70,10 +85,10 @@ impl HealthStatus {
     /// - `true` if all services are operational, `false` otherwise
     ///
     /// Details:
-    /// - Delegates to `ServiceStatus::is_operational()` for the AUR API
+    /// - Requires `ServiceStatus::is_operational()` for the RPC API, web frontend, and cgit
     #[must_use]
     pub const fn is_healthy(&self) -> bool {
-        self.aur_api.is_operational()
+        self.rpc.is_operational() && self.web.is_operational() && self.cgit.is_operational()
     }
 }
 
@@ -85,6 +100,7 @@ mod tests {
     fn test_service_status_is_operational() {
         assert!(ServiceStatus::Healthy.is_operational());
         assert!(ServiceStatus::Degraded.is_operational());
+        assert!(ServiceStatus::RateLimited.is_operational());
         assert!(!ServiceStatus::Unreachable.is_operational());
         assert!(!ServiceStatus::Timeout.is_operational());
     }
@@ -92,31 +108,57 @@ mod tests {
     #[test]
     fn test_health_status_is_healthy() {
         let healthy = HealthStatus {
-            aur_api: ServiceStatus::Healthy,
+            rpc: ServiceStatus::Healthy,
+            web: ServiceStatus::Healthy,
+            cgit: ServiceStatus::Healthy,
             latency: Some(Duration::from_millis(100)),
             checked_at: Instant::now(),
+            current_backoff_ms: 500,
+            consecutive_failures: 0,
+            retry_after: None,
         };
         assert!(healthy.is_healthy());
 
         let degraded = HealthStatus {
-            aur_api: ServiceStatus::Degraded,
+            rpc: ServiceStatus::Degraded,
+            web: ServiceStatus::Healthy,
+            cgit: ServiceStatus::Healthy,
             latency: Some(Duration::from_secs(3)),
             checked_at: Instant::now(),
+            current_backoff_ms: 500,
+            consecutive_failures: 0,
+            retry_after: None,
         };
         assert!(degraded.is_healthy()); // Degraded is still operational
 
         let unreachable = HealthStatus {
-            aur_api: ServiceStatus::Unreachable,
+            rpc: ServiceStatus::Unreachable,
+            web: ServiceStatus::Healthy,
+            cgit: ServiceStatus::Healthy,
             latency: None,
             checked_at: Instant::now(),
+            current_backoff_ms: 500,
+            consecutive_failures: 0,
+            retry_after: None,
         };
         assert!(!unreachable.is_healthy());
 
         let timeout = HealthStatus {
-            aur_api: ServiceStatus::Timeout,
+            rpc: ServiceStatus::Timeout,
+            web: ServiceStatus::Healthy,
+            cgit: ServiceStatus::Healthy,
             latency: None,
             checked_at: Instant::now(),
+            current_backoff_ms: 500,
+            consecutive_failures: 0,
+            retry_after: None,
         };
         assert!(!timeout.is_healthy());
+
+        let cgit_down = HealthStatus {
+            cgit: ServiceStatus::Unreachable,
+            ..healthy
+        };
+        assert!(!cgit_down.is_healthy());
     }
 }
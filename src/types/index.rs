@@ -1,6 +1,7 @@
 //! Index-related data types for official repository package operations.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::time::{Duration, SystemTime};
 
 use serde::{Deserialize, Serialize};
 
@@ -32,6 +33,15 @@ pub struct OfficialPackage {
     /// Package description.
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub description: String,
+    /// Packager identity (e.g., `"Jane Doe <jane@archlinux.org>"`).
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub packager: String,
+    /// Compressed package (download) size in bytes; 0 when unknown.
+    #[serde(default)]
+    pub download_size: u64,
+    /// Installed size in bytes; 0 when unknown.
+    #[serde(default)]
+    pub installed_size: u64,
 }
 
 /// What: Represent the full collection of official packages maintained in memory.
@@ -46,10 +56,14 @@ pub struct OfficialPackage {
 /// - Serializable via Serde to allow saving and restoring across sessions.
 /// - The `name_to_idx` field is derived from `pkgs` and skipped during serialization.
 /// - Provides O(1) lookup via `find_package_by_name()` when `name_to_idx` is populated.
+/// - `fetched_at` records when the package data was fetched, for freshness checks.
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct OfficialIndex {
     /// All known official packages in the index.
     pub pkgs: Vec<OfficialPackage>,
+    /// Time the package data was fetched from pacman or the API; `None` if unknown.
+    #[serde(default)]
+    pub fetched_at: Option<SystemTime>,
     /// Index mapping lowercase package names to their position in `pkgs` for O(1) lookups.
     /// Skipped during serialization; rebuilt after deserialization via `rebuild_name_index()`.
     #[serde(skip)]
@@ -57,6 +71,60 @@ pub struct OfficialIndex {
 }
 
 impl OfficialIndex {
+    /// What: Build an index from a package list.
+    ///
+    /// Inputs:
+    /// - `pkgs`: Packages of the index.
+    ///
+    /// Output:
+    /// - `OfficialIndex` with `name_to_idx` populated and an unknown `fetched_at`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use arch_toolkit::types::index::OfficialIndex;
+    ///
+    /// let index = OfficialIndex::new(Vec::new());
+    /// assert!(index.pkgs.is_empty());
+    /// ```
+    #[must_use]
+    pub fn new(pkgs: Vec<OfficialPackage>) -> Self {
+        let mut index = Self {
+            pkgs,
+            ..Self::default()
+        };
+        index.rebuild_name_index();
+        index
+    }
+
+    /// What: Time elapsed since the index was fetched.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - `Some(Duration)` since `fetched_at`, or `None` if the fetch time is unknown.
+    ///
+    /// Details:
+    /// - A `fetched_at` in the future (clock changes) counts as zero age.
+    /// - Useful for displays such as "index from N hours ago".
+    #[must_use]
+    pub fn age(&self) -> Option<Duration> {
+        self.fetched_at
+            .map(|fetched| fetched.elapsed().unwrap_or(Duration::ZERO))
+    }
+
+    /// What: Check whether the index is younger than a maximum age.
+    ///
+    /// Inputs:
+    /// - `max_age`: Maximum acceptable age.
+    ///
+    /// Output:
+    /// - `true` if the fetch time is known and no older than `max_age`.
+    #[must_use]
+    pub fn is_fresh(&self, max_age: Duration) -> bool {
+        self.age().is_some_and(|age| age <= max_age)
+    }
+
     /// What: Rebuild the `name_to_idx` `HashMap` from the current `pkgs` Vec.
     ///
     /// Inputs:
@@ -112,15 +180,105 @@ impl OfficialIndex {
 /// Details:
 /// - Used to return search results with relevance scores for sorting.
 /// - `fuzzy_score` is `None` for exact or substring matches.
-/// - `fuzzy_score` is `Some(i64)` when fuzzy matching is enabled, with higher scores indicating better matches.
+/// - `fuzzy_score` is `Some(0..=100)` when fuzzy matching is enabled, with higher scores indicating
+///   better matches; `100` is as good as the query matching itself, so scores are comparable
+///   across queries.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct IndexQueryResult {
     /// The matched package.
     pub package: OfficialPackage,
-    /// Fuzzy matching score, if fuzzy matching was used.
+    /// Normalized fuzzy matching score (`0..=100`), if fuzzy matching was used.
     pub fuzzy_score: Option<i64>,
 }
 
+impl IndexQueryResult {
+    /// What: Repository of the matched package.
+    ///
+    /// Output:
+    /// - Repository name (e.g., `core`, `extra`, `multilib`); empty if unknown.
+    #[must_use]
+    pub fn repo(&self) -> &str {
+        &self.package.repo
+    }
+
+    /// What: Description of the matched package.
+    ///
+    /// Output:
+    /// - Package description; empty for pacman-fetched indexes unless `fill_api_metadata()`
+    ///   was applied.
+    #[must_use]
+    pub fn description(&self) -> &str {
+        &self.package.description
+    }
+}
+
+/// What: Options for ranked fuzzy searches of the official index.
+///
+/// Inputs:
+/// - Passed to `search_official_with()`.
+///
+/// Output:
+/// - Controls which matches are kept and how many are returned.
+///
+/// Details:
+/// - `threshold` applies to the normalized `fuzzy_score` (`0..=100`); matches scoring below it
+///   are dropped. The default `0` keeps every match.
+/// - `limit` caps the number of results after sorting; `0` means no limit.
+/// - `case_sensitive` makes both fuzzy and substring matching respect case.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FuzzyOptions {
+    /// Minimum normalized fuzzy score (`0..=100`) a match must reach.
+    pub threshold: i64,
+    /// Maximum number of results; `0` returns all matches.
+    pub limit: usize,
+    /// Match case exactly instead of ignoring it.
+    pub case_sensitive: bool,
+}
+
+/// What: Where a unified search result comes from.
+///
+/// Inputs: None (enum variant)
+///
+/// Output: Enum identifying the package source
+///
+/// Details:
+/// - Produced by `ArchClient::search_all()` and `index::search_all_with()`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResultSource {
+    /// Official repository package.
+    Official {
+        /// Repository name (e.g., `core`, `extra`).
+        repo: String,
+    },
+    /// AUR package.
+    Aur,
+}
+
+/// What: Search result merged from the official index and the AUR.
+///
+/// Inputs:
+/// - Produced by `ArchClient::search_all()` and `index::search_all_with()`.
+///
+/// Output:
+/// - One entry per package name, marked with its source.
+///
+/// Details:
+/// - When a name exists in both sources, the official entry is kept and `in_aur` is `true`.
+/// - `in_aur` is always `true` for AUR entries.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnifiedResult {
+    /// Package name.
+    pub name: String,
+    /// Package version.
+    pub version: String,
+    /// Package description.
+    pub description: String,
+    /// Source the entry was taken from.
+    pub source: ResultSource,
+    /// Whether the AUR has a package with this name.
+    pub in_aur: bool,
+}
+
 /// What: Filter mode for querying explicitly installed packages.
 ///
 /// Inputs:
@@ -132,12 +290,196 @@ pub struct IndexQueryResult {
 /// Details:
 /// - `LeafOnly`: Uses `pacman -Qetq` (explicitly installed AND not required by other packages).
 /// - `AllExplicit`: Uses `pacman -Qeq` (all explicitly installed packages, including dependencies).
+/// - `ForeignExplicit`: Uses `pacman -Qemq` (explicitly installed packages not found in any sync
+///   database, typically AUR packages).
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum InstalledPackagesMode {
     /// Query only leaf packages (explicitly installed and not required).
     LeafOnly,
     /// Query all explicitly installed packages.
     AllExplicit,
+    /// Query explicitly installed foreign packages (not in any sync database).
+    ForeignExplicit,
+}
+
+/// What: Snapshot of the sonames provided by each installed package.
+///
+/// Inputs:
+/// - Captured by `snapshot_sonames()` from the local pacman database.
+///
+/// Output:
+/// - Maps package names to the soname provides (e.g., `libfoo.so=1-64`) they ship.
+///
+/// Details:
+/// - Serializable via Serde so a snapshot taken before an upgrade can be persisted and
+///   diffed against one taken afterwards.
+/// - Packages providing no sonames are omitted.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SonameSnapshot {
+    /// Soname provides per installed package, sorted for stable serialization.
+    pub packages: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl SonameSnapshot {
+    /// What: Compare this snapshot against a newer one.
+    ///
+    /// Inputs:
+    /// - `newer`: Snapshot taken after this one (e.g., after a system upgrade).
+    ///
+    /// Output:
+    /// - One `SonameChange` per package whose provided sonames differ, sorted by package name.
+    ///
+    /// Details:
+    /// - Packages only present in one snapshot report all their sonames as removed or added.
+    #[must_use]
+    pub fn diff(&self, newer: &Self) -> Vec<SonameChange> {
+        let empty = BTreeSet::new();
+        let names: BTreeSet<&String> = self.packages.keys().chain(newer.packages.keys()).collect();
+
+        names
+            .into_iter()
+            .filter_map(|name| {
+                let old = self.packages.get(name).unwrap_or(&empty);
+                let new = newer.packages.get(name).unwrap_or(&empty);
+                let removed: Vec<String> = old.difference(new).cloned().collect();
+                let added: Vec<String> = new.difference(old).cloned().collect();
+                if removed.is_empty() && added.is_empty() {
+                    None
+                } else {
+                    Some(SonameChange {
+                        package: name.clone(),
+                        removed,
+                        added,
+                    })
+                }
+            })
+            .collect()
+    }
+}
+
+/// What: Change in the sonames provided by a single package between two snapshots.
+///
+/// Inputs:
+/// - Produced by `SonameSnapshot::diff()`.
+///
+/// Output:
+/// - Lists sonames no longer provided and sonames newly provided.
+///
+/// Details:
+/// - Packages linked against a `removed` soname need to be rebuilt.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SonameChange {
+    /// Package whose soname provides changed.
+    pub package: String,
+    /// Sonames provided in the older snapshot but not in the newer one.
+    pub removed: Vec<String>,
+    /// Sonames provided in the newer snapshot but not in the older one.
+    pub added: Vec<String>,
+}
+
+/// What: A pacman mirror entry from a mirrorlist.
+///
+/// Inputs:
+/// - Parsed from `Server = ...` lines via `parse_mirrorlist()`.
+///
+/// Output:
+/// - Holds the server URL template.
+///
+/// Details:
+/// - `url` keeps the `$repo` and `$arch` placeholders, as written in `/etc/pacman.d/mirrorlist`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Mirror {
+    /// Server URL template (e.g., `https://mirror.example/$repo/os/$arch`).
+    pub url: String,
+}
+
+/// What: Result of probing a single mirror.
+///
+/// Inputs:
+/// - Produced by `probe_mirrors()`.
+///
+/// Output:
+/// - Reachability, latency, and database modification time of the mirror.
+///
+/// Details:
+/// - `latency` is `None` when the mirror could not be reached.
+/// - `last_modified` holds the raw `Last-Modified` header of the probed database, if sent.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MirrorProbe {
+    /// Probed database URL (placeholders substituted).
+    pub url: String,
+    /// Whether the mirror answered with a success status.
+    pub reachable: bool,
+    /// Time until the response headers were received.
+    pub latency: Option<Duration>,
+    /// `Last-Modified` header value of the probed database.
+    pub last_modified: Option<String>,
+}
+
+/// What: A repository section of `pacman.conf`.
+///
+/// Inputs:
+/// - Parsed from a `[repo]` section via `parse_pacman_conf()`.
+///
+/// Output:
+/// - Repository name with its servers in priority order.
+///
+/// Details:
+/// - `servers` combines `Server =` lines and servers from `Include`d mirrorlists, in file order.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PacmanRepo {
+    /// Repository name (e.g., `core`, `chaotic-aur`).
+    pub name: String,
+    /// Server URL templates in the order pacman tries them.
+    pub servers: Vec<Mirror>,
+    /// `SigLevel` override for this repository, if set.
+    pub sig_level: Option<String>,
+}
+
+/// What: Parsed pacman configuration.
+///
+/// Inputs:
+/// - Produced by `parse_pacman_conf()`.
+///
+/// Output:
+/// - Enabled repositories in priority order and `[options]` settings.
+///
+/// Details:
+/// - `options` holds every `[options]` entry as written; flags without a value (e.g., `Color`)
+///   map to an empty string.
+/// - `parallel_downloads` is the parsed `ParallelDownloads` value, if set and valid.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PacmanConf {
+    /// Enabled repositories, in `pacman.conf` order (highest priority first).
+    pub repos: Vec<PacmanRepo>,
+    /// Raw `[options]` entries.
+    pub options: BTreeMap<String, String>,
+    /// `ParallelDownloads` setting.
+    pub parallel_downloads: Option<u32>,
+}
+
+impl PacmanConf {
+    /// What: Look up an enabled repository by name.
+    ///
+    /// Inputs:
+    /// - `name`: Repository name.
+    ///
+    /// Output:
+    /// - `Some(&PacmanRepo)` if the repository is enabled.
+    #[must_use]
+    pub fn repo(&self, name: &str) -> Option<&PacmanRepo> {
+        self.repos.iter().find(|repo| repo.name == name)
+    }
+
+    /// What: Names of the enabled repositories.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - Iterator over repository names in priority order.
+    pub fn repo_names(&self) -> impl Iterator<Item = &str> {
+        self.repos.iter().map(|repo| repo.name.as_str())
+    }
 }
 
 #[cfg(test)]
@@ -164,6 +506,9 @@ mod tests {
                     arch: "x86_64".to_string(),
                     version: "1.0".to_string(),
                     description: "Desc A".to_string(),
+                    packager: String::new(),
+                    download_size: 0,
+                    installed_size: 0,
                 },
                 OfficialPackage {
                     name: "PackageB".to_string(),
@@ -171,8 +516,12 @@ mod tests {
                     arch: "any".to_string(),
                     version: "2.0".to_string(),
                     description: "Desc B".to_string(),
+                    packager: String::new(),
+                    download_size: 0,
+                    installed_size: 0,
                 },
             ],
+            fetched_at: None,
             name_to_idx: HashMap::new(),
         };
 
@@ -205,6 +554,9 @@ mod tests {
                     arch: "x86_64".to_string(),
                     version: "14.0.0".to_string(),
                     description: "Fast grep".to_string(),
+                    packager: String::new(),
+                    download_size: 0,
+                    installed_size: 0,
                 },
                 OfficialPackage {
                     name: "vim".to_string(),
@@ -212,8 +564,12 @@ mod tests {
                     arch: "x86_64".to_string(),
                     version: "9.0".to_string(),
                     description: "Text editor".to_string(),
+                    packager: String::new(),
+                    download_size: 0,
+                    installed_size: 0,
                 },
             ],
+            fetched_at: None,
             name_to_idx: HashMap::new(),
         };
 
@@ -253,7 +609,11 @@ mod tests {
                 arch: "x86_64".to_string(),
                 version: "1.0".to_string(),
                 description: "Test".to_string(),
+                packager: String::new(),
+                download_size: 0,
+                installed_size: 0,
             }],
+            fetched_at: None,
             name_to_idx: HashMap::new(),
         };
 
@@ -288,6 +648,9 @@ mod tests {
                     arch: "x86_64".to_string(),
                     version: "1.0".to_string(),
                     description: "Package 1".to_string(),
+                    packager: String::new(),
+                    download_size: 0,
+                    installed_size: 0,
                 },
                 OfficialPackage {
                     name: "package2".to_string(),
@@ -295,8 +658,12 @@ mod tests {
                     arch: "any".to_string(),
                     version: "2.0".to_string(),
                     description: "Package 2".to_string(),
+                    packager: String::new(),
+                    download_size: 0,
+                    installed_size: 0,
                 },
             ],
+            fetched_at: None,
             name_to_idx: HashMap::new(),
         };
 
@@ -342,6 +709,9 @@ mod tests {
             arch: "x86_64".to_string(),
             version: "1.0".to_string(),
             description: "Test package".to_string(),
+            packager: String::new(),
+            download_size: 0,
+            installed_size: 0,
         };
 
         // With fuzzy score
@@ -365,4 +735,51 @@ mod tests {
         assert_eq!(deserialized.fuzzy_score, Some(100));
         assert_eq!(deserialized.package.name, "test");
     }
+
+    #[test]
+    /// What: Verify `SonameSnapshot::diff` reports a bumped soname.
+    ///
+    /// Inputs:
+    /// - Two snapshots where `libfoo` bumps `libfoo.so=1-64` to `libfoo.so=2-64`.
+    ///
+    /// Output:
+    /// - A single change listing the removed and added soname.
+    ///
+    /// Details:
+    /// - Unchanged packages are not reported; snapshots survive a serde roundtrip.
+    fn soname_snapshot_diff_reports_bumped_soname() {
+        let set = |items: &[&str]| {
+            items
+                .iter()
+                .map(ToString::to_string)
+                .collect::<BTreeSet<_>>()
+        };
+        let older = SonameSnapshot {
+            packages: BTreeMap::from([
+                ("libfoo".to_string(), set(&["libfoo.so=1-64"])),
+                ("glibc".to_string(), set(&["libc.so=6-64", "libm.so=6-64"])),
+            ]),
+        };
+        let newer = SonameSnapshot {
+            packages: BTreeMap::from([
+                ("libfoo".to_string(), set(&["libfoo.so=2-64"])),
+                ("glibc".to_string(), set(&["libc.so=6-64", "libm.so=6-64"])),
+            ]),
+        };
+
+        let changes = older.diff(&newer);
+        assert_eq!(
+            changes,
+            vec![SonameChange {
+                package: "libfoo".to_string(),
+                removed: vec!["libfoo.so=1-64".to_string()],
+                added: vec!["libfoo.so=2-64".to_string()],
+            }]
+        );
+        assert!(older.diff(&older).is_empty());
+
+        let json = serde_json::to_string(&older).expect("serialize snapshot");
+        let restored: SonameSnapshot = serde_json::from_str(&json).expect("deserialize snapshot");
+        assert_eq!(restored, older);
+    }
 }
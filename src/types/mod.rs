@@ -11,16 +11,43 @@ pub mod dependency;
 #[cfg(feature = "index")]
 pub mod index;
 
-pub use package::{AurComment, AurPackage, AurPackageDetails};
+#[cfg(feature = "news")]
+pub mod news;
+
+pub use package::{
+    AurComment, AurPackage, AurPackageDetails, AurUpdate, AurUpdateReport, SearchPage,
+};
 
 #[cfg(feature = "aur")]
 pub use health::{HealthStatus, ServiceStatus};
 
 #[cfg(feature = "deps")]
 pub use dependency::{
-    Dependency, DependencySource, DependencySpec, DependencyStatus, PackageRef, PackageSource,
-    ReverseDependencySummary, SrcinfoData,
+    ArchDependencies, Dependency, DependencyArrays, DependencyKind, DependencySource,
+    DependencySpec, DependencyStatus, FieldChange, Inconsistency, InstallReason, PackageRef,
+    PackageSource, ParsedPkgbuild, PkgbuildDiff, ProvidedPackage, ResolveOverrides,
+    ReverseDependencySummary, ReverseOptions, ReverseProgress, SourceConfig, SplitPackage,
+    SrcinfoData, TransactionPlan, Upgrade,
 };
 
 #[cfg(feature = "index")]
-pub use index::{IndexQueryResult, OfficialIndex, OfficialPackage};
+pub use index::{
+    FuzzyOptions, IndexQueryResult, OfficialIndex, OfficialPackage, ResultSource, UnifiedResult,
+};
+
+#[cfg(feature = "news")]
+pub use news::{Advisory, AdvisorySeverity, AdvisoryStatus, NewsItem};
+
+/// What: Check if a provides/depends entry names a shared library.
+///
+/// Inputs:
+/// - `entry`: Provides or depends entry (e.g., `libfoo.so=1-64`).
+///
+/// Output:
+/// - `true` when the name part (before any version operator) ends with `.so`.
+#[cfg(any(feature = "deps", feature = "index"))]
+#[allow(clippy::case_sensitive_file_extension_comparisons)]
+pub(crate) fn is_soname(entry: &str) -> bool {
+    let name = entry.split(['=', '<', '>']).next().unwrap_or(entry);
+    name.ends_with(".so")
+}
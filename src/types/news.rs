@@ -0,0 +1,251 @@
+//! News feed types for Arch Linux announcements.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::deps::version_satisfies;
+
+/// What: A single Arch Linux news announcement.
+///
+/// Inputs: None (created by `news::fetch_news()` or `news::parse_news_feed()`)
+///
+/// Output: Struct containing the announcement's title, link, date, and text
+///
+/// Details:
+/// - `published` is parsed from the RSS `pubDate` (RFC 2822) and normalized to UTC
+/// - `summary` is the item description with HTML markup removed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NewsItem {
+    /// Announcement title.
+    pub title: String,
+    /// Link to the announcement on archlinux.org.
+    pub url: String,
+    /// Publication time in UTC.
+    pub published: DateTime<Utc>,
+    /// Plain-text announcement body.
+    pub summary: String,
+}
+
+/// What: Severity assigned to an advisory group by the Arch security tracker.
+///
+/// Inputs: None (enum variant)
+///
+/// Output: Enum naming the tracker severity
+///
+/// Details:
+/// - Values the tracker does not define deserialize as `Unknown`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AdvisorySeverity {
+    /// Low severity.
+    Low,
+    /// Medium severity.
+    Medium,
+    /// High severity.
+    High,
+    /// Critical severity.
+    Critical,
+    /// Severity not yet assessed.
+    #[serde(other)]
+    Unknown,
+}
+
+/// What: Status of an advisory group in the Arch security tracker.
+///
+/// Inputs: None (enum variant)
+///
+/// Output: Enum describing whether a fix has shipped
+///
+/// Details:
+/// - `Testing`: A fixed package is in the testing repositories
+/// - Values the tracker does not define deserialize as `Unknown`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AdvisoryStatus {
+    /// No fixed package is available yet.
+    Vulnerable,
+    /// A fixed package is in testing.
+    Testing,
+    /// A fixed package is available in the stable repositories.
+    Fixed,
+    /// The packaged version is not affected.
+    #[serde(rename = "Not affected")]
+    NotAffected,
+    /// Status not determined.
+    #[serde(other)]
+    Unknown,
+}
+
+/// What: An Arch security tracker advisory group (AVG).
+///
+/// Inputs: None (created by `news::security_advisories()` or `news::parse_advisories()`)
+///
+/// Output: Struct describing the affected packages and versions
+///
+/// Details:
+/// - `name` is the group identifier, e.g. `AVG-2843`
+/// - `fixed_version` is `None` while no fixed package exists
+/// - Versions are full pacman versions (`[epoch:]pkgver-pkgrel`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Advisory {
+    /// Advisory group identifier.
+    pub name: String,
+    /// Affected package names.
+    pub packages: Vec<String>,
+    /// Tracker severity.
+    pub severity: AdvisorySeverity,
+    /// Tracker status.
+    pub status: AdvisoryStatus,
+    /// Version the issue was reported against.
+    pub affected_version: String,
+    /// First version containing the fix, if any.
+    pub fixed_version: Option<String>,
+}
+
+impl Advisory {
+    /// What: Check whether a given package version is affected by this advisory.
+    ///
+    /// Inputs:
+    /// - `version`: Installed package version.
+    ///
+    /// Output:
+    /// - `true` if the version is vulnerable
+    ///
+    /// Details:
+    /// - With a fixed version: vulnerable if `version < fixed_version`
+    /// - Without one: vulnerable unless the status is `Fixed` or `NotAffected`
+    /// - Comparison uses `deps::version_satisfies` (pacman `vercmp` semantics, including epoch
+    ///   and pkgrel)
+    #[must_use]
+    pub fn affects_version(&self, version: &str) -> bool {
+        if self.status == AdvisoryStatus::NotAffected {
+            return false;
+        }
+        self.fixed_version.as_ref().map_or_else(
+            || self.status != AdvisoryStatus::Fixed,
+            |fixed| version_satisfies(version, &format!("<{fixed}")),
+        )
+    }
+}
+
+/// Phrases in a news title that indicate required user action.
+const TITLE_INTERVENTION_PHRASES: &[&str] = &["intervention", "requires", "action required"];
+
+/// Phrases in a news body that indicate required user action.
+const SUMMARY_INTERVENTION_PHRASES: &[&str] = &[
+    "manual intervention",
+    "before upgrading",
+    "prior to upgrading",
+    "action required",
+    "--overwrite",
+];
+
+impl NewsItem {
+    /// What: Heuristically check whether the announcement requires manual action.
+    ///
+    /// Inputs: None
+    ///
+    /// Output:
+    /// - `true` if the title or summary contains an intervention phrase
+    ///
+    /// Details:
+    /// - Case-insensitive
+    /// - Title phrases: "intervention", "requires", "action required"
+    ///   (Arch titles follow "... upgrade requires manual intervention")
+    /// - Summary phrases: "manual intervention", "before upgrading", "prior to upgrading",
+    ///   "action required", and `--overwrite` (file conflict workarounds)
+    /// - A heuristic: it may flag informational posts and miss unusual wording
+    #[must_use]
+    pub fn requires_intervention(&self) -> bool {
+        let title = self.title.to_lowercase();
+        let summary = self.summary.to_lowercase();
+        TITLE_INTERVENTION_PHRASES
+            .iter()
+            .any(|phrase| title.contains(phrase))
+            || SUMMARY_INTERVENTION_PHRASES
+                .iter()
+                .any(|phrase| summary.contains(phrase))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// What: Build a news item with the given title and summary.
+    fn item(title: &str, summary: &str) -> NewsItem {
+        NewsItem {
+            title: title.to_string(),
+            url: "https://archlinux.org/news/x/".to_string(),
+            published: DateTime::UNIX_EPOCH,
+            summary: summary.to_string(),
+        }
+    }
+
+    /// What: Build an advisory with the given status and fixed version.
+    fn advisory(status: AdvisoryStatus, fixed: Option<&str>) -> Advisory {
+        Advisory {
+            name: "AVG-1".to_string(),
+            packages: vec!["openssl".to_string()],
+            severity: AdvisorySeverity::High,
+            status,
+            affected_version: "3.0.0-1".to_string(),
+            fixed_version: fixed.map(str::to_string),
+        }
+    }
+
+    #[test]
+    /// What: Decide whether an installed version is vulnerable.
+    ///
+    /// Inputs:
+    /// - Advisories with and without a fixed version, in each relevant status.
+    ///
+    /// Output:
+    /// - Versions below the fix are affected; unfixed groups affect every version.
+    fn test_advisory_affects_version() {
+        let fixed = advisory(AdvisoryStatus::Fixed, Some("3.0.1-1"));
+        assert!(fixed.affects_version("3.0.0-1"));
+        assert!(!fixed.affects_version("3.0.1-1"));
+
+        assert!(advisory(AdvisoryStatus::Vulnerable, None).affects_version("3.0.0-1"));
+        assert!(!advisory(AdvisoryStatus::Fixed, None).affects_version("3.0.0-1"));
+        assert!(!advisory(AdvisoryStatus::NotAffected, None).affects_version("3.0.0-1"));
+    }
+
+    #[test]
+    /// What: Detect intervention wording in titles and summaries.
+    ///
+    /// Inputs:
+    /// - Real-world style titles and bodies, plus a purely informational post.
+    ///
+    /// Output:
+    /// - Intervention posts flagged, the informational post not.
+    fn test_requires_intervention() {
+        assert!(
+            item(
+                "linux-firmware >= 20250613 upgrade requires manual intervention",
+                ""
+            )
+            .requires_intervention()
+        );
+        assert!(
+            item(
+                "Cleaning up old repositories",
+                "Please update your pacman.conf BEFORE UPGRADING."
+            )
+            .requires_intervention()
+        );
+        assert!(
+            item(
+                "nvidia 470 file conflicts",
+                "Run pacman -Syu --overwrite /usr/lib/firmware/nvidia/* to fix."
+            )
+            .requires_intervention()
+        );
+        assert!(
+            !item(
+                "Arch Linux ISO now ships with Wayland",
+                "The new ISO includes a new installer."
+            )
+            .requires_intervention()
+        );
+    }
+}
@@ -1,5 +1,7 @@
 //! Package-related data types for AUR operations.
 
+#[cfg(feature = "aur")]
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Basic AUR package information from search results.
@@ -18,14 +20,171 @@ pub struct AurPackage {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub popularity: Option<f64>,
     /// Timestamp when package was flagged out-of-date (Unix timestamp in seconds).
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub out_of_date: Option<u64>,
-    /// Whether package is orphaned (no active maintainer).
+    #[serde(
+        default,
+        rename = "OutOfDate",
+        alias = "out_of_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub out_of_date: Option<i64>,
+    /// Whether package is orphaned (no active maintainer); mirrors `is_orphaned()`.
     #[serde(default)]
     pub orphaned: bool,
     /// Package maintainer username (None if orphaned).
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        rename = "Maintainer",
+        alias = "maintainer",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub maintainer: Option<String>,
+    /// Number of votes on AUR.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub num_votes: Option<u64>,
+    /// Last modification timestamp (Unix timestamp in seconds).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<i64>,
+    /// First submission timestamp (Unix timestamp in seconds).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_submitted: Option<i64>,
+}
+
+impl AurPackage {
+    /// What: Check whether the package has no maintainer.
+    ///
+    /// Inputs:
+    /// - `self`: Package to check.
+    ///
+    /// Output:
+    /// - `true` when `maintainer` is `None`.
+    ///
+    /// Details:
+    /// - Orphaned packages can be adopted by anyone and receive no updates; callers
+    ///   typically warn before installing them.
+    #[must_use]
+    pub const fn is_orphaned(&self) -> bool {
+        self.maintainer.is_none()
+    }
+
+    /// What: Popularity used as sort key.
+    ///
+    /// Inputs:
+    /// - `self`: Package to read.
+    ///
+    /// Output:
+    /// - AUR popularity score, `0.0` when unknown.
+    #[must_use]
+    pub fn popularity_key(&self) -> f64 {
+        self.popularity.unwrap_or(0.0)
+    }
+
+    /// What: Vote count used as sort key.
+    ///
+    /// Inputs:
+    /// - `self`: Package to read.
+    ///
+    /// Output:
+    /// - Number of votes, `0` when unknown.
+    #[must_use]
+    pub fn votes_key(&self) -> u64 {
+        self.num_votes.unwrap_or(0)
+    }
+
+    /// What: Last modification time used as sort key.
+    ///
+    /// Inputs:
+    /// - `self`: Package to read.
+    ///
+    /// Output:
+    /// - Unix timestamp in seconds, `0` when unknown.
+    #[must_use]
+    pub fn last_modified_key(&self) -> i64 {
+        self.last_modified.unwrap_or(0)
+    }
+
+    /// What: Out-of-date flag used as sort key.
+    ///
+    /// Inputs:
+    /// - `self`: Package to read.
+    ///
+    /// Output:
+    /// - `true` when the package is flagged out-of-date.
+    #[must_use]
+    pub const fn out_of_date_key(&self) -> bool {
+        self.out_of_date.is_some()
+    }
+
+    /// What: First submission time of the package.
+    ///
+    /// Inputs:
+    /// - `self`: Package to read.
+    ///
+    /// Output:
+    /// - `first_submitted` as a UTC date-time, `None` when unknown.
+    #[cfg(feature = "aur")]
+    #[must_use]
+    pub fn submitted_at(&self) -> Option<DateTime<Utc>> {
+        self.first_submitted
+            .and_then(|ts| DateTime::from_timestamp(ts, 0))
+    }
+
+    /// What: Last modification time of the package.
+    ///
+    /// Inputs:
+    /// - `self`: Package to read.
+    ///
+    /// Output:
+    /// - `last_modified` as a UTC date-time, `None` when unknown.
+    #[cfg(feature = "aur")]
+    #[must_use]
+    pub fn modified_at(&self) -> Option<DateTime<Utc>> {
+        self.last_modified
+            .and_then(|ts| DateTime::from_timestamp(ts, 0))
+    }
+}
+
+/// A page of AUR search results.
+///
+/// Returned by paginated search; `total` is the size of the full result set so callers
+/// can compute the number of pages.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SearchPage {
+    /// Packages on this page.
+    pub items: Vec<AurPackage>,
+    /// Total number of results across all pages.
+    pub total: usize,
+    /// Offset of the first item on this page within the full result set.
+    pub offset: usize,
+}
+
+impl SearchPage {
+    /// What: Slice a page out of a full result set.
+    ///
+    /// Inputs:
+    /// - `results`: Full, ordered result set.
+    /// - `offset`: Index of the first item to include.
+    /// - `limit`: Maximum number of items (`0` means all remaining items).
+    ///
+    /// Output:
+    /// - `SearchPage` with the requested items and the total result count.
+    ///
+    /// Details:
+    /// - An `offset` at or beyond the end yields an empty page, not an error.
+    #[must_use]
+    pub fn from_results(results: &[AurPackage], offset: usize, limit: usize) -> Self {
+        let total = results.len();
+        let start = offset.min(total);
+        let end = if limit == 0 {
+            total
+        } else {
+            start.saturating_add(limit).min(total)
+        };
+        Self {
+            items: results[start..end].to_vec(),
+            total,
+            offset,
+        }
+    }
 }
 
 /// Full AUR package details from the info endpoint.
@@ -36,6 +195,9 @@ pub struct AurPackage {
 pub struct AurPackageDetails {
     /// Package name.
     pub name: String,
+    /// Package base (`pkgbase`) the package is built from; names the AUR git repository.
+    #[serde(default)]
+    pub package_base: String,
     /// Full version string.
     pub version: String,
     /// Long description.
@@ -43,21 +205,32 @@ pub struct AurPackageDetails {
     /// Upstream project URL (may be empty if unknown).
     pub url: String,
     /// SPDX or human-readable license identifiers.
+    #[serde(default)]
     pub licenses: Vec<String>,
     /// Group memberships.
+    #[serde(default)]
     pub groups: Vec<String>,
     /// Virtual provisions supplied by this package.
+    #[serde(default)]
     pub provides: Vec<String>,
     /// Required dependencies.
+    #[serde(default)]
     pub depends: Vec<String>,
     /// Build dependencies.
+    #[serde(default)]
     pub make_depends: Vec<String>,
     /// Optional dependencies with annotations.
+    #[serde(default)]
     pub opt_depends: Vec<String>,
     /// Conflicting packages.
+    #[serde(default)]
     pub conflicts: Vec<String>,
     /// Packages that this package replaces.
+    #[serde(default)]
     pub replaces: Vec<String>,
+    /// Search keywords assigned by the maintainer.
+    #[serde(default)]
+    pub keywords: Vec<String>,
     /// Package maintainer username (None if orphaned).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub maintainer: Option<String>,
@@ -81,6 +254,75 @@ pub struct AurPackageDetails {
     pub orphaned: bool,
 }
 
+impl AurPackageDetails {
+    /// What: Check whether the package is tagged with a keyword.
+    ///
+    /// Inputs:
+    /// - `kw`: Keyword to look for.
+    ///
+    /// Output:
+    /// - `true` if `keywords` contains `kw`, compared ASCII case-insensitively.
+    #[must_use]
+    pub fn has_keyword(&self, kw: &str) -> bool {
+        self.keywords
+            .iter()
+            .any(|keyword| keyword.eq_ignore_ascii_case(kw))
+    }
+
+    /// What: First submission time of the package.
+    ///
+    /// Inputs:
+    /// - `self`: Package to read.
+    ///
+    /// Output:
+    /// - `first_submitted` as a UTC date-time, `None` when unknown.
+    #[cfg(feature = "aur")]
+    #[must_use]
+    pub fn submitted_at(&self) -> Option<DateTime<Utc>> {
+        self.first_submitted
+            .and_then(|ts| DateTime::from_timestamp(ts, 0))
+    }
+
+    /// What: Last modification time of the package.
+    ///
+    /// Inputs:
+    /// - `self`: Package to read.
+    ///
+    /// Output:
+    /// - `last_modified` as a UTC date-time, `None` when unknown.
+    #[cfg(feature = "aur")]
+    #[must_use]
+    pub fn modified_at(&self) -> Option<DateTime<Utc>> {
+        self.last_modified
+            .and_then(|ts| DateTime::from_timestamp(ts, 0))
+    }
+}
+
+/// An installed AUR package with a newer version available.
+///
+/// Returned by `Aur::updates()` inside an [`AurUpdateReport`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AurUpdate {
+    /// Package name.
+    pub name: String,
+    /// Installed version.
+    pub current: String,
+    /// Newer version available in the AUR.
+    pub available: String,
+}
+
+/// Result of checking installed packages for AUR updates.
+///
+/// `updates` lists packages with a newer AUR version; `missing` lists installed packages the
+/// AUR no longer knows (deleted or renamed).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AurUpdateReport {
+    /// Packages with a newer version in the AUR, in input order.
+    pub updates: Vec<AurUpdate>,
+    /// Installed package names without an AUR entry, in input order.
+    pub missing: Vec<String>,
+}
+
 /// AUR comment from a package page.
 ///
 /// Contains author, date, and content of a comment, with optional timestamp
@@ -105,4 +347,13 @@ pub struct AurComment {
     /// Whether this comment is pinned (shown at the top).
     #[serde(default)]
     pub pinned: bool,
+    /// Numeric comment ID (e.g. `931234` for `comment-931234`), when available.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub numeric_id: Option<u64>,
+    /// Date of the last edit in local time, if the comment was edited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edited: Option<String>,
+    /// Whether the comment was deleted (only its placeholder is shown).
+    #[serde(default)]
+    pub deleted: bool,
 }
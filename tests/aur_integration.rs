@@ -0,0 +1,225 @@
+//! Integration tests for AUR operations against a local mock HTTP server.
+//!
+//! These tests point `ArchClient` at a `wiremock` server so the real URL building, request
+//! headers, rate limiting, retry handling and response parsing run end to end, which the
+//! trait-level `MockAurApi` bypasses. No network access is required.
+
+#![cfg(feature = "aur")]
+
+use arch_toolkit::error::Result;
+use arch_toolkit::{ArchClient, ClientEvent, RetryPolicy};
+use std::sync::mpsc;
+use wiremock::matchers::{header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// User agent sent by the test clients, checked by the mock server.
+const USER_AGENT: &str = "arch-toolkit-tests/1.0";
+
+/// What: Build a client talking to the mock server.
+///
+/// Inputs:
+/// - `server`: Mock server standing in for the AUR.
+///
+/// Output:
+/// - Client with a test user agent and a fast retry policy.
+fn client_for(server: &MockServer) -> Result<ArchClient> {
+    ArchClient::builder()
+        .aur_base_url(server.uri())
+        .user_agent(USER_AGENT)
+        .retry_policy(fast_retry_policy())
+        .build()
+}
+
+/// What: Retry policy with millisecond delays so retries do not slow the tests down.
+fn fast_retry_policy() -> RetryPolicy {
+    RetryPolicy {
+        max_retries: 2,
+        initial_delay_ms: 1,
+        max_delay_ms: 10,
+        jitter_max_ms: 0,
+        circuit_breaker_threshold: 0,
+        ..RetryPolicy::default()
+    }
+}
+
+/// Search hits `/rpc/v5/search` with the query and parses the results.
+#[tokio::test]
+async fn test_search_against_mock_server() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rpc/v5/search"))
+        .and(query_param("by", "name-desc"))
+        .and(query_param("arg", "yay"))
+        .and(header("user-agent", USER_AGENT))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "resultcount": 2,
+            "results": [
+                { "Name": "yay", "Version": "12.3.4", "Description": "AUR helper",
+                  "Popularity": 12.5, "OutOfDate": null, "Maintainer": "jguer" },
+                { "Name": "yay-bin", "Version": "12.3.4-1", "Description": null }
+            ],
+            "type": "search",
+            "version": 5
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let packages = client_for(&server)?.aur().search("yay").await?;
+
+    assert_eq!(packages.len(), 2);
+    assert_eq!(packages[0].name, "yay");
+    assert_eq!(packages[0].version, "12.3.4");
+    assert_eq!(packages[0].maintainer.as_deref(), Some("jguer"));
+    assert_eq!(packages[1].name, "yay-bin");
+    assert_eq!(packages[1].description, "");
+    Ok(())
+}
+
+/// Info requests all names in one `arg[]` query and parses the details.
+#[tokio::test]
+async fn test_info_against_mock_server() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rpc/v5/info"))
+        .and(query_param("arg[]", "paru"))
+        .and(header("user-agent", USER_AGENT))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "resultcount": 1,
+            "results": [{
+                "Name": "paru",
+                "PackageBase": "paru",
+                "Version": "2.0.4-1",
+                "Description": "Feature packed AUR helper",
+                "URL": "https://github.com/morganamilo/paru",
+                "License": ["GPL-3.0-or-later"],
+                "Depends": ["git", "pacman"],
+                "MakeDepends": ["cargo"],
+                "FirstSubmitted": 1_595_000_000,
+                "OutOfDate": null
+            }],
+            "type": "multiinfo",
+            "version": 5
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let details = client_for(&server)?.aur().info(&["paru"]).await?;
+
+    assert_eq!(details.len(), 1);
+    assert_eq!(details[0].name, "paru");
+    assert_eq!(details[0].version, "2.0.4-1");
+    assert_eq!(details[0].depends, ["git", "pacman"]);
+    assert_eq!(details[0].make_depends, ["cargo"]);
+    assert_eq!(details[0].licenses, ["GPL-3.0-or-later"]);
+    assert_eq!(details[0].first_submitted, Some(1_595_000_000));
+    assert_eq!(details[0].out_of_date, None);
+    Ok(())
+}
+
+/// PKGBUILD is fetched from the cgit plain endpoint for the package.
+#[tokio::test]
+async fn test_pkgbuild_against_mock_server() -> Result<()> {
+    let pkgbuild = "pkgname=yay\npkgver=12.3.4\npkgrel=1\ndepends=('pacman' 'git')\n";
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/cgit/aur.git/plain/PKGBUILD"))
+        .and(query_param("h", "yay"))
+        .and(header("user-agent", USER_AGENT))
+        .respond_with(ResponseTemplate::new(200).set_body_string(pkgbuild))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let text = client_for(&server)?.aur().pkgbuild("yay").await?;
+
+    assert_eq!(text, pkgbuild);
+    Ok(())
+}
+
+/// Comments are scraped from the package page, pinned comments first.
+#[tokio::test]
+async fn test_comments_against_mock_server() -> Result<()> {
+    let html = "<html><body>\
+        <h3>Pinned Comments</h3>\
+        <h4 class=\"comment-header\" id=\"comment-10\">maintainer commented on \
+        <a class=\"date\" href=\"#comment-10\">2024-01-01 00:00 (UTC)</a></h4>\
+        <div class=\"article-content\" id=\"comment-10-content\"><p>Read the wiki</p></div>\
+        <h3>Latest Comments</h3>\
+        <h4 class=\"comment-header\" id=\"comment-12\">alice commented on \
+        <a class=\"date\" href=\"#comment-12\">2024-03-01 12:00 (UTC)</a></h4>\
+        <div class=\"article-content\" id=\"comment-12-content\"><p>Works with <strong>12.3</strong></p></div>\
+        <h4 class=\"comment-header\" id=\"comment-11\">bob commented on \
+        <a class=\"date\" href=\"#comment-11\">2024-02-01 12:00 (UTC)</a></h4>\
+        <div class=\"article-content\" id=\"comment-11-content\"><p>Build fails</p></div>\
+        </body></html>";
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/packages/yay"))
+        .and(header("user-agent", USER_AGENT))
+        .respond_with(ResponseTemplate::new(200).set_body_string(html))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let comments = client_for(&server)?.aur().comments("yay").await?;
+
+    let authors: Vec<&str> = comments.iter().map(|c| c.author.as_str()).collect();
+    assert_eq!(authors, ["maintainer", "alice", "bob"]);
+    assert!(comments[0].pinned);
+    assert_eq!(comments[1].numeric_id, Some(12));
+    assert_eq!(comments[1].content, "Works with **12.3**");
+    Ok(())
+}
+
+/// A 429 answer is retried with backoff and the following 200 succeeds.
+#[tokio::test]
+async fn test_rate_limited_search_is_retried() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rpc/v5/search"))
+        .respond_with(ResponseTemplate::new(429))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rpc/v5/search"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "results": [{ "Name": "yay", "Version": "12.3.4" }]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let (tx, rx) = mpsc::channel();
+    let client = ArchClient::builder()
+        .aur_base_url(server.uri())
+        .retry_policy(fast_retry_policy())
+        .event_sender(tx)
+        .build()?;
+
+    let packages = client.aur().search("yay").await?;
+    assert_eq!(packages.len(), 1);
+
+    let events: Vec<ClientEvent> = rx.try_iter().collect();
+    assert!(
+        events
+            .iter()
+            .any(|event| matches!(event, ClientEvent::BackoffIncreased { .. })),
+        "backoff was not increased: {events:?}"
+    );
+    assert!(
+        events.iter().any(|event| matches!(
+            event,
+            ClientEvent::Retry { op, attempt: 1, .. } if op == "search"
+        )),
+        "search was not retried: {events:?}"
+    );
+    assert!(events.iter().any(|event| matches!(
+        event,
+        ClientEvent::RequestSucceeded { op } if op == "search"
+    )));
+    Ok(())
+}
@@ -86,14 +86,20 @@ fn test_dependency_resolver_multiple_packages() -> Result<()> {
 #[test]
 fn test_dependency_resolver_with_config() -> Result<()> {
     use arch_toolkit::ResolverConfig;
+    use arch_toolkit::deps::SourceConfig;
 
     let config = ResolverConfig {
         include_optdepends: true,
         include_makedepends: false,
         include_checkdepends: false,
         max_depth: 0,
+        max_packages: 1000,
         pkgbuild_cache: None,
+        pkgbuild_hash: None,
+        aur_commit: None,
         check_aur: false,
+        offline: false,
+        source_config: SourceConfig::default(),
     };
 
     let resolver = DependencyResolver::with_config(config);
@@ -212,6 +218,80 @@ fn test_get_upgradable_packages_integration() -> Result<()> {
     Ok(())
 }
 
+/// Test that offline resolution never invokes AUR helpers.
+///
+/// Replaces `PATH` with a directory of stub `paru`, `yay`, and `pacman` scripts that record
+/// their invocations, so it must not run concurrently with other tests.
+#[cfg(unix)]
+#[test]
+#[ignore = "Replaces PATH for the whole process; run alone with --ignored"]
+fn test_dependency_resolver_offline_skips_helpers() -> Result<()> {
+    use arch_toolkit::{DependencyStatus, ResolverConfig};
+    use std::os::unix::fs::PermissionsExt;
+
+    let stub_dir = tempfile::tempdir().expect("temp dir");
+    let log = stub_dir.path().join("calls.log");
+    for helper in ["paru", "yay", "pacman"] {
+        let script = stub_dir.path().join(helper);
+        std::fs::write(
+            &script,
+            format!(
+                "#!/bin/sh\necho \"{helper} $*\" >> '{}'\nexit 1\n",
+                log.display()
+            ),
+        )
+        .expect("write stub");
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))
+            .expect("make stub executable");
+    }
+    let original_path = std::env::var_os("PATH");
+    // SAFETY: the test is ignored by default and documented to run on its own.
+    unsafe {
+        std::env::set_var("PATH", stub_dir.path());
+    }
+
+    let resolver = DependencyResolver::with_config(ResolverConfig {
+        offline: true,
+        pkgbuild_cache: Some(Box::new(|name: &str| {
+            (name == "offline-app").then(|| {
+                "pkgname=offline-app\ndepends=('offline-dep')\nconflicts=('offline-app-git')\n"
+                    .to_string()
+            })
+        })),
+        ..ResolverConfig::default()
+    });
+    let result = resolver.resolve(&[PackageRef {
+        name: "offline-app".to_string(),
+        version: "1.0-1".to_string(),
+        source: PackageSource::Aur,
+    }]);
+
+    // SAFETY: restores the environment modified above.
+    unsafe {
+        match original_path {
+            Some(path) => std::env::set_var("PATH", path),
+            None => std::env::remove_var("PATH"),
+        }
+    }
+    let result = result?;
+
+    let calls = std::fs::read_to_string(&log).unwrap_or_default();
+    assert!(
+        !calls
+            .lines()
+            .any(|call| call.starts_with("paru") || call.starts_with("yay")),
+        "AUR helpers invoked offline: {calls}"
+    );
+    let names: Vec<&str> = result
+        .dependencies
+        .iter()
+        .map(|d| d.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["offline-dep"]);
+    assert_eq!(result.dependencies[0].status, DependencyStatus::ToInstall);
+    Ok(())
+}
+
 /// Test graceful degradation when pacman is unavailable.
 #[test]
 fn test_graceful_degradation_no_pacman() {
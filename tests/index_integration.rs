@@ -2,6 +2,7 @@
 
 #[cfg(feature = "index")]
 mod tests {
+    use arch_toolkit::error::ArchToolkitError;
     use arch_toolkit::index::{
         InstalledPackagesMode, get_installed_packages, is_explicit, is_installed,
         refresh_explicit_cache, refresh_explicit_cache_async, refresh_installed_cache,
@@ -314,19 +315,20 @@ exit 1
     }
 
     #[test]
-    /// What: Verify `get_installed_packages` returns `HashSet`.
+    /// What: Verify `get_installed_packages` returns `HashSet` or reports a missing pacman.
     ///
     /// Inputs:
     /// - Direct call to `get_installed_packages`.
     ///
     /// Output:
-    /// - Returns Ok(HashSet<String>) (may be empty if pacman unavailable).
+    /// - Returns Ok(HashSet<String>), or `PacmanUnavailable` if pacman is not installed.
     ///
     /// Details:
-    /// - Tests that function returns correct type and handles errors gracefully.
+    /// - Tests that function returns correct type without depending on the system state.
     fn get_installed_packages_returns_hashset() {
-        let result = get_installed_packages();
-        assert!(result.is_ok());
-        // Result may be empty if pacman unavailable, which is graceful degradation
+        match get_installed_packages() {
+            Ok(_) | Err(ArchToolkitError::PacmanUnavailable { .. }) => {}
+            Err(e) => panic!("unexpected error: {e}"),
+        }
     }
 }